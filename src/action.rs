@@ -0,0 +1,209 @@
+//! What a key press asks Browse mode to do
+//!
+//! Keys are mapped to an `Action` here and the action is carried out by
+//! `handlers::apply_browse_action`, so which key does what is decided in one place
+//! and the behavior can be driven without going through the keyboard.
+
+use crate::keymap::{Key, Keymap, Lookup};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    OpenMenu,
+    /// Move the selection up or down a row, which is a whole row of tiles in the grid
+    MoveUp,
+    MoveDown,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    MoveTop,
+    MoveBottom,
+    /// Move between tiles in the grid
+    MoveLeft,
+    MoveRight,
+    /// Move the split between the list and the details, wider when true
+    ResizeSplit(bool),
+    ToggleGrid,
+    /// Switch to the tab in this slot (1-based)
+    SwitchTab(usize),
+    PinTab,
+    CloseTab,
+    /// Switch the pinned filter in this slot (1-based) on or off
+    TogglePinnedFilter(usize),
+    /// Mark or unmark the selected episode for batch actions
+    ToggleMark,
+    ShowHelp,
+    ShowNotifications,
+    /// Play the selected episode, or open the selected series or season
+    PlaySelected,
+    /// Copy the selected episode's absolute file path or its title to the clipboard
+    CopyPath,
+    CopyTitle,
+    /// Clear the marks, leave the view or clear the filter, and quit from the library
+    Back,
+    StartFilter,
+    AcceptFilter,
+    CancelFilter,
+    FilterCursorLeft,
+    FilterCursorRight,
+    FilterWordLeft,
+    FilterWordRight,
+    FilterHome,
+    FilterEnd,
+    FilterBackspace,
+    FilterDelete,
+    FilterInsert(char),
+    /// Put an older or newer filter from the history in the filter line
+    FilterHistoryPrevious,
+    FilterHistoryNext,
+    /// Move to the next or previous entry matching the kept filter, wrapping around the ends
+    NextMatch,
+    PreviousMatch,
+    /// Type the name of an action, menu action or entry number after a `:`
+    OpenCommandLine,
+    CommandInsert(char),
+    CommandBackspace,
+    RunCommand,
+    CancelCommand,
+    /// Start recording the keys pressed as a macro, or stop and keep them
+    RecordMacro,
+    /// Handle the keys of the recorded macro again
+    ReplayMacro,
+}
+
+impl Action {
+    /// Whether the action only moves the selection through the list, leaving its entries as they are
+    pub fn only_moves(&self) -> bool {
+        matches!(
+            self,
+            Action::MoveUp
+                | Action::MoveDown
+                | Action::PageUp
+                | Action::PageDown
+                | Action::HalfPageUp
+                | Action::HalfPageDown
+                | Action::MoveTop
+                | Action::MoveBottom
+                | Action::NextMatch
+                | Action::PreviousMatch
+        )
+    }
+}
+
+/// A number typed in Browse mode before the key it is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Count {
+    /// How many times the next move goes, as in 15j
+    Repeat(usize),
+    /// Entry to select with Enter after #, None until a digit is typed
+    Jump(Option<usize>),
+}
+
+impl Count {
+    /// The count with another digit typed after it
+    pub fn push_digit(self, digit: usize) -> Count {
+        let push = |number: usize| number.saturating_mul(10).saturating_add(digit);
+        match self {
+            Count::Repeat(number) => Count::Repeat(push(number)),
+            Count::Jump(number) => Count::Jump(Some(push(number.unwrap_or(0)))),
+        }
+    }
+
+    /// The count without its last digit, None once nothing is left of it
+    pub fn pop_digit(self) -> Option<Count> {
+        match self {
+            Count::Repeat(number) => Some(number / 10).filter(|n| *n > 0).map(Count::Repeat),
+            Count::Jump(None) => None,
+            Count::Jump(Some(number)) => Some(Count::Jump(Some(number / 10).filter(|n| *n > 0))),
+        }
+    }
+
+    /// The count as typed, such as "15" or "#12"
+    pub fn label(&self) -> String {
+        match self {
+            Count::Repeat(number) => number.to_string(),
+            Count::Jump(None) => "#".to_string(),
+            Count::Jump(Some(number)) => format!("#{}", number),
+        }
+    }
+}
+
+/// Action for a single key pressed in Browse mode with `keymap`, None when the key does nothing there
+///
+/// While the filter is being typed only the filter keys are mapped. Otherwise the keys come
+/// from the keymap, except for 1-3 and Alt+1-9, which are kept for pinned filters and tabs.
+pub fn browse_action(keymap: &Keymap, code: KeyCode, modifiers: KeyModifiers, filter_mode: bool) -> Option<Action> {
+    let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+    let alt = modifiers.contains(KeyModifiers::ALT);
+    if filter_mode {
+        return match code {
+            KeyCode::Enter => Some(Action::AcceptFilter),
+            KeyCode::Esc => Some(Action::CancelFilter),
+            KeyCode::Left if ctrl => Some(Action::FilterWordLeft),
+            KeyCode::Right if ctrl => Some(Action::FilterWordRight),
+            KeyCode::Left => Some(Action::FilterCursorLeft),
+            KeyCode::Right => Some(Action::FilterCursorRight),
+            KeyCode::Home => Some(Action::FilterHome),
+            KeyCode::End => Some(Action::FilterEnd),
+            KeyCode::Backspace => Some(Action::FilterBackspace),
+            KeyCode::Delete => Some(Action::FilterDelete),
+            KeyCode::Up => Some(Action::FilterHistoryPrevious),
+            KeyCode::Down => Some(Action::FilterHistoryNext),
+            KeyCode::Char(c) => Some(Action::FilterInsert(c)),
+            _ => None,
+        };
+    }
+    if let Some(action) = keymap.browse_action(code, modifiers) {
+        return Some(action);
+    }
+    match code {
+        KeyCode::Char(c @ '1'..='9') if alt => c.to_digit(10).map(|slot| Action::SwitchTab(slot as usize)),
+        KeyCode::Char(c @ '1'..='3') => c.to_digit(10).map(|slot| Action::TogglePinnedFilter(slot as usize)),
+        _ => None,
+    }
+}
+
+/// Action for a key pressed in Browse mode with `keymap`, keeping the start of a key sequence
+/// such as gg in `pending` until the key that finishes it
+///
+/// A key that doesn't continue the sequence drops it and is looked up by itself.
+pub fn browse_key_action(
+    keymap: &Keymap,
+    pending: &mut Vec<Key>,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+    filter_mode: bool,
+) -> Option<Action> {
+    if filter_mode {
+        pending.clear();
+        return browse_action(keymap, code, modifiers, filter_mode);
+    }
+    let key = Key::pressed(code, modifiers);
+    let had_pending = !pending.is_empty();
+    pending.push(key);
+    match keymap.lookup(pending) {
+        Lookup::Action(action) => {
+            pending.clear();
+            return Some(action);
+        }
+        Lookup::Pending => return None,
+        Lookup::Unbound => pending.clear(),
+    }
+    if had_pending && keymap.lookup(&[key]) == Lookup::Pending {
+        pending.push(key);
+        return None;
+    }
+    browse_action(keymap, code, modifiers, filter_mode)
+}
+
+/// Action for a key pressed while a command is typed after `:`
+pub fn command_line_action(code: KeyCode) -> Option<Action> {
+    match code {
+        KeyCode::Enter => Some(Action::RunCommand),
+        KeyCode::Esc => Some(Action::CancelCommand),
+        KeyCode::Backspace => Some(Action::CommandBackspace),
+        KeyCode::Char(c) => Some(Action::CommandInsert(c)),
+        _ => None,
+    }
+}
@@ -0,0 +1,290 @@
+//! State of the running application, shared by the screen and the key handlers
+//!
+//! `AppState` owns everything the main loop changes from one key press to the next.
+//! Settings and services the handlers only read are passed next to it in an
+//! `AppContext`, and the screen gets what it needs to draw in a `DrawContext`.
+
+use crate::action::Count;
+use crate::archive::Archive;
+use crate::components::alt_titles_editor::AltTitlesDraft;
+use crate::components::ThemePicker;
+use crate::config::Config;
+use crate::database::Database;
+use crate::digest::Digest;
+use crate::dto::{EpisodeDetail, Series};
+use crate::duplicates::DuplicateGroup;
+use crate::episode_field::EpisodeField;
+use crate::filter_history::FilterHistory;
+use crate::input::ClickTracker;
+use crate::keymap::Key;
+use crate::macros::MacroRecorder;
+use crate::menu::{self, MenuContext, MenuItem, PendingConfirmation};
+use crate::modal::ModalStack;
+use crate::notifications::Notifications;
+use crate::path_resolver::PathResolver;
+use crate::query::{FilterCache, PinnedFilters};
+use crate::relocate::RelocationCheck;
+use crate::rename::RenamePreview;
+use crate::scan::{ScanProgress, ScanReport};
+use crate::tabs::Tabs;
+use crate::theme::Theme;
+use crate::torrent_search::TorrentResult;
+use crate::tutorial::Tutorial;
+use crate::util::{AppEvent, Entry, LastAction, Mode, SortMode, ViewContext};
+use crate::version::EpisodeVersion;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+
+/// Everything the main loop keeps between key presses
+pub struct AppState {
+    /// Entries of the current view, before filtering
+    pub entries: Vec<Entry>,
+    /// Entries shown in the list, filtered and sorted
+    pub filtered_entries: Vec<Entry>,
+    /// Set by keys that only move the selection, so the next redraw keeps `filtered_entries`
+    /// instead of filtering and sorting the entries again
+    pub list_unchanged: bool,
+    pub current_item: usize,
+    pub first_entry: usize,
+    /// Selection the menu was opened on
+    pub remembered_item: usize,
+    /// Episode selected in Browse mode, None when a series or season is selected
+    pub selected_entry_id: Option<usize>,
+    /// Episodes picked for batch actions
+    pub marked_episodes: HashSet<usize>,
+    pub mode: Mode,
+    /// Screens under the one in `mode`, put back as the modals over them close
+    pub modals: ModalStack,
+    pub redraw: bool,
+
+    pub search: String,
+    pub filter_mode: bool,
+    /// Filters kept with Enter, recalled with Up/Down while typing the filter
+    pub filter_history: FilterHistory,
+    pub sort_mode: SortMode,
+    pub pinned_filters: PinnedFilters,
+    /// Entries the filter matched last, narrowed as more of the filter is typed
+    pub filter_cache: FilterCache,
+    pub view_context: ViewContext,
+    pub tabs: Tabs,
+    /// Browse entries as a grid of tiles instead of a list
+    pub grid_view: bool,
+    /// Start of a key sequence such as gg, waiting for its next key
+    pub pending_keys: Vec<Key>,
+    /// Count or entry number typed before the key it is for
+    pub count: Option<Count>,
+    /// Command typed after `:`, None when the command line is closed
+    pub command_line: Option<String>,
+    /// Keys recorded with q and replayed with @
+    pub macros: MacroRecorder,
+
+    pub edit_details: EpisodeDetail,
+    /// Details as they were when Edit mode was entered, None outside it
+    pub original_edit_details: Option<EpisodeDetail>,
+    pub edit_field: EpisodeField,
+    pub edit_cursor_pos: usize,
+    pub dirty_fields: HashSet<EpisodeField>,
+    pub season_number: Option<usize>,
+    pub last_action: Option<LastAction>,
+    pub alt_titles_draft: Option<AltTitlesDraft>,
+    /// File of the episode being edited and the name the rename template gives it, while the preview is open
+    pub rename_preview: Option<RenamePreview>,
+    /// Path typed in Entry mode for a manual scan
+    pub entry_path: String,
+    /// Folders matching `entry_path`, listed after a Tab that had more than one to complete to
+    pub entry_completions: Vec<String>,
+    /// Entry mode was opened from the menu to scan one folder of the library, not to set one up
+    pub scan_folder_entry: bool,
+
+    pub series: Vec<Series>,
+    pub series_selection: Option<usize>,
+    pub new_series: String,
+    pub first_series: usize,
+
+    pub menu_selection: usize,
+    /// Destructive action waiting for its confirmation dialog to be answered
+    pub pending_confirmation: Option<PendingConfirmation>,
+    /// Theme files listed by the theme picker while it is open
+    pub theme_picker: Option<ThemePicker>,
+    pub tutorial: Option<Tutorial>,
+    /// Scroll position of the keybinding reference
+    pub help_scroll: usize,
+    /// Scroll position of the recent messages list
+    pub notification_scroll: usize,
+
+    pub notifications: Notifications,
+    pub playing_file: Option<String>,
+    /// Progress of a background rescan, and the spinner frame shown with it
+    pub scan_progress: Option<ScanProgress>,
+    pub scan_frame: usize,
+    /// The running rescan was started by `rescan_interval_minutes`, so its report only notifies
+    pub automatic_rescan: bool,
+    /// What a running Trakt or Jellyfin sync is doing
+    pub sync_status: Option<String>,
+
+    pub search_query: String,
+    pub torrent_results: Vec<TorrentResult>,
+    pub selected_torrent_result: usize,
+    pub weekly_digest: Digest,
+    pub selected_digest_item: usize,
+    /// What the last rescan imported, skipped and found missing
+    pub scan_report: ScanReport,
+    pub scan_report_scroll: usize,
+    /// Archives with videos found by the last rescan
+    pub pending_archives: Vec<Archive>,
+    pub selected_archive: usize,
+    pub versions: Vec<EpisodeVersion>,
+    pub selected_version: usize,
+    /// New root folder typed when relocating the library, and what checking it found
+    pub relocate_path: String,
+    pub relocation_check: Option<RelocationCheck>,
+    /// URL typed in "Add from URL"
+    pub download_url: String,
+    /// Copies found by the duplicate finder, and the copy selected in the review
+    pub duplicate_groups: Vec<DuplicateGroup>,
+    pub selected_duplicate: usize,
+    /// Problems found in config.yaml and the theme when the app started
+    pub config_problems: Vec<String>,
+    pub config_report_scroll: usize,
+
+    /// Mouse clicks are mapped onto the entry list drawn from this row
+    pub list_top: usize,
+    pub clicks: ClickTracker,
+}
+
+impl AppState {
+    /// Start in Browse mode on the first of `entries`, with nothing filtered or being edited
+    pub fn new(entries: Vec<Entry>) -> Self {
+        Self {
+            filtered_entries: entries.clone(),
+            list_unchanged: false,
+            entries,
+            current_item: 0,
+            first_entry: 0,
+            remembered_item: 0,
+            selected_entry_id: None,
+            marked_episodes: HashSet::new(),
+            mode: Mode::Browse,
+            modals: ModalStack::default(),
+            redraw: true,
+            search: String::new(),
+            filter_mode: false,
+            filter_history: FilterHistory::default(),
+            sort_mode: SortMode::Default,
+            pinned_filters: PinnedFilters::default(),
+            filter_cache: FilterCache::default(),
+            view_context: ViewContext::TopLevel,
+            tabs: Tabs::default(),
+            grid_view: false,
+            pending_keys: Vec::new(),
+            count: None,
+            command_line: None,
+            macros: MacroRecorder::default(),
+            edit_details: EpisodeDetail::default(),
+            original_edit_details: None,
+            edit_field: EpisodeField::Title,
+            edit_cursor_pos: 0,
+            dirty_fields: HashSet::new(),
+            season_number: None,
+            last_action: None,
+            alt_titles_draft: None,
+            rename_preview: None,
+            entry_path: String::new(),
+            entry_completions: Vec::new(),
+            scan_folder_entry: false,
+            series: Vec::new(),
+            series_selection: None,
+            new_series: String::new(),
+            first_series: 0,
+            menu_selection: 0,
+            pending_confirmation: None,
+            theme_picker: None,
+            tutorial: None,
+            help_scroll: 0,
+            notification_scroll: 0,
+            notifications: Notifications::default(),
+            playing_file: None,
+            scan_progress: None,
+            scan_frame: 0,
+            automatic_rescan: false,
+            sync_status: None,
+            search_query: String::new(),
+            torrent_results: Vec::new(),
+            selected_torrent_result: 0,
+            weekly_digest: Digest::default(),
+            selected_digest_item: 0,
+            scan_report: ScanReport::default(),
+            scan_report_scroll: 0,
+            pending_archives: Vec::new(),
+            selected_archive: 0,
+            versions: Vec::new(),
+            selected_version: 0,
+            relocate_path: String::new(),
+            relocation_check: None,
+            download_url: String::new(),
+            duplicate_groups: Vec::new(),
+            config_problems: Vec::new(),
+            config_report_scroll: 0,
+            selected_duplicate: 0,
+            list_top: 0,
+            clicks: ClickTracker::default(),
+        }
+    }
+
+    /// Open `mode` over the current screen, keeping that screen to return to
+    pub fn open_modal(&mut self, mode: Mode) {
+        self.modals.open(&mut self.mode, mode, self.current_item, self.first_entry, self.menu_selection);
+        self.redraw = true;
+    }
+
+    /// Close the screen on top and go back to the one under it, or to Browse when none is left
+    pub fn close_modal(&mut self) {
+        match self.modals.pop() {
+            Some(screen) => {
+                self.mode = screen.mode;
+                // The list may have changed while the modal was open
+                self.current_item = screen.current_item.min(self.filtered_entries.len().saturating_sub(1));
+                self.first_entry = screen.first_entry.min(self.current_item);
+                self.menu_selection = screen.menu_selection;
+            }
+            None => self.mode = Mode::Browse,
+        }
+        self.redraw = true;
+    }
+
+    /// Items of the context menu for the selection it was opened on
+    pub fn menu_items(&self) -> Vec<MenuItem> {
+        menu::get_context_menu_items(&MenuContext {
+            selected_entry: self.filtered_entries.get(self.remembered_item).cloned(),
+            episode_detail: self.edit_details.clone(),
+            mode: self.modals.top().map_or(Mode::Browse, |screen| screen.mode.clone()),
+            last_action: self.last_action.clone(),
+            marked_count: self.marked_episodes.len(),
+            extras_view: self.view_context == ViewContext::Extras,
+        })
+    }
+}
+
+/// Settings and services the key handlers read but do not change
+pub struct AppContext<'a> {
+    pub config: &'a Config,
+    /// The open library; background threads get their own clone of the handle
+    pub database: &'a Arc<Database>,
+    pub resolver: &'a PathResolver,
+    /// Background threads report playback and rescans on this channel
+    pub tx: &'a Sender<AppEvent>,
+    /// Where the config is saved; themes are kept next to it
+    pub config_path: &'a Path,
+}
+
+/// What `draw_screen` needs besides the state it draws
+pub struct DrawContext<'a> {
+    pub theme: &'a Theme,
+    pub database: &'a Database,
+    pub resolver: &'a PathResolver,
+    /// Rows kept visible above and below the selection
+    pub scroll_margin: usize,
+    pub accessible: bool,
+}
@@ -0,0 +1,139 @@
+use crate::config::ScanRules;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Archive extensions picked up during scans
+pub const ARCHIVE_EXTENSIONS: [&str; 2] = ["rar", "zip"];
+
+/// An archive in the library holding videos that have not been extracted yet
+#[derive(Debug, Clone, PartialEq)]
+pub struct Archive {
+    pub path: PathBuf,
+    /// Video paths listed inside the archive, empty when it could not be listed
+    pub videos: Vec<String>,
+}
+
+impl Archive {
+    /// Directory the archive is extracted into
+    pub fn dir(&self) -> &Path {
+        self.path.parent().unwrap_or_else(|| Path::new("."))
+    }
+
+    /// One-line description for the review screen
+    pub fn label(&self) -> String {
+        let name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match self.videos.len() {
+            0 => format!("{} (contents unknown)", name),
+            1 => format!("{} (1 video)", name),
+            count => format!("{} ({} videos)", name, count),
+        }
+    }
+}
+
+/// Check whether a file is an archive to offer, skipping later volumes of split rars
+pub fn is_archive(path: &Path) -> bool {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+    if !ARCHIVE_EXTENSIONS.contains(&extension.as_str()) {
+        return false;
+    }
+
+    // name.part02.rar and later are extracted together with the first volume
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if let Some(pos) = stem.rfind(".part") {
+        let number = &stem[pos + ".part".len()..];
+        if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+            return number.trim_start_matches('0') == "1";
+        }
+    }
+    true
+}
+
+/// Build a command from a template, replacing {archive} and {dir} in each argument
+pub fn build_command(template: &str, archive: &Path, dir: &Path) -> Option<Command> {
+    let mut parts = template.split_whitespace().map(|part| {
+        part.replace("{archive}", &archive.to_string_lossy())
+            .replace("{dir}", &dir.to_string_lossy())
+    });
+    let mut command = Command::new(parts.next()?);
+    command.args(parts);
+    Some(command)
+}
+
+/// Video paths in an archive listing, one path per line (`Path = ` prefixes from `7z l -slt` are stripped)
+pub fn videos_in_listing(listing: &str, rules: &ScanRules) -> Vec<String> {
+    listing
+        .lines()
+        .map(|line| line.trim())
+        .map(|line| line.strip_prefix("Path = ").unwrap_or(line))
+        .filter(|line| {
+            Path::new(line)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| rules.video_extensions.contains(&ext.to_lowercase()))
+        })
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// List the videos inside an archive with the configured list command
+pub fn list_videos(archive: &Path, list_command: &str, rules: &ScanRules) -> io::Result<Vec<String>> {
+    let dir = archive.parent().unwrap_or_else(|| Path::new("."));
+    let mut command = build_command(list_command, archive, dir)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "archive_list_command is empty"))?;
+    let output = command.stdin(Stdio::null()).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("listing {} failed", archive.display())));
+    }
+    Ok(videos_in_listing(&String::from_utf8_lossy(&output.stdout), rules))
+}
+
+/// Find archives under a root that hold videos missing from their directory
+pub fn find_pending_archives(root: &Path, list_command: &str, rules: &ScanRules) -> Vec<Archive> {
+    let mut archives = Vec::new();
+    for entry in rules.walk(root).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() || !is_archive(entry.path()) || rules.excludes(root, entry.path()) {
+            continue;
+        }
+        let path = entry.into_path();
+        let videos = match list_videos(&path, list_command, rules) {
+            Ok(videos) => videos,
+            Err(e) => {
+                crate::logger::log_warn(&format!("Could not list archive {}: {}", path.display(), e));
+                archives.push(Archive { path, videos: Vec::new() });
+                continue;
+            }
+        };
+        let archive = Archive { path, videos };
+        let extracted = archive.videos.iter().all(|video| archive.dir().join(video).exists());
+        if !archive.videos.is_empty() && !extracted {
+            archives.push(archive);
+        }
+    }
+    archives.sort_by(|a, b| a.path.cmp(&b.path));
+    archives
+}
+
+/// Extract an archive next to itself with the configured extract command
+pub fn extract(archive: &Archive, extract_command: &str) -> io::Result<()> {
+    let mut command = build_command(extract_command, &archive.path, archive.dir())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "archive_extract_command is empty"))?;
+    let output = command.stdin(Stdio::null()).output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let reason = stderr.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("unknown error");
+    Err(io::Error::other(format!("{} exited with {}: {}", extract_command, output.status, reason.trim())))
+}
@@ -0,0 +1,215 @@
+use crate::database::Database;
+use crate::location;
+use crate::path_resolver::PathResolver;
+use crate::util::Entry;
+use base64::Engine;
+use std::path::{Path, PathBuf};
+
+/// Suffixes after a video's file stem that name its own thumbnail
+const EPISODE_ARTWORK: &[&str] = &[".png", "-thumb.png", "-poster.png"];
+
+/// Artwork file names looked for in a video's folder, then in the folder above it
+const FOLDER_ARTWORK: &[&str] = &["poster.png", "folder.png", "cover.png"];
+
+/// Pixel size assumed for one terminal cell when scaling sixel images
+/// Small on purpose: an image sized for small cells never spills out of its area
+pub const CELL_WIDTH_PX: usize = 8;
+pub const CELL_HEIGHT_PX: usize = 16;
+
+/// Kitty image id used for the artwork pane
+pub const KITTY_IMAGE_ID: u32 = 1;
+
+/// Largest base64 payload kitty accepts in one escape sequence
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// What the artwork pane currently shows and the escape sequence that draws it
+#[derive(Debug, Default)]
+pub struct ArtworkState {
+    /// Poster file and the pane area (column, row, width, height) it was fitted into
+    pub shown: Option<(PathBuf, (usize, usize, usize, usize))>,
+    /// Column and row of the top-left corner of the image
+    pub position: (usize, usize),
+    /// Sequence repeated on every redraw; empty when nothing is shown or kitty keeps the image itself
+    pub sequence: String,
+}
+
+/// Artwork stored next to a video: its own thumbnail, otherwise a poster in its folder or the one above
+pub fn find_episode_artwork(video: &Path) -> Option<PathBuf> {
+    let stem = video.file_stem()?.to_string_lossy();
+    let parent = video.parent()?;
+    EPISODE_ARTWORK
+        .iter()
+        .map(|suffix| parent.join(format!("{}{}", stem, suffix)))
+        .find(|path| path.is_file())
+        .or_else(|| find_folder_artwork(video))
+}
+
+/// Poster in a video's folder or the folder above it, shared by the series or season
+pub fn find_folder_artwork(video: &Path) -> Option<PathBuf> {
+    let parent = video.parent()?;
+    [Some(parent), parent.parent()]
+        .into_iter()
+        .flatten()
+        .flat_map(|folder| FOLDER_ARTWORK.iter().map(move |name| folder.join(name)))
+        .find(|path| path.is_file())
+}
+
+/// Artwork for a browse entry; series and seasons use the poster next to their first episode
+pub fn find_artwork(entry: &Entry, database: &Database, resolver: &PathResolver) -> Option<PathBuf> {
+    match entry {
+        Entry::Episode { location, .. } => find_episode_artwork(&resolver.to_absolute(&location::decode(location))),
+        Entry::Series { series_id, .. } => {
+            let entries = database.get_entries_for_series(*series_id).ok()?;
+            let first_episode = entries.iter().find(|entry| matches!(entry, Entry::Episode { .. }));
+            match first_episode.or_else(|| entries.first()) {
+                Some(Entry::Episode { location, .. }) => find_folder_artwork(&resolver.to_absolute(&location::decode(location))),
+                Some(season @ Entry::Season { .. }) => find_artwork(season, database, resolver),
+                _ => None,
+            }
+        }
+        Entry::Season { season_id, .. } => database.get_entries_for_season(*season_id)
+            .ok()?
+            .iter()
+            .find_map(|entry| match entry {
+                Entry::Episode { location, .. } => Some(resolver.to_absolute(&location::decode(location))),
+                _ => None,
+            })
+            .and_then(|video| find_folder_artwork(&video)),
+    }
+}
+
+/// Width and height of a PNG image, read from its header
+pub fn png_dimensions(png: &[u8]) -> Option<(usize, usize)> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if png.len() < 24 || &png[..8] != SIGNATURE || &png[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes([png[16], png[17], png[18], png[19]]) as usize;
+    let height = u32::from_be_bytes([png[20], png[21], png[22], png[23]]) as usize;
+    (width > 0 && height > 0).then_some((width, height))
+}
+
+/// Largest size in cells that keeps the image's shape within `max_cols` x `max_rows`
+/// Cells are taken to be twice as tall as they are wide
+pub fn fit_cells(width: usize, height: usize, max_cols: usize, max_rows: usize) -> (usize, usize) {
+    if width == 0 || height == 0 || max_cols == 0 || max_rows == 0 {
+        return (0, 0);
+    }
+    let cols = max_rows * 2 * width / height;
+    if cols <= max_cols {
+        (cols.max(1), max_rows)
+    } else {
+        (max_cols, (max_cols * height / (2 * width)).max(1))
+    }
+}
+
+/// Kitty graphics sequences that upload a PNG and show it across `cols` x `rows` cells at the cursor
+pub fn kitty_sequence(png: &[u8], cols: usize, rows: usize) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(png);
+    let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+    let mut sequence = String::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(index + 1 < chunks.len());
+        let payload = String::from_utf8_lossy(chunk);
+        if index == 0 {
+            sequence.push_str(&format!(
+                "\x1b_Ga=T,f=100,i={},p=1,c={},r={},C=1,q=2,m={};{}\x1b\\",
+                KITTY_IMAGE_ID, cols, rows, more, payload
+            ));
+        } else {
+            sequence.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, payload));
+        }
+    }
+    sequence
+}
+
+/// Kitty graphics sequence that removes the artwork pane's image
+pub fn kitty_delete_sequence() -> String {
+    format!("\x1b_Ga=d,d=I,i={},q=2\x1b\\", KITTY_IMAGE_ID)
+}
+
+/// Sixel sequence drawing a PNG scaled to `cols` x `rows` cells, or None if the PNG can't be decoded
+pub fn sixel_sequence(png: &[u8], cols: usize, rows: usize) -> Option<String> {
+    let mut decoder = png::Decoder::new(png);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().ok()?;
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut pixels).ok()?;
+    let channels = frame.color_type.samples();
+    let (width, height) = (frame.width as usize, frame.height as usize);
+
+    // Nearest pixel of the source image, blended onto black, as a 6x6x6 color cube index
+    let target_width = cols * CELL_WIDTH_PX;
+    let target_height = rows * CELL_HEIGHT_PX;
+    if target_width == 0 || target_height == 0 || width == 0 || height == 0 {
+        return None;
+    }
+    let color_at = |x: usize, y: usize| -> usize {
+        let offset = (y * height / target_height) * frame.line_size + (x * width / target_width) * channels;
+        let pixel = &pixels[offset..offset + channels];
+        let (rgb, alpha) = match channels {
+            1 => ([pixel[0]; 3], 255),
+            2 => ([pixel[0]; 3], pixel[1]),
+            3 => ([pixel[0], pixel[1], pixel[2]], 255),
+            _ => ([pixel[0], pixel[1], pixel[2]], pixel[3]),
+        };
+        let level = |value: u8| (value as usize * alpha as usize / 255 * 5 + 127) / 255;
+        level(rgb[0]) * 36 + level(rgb[1]) * 6 + level(rgb[2])
+    };
+    let image: Vec<Vec<usize>> = (0..target_height)
+        .map(|y| (0..target_width).map(|x| color_at(x, y)).collect())
+        .collect();
+
+    let mut sequence = format!("\x1bPq\"1;1;{};{}", target_width, target_height);
+    let mut used = [false; 216];
+    image.iter().flatten().for_each(|color| used[*color] = true);
+    for (color, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+        let percent = |level: usize| level * 20;
+        sequence.push_str(&format!(
+            "#{};2;{};{};{}",
+            color,
+            percent(color / 36),
+            percent(color / 6 % 6),
+            percent(color % 6)
+        ));
+    }
+
+    for band in image.chunks(6) {
+        let mut colors: Vec<usize> = band.iter().flatten().copied().collect();
+        colors.sort_unstable();
+        colors.dedup();
+        for color in colors {
+            sequence.push_str(&format!("#{}", color));
+            let sixels: Vec<char> = (0..target_width)
+                .map(|x| {
+                    let bits = band
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, row)| row[x] == color)
+                        .fold(0, |bits, (bit, _)| bits | (1 << bit));
+                    char::from(63 + bits as u8)
+                })
+                .collect();
+            push_run_length(&mut sequence, &sixels);
+            sequence.push('$');
+        }
+        sequence.push('-');
+    }
+    sequence.push_str("\x1b\\");
+    Some(sequence)
+}
+
+/// Append sixel characters, collapsing runs into `!<count><char>`
+fn push_run_length(sequence: &mut String, sixels: &[char]) {
+    let mut index = 0;
+    while index < sixels.len() {
+        let ch = sixels[index];
+        let run = sixels[index..].iter().take_while(|other| **other == ch).count();
+        if run > 3 {
+            sequence.push_str(&format!("!{}{}", run, ch));
+        } else {
+            (0..run).for_each(|_| sequence.push(ch));
+        }
+        index += run;
+    }
+}
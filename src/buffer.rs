@@ -53,14 +53,16 @@
 //!
 //! - **Terminal Resize**: Both buffers are recreated with new dimensions
 //! - **Mode Changes**: `force_full_redraw()` ensures clean visual state
-//! - **Cursor Visibility**: Handled separately via direct terminal calls
+//! - **Cursor**: Set with `set_cursor()` and placed after the changed cells, hidden otherwise
 
 use crossterm::style::Color;
 use std::io::{self, Write};
+use crate::text::{self, WIDE_CHAR_TAIL};
 use crossterm::{
     cursor,
-    execute,
+    queue,
     style::{Attribute, SetAttribute, SetBackgroundColor, SetForegroundColor},
+    Command,
 };
 
 /// Represents a single terminal cell with character, colors, and style attributes
@@ -185,7 +187,13 @@ impl<'a> BufferWriter<'a> {
     }
 
     /// Write a character at current position
+    /// A wide character also fills the next cell with a tail, and zero-width characters are skipped
     pub fn write_char(&mut self, c: char) {
+        let width = text::char_width(c);
+        if width == 0 {
+            // Also skips the tails components put after wide characters, which are written here
+            return;
+        }
         let cell = Cell {
             character: c,
             fg_color: self.current_fg,
@@ -195,8 +203,43 @@ impl<'a> BufferWriter<'a> {
             underlined: self.current_underlined,
             dim: self.current_dim,
         };
-        self.buffer.set_cell(self.current_x, self.current_y, cell);
-        self.current_x += 1;
+        let (x, y) = (self.current_x, self.current_y);
+        // Writing over half of a wide character leaves the other half blank
+        if x > 0 && self.is_tail(x, y) {
+            self.blank_cell(x - 1, y);
+        }
+        if self.is_tail(x + width, y) {
+            self.blank_cell(x + width, y);
+        }
+        if width > 1 {
+            // A wide character cut by the right edge would wrap, so it is drawn as a space
+            if x + 1 >= self.buffer.width {
+                self.buffer.set_cell(x, y, Cell { character: ' ', ..cell });
+                self.current_x += width;
+                return;
+            }
+            let tail = Cell {
+                character: WIDE_CHAR_TAIL,
+                ..cell.clone()
+            };
+            self.buffer.set_cell(x + 1, y, tail);
+        }
+        self.buffer.set_cell(x, y, cell);
+        self.current_x += width;
+    }
+
+    fn is_tail(&self, x: usize, y: usize) -> bool {
+        self.buffer.get_cell(x, y).is_some_and(|cell| cell.character == WIDE_CHAR_TAIL)
+    }
+
+    fn blank_cell(&mut self, x: usize, y: usize) {
+        if let Some(cell) = self.buffer.get_cell(x, y) {
+            let blank = Cell {
+                character: ' ',
+                ..cell.clone()
+            };
+            self.buffer.set_cell(x, y, blank);
+        }
     }
 
     /// Write a string at current position
@@ -241,6 +284,19 @@ impl<'a> BufferWriter<'a> {
     pub fn set_dim(&mut self, dim: bool) {
         self.current_dim = dim;
     }
+
+    /// Change the background of cells already written in a rectangle, keeping their content
+    pub fn set_region_bg(&mut self, x: usize, y: usize, width: usize, height: usize, color: Color) {
+        for row in y..y + height {
+            for col in x..x + width {
+                if let Some(cell) = self.buffer.get_cell(col, row) {
+                    let mut cell = cell.clone();
+                    cell.bg_color = color;
+                    self.buffer.set_cell(col, row, cell);
+                }
+            }
+        }
+    }
 }
 
 /// Main coordinator that manages both buffers and orchestrates the rendering pipeline.
@@ -271,6 +327,10 @@ pub struct BufferManager {
     desired_buffer: ScreenBuffer,
     width: usize,
     height: usize,
+    /// Where the cursor should be shown this frame, hidden for None
+    cursor: Option<(usize, usize)>,
+    /// The cursor as it was last left on the terminal; None when unknown
+    terminal_cursor: Option<Option<(usize, usize)>>,
 }
 
 impl BufferManager {
@@ -281,12 +341,20 @@ impl BufferManager {
             desired_buffer: ScreenBuffer::new(width, height),
             width,
             height,
+            cursor: None,
+            terminal_cursor: None,
         }
     }
 
     /// Clear desired buffer to empty state (start of frame)
     pub fn clear_desired_buffer(&mut self) {
         self.desired_buffer.clear();
+        self.cursor = None;
+    }
+
+    /// Show the cursor at a cell once the frame is rendered, or keep it hidden for None
+    pub fn set_cursor(&mut self, position: Option<(usize, usize)>) {
+        self.cursor = position;
     }
 
     /// Get a writer for drawing to the desired buffer
@@ -300,6 +368,7 @@ impl BufferManager {
         self.height = height;
         self.current_buffer = ScreenBuffer::new(width, height);
         self.desired_buffer = ScreenBuffer::new(width, height);
+        self.terminal_cursor = None;
     }
 
     /// Force full redraw (for mode changes)
@@ -322,6 +391,7 @@ impl BufferManager {
                 *cell = sentinel.clone();
             }
         }
+        self.terminal_cursor = None;
     }
 
     /// Compare buffers and identify changed cells
@@ -353,15 +423,90 @@ impl BufferManager {
         }
     }
 
+    /// The desired buffer as plain text, one line per row with trailing spaces trimmed
+    pub fn desired_text(&self) -> String {
+        let mut text = String::new();
+        for row in &self.desired_buffer.cells {
+            let line: String = row
+                .iter()
+                .map(|cell| cell.character)
+                .filter(|&ch| ch != WIDE_CHAR_TAIL)
+                .collect();
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The desired buffer with ANSI colors and attributes, for viewing with `cat` or `less -R`
+    pub fn desired_ansi(&self) -> String {
+        let mut text = String::new();
+        for row in &self.desired_buffer.cells {
+            let mut previous: Option<&Cell> = None;
+            for cell in row.iter().filter(|cell| cell.character != WIDE_CHAR_TAIL) {
+                let style_changed = match previous {
+                    Some(prev) => {
+                        prev.fg_color != cell.fg_color
+                            || prev.bg_color != cell.bg_color
+                            || prev.bold != cell.bold
+                            || prev.italic != cell.italic
+                            || prev.underlined != cell.underlined
+                            || prev.dim != cell.dim
+                    }
+                    None => true,
+                };
+                if style_changed {
+                    // Writing to a String cannot fail
+                    let _ = SetAttribute(Attribute::Reset).write_ansi(&mut text);
+                    let _ = SetForegroundColor(cell.fg_color).write_ansi(&mut text);
+                    let _ = SetBackgroundColor(cell.bg_color).write_ansi(&mut text);
+                    for (enabled, attribute) in [
+                        (cell.bold, Attribute::Bold),
+                        (cell.italic, Attribute::Italic),
+                        (cell.underlined, Attribute::Underlined),
+                        (cell.dim, Attribute::Dim),
+                    ] {
+                        if enabled {
+                            let _ = SetAttribute(attribute).write_ansi(&mut text);
+                        }
+                    }
+                }
+                text.push(cell.character);
+                previous = Some(cell);
+            }
+            let _ = SetAttribute(Attribute::Reset).write_ansi(&mut text);
+            text.push('\n');
+        }
+        text
+    }
+
     /// Compare buffers and write differences to terminal
     pub fn render_to_terminal(&mut self) -> io::Result<()> {
+        // Headless rendering keeps the frame in the buffer for screenshots
+        if crate::terminal::headless_size().is_some() {
+            self.update_current_buffer();
+            self.terminal_cursor = Some(self.cursor);
+            return Ok(());
+        }
+        // Buffered so that a whole frame reaches the terminal at once
+        self.render_to(&mut io::BufWriter::with_capacity(1 << 16, io::stdout().lock()))
+    }
+
+    /// Write the changed cells and the cursor to `out` with a single flush
+    ///
+    /// The cursor is hidden while cells are written, so it never shows up in the middle of a frame,
+    /// and then left where `set_cursor` put it. Nothing is written when neither changed.
+    pub fn render_to(&mut self, out: &mut impl Write) -> io::Result<()> {
         let changes = self.compare_buffers();
-        
-        if changes.is_empty() {
+        let cells_changed = !changes.is_empty();
+
+        if !cells_changed && self.terminal_cursor == Some(self.cursor) {
             return Ok(());
         }
-        
-        let mut stdout = io::stdout();
+
+        if cells_changed && self.terminal_cursor != Some(None) {
+            queue!(out, cursor::Hide)?;
+        }
         
         // Group consecutive cells on the same row for batching
         let mut batches: Vec<Vec<(usize, usize, Cell)>> = Vec::new();
@@ -398,7 +543,7 @@ impl BufferManager {
             
             // Move cursor to the start of the batch
             let (start_x, start_y, _) = &batch[0];
-            execute!(stdout, cursor::MoveTo(*start_x as u16, *start_y as u16))?;
+            queue!(out, cursor::MoveTo(*start_x as u16, *start_y as u16))?;
             
             // Track current style to minimize escape sequences
             let mut current_fg: Option<Color> = None;
@@ -408,26 +553,36 @@ impl BufferManager {
             let mut current_underlined = false;
             let mut current_dim = false;
             
-            // Write all cells in the batch
-            for (_, _, cell) in batch {
+            // Write all cells in the batch, moving the cursor again where a wide character
+            // was written over a different number of columns than cells
+            let mut cursor_x = *start_x;
+            for (x, y, cell) in batch {
+                if cell.character == WIDE_CHAR_TAIL {
+                    continue;
+                }
+                if x != cursor_x {
+                    queue!(out, cursor::MoveTo(x as u16, y as u16))?;
+                }
+                cursor_x = x + text::char_width(cell.character);
+
                 // Update foreground color if changed
                 if current_fg.as_ref() != Some(&cell.fg_color) {
-                    execute!(stdout, SetForegroundColor(cell.fg_color))?;
+                    queue!(out, SetForegroundColor(cell.fg_color))?;
                     current_fg = Some(cell.fg_color);
                 }
                 
                 // Update background color if changed
                 if current_bg.as_ref() != Some(&cell.bg_color) {
-                    execute!(stdout, SetBackgroundColor(cell.bg_color))?;
+                    queue!(out, SetBackgroundColor(cell.bg_color))?;
                     current_bg = Some(cell.bg_color);
                 }
                 
                 // Update bold attribute if changed
                 if current_bold != cell.bold {
                     if cell.bold {
-                        execute!(stdout, SetAttribute(Attribute::Bold))?;
+                        queue!(out, SetAttribute(Attribute::Bold))?;
                     } else {
-                        execute!(stdout, SetAttribute(Attribute::NormalIntensity))?;
+                        queue!(out, SetAttribute(Attribute::NormalIntensity))?;
                     }
                     current_bold = cell.bold;
                 }
@@ -435,9 +590,9 @@ impl BufferManager {
                 // Update italic attribute if changed
                 if current_italic != cell.italic {
                     if cell.italic {
-                        execute!(stdout, SetAttribute(Attribute::Italic))?;
+                        queue!(out, SetAttribute(Attribute::Italic))?;
                     } else {
-                        execute!(stdout, SetAttribute(Attribute::NoItalic))?;
+                        queue!(out, SetAttribute(Attribute::NoItalic))?;
                     }
                     current_italic = cell.italic;
                 }
@@ -445,9 +600,9 @@ impl BufferManager {
                 // Update underlined attribute if changed
                 if current_underlined != cell.underlined {
                     if cell.underlined {
-                        execute!(stdout, SetAttribute(Attribute::Underlined))?;
+                        queue!(out, SetAttribute(Attribute::Underlined))?;
                     } else {
-                        execute!(stdout, SetAttribute(Attribute::NoUnderline))?;
+                        queue!(out, SetAttribute(Attribute::NoUnderline))?;
                     }
                     current_underlined = cell.underlined;
                 }
@@ -455,26 +610,34 @@ impl BufferManager {
                 // Update dim attribute if changed
                 if current_dim != cell.dim {
                     if cell.dim {
-                        execute!(stdout, SetAttribute(Attribute::Dim))?;
+                        queue!(out, SetAttribute(Attribute::Dim))?;
                     } else {
-                        execute!(stdout, SetAttribute(Attribute::NormalIntensity))?;
+                        queue!(out, SetAttribute(Attribute::NormalIntensity))?;
                     }
                     current_dim = cell.dim;
                 }
                 
                 // Write the character
-                write!(stdout, "{}", cell.character)?;
+                write!(out, "{}", cell.character)?;
             }
             
             // Reset attributes after each batch to avoid style bleeding
-            execute!(stdout, SetAttribute(Attribute::Reset))?;
+            queue!(out, SetAttribute(Attribute::Reset))?;
         }
         
+        // Writing cells moved the cursor, so it goes back even when it stayed put
+        match self.cursor {
+            Some((x, y)) => queue!(out, cursor::MoveTo(x as u16, y as u16), cursor::Show)?,
+            None if !cells_changed => queue!(out, cursor::Hide)?,
+            None => {}
+        }
+
         // Flush to ensure all writes are sent to terminal
-        stdout.flush()?;
+        out.flush()?;
         
         // Update current buffer to match desired buffer after successful write
         self.update_current_buffer();
+        self.terminal_cursor = Some(self.cursor);
         
         Ok(())
     }
@@ -0,0 +1,91 @@
+//! Settings given for a single run, on the command line or in environment variables
+//!
+//! `--db`, `--config` and `--log-level` win over config.yaml for the run they are given to,
+//! and are never saved to it, so another library can be tried without touching the real
+//! config. Each has an environment variable, `MOVIES_DB`, `MOVIES_CONFIG` and
+//! `MOVIES_LOG_LEVEL`, and a flag wins over its variable. `--portable` keeps everything next
+//! to the executable instead, see `paths`.
+
+use std::path::PathBuf;
+
+pub const USAGE: &str = "Usage: movies [--portable] [--db PATH] [--config PATH] [--log-level LEVEL]
+
+  --db PATH          open the database at PATH instead of the one in config.yaml (MOVIES_DB)
+  --config PATH      read and save settings in PATH instead of config.yaml (MOVIES_CONFIG)
+  --log-level LEVEL  log at error, warn, info or debug for this run (MOVIES_LOG_LEVEL)
+  --portable         keep settings, log and state next to the program, as a movies.portable file there does
+  --help             show this help";
+
+const LOG_LEVELS: [&str; 4] = ["error", "warn", "info", "debug"];
+
+/// Values that win over config.yaml for this run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Overrides {
+    pub db: Option<PathBuf>,
+    pub config: Option<PathBuf>,
+    pub log_level: Option<String>,
+    /// Keep the config and the rest next to the executable
+    pub portable: bool,
+}
+
+/// What the command line asks for
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Open the library, with what the flags and variables override
+    Run(Overrides),
+    /// Hidden: render screenshots of a sample library into the folder and exit
+    Demo(PathBuf),
+    Help,
+}
+
+/// Read the command line `args`, without the program name, with the environment variables from `env`
+pub fn parse_args(args: &[String], env: impl Fn(&str) -> Option<String>) -> Result<Command, String> {
+    if args.first().map(String::as_str) == Some("--demo") {
+        let output_dir = args.get(1).map(String::as_str).unwrap_or("demo-screenshots");
+        return Ok(Command::Demo(PathBuf::from(output_dir)));
+    }
+
+    let non_empty = |name: &str| env(name).filter(|value| !value.trim().is_empty());
+    let mut db = non_empty("MOVIES_DB");
+    let mut config = non_empty("MOVIES_CONFIG");
+    let mut log_level = non_empty("MOVIES_LOG_LEVEL");
+    let mut portable = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--help" || arg == "-h" {
+            return Ok(Command::Help);
+        }
+        if arg == "--portable" {
+            portable = true;
+            continue;
+        }
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let target = match flag {
+            "--db" => &mut db,
+            "--config" => &mut config,
+            "--log-level" => &mut log_level,
+            _ => return Err(format!("Unknown argument '{}'", arg)),
+        };
+        let value = inline_value.or_else(|| args.next().cloned());
+        match value.filter(|value| !value.trim().is_empty()) {
+            Some(value) => *target = Some(value),
+            None => return Err(format!("{} needs a value", flag)),
+        }
+    }
+
+    if let Some(ref level) = log_level {
+        if !LOG_LEVELS.contains(&level.trim().to_lowercase().as_str()) {
+            return Err(format!("Unknown log level '{}'; use error, warn, info or debug", level));
+        }
+    }
+    Ok(Command::Run(Overrides {
+        db: db.map(PathBuf::from),
+        config: config.map(PathBuf::from),
+        log_level,
+        portable,
+    }))
+}
@@ -0,0 +1,43 @@
+//! Copying the selected episode's file path or title to the system clipboard
+//!
+//! The clipboard is opened on the first copy and kept open until the program exits, since on
+//! Linux the copied text is only there while the program holding it still runs.
+
+use crate::dto::EpisodeDetail;
+use arboard::Clipboard;
+use lazy_static::lazy_static;
+use std::sync::Mutex;
+
+lazy_static! {
+    static ref CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+}
+
+/// Put `text` on the system clipboard
+pub fn copy(text: &str) -> Result<(), String> {
+    let mut clipboard = CLIPBOARD.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if clipboard.is_none() {
+        *clipboard = Some(Clipboard::new().map_err(|e| e.to_string())?);
+    }
+    match clipboard.as_mut() {
+        Some(clipboard) => clipboard.set_text(text).map_err(|e| e.to_string()),
+        None => Err("no clipboard".to_string()),
+    }
+}
+
+/// Title of an episode as it is copied, e.g. "The Wire S02E05 — Undertow" or "Heat (1995)"
+pub fn episode_title(details: &EpisodeDetail) -> String {
+    let Some(ref series) = details.series else {
+        return match details.year.trim() {
+            "" => details.title.clone(),
+            year => format!("{} ({})", details.title, year),
+        };
+    };
+    let episode = details.episode_number.trim().parse::<usize>().ok();
+    let number = match (&details.season, episode) {
+        (Some(season), Some(episode)) => format!(" S{:02}E{:02}", season.number, episode),
+        (Some(season), None) => format!(" S{:02}", season.number),
+        (None, Some(episode)) => format!(" E{:02}", episode),
+        (None, None) => String::new(),
+    };
+    format!("{}{} \u{2014} {}", series.name, number, details.title)
+}
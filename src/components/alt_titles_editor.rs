@@ -0,0 +1,147 @@
+use super::{Cell, Component, TextStyle};
+use crate::dto::AltTitles;
+use crate::theme::{BorderChars, Theme};
+use crossterm::style::Color;
+
+/// Rows of the alternate titles being edited, one `language: title` line each
+/// The last row is always empty so a new title can be added
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AltTitlesDraft {
+    pub rows: Vec<String>,
+    pub selected: usize,
+}
+
+impl AltTitlesDraft {
+    /// Start editing the given titles with the empty row selected
+    pub fn from_titles(titles: &AltTitles) -> Self {
+        let mut rows: Vec<String> = titles
+            .iter()
+            .map(|(language, title)| format!("{}: {}", language, title))
+            .collect();
+        rows.push(String::new());
+        let selected = rows.len() - 1;
+        Self { rows, selected }
+    }
+
+    /// Keep exactly one empty row at the end
+    pub fn normalize(&mut self) {
+        while self.rows.len() > 1 && self.rows.iter().rev().take(2).all(|row| row.is_empty()) {
+            self.rows.pop();
+        }
+        if self.rows.last().is_none_or(|row| !row.is_empty()) {
+            self.rows.push(String::new());
+        }
+        self.selected = self.selected.min(self.rows.len() - 1);
+    }
+
+    /// Parse the rows; empty rows are skipped
+    pub fn to_titles(&self) -> Result<AltTitles, String> {
+        let mut titles = AltTitles::new();
+        for row in self.rows.iter().filter(|row| !row.trim().is_empty()) {
+            let (language, title) = row
+                .split_once(':')
+                .map(|(language, title)| (language.trim().to_lowercase(), title.trim()))
+                .filter(|(language, title)| !language.is_empty() && !title.is_empty())
+                .ok_or_else(|| format!("\"{}\" is not in the form language: title", row.trim()))?;
+            titles.insert(language, title.to_string());
+        }
+        Ok(titles)
+    }
+}
+
+/// Bordered modal listing an episode's alternate titles, one editable row each
+pub struct AltTitlesEditor {
+    rows: Vec<String>,
+    selected: usize,
+    cursor_position: usize,
+    window_width: usize,
+}
+
+impl AltTitlesEditor {
+    /// Create a new AltTitlesEditor component
+    pub fn new(draft: &AltTitlesDraft, cursor_position: usize, window_width: usize) -> Self {
+        Self {
+            rows: draft.rows.clone(),
+            selected: draft.selected,
+            cursor_position,
+            window_width,
+        }
+    }
+
+    /// Rows needed: borders, prompt, and one row per title
+    pub fn height(&self) -> usize {
+        self.rows.len() + 3
+    }
+
+    /// Column of the cursor relative to the left edge of the window
+    pub fn cursor_column(&self) -> usize {
+        let length = self.rows.get(self.selected).map_or(0, |row| row.chars().count());
+        1 + self.cursor_position.min(length).min(self.content_width().saturating_sub(1))
+    }
+
+    /// Row of the cursor relative to the top of the window
+    pub fn cursor_row(&self) -> usize {
+        2 + self.selected
+    }
+
+    /// Width available for text inside the borders
+    fn content_width(&self) -> usize {
+        self.window_width.saturating_sub(2)
+    }
+
+    /// Build a bordered row of cells padded to the content width
+    fn build_row(&self, border: &BorderChars, text: &str, fg: Color, bg: Color) -> Vec<Cell> {
+        let width = self.content_width();
+        let mut row = vec![Cell::new(border.vertical, Color::Reset, Color::Reset, TextStyle::new())];
+        let mut used = 0;
+        for ch in text.chars().take(width) {
+            row.push(Cell::new(ch, fg, bg, TextStyle::new()));
+            used += 1;
+        }
+        for _ in used..width {
+            row.push(Cell::new(' ', fg, bg, TextStyle::new()));
+        }
+        row.push(Cell::new(border.vertical, Color::Reset, Color::Reset, TextStyle::new()));
+        row
+    }
+
+    /// Build the top or bottom border row
+    fn horizontal_border(&self, border: &BorderChars, top: bool) -> Vec<Cell> {
+        let (left, right) = if top {
+            (border.top_left, border.top_right)
+        } else {
+            (border.bottom_left, border.bottom_right)
+        };
+        let mut row = vec![Cell::new(left, Color::Reset, Color::Reset, TextStyle::new())];
+        for _ in 0..self.content_width() {
+            row.push(Cell::new(border.horizontal, Color::Reset, Color::Reset, TextStyle::new()));
+        }
+        row.push(Cell::new(right, Color::Reset, Color::Reset, TextStyle::new()));
+        row
+    }
+}
+
+impl Component for AltTitlesEditor {
+    /// Renders the prompt and the title rows, highlighting the row being edited
+    fn render(&self, _width: usize, height: usize, theme: &Theme, _is_selected: bool) -> Vec<Vec<Cell>> {
+        let border = theme.active_border_chars();
+        let mut cells = vec![self.horizontal_border(&border, true)];
+
+        // Prompt row with inverted colors, matching the notes window
+        let prompt = "Titles as language: title, [ENTER] save, [ESC] cancel:";
+        cells.push(self.build_row(&border, prompt, Color::Black, Color::White));
+
+        for (index, row) in self.rows.iter().enumerate() {
+            let (fg, bg) = if index == self.selected {
+                (Color::White, Color::DarkGrey)
+            } else {
+                (Color::Reset, Color::Reset)
+            };
+            cells.push(self.build_row(&border, row, fg, bg));
+        }
+
+        cells.push(self.horizontal_border(&border, false));
+        cells.truncate(height);
+        cells
+    }
+}
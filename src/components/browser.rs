@@ -1,7 +1,19 @@
-use super::{Cell, Component, TextStyle, Category, Scrollbar};
+use super::{push_char_cell, Cell, Component, TextStyle, Category, Scrollbar};
 use super::episode::Episode;
-use crate::theme::Theme;
+use crate::text;
+use crate::theme::{string_to_color, Theme};
 use crossterm::style::Color;
+use std::collections::HashSet;
+
+/// Marker shown in front of marked items when the theme doesn't set one, followed by a space
+const MARKER: char = '*';
+
+/// One row of the list: a group header or the item at an index
+#[derive(Debug, Clone, PartialEq)]
+pub enum BrowserRow {
+    Header(String),
+    Item(usize),
+}
 
 /// Browser component that serves as the main display element for the episode browser application
 ///
@@ -21,6 +33,17 @@ pub struct Browser {
     pub selected_item: usize,
     /// Index of the first visible item in the viewport
     pub first_visible_item: usize,
+    /// Indices of marked items; while any are marked a marker column is shown
+    pub marked_items: HashSet<usize>,
+    /// Rows kept visible above and below the selection before the list scrolls
+    pub scroll_margin: usize,
+    /// Group labels, each drawn on its own row above the item at its index; the rows can't be selected
+    pub group_headers: Vec<(usize, String)>,
+    /// Index of the item the first of `categories` and `episodes` stands for, when only a window
+    /// of a longer list is held
+    pub window_start: usize,
+    /// Number of items in the whole list when only a window of it is held
+    pub window_total: Option<usize>,
 }
 
 impl Browser {
@@ -38,17 +61,90 @@ impl Browser {
             episodes,
             selected_item: 0,
             first_visible_item: 0,
+            marked_items: HashSet::new(),
+            scroll_margin: 0,
+            group_headers: Vec::new(),
+            window_start: 0,
+            window_total: None,
+        }
+    }
+
+    /// A browser over a list of `total` items, holding none of them until `set_window` is called
+    ///
+    /// Scrolling only needs the number of items, so the scroll position can be worked out first
+    /// and only the items on screen turned into components.
+    pub fn windowed(top_left: (usize, usize), width: usize, total: usize) -> Self {
+        Self {
+            window_total: Some(total),
+            ..Self::new(top_left, width, Vec::new(), Vec::new())
         }
     }
 
+    /// Hold the components of the items from `start` on: the categories, then the episodes
+    pub fn set_window(&mut self, start: usize, categories: Vec<Category>, episodes: Vec<Episode>) {
+        self.window_start = start;
+        self.categories = categories;
+        self.episodes = episodes;
+    }
+
     /// Get the total number of items (categories + episodes)
     pub fn total_items(&self) -> usize {
-        self.categories.len() + self.episodes.len()
+        self.window_total.unwrap_or(self.categories.len() + self.episodes.len())
+    }
+
+    /// The category at `index` of the list, if it is one and is held
+    pub fn category_at(&self, index: usize) -> Option<&Category> {
+        self.categories.get(index.checked_sub(self.window_start)?)
+    }
+
+    /// The episode at `index` of the list, if it is one and is held
+    pub fn episode_at(&self, index: usize) -> Option<&Episode> {
+        let index = index.checked_sub(self.window_start)?;
+        self.episodes.get(index.checked_sub(self.categories.len())?)
+    }
+
+    /// Get the total number of rows, counting the group headers
+    pub fn total_rows(&self) -> usize {
+        self.rows_before(self.total_items())
+    }
+
+    /// Rows above the item at `index`, or above its group header when it starts a group
+    fn rows_before(&self, index: usize) -> usize {
+        index + self.group_headers.iter().filter(|(start, _)| *start < index).count()
+    }
+
+    /// Rows taken by the items from `first` up to but not including `end`, with their headers
+    fn rows_in(&self, first: usize, end: usize) -> usize {
+        self.rows_before(end) - self.rows_before(first)
+    }
+
+    /// Rows shown from `first_visible_item` on, with each group header above its first item
+    pub fn layout_rows(group_headers: &[(usize, String)], first_visible_item: usize, total: usize, height: usize) -> Vec<BrowserRow> {
+        let mut rows = Vec::new();
+        for index in first_visible_item..total {
+            if rows.len() >= height {
+                break;
+            }
+            if let Some((_, label)) = group_headers.iter().find(|(start, _)| *start == index) {
+                rows.push(BrowserRow::Header(label.clone()));
+            }
+            rows.push(BrowserRow::Item(index));
+        }
+        rows.truncate(height);
+        rows
+    }
+
+    /// Item drawn on a row of the list, None for header rows and rows past the end
+    pub fn item_at_row(group_headers: &[(usize, String)], first_visible_item: usize, total: usize, row: usize) -> Option<usize> {
+        match Self::layout_rows(group_headers, first_visible_item, total, row + 1).get(row) {
+            Some(BrowserRow::Item(index)) => Some(*index),
+            _ => None,
+        }
     }
 
     /// Check if a scrollbar is needed based on content size vs available height
     pub fn needs_scrollbar(&self, height: usize) -> bool {
-        self.total_items() > height
+        self.total_rows() > height
     }
 
     /// Get the width available for content (accounting for scrollbar if needed)
@@ -60,9 +156,9 @@ impl Browser {
         }
     }
 
-    /// Get the number of items that can fit in the viewport
+    /// Get the number of rows that can fit in the viewport, counting the group headers
     pub fn visible_items(&self, height: usize) -> usize {
-        std::cmp::min(height, self.total_items())
+        std::cmp::min(height, self.total_rows())
     }
 
     /// Clamp the selected item to valid bounds
@@ -78,12 +174,15 @@ impl Browser {
     /// Clamp the first visible item to valid scroll bounds
     pub fn clamp_first_visible_item(&mut self, height: usize) {
         let total = self.total_items();
-        if total <= height {
+        if self.total_rows() <= height {
             // All items fit, no scrolling needed
             self.first_visible_item = 0;
         } else {
             // Ensure we don't scroll past the last screenful
-            let max_first_visible = total.saturating_sub(height);
+            let mut max_first_visible = total;
+            while max_first_visible > 0 && self.rows_in(max_first_visible - 1, total) <= height {
+                max_first_visible -= 1;
+            }
             self.first_visible_item = std::cmp::min(self.first_visible_item, max_first_visible);
         }
     }
@@ -97,15 +196,20 @@ impl Browser {
             return;
         }
 
-        // If selected item is above the viewport, scroll up
-        if self.selected_item < self.first_visible_item {
-            self.first_visible_item = self.selected_item;
+        // The margin can't be more than half the viewport, or the selection could never move
+        let margin = self.scroll_margin.min(height.saturating_sub(1) / 2);
+
+        // If selected item is above the viewport or its margin, scroll up
+        if self.selected_item < self.first_visible_item + margin {
+            self.first_visible_item = self.selected_item.saturating_sub(margin);
         }
         
-        // If selected item is below the viewport, scroll down
-        let last_visible_item = self.first_visible_item + height - 1;
-        if self.selected_item > last_visible_item {
-            self.first_visible_item = self.selected_item.saturating_sub(height - 1);
+        // If selected item is below the viewport or its margin, scroll down
+        let last_needed_item = (self.selected_item + margin).min(total - 1);
+        while self.first_visible_item < self.selected_item
+            && self.rows_in(self.first_visible_item, last_needed_item + 1) > height
+        {
+            self.first_visible_item += 1;
         }
 
         self.clamp_first_visible_item(height);
@@ -120,17 +224,26 @@ impl Browser {
 
 
 
+    /// Render a group header row: the label in bold in the help color, padded to the width
+    fn header_row(label: &str, width: usize, theme: &Theme) -> Vec<Cell> {
+        let fg = string_to_color(&theme.help_fg).unwrap_or(Color::Reset);
+        let style = TextStyle {
+            bold: true,
+            ..TextStyle::new()
+        };
+        let mut row = Vec::new();
+        for ch in text::truncate_to_width(label, width).chars() {
+            push_char_cell(&mut row, Cell::new(ch, fg, Color::Reset, style));
+        }
+        row.resize(width, Cell::new(' ', fg, Color::Reset, TextStyle::new()));
+        row
+    }
+
     /// Get the component at the specified index (category or episode)
     fn get_component_at_index(&self, index: usize) -> Option<&dyn Component> {
-        if index < self.categories.len() {
-            Some(&self.categories[index])
-        } else {
-            let episode_index = index - self.categories.len();
-            if episode_index < self.episodes.len() {
-                Some(&self.episodes[episode_index])
-            } else {
-                None
-            }
+        match self.category_at(index) {
+            Some(category) => Some(category),
+            None => self.episode_at(index).map(|episode| episode as &dyn Component),
         }
     }
 }
@@ -158,15 +271,16 @@ impl Component for Browser {
         // Calculate layout
         let needs_scrollbar = self.needs_scrollbar(height);
         let content_width = self.content_width(height);
+        let marker_width = if self.marked_items.is_empty() { 0 } else { 2.min(content_width) };
+        let item_width = content_width - marker_width;
         
-        // Ensure we have valid scroll position
+        // Ensure we have valid scroll position; scrolling only needs the number of items
         let mut browser_copy = Browser {
-            top_left: self.top_left,
-            width: self.width,
-            categories: self.categories.clone(),
-            episodes: self.episodes.clone(),
             selected_item: self.selected_item,
             first_visible_item: self.first_visible_item,
+            scroll_margin: self.scroll_margin,
+            group_headers: self.group_headers.clone(),
+            ..Browser::windowed(self.top_left, self.width, total_items)
         };
         browser_copy.clamp_selected_item();
         browser_copy.clamp_first_visible_item(height);
@@ -174,26 +288,47 @@ impl Component for Browser {
 
         let mut result = Vec::with_capacity(height);
 
+        let rows = Self::layout_rows(&self.group_headers, browser_copy.first_visible_item, total_items, height);
+
         // Render visible items
         for row in 0..height {
-            let item_index = browser_copy.first_visible_item + row;
-            
-            if item_index >= total_items {
-                // No more items, render empty row
-                result.push(vec![]);
-                continue;
-            }
+            let item_index = match rows.get(row) {
+                Some(BrowserRow::Item(index)) => *index,
+                Some(BrowserRow::Header(label)) => {
+                    result.push(Self::header_row(label, content_width, theme));
+                    continue;
+                }
+                None => {
+                    // No more items, render empty row
+                    result.push(vec![]);
+                    continue;
+                }
+            };
 
             // Determine if this item is selected
             let is_item_selected = item_index == browser_copy.selected_item;
 
             // Get and render the component
-            if let Some(component) = browser_copy.get_component_at_index(item_index) {
-                let rendered = component.render(content_width, 1, theme, is_item_selected);
+            if let Some(component) = self.get_component_at_index(item_index) {
+                let rendered = component.render(item_width, 1, theme, is_item_selected);
                 
                 // Take the first row of the rendered component (components should render single rows)
                 if let Some(first_row) = rendered.first() {
-                    let mut row_cells = first_row.clone();
+                    let mut row_cells = Vec::with_capacity(content_width);
+                    if marker_width > 0 {
+                        let marker = if self.marked_items.contains(&item_index) {
+                            theme.mark_indicator.chars().next().unwrap_or(MARKER)
+                        } else {
+                            ' '
+                        };
+                        let style = first_row.first().cloned().unwrap_or_else(|| {
+                            Cell::new(' ', Color::Reset, Color::Reset, TextStyle::new())
+                        });
+                        row_cells.push(Cell::new(marker, style.fg_color, style.bg_color, TextStyle::new()));
+                        row_cells.push(Cell::new(' ', style.fg_color, style.bg_color, TextStyle::new()));
+                        row_cells.truncate(marker_width);
+                    }
+                    row_cells.extend(first_row.iter().cloned());
                     
                     // Ensure the row doesn't exceed content width
                     if row_cells.len() > content_width {
@@ -219,9 +354,9 @@ impl Component for Browser {
         // Add scrollbar if needed
         if needs_scrollbar {
             let scrollbar = Scrollbar::new(
-                total_items,
-                self.visible_items(height),
-                browser_copy.first_visible_item,
+                browser_copy.total_rows(),
+                browser_copy.visible_items(height),
+                browser_copy.rows_before(browser_copy.first_visible_item),
             );
             
             let scrollbar_cells = scrollbar.render(1, height, theme, false);
@@ -1,6 +1,9 @@
-use super::{Cell, Component, TextStyle};
-use crate::theme::Theme;
+use super::{push_char_cell, truncate_cells, Cell, Component, TextStyle};
+use crate::text;
+use crate::theme::{string_to_color, Theme};
+use super::episode::highlight_cell;
 use crossterm::style::Color;
+use std::ops::Range;
 
 /// Type of category being displayed
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -12,13 +15,17 @@ pub enum CategoryType {
 /// Category component that renders series and season information
 ///
 /// This component encapsulates the rendering logic for category entries,
-/// displaying title, episode count, and watched count information.
+/// displaying title, episode count, and watched count or completion information.
 #[derive(Clone)]
 pub struct Category {
     pub title: String,
     pub episode_count: usize,
     pub watched_count: usize,
     pub category_type: CategoryType,
+    /// Character ranges of the title that match the filter
+    pub highlights: Vec<Range<usize>>,
+    /// Episodes not renamed since import, shown as "(3 new)" after the title
+    pub new_count: usize,
 }
 
 impl Category {
@@ -34,6 +41,74 @@ impl Category {
             episode_count,
             watched_count,
             category_type,
+            highlights: Vec::new(),
+            new_count: 0,
+        }
+    }
+
+    /// Highlight the parts of the title that match the filter
+    pub fn with_highlights(mut self, highlights: Vec<Range<usize>>) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    /// Set the number of newly imported episodes
+    pub fn with_new_count(mut self, new_count: usize) -> Self {
+        self.new_count = new_count;
+        self
+    }
+
+    /// Indicator after the title: the new episode count, or the theme's character while
+    /// anything is unwatched, or nothing once everything is watched
+    pub fn indicator_text(&self, theme: &Theme) -> String {
+        if self.new_count > 0 {
+            format!("({} new)", self.new_count)
+        } else if self.watched_count < self.episode_count {
+            theme.category_indicator.clone()
+        } else {
+            String::new()
+        }
+    }
+
+    /// Percentage of episodes watched, rounded down
+    pub fn watched_percent(&self) -> usize {
+        (self.watched_count.min(self.episode_count) * 100)
+            .checked_div(self.episode_count)
+            .unwrap_or(0)
+    }
+
+    /// Filled and empty bar lengths for the theme, both 0 when no bar is shown
+    fn bar_lengths(&self, theme: &Theme) -> (usize, usize) {
+        if !theme.progress_display.eq_ignore_ascii_case("bar") {
+            return (0, 0);
+        }
+        let width = theme.progress_width;
+        let filled = (self.watched_count.min(self.episode_count) * width)
+            .checked_div(self.episode_count)
+            .unwrap_or(0);
+        (filled, width - filled)
+    }
+
+    /// Right-hand count text in the theme's progress display style
+    fn count_text(&self, theme: &Theme) -> String {
+        match theme.progress_display.to_lowercase().as_str() {
+            "bar" => {
+                let (filled, empty) = self.bar_lengths(theme);
+                let filled_char = theme.progress_filled_char.chars().next().unwrap_or('▓');
+                let empty_char = theme.progress_empty_char.chars().next().unwrap_or('░');
+                let bar = format!(
+                    "{}{}",
+                    filled_char.to_string().repeat(filled),
+                    empty_char.to_string().repeat(empty)
+                );
+                if bar.is_empty() {
+                    format!("{}%", self.watched_percent())
+                } else {
+                    format!("{} {}%", bar, self.watched_percent())
+                }
+            }
+            "percent" => format!("{}%", self.watched_percent()),
+            _ => format!("{}/{} watched", self.watched_count, self.episode_count),
         }
     }
 }
@@ -46,24 +121,30 @@ impl Component for Category {
         }
 
         // Use right-justified format with styled count for both selected and unselected
-        // Format: "Title  X/Y watched" where count is right-justified
+        // Format: "Title  X/Y watched" (or a completion bar/percentage) right-justified
         
-        let count_text = format!("{}/{} watched", self.watched_count, self.episode_count);
+        let count_text = self.count_text(theme);
         let count_visual_len = count_text.chars().count();
         
-        // Calculate available space for title (reserve space for count + spacing)
+        // The indicator follows the title after one space
+        let indicator = self.indicator_text(theme);
+        let indicator_len = if indicator.is_empty() { 0 } else { text::display_width(&indicator) + 1 };
+        
+        // Calculate available space for title (reserve space for count + indicator + spacing)
         let min_spacing = 1;
         let available_for_title = width
             .saturating_sub(count_visual_len)
+            .saturating_sub(indicator_len)
             .saturating_sub(min_spacing);
         
         // Truncate title if needed
-        let truncated_title = truncate_string(&self.title, available_for_title);
-        let title_len = truncated_title.chars().count();
+        let truncated_title = text::truncate_to_width(&self.title, available_for_title);
+        let title_len = text::display_width(&truncated_title);
         
         // Calculate actual spacing needed
         let spacing = width
             .saturating_sub(title_len)
+            .saturating_sub(indicator_len)
             .saturating_sub(count_visual_len)
             .max(1); // Ensure at least 1 space
         
@@ -98,9 +179,23 @@ impl Component for Category {
         // Build the cell array
         let mut cells = Vec::new();
         
-        // Add title cells
-        for ch in truncated_title.chars() {
-            cells.push(Cell::new(ch, title_fg, title_bg, title_style));
+        // Add title cells, highlighting filter matches
+        for (index, ch) in truncated_title.chars().enumerate() {
+            let matched = self.highlights.iter().any(|range| range.contains(&index));
+            push_char_cell(&mut cells, highlight_cell(ch, title_fg, title_bg, title_style, matched, is_selected, theme));
+        }
+        
+        // Add the indicator cells (in the indicator color unless selected)
+        if !indicator.is_empty() {
+            let indicator_fg = if is_selected {
+                title_fg
+            } else {
+                string_to_fg_color_or_default(&theme.category_indicator_fg)
+            };
+            cells.push(Cell::new(' ', title_fg, title_bg, title_style));
+            for ch in indicator.chars() {
+                push_char_cell(&mut cells, Cell::new(ch, indicator_fg, title_bg, title_style));
+            }
         }
         
         // Add spacing cells (use title colors)
@@ -108,15 +203,23 @@ impl Component for Category {
             cells.push(Cell::new(' ', title_fg, title_bg, title_style));
         }
         
-        // Add count cells (with count styling)
-        for ch in count_text.chars() {
-            cells.push(Cell::new(ch, count_fg, count_bg, count_style));
+        // Add count cells (with count styling, bar cells with progress colors)
+        let filled_fg = string_to_fg_color_or_default(&theme.progress_fg);
+        let empty_fg = string_to_fg_color_or_default(&theme.progress_empty_fg);
+        let (filled, empty) = self.bar_lengths(theme);
+        for (i, ch) in count_text.chars().enumerate() {
+            let fg = if is_selected || i >= filled + empty {
+                count_fg
+            } else if i < filled {
+                filled_fg
+            } else {
+                empty_fg
+            };
+            cells.push(Cell::new(ch, fg, count_bg, count_style));
         }
         
         // Ensure we don't exceed the width (truncate if necessary)
-        if cells.len() > width {
-            cells.truncate(width);
-        }
+        truncate_cells(&mut cells, width);
         
         // Return as single-row 2D array
         vec![cells]
@@ -136,32 +239,6 @@ fn string_to_bg_color_or_default(color: &str) -> Color {
     string_to_color(color).unwrap_or(Color::Reset)
 }
 
-/// Convert a color string to a Color enum
-fn string_to_color(color: &str) -> Option<Color> {
-    match color.to_lowercase().as_str() {
-        "black" => Some(Color::Black),
-        "red" => Some(Color::Red),
-        "green" => Some(Color::Green),
-        "yellow" => Some(Color::Yellow),
-        "blue" => Some(Color::Blue),
-        "magenta" => Some(Color::Magenta),
-        "cyan" => Some(Color::Cyan),
-        "white" => Some(Color::White),
-        "darkgray" | "dark_gray" => Some(Color::DarkGrey),
-        "reset" => Some(Color::Reset),
-        _ => None,
-    }
-}
-
-/// Truncate a string to fit within a specified width
-fn truncate_string(s: &str, max_width: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_width {
-        s.to_string()
-    } else {
-        s.chars().take(max_width).collect()
-    }
-}
 
 /// Parse a text style string into a TextStyle struct
 fn parse_text_style(style: &str) -> TextStyle {
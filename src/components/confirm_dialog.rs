@@ -0,0 +1,136 @@
+use super::{Cell, Component, TextStyle};
+use crate::theme::{string_to_color, BorderChars, Theme};
+use crossterm::style::Color;
+
+/// Widest the dialog gets, including its borders
+pub const CONFIRM_WINDOW_WIDTH: usize = 50;
+
+/// Bordered modal asking the user to confirm a destructive action
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmDialog {
+    pub title: String,
+    pub message: String,
+    /// True when Yes is highlighted; starts on No so a stray Enter does nothing
+    pub yes_selected: bool,
+}
+
+impl ConfirmDialog {
+    /// Create a new ConfirmDialog component with No selected
+    pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+            yes_selected: false,
+        }
+    }
+
+    /// Move the highlight to the other button
+    pub fn toggle(&mut self) {
+        self.yes_selected = !self.yes_selected;
+    }
+
+    /// Message wrapped to the space inside the borders
+    fn message_lines(&self, window_width: usize) -> Vec<String> {
+        let width = window_width.saturating_sub(4).max(1);
+        let mut lines = Vec::new();
+        let mut current = String::new();
+        for word in self.message.split_whitespace() {
+            if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+        lines
+    }
+
+    /// Rows needed at this width: borders, title, blank, message, blank, buttons
+    pub fn height(&self, window_width: usize) -> usize {
+        self.message_lines(window_width).len() + 6
+    }
+
+    /// Build a padded content row inside the borders from styled pieces of text
+    fn content_row(&self, border: &BorderChars, window_width: usize, pieces: &[(&str, Color, Color, TextStyle)]) -> Vec<Cell> {
+        let content_width = window_width.saturating_sub(4);
+        let mut row = vec![
+            Cell::new(border.vertical, Color::Reset, Color::Black, TextStyle::new()),
+            Cell::new(' ', Color::Reset, Color::Black, TextStyle::new()),
+        ];
+        for (text, fg, bg, style) in pieces {
+            for ch in text.chars() {
+                if row.len() - 2 < content_width {
+                    row.push(Cell::new(ch, *fg, *bg, *style));
+                }
+            }
+        }
+        while row.len() - 2 < content_width {
+            row.push(Cell::new(' ', Color::Reset, Color::Black, TextStyle::new()));
+        }
+        row.push(Cell::new(' ', Color::Reset, Color::Black, TextStyle::new()));
+        row.push(Cell::new(border.vertical, Color::Reset, Color::Black, TextStyle::new()));
+        row
+    }
+
+    /// Build the top or bottom border row
+    fn horizontal_border(&self, border: &BorderChars, window_width: usize, top: bool) -> Vec<Cell> {
+        let (left, right) = if top {
+            (border.top_left, border.top_right)
+        } else {
+            (border.bottom_left, border.bottom_right)
+        };
+        let mut row = vec![Cell::new(left, Color::Reset, Color::Black, TextStyle::new())];
+        for _ in 0..window_width.saturating_sub(2) {
+            row.push(Cell::new(border.horizontal, Color::Reset, Color::Black, TextStyle::new()));
+        }
+        row.push(Cell::new(right, Color::Reset, Color::Black, TextStyle::new()));
+        row
+    }
+}
+
+impl Component for ConfirmDialog {
+    /// Renders the title, the wrapped message, and the Yes/No buttons with the selected one highlighted
+    fn render(&self, width: usize, height: usize, theme: &Theme, _is_selected: bool) -> Vec<Vec<Cell>> {
+        let window_width = width.min(CONFIRM_WINDOW_WIDTH);
+        if window_width < 5 {
+            return vec![];
+        }
+        let mut bold = TextStyle::new();
+        bold.bold = true;
+        let plain = TextStyle::new();
+        let selected_fg = string_to_color(&theme.current_fg).unwrap_or(Color::Black);
+        let selected_bg = string_to_color(&theme.current_bg).unwrap_or(Color::White);
+        let button = |label: &'static str, selected: bool| {
+            if selected {
+                (label, selected_fg, selected_bg, bold)
+            } else {
+                (label, Color::White, Color::Black, plain)
+            }
+        };
+
+        let border = theme.active_border_chars();
+        let mut cells = vec![self.horizontal_border(&border, window_width, true)];
+        cells.push(self.content_row(&border, window_width, &[(&self.title, Color::White, Color::Black, bold)]));
+        cells.push(self.content_row(&border, window_width, &[]));
+        for line in self.message_lines(window_width) {
+            cells.push(self.content_row(&border, window_width, &[(&line, Color::White, Color::Black, plain)]));
+        }
+        cells.push(self.content_row(&border, window_width, &[]));
+        cells.push(self.content_row(
+            &border,
+            window_width,
+            &[
+                button(" Yes ", self.yes_selected),
+                ("  ", Color::Reset, Color::Black, plain),
+                button(" No ", !self.yes_selected),
+            ],
+        ));
+        cells.push(self.horizontal_border(&border, window_width, false));
+        cells.truncate(height);
+        cells
+    }
+}
@@ -1,6 +1,6 @@
 use super::{Cell, Component, TextStyle};
 use crate::menu::MenuItem;
-use crate::theme::Theme;
+use crate::theme::{string_to_color, Theme};
 use crossterm::event::KeyCode;
 use crossterm::style::Color;
 
@@ -22,6 +22,16 @@ impl ContextMenu {
         }
     }
 
+    /// Index of the menu item at a screen position, with the menu drawn at the top-right
+    pub fn item_at(&self, terminal_width: usize, column: usize, row: usize) -> Option<usize> {
+        let (menu_width, _) = self.calculate_menu_dimensions();
+        let start_col = terminal_width.saturating_sub(menu_width);
+        if column < start_col || column >= terminal_width || row == 0 || row > self.menu_items.len() {
+            return None;
+        }
+        Some(row - 1)
+    }
+
     /// Calculate the dimensions needed for the menu based on content
     fn calculate_menu_dimensions(&self) -> (usize, usize) {
         if self.menu_items.is_empty() {
@@ -86,7 +96,8 @@ impl ContextMenu {
         };
 
         // Left border (with black background to make menu opaque)
-        cells.push(Cell::new('║', Color::Reset, Color::Black, TextStyle::new()));
+        let border = theme.active_border_chars();
+        cells.push(Cell::new(border.vertical, Color::Reset, Color::Black, TextStyle::new()));
 
         // Left padding
         cells.push(Cell::new(' ', fg_color, bg_color, TextStyle::new()));
@@ -117,7 +128,7 @@ impl ContextMenu {
         cells.push(Cell::new(' ', fg_color, bg_color, TextStyle::new()));
 
         // Right border (with black background to make menu opaque)
-        cells.push(Cell::new('║', Color::Reset, Color::Black, TextStyle::new()));
+        cells.push(Cell::new(border.vertical, Color::Reset, Color::Black, TextStyle::new()));
 
         cells
     }
@@ -143,13 +154,14 @@ impl Component for ContextMenu {
         let content_width = menu_width.saturating_sub(4); // Subtract borders and padding
 
         // Top border - use saturating arithmetic to prevent underflow (with black background)
+        let border = theme.active_border_chars();
         let mut top_border = Vec::new();
-        top_border.push(Cell::new('╔', Color::Reset, Color::Black, TextStyle::new()));
+        top_border.push(Cell::new(border.top_left, Color::Reset, Color::Black, TextStyle::new()));
         let border_fill_width = menu_width.saturating_sub(2);
         for _ in 0..border_fill_width {
-            top_border.push(Cell::new('═', Color::Reset, Color::Black, TextStyle::new()));
+            top_border.push(Cell::new(border.horizontal, Color::Reset, Color::Black, TextStyle::new()));
         }
-        top_border.push(Cell::new('╗', Color::Reset, Color::Black, TextStyle::new()));
+        top_border.push(Cell::new(border.top_right, Color::Reset, Color::Black, TextStyle::new()));
         result.push(top_border);
 
         // Menu items
@@ -161,11 +173,11 @@ impl Component for ContextMenu {
 
         // Bottom border - use saturating arithmetic to prevent underflow (with black background)
         let mut bottom_border = Vec::new();
-        bottom_border.push(Cell::new('╚', Color::Reset, Color::Black, TextStyle::new()));
+        bottom_border.push(Cell::new(border.bottom_left, Color::Reset, Color::Black, TextStyle::new()));
         for _ in 0..border_fill_width {
-            bottom_border.push(Cell::new('═', Color::Reset, Color::Black, TextStyle::new()));
+            bottom_border.push(Cell::new(border.horizontal, Color::Reset, Color::Black, TextStyle::new()));
         }
-        bottom_border.push(Cell::new('╝', Color::Reset, Color::Black, TextStyle::new()));
+        bottom_border.push(Cell::new(border.bottom_right, Color::Reset, Color::Black, TextStyle::new()));
         result.push(bottom_border);
 
         result
@@ -181,20 +193,3 @@ fn string_to_fg_color_or_default(color: &str) -> Color {
 fn string_to_bg_color_or_default(color: &str) -> Color {
     string_to_color(color).unwrap_or(Color::Reset)
 }
-
-/// Convert a color string to a Color enum value
-fn string_to_color(color: &str) -> Option<Color> {
-    match color.to_lowercase().as_str() {
-        "black" => Some(Color::Black),
-        "red" => Some(Color::Red),
-        "green" => Some(Color::Green),
-        "yellow" => Some(Color::Yellow),
-        "blue" => Some(Color::Blue),
-        "magenta" => Some(Color::Magenta),
-        "cyan" => Some(Color::Cyan),
-        "white" => Some(Color::White),
-        "darkgray" => Some(Color::DarkGrey),
-        "reset" => Some(Color::Reset),
-        _ => None,
-    }
-}
\ No newline at end of file
@@ -16,6 +16,7 @@ pub struct DetailPanel {
     season_number: Option<usize>,
     dirty_fields: HashSet<EpisodeField>,
     entry_location: String,
+    original_details: Option<EpisodeDetail>,
 }
 
 impl DetailPanel {
@@ -37,8 +38,15 @@ impl DetailPanel {
             season_number,
             dirty_fields,
             entry_location,
+            original_details: None,
         }
     }
+
+    /// Details from before editing, so the editor can show what changed
+    pub fn with_original(mut self, original_details: Option<EpisodeDetail>) -> Self {
+        self.original_details = original_details;
+        self
+    }
 }
 
 impl Component for DetailPanel {
@@ -53,7 +61,7 @@ impl Component for DetailPanel {
                 metadata_display.render(width, height, theme, is_selected)
             }
             Mode::Edit => {
-                let episode_editor = EpisodeEditor::new(
+                let mut episode_editor = EpisodeEditor::new(
                     self.episode_details.clone(),
                     self.edit_field,
                     self.edit_cursor_pos,
@@ -61,6 +69,9 @@ impl Component for DetailPanel {
                     self.dirty_fields.clone(),
                     self.entry_location.clone(),
                 );
+                if let Some(original) = &self.original_details {
+                    episode_editor = episode_editor.with_original(original.clone());
+                }
                 episode_editor.render(width, height, theme, is_selected)
             }
             _ => {
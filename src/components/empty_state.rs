@@ -0,0 +1,98 @@
+use super::{Cell, Component, TextStyle};
+use crate::theme::{string_to_color, Theme};
+use crate::util::ViewContext;
+use crossterm::style::Color;
+
+/// Centered message shown in place of an empty list, with the keys that get out of it
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmptyState {
+    pub title: String,
+    /// Suggested actions, one per line below the title
+    pub suggestions: Vec<String>,
+}
+
+impl EmptyState {
+    /// Create a new EmptyState component without suggestions
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Add a suggested action below the title
+    pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestions.push(suggestion.into());
+        self
+    }
+
+    /// Message for a browse view that has nothing to show
+    /// A filter is the most likely reason, then the view itself; pinned filters are mentioned when there are any
+    pub fn for_view(view_context: &ViewContext, filter: &str, has_pinned_filters: bool) -> Self {
+        let state = if !filter.trim().is_empty() {
+            Self::new(format!("Nothing matches \"{}\"", filter.trim()))
+                .with_suggestion("Press / to change the filter")
+                .with_suggestion("Press Esc to clear the filter")
+        } else {
+            match view_context {
+                ViewContext::TopLevel => Self::new("Your library is empty")
+                    .with_suggestion("Press s to rescan for videos")
+                    .with_suggestion("Press F1 for more actions"),
+                ViewContext::Extras => Self::new("No samples or trailers were imported as extras")
+                    .with_suggestion("Press Esc to go back to the library"),
+                ViewContext::Series { series_name, .. } => Self::new(format!("{} has no episodes", series_name))
+                    .with_suggestion("Press Esc to go back")
+                    .with_suggestion("Press s to rescan for videos"),
+                ViewContext::Season { series_name, season_number, .. } => {
+                    Self::new(format!("{} season {} has no episodes", series_name, season_number))
+                        .with_suggestion(format!("Press Esc to go back to {}", series_name))
+                        .with_suggestion("Press s to rescan for videos")
+                }
+            }
+        };
+        if has_pinned_filters {
+            state.with_suggestion("Press 1-3 to switch pinned filters off")
+        } else {
+            state
+        }
+    }
+
+    /// Center a line of text in the width, cutting it when it doesn't fit
+    fn centered_row(text: &str, width: usize, fg: Color, style: TextStyle) -> Vec<Cell> {
+        let text: Vec<char> = text.chars().take(width).collect();
+        let left = (width - text.len()) / 2;
+        let mut row = vec![Cell::new(' ', Color::Reset, Color::Reset, TextStyle::new()); left];
+        row.extend(text.into_iter().map(|ch| Cell::new(ch, fg, Color::Reset, style)));
+        row.resize(width, Cell::new(' ', Color::Reset, Color::Reset, TextStyle::new()));
+        row
+    }
+}
+
+impl Component for EmptyState {
+    /// Renders the title and suggestions centered in the area, with a blank line between them
+    fn render(&self, width: usize, height: usize, theme: &Theme, _is_selected: bool) -> Vec<Vec<Cell>> {
+        if width == 0 || height == 0 {
+            return vec![];
+        }
+        let title_style = TextStyle {
+            bold: true,
+            ..TextStyle::new()
+        };
+        let suggestion_fg = string_to_color(&theme.help_fg).unwrap_or(Color::Reset);
+
+        let mut lines = vec![Self::centered_row(&self.title, width, Color::Reset, title_style)];
+        if !self.suggestions.is_empty() {
+            lines.push(Self::centered_row("", width, Color::Reset, TextStyle::new()));
+        }
+        for suggestion in &self.suggestions {
+            lines.push(Self::centered_row(suggestion, width, suggestion_fg, TextStyle::new()));
+        }
+        lines.truncate(height);
+
+        let top = (height - lines.len()) / 2;
+        let mut rows = vec![Self::centered_row("", width, Color::Reset, TextStyle::new()); top];
+        rows.extend(lines);
+        rows.resize(height, Self::centered_row("", width, Color::Reset, TextStyle::new()));
+        rows
+    }
+}
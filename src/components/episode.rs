@@ -1,6 +1,8 @@
-use super::{Cell, Component, TextStyle};
-use crate::theme::Theme;
+use super::{push_char_cell, Cell, Component, TextStyle};
+use crate::text;
+use crate::theme::{string_to_color, Theme};
 use crossterm::style::Color;
+use std::ops::Range;
 
 /// Episode component that renders episode information
 ///
@@ -12,6 +14,10 @@ pub struct Episode {
     pub is_watched: bool,
     pub file_exists: bool,
     pub is_new: bool,
+    /// The video is on a library folder that isn't mounted, so whether it exists isn't known
+    pub is_offline: bool,
+    /// Character ranges of the name that match the filter
+    pub highlights: Vec<Range<usize>>,
 }
 
 impl Episode {
@@ -22,8 +28,22 @@ impl Episode {
             is_watched,
             file_exists,
             is_new,
+            is_offline: false,
+            highlights: Vec::new(),
         }
     }
+
+    /// Highlight the parts of the name that match the filter
+    pub fn with_highlights(mut self, highlights: Vec<Range<usize>>) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    /// Mark the episode as being on a library folder that isn't mounted
+    pub fn with_offline(mut self, is_offline: bool) -> Self {
+        self.is_offline = is_offline;
+        self
+    }
 }
 
 impl Component for Episode {
@@ -34,7 +54,13 @@ impl Component for Episode {
         }
 
         // Step 1: Determine base state colors (priority order)
-        let (base_fg, base_bg) = if !self.file_exists {
+        let (base_fg, base_bg) = if self.is_offline {
+            // Offline (drive not mounted) - shown apart from missing files
+            (
+                string_to_fg_color_or_default(&theme.offline_fg),
+                string_to_bg_color_or_default(&theme.offline_bg),
+            )
+        } else if !self.file_exists {
             // Invalid (file doesn't exist) - highest priority
             (
                 string_to_fg_color_or_default(&theme.invalid_fg),
@@ -58,7 +84,7 @@ impl Component for Episode {
         let formatted_name = format_episode_with_indicator(&self.name, self.is_watched, theme);
 
         // Step 3: Truncate to width
-        let truncated_name = truncate_string(&formatted_name, width);
+        let truncated_name = text::truncate_to_width(&formatted_name, width);
 
         // Step 4: Apply selection override if needed
         let (final_fg, final_bg) = if is_selected {
@@ -77,11 +103,14 @@ impl Component for Episode {
             parse_text_style(&theme.unwatched_style)
         };
 
-        // Step 6: Convert to Cell array
-        let cells: Vec<Cell> = truncated_name
-            .chars()
-            .map(|ch| Cell::new(ch, final_fg, final_bg, text_style))
-            .collect();
+        // Step 6: Convert to Cell array, highlighting filter matches after the indicator
+        let name_start = formatted_name.chars().count() - self.name.chars().count();
+        let mut cells: Vec<Cell> = Vec::new();
+        for (index, ch) in truncated_name.chars().enumerate() {
+            let matched = index >= name_start
+                && self.highlights.iter().any(|range| range.contains(&(index - name_start)));
+            push_char_cell(&mut cells, highlight_cell(ch, final_fg, final_bg, text_style, matched, is_selected, theme));
+        }
 
         // Return as single-row 2D array
         vec![cells]
@@ -98,20 +127,31 @@ fn string_to_bg_color_or_default(color: &str) -> Color {
     string_to_color(color).unwrap_or(Color::Reset)
 }
 
-/// Convert a color string to a Color enum
-fn string_to_color(color: &str) -> Option<Color> {
-    match color.to_lowercase().as_str() {
-        "black" => Some(Color::Black),
-        "red" => Some(Color::Red),
-        "green" => Some(Color::Green),
-        "yellow" => Some(Color::Yellow),
-        "blue" => Some(Color::Blue),
-        "magenta" => Some(Color::Magenta),
-        "cyan" => Some(Color::Cyan),
-        "white" => Some(Color::White),
-        "darkgray" | "dark_gray" => Some(Color::DarkGrey),
-        "reset" => Some(Color::Reset),
-        _ => None,
+
+/// Build one cell of a list row, in the match colors when it is part of a filter match
+/// The selected row keeps its colors and underlines the match instead
+pub(crate) fn highlight_cell(
+    ch: char,
+    fg: Color,
+    bg: Color,
+    style: TextStyle,
+    matched: bool,
+    is_selected: bool,
+    theme: &Theme,
+) -> Cell {
+    if !matched {
+        Cell::new(ch, fg, bg, style)
+    } else if is_selected {
+        let mut underlined = style;
+        underlined.underlined = true;
+        Cell::new(ch, fg, bg, underlined)
+    } else {
+        Cell::new(
+            ch,
+            string_to_fg_color_or_default(&theme.match_fg),
+            string_to_bg_color_or_default(&theme.match_bg),
+            style,
+        )
     }
 }
 
@@ -159,13 +199,3 @@ fn format_episode_with_indicator(name: &str, is_watched: bool, theme: &Theme) ->
         }
     }
 }
-
-/// Truncate a string to fit within a specified width
-fn truncate_string(s: &str, max_width: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_width {
-        s.to_string()
-    } else {
-        s.chars().take(max_width).collect()
-    }
-}
@@ -1,26 +1,10 @@
-use super::{Cell, Component, TextStyle};
+use super::{push_char_cell, truncate_cells, Cell, Component, TextStyle};
 use crate::dto::EpisodeDetail;
 use crate::episode_field::EpisodeField;
-use crate::theme::Theme;
+use crate::theme::{string_to_color, Theme};
 use crossterm::style::Color;
 use std::collections::HashSet;
 
-/// Convert a color string to a Color, with default fallback
-fn string_to_color(color: &str) -> Option<Color> {
-    match color.to_lowercase().as_str() {
-        "black" => Some(Color::Black),
-        "red" => Some(Color::Red),
-        "green" => Some(Color::Green),
-        "yellow" => Some(Color::Yellow),
-        "blue" => Some(Color::Blue),
-        "magenta" => Some(Color::Magenta),
-        "cyan" => Some(Color::Cyan),
-        "white" => Some(Color::White),
-        "darkgray" | "dark_gray" => Some(Color::DarkGrey),
-        "reset" => Some(Color::Reset),
-        _ => None,
-    }
-}
 
 /// Convert a color string to a foreground Color, with default fallback
 fn string_to_fg_color_or_default(color: &str) -> Color {
@@ -40,6 +24,8 @@ pub struct EpisodeEditor {
     season_number: Option<usize>,
     dirty_fields: HashSet<EpisodeField>,
     entry_location: String,
+    /// Details from before editing, shown dimmed after each changed field
+    original_details: Option<EpisodeDetail>,
 }
 
 impl EpisodeEditor {
@@ -59,7 +45,27 @@ impl EpisodeEditor {
             season_number,
             dirty_fields,
             entry_location,
+            original_details: None,
+        }
+    }
+
+    /// Show the value each changed field had before editing
+    pub fn with_original(mut self, original_details: EpisodeDetail) -> Self {
+        self.original_details = Some(original_details);
+        self
+    }
+
+    /// Dimmed note after a changed field with its value from before editing
+    fn original_value_note(&self, field: EpisodeField) -> Option<String> {
+        if !self.is_field_dirty(field) {
+            return None;
         }
+        let original = field.get_field_value(self.original_details.as_ref()?);
+        Some(if original.is_empty() {
+            "  (was empty)".to_string()
+        } else {
+            format!("  (was {})", original)
+        })
     }
 
     /// Format a field line with highlighting for editing
@@ -133,7 +139,7 @@ impl EpisodeEditor {
                                 // Check if this character is part of the value (not trailing spaces)
                                 let chars_after_colon: String = line.chars().skip(field_name_end + 1).collect();
                                 let trimmed_value = chars_after_colon.trim_start();
-                                let value_end_pos = value_start_pos + trimmed_value.trim_end().len();
+                                let value_end_pos = value_start_pos + trimmed_value.trim_end().chars().count();
                                 
                                 if char_index < value_end_pos {
                                     cell_bg = string_to_fg_color_or_default(&theme.episode_fg);
@@ -155,7 +161,19 @@ impl EpisodeEditor {
                 }
             }
             
-            cells.push(Cell::new(ch, cell_fg, cell_bg, TextStyle::new()));
+            push_char_cell(&mut cells, Cell::new(ch, cell_fg, cell_bg, TextStyle::new()));
+        }
+
+        // The value before editing follows a changed field
+        if let Some(note) = self.original_value_note(field) {
+            let note_style = TextStyle {
+                dim: true,
+                ..TextStyle::new()
+            };
+            let note_fg = string_to_fg_color_or_default(&theme.episode_fg);
+            for ch in note.chars() {
+                push_char_cell(&mut cells, Cell::new(ch, note_fg, Color::Reset, note_style));
+            }
         }
         
         cells
@@ -178,7 +196,7 @@ impl EpisodeEditor {
         
         // Clamp cursor position to valid range within the field value
         let value_start = label_end;
-        let value_end = field_line.len();
+        let value_end = field_line.chars().count();
         let max_cursor_pos = value_end.saturating_sub(value_start);
         
         let clamped_cursor = self.edit_cursor_pos.min(max_cursor_pos);
@@ -189,7 +207,9 @@ impl EpisodeEditor {
     fn extract_path_and_filename(&self) -> (String, String) {
         use std::path::Path;
         
-        let path = Path::new(&self.entry_location);
+        // Bytes that aren't valid UTF-8 are shown as replacement characters
+        let shown_location = crate::location::display(&self.entry_location);
+        let path = Path::new(&shown_location);
         let filename = path.file_name()
             .and_then(|name| name.to_str())
             .unwrap_or("")
@@ -230,9 +250,7 @@ impl Component for EpisodeEditor {
             let mut row = self.format_field_line_with_highlighting(field, theme);
             
             // Truncate if necessary
-            if row.len() > width {
-                row.truncate(width);
-            }
+            truncate_cells(&mut row, width);
             
             // Pad row to width if needed
             while row.len() < width {
@@ -255,4 +273,4 @@ impl Component for EpisodeEditor {
         
         result
     }
-}
\ No newline at end of file
+}
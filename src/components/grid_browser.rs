@@ -0,0 +1,209 @@
+use super::{Browser, Cell, Component, TextStyle, CategoryType};
+use crate::theme::{string_to_color, Theme};
+use crossterm::style::Color;
+use std::collections::HashSet;
+
+/// Columns taken by one tile, including the gap to its right neighbour
+pub const TILE_WIDTH: usize = 15;
+
+/// Rows taken by one tile: the name, the watched fraction and a blank gap row
+pub const TILE_HEIGHT: usize = 3;
+
+/// Length of the watched bar on series and season tiles
+const TILE_BAR_WIDTH: usize = 5;
+
+/// Badge shown in the corner of tiles that have poster artwork
+const ARTWORK_BADGE: char = '▣';
+
+/// Grid layout of the browse list: every entry is a fixed-width tile with its name and watched fraction
+///
+/// The selection, scroll position and marks are the wrapped `Browser`'s, so switching between the
+/// list and the grid keeps the same entry selected. `first_visible_item` is always the first tile of a row.
+pub struct GridBrowser {
+    pub browser: Browser,
+    /// Indices of items that have poster artwork, shown with a badge
+    pub artwork_items: HashSet<usize>,
+}
+
+impl GridBrowser {
+    /// Create a new GridBrowser laying out the items of `browser`
+    pub fn new(browser: Browser) -> Self {
+        Self {
+            browser,
+            artwork_items: HashSet::new(),
+        }
+    }
+
+    /// Number of tiles in a row for the given width, at least one
+    pub fn columns_for(width: usize) -> usize {
+        (width / TILE_WIDTH).max(1)
+    }
+
+    /// Number of tile rows that fit in the given height, at least one
+    pub fn rows_for(height: usize) -> usize {
+        (height / TILE_HEIGHT).max(1)
+    }
+
+    /// Number of tiles shown at once, used for PageUp/PageDown
+    pub fn page_size(width: usize, height: usize) -> usize {
+        Self::columns_for(width) * Self::rows_for(height)
+    }
+
+    /// Item under a cell, given relative to the top-left of the grid
+    pub fn item_at(width: usize, column: usize, row: usize, first_visible_item: usize, total: usize) -> Option<usize> {
+        let columns = Self::columns_for(width);
+        let tile_column = column / TILE_WIDTH;
+        // The gap column and row belong to no tile
+        if tile_column >= columns || column % TILE_WIDTH == TILE_WIDTH - 1 || row % TILE_HEIGHT == TILE_HEIGHT - 1 {
+            return None;
+        }
+        let index = first_visible_item + (row / TILE_HEIGHT) * columns + tile_column;
+        (index < total).then_some(index)
+    }
+
+    /// Scroll by whole rows so the selected tile is visible
+    pub fn ensure_selection_visible(&mut self, height: usize) {
+        let browser = &mut self.browser;
+        browser.clamp_selected_item();
+        let columns = Self::columns_for(browser.width);
+        let rows = Self::rows_for(height);
+        let total_rows = browser.total_items().div_ceil(columns);
+
+        let selected_row = browser.selected_item / columns;
+        let mut first_row = browser.first_visible_item / columns;
+        if selected_row < first_row {
+            first_row = selected_row;
+        } else if selected_row >= first_row + rows {
+            first_row = selected_row + 1 - rows;
+        }
+        first_row = first_row.min(total_rows.saturating_sub(rows));
+        browser.first_visible_item = first_row * columns;
+    }
+
+    /// Name and second line of the tile for an item, with the colors it is drawn in
+    fn tile_text(&self, index: usize, inner_width: usize, theme: &Theme) -> (String, String, Color, Color) {
+        if let Some(category) = self.browser.category_at(index) {
+            let (fg, bg) = match category.category_type {
+                CategoryType::Series => (&theme.series_fg, &theme.series_bg),
+                CategoryType::Season => (&theme.season_fg, &theme.season_bg),
+            };
+            let filled = (category.watched_count.min(category.episode_count) * TILE_BAR_WIDTH)
+                .checked_div(category.episode_count)
+                .unwrap_or(0);
+            let filled_char = theme.progress_filled_char.chars().next().unwrap_or('▓');
+            let empty_char = theme.progress_empty_char.chars().next().unwrap_or('░');
+            let fraction = format!("{}/{}", category.watched_count, category.episode_count);
+            let bar_width = if fraction.chars().count() + 1 + TILE_BAR_WIDTH <= inner_width { TILE_BAR_WIDTH } else { 0 };
+            let filled = filled.min(bar_width);
+            let progress = format!(
+                "{}{}{}{}",
+                filled_char.to_string().repeat(filled),
+                empty_char.to_string().repeat(bar_width - filled),
+                if bar_width > 0 { " " } else { "" },
+                fraction
+            );
+            return (
+                category.title.clone(),
+                progress,
+                string_to_color(fg).unwrap_or(Color::Reset),
+                string_to_color(bg).unwrap_or(Color::Reset),
+            );
+        }
+        let Some(episode) = self.browser.episode_at(index) else {
+            return (String::new(), String::new(), Color::Reset, Color::Reset);
+        };
+        let (fg, bg) = if episode.is_offline {
+            (&theme.offline_fg, &theme.offline_bg)
+        } else if !episode.file_exists {
+            (&theme.invalid_fg, &theme.invalid_bg)
+        } else if episode.is_new {
+            (&theme.new_fg, &theme.new_bg)
+        } else {
+            (&theme.episode_fg, &theme.episode_bg)
+        };
+        let status = if episode.is_watched {
+            format!("{} watched", theme.watched_indicator)
+        } else {
+            format!("{} unwatched", theme.unwatched_indicator)
+        };
+        (
+            episode.name.clone(),
+            status,
+            string_to_color(fg).unwrap_or(Color::Reset),
+            string_to_color(bg).unwrap_or(Color::Reset),
+        )
+    }
+
+    /// Render one tile as its name row and progress row, without the gaps
+    fn render_tile(&self, index: usize, theme: &Theme) -> [Vec<Cell>; 2] {
+        let inner_width = TILE_WIDTH - 1;
+        let (name, progress, fg, bg) = self.tile_text(index, inner_width, theme);
+        let (fg, bg) = if index == self.browser.selected_item {
+            (
+                string_to_color(&theme.current_fg).unwrap_or(Color::Black),
+                string_to_color(&theme.current_bg).unwrap_or(Color::White),
+            )
+        } else {
+            (fg, bg)
+        };
+
+        let marker = if self.browser.marked_items.contains(&index) {
+            format!("{} ", theme.mark_indicator.chars().next().unwrap_or('*'))
+        } else {
+            String::new()
+        };
+        let mut name_row: Vec<char> = marker.chars().chain(name.chars()).collect();
+        if name_row.len() > inner_width {
+            name_row.truncate(inner_width);
+            name_row[inner_width - 1] = '…';
+        }
+        name_row.resize(inner_width, ' ');
+
+        let mut progress_row: Vec<char> = progress.chars().take(inner_width).collect();
+        progress_row.resize(inner_width, ' ');
+        if self.artwork_items.contains(&index) {
+            progress_row[inner_width - 1] = ARTWORK_BADGE;
+        }
+
+        let to_cells = |chars: Vec<char>| chars.into_iter().map(|ch| Cell::new(ch, fg, bg, TextStyle::new())).collect();
+        [to_cells(name_row), to_cells(progress_row)]
+    }
+}
+
+impl Component for GridBrowser {
+    /// Renders the visible rows of tiles, starting at the browser's first visible item
+    fn render(&self, _width: usize, height: usize, theme: &Theme, _is_selected: bool) -> Vec<Vec<Cell>> {
+        let width = self.browser.width;
+        if width == 0 || height == 0 {
+            return vec![];
+        }
+        let total = self.browser.total_items();
+        if total == 0 {
+            return vec![vec![]; height];
+        }
+
+        let columns = Self::columns_for(width);
+        let blank = Cell::new(' ', Color::Reset, Color::Reset, TextStyle::new());
+        let mut result = vec![vec![blank.clone(); width]; height];
+        for tile_row in 0..Self::rows_for(height) {
+            for tile_column in 0..columns {
+                let index = self.browser.first_visible_item + tile_row * columns + tile_column;
+                if index >= total {
+                    break;
+                }
+                let left = tile_column * TILE_WIDTH;
+                for (offset, row_cells) in self.render_tile(index, theme).into_iter().enumerate() {
+                    let Some(row) = result.get_mut(tile_row * TILE_HEIGHT + offset) else {
+                        continue;
+                    };
+                    for (column, cell) in row_cells.into_iter().enumerate() {
+                        if let Some(target) = row.get_mut(left + column) {
+                            *target = cell;
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+}
@@ -1,7 +1,8 @@
 use crate::dto::EpisodeDetail;
 use crate::menu::{MenuContext, get_first_line_preferred_items, calculate_menu_helper_width};
-use crate::util::{Entry, LastAction, Mode, ViewContext};
-use crate::components::{Component, Cell, TextStyle};
+use crate::util::{Entry, LastAction, Mode, SortMode, ViewContext};
+use crate::components::{push_char_cell, truncate_cells, Component, Cell, TextStyle};
+use crate::text;
 use crate::theme::Theme;
 
 use crossterm::event::KeyCode;
@@ -21,6 +22,16 @@ pub struct HeaderContext {
     pub view_context: ViewContext,
     pub filter_text: String,
     pub filter_focused: bool,
+    pub pinned_filters: String,
+    /// Views pinned to tabs, as listed by `Tabs::label`
+    pub tabs: String,
+    /// Count or entry number typed before a Browse key, as shown by `Count::label`
+    pub count: String,
+    pub sort_mode: SortMode,
+    /// Entries shown and entries in total, while a filter or pinned filter hides some
+    pub entry_counts: Option<(usize, usize)>,
+    /// Episodes and unwatched episodes of the series or season being browsed
+    pub view_counts: Option<(usize, usize)>,
 }
 
 impl HeaderContext {
@@ -37,6 +48,12 @@ impl HeaderContext {
         view_context: ViewContext,
         filter_text: String,
         filter_focused: bool,
+        pinned_filters: String,
+        tabs: String,
+        count: String,
+        sort_mode: SortMode,
+        entry_counts: Option<(usize, usize)>,
+        view_counts: Option<(usize, usize)>,
     ) -> Self {
         Self {
             mode,
@@ -50,6 +67,12 @@ impl HeaderContext {
             view_context,
             filter_text,
             filter_focused,
+            pinned_filters,
+            tabs,
+            count,
+            sort_mode,
+            entry_counts,
+            view_counts,
         }
     }
 }
@@ -104,19 +127,19 @@ impl HotkeyHelper {
                     // Determine context based on view_context
                     match &self.view_context {
                         ViewContext::TopLevel => {
-                            "[/] filter, [\u{2191}]/[\u{2193}] navigate, [ENTER] play, [ESC] exit".to_string()
+                            "[/] filter, [\u{2191}]/[\u{2193}] navigate, [ENTER] play, [?] help, [ESC] exit".to_string()
                         }
                         ViewContext::Series { .. } => {
-                            "[/] filter, [\u{2191}]/[\u{2193}] navigate, [ENTER] show episodes, [ESC] exit".to_string()
+                            "[/] filter, [\u{2191}]/[\u{2193}] navigate, [ENTER] show episodes, [?] help, [ESC] exit".to_string()
                         }
-                        ViewContext::Season { .. } => {
-                            "[/] filter, [\u{2191}]/[\u{2193}] navigate, [ENTER] play, [ESC] back".to_string()
+                        ViewContext::Season { .. } | ViewContext::Extras => {
+                            "[/] filter, [\u{2191}]/[\u{2193}] navigate, [ENTER] play, [?] help, [ESC] back".to_string()
                         }
                     }
                 }
             }
             Mode::Edit => {
-                let mut instruction = "[\u{2191}]/[\u{2193}] change field, [ESC] cancel".to_string();
+                let mut instruction = "[\u{2191}]/[\u{2193}] change field, [F3] rename file, [ESC] cancel".to_string();
                 if self.is_dirty {
                     instruction.push_str(", [F2] save");
                 }
@@ -125,15 +148,19 @@ impl HotkeyHelper {
             Mode::Entry => {
                 // Check if we're in first-run state (no entries and no database)
                 if self.is_first_run {
-                    "Welcome! Enter the path to your video collection directory, [ESC] cancel".to_string()
+                    "Welcome! Enter the path to your video collection directory, [TAB] complete, [ESC] cancel".to_string()
                 } else {
-                    "Enter a file path to scan, [ESC] cancel".to_string()
+                    "Enter a folder of the library to scan, [TAB] complete, [ENTER] scan, [ESC] cancel".to_string()
                 }
             }
             Mode::SeriesSelect => {
                 "[\u{2191}]/[\u{2193}] navigate, [ENTER] select, [ESC] cancel, [+] create a new series, [CTRL][-] deselect series".to_string()
             }
             Mode::SeriesCreate => "Type a series name, [ENTER] save, [ESC] cancel".to_string(),
+            Mode::NotesEdit => "Type your notes, [ENTER] save, [ESC] cancel".to_string(),
+            Mode::AltTitlesEdit => {
+                "[\u{2191}]/[\u{2193}] select row, [ENTER] save, [ESC] cancel".to_string()
+            }
             Mode::Menu => {
                 "[\u{2191}]/[\u{2193}] navigate, [ENTER] select, [ESC] close menu".to_string()
             }
@@ -143,6 +170,42 @@ impl HotkeyHelper {
             Mode::TorrentSearchResults => {
                 "[\u{2191}]/[\u{2193}]: Navigate | Enter: Download | ESC: Cancel".to_string()
             }
+            Mode::Digest => {
+                "[\u{2191}]/[\u{2193}] navigate, [ENTER] jump to item, [ESC] close".to_string()
+            }
+            Mode::Help => {
+                "[\u{2191}]/[\u{2193}] scroll, [ESC] close".to_string()
+            }
+            Mode::Confirm => {
+                "[\u{2190}]/[\u{2192}] choose, [ENTER] answer, [Y] yes, [N]/[ESC] no".to_string()
+            }
+            Mode::Notifications => {
+                "[\u{2191}]/[\u{2193}] scroll, [ESC] close".to_string()
+            }
+            Mode::ArchiveReview => {
+                "[\u{2191}]/[\u{2193}] navigate, [ENTER] extract, [S] skip, [ESC] close".to_string()
+            }
+            Mode::Relocate => {
+                "Type the new library folder, [ENTER] check and move, [ESC] cancel".to_string()
+            }
+            Mode::AddFromUrl => {
+                "Type or paste the video's URL, [ENTER] download, [ESC] cancel".to_string()
+            }
+            Mode::DuplicateReview => {
+                "[\u{2191}]/[\u{2193}] navigate, [SPACE] keep or trash, [ENTER] trash the marked copies, [ESC] cancel".to_string()
+            }
+            Mode::RenameFile => {
+                "[ENTER] rename the file, [ESC] cancel".to_string()
+            }
+            Mode::ScanReport | Mode::ConfigReport => {
+                "[\u{2191}]/[\u{2193}] scroll, [ESC] close".to_string()
+            }
+            Mode::VersionSelect => {
+                "[\u{2191}]/[\u{2193}] navigate, [ENTER] play, [ESC] cancel".to_string()
+            }
+            Mode::ThemeSelect => {
+                "[\u{2191}]/[\u{2193}] preview, [ENTER] keep, [ESC] cancel".to_string()
+            }
         }
     }
 
@@ -158,6 +221,8 @@ impl HotkeyHelper {
             episode_detail: self.edit_details.clone(),
             mode: self.mode.clone(),
             last_action: self.last_action.clone(),
+            marked_count: 0,
+            extras_view: self.view_context == ViewContext::Extras,
         };
 
         let first_line_preferred = get_first_line_preferred_items(&menu_context);
@@ -225,7 +290,7 @@ impl HotkeyHelper {
         }
         
         // Calculate visual width (accounting for multi-byte UTF-8 characters)
-        let visual_width = header.chars().count();
+        let visual_width = text::display_width(&header);
         
         // Pad to terminal width based on visual width, not byte length
         let padding_needed = self.terminal_width.saturating_sub(visual_width);
@@ -278,27 +343,39 @@ impl LastActionLine {
 /// Component that displays navigation context showing current location
 pub struct Breadcrumbs {
     view_context: ViewContext,
+    view_counts: Option<(usize, usize)>,
 }
 
 impl Breadcrumbs {
-    /// Creates a new Breadcrumbs component
-    pub fn new(view_context: ViewContext) -> Self {
-        Self { view_context }
+    /// Creates a new Breadcrumbs component, with the episode and unwatched counts of the view when known
+    pub fn new(view_context: ViewContext, view_counts: Option<(usize, usize)>) -> Self {
+        Self { view_context, view_counts }
     }
 
     /// Formats the breadcrumb display based on current view context
     fn format_breadcrumb(&self) -> String {
-        match &self.view_context {
+        let (path, name) = match &self.view_context {
             ViewContext::TopLevel => {
                 // No breadcrumbs at top level (matches original implementation)
-                String::new()
+                return String::new();
             }
             ViewContext::Series { series_name, .. } => {
-                format!("Browsing [{}]", series_name)
+                (format!("Browsing [{}]", series_name), series_name.clone())
             }
+            ViewContext::Extras => ("Browsing [extras]".to_string(), "Extras".to_string()),
             ViewContext::Season { series_name, season_number, .. } => {
-                format!("Browsing [{}] -> [season {}]", series_name, season_number)
+                (
+                    format!("Browsing [{}] -> [season {}]", series_name, season_number),
+                    format!("Season {}", season_number),
+                )
+            }
+        };
+        match self.view_counts {
+            Some((total, unwatched)) => {
+                let noun = if total == 1 { "episode" } else { "episodes" };
+                format!("{}    {} \u{2014} {} {}, {} unwatched", path, name, total, noun, unwatched)
             }
+            None => path,
         }
     }
 
@@ -312,26 +389,66 @@ impl Breadcrumbs {
 pub struct FilterLine {
     filter_text: String,
     filter_focused: bool,
+    pinned_filters: String,
+    tabs: String,
+    count: String,
+    sort_mode: SortMode,
+    entry_counts: Option<(usize, usize)>,
 }
 
 impl FilterLine {
     /// Creates a new FilterLine component
-    pub fn new(filter_text: String, filter_focused: bool) -> Self {
+    pub fn new(
+        filter_text: String,
+        filter_focused: bool,
+        pinned_filters: String,
+        tabs: String,
+        count: String,
+        sort_mode: SortMode,
+        entry_counts: Option<(usize, usize)>,
+    ) -> Self {
         Self {
             filter_text,
             filter_focused,
+            pinned_filters,
+            tabs,
+            count,
+            sort_mode,
+            entry_counts,
         }
     }
 
     /// Formats the filter display with highlighting for active state
     fn format_filter_display(&self) -> String {
-        // Show filter line when filter_mode is true OR filter string is not empty
-        if !self.filter_focused && self.filter_text.is_empty() {
-            return String::new();
+        let mut parts = Vec::new();
+
+        // Tabs come first so their numbers stay in the same place
+        if !self.tabs.is_empty() {
+            parts.push(format!("tabs: {}", self.tabs));
         }
 
+        // Show filter line when filter_mode is true OR filter string is not empty
         // Format as "filter: {text}" to match original implementation
-        format!("filter: {}", self.filter_text)
+        if self.filter_focused || !self.filter_text.is_empty() {
+            parts.push(format!("filter: {}", self.filter_text));
+        }
+
+        // Pinned filters and a non-default sort stay visible even when no filter is typed
+        if !self.pinned_filters.is_empty() {
+            parts.push(format!("pinned: {}", self.pinned_filters));
+        }
+        if self.sort_mode != SortMode::Default {
+            parts.push(format!("sort: {}", self.sort_mode.label()));
+        }
+        if let Some((shown, total)) = self.entry_counts {
+            parts.push(format!("{} / {} entries", shown, total));
+        }
+        // A count is shown as it is typed, since it does nothing until the key it is for
+        if !self.count.is_empty() {
+            parts.push(self.count.clone());
+        }
+
+        parts.join("    ")
     }
 
     /// Renders the filter line, returning formatted string or empty if no filter active
@@ -368,8 +485,16 @@ impl Header {
             context.selected_entry.clone(),
             context.edit_details.clone(),
         );
-        let breadcrumbs = Breadcrumbs::new(context.view_context.clone());
-        let filter_line = FilterLine::new(context.filter_text.clone(), context.filter_focused);
+        let breadcrumbs = Breadcrumbs::new(context.view_context.clone(), context.view_counts);
+        let filter_line = FilterLine::new(
+            context.filter_text.clone(),
+            context.filter_focused,
+            context.pinned_filters.clone(),
+            context.tabs.clone(),
+            context.count.clone(),
+            context.sort_mode,
+            context.entry_counts,
+        );
 
         Self {
             hotkey_helper,
@@ -386,9 +511,11 @@ impl Header {
 
     /// Converts a string to a vector of Cells with the given colors and style
     fn string_to_cells(&self, text: &str, fg_color: Color, bg_color: Color, style: TextStyle) -> Vec<Cell> {
-        text.chars()
-            .map(|c| Cell::new(c, fg_color, bg_color, style))
-            .collect()
+        let mut cells = Vec::new();
+        for c in text.chars() {
+            push_char_cell(&mut cells, Cell::new(c, fg_color, bg_color, style));
+        }
+        cells
     }
 }
 
@@ -407,7 +534,7 @@ impl Component for Header {
         // Row 0: HotkeyHelper (always present) with white background
         let hotkey_text = self.hotkey_helper.render();
         let mut cells = self.string_to_cells(&hotkey_text, header_fg, header_bg, header_style);
-        cells.truncate(width);
+        truncate_cells(&mut cells, width);
         while cells.len() < width {
             cells.push(Cell::new(' ', header_fg, header_bg, header_style));
         }
@@ -416,7 +543,7 @@ impl Component for Header {
         // Row 1: LastActionLine (always allocated, may be empty) with normal colors
         let last_action_text = self.last_action_line.render();
         let mut cells = self.string_to_cells(&last_action_text, normal_fg, normal_bg, header_style);
-        cells.truncate(width);
+        truncate_cells(&mut cells, width);
         while cells.len() < width {
             cells.push(Cell::new(' ', normal_fg, normal_bg, header_style));
         }
@@ -425,7 +552,7 @@ impl Component for Header {
         // Row 2: FilterLine (always allocated, may be empty) with normal colors
         let filter_text = self.filter_line.render();
         let mut cells = self.string_to_cells(&filter_text, normal_fg, normal_bg, header_style);
-        cells.truncate(width);
+        truncate_cells(&mut cells, width);
         while cells.len() < width {
             cells.push(Cell::new(' ', normal_fg, normal_bg, header_style));
         }
@@ -434,7 +561,7 @@ impl Component for Header {
         // Row 3: Breadcrumbs (always allocated, may be empty) with normal colors
         let breadcrumb_text = self.breadcrumbs.render();
         let mut cells = self.string_to_cells(&breadcrumb_text, normal_fg, normal_bg, header_style);
-        cells.truncate(width);
+        truncate_cells(&mut cells, width);
         while cells.len() < width {
             cells.push(Cell::new(' ', normal_fg, normal_bg, header_style));
         }
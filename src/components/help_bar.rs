@@ -0,0 +1,63 @@
+use super::{Cell, Component, TextStyle};
+use crate::keybindings::KeyBinding;
+use crate::theme::{string_to_color, Theme};
+use crossterm::style::Color;
+
+/// Space between two key bindings on the bar
+const SEPARATOR: &str = "  ";
+
+/// One-line footer listing the keys that matter right now, drawn above the status bar
+/// Bindings that don't fit in the width are left off rather than cut in half
+pub struct HelpBar {
+    pub bindings: Vec<KeyBinding>,
+}
+
+impl HelpBar {
+    /// Create a new HelpBar component, listing the bindings in order
+    pub fn new(bindings: Vec<KeyBinding>) -> Self {
+        Self { bindings }
+    }
+
+    /// Pieces of the bar and whether each is a key, stopping at the first binding that doesn't fit
+    fn render_pieces(&self, width: usize) -> Vec<(String, bool)> {
+        let mut pieces = Vec::new();
+        let mut used = 0;
+        for binding in &self.bindings {
+            let separator = if pieces.is_empty() { "" } else { SEPARATOR };
+            let length = separator.chars().count() + binding.keys.chars().count() + 1 + binding.description.chars().count();
+            if used + length > width {
+                break;
+            }
+            used += length;
+            pieces.push((separator.to_string(), false));
+            pieces.push((binding.keys.clone(), true));
+            pieces.push((format!(" {}", binding.description), false));
+        }
+        pieces
+    }
+}
+
+impl Component for HelpBar {
+    /// Renders the keys in bold and their descriptions in the help color, padded to the width
+    fn render(&self, width: usize, height: usize, theme: &Theme, _is_selected: bool) -> Vec<Vec<Cell>> {
+        if width == 0 || height == 0 {
+            return vec![];
+        }
+        let fg = string_to_color(&theme.help_fg).unwrap_or(Color::Reset);
+        let key_style = TextStyle {
+            bold: true,
+            ..TextStyle::new()
+        };
+
+        let mut row: Vec<Cell> = self
+            .render_pieces(width)
+            .into_iter()
+            .flat_map(|(text, is_key)| {
+                let style = if is_key { key_style } else { TextStyle::new() };
+                text.chars().map(move |ch| Cell::new(ch, fg, Color::Reset, style)).collect::<Vec<_>>()
+            })
+            .collect();
+        row.resize(width, Cell::new(' ', fg, Color::Reset, TextStyle::new()));
+        vec![row]
+    }
+}
@@ -1,25 +1,10 @@
-use super::{Cell, Component, TextStyle};
+use super::{push_char_cell, Cell, Component, TextStyle};
+use crate::text;
 use crate::dto::EpisodeDetail;
 use crate::episode_field::EpisodeField;
-use crate::theme::Theme;
+use crate::theme::{string_to_color, Theme};
 use crossterm::style::Color;
 
-/// Convert a color string to a Color, with default fallback
-fn string_to_color(color: &str) -> Option<Color> {
-    match color.to_lowercase().as_str() {
-        "black" => Some(Color::Black),
-        "red" => Some(Color::Red),
-        "green" => Some(Color::Green),
-        "yellow" => Some(Color::Yellow),
-        "blue" => Some(Color::Blue),
-        "magenta" => Some(Color::Magenta),
-        "cyan" => Some(Color::Cyan),
-        "white" => Some(Color::White),
-        "darkgray" | "dark_gray" => Some(Color::DarkGrey),
-        "reset" => Some(Color::Reset),
-        _ => None,
-    }
-}
 
 /// Convert a color string to a foreground Color, with default fallback
 fn string_to_fg_color_or_default(color: &str) -> Color {
@@ -64,7 +49,9 @@ impl MetadataDisplay {
     fn extract_path_and_filename(&self) -> (String, String) {
         use std::path::Path;
         
-        let path = Path::new(&self.entry_location);
+        // Bytes that aren't valid UTF-8 are shown as replacement characters
+        let shown_location = crate::location::display(&self.entry_location);
+        let path = Path::new(&shown_location);
         let filename = path.file_name()
             .and_then(|name| name.to_str())
             .unwrap_or("")
@@ -97,21 +84,26 @@ impl Component for MetadataDisplay {
             EpisodeField::EpisodeNumber,
             EpisodeField::LastWatchedTime,
             EpisodeField::LastProgressTime,
+            EpisodeField::PlayCount,
         ];
         
-        for (row_index, &field) in fields.iter().enumerate() {
+        // Notes are shown below the fields only when present
+        let mut lines: Vec<String> = fields.iter().map(|&field| self.format_field_line(field)).collect();
+        if let Some(notes) = &self.episode_details.notes {
+            lines.push(format!("Notes: {}", notes));
+        }
+        
+        for (row_index, line) in lines.iter().enumerate() {
             if row_index >= height {
                 break;
             }
             
-            let line = self.format_field_line(field);
             let mut row = Vec::new();
             
             // Convert string to cells, truncating if necessary
-            let chars: Vec<char> = line.chars().take(width).collect();
             let fg_color = string_to_fg_color_or_default(&theme.episode_fg);
-            for ch in chars {
-                row.push(Cell::new(ch, fg_color, Color::Reset, TextStyle::new()));
+            for ch in text::truncate_to_width(line, width).chars() {
+                push_char_cell(&mut row, Cell::new(ch, fg_color, Color::Reset, TextStyle::new()));
             }
             
             // Pad row to width if needed
@@ -134,4 +126,4 @@ impl Component for MetadataDisplay {
         
         result
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,5 @@
 use crossterm::style::Color;
+use crate::text::{self, WIDE_CHAR_TAIL};
 use crate::theme::Theme;
 
 pub mod episode;
@@ -14,6 +15,15 @@ pub mod context_menu;
 pub mod series_creator;
 pub mod series_selector;
 pub mod series_select_window;
+pub mod notes_editor;
+pub mod tutorial_overlay;
+pub mod alt_titles_editor;
+pub mod confirm_dialog;
+pub mod theme_picker;
+pub mod grid_browser;
+pub mod empty_state;
+pub mod help_bar;
+pub mod toast;
 
 pub use category::*;
 pub use scrollbar::Scrollbar;
@@ -24,6 +34,15 @@ pub use context_menu::ContextMenu;
 pub use series_creator::SeriesCreator;
 pub use series_selector::SeriesSelector;
 pub use series_select_window::SeriesSelectWindow;
+pub use notes_editor::NotesEditor;
+pub use tutorial_overlay::TutorialOverlay;
+pub use alt_titles_editor::AltTitlesEditor;
+pub use confirm_dialog::ConfirmDialog;
+pub use theme_picker::ThemePicker;
+pub use grid_browser::GridBrowser;
+pub use empty_state::EmptyState;
+pub use help_bar::HelpBar;
+pub use toast::Toast;
 
 /// Represents text styling attributes that can be applied to terminal output
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -75,6 +94,34 @@ impl Cell {
 
 }
 
+/// Append the cells one character takes on screen, so every cell in a row is one terminal column
+/// A wide character is followed by a tail cell that the terminal covers with it, and zero-width
+/// characters such as combining marks are left out because a cell holds a single character
+pub fn push_char_cell(row: &mut Vec<Cell>, cell: Cell) {
+    match text::char_width(cell.character) {
+        0 => {}
+        1 => row.push(cell),
+        _ => {
+            let tail = Cell::new(WIDE_CHAR_TAIL, cell.fg_color, cell.bg_color, cell.style);
+            row.push(cell);
+            row.push(tail);
+        }
+    }
+}
+
+/// Cut a row to `width` cells, blanking a wide character whose tail would be cut off
+pub fn truncate_cells(row: &mut Vec<Cell>, width: usize) {
+    if row.len() <= width {
+        return;
+    }
+    row.truncate(width);
+    if let Some(last) = row.last_mut() {
+        if text::char_width(last.character) > 1 {
+            last.character = ' ';
+        }
+    }
+}
+
 /// Trait for components that can render themselves to a 2D array of Cells
 ///
 /// Components are self-contained rendering units that produce terminal output
@@ -0,0 +1,115 @@
+use super::{Cell, Component, TextStyle};
+use crate::theme::{BorderChars, Theme};
+use crossterm::style::Color;
+
+/// Fixed height of the notes window: borders, prompt, and input rows
+pub const NOTES_WINDOW_HEIGHT: usize = 4;
+
+/// Bordered modal for editing the freeform notes of an episode
+pub struct NotesEditor {
+    text: String,
+    cursor_position: usize,
+    window_width: usize,
+}
+
+impl NotesEditor {
+    /// Create a new NotesEditor component
+    pub fn new(text: String, cursor_position: usize, window_width: usize) -> Self {
+        Self {
+            text,
+            cursor_position,
+            window_width,
+        }
+    }
+
+    /// Width available for text inside the borders
+    fn content_width(&self) -> usize {
+        self.window_width.saturating_sub(2)
+    }
+
+    /// First character shown in the input row, scrolled so the cursor stays visible
+    fn scroll_offset(&self) -> usize {
+        let cursor = self.cursor_position.min(self.text.chars().count());
+        cursor.saturating_sub(self.content_width().saturating_sub(1))
+    }
+
+    /// Column of the cursor relative to the left edge of the window
+    pub fn cursor_column(&self) -> usize {
+        let cursor = self.cursor_position.min(self.text.chars().count());
+        1 + cursor - self.scroll_offset()
+    }
+
+    /// Build a row of cells padded to the content width
+    fn build_row(&self, text: &str, fg: Color, bg: Color, cursor: Option<usize>) -> Vec<Cell> {
+        let width = self.content_width();
+        let mut row: Vec<Cell> = text
+            .chars()
+            .take(width)
+            .enumerate()
+            .map(|(i, ch)| {
+                if cursor == Some(i) {
+                    Cell::new(ch, Color::White, Color::Black, TextStyle::new())
+                } else {
+                    Cell::new(ch, fg, bg, TextStyle::new())
+                }
+            })
+            .collect();
+
+        // Show the cursor as a space when it sits past the end of the text
+        if let Some(cursor) = cursor {
+            if cursor >= row.len() && row.len() < width {
+                row.push(Cell::new(' ', Color::White, Color::Black, TextStyle::new()));
+            }
+        }
+
+        while row.len() < width {
+            row.push(Cell::new(' ', fg, bg, TextStyle::new()));
+        }
+        row
+    }
+
+    /// Wrap a content row in vertical borders
+    fn bordered(&self, border: &BorderChars, content: Vec<Cell>) -> Vec<Cell> {
+        let mut row = vec![Cell::new(border.vertical, Color::Reset, Color::Reset, TextStyle::new())];
+        row.extend(content);
+        row.push(Cell::new(border.vertical, Color::Reset, Color::Reset, TextStyle::new()));
+        row
+    }
+
+    /// Build the top or bottom border row
+    fn horizontal_border(&self, border: &BorderChars, top: bool) -> Vec<Cell> {
+        let (left, right) = if top {
+            (border.top_left, border.top_right)
+        } else {
+            (border.bottom_left, border.bottom_right)
+        };
+        let mut row = vec![Cell::new(left, Color::Reset, Color::Reset, TextStyle::new())];
+        for _ in 0..self.content_width() {
+            row.push(Cell::new(border.horizontal, Color::Reset, Color::Reset, TextStyle::new()));
+        }
+        row.push(Cell::new(right, Color::Reset, Color::Reset, TextStyle::new()));
+        row
+    }
+}
+
+impl Component for NotesEditor {
+    /// Renders the notes window with a prompt row and a horizontally scrolling input row
+    fn render(&self, _width: usize, height: usize, theme: &Theme, _is_selected: bool) -> Vec<Vec<Cell>> {
+        let border = theme.active_border_chars();
+        let mut cells = vec![self.horizontal_border(&border, true)];
+
+        // Prompt row with inverted colors, matching the series creation window
+        let prompt = "Episode notes, [ENTER] save, [ESC] cancel:";
+        cells.push(self.bordered(&border, self.build_row(prompt, Color::Black, Color::White, None)));
+
+        // Input row showing the visible slice of the notes
+        let offset = self.scroll_offset();
+        let visible: String = self.text.chars().skip(offset).collect();
+        let cursor = self.cursor_position.min(self.text.chars().count()) - offset;
+        cells.push(self.bordered(&border, self.build_row(&visible, Color::Reset, Color::Reset, Some(cursor))));
+
+        cells.push(self.horizontal_border(&border, false));
+        cells.truncate(height);
+        cells
+    }
+}
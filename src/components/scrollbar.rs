@@ -1,5 +1,5 @@
 use super::{Cell, Component, TextStyle};
-use crate::theme::Theme;
+use crate::theme::{string_to_color, Theme};
 use crossterm::style::Color;
 
 /// Scrollbar component that renders a vertical scrollbar with track and indicator
@@ -87,24 +87,8 @@ fn string_to_bg_color_or_default(color: &str) -> Color {
     string_to_color(color).unwrap_or(Color::Reset)
 }
 
-/// Convert a color string to a Color enum
-fn string_to_color(color: &str) -> Option<Color> {
-    match color.to_lowercase().as_str() {
-        "black" => Some(Color::Black),
-        "red" => Some(Color::Red),
-        "green" => Some(Color::Green),
-        "yellow" => Some(Color::Yellow),
-        "blue" => Some(Color::Blue),
-        "magenta" => Some(Color::Magenta),
-        "cyan" => Some(Color::Cyan),
-        "white" => Some(Color::White),
-        "darkgray" | "dark_gray" => Some(Color::DarkGrey),
-        "reset" => Some(Color::Reset),
-        _ => None,
-    }
-}
 
 /// Get the first character from a string, or return a default character if empty
 fn get_first_char_or_default(s: &str, default: char) -> char {
     s.chars().next().unwrap_or(default)
-}
\ No newline at end of file
+}
@@ -1,14 +1,14 @@
 use super::{Cell, Component, TextStyle, SeriesSelector, SeriesCreator};
+use crate::display::{list_split, list_width_for};
 use crate::dto::Series;
-use crate::theme::Theme;
+use crate::theme::{BorderChars, Theme};
 use crate::util::Mode;
 use crossterm::style::Color;
 use crossterm::terminal::size as get_terminal_size;
 use std::io;
 
 // Constants from display.rs for positioning calculations
-const COL1_WIDTH: usize = 45;
-const DETAIL_HEIGHT: usize = 13; // Increased from 11 to accommodate progress tracking fields
+const DETAIL_HEIGHT: usize = 15; // Progress tracking and play count fields plus the optional notes line
 const SERIES_WIDTH: usize = 40;
 
 /// Container component that switches between sub-components based on application mode
@@ -77,7 +77,8 @@ impl SeriesSelectWindow {
         window_width: usize,
         header_height: usize,
     ) -> io::Result<(usize, usize)> {
-        let start_col = COL1_WIDTH + 2;
+        let (cols, _) = get_terminal_size()?;
+        let start_col = list_width_for(cols as usize, list_split()) + 2;
         let start_row = header_height + DETAIL_HEIGHT;
         let sidebar_width = Self::get_sidebar_width()?;
         
@@ -91,7 +92,7 @@ impl SeriesSelectWindow {
     fn get_sidebar_width() -> io::Result<usize> {
         let (cols, _) = get_terminal_size()?;
         let cols = cols as usize;
-        let sidebar_width = cols.saturating_sub(COL1_WIDTH + 2);
+        let sidebar_width = cols.saturating_sub(list_width_for(cols, list_split()) + 2);
         const MIN_COL2_WIDTH: usize = 20;
         Ok(sidebar_width.max(MIN_COL2_WIDTH))
     }
@@ -110,12 +111,13 @@ impl SeriesSelectWindow {
         }
         
         // Handle very small terminal sizes
-        let min_terminal_width = COL1_WIDTH + 2 + 20; // Minimum viable width
+        let col1_width = list_width_for(terminal_width, list_split());
+        let min_terminal_width = col1_width + 2 + 20; // Minimum viable width
         let min_terminal_height = header_height + DETAIL_HEIGHT + 6; // Minimum viable height
         
         if terminal_width < min_terminal_width || terminal_height < min_terminal_height {
             // Adjust window dimensions for small terminals
-            let available_width = terminal_width.saturating_sub(COL1_WIDTH + 4);
+            let available_width = terminal_width.saturating_sub(col1_width + 4);
             self.window_width = self.window_width.min(available_width).max(20);
             
             let available_height = terminal_height.saturating_sub(header_height + DETAIL_HEIGHT + 2);
@@ -214,16 +216,13 @@ impl SeriesSelectWindow {
     }
     
     /// Render window borders around the content
-    fn render_borders(&self, content_cells: Vec<Vec<Cell>>, _theme: &Theme) -> Vec<Vec<Cell>> {
+    fn render_borders(&self, content_cells: Vec<Vec<Cell>>, theme: &Theme) -> Vec<Vec<Cell>> {
         let mut result = Vec::new();
         let thick = self.use_thick_borders();
         
-        // Choose border characters based on thickness
-        let (top_left, top_right, bottom_left, bottom_right, horizontal, vertical) = if thick {
-            ('╔', '╗', '╚', '╝', '═', '║')
-        } else {
-            ('┌', '┐', '└', '┘', '─', '│')
-        };
+        // Choose the theme's border characters based on thickness
+        let border = if thick { theme.active_border_chars() } else { theme.border_chars() };
+        let BorderChars { top_left, top_right, bottom_left, bottom_right, horizontal, vertical } = border;
         
         // Top border
         let mut top_border = Vec::new();
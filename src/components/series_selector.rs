@@ -1,6 +1,6 @@
-use super::{Cell, Component, TextStyle, Scrollbar};
+use super::{push_char_cell, Cell, Component, TextStyle, Scrollbar};
 use crate::dto::Series;
-use crate::theme::Theme;
+use crate::theme::{string_to_color, Theme};
 use crate::util::truncate_string;
 use crossterm::style::Color;
 
@@ -138,7 +138,7 @@ impl Component for SeriesSelector {
             
             // Add series text characters
             for ch in formatted_text.chars() {
-                series_row.push(Cell::new(
+                push_char_cell(&mut series_row, Cell::new(
                     ch,
                     fg_color,
                     bg_color,
@@ -224,20 +224,3 @@ fn string_to_fg_color_or_default(color: &str) -> Color {
 fn string_to_bg_color_or_default(color: &str) -> Color {
     string_to_color(color).unwrap_or(Color::Reset)
 }
-
-/// Convert a color string to a Color enum
-fn string_to_color(color: &str) -> Option<Color> {
-    match color.to_lowercase().as_str() {
-        "black" => Some(Color::Black),
-        "red" => Some(Color::Red),
-        "green" => Some(Color::Green),
-        "yellow" => Some(Color::Yellow),
-        "blue" => Some(Color::Blue),
-        "magenta" => Some(Color::Magenta),
-        "cyan" => Some(Color::Cyan),
-        "white" => Some(Color::White),
-        "darkgray" | "dark_gray" => Some(Color::DarkGrey),
-        "reset" => Some(Color::Reset),
-        _ => None,
-    }
-}
\ No newline at end of file
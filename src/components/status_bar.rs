@@ -1,5 +1,7 @@
-use super::{Cell, Component, TextStyle};
-use crate::theme::Theme;
+use super::{push_char_cell, Cell, Component, TextStyle};
+use crate::text;
+use crate::notifications::NotificationLevel;
+use crate::theme::{string_to_color, Theme};
 use crossterm::style::Color;
 
 /// StatusBar component that renders status messages at the bottom of the terminal
@@ -10,12 +12,23 @@ use crossterm::style::Color;
 pub struct StatusBar {
     /// The status message to display
     message: String,
+    /// Level of the message, which picks the foreground color
+    level: NotificationLevel,
 }
 
 impl StatusBar {
     /// Create a new StatusBar component with the given message
     pub fn new(message: String) -> Self {
-        Self { message }
+        Self {
+            message,
+            level: NotificationLevel::Info,
+        }
+    }
+
+    /// Color the message for a warning or an error
+    pub fn with_level(mut self, level: NotificationLevel) -> Self {
+        self.level = level;
+        self
     }
 }
 
@@ -32,25 +45,22 @@ impl Component for StatusBar {
         }
 
         // Get status line colors from theme
-        let status_fg = string_to_fg_color_or_default(&theme.status_fg);
+        let status_fg = string_to_fg_color_or_default(match self.level {
+            NotificationLevel::Info => &theme.status_fg,
+            NotificationLevel::Warn => &theme.status_warn_fg,
+            NotificationLevel::Error => &theme.status_error_fg,
+        });
         let status_bg = string_to_bg_color_or_default(&theme.status_bg);
         let text_style = TextStyle::new();
 
-        // Calculate visual width (accounting for multi-byte UTF-8 characters)
-        let visual_width = self.message.chars().count();
-        
-        // Truncate if message is too long (based on visual width)
-        let truncated_message = if visual_width > width {
-            self.message.chars().take(width).collect::<String>()
-        } else {
-            self.message.clone()
-        };
+        // Truncate if message is too long, measured in terminal columns
+        let truncated_message = text::truncate_to_width(&self.message, width);
 
-        // Create cells for the message
-        let mut cells: Vec<Cell> = truncated_message
-            .chars()
-            .map(|c| Cell::new(c, status_fg, status_bg, text_style))
-            .collect();
+        // Create cells for the message, one per column
+        let mut cells: Vec<Cell> = Vec::new();
+        for c in truncated_message.chars() {
+            push_char_cell(&mut cells, Cell::new(c, status_fg, status_bg, text_style));
+        }
 
         // Pad to terminal width based on visual width
         let current_visual_width = cells.len();
@@ -75,20 +85,3 @@ fn string_to_fg_color_or_default(color: &str) -> Color {
 fn string_to_bg_color_or_default(color: &str) -> Color {
     string_to_color(color).unwrap_or(Color::Reset)
 }
-
-/// Convert a color string to a Color enum
-fn string_to_color(color: &str) -> Option<Color> {
-    match color.to_lowercase().as_str() {
-        "black" => Some(Color::Black),
-        "red" => Some(Color::Red),
-        "green" => Some(Color::Green),
-        "yellow" => Some(Color::Yellow),
-        "blue" => Some(Color::Blue),
-        "magenta" => Some(Color::Magenta),
-        "cyan" => Some(Color::Cyan),
-        "white" => Some(Color::White),
-        "darkgray" | "dark_gray" => Some(Color::DarkGrey),
-        "reset" => Some(Color::Reset),
-        _ => None,
-    }
-}
\ No newline at end of file
@@ -0,0 +1,112 @@
+use super::{Cell, Component, TextStyle};
+use crate::theme::{string_to_color, BorderChars, Theme};
+use crossterm::style::Color;
+
+/// Bordered list of the theme files, drawn at the top-right like the menu
+/// The screen behind it is drawn with the highlighted theme
+#[derive(Clone)]
+pub struct ThemePicker {
+    /// Theme file names, e.g. THEME-default.yaml
+    pub names: Vec<String>,
+    pub selected: usize,
+    /// Theme in use when the picker opened, put back on cancel
+    pub original: Theme,
+}
+
+impl ThemePicker {
+    /// Create a new ThemePicker with the active theme highlighted
+    pub fn new(names: Vec<String>, active_theme: &str, original: Theme) -> Self {
+        let selected = names.iter().position(|name| name == active_theme).unwrap_or(0);
+        Self {
+            names,
+            selected,
+            original,
+        }
+    }
+
+    /// File name of the highlighted theme
+    pub fn selected_name(&self) -> Option<&str> {
+        self.names.get(self.selected).map(String::as_str)
+    }
+
+    /// Move the highlight up or down, returning false at either end
+    pub fn move_selection(&mut self, down: bool) -> bool {
+        if down && self.selected + 1 < self.names.len() {
+            self.selected += 1;
+            true
+        } else if !down && self.selected > 0 {
+            self.selected -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Name shown in the list: THEME-dark.yaml is listed as "dark"
+    pub fn display_name(name: &str) -> &str {
+        name.strip_prefix("THEME-")
+            .and_then(|rest| rest.strip_suffix(".yaml"))
+            .unwrap_or(name)
+    }
+
+    /// Build the top or bottom border row, with an optional title after the left corner
+    fn horizontal_border(&self, border: &BorderChars, window_width: usize, top: bool, title: &str) -> Vec<Cell> {
+        let (left, right) = if top {
+            (border.top_left, border.top_right)
+        } else {
+            (border.bottom_left, border.bottom_right)
+        };
+        let mut row = vec![Cell::new(left, Color::Reset, Color::Black, TextStyle::new())];
+        let mut title_chars = title.chars();
+        for _ in 0..window_width.saturating_sub(2) {
+            let ch = title_chars.next().unwrap_or(border.horizontal);
+            row.push(Cell::new(ch, Color::Reset, Color::Black, TextStyle::new()));
+        }
+        row.push(Cell::new(right, Color::Reset, Color::Black, TextStyle::new()));
+        row
+    }
+}
+
+impl Component for ThemePicker {
+    /// Renders the theme names with the highlighted one in the current selection colors
+    fn render(&self, width: usize, height: usize, theme: &Theme, _is_selected: bool) -> Vec<Vec<Cell>> {
+        let content_width = self
+            .names
+            .iter()
+            .map(|name| Self::display_name(name).chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(" Theme ".len());
+        let window_width = (content_width + 4).min(width);
+        if window_width < 5 || height < 3 {
+            return vec![];
+        }
+        let selected_fg = string_to_color(&theme.current_fg).unwrap_or(Color::Black);
+        let selected_bg = string_to_color(&theme.current_bg).unwrap_or(Color::White);
+
+        let border = theme.active_border_chars();
+        let mut cells = vec![self.horizontal_border(&border, window_width, true, " Theme ")];
+        // Keep the highlight visible when there are more themes than rows
+        let visible_rows = height - 2;
+        let first_row = self.selected.saturating_sub(visible_rows.saturating_sub(1));
+        for (index, name) in self.names.iter().enumerate().skip(first_row).take(visible_rows) {
+            let (fg, bg) = if index == self.selected {
+                (selected_fg, selected_bg)
+            } else {
+                (Color::Reset, Color::Black)
+            };
+            let mut row = vec![
+                Cell::new(border.vertical, Color::Reset, Color::Black, TextStyle::new()),
+                Cell::new(' ', fg, bg, TextStyle::new()),
+            ];
+            let mut label: Vec<char> = Self::display_name(name).chars().take(window_width - 4).collect();
+            label.resize(window_width - 4, ' ');
+            row.extend(label.into_iter().map(|ch| Cell::new(ch, fg, bg, TextStyle::new())));
+            row.push(Cell::new(' ', fg, bg, TextStyle::new()));
+            row.push(Cell::new(border.vertical, Color::Reset, Color::Black, TextStyle::new()));
+            cells.push(row);
+        }
+        cells.push(self.horizontal_border(&border, window_width, false, ""));
+        cells
+    }
+}
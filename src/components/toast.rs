@@ -0,0 +1,63 @@
+use super::{push_char_cell, Cell, Component, TextStyle};
+use crate::text;
+use crate::theme::{string_to_color, Theme};
+use crossterm::style::Color;
+
+/// Rows taken by a toast: the message between a top and bottom border
+pub const TOAST_HEIGHT: usize = 3;
+
+/// Small bordered box with a short confirmation, drawn in the bottom-right corner for a few seconds
+/// The message is cut to fit the width; the box is only as wide as the message needs
+pub struct Toast {
+    pub message: String,
+}
+
+impl Toast {
+    /// Create a new Toast component for the message
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+
+    /// Width of the box, borders and padding included, at most `max_width`
+    pub fn width(&self, max_width: usize) -> usize {
+        (text::display_width(&self.message) + 4).min(max_width)
+    }
+}
+
+impl Component for Toast {
+    /// Renders the message in the status colors inside the theme's active border
+    fn render(&self, width: usize, height: usize, theme: &Theme, _is_selected: bool) -> Vec<Vec<Cell>> {
+        let window_width = self.width(width);
+        if window_width < 5 || height < TOAST_HEIGHT {
+            return vec![];
+        }
+        let fg = string_to_color(&theme.status_fg).unwrap_or(Color::Reset);
+        let bg = string_to_color(&theme.status_bg).unwrap_or(Color::Reset);
+        let style = TextStyle::new();
+        let border = theme.active_border_chars();
+        let inner_width = window_width - 2;
+
+        let edge = |left: char, right: char| {
+            let mut row = vec![Cell::new(left, fg, bg, style)];
+            row.extend((0..inner_width).map(|_| Cell::new(border.horizontal, fg, bg, style)));
+            row.push(Cell::new(right, fg, bg, style));
+            row
+        };
+
+        let mut middle = vec![
+            Cell::new(border.vertical, fg, bg, style),
+            Cell::new(' ', fg, bg, style),
+        ];
+        for ch in text::truncate_to_width(&self.message, inner_width - 2).chars() {
+            push_char_cell(&mut middle, Cell::new(ch, fg, bg, style));
+        }
+        middle.resize(window_width - 1, Cell::new(' ', fg, bg, style));
+        middle.push(Cell::new(border.vertical, fg, bg, style));
+
+        vec![
+            edge(border.top_left, border.top_right),
+            middle,
+            edge(border.bottom_left, border.bottom_right),
+        ]
+    }
+}
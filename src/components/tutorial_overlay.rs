@@ -0,0 +1,120 @@
+use super::{Cell, Component, TextStyle};
+use crate::theme::{BorderChars, Theme};
+use crate::tutorial::Tutorial;
+use crossterm::style::Color;
+
+/// Background given to the screen region the current step points at
+pub const TUTORIAL_HIGHLIGHT_BG: Color = Color::DarkGrey;
+
+/// Bordered window explaining the current tutorial step
+pub struct TutorialOverlay {
+    title: String,
+    lines: Vec<String>,
+    window_width: usize,
+}
+
+impl TutorialOverlay {
+    /// Create a new TutorialOverlay component, wrapping the step text to the window width
+    pub fn new(tutorial: &Tutorial, window_width: usize) -> Self {
+        let (index, total) = tutorial.progress();
+        let step = tutorial.step();
+        let content_width = window_width.saturating_sub(4);
+        Self {
+            title: format!("Tutorial {}/{}: {}", index, total, step.title()),
+            lines: wrap_words(step.text(), content_width),
+            window_width,
+        }
+    }
+
+    /// Rows needed: borders, title, blank, text, blank, footer
+    pub fn height(&self) -> usize {
+        self.lines.len() + 6
+    }
+
+    /// Build a padded content row inside the borders
+    fn content_row(&self, border: &BorderChars, text: &str, style: TextStyle) -> Vec<Cell> {
+        let content_width = self.window_width.saturating_sub(4);
+        let mut row = vec![
+            Cell::new(border.vertical, Color::Reset, Color::Black, TextStyle::new()),
+            Cell::new(' ', Color::Reset, Color::Black, TextStyle::new()),
+        ];
+        let mut used = 0;
+        for ch in text.chars().take(content_width) {
+            row.push(Cell::new(ch, Color::White, Color::Black, style));
+            used += 1;
+        }
+        for _ in used..content_width {
+            row.push(Cell::new(' ', Color::Reset, Color::Black, TextStyle::new()));
+        }
+        row.push(Cell::new(' ', Color::Reset, Color::Black, TextStyle::new()));
+        row.push(Cell::new(border.vertical, Color::Reset, Color::Black, TextStyle::new()));
+        row
+    }
+
+    /// Build the top or bottom border row
+    fn horizontal_border(&self, border: &BorderChars, top: bool) -> Vec<Cell> {
+        let (left, right) = if top {
+            (border.top_left, border.top_right)
+        } else {
+            (border.bottom_left, border.bottom_right)
+        };
+        let mut row = vec![Cell::new(left, Color::Reset, Color::Black, TextStyle::new())];
+        for _ in 0..self.window_width.saturating_sub(2) {
+            row.push(Cell::new(border.horizontal, Color::Reset, Color::Black, TextStyle::new()));
+        }
+        row.push(Cell::new(right, Color::Reset, Color::Black, TextStyle::new()));
+        row
+    }
+}
+
+impl Component for TutorialOverlay {
+    /// Renders the step title, its instructions, and how to leave the tutorial
+    fn render(&self, _width: usize, height: usize, theme: &Theme, _is_selected: bool) -> Vec<Vec<Cell>> {
+        let mut bold = TextStyle::new();
+        bold.bold = true;
+        let mut dim = TextStyle::new();
+        dim.dim = true;
+
+        let border = theme.active_border_chars();
+        let mut cells = vec![self.horizontal_border(&border, true)];
+        cells.push(self.content_row(&border, &self.title, bold));
+        cells.push(self.content_row(&border, "", TextStyle::new()));
+        for line in &self.lines {
+            cells.push(self.content_row(&border, line, TextStyle::new()));
+        }
+        cells.push(self.content_row(&border, "", TextStyle::new()));
+        cells.push(self.content_row(&border, "[ESC] end tutorial", dim));
+        cells.push(self.horizontal_border(&border, false));
+        cells.truncate(height);
+        cells
+    }
+}
+
+/// Greedy word wrap; words longer than the width are split
+pub fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            lines.push(word.drain(..width).collect());
+        }
+        let word: String = word.into_iter().collect();
+        let current_len = current.chars().count();
+        if current_len > 0 && current_len + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
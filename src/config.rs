@@ -1,21 +1,39 @@
+use crate::extras::SampleFiles;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
 
 #[derive(Deserialize, Serialize)]
 pub struct Config {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub db_location: Option<String>,
+    // Database given with --db or MOVIES_DB, used in place of db_location for this run and never saved
+    #[serde(skip)]
+    pub db_override: Option<String>,
     
     // Theme configuration
     #[serde(default = "default_active_theme")]
     pub active_theme: String,
+    // Show the splash screen at startup; any key skips it
+    #[serde(default = "default_show_splash")]
+    pub show_splash: bool,
+    // Text file with the ASCII art shown on the splash screen instead of the logo, relative to the config folder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub splash_art: Option<String>,
     
     // Logging configuration
     #[serde(default = "default_log_file")]
     pub log_file: Option<String>,
     #[serde(default = "default_log_level")]
     pub log_level: String,
+    // Megabytes the log grows to before a new one is started, 0 for no limit
+    #[serde(default = "default_log_max_size")]
+    pub log_max_size: u64,
+    // Earlier logs kept next to the current one, the last session's always among them
+    #[serde(default = "default_log_keep_files")]
+    pub log_keep_files: usize,
     
     // Progress tracking configuration
     #[serde(default = "default_watched_threshold")]
@@ -23,6 +41,303 @@ pub struct Config {
     
     pub video_extensions: Vec<String>,
     pub video_player: String,
+    
+    // Scan configuration
+    #[serde(default)]
+    pub min_file_size_mb: u64,
+    #[serde(default)]
+    pub root_overrides: Vec<RootOverride>,
+    // Follow symbolic links to files and folders while scanning
+    #[serde(default)]
+    pub scan_follow_symlinks: bool,
+    // Import a file hard linked into several folders only once
+    #[serde(default)]
+    pub scan_dedupe_hardlinks: bool,
+    // Offer to put new episodes in the series and season named by their folders after a rescan
+    #[serde(default)]
+    pub infer_series_from_folders: bool,
+    // What scans do with sample and trailer files: "import", "flag" as extras, or "skip"
+    #[serde(default = "default_sample_files")]
+    pub sample_files: String,
+    // Videos no longer than this many seconds count as samples, 0 to go by name only
+    #[serde(default = "default_sample_max_seconds")]
+    pub sample_max_seconds: u64,
+    // Minutes between rescans started in the background while the app is open, 0 for none
+    #[serde(default)]
+    pub rescan_interval_minutes: u64,
+    // Glob patterns of files and folders that scans leave out, e.g. "**/extras/**" or "*sample*"
+    #[serde(default)]
+    pub scan_exclude: Vec<String>,
+    // Folders outside the database's directory that are part of the library too, e.g. an external drive
+    #[serde(default)]
+    pub library_roots: Vec<String>,
+    
+    // Commands used to list and extract .rar/.zip archives found by a rescan
+    #[serde(default = "default_archive_list_command")]
+    pub archive_list_command: String,
+    #[serde(default = "default_archive_extract_command")]
+    pub archive_extract_command: String,
+    // yt-dlp program used by "Add from URL", and the folder under the library root it saves into
+    #[serde(default = "default_ytdlp_path")]
+    pub ytdlp_path: String,
+    #[serde(default = "default_download_folder")]
+    pub download_folder: String,
+    // Commands added to the F1 menu, run on the selected episode
+    #[serde(default)]
+    pub custom_commands: Vec<CustomCommand>,
+    // Rhai script with on_import, on_play and on_watched hooks (used when built with the `scripting` feature)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hook_script: Option<String>,
+    
+    // Navigation configuration
+    #[serde(default = "default_scroll_acceleration")]
+    pub scroll_acceleration: Vec<usize>,
+    #[serde(default = "default_scroll_acceleration_repeats")]
+    pub scroll_acceleration_repeats: usize,
+    #[serde(default = "default_scroll_margin")]
+    pub scroll_margin: usize,
+    // Percent of the terminal width taken by the list; unset keeps it 45 columns wide
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_split: Option<u8>,
+    #[serde(default)]
+    pub mouse: bool,
+    #[serde(default = "default_sort")]
+    pub default_sort: String,
+    // Open on the view, filter and selection left at the last exit
+    #[serde(default = "default_restore_session")]
+    pub restore_session: bool,
+    // Ask before removing an episode from the library, unwatching a whole view and clearing series data
+    #[serde(default = "default_confirm")]
+    pub confirm_delete: bool,
+    #[serde(default = "default_confirm")]
+    pub confirm_unwatch_all: bool,
+    #[serde(default = "default_confirm")]
+    pub confirm_clear_series: bool,
+    // Listen on a Unix domain socket for commands from other programs, such as a status bar
+    #[serde(default)]
+    pub remote_control: bool,
+    // Socket used by remote_control instead of movies.sock in the data directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_socket: Option<String>,
+    // What Browse opens on: top_level, last_session, continue_watching or series:<name>
+    #[serde(default = "default_startup_view")]
+    pub startup_view: String,
+    
+    // Filter expressions pinned to the number keys in Browse mode
+    #[serde(default)]
+    pub pinned_filters: Vec<String>,
+    
+    // Language code of the alternate title shown in the browser, e.g. "ja"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_title_language: Option<String>,
+    
+    // Label of the version preselected when an episode has several files, e.g. "4K"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_version: Option<String>,
+    
+    // Names given to a file renamed from the editor, for episodes in a series and for the others
+    #[serde(default = "default_rename_template")]
+    pub rename_template: String,
+    #[serde(default = "default_rename_movie_template")]
+    pub rename_movie_template: String,
+    
+    // Show poster artwork below the details in terminals with kitty or sixel graphics
+    #[serde(default)]
+    pub artwork: bool,
+    
+    // Show a digest of the library on the first launch each week
+    #[serde(default)]
+    pub weekly_digest: bool,
+    
+    // Plain ASCII borders and indicators, fewer colors, and the cursor on the selection for screen readers
+    #[serde(default)]
+    pub accessible: bool,
+    
+    // Keys rebound by the user, checked for clashes when the keymap is built
+    #[serde(default)]
+    pub keybindings: KeybindingsConfig,
+    
+    // Trakt sync configuration (used when built with the `trakt` feature)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trakt_client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trakt_client_secret: Option<String>,
+    
+    // Jellyfin sync configuration (used when built with the `jellyfin` feature)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jellyfin_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jellyfin_api_key: Option<String>,
+    // User whose watched state is synced; unset for the server's first user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jellyfin_user: Option<String>,
+}
+
+/// Scan rule overrides for a specific library root
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct RootOverride {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video_extensions: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_file_size_mb: Option<u64>,
+}
+
+/// A command of the user's own in the F1 menu
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct CustomCommand {
+    pub label: String,
+    /// Program and arguments; {path}, {title} and {series} are those of the selected episode
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hotkey: Option<String>,
+}
+
+/// Rebound keys by mode: Browse actions and menu hotkeys, each by its name
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct KeybindingsConfig {
+    /// Keys to start from: "default", or "vim" for gg/G, Ctrl+D/U, n/N and a `:` command line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    #[serde(default)]
+    pub browse: BTreeMap<String, KeyList>,
+    /// A null hotkey takes the menu action's hotkey away
+    #[serde(default)]
+    pub menu: BTreeMap<String, Option<String>>,
+}
+
+impl KeybindingsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.preset.is_none() && self.browse.is_empty() && self.menu.is_empty()
+    }
+}
+
+/// One key, or a list of keys that all do the same
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn keys(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(key) => vec![key.as_str()],
+            KeyList::Many(keys) => keys.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// Effective scan rules for a root after applying any overrides
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScanRules {
+    pub video_extensions: Vec<String>,
+    pub min_file_size_mb: u64,
+    /// Glob patterns of paths left out, matched relative to the root being scanned
+    pub exclude: Vec<String>,
+    pub follow_symlinks: bool,
+    /// Skip files that are another hard link to one already found
+    pub dedupe_hardlinks: bool,
+    /// What to do with sample and trailer files
+    pub samples: SampleFiles,
+    /// Videos no longer than this many seconds are samples, 0 to go by name only
+    pub sample_max_seconds: u64,
+}
+
+impl ScanRules {
+    /// Check whether a file should be imported under these rules
+    pub fn accepts(&self, path: &Path) -> bool {
+        let extension_matches = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.video_extensions.contains(&ext.to_lowercase()));
+        if !extension_matches {
+            return false;
+        }
+
+        // Only hit the filesystem when a size filter is active
+        if self.min_file_size_mb == 0 {
+            return true;
+        }
+        fs::metadata(path)
+            .map(|metadata| metadata.len() >= self.min_file_size_mb * 1024 * 1024)
+            .unwrap_or(false)
+    }
+
+    /// Walk the files under `root`, following symbolic links when the rules say so
+    /// Sorted by name, so every scan finds the same one of several hard links to a file first
+    pub fn walk(&self, root: &Path) -> WalkDir {
+        WalkDir::new(root).follow_links(self.follow_symlinks).sort_by_file_name()
+    }
+
+    /// Check whether a path under `root` matches one of the exclude patterns
+    /// A pattern without a `/` is matched against each folder and file name on its own
+    pub fn excludes(&self, root: &Path, path: &Path) -> bool {
+        if self.exclude.is_empty() {
+            return false;
+        }
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let names: Vec<String> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect();
+        let joined = names.join("/");
+        self.exclude.iter().any(|pattern| {
+            if pattern.contains('/') {
+                glob_matches(pattern, &joined)
+            } else {
+                names.iter().any(|name| wildcard_matches(pattern, name))
+            }
+        })
+    }
+}
+
+/// Whether a `/`-separated path matches a glob pattern, where `**` stands for any number of folders
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').filter(|part| !part.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+    parts_match(&pattern, &path)
+}
+
+fn parts_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skipped| parts_match(rest, &path[skipped..])),
+        Some((first, rest)) => {
+            path.first().is_some_and(|name| wildcard_matches(first, name)) && parts_match(rest, &path[1..])
+        }
+    }
+}
+
+/// Whether a single name matches a pattern with `*` for any run of characters and `?` for one,
+/// ignoring case
+fn wildcard_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and where in the name it started matching, to backtrack to
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+fn default_show_splash() -> bool {
+    true
 }
 
 fn default_active_theme() -> String {
@@ -37,17 +352,90 @@ fn default_log_level() -> String {
     "info".to_string()
 }
 
+fn default_log_max_size() -> u64 {
+    10
+}
+
+fn default_log_keep_files() -> usize {
+    3
+}
+
 fn default_watched_threshold() -> u8 {
     95
 }
 
+fn default_archive_list_command() -> String {
+    "7z l -slt {archive}".to_string()
+}
+
+fn default_archive_extract_command() -> String {
+    "7z x -y -o{dir} {archive}".to_string()
+}
+
+fn default_ytdlp_path() -> String {
+    "yt-dlp".to_string()
+}
+
+fn default_download_folder() -> String {
+    "Downloads".to_string()
+}
+
+fn default_scroll_acceleration() -> Vec<usize> {
+    vec![1, 3, 10]
+}
+
+fn default_scroll_acceleration_repeats() -> usize {
+    8
+}
+
+fn default_scroll_margin() -> usize {
+    2
+}
+
+fn default_sort() -> String {
+    "default".to_string()
+}
+
+fn default_sample_files() -> String {
+    "flag".to_string()
+}
+
+fn default_sample_max_seconds() -> u64 {
+    60
+}
+
+fn default_rename_template() -> String {
+    "{series} - S{season:02}E{episode:02} - {title}.{ext}".to_string()
+}
+
+fn default_rename_movie_template() -> String {
+    "{title}.{ext}".to_string()
+}
+
+fn default_restore_session() -> bool {
+    true
+}
+
+fn default_confirm() -> bool {
+    true
+}
+
+fn default_startup_view() -> String {
+    "last_session".to_string()
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
             db_location: None,
+            db_override: None,
             active_theme: "THEME-default.yaml".to_string(),
+            show_splash: default_show_splash(),
+            splash_art: None,
             log_file: None,
             log_level: "info".to_string(),
+            log_max_size: default_log_max_size(),
+            log_keep_files: default_log_keep_files(),
             watched_threshold: 95,
             video_extensions: vec![
                 "mp4".to_string(),
@@ -59,24 +447,75 @@ impl Default for Config {
                 "webm".to_string(),
             ],
             video_player: "/usr/bin/vlc".to_string(),
+            min_file_size_mb: 0,
+            root_overrides: Vec::new(),
+            scan_follow_symlinks: false,
+            scan_dedupe_hardlinks: false,
+            infer_series_from_folders: false,
+            sample_files: default_sample_files(),
+            sample_max_seconds: default_sample_max_seconds(),
+            rescan_interval_minutes: 0,
+            scan_exclude: Vec::new(),
+            library_roots: Vec::new(),
+            archive_list_command: default_archive_list_command(),
+            archive_extract_command: default_archive_extract_command(),
+            ytdlp_path: default_ytdlp_path(),
+            download_folder: default_download_folder(),
+            custom_commands: Vec::new(),
+            hook_script: None,
+            scroll_acceleration: default_scroll_acceleration(),
+            scroll_acceleration_repeats: default_scroll_acceleration_repeats(),
+            scroll_margin: default_scroll_margin(),
+            list_split: None,
+            mouse: false,
+            default_sort: default_sort(),
+            restore_session: default_restore_session(),
+            confirm_delete: default_confirm(),
+            confirm_unwatch_all: default_confirm(),
+            confirm_clear_series: default_confirm(),
+            remote_control: false,
+            remote_socket: None,
+            startup_view: default_startup_view(),
+            pinned_filters: Vec::new(),
+            preferred_title_language: None,
+            preferred_version: None,
+            rename_template: default_rename_template(),
+            rename_movie_template: default_rename_movie_template(),
+            artwork: false,
+            weekly_digest: false,
+            accessible: false,
+            keybindings: KeybindingsConfig::default(),
+            trakt_client_id: None,
+            trakt_client_secret: None,
+            jellyfin_url: None,
+            jellyfin_api_key: None,
+            jellyfin_user: None,
         }
     }
 }
 
 impl Config {
-    /// Get the database path as a PathBuf
+    /// Get the database path as a PathBuf, the one given for this run if any
     pub fn get_database_path(&self) -> Option<PathBuf> {
-        self.db_location.as_ref().map(PathBuf::from)
+        self.db_override
+            .as_ref()
+            .map(PathBuf::from)
+            .or_else(|| self.db_location.as_deref().map(crate::paths::resolve_location))
     }
     
     /// Set the database path and save the config
+    /// A database given for this run is moved on its own, leaving db_location as it is
     pub fn set_database_path(&mut self, path: PathBuf) {
-        self.db_location = Some(path.to_string_lossy().to_string());
+        if self.db_override.is_some() {
+            self.db_override = Some(path.to_string_lossy().to_string());
+        } else {
+            self.db_location = Some(crate::paths::store_location(&path));
+        }
     }
     
     /// Check if this is a first run (no database location configured)
     pub fn is_first_run(&self) -> bool {
-        self.db_location.is_none()
+        self.get_database_path().is_none()
     }
     
     /// Validate and correct the watched threshold value
@@ -89,39 +528,96 @@ impl Config {
             self.watched_threshold = 95;
         }
     }
+    
+    /// When the log is rotated, from `log_max_size` and `log_keep_files`
+    pub fn log_rotation(&self) -> crate::logger::LogRotation {
+        crate::logger::LogRotation {
+            max_size: self.log_max_size.saturating_mul(1024 * 1024),
+            keep_files: self.log_keep_files,
+        }
+    }
+    
+    /// Resolve the scan rules for a root, applying a matching root override if present
+    pub fn scan_rules_for(&self, root: &Path) -> ScanRules {
+        let mut rules = ScanRules {
+            video_extensions: self.video_extensions.clone(),
+            min_file_size_mb: self.min_file_size_mb,
+            exclude: self.scan_exclude.clone(),
+            follow_symlinks: self.scan_follow_symlinks,
+            dedupe_hardlinks: self.scan_dedupe_hardlinks,
+            // An unknown value keeps the default of importing them as extras
+            samples: SampleFiles::from_config(&self.sample_files).unwrap_or_default(),
+            sample_max_seconds: self.sample_max_seconds,
+        };
+        
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let matching = self.root_overrides.iter().find(|o| {
+            let override_path = Path::new(&o.path);
+            override_path.canonicalize().unwrap_or_else(|_| override_path.to_path_buf()) == root
+        });
+        
+        if let Some(root_override) = matching {
+            if let Some(ref extensions) = root_override.video_extensions {
+                rules.video_extensions = extensions.iter().map(|ext| ext.to_lowercase()).collect();
+            }
+            if let Some(min_size) = root_override.min_file_size_mb {
+                rules.min_file_size_mb = min_size;
+            }
+        }
+        
+        rules
+    }
 }
 
 /// Read configuration from file, creating default if missing
 pub fn read_config(config_path: &PathBuf) -> Config {
+    load_config(config_path).0
+}
+
+/// Read configuration from file like `read_config`, along with the problems found in it
+///
+/// A file that can't be parsed is kept as config.yaml.broken before the defaults are written
+/// in its place, so the settings in it can be copied back once it is fixed.
+pub fn load_config(config_path: &PathBuf) -> (Config, Vec<String>) {
     if config_path.exists() {
         match fs::read_to_string(config_path) {
             Ok(content) => {
                 match serde_yaml::from_str::<Config>(&content) {
                     Ok(mut config) => {
+                        let problems = crate::config_check::check_config(&content, &config);
                         config.validate_watched_threshold();
-                        config
+                        (config, problems)
                     }
                     Err(e) => {
                         eprintln!("Error: Could not parse config.yaml: {}. Using default values.", e);
                         crate::logger::log_warn(&format!("Could not parse config.yaml: {}. Using default values.", e));
+                        let broken_path = config_path.with_extension("yaml.broken");
+                        let problem = match fs::copy(config_path, &broken_path) {
+                            Ok(_) => format!(
+                                "Could not parse config.yaml: {}. The defaults are used; the file was kept as {}",
+                                e,
+                                broken_path.display()
+                            ),
+                            Err(_) => format!("Could not parse config.yaml: {}. The defaults are used", e),
+                        };
                         let default_config = Config::default();
                         // Try to write the default config
                         save_config(&default_config, config_path);
-                        default_config
+                        (default_config, vec![problem])
                     }
                 }
             }
             Err(e) => {
                 eprintln!("Error: Could not read the config.yaml file. Using default values.");
                 crate::logger::log_warn(&format!("Could not read config.yaml file: {}. Using default values.", e));
-                Config::default()
+                (Config::default(), vec![format!("Could not read config.yaml: {}. The defaults are used", e)])
             }
         }
     } else {
         // config.yaml doesn't exist, create default config.yaml
         let default_config = Config::default();
         save_config(&default_config, config_path);
-        default_config
+        (default_config, Vec::new())
     }
 }
 
@@ -148,6 +644,16 @@ pub fn generate_yaml_with_comments(config: &Config) -> String {
     yaml.push_str(&format!("active_theme: {}\n", config.active_theme));
     yaml.push('\n');
     
+    yaml.push_str("# Show the splash screen at startup, skipped with any key (default: true)\n");
+    yaml.push_str(&format!("show_splash: {}\n", config.show_splash));
+    yaml.push_str("# Text file with your own ASCII art for the splash screen, relative to this folder\n");
+    yaml.push_str("# null shows the movies logo (default: null)\n");
+    match config.splash_art {
+        Some(ref art) => yaml.push_str(&format!("splash_art: \"{}\"\n", art.replace('\\', "\\\\").replace('"', "\\\""))),
+        None => yaml.push_str("splash_art: null\n"),
+    }
+    yaml.push('\n');
+    
     // Logging configuration
     yaml.push_str("# === Logging Configuration ===\n");
     yaml.push_str("# Log file location\n");
@@ -169,6 +675,14 @@ pub fn generate_yaml_with_comments(config: &Config) -> String {
     yaml.push_str(&format!("log_level: {}\n", config.log_level));
     yaml.push('\n');
     
+    yaml.push_str("# Log rotation\n");
+    yaml.push_str("# The log of the last session is kept as movies.log.1, the one before as movies.log.2, and so on\n");
+    yaml.push_str("# log_max_size: megabytes the log grows to before a new one is started (0 for no limit)\n");
+    yaml.push_str("# log_keep_files: earlier logs kept; at least 1, so the last session's log is never lost\n");
+    yaml.push_str(&format!("log_max_size: {}\n", config.log_max_size));
+    yaml.push_str(&format!("log_keep_files: {}\n", config.log_keep_files));
+    yaml.push('\n');
+    
     // Progress tracking configuration
     yaml.push_str("# === Progress Tracking Configuration ===\n");
     yaml.push_str("# Percentage of episode completion that triggers automatic watched status\n");
@@ -190,12 +704,364 @@ pub fn generate_yaml_with_comments(config: &Config) -> String {
     
     yaml.push_str("# Path to external video player executable\n");
     yaml.push_str(&format!("video_player: {}\n", config.video_player));
+    yaml.push('\n');
+    
+    // Scan configuration
+    yaml.push_str("# === Scan Configuration ===\n");
+    yaml.push_str("# Skip video files smaller than this many megabytes during scans\n");
+    yaml.push_str("# Set to 0 to import files of any size (default: 0)\n");
+    yaml.push_str(&format!("min_file_size_mb: {}\n", config.min_file_size_mb));
+    yaml.push('\n');
+    
+    yaml.push_str("# Per-root overrides of the scan rules above\n");
+    yaml.push_str("# Each entry matches a library root by path; omitted fields use the global values\n");
+    yaml.push_str("# Example:\n");
+    yaml.push_str("#   root_overrides:\n");
+    yaml.push_str("#     - path: \"/media/home-videos\"\n");
+    yaml.push_str("#       video_extensions: [mp4, mov, mts]\n");
+    yaml.push_str("#       min_file_size_mb: 0\n");
+    if config.root_overrides.is_empty() {
+        yaml.push_str("root_overrides: []\n");
+    } else {
+        yaml.push_str("root_overrides:\n");
+        let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+        for root_override in &config.root_overrides {
+            yaml.push_str(&format!("  - path: {}\n", quote(&root_override.path)));
+            if let Some(ref extensions) = root_override.video_extensions {
+                let extensions: Vec<String> = extensions.iter().map(|ext| quote(ext)).collect();
+                yaml.push_str(&format!("    video_extensions: [{}]\n", extensions.join(", ")));
+            }
+            if let Some(min_size) = root_override.min_file_size_mb {
+                yaml.push_str(&format!("    min_file_size_mb: {}\n", min_size));
+            }
+        }
+    }
+    yaml.push('\n');
+    
+    yaml.push_str("# Rescan the library in the background every this many minutes while the app is open\n");
+    yaml.push_str("# Only a notification tells what was found; set to 0 to rescan only from the menu (default: 0)\n");
+    yaml.push_str(&format!("rescan_interval_minutes: {}\n", config.rescan_interval_minutes));
+    yaml.push('\n');
+    
+    yaml.push_str("# Follow symbolic links to files and folders while scanning (default: false)\n");
+    yaml.push_str("# A link leading out of the library folders keeps its path inside them\n");
+    yaml.push_str(&format!("scan_follow_symlinks: {}\n", config.scan_follow_symlinks));
+    yaml.push_str("# Import a file hard linked into several folders only once, at the path found first by name\n");
+    yaml.push_str("# (default: false)\n");
+    yaml.push_str(&format!("scan_dedupe_hardlinks: {}\n", config.scan_dedupe_hardlinks));
+    yaml.push('\n');
+    
+    yaml.push_str("# After a rescan, propose putting episodes in \"Show Name/Season 02/\" folders into that series\n");
+    yaml.push_str("# and season, applied with Enter on the scan report (default: false)\n");
+    yaml.push_str(&format!("infer_series_from_folders: {}\n", config.infer_series_from_folders));
+    yaml.push('\n');
+    
+    yaml.push_str("# Sample and trailer files, by name or by a video no longer than sample_max_seconds:\n");
+    yaml.push_str("# \"import\" them like any video, \"flag\" them as extras hidden from the library until\n");
+    yaml.push_str("# Show Extras, or \"skip\" them (default: \"flag\", 60 seconds; 0 to go by name only)\n");
+    yaml.push_str(&format!("sample_files: \"{}\"\n", config.sample_files));
+    yaml.push_str(&format!("sample_max_seconds: {}\n", config.sample_max_seconds));
+    yaml.push('\n');
+    
+    yaml.push_str("# Glob patterns of files and folders left out of scans, matched relative to the library root\n");
+    yaml.push_str("# * and ? stay within a name, ** spans folders; a pattern without / matches any single name\n");
+    yaml.push_str("# Letters match regardless of case\n");
+    yaml.push_str("# Example:\n");
+    yaml.push_str("#   scan_exclude:\n");
+    yaml.push_str("#     - \"**/extras/**\"\n");
+    yaml.push_str("#     - \"*sample*\"\n");
+    if config.scan_exclude.is_empty() {
+        yaml.push_str("scan_exclude: []\n");
+    } else {
+        yaml.push_str("scan_exclude:\n");
+        for pattern in &config.scan_exclude {
+            yaml.push_str(&format!("  - \"{}\"\n", pattern.replace('\\', "\\\\").replace('"', "\\\"")));
+        }
+    }
+    yaml.push('\n');
+    
+    yaml.push_str("# Other folders of the library besides the one holding the database, e.g. an external drive\n");
+    yaml.push_str("# Rescans look for videos in them too; while a folder isn't there its episodes stay in the library\n");
+    yaml.push_str("# Example:\n");
+    yaml.push_str("#   library_roots:\n");
+    yaml.push_str("#     - \"/media/external/movies\"\n");
+    if config.library_roots.is_empty() {
+        yaml.push_str("library_roots: []\n");
+    } else {
+        yaml.push_str("library_roots:\n");
+        for folder in &config.library_roots {
+            yaml.push_str(&format!("  - \"{}\"\n", folder.replace('\\', "\\\\").replace('"', "\\\"")));
+        }
+    }
+    yaml.push('\n');
+    
+    yaml.push_str("# Commands for archives (.rar/.zip) holding videos, offered for extraction after a rescan\n");
+    yaml.push_str("# {archive} is replaced with the archive path and {dir} with its directory\n");
+    yaml.push_str("# The list command prints one path per line (7z -slt \"Path = \" lines also work)\n");
+    yaml.push_str("# Example for unar: \"lsar {archive}\" and \"unar -f -D -o {dir} {archive}\"\n");
+    yaml.push_str("# Set the list command to \"\" to stop looking for archives\n");
+    yaml.push_str(&format!("archive_list_command: \"{}\"\n", config.archive_list_command));
+    yaml.push_str(&format!("archive_extract_command: \"{}\"\n", config.archive_extract_command));
+    yaml.push('\n');
+
+    yaml.push_str("# yt-dlp program that \"Add from URL\" downloads web videos with\n");
+    yaml.push_str("# Downloads are saved in download_folder, relative to the library root unless absolute\n");
+    yaml.push_str(&format!("ytdlp_path: \"{}\"\n", config.ytdlp_path.replace('\\', "\\\\").replace('"', "\\\"")));
+    yaml.push_str(&format!("download_folder: \"{}\"\n", config.download_folder.replace('\\', "\\\\").replace('"', "\\\"")));
+    yaml.push('\n');
+
+    yaml.push_str("# Commands of your own in the F1 menu, run on the selected episode with the output in the log\n");
+    yaml.push_str("# {path}, {title} and {series} are replaced with the episode's file, title and series name\n");
+    yaml.push_str("# hotkey is optional: a letter or F-key not used by anything else\n");
+    yaml.push_str("# Example:\n");
+    yaml.push_str("#   custom_commands:\n");
+    yaml.push_str("#     - label: \"check with mediainfo\"\n");
+    yaml.push_str("#       command: \"mediainfo {path}\"\n");
+    yaml.push_str("#       hotkey: \"i\"\n");
+    let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+    if config.custom_commands.is_empty() {
+        yaml.push_str("custom_commands: []\n");
+    } else {
+        yaml.push_str("custom_commands:\n");
+        for custom in &config.custom_commands {
+            yaml.push_str(&format!("  - label: {}\n", quote(&custom.label)));
+            yaml.push_str(&format!("    command: {}\n", quote(&custom.command)));
+            if let Some(ref hotkey) = custom.hotkey {
+                yaml.push_str(&format!("    hotkey: {}\n", quote(hotkey)));
+            }
+        }
+    }
+    yaml.push('\n');
+
+    yaml.push_str("# Rhai script run on events: fn on_import(episode), on_play(episode) and on_watched(episode)\n");
+    yaml.push_str("# A hook returning the episode saves its changed fields; relative to this file's folder\n");
+    yaml.push_str("# Only used when built with the scripting feature (default: null)\n");
+    match config.hook_script {
+        Some(ref script) => yaml.push_str(&format!("hook_script: {}\n", quote(script))),
+        None => yaml.push_str("hook_script: null\n"),
+    }
+    yaml.push('\n');
+    
+    // Navigation configuration
+    yaml.push_str("# === Navigation Configuration ===\n");
+    yaml.push_str("# Rows moved per key press while Up/Down is held, growing from left to right\n");
+    yaml.push_str("# Use [1] to disable acceleration (default: [1, 3, 10])\n");
+    let steps: Vec<String> = config.scroll_acceleration.iter().map(|s| s.to_string()).collect();
+    yaml.push_str(&format!("scroll_acceleration: [{}]\n", steps.join(", ")));
+    yaml.push_str("# Number of key repeats before moving to the next step (default: 8)\n");
+    yaml.push_str(&format!("scroll_acceleration_repeats: {}\n", config.scroll_acceleration_repeats));
+    yaml.push_str("# Rows kept visible above and below the selection before the list scrolls (default: 2)\n");
+    yaml.push_str(&format!("scroll_margin: {}\n", config.scroll_margin));
+    yaml.push_str("# Percent of the terminal width taken by the list, changed with Ctrl+Left/Right while browsing\n");
+    yaml.push_str("# null keeps the list 45 columns wide (default: null)\n");
+    match config.list_split {
+        Some(split) => yaml.push_str(&format!("list_split: {}\n", split)),
+        None => yaml.push_str("list_split: null\n"),
+    }
+    yaml.push('\n');
+    
+    yaml.push_str("# Click to select, double-click to open or play, and scroll with the wheel in the list and menu\n");
+    yaml.push_str("# Capturing the mouse disables the terminal's own text selection (default: false)\n");
+    yaml.push_str(&format!("mouse: {}\n", config.mouse));
+    yaml.push('\n');
+    
+    yaml.push_str("# Browse order at startup, cycled with [O]: default, name, year, date_added,\n");
+    yaml.push_str("# episode_number, watched, file_size, recently_watched, most_played (default: default)\n");
+    yaml.push_str(&format!("default_sort: \"{}\"\n", config.default_sort));
+    yaml.push('\n');
+    
+    yaml.push_str("# Open on the series or season, filter and selection left at the last exit (default: true)\n");
+    yaml.push_str(&format!("restore_session: {}\n", config.restore_session));
+    yaml.push('\n');
+    
+    yaml.push_str("# Ask for a yes before Delete, Unwatch all and Clear series data (default: true)\n");
+    yaml.push_str("# Delete File always asks, as files that can't go to a trash are deleted for good\n");
+    yaml.push_str(&format!("confirm_delete: {}\n", config.confirm_delete));
+    yaml.push_str(&format!("confirm_unwatch_all: {}\n", config.confirm_unwatch_all));
+    yaml.push_str(&format!("confirm_clear_series: {}\n", config.confirm_clear_series));
+    yaml.push('\n');
+    
+    yaml.push_str("# Listen on a Unix domain socket for commands such as \"play 42\" or \"rescan\", and send events\n");
+    yaml.push_str("# to the programs connected, e.g. a status bar widget (default: false)\n");
+    yaml.push_str(&format!("remote_control: {}\n", config.remote_control));
+    yaml.push_str("# Socket to listen on; null for movies.sock in the data directory (default: null)\n");
+    match config.remote_socket {
+        Some(ref socket) => yaml.push_str(&format!("remote_socket: \"{}\"\n", socket.replace('\\', "\\\\").replace('"', "\\\""))),
+        None => yaml.push_str("remote_socket: null\n"),
+    }
+    yaml.push('\n');
+    
+    yaml.push_str("# What the library opens on: top_level, last_session, continue_watching (the view of the\n");
+    yaml.push_str("# episode played last, at the next unwatched one) or series:<name> (default: last_session)\n");
+    yaml.push_str(&format!("startup_view: \"{}\"\n", config.startup_view.replace('\\', "\\\\").replace('"', "\\\"")));
+    yaml.push('\n');
+    
+    yaml.push_str("# Filters pinned with the menu's \"Pin filter\" action, toggled with [1]-[3] while browsing\n");
+    yaml.push_str("# Expressions combine name text with: watched, unwatched, <30min, >90min, >2h\n");
+    if config.pinned_filters.is_empty() {
+        yaml.push_str("pinned_filters: []\n");
+    } else {
+        yaml.push_str("pinned_filters:\n");
+        for expression in &config.pinned_filters {
+            yaml.push_str(&format!("  - \"{}\"\n", expression.replace('\\', "\\\\").replace('"', "\\\"")));
+        }
+    }
+    yaml.push('\n');
+    
+    // Title language configuration
+    yaml.push_str("# === Alternate Titles ===\n");
+    yaml.push_str("# Language code whose alternate title is shown in the browser (e.g. en, ja)\n");
+    yaml.push_str("# Episodes without a title in that language keep their own name; null to disable\n");
+    match config.preferred_title_language {
+        Some(ref language) => yaml.push_str(&format!("preferred_title_language: \"{}\"\n", language)),
+        None => yaml.push_str("preferred_title_language: null\n"),
+    }
+    yaml.push('\n');
+    
+    // Version configuration
+    yaml.push_str("# === Versions ===\n");
+    yaml.push_str("# Version preselected when an episode has several files (e.g. 4K, 1080p, Director's Cut)\n");
+    yaml.push_str("# Matched against the version labels; null to preselect the episode's own file\n");
+    match config.preferred_version {
+        Some(ref version) => yaml.push_str(&format!("preferred_version: \"{}\"\n", version.replace('\\', "\\\\").replace('"', "\\\""))),
+        None => yaml.push_str("preferred_version: null\n"),
+    }
+    yaml.push('\n');
+    
+    // Rename configuration
+    yaml.push_str("# === Renaming Files ===\n");
+    yaml.push_str("# Names given to a file with [F3] in the editor, in the folder it is in now\n");
+    yaml.push_str("# Placeholders: {series} {season} {episode} {title} {year} {ext}; {season:02} pads with zeros\n");
+    yaml.push_str("# rename_template is used for episodes in a series, rename_movie_template for the others\n");
+    yaml.push_str(&format!("rename_template: \"{}\"\n", config.rename_template.replace('\\', "\\\\").replace('"', "\\\"")));
+    yaml.push_str(&format!("rename_movie_template: \"{}\"\n", config.rename_movie_template.replace('\\', "\\\\").replace('"', "\\\"")));
+    yaml.push('\n');
+    
+    // Artwork configuration
+    yaml.push_str("# === Artwork ===\n");
+    yaml.push_str("# Show a poster below the details in terminals that support kitty or sixel graphics\n");
+    yaml.push_str("# Posters are PNG files next to the videos, e.g. poster.png or <video name>.png (default: false)\n");
+    yaml.push_str(&format!("artwork: {}\n", config.artwork));
+    yaml.push('\n');
+    
+    // Digest configuration
+    yaml.push_str("# === Weekly Digest ===\n");
+    yaml.push_str("# Show new items, nearly finished and stale series, and last week's watch time\n");
+    yaml.push_str("# on the first launch of each week (default: false)\n");
+    yaml.push_str(&format!("weekly_digest: {}\n", config.weekly_digest));
+    yaml.push('\n');
+    
+    // Accessibility configuration
+    yaml.push_str("# === Accessibility ===\n");
+    yaml.push_str("# Draw borders and indicators in plain ASCII ([WATCHED], -----), use fewer colors, and leave\n");
+    yaml.push_str("# the cursor on the selected entry for screen readers (default: false)\n");
+    yaml.push_str(&format!("accessible: {}\n", config.accessible));
+    yaml.push('\n');
+    
+    // Keybindings configuration
+    yaml.push_str("# === Keybindings ===\n");
+    yaml.push_str(&format!("# Preset the keys start from: {} (default: default)\n", crate::keymap::preset_names().join(", ")));
+    yaml.push_str("# vim adds gg/G for the top and bottom, Ctrl+D/U for half pages, n/N to jump between filter\n");
+    yaml.push_str("# matches and : to run an action by name; the grid moves to Ctrl+G and recent messages to M\n");
+    yaml.push_str("# Keys for the Browse actions, replacing their default keys; [] leaves an action without keys:\n");
+    yaml.push_str(&comment_list(&crate::keymap::browse_action_names()));
+    yaml.push_str("# Menu hotkeys by action name (letters or F-keys; null removes the hotkey):\n");
+    let mut menu_names: Vec<&str> = crate::menu::all_menu_items()
+        .iter()
+        .filter(|item| !matches!(item.action, crate::menu::MenuAction::Custom(_)))
+        .map(|item| item.action.usage_key())
+        .collect();
+    menu_names.dedup();
+    yaml.push_str(&comment_list(&menu_names));
+    yaml.push_str("# Keys are written like \"g\", \"F2\", \"Space\", \"PgDn\", \"Ctrl+Left\" or \"g g\" for a sequence; 1-3 and Alt+1-9 are kept\n");
+    yaml.push_str("# for pinned filters and tabs, and a key bound twice keeps the action's default\n");
+    yaml.push_str("# Example:\n");
+    yaml.push_str("#   keybindings:\n");
+    yaml.push_str("#     preset: vim\n");
+    yaml.push_str("#     browse:\n");
+    yaml.push_str("#       toggle_grid: \"Ctrl+t\"\n");
+    yaml.push_str("#       move_down: [\"Down\", \"j\", \"Ctrl+n\"]\n");
+    yaml.push_str("#     menu:\n");
+    yaml.push_str("#       rescan: \"r\"\n");
+    let quote = |key: &str| format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""));
+    if config.keybindings.is_empty() {
+        yaml.push_str("keybindings: {}\n");
+    } else {
+        yaml.push_str("keybindings:\n");
+        if let Some(ref preset) = config.keybindings.preset {
+            yaml.push_str(&format!("  preset: {}\n", quote(preset)));
+        }
+        if !config.keybindings.browse.is_empty() {
+            yaml.push_str("  browse:\n");
+            for (name, keys) in &config.keybindings.browse {
+                match keys {
+                    KeyList::One(key) => yaml.push_str(&format!("    {}: {}\n", name, quote(key))),
+                    KeyList::Many(keys) => {
+                        let keys: Vec<String> = keys.iter().map(|key| quote(key)).collect();
+                        yaml.push_str(&format!("    {}: [{}]\n", name, keys.join(", ")));
+                    }
+                }
+            }
+        }
+        if !config.keybindings.menu.is_empty() {
+            yaml.push_str("  menu:\n");
+            for (name, key) in &config.keybindings.menu {
+                match key {
+                    Some(key) => yaml.push_str(&format!("    {}: {}\n", name, quote(key))),
+                    None => yaml.push_str(&format!("    {}: null\n", name)),
+                }
+            }
+        }
+    }
+    yaml.push('\n');
+    
+    // Trakt sync configuration
+    yaml.push_str("# === Trakt Sync Configuration ===\n");
+    yaml.push_str("# Credentials of your Trakt API application (https://trakt.tv/oauth/applications)\n");
+    yaml.push_str("# Only used when built with the trakt feature; leave null to disable\n");
+    match config.trakt_client_id {
+        Some(ref client_id) => yaml.push_str(&format!("trakt_client_id: \"{}\"\n", client_id)),
+        None => yaml.push_str("trakt_client_id: null\n"),
+    }
+    match config.trakt_client_secret {
+        Some(ref client_secret) => yaml.push_str(&format!("trakt_client_secret: \"{}\"\n", client_secret)),
+        None => yaml.push_str("trakt_client_secret: null\n"),
+    }
+    yaml.push('\n');
+    
+    // Jellyfin sync configuration
+    yaml.push_str("# === Jellyfin Sync Configuration ===\n");
+    yaml.push_str("# Address of your Jellyfin server, e.g. \"http://192.168.1.10:8096\", and an API key made under\n");
+    yaml.push_str("# Dashboard > API Keys; jellyfin_user is the user to sync, null for the server's first user\n");
+    yaml.push_str("# Only used when built with the jellyfin feature; leave null to disable\n");
+    let optional = |value: &Option<String>| match value {
+        Some(value) => format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"")),
+        None => "null".to_string(),
+    };
+    yaml.push_str(&format!("jellyfin_url: {}\n", optional(&config.jellyfin_url)));
+    yaml.push_str(&format!("jellyfin_api_key: {}\n", optional(&config.jellyfin_api_key)));
+    yaml.push_str(&format!("jellyfin_user: {}\n", optional(&config.jellyfin_user)));
     
     yaml
 }
 
 
 
+/// Names listed in a config comment, wrapped to lines of about 90 columns
+fn comment_list(names: &[&str]) -> String {
+    let mut lines = vec![String::from("#  ")];
+    for (index, name) in names.iter().enumerate() {
+        let separator = if index + 1 < names.len() { "," } else { "" };
+        if lines.last().is_some_and(|line| line.len() + name.len() > 90) {
+            lines.push(String::from("#  "));
+        }
+        if let Some(line) = lines.last_mut() {
+            line.push_str(&format!(" {}{}", name, separator));
+        }
+    }
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
 /// Save configuration to file
 pub fn save_config(config: &Config, config_path: &PathBuf) {
     let yaml_content = generate_yaml_with_comments(config);
@@ -0,0 +1,202 @@
+//! Checking config.yaml for settings that won't be used as they are written
+//!
+//! Unknown keys, values a setting doesn't take and a video player that isn't installed are
+//! reported as problems, each saying what to change. The app still starts with them, using the
+//! default in place of a bad value, and lists them in a panel when it opens.
+
+use crate::config::Config;
+use crate::extras::SampleFiles;
+use crate::rename::{self, RenameFields};
+use crate::session::StartupView;
+use crate::util::SortMode;
+use std::path::Path;
+
+/// Settings left out of a saved config while they are unset
+const OPTIONAL_KEYS: [&str; 12] = [
+    "db_location",
+    "hook_script",
+    "jellyfin_api_key",
+    "jellyfin_url",
+    "jellyfin_user",
+    "list_split",
+    "preferred_title_language",
+    "preferred_version",
+    "remote_socket",
+    "splash_art",
+    "trakt_client_id",
+    "trakt_client_secret",
+];
+
+const LOG_LEVELS: [&str; 4] = ["error", "warn", "info", "debug"];
+
+/// Every top-level key config.yaml can have
+pub fn known_keys() -> Vec<String> {
+    let mut keys: Vec<String> = match serde_yaml::to_value(Config::default()) {
+        Ok(serde_yaml::Value::Mapping(mapping)) => {
+            mapping.keys().filter_map(|key| key.as_str().map(str::to_string)).collect()
+        }
+        _ => Vec::new(),
+    };
+    keys.extend(OPTIONAL_KEYS.iter().map(|key| key.to_string()));
+    keys
+}
+
+/// Problems with the config read from `content`, which `config` was parsed from
+pub fn check_config(content: &str, config: &Config) -> Vec<String> {
+    let mut problems = unknown_keys(content);
+    problems.extend(check_values(config));
+    problems
+}
+
+/// A problem for each top-level key of `content` that isn't a setting, such as a misspelled one
+pub fn unknown_keys(content: &str) -> Vec<String> {
+    let Ok(serde_yaml::Value::Mapping(mapping)) = serde_yaml::from_str::<serde_yaml::Value>(content) else {
+        return Vec::new();
+    };
+    let known = known_keys();
+    mapping
+        .keys()
+        .filter_map(|key| key.as_str())
+        .filter(|key| !known.iter().any(|known| known == key))
+        .map(|key| match closest_key(key, &known) {
+            Some(suggestion) => format!("Unknown setting '{}' is ignored; did you mean '{}'?", key, suggestion),
+            None => format!("Unknown setting '{}' is ignored; remove it from config.yaml", key),
+        })
+        .collect()
+}
+
+/// Problems with the values of `config`'s settings
+pub fn check_values(config: &Config) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if config.video_player.trim().is_empty() {
+        problems.push("video_player is empty; set it to a player such as /usr/bin/vlc or mpv".to_string());
+    } else if !player_exists(&config.video_player) {
+        problems.push(format!(
+            "Video player '{}' was not found; install it or point video_player at a player that is",
+            config.video_player
+        ));
+    }
+
+    if config.video_extensions.is_empty() {
+        problems.push("video_extensions is empty, so scans find no videos; list extensions such as mp4 and mkv".to_string());
+    }
+    let overrides = config.root_overrides.iter().filter_map(|o| o.video_extensions.as_ref());
+    for extension in config.video_extensions.iter().chain(overrides.flatten()) {
+        if let Some(problem) = extension_problem(extension) {
+            problems.push(problem);
+        }
+    }
+
+    if !(1..=100).contains(&config.watched_threshold) {
+        problems.push(format!(
+            "watched_threshold {} is not between 1 and 100; 95 is used instead",
+            config.watched_threshold
+        ));
+    }
+    if !LOG_LEVELS.contains(&config.log_level.to_lowercase().as_str()) {
+        problems.push(format!(
+            "Unknown log_level '{}'; use error, warn, info or debug (info is used instead)",
+            config.log_level
+        ));
+    }
+    if SampleFiles::from_config(&config.sample_files).is_none() {
+        problems.push(format!(
+            "Unknown sample_files '{}'; use import, flag or skip (flag is used instead)",
+            config.sample_files
+        ));
+    }
+    if SortMode::from_config(&config.default_sort).is_none() {
+        problems.push(format!(
+            "Unknown default_sort '{}'; use name, year, date_added, episode_number, watched, file_size, recently_watched or most_played",
+            config.default_sort
+        ));
+    }
+    if StartupView::from_config(&config.startup_view).is_none() {
+        problems.push(format!(
+            "Unknown startup_view '{}'; use top_level, last_session, continue_watching or series:<name> (last_session is used instead)",
+            config.startup_view
+        ));
+    }
+    for (key, template) in [
+        ("rename_template", &config.rename_template),
+        ("rename_movie_template", &config.rename_movie_template),
+    ] {
+        if let Err(e) = rename::render_template(template, &sample_fields()) {
+            problems.push(format!("{} can't be used: {}", key, e));
+        }
+    }
+    problems
+}
+
+/// What is wrong with an entry of `video_extensions`, None when it is fine
+fn extension_problem(extension: &str) -> Option<String> {
+    let trimmed = extension.trim();
+    if trimmed.is_empty() {
+        Some("video_extensions has an empty entry; remove it".to_string())
+    } else if let Some(without_dot) = trimmed.strip_prefix('.') {
+        Some(format!(
+            "Video extension '{}' never matches; write it without the dot, as '{}'",
+            extension, without_dot
+        ))
+    } else if trimmed != extension || trimmed.contains(|c: char| c.is_whitespace() || c == '/' || c == '\\' || c == '*') {
+        Some(format!(
+            "Video extension '{}' never matches; write only the letters after the dot, e.g. mkv",
+            extension
+        ))
+    } else {
+        None
+    }
+}
+
+/// True when `command` names a file, either as a path or as a program on the PATH
+pub fn player_exists(command: &str) -> bool {
+    let command = command.trim();
+    if command.contains(['/', '\\']) {
+        return Path::new(command).is_file();
+    }
+    let Some(search_path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&search_path).any(|folder| {
+        folder.join(command).is_file() || (cfg!(windows) && folder.join(format!("{}.exe", command)).is_file())
+    })
+}
+
+/// The known key closest to a misspelled `key`, if one is close enough to be meant
+fn closest_key<'a>(key: &str, known: &'a [String]) -> Option<&'a str> {
+    let key = key.to_lowercase();
+    known
+        .iter()
+        .map(|candidate| (edit_distance(&key, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= (candidate.len() / 4).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+/// Number of characters to insert, remove or change to turn `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let change = previous[j] + usize::from(a_char != *b_char);
+            current.push(change.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Values the rename templates are tried with
+fn sample_fields() -> RenameFields {
+    RenameFields {
+        series: Some("Series".to_string()),
+        season: Some(1),
+        episode: Some(1),
+        title: "Title".to_string(),
+        year: "2000".to_string(),
+        ext: "mkv".to_string(),
+    }
+}
@@ -0,0 +1,148 @@
+//! Commands of the user's own in the F1 menu
+//!
+//! Each entry of `custom_commands` in the config becomes a menu item running its command on the
+//! selected episode, such as `mediainfo {path}`. Like the archive commands, the template is split
+//! on whitespace and `{path}`, `{title}` and `{series}` are replaced in each argument, so a title
+//! with spaces stays one argument. The command runs on a background thread; what it prints goes
+//! to the log and the main loop hears how it ended as `AppEvent::CommandFinished`.
+
+use crate::config::CustomCommand;
+use crate::keymap::{Key, Keymap};
+use crate::logger;
+use crate::util::AppEvent;
+use crossterm::event::KeyCode;
+use std::ffi::{OsStr, OsString};
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::Sender;
+use std::sync::RwLock;
+use std::thread;
+
+/// A custom command as the menu offers it, with its hotkey checked
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuCommand {
+    pub label: String,
+    pub template: String,
+    pub hotkey: Option<KeyCode>,
+}
+
+static COMMANDS: RwLock<Vec<MenuCommand>> = RwLock::new(Vec::new());
+
+/// Check the configured `commands` and install them for the menu
+///
+/// Also returns a message for each command left out for missing its label or command, and for
+/// each hotkey left out for not being a letter or function key, or for a key `keymap` or an
+/// earlier command already uses. A command whose hotkey is left out is still in the menu.
+pub fn install(commands: &[CustomCommand], keymap: &Keymap) -> Vec<String> {
+    // The keymap looks through the menu for the keys in use, so the old commands go first
+    *COMMANDS.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Vec::new();
+    let mut problems = Vec::new();
+    let mut installed: Vec<MenuCommand> = Vec::new();
+
+    for command in commands {
+        let label = command.label.trim();
+        if label.is_empty() {
+            problems.push(format!("The custom command '{}' has no label", command.command.trim()));
+            continue;
+        }
+        if command.command.trim().is_empty() {
+            problems.push(format!("The custom command '{}' has no command", label));
+            continue;
+        }
+        let hotkey = match command.hotkey.as_deref().map(Key::parse) {
+            None => None,
+            Some(Ok(Key { code: code @ (KeyCode::Char(_) | KeyCode::F(_)), modifiers })) if modifiers.is_empty() => {
+                let earlier = installed.iter().find(|other| other.hotkey == Some(code)).map(|other| other.label.clone());
+                match earlier.or_else(|| keymap.hotkey_owner(code)) {
+                    Some(owner) => {
+                        let key = Key { code, modifiers }.label();
+                        problems.push(format!("{} is bound to both {} and {}; {} has no hotkey", key, label, owner, label));
+                        None
+                    }
+                    None => Some(code),
+                }
+            }
+            Some(Ok(_)) => {
+                problems.push(format!("The hotkey for {} must be a letter or function key", label));
+                None
+            }
+            Some(Err(message)) => {
+                problems.push(format!("{} for {} in custom_commands", message, label));
+                None
+            }
+        };
+        installed.push(MenuCommand { label: label.to_string(), template: command.command.trim().to_string(), hotkey });
+    }
+
+    *COMMANDS.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = installed;
+    problems
+}
+
+/// The commands installed by `install`, in the order of the config
+pub fn installed() -> Vec<MenuCommand> {
+    COMMANDS.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Build a command from a template, replacing {path}, {title} and {series} in each argument
+pub fn build_command(template: &str, path: &Path, title: &str, series: &str) -> Option<Command> {
+    let mut parts = template.split_whitespace().map(|part| expand(part, path, title, series));
+    let mut command = Command::new(parts.next()?);
+    command.args(parts);
+    Some(command)
+}
+
+/// Replace the placeholders in one argument, reading it left to right so a title or series
+/// containing `{path}` is passed as written, and the path is passed as it is on disk
+fn expand(part: &str, path: &Path, title: &str, series: &str) -> OsString {
+    let mut expanded = OsString::new();
+    let mut rest = part;
+    while let Some(start) = rest.find('{') {
+        expanded.push(&rest[..start]);
+        rest = &rest[start..];
+        let placeholder = [("{path}", path.as_os_str()), ("{title}", OsStr::new(title)), ("{series}", OsStr::new(series))]
+            .into_iter()
+            .find(|(name, _)| rest.starts_with(name));
+        match placeholder {
+            Some((name, value)) => {
+                expanded.push(value);
+                rest = &rest[name.len()..];
+            }
+            None => {
+                expanded.push("{");
+                rest = &rest[1..];
+            }
+        }
+    }
+    expanded.push(rest);
+    expanded
+}
+
+/// Run `command` on a background thread, logging its output and telling the main loop over `tx` how it ended
+pub fn spawn(label: String, command: Command, tx: Sender<AppEvent>) {
+    thread::spawn(move || {
+        let result = run(&label, command);
+        let _ = tx.send(AppEvent::CommandFinished { label, result });
+    });
+}
+
+/// Run `command` to the end, writing what it printed to the log under `label`
+pub fn run(label: &str, mut command: Command) -> Result<(), String> {
+    let program = command.get_program().to_string_lossy().to_string();
+    logger::log_info(&format!("Running {}: {:?}", label, command));
+    let output = command.stdin(Stdio::null()).output().map_err(|e| match e.kind() {
+        ErrorKind::NotFound => format!("{} not found; check custom_commands in config.yaml", program),
+        _ => format!("Couldn't run {}: {}", program, e),
+    })?;
+    for line in String::from_utf8_lossy(&output.stdout).lines().filter(|line| !line.trim().is_empty()) {
+        logger::log_info(&format!("[{}] {}", label, line));
+    }
+    for line in String::from_utf8_lossy(&output.stderr).lines().filter(|line| !line.trim().is_empty()) {
+        logger::log_warn(&format!("[{}] {}", label, line));
+    }
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} exited with {}", program, output.status))
+    }
+}
@@ -1,9 +1,82 @@
-use crate::dto::{EpisodeDetail, Season, Series};
-use crate::path_resolver::PathResolver;
-use crate::util::Entry;
+use crate::digest::{Digest, DigestItem, DigestTarget, DIGEST_SECTION_SIZE, STALE_SERIES_DAYS};
+use crate::dto::{AltTitles, EpisodeDetail, Season, Series};
+use crate::duplicates::DuplicateCopy;
+use crate::export::{
+    describe_changes, merge_record, parse_csv, parse_json, records_to_csv, records_to_json, ExportFormat, ImportReport,
+    LibraryRecord,
+};
+use crate::folder_series::SeriesProposal;
+use crate::location;
+use crate::path_resolver::{PathResolver, PathResolverError};
+use crate::query::EpisodeFacts;
+use crate::util::{CategoryCounts, Entry, EntryKey, SortKeys, ViewContext};
+use crate::version::{version_label, EpisodeVersion};
+use crate::watch_import::{common_suffix_len, file_name_of, WatchImportReport, WatchRecord};
 use rusqlite::{params, Connection, Result};
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
 use std::path::Path;
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Errors returned by the database module, surfaced to the user on the status line
+#[derive(Debug)]
+pub enum DatabaseError {
+    EpisodeNotFound(usize),
+    Import(String),
+    Path(PathResolverError),
+    Io(io::Error),
+    Sqlite(rusqlite::Error),
+}
+
+impl fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatabaseError::EpisodeNotFound(id) => write!(f, "Episode {} not found", id),
+            DatabaseError::Import(message) => write!(f, "Import failed: {}", message),
+            DatabaseError::Path(err) => write!(f, "{}", err),
+            DatabaseError::Io(err) => write!(f, "IO error: {}", err),
+            DatabaseError::Sqlite(err) => write!(f, "Database error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DatabaseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DatabaseError::Path(err) => Some(err),
+            DatabaseError::Io(err) => Some(err),
+            DatabaseError::Sqlite(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for DatabaseError {
+    fn from(err: rusqlite::Error) -> Self {
+        DatabaseError::Sqlite(err)
+    }
+}
+
+impl From<io::Error> for DatabaseError {
+    fn from(err: io::Error) -> Self {
+        DatabaseError::Io(err)
+    }
+}
+
+impl From<PathResolverError> for DatabaseError {
+    fn from(err: PathResolverError) -> Self {
+        DatabaseError::Path(err)
+    }
+}
+
+impl From<serde_json::Error> for DatabaseError {
+    fn from(err: serde_json::Error) -> Self {
+        DatabaseError::Import(err.to_string())
+    }
+}
+
+pub type DbResult<T> = std::result::Result<T, DatabaseError>;
 
 /// Format ISO 8601 datetime string to human-readable format
 pub fn format_last_watched_time(iso_datetime: &str) -> String {
@@ -16,27 +89,35 @@ pub fn format_last_watched_time(iso_datetime: &str) -> String {
     }
 }
 
-static DB_CONN: OnceLock<Mutex<Connection>> = OnceLock::new();
+/// Location of an episode as handed out of the database: relative to the library root, or
+/// absolute under another folder of the library, which `PathResolver::to_absolute` keeps as it is
+const EPISODE_LOCATION: &str = "COALESCE(root.path || '/' || episode.location, episode.location)";
 
-/// Initialize the database connection and schema
-pub fn initialize_database(db_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    // Create parent directory if it doesn't exist
-    if let Some(parent) = db_path.parent() {
-        if let Err(e) = std::fs::create_dir_all(parent) {
-            crate::logger::log_error(&format!("Failed to create database directory {}: {}", parent.display(), e));
-            return Err(e.into());
-        }
-    }
-    
-    // Open or create database connection
-    let conn = match Connection::open(db_path) {
-        Ok(c) => c,
-        Err(e) => {
-            crate::logger::log_error(&format!("Failed to open database at {}: {}", db_path.display(), e));
-            return Err(e.into());
-        }
-    };
-    
+/// Join that `EPISODE_LOCATION` needs
+const ROOT_JOIN: &str = "LEFT JOIN root ON root.id = episode.root_id";
+
+/// Handle to an open library database
+pub struct Database {
+    conn: Mutex<Connection>,
+    /// Reads made for the list, kept until the next write to the database
+    read_cache: Mutex<ReadCache>,
+}
+
+/// Writes made by `Database::write_together`, saved all at once or not at all
+pub struct Writes<'a> {
+    conn: &'a Connection,
+}
+
+#[derive(Default)]
+struct ReadCache {
+    /// `change_count` when the cached reads were made
+    changes: i64,
+    details: HashMap<usize, EpisodeDetail>,
+    category_counts: Option<Arc<HashMap<EntryKey, CategoryCounts>>>,
+}
+
+/// Create tables, add newer columns, and clean up orphaned rows
+fn apply_schema(conn: &Connection) -> DbResult<()> {
     // Initialize schema
     if let Err(e) = conn.execute(
         "CREATE TABLE IF NOT EXISTS series (
@@ -48,7 +129,7 @@ pub fn initialize_database(db_path: &Path) -> Result<(), Box<dyn std::error::Err
         crate::logger::log_error(&format!("Failed to create series table: {}", e));
         return Err(e.into());
     }
-    
+
     if let Err(e) = conn.execute(
         "CREATE TABLE IF NOT EXISTS season (
             id INTEGER PRIMARY KEY,
@@ -61,7 +142,7 @@ pub fn initialize_database(db_path: &Path) -> Result<(), Box<dyn std::error::Err
         crate::logger::log_error(&format!("Failed to create season table: {}", e));
         return Err(e.into());
     }
-    
+
     if let Err(e) = conn.execute(
         "CREATE TABLE IF NOT EXISTS episode (
             id INTEGER PRIMARY KEY,
@@ -81,7 +162,7 @@ pub fn initialize_database(db_path: &Path) -> Result<(), Box<dyn std::error::Err
         crate::logger::log_error(&format!("Failed to create episode table: {}", e));
         return Err(e.into());
     }
-    
+
     // Progress tracking schema migration - add new columns if they don't exist
     if let Err(e) = conn.execute(
         "ALTER TABLE episode ADD COLUMN last_watched_time TEXT",
@@ -93,7 +174,7 @@ pub fn initialize_database(db_path: &Path) -> Result<(), Box<dyn std::error::Err
             return Err(e.into());
         }
     }
-    
+
     if let Err(e) = conn.execute(
         "ALTER TABLE episode ADD COLUMN last_progress_time INTEGER",
         [],
@@ -104,13 +185,116 @@ pub fn initialize_database(db_path: &Path) -> Result<(), Box<dyn std::error::Err
             return Err(e.into());
         }
     }
-    
+
+    // Number of times an episode has been played
+    if let Err(e) = conn.execute(
+        "ALTER TABLE episode ADD COLUMN play_count INTEGER NOT NULL DEFAULT 0",
+        [],
+    ) {
+        if !e.to_string().contains("duplicate column name") {
+            crate::logger::log_error(&format!("Failed to add play_count column: {}", e));
+            return Err(e.into());
+        }
+    }
+
+    // Freeform episode notes
+    if let Err(e) = conn.execute(
+        "ALTER TABLE episode ADD COLUMN notes TEXT",
+        [],
+    ) {
+        if !e.to_string().contains("duplicate column name") {
+            crate::logger::log_error(&format!("Failed to add notes column: {}", e));
+            return Err(e.into());
+        }
+    }
+
+    // Alternate titles by language, stored as a JSON object
+    if let Err(e) = conn.execute(
+        "ALTER TABLE episode ADD COLUMN alt_titles TEXT",
+        [],
+    ) {
+        if !e.to_string().contains("duplicate column name") {
+            crate::logger::log_error(&format!("Failed to add alt_titles column: {}", e));
+            return Err(e.into());
+        }
+    }
+
+    // When the episode was first imported, used by the weekly digest
+    if let Err(e) = conn.execute(
+        "ALTER TABLE episode ADD COLUMN added_time TEXT",
+        [],
+    ) {
+        if !e.to_string().contains("duplicate column name") {
+            crate::logger::log_error(&format!("Failed to add added_time column: {}", e));
+            return Err(e.into());
+        }
+    }
+
+    // File size in bytes, used by the file size sort; filled on import and by rescans
+    if let Err(e) = conn.execute(
+        "ALTER TABLE episode ADD COLUMN file_size INTEGER",
+        [],
+    ) {
+        if !e.to_string().contains("duplicate column name") {
+            crate::logger::log_error(&format!("Failed to add file_size column: {}", e));
+            return Err(e.into());
+        }
+    }
+
+    // Sample and trailer files imported by scans, kept out of the library's views
+    if let Err(e) = conn.execute(
+        "ALTER TABLE episode ADD COLUMN extra BOOLEAN NOT NULL DEFAULT 0",
+        [],
+    ) {
+        if !e.to_string().contains("duplicate column name") {
+            crate::logger::log_error(&format!("Failed to add extra column: {}", e));
+            return Err(e.into());
+        }
+    }
+
+    // Alternate files of an episode (other resolutions or editions); watched state stays on the episode
+    if let Err(e) = conn.execute(
+        "CREATE TABLE IF NOT EXISTS version (
+            id INTEGER PRIMARY KEY,
+            episode_id INTEGER NOT NULL,
+            location TEXT NOT NULL UNIQUE,
+            label TEXT NOT NULL,
+            FOREIGN KEY(episode_id) REFERENCES episode(id)
+        )",
+        [],
+    ) {
+        crate::logger::log_error(&format!("Failed to create version table: {}", e));
+        return Err(e.into());
+    }
+
+    // Other folders of the library, such as an external drive; episodes under the
+    // database's own directory have no root_id
+    if let Err(e) = conn.execute(
+        "CREATE TABLE IF NOT EXISTS root (
+            id INTEGER PRIMARY KEY,
+            path TEXT NOT NULL UNIQUE
+        )",
+        [],
+    ) {
+        crate::logger::log_error(&format!("Failed to create root table: {}", e));
+        return Err(e.into());
+    }
+
+    for table in ["episode", "version"] {
+        if let Err(e) = conn.execute(&format!("ALTER TABLE {} ADD COLUMN root_id INTEGER REFERENCES root(id)", table), []) {
+            if !e.to_string().contains("duplicate column name") {
+                crate::logger::log_error(&format!("Failed to add root_id column to {}: {}", table, e));
+                return Err(e.into());
+            }
+        }
+    }
+
     // Data cleanup operations
     conn.execute(
         "UPDATE episode SET season_id = NULL WHERE series_id IS NULL",
         [],
     )?;
-    
+
     conn.execute(
         "UPDATE episode 
          SET season_id = NULL 
@@ -118,12 +302,24 @@ pub fn initialize_database(db_path: &Path) -> Result<(), Box<dyn std::error::Err
          AND (SELECT series_id FROM season WHERE id = episode.season_id) != episode.series_id",
         [],
     )?;
-    
+
     conn.execute(
         "UPDATE episode SET episode_number = NULL WHERE season_id IS NULL",
         [],
     )?;
-    
+
+    delete_orphaned_series(conn)?;
+
+    conn.execute(
+        "DELETE FROM version WHERE episode_id NOT IN (SELECT id FROM episode)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Delete the series and seasons no episode belongs to
+fn delete_orphaned_series(conn: &Connection) -> Result<()> {
     conn.execute(
         "DELETE FROM season 
          WHERE series_id IN (
@@ -132,745 +328,1842 @@ pub fn initialize_database(db_path: &Path) -> Result<(), Box<dyn std::error::Err
          )",
         [],
     )?;
-    
+
     conn.execute(
         "DELETE FROM series 
          WHERE id NOT IN (SELECT DISTINCT series_id FROM episode WHERE series_id IS NOT NULL)",
         [],
     )?;
-    
+
     conn.execute(
         "DELETE FROM season 
          WHERE id NOT IN (SELECT DISTINCT season_id FROM episode WHERE season_id IS NOT NULL)",
         [],
     )?;
-    
-    // Store connection in OnceLock
-    DB_CONN.set(Mutex::new(conn))
-        .map_err(|_| "Database already initialized")?;
-    
-    Ok(())
-}
 
-/// Get a reference to the database connection
-pub fn get_connection() -> &'static Mutex<Connection> {
-    DB_CONN.get().expect("Database not initialized")
+    Ok(())
 }
 
-pub fn episode_exists(location: &str) -> Result<bool> {
-    let conn = get_connection().lock().unwrap();
+impl Database {
+    /// Open or create the database at `db_path` and bring its schema up to date
+    pub fn open(db_path: &Path) -> DbResult<Self> {
+        // Create parent directory if it doesn't exist
+        if let Some(parent) = db_path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                crate::logger::log_error(&format!("Failed to create database directory {}: {}", parent.display(), e));
+                return Err(e.into());
+            }
+        }
 
-    let mut stmt = conn.prepare("SELECT EXISTS(SELECT 1 FROM episode WHERE location = ?1)")?;
-    let exists: bool = stmt.query_row(params![location], |row| row.get(0))?;
-    Ok(exists)
-}
+        // Open or create database connection
+        let conn = match Connection::open(db_path) {
+            Ok(c) => c,
+            Err(e) => {
+                crate::logger::log_error(&format!("Failed to open database at {}: {}", db_path.display(), e));
+                return Err(e.into());
+            }
+        };
 
-/// Import an episode with relative path storage
-pub fn import_episode_relative(
-    absolute_location: &str,
-    name: &str,
-    resolver: &PathResolver,
-) -> Result<bool, Box<dyn std::error::Error>> {
-    let absolute_path = Path::new(absolute_location);
-    
-    // Validate that the path is under the configured root directory
-    resolver.validate_path_under_root(absolute_path)?;
-    
-    // Convert absolute path to relative path
-    let relative_path = resolver.to_relative(absolute_path)?;
-    let relative_location = relative_path.to_str()
-        .ok_or("Failed to convert path to string")?;
-    
-    // Check if episode already exists with this relative path
-    if episode_exists(relative_location)? {
-        crate::logger::log_debug(&format!(
-            "Skipping duplicate episode: '{}' (relative path: {})",
-            name, relative_location
-        ));
-        return Ok(false); // Already exists, not inserted
+        apply_schema(&conn)?;
+        Ok(Database { conn: Mutex::new(conn), read_cache: Mutex::default() })
     }
 
-    crate::logger::log_debug(&format!(
-        "Importing new episode: '{}' (relative path: {})",
-        name, relative_location
-    ));
+    /// Lock the connection for a query
+    pub fn connection(&self) -> MutexGuard<'_, Connection> {
+        // A panic while holding the lock leaves the connection itself usable
+        self.conn.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
 
-    let conn = get_connection().lock().unwrap();
+    /// Make the writes of `write` in one transaction, holding the connection throughout: either
+    /// all of them are saved or, when one fails, none is
+    pub fn write_together<T>(&self, write: impl FnOnce(&Writes) -> DbResult<T>) -> DbResult<T> {
+        let mut conn = self.connection();
+        let tx = conn.transaction()?;
+        let result = write(&Writes { conn: &tx })?;
+        tx.commit()?;
+        Ok(result)
+    }
 
-    conn.execute(
-        "INSERT INTO episode (location, name, watched, length, series_id, season_id, episode_number, year)
-         VALUES (?1, ?2, false, 0, null, null, null, null)",
-        params![relative_location, name],
-    )?;
-    Ok(true) // Successfully inserted
-}
+    /// Rows written through the connection since it was opened; it moves on with every change
+    pub fn change_count(&self) -> DbResult<i64> {
+        Ok(self.connection().query_row("SELECT total_changes()", [], |row| row.get(0))?)
+    }
 
-pub fn get_entries() -> Result<Vec<Entry>> {
-    let conn = get_connection().lock().unwrap();
+    /// The read cache, emptied first when the database has changed since `changes` was taken
+    fn read_cache(&self, changes: i64) -> MutexGuard<'_, ReadCache> {
+        let mut cache = self.read_cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if cache.changes != changes {
+            *cache = ReadCache { changes, ..ReadCache::default() };
+        }
+        cache
+    }
 
-    let mut entries = Vec::new();
+    /// Details of an episode, read from the database once between changes to it
+    pub fn cached_episode_detail(&self, id: usize) -> DbResult<EpisodeDetail> {
+        let changes = self.change_count()?;
+        if let Some(details) = self.read_cache(changes).details.get(&id) {
+            return Ok(details.clone());
+        }
+        let details = self.get_episode_detail(id)?;
+        // Details read before a change another caller has already seen aren't kept
+        let mut cache = self.read_cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if cache.changes == changes {
+            cache.details.insert(id, details.clone());
+        }
+        Ok(details)
+    }
 
-    // Retrieve series
-    let mut stmt = match conn.prepare("SELECT id, name FROM series ORDER BY name") {
-        Ok(s) => s,
-        Err(e) => {
-            crate::logger::log_error(&format!("Failed to prepare query for series: {}", e));
-            return Err(e);
+    /// `get_category_counts`, counted once between changes to the database
+    pub fn cached_category_counts(&self) -> DbResult<Arc<HashMap<EntryKey, CategoryCounts>>> {
+        let changes = self.change_count()?;
+        if let Some(counts) = &self.read_cache(changes).category_counts {
+            return Ok(Arc::clone(counts));
         }
-    };
-    let series_iter = stmt.query_map([], |row| {
-        Ok(Entry::Series {
-            series_id: row.get(0)?,
-            name: row.get(1)?,
-        })
-    })?;
-
-    for series in series_iter {
-        entries.push(series?);
-    }
-
-    // Retrieve episodes that are not part of a series
-    let mut stmt = match conn.prepare(
-        "SELECT id, name, location 
-         FROM episode WHERE series_id IS NULL 
-         ORDER BY 
-           CASE WHEN episode_number IS NULL OR episode_number = '' THEN 1 ELSE 0 END,
-           CAST(episode_number AS INTEGER),
-           name",
-    ) {
-        Ok(s) => s,
-        Err(e) => {
-            crate::logger::log_error(&format!("Failed to prepare query for episodes: {}", e));
-            return Err(e);
+        let counts = Arc::new(self.get_category_counts()?);
+        let mut cache = self.read_cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if cache.changes == changes {
+            cache.category_counts = Some(Arc::clone(&counts));
         }
-    };
-    let episode_iter = stmt.query_map([], |row| {
-        Ok(Entry::Episode {
-            episode_id: row.get(0)?,
-            name: row.get(1)?,
-            location: row.get(2)?,
-        })
-    })?;
+        Ok(counts)
+    }
 
-    for episode in episode_iter {
-        entries.push(episode?);
+    /// Whether a file under the root with `root_id` (None for the library root) is in the library
+    pub fn episode_exists(&self, root_id: Option<usize>, location: &str) -> DbResult<bool> {
+        let conn = self.connection();
+
+        // Files attached as versions of another episode count as imported
+        let mut stmt = conn.prepare(
+            "SELECT EXISTS(SELECT 1 FROM episode WHERE location = ?1 AND root_id IS ?2)
+             OR EXISTS(SELECT 1 FROM version WHERE location = ?1 AND root_id IS ?2)",
+        )?;
+        let exists: bool = stmt.query_row(params![location, root_id], |row| row.get(0))?;
+        Ok(exists)
     }
 
-    Ok(entries)
-}
+    /// Id of another folder of the library, added the first time it is seen
+    pub fn register_root(&self, path: &str) -> DbResult<usize> {
+        let conn = self.connection();
+        conn.execute("INSERT OR IGNORE INTO root (path) VALUES (?1)", params![path])?;
+        let id = conn.query_row("SELECT id FROM root WHERE path = ?1", params![path], |row| row.get(0))?;
+        Ok(id)
+    }
+
+    /// Register the other folders of the library from the config and let `resolver` resolve paths under them
+    /// A folder that can't be registered is logged and left out
+    pub fn add_library_roots(&self, resolver: &mut PathResolver, folders: &[String]) {
+        for folder in folders {
+            let path = Path::new(folder);
+            let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            match self.register_root(&path.to_string_lossy()) {
+                Ok(id) => resolver.add_root(id, &path),
+                Err(e) => crate::logger::log_warn(&format!("Failed to add library folder {}: {}", folder, e)),
+            }
+        }
+    }
 
-pub fn get_entries_for_series(series_id: usize) -> Result<Vec<Entry>> {
-    let conn = get_connection().lock().unwrap();
+    /// Import an episode with relative path storage
+    pub fn import_episode_relative(
+        &self,
+        absolute_location: &str,
+        name: &str,
+        resolver: &PathResolver,
+    ) -> DbResult<bool> {
+        let absolute_path = location::decode(absolute_location);
+
+        // Find the folder of the library the file is under and its path relative to it
+        let (root_id, relative_path) = resolver.locate(&absolute_path)?;
+        let relative_location = location::encode(&relative_path);
+
+        let file_size = std::fs::metadata(&absolute_path).ok().map(|metadata| metadata.len() as i64);
+
+        // Check if episode already exists with this relative path
+        if self.episode_exists(root_id, &relative_location)? {
+            crate::logger::log_debug(&format!(
+                "Skipping duplicate episode: '{}' (relative path: {})",
+                name, relative_location
+            ));
+            // Fill in sizes of episodes imported before they were recorded
+            self.connection().execute(
+                "UPDATE episode SET file_size = ?1 WHERE location = ?2 AND root_id IS ?3 AND file_size IS NULL",
+                params![file_size, relative_location, root_id],
+            )?;
+            return Ok(false); // Already exists, not inserted
+        }
 
-    let mut entries = Vec::new();
+        crate::logger::log_debug(&format!(
+            "Importing new episode: '{}' (relative path: {})",
+            name, relative_location
+        ));
 
-    // Retrieve seasons for the selected series
-    let mut stmt =
-        conn.prepare("SELECT id, number FROM season WHERE series_id = ?1 ORDER BY number")?;
-    let season_iter = stmt.query_map(params![series_id], |row| {
-        Ok(Entry::Season {
-            season_id: row.get(0)?,
-            number: row.get(1)?,
-        })
-    })?;
+        let conn = self.connection();
 
-    for season in season_iter {
-        entries.push(season?);
+        conn.execute(
+            "INSERT INTO episode (location, name, watched, length, series_id, season_id, episode_number, year, added_time, file_size, root_id)
+             VALUES (?1, ?2, false, 0, null, null, null, null, ?3, ?4, ?5)",
+            params![relative_location, name, chrono::Utc::now().to_rfc3339(), file_size, root_id],
+        )?;
+        Ok(true) // Successfully inserted
     }
 
-    // Retrieve episodes that are part of the series but not part of a season
-    let mut stmt = conn.prepare(
-        "SELECT id, name, location 
-         FROM episode WHERE series_id = ?1 AND season_id IS NULL ORDER BY year, name",
-    )?;
-    let episode_iter = stmt.query_map(params![series_id], |row| {
-        Ok(Entry::Episode {
-            episode_id: row.get(0)?,
-            name: row.get(1)?,
-            location: row.get(2)?,
-        })
-    })?;
+    pub fn get_entries(&self) -> DbResult<Vec<Entry>> {
+        let conn = self.connection();
 
-    for episode in episode_iter {
-        entries.push(episode?);
-    }
+        let mut entries = Vec::new();
 
-    Ok(entries)
-}
+        // Retrieve series
+        let mut stmt = match conn.prepare("SELECT id, name FROM series ORDER BY name") {
+            Ok(s) => s,
+            Err(e) => {
+                crate::logger::log_error(&format!("Failed to prepare query for series: {}", e));
+                return Err(e.into());
+            }
+        };
+        let series_iter = stmt.query_map([], |row| {
+            Ok(Entry::Series {
+                series_id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })?;
 
-pub fn get_entries_for_season(season_id: usize) -> Result<Vec<Entry>> {
-    let conn = get_connection().lock().unwrap();
+        for series in series_iter {
+            entries.push(series?);
+        }
 
-    let mut entries = Vec::new();
+        // Retrieve episodes that are not part of a series
+        let mut stmt = match conn.prepare(&format!(
+            "SELECT episode.id, episode.name, {EPISODE_LOCATION}
+             FROM episode {ROOT_JOIN} WHERE series_id IS NULL AND extra = 0
+             ORDER BY 
+               CASE WHEN episode_number IS NULL OR episode_number = '' THEN 1 ELSE 0 END,
+               CAST(episode_number AS INTEGER),
+               episode.name"
+        )) {
+            Ok(s) => s,
+            Err(e) => {
+                crate::logger::log_error(&format!("Failed to prepare query for episodes: {}", e));
+                return Err(e.into());
+            }
+        };
+        let episode_iter = stmt.query_map([], |row| {
+            Ok(Entry::Episode {
+                episode_id: row.get(0)?,
+                name: row.get(1)?,
+                location: row.get(2)?,
+            })
+        })?;
 
-    // Retrieve episodes that are part of the season
-    let mut stmt = conn.prepare(
-        "SELECT id, name, location 
-         FROM episode WHERE season_id = ?1 
-         ORDER BY 
-           CASE WHEN episode_number IS NULL OR episode_number = '' THEN 1 ELSE 0 END,
-           CAST(episode_number AS INTEGER),
-           name",
-    )?;
-    let episode_iter = stmt.query_map(params![season_id], |row| {
-        Ok(Entry::Episode {
-            episode_id: row.get(0)?,
-            name: row.get(1)?,
-            location: row.get(2)?,
-        })
-    })?;
+        for episode in episode_iter {
+            entries.push(episode?);
+        }
 
-    for episode in episode_iter {
-        entries.push(episode?);
+        Ok(entries)
     }
 
-    Ok(entries)
-}
-
-/// Get the absolute location of an episode by resolving its relative path
-pub fn get_episode_absolute_location(
-    episode_id: usize,
-    resolver: &PathResolver,
-) -> Result<String, Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
-
-    let mut stmt = conn.prepare("SELECT location FROM episode WHERE id = ?1")?;
-    let relative_location: String = stmt.query_row(params![episode_id], |row| row.get(0))?;
-    
-    // Convert relative path to absolute path
-    let relative_path = Path::new(&relative_location);
-    let absolute_path = resolver.to_absolute(relative_path);
-    
-    absolute_path.to_str()
-        .ok_or("Failed to convert path to string".into())
-        .map(|s| s.to_string())
-}
+    /// Load the entries shown for a browse view
+    pub fn get_entries_for_view(&self, view_context: &ViewContext) -> DbResult<Vec<Entry>> {
+        match view_context {
+            ViewContext::TopLevel => self.get_entries(),
+            ViewContext::Series { series_id, .. } => self.get_entries_for_series(*series_id),
+            ViewContext::Season { season_id, .. } => self.get_entries_for_season(*season_id),
+            ViewContext::Extras => self.get_extras(),
+        }
+    }
 
-pub fn get_episode_detail(id: usize) -> Result<EpisodeDetail, Box<dyn std::error::Error>> {
-    // Fetch details from the database for episode
-    let conn = get_connection().lock().unwrap();
+    pub fn get_entries_for_series(&self, series_id: usize) -> DbResult<Vec<Entry>> {
+        let conn = self.connection();
 
-    crate::logger::log_debug(&format!("Querying episode details for episode_id={}", id));
+        let mut entries = Vec::new();
 
-    let mut stmt = conn.prepare(
-        "SELECT 
-                episode.name as title, 
-                COALESCE(CAST(episode.year AS TEXT), '') as year, 
-                CASE WHEN episode.watched THEN 'true' ELSE 'false' END as watched, 
-                COALESCE(CAST(episode.length AS TEXT), '') as length, 
-                series.id as series_id,
-                COALESCE(series.name, '') as series_name, 
-                season.id as season_id,
-                COALESCE(season.number, '') as season_number, 
-                COALESCE(CAST(episode.episode_number AS TEXT), '') as episode_number,
-                episode.last_watched_time,
-                episode.last_progress_time
-            FROM episode
-            LEFT JOIN season ON season.id = episode.season_id AND season.series_id = episode.series_id
-            LEFT JOIN series ON series.id = episode.series_id
-            WHERE episode.id = ?1"
-    )?;
-    let mut rows = stmt.query(params![id])?;
-
-    if let Some(row) = rows.next()? {
-        let year: String = row.get(1)?;
-        let length: String = row.get(3)?;
-        
-        // Note: We don't log warnings here for missing metadata because this function
-        // is called frequently (e.g., on every cursor movement in Browse mode).
-        // Warnings for missing metadata should only be logged when attempting to
-        // extract/update the metadata (e.g., when playing a video or editing metadata).
-        
-        // Debug log for data validation
-        crate::logger::log_debug(&format!(
-            "Episode {} details: title='{}', year='{}', watched='{}', length='{}', has_series={}, has_season={}",
-            id,
-            row.get::<_, String>(0).unwrap_or_default(),
-            year,
-            row.get::<_, String>(2).unwrap_or_default(),
-            length,
-            row.get::<_, Option<usize>>(4).unwrap_or(None).is_some(),
-            row.get::<_, Option<usize>>(6).unwrap_or(None).is_some()
-        ));
-        
-        let series = if let Some(series_id) = row.get::<_, Option<usize>>(4)? {
-            Some(Series {
-                id: series_id,
-                name: row.get(5)?,
+        // Retrieve seasons for the selected series
+        let mut stmt =
+            conn.prepare("SELECT id, number FROM season WHERE series_id = ?1 ORDER BY number")?;
+        let season_iter = stmt.query_map(params![series_id], |row| {
+            Ok(Entry::Season {
+                season_id: row.get(0)?,
+                number: row.get(1)?,
             })
-        } else {
-            None
-        };
+        })?;
+
+        for season in season_iter {
+            entries.push(season?);
+        }
 
-        let season = if let Some(season_id) = row.get::<_, Option<usize>>(6)? {
-            Some(Season {
-                id: season_id,
-                number: row.get(7)?,
+        // Retrieve episodes that are part of the series but not part of a season
+        let mut stmt = conn.prepare(&format!(
+            "SELECT episode.id, episode.name, {EPISODE_LOCATION}
+             FROM episode {ROOT_JOIN} WHERE series_id = ?1 AND season_id IS NULL AND extra = 0 ORDER BY year, episode.name"
+        ))?;
+        let episode_iter = stmt.query_map(params![series_id], |row| {
+            Ok(Entry::Episode {
+                episode_id: row.get(0)?,
+                name: row.get(1)?,
+                location: row.get(2)?,
             })
-        } else {
-            None
-        };
+        })?;
 
-        // Return raw progress data for UI formatting
-        let last_watched_time = row.get::<_, Option<String>>(9)?;
-        
-        let last_progress_time = row.get::<_, Option<i64>>(10)?
-            .map(|seconds| seconds.to_string());
-
-        Ok(EpisodeDetail {
-            title: row.get(0)?,
-            year,
-            watched: row.get(2)?,
-            length,
-            series,
-            season,
-            episode_number: row.get(8)?,
-            last_watched_time,
-            last_progress_time,
-        })
-    } else {
-        Err("Episode not found".into())
+        for episode in episode_iter {
+            entries.push(episode?);
+        }
+
+        Ok(entries)
     }
-}
 
-pub fn update_episode_detail(
-    id: usize,
-    details: &EpisodeDetail,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
+    pub fn get_entries_for_season(&self, season_id: usize) -> DbResult<Vec<Entry>> {
+        let conn = self.connection();
+
+        let mut entries = Vec::new();
+
+        // Retrieve episodes that are part of the season
+        let mut stmt = conn.prepare(&format!(
+            "SELECT episode.id, episode.name, {EPISODE_LOCATION}
+             FROM episode {ROOT_JOIN} WHERE season_id = ?1 AND extra = 0
+             ORDER BY 
+               CASE WHEN episode_number IS NULL OR episode_number = '' THEN 1 ELSE 0 END,
+               CAST(episode_number AS INTEGER),
+               episode.name"
+        ))?;
+        let episode_iter = stmt.query_map(params![season_id], |row| {
+            Ok(Entry::Episode {
+                episode_id: row.get(0)?,
+                name: row.get(1)?,
+                location: row.get(2)?,
+            })
+        })?;
 
-    if let Err(e) = conn.execute(
-        "UPDATE episode SET name = ?1, year = ?2, watched = ?3, length = ?4, series_id = ?5, season_id = ?6, episode_number = ?7 WHERE id = ?8",
-        params![
-            details.title,
-            details.year,
-            details.watched == "true",
-            details.length,
-            details.series.as_ref().map(|s| &s.id),
-            details.season.as_ref().map(|s| &s.id),
-            details.episode_number,
-            id
-        ],
-    ) {
-        crate::logger::log_error(&format!("Failed to update episode {}: {}", id, e));
-        return Err(e.into());
+        for episode in episode_iter {
+            entries.push(episode?);
+        }
+
+        Ok(entries)
     }
-    Ok(())
-}
 
-pub fn toggle_watched_status(id: usize) -> Result<bool, Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
+    /// Every episode imported as an extra, wherever it is in the library, sorted by name
+    pub fn get_extras(&self) -> DbResult<Vec<Entry>> {
+        let conn = self.connection();
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT episode.id, episode.name, {EPISODE_LOCATION}
+             FROM episode {ROOT_JOIN} WHERE extra = 1 ORDER BY episode.name"
+        ))?;
+        let extras = stmt
+            .query_map([], |row| {
+                Ok(Entry::Episode {
+                    episode_id: row.get(0)?,
+                    name: row.get(1)?,
+                    location: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<Entry>>>()?;
+        Ok(extras)
+    }
 
-    // First, get the current watched status
-    let mut stmt = conn.prepare("SELECT watched FROM episode WHERE id = ?1")?;
-    let current_watched: bool = stmt.query_row(params![id], |row| row.get(0))?;
-    
-    if current_watched {
-        // If currently watched, mark as unwatched, preserve last_watched_time, and reset progress
-        conn.execute(
-            "UPDATE episode SET watched = false, last_progress_time = 0 WHERE id = ?1",
-            params![id],
-        )?;
-        Ok(false) // Now unwatched
-    } else {
-        // If currently unwatched, mark as watched with timestamp and reset progress
-        let now = chrono::Utc::now().to_rfc3339();
-        conn.execute(
-            "UPDATE episode SET watched = true, last_watched_time = ?1, last_progress_time = 0 WHERE id = ?2",
-            params![now, id],
+    /// Flag the episode at `absolute_location` as an extra, or as a regular episode again
+    pub fn set_extra_location(&self, absolute_location: &Path, extra: bool, resolver: &PathResolver) -> DbResult<()> {
+        let (root_id, relative_path) = resolver.locate(absolute_location)?;
+        let relative_location = location::encode(&relative_path);
+        self.connection().execute(
+            "UPDATE episode SET extra = ?1 WHERE location = ?2 AND root_id IS ?3",
+            params![extra, relative_location, root_id],
         )?;
-        Ok(true) // Now watched
+        Ok(())
     }
-}
 
-pub fn unwatch_all_in_season(season_id: usize) -> Result<(), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
+    /// Id of the episode at `absolute_location`, None when it isn't in the library
+    pub fn find_episode_id(&self, absolute_location: &Path, resolver: &PathResolver) -> DbResult<Option<usize>> {
+        let (root_id, relative_path) = resolver.locate(absolute_location)?;
+        let relative_location = location::encode(&relative_path);
+        let id = self
+            .connection()
+            .query_row(
+                "SELECT id FROM episode WHERE location = ?1 AND root_id IS ?2",
+                params![relative_location, root_id],
+                |row| row.get(0),
+            )
+            .map(Some)
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                other => Err(other),
+            })?;
+        Ok(id)
+    }
 
-    conn.execute(
-        "UPDATE episode SET watched = false WHERE season_id = ?1",
-        params![season_id],
-    )?;
+    /// Flag an episode as an extra, or as a regular episode again
+    pub fn set_extra(&self, episode_id: usize, extra: bool) -> DbResult<()> {
+        self.connection().execute("UPDATE episode SET extra = ?1 WHERE id = ?2", params![extra, episode_id])?;
+        Ok(())
+    }
 
-    Ok(())
-}
+    /// Point an episode at its file's new absolute location after the file was renamed or moved
+    pub fn update_episode_location(&self, episode_id: usize, absolute_location: &Path, resolver: &PathResolver) -> DbResult<()> {
+        let (root_id, relative_path) = resolver.locate(absolute_location)?;
+        let relative_location = location::encode(&relative_path);
+        if let Err(e) = self.connection().execute(
+            "UPDATE episode SET location = ?1, root_id = ?2 WHERE id = ?3",
+            params![relative_location, root_id, episode_id],
+        ) {
+            crate::logger::log_error(&format!("Failed to update the location of episode {}: {}", episode_id, e));
+            return Err(e.into());
+        }
+        Ok(())
+    }
 
-pub fn unwatch_all_in_series(series_id: usize) -> Result<(), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
+    /// Get the absolute location of an episode by resolving its relative path
+    pub fn get_episode_absolute_location(
+        &self,
+        episode_id: usize,
+        resolver: &PathResolver,
+    ) -> DbResult<String> {
+        let conn = self.connection();
 
-    conn.execute(
-        "UPDATE episode SET watched = false WHERE series_id = ?1",
-        params![series_id],
-    )?;
+        let mut stmt = conn.prepare(&format!("SELECT {EPISODE_LOCATION} FROM episode {ROOT_JOIN} WHERE episode.id = ?1"))?;
+        let relative_location: String = stmt.query_row(params![episode_id], |row| row.get(0))?;
 
-    Ok(())
-}
+        // Convert relative path to absolute path
+        let absolute_path = resolver.to_absolute(&location::decode(&relative_location));
+        Ok(location::encode(&absolute_path))
+    }
 
-pub fn unwatch_all_standalone() -> Result<(), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
+    /// Every file of an episode as absolute paths, its own file first and then attached versions
+    pub fn get_episode_versions(&self, episode_id: usize, resolver: &PathResolver) -> DbResult<Vec<EpisodeVersion>> {
+        let conn = self.connection();
+
+        let mut locations: Vec<(String, Option<String>)> = Vec::new();
+        let primary: String = conn
+            .query_row(
+                &format!("SELECT {EPISODE_LOCATION} FROM episode {ROOT_JOIN} WHERE episode.id = ?1"),
+                params![episode_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => DatabaseError::EpisodeNotFound(episode_id),
+                other => other.into(),
+            })?;
+        locations.push((primary, None));
 
-    conn.execute(
-        "UPDATE episode SET watched = false WHERE series_id IS NULL",
-        [],
-    )?;
+        let mut stmt = conn.prepare(
+            "SELECT COALESCE(root.path || '/' || version.location, version.location), label
+             FROM version LEFT JOIN root ON root.id = version.root_id WHERE episode_id = ?1 ORDER BY version.id",
+        )?;
+        let rows = stmt.query_map(params![episode_id], |row| Ok((row.get::<_, String>(0)?, Some(row.get::<_, String>(1)?))))?;
+        for row in rows {
+            locations.push(row?);
+        }
 
-    Ok(())
-}
+        let mut versions = Vec::new();
+        for (relative_location, label) in locations {
+            let absolute_path = resolver.to_absolute(&location::decode(&relative_location));
+            versions.push(EpisodeVersion {
+                label: label.unwrap_or_else(|| version_label(&location::display(&relative_location))),
+                location: location::encode(&absolute_path),
+            });
+        }
+        Ok(versions)
+    }
 
-pub fn clear_series_data(episode_id: usize) -> Result<(), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
+    /// Attach other episodes holding the same episode as versions of this one
+    /// Matches share the season and episode number, or the name when unnumbered; their watched state and plays are folded in
+    /// Returns the number of episodes merged
+    pub fn merge_versions(&self, episode_id: usize) -> DbResult<usize> {
+        let mut conn = self.connection();
+
+        let (name, series_id, season_id, episode_number): (String, Option<usize>, Option<usize>, Option<usize>) = conn
+            .query_row(
+                "SELECT name, series_id, season_id, episode_number FROM episode WHERE id = ?1",
+                params![episode_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => DatabaseError::EpisodeNotFound(episode_id),
+                other => other.into(),
+            })?;
+
+        let numbered = season_id.is_some() && episode_number.unwrap_or(0) > 0;
+        // Id, location, root, watched, play count and last watched time of each match
+        type Match = (usize, String, Option<usize>, bool, u32, Option<String>);
+        let matches: Vec<Match> = {
+            let mut stmt = conn.prepare(
+                "SELECT id, location, root_id, watched, play_count, last_watched_time FROM episode
+                 WHERE id != ?1 AND CASE WHEN ?6
+                     THEN season_id = ?2 AND episode_number = ?3
+                     ELSE name = ?4 AND series_id IS ?5 AND season_id IS ?2
+                 END",
+            )?;
+            let rows = stmt.query_map(
+                params![episode_id, season_id, episode_number, name, series_id, numbered],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)),
+            )?;
+            rows.collect::<Result<_>>()?
+        };
 
-    conn.execute(
-        "UPDATE episode SET series_id = NULL, season_id = NULL, episode_number = NULL WHERE id = ?1",
-        params![episode_id],
-    )?;
+        let tx = conn.transaction()?;
+        for (other_id, location, root_id, watched, play_count, last_watched_time) in &matches {
+            tx.execute(
+                "INSERT INTO version (episode_id, location, label, root_id) VALUES (?1, ?2, ?3, ?4)",
+                params![episode_id, location, version_label(&location::display(location)), root_id],
+            )?;
+            tx.execute(
+                "UPDATE version SET episode_id = ?1 WHERE episode_id = ?2",
+                params![episode_id, other_id],
+            )?;
+            tx.execute(
+                "UPDATE episode SET watched = watched OR ?1, play_count = play_count + ?2,
+                 last_watched_time = CASE WHEN ?3 > COALESCE(last_watched_time, '') THEN ?3 ELSE last_watched_time END
+                 WHERE id = ?4",
+                params![watched, play_count, last_watched_time, episode_id],
+            )?;
+            tx.execute("DELETE FROM episode WHERE id = ?1", params![other_id])?;
+        }
+        tx.commit()?;
 
-    Ok(())
-}
+        if !matches.is_empty() {
+            crate::logger::log_info(&format!("Merged {} versions into episode {}", matches.len(), episode_id));
+        }
+        Ok(matches.len())
+    }
 
-pub fn delete_episode(episode_id: usize) -> Result<(), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
+    pub fn get_episode_detail(&self, id: usize) -> DbResult<EpisodeDetail> {
+        // Fetch details from the database for episode
+        let conn = self.connection();
 
-    if let Err(e) = conn.execute(
-        "DELETE FROM episode WHERE id = ?1",
-        params![episode_id],
-    ) {
-        crate::logger::log_error(&format!("Failed to delete episode {}: {}", episode_id, e));
-        return Err(e.into());
+        crate::logger::log_debug(&format!("Querying episode details for episode_id={}", id));
+
+        let mut stmt = conn.prepare(
+            "SELECT 
+                    episode.name as title, 
+                    COALESCE(CAST(episode.year AS TEXT), '') as year, 
+                    CASE WHEN episode.watched THEN 'true' ELSE 'false' END as watched, 
+                    COALESCE(CAST(episode.length AS TEXT), '') as length, 
+                    series.id as series_id,
+                    COALESCE(series.name, '') as series_name, 
+                    season.id as season_id,
+                    COALESCE(season.number, '') as season_number, 
+                    COALESCE(CAST(episode.episode_number AS TEXT), '') as episode_number,
+                    episode.last_watched_time,
+                    episode.last_progress_time,
+                    episode.notes,
+                    episode.play_count,
+                    episode.alt_titles
+                FROM episode
+                LEFT JOIN season ON season.id = episode.season_id AND season.series_id = episode.series_id
+                LEFT JOIN series ON series.id = episode.series_id
+                WHERE episode.id = ?1"
+        )?;
+        let mut rows = stmt.query(params![id])?;
+
+        if let Some(row) = rows.next()? {
+            let year: String = row.get(1)?;
+            let length: String = row.get(3)?;
+
+            // Note: We don't log warnings here for missing metadata because this function
+            // is called frequently (e.g., on every cursor movement in Browse mode).
+            // Warnings for missing metadata should only be logged when attempting to
+            // extract/update the metadata (e.g., when playing a video or editing metadata).
+
+            // Debug log for data validation
+            crate::logger::log_debug(&format!(
+                "Episode {} details: title='{}', year='{}', watched='{}', length='{}', has_series={}, has_season={}",
+                id,
+                row.get::<_, String>(0).unwrap_or_default(),
+                year,
+                row.get::<_, String>(2).unwrap_or_default(),
+                length,
+                row.get::<_, Option<usize>>(4).unwrap_or(None).is_some(),
+                row.get::<_, Option<usize>>(6).unwrap_or(None).is_some()
+            ));
+
+            let series = if let Some(series_id) = row.get::<_, Option<usize>>(4)? {
+                Some(Series {
+                    id: series_id,
+                    name: row.get(5)?,
+                })
+            } else {
+                None
+            };
+
+            let season = if let Some(season_id) = row.get::<_, Option<usize>>(6)? {
+                Some(Season {
+                    id: season_id,
+                    number: row.get(7)?,
+                })
+            } else {
+                None
+            };
+
+            // Return raw progress data for UI formatting
+            let last_watched_time = row.get::<_, Option<String>>(9)?;
+
+            let last_progress_time = row.get::<_, Option<i64>>(10)?
+                .map(|seconds| seconds.to_string());
+
+            Ok(EpisodeDetail {
+                title: row.get(0)?,
+                year,
+                watched: row.get(2)?,
+                length,
+                series,
+                season,
+                episode_number: row.get(8)?,
+                last_watched_time,
+                last_progress_time,
+                notes: row.get::<_, Option<String>>(11)?.filter(|notes| !notes.is_empty()),
+                play_count: row.get::<_, i64>(12)?.max(0) as u32,
+                alt_titles: parse_alt_titles(row.get::<_, Option<String>>(13)?.as_deref()),
+            })
+        } else {
+            Err(DatabaseError::EpisodeNotFound(id))
+        }
     }
 
-    Ok(())
-}
+    pub fn update_episode_detail(
+        &self,
+        id: usize,
+        details: &EpisodeDetail,
+    ) -> DbResult<()> {
+        update_episode_detail(&self.connection(), id, details)
+    }
 
-pub fn get_all_series() -> Result<Vec<Series>> {
-    let conn = get_connection().lock().unwrap();
+    /// Save the notes of an episode, clearing them when blank
+    pub fn update_episode_notes(&self, id: usize, notes: &str) -> DbResult<()> {
+        update_episode_notes(&self.connection(), id, notes)
+    }
 
-    let mut series = Vec::new();
+    pub fn update_episode_alt_titles(&self, id: usize, alt_titles: &AltTitles) -> DbResult<()> {
+        update_episode_alt_titles(&self.connection(), id, alt_titles)
+    }
 
-    let mut stmt = conn.prepare("SELECT id, name FROM series")?;
-    let series_iter = stmt.query_map([], |row| {
-        Ok(Series {
-            id: row.get(0)?,
-            name: row.get(1)?,
-        })
-    })?;
+    /// Load the alternate titles of every episode that has any
+    pub fn get_all_alt_titles(&self) -> DbResult<HashMap<usize, AltTitles>> {
+        let conn = self.connection();
+
+        let mut stmt = conn.prepare("SELECT id, alt_titles FROM episode WHERE alt_titles IS NOT NULL")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, usize>(0)?, row.get::<_, String>(1)?)))?;
+        let mut titles = HashMap::new();
+        for row in rows {
+            let (id, json) = row?;
+            let parsed = parse_alt_titles(Some(&json));
+            if !parsed.is_empty() {
+                titles.insert(id, parsed);
+            }
+        }
+        Ok(titles)
+    }
 
-    for s in series_iter {
-        series.push(s?);
+    pub fn toggle_watched_status(&self, id: usize) -> DbResult<bool> {
+        toggle_watched_status(&self.connection(), id)
     }
 
-    Ok(series)
-}
+    pub fn unwatch_all_in_season(&self, season_id: usize) -> DbResult<()> {
+        let conn = self.connection();
 
-pub fn get_series_by_id(series_id: usize) -> Result<Series> {
-    let conn = get_connection().lock().unwrap();
-    
-    let mut stmt = conn.prepare("SELECT id, name FROM series WHERE id = ?1")?;
-    let series = stmt.query_row(params![series_id], |row| {
-        Ok(Series {
-            id: row.get(0)?,
-            name: row.get(1)?,
-        })
-    })?;
-    
-    Ok(series)
-}
+        conn.execute(
+            "UPDATE episode SET watched = false WHERE season_id = ?1",
+            params![season_id],
+        )?;
 
-pub fn get_season_by_id(season_id: usize) -> Result<(Season, usize)> {
-    let conn = get_connection().lock().unwrap();
-    
-    let mut stmt = conn.prepare("SELECT id, number, series_id FROM season WHERE id = ?1")?;
-    let (season, series_id) = stmt.query_row(params![season_id], |row| {
-        Ok((
-            Season {
-                id: row.get(0)?,
-                number: row.get(1)?,
-            },
-            row.get(2)?
-        ))
-    })?;
-    
-    Ok((season, series_id))
-}
+        Ok(())
+    }
+
+    pub fn unwatch_all_in_series(&self, series_id: usize) -> DbResult<()> {
+        let conn = self.connection();
 
-pub fn create_series_and_assign(name: &str, episode_id: usize) -> Result<EpisodeDetail> {
-    {
-        // Create a new scope to release the lock after the transaction
-        let conn = get_connection().lock().unwrap();
-        conn.execute("INSERT INTO series (name) VALUES (?1)", params![name])?;
-        let series_id = conn.last_insert_rowid() as i32;
         conn.execute(
-            "UPDATE episode SET series_id = ?1 WHERE id = ?2",
-            params![series_id, episode_id],
+            "UPDATE episode SET watched = false WHERE series_id = ?1",
+            params![series_id],
         )?;
+
+        Ok(())
     }
-    Ok(get_episode_detail(episode_id).expect("Failed to get episode details"))
-}
 
-pub fn assign_series(series_id: usize, episode_id: usize) -> Result<EpisodeDetail> {
-    {
-        // Create a new scope to release the lock after the transaction
-        let conn = get_connection().lock().unwrap();
+    pub fn unwatch_all_standalone(&self) -> DbResult<()> {
+        let conn = self.connection();
+
         conn.execute(
-            "UPDATE episode SET series_id = ?1 WHERE id = ?2",
-            params![series_id, episode_id],
+            "UPDATE episode SET watched = false WHERE series_id IS NULL AND extra = 0",
+            [],
         )?;
+
+        Ok(())
     }
-    Ok(get_episode_detail(episode_id).expect("Failed to get episode details"))
-}
 
-// can_create_season is a helper function to determine if a season can be created for the series
-// it takes a series_id and a season_number (int) and returns a boolean
-// special rules:
-//  if the series does not exist, return false
-//  if the season_number is 1 or 0, return true
-//  if the season_number is greater than 1, check if the previous season exists
-//  if the previous season exists, return true
-//  otherwise, return false
-pub fn can_create_season(series_id: Option<usize>, season_number: usize) -> Result<bool> {
-    let conn = get_connection().lock().unwrap();
+    pub fn unwatch_all_extras(&self) -> DbResult<()> {
+        self.connection().execute("UPDATE episode SET watched = false WHERE extra = 1", [])?;
+        Ok(())
+    }
+
+    pub fn clear_series_data(&self, episode_id: usize) -> DbResult<()> {
+        let conn = self.connection();
+
+        conn.execute(
+            "UPDATE episode SET series_id = NULL, season_id = NULL, episode_number = NULL WHERE id = ?1",
+            params![episode_id],
+        )?;
 
-    if series_id.is_none() {
-        crate::logger::log_debug("Cannot create season: no series_id provided");
-        return Ok(false);
+        Ok(())
     }
 
-    if season_number <= 1 {
+    pub fn delete_episode(&self, episode_id: usize) -> DbResult<()> {
+        let conn = self.connection();
+
+        conn.execute("DELETE FROM version WHERE episode_id = ?1", params![episode_id])?;
+        if let Err(e) = conn.execute(
+            "DELETE FROM episode WHERE id = ?1",
+            params![episode_id],
+        ) {
+            crate::logger::log_error(&format!("Failed to delete episode {}: {}", episode_id, e));
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn get_all_series(&self) -> DbResult<Vec<Series>> {
+        let conn = self.connection();
+
+        let mut series = Vec::new();
+
+        let mut stmt = conn.prepare("SELECT id, name FROM series")?;
+        let series_iter = stmt.query_map([], |row| {
+            Ok(Series {
+                id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })?;
+
+        for s in series_iter {
+            series.push(s?);
+        }
+
+        Ok(series)
+    }
+
+    pub fn get_series_by_id(&self, series_id: usize) -> DbResult<Series> {
+        let conn = self.connection();
+
+        let mut stmt = conn.prepare("SELECT id, name FROM series WHERE id = ?1")?;
+        let series = stmt.query_row(params![series_id], |row| {
+            Ok(Series {
+                id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })?;
+
+        Ok(series)
+    }
+
+    pub fn get_season_by_id(&self, season_id: usize) -> DbResult<(Season, usize)> {
+        let conn = self.connection();
+
+        let mut stmt = conn.prepare("SELECT id, number, series_id FROM season WHERE id = ?1")?;
+        let (season, series_id) = stmt.query_row(params![season_id], |row| {
+            Ok((
+                Season {
+                    id: row.get(0)?,
+                    number: row.get(1)?,
+                },
+                row.get(2)?
+            ))
+        })?;
+
+        Ok((season, series_id))
+    }
+
+    pub fn create_series_and_assign(&self, name: &str, episode_id: usize) -> DbResult<EpisodeDetail> {
+        {
+            // Create a new scope to release the lock after the transaction
+            let conn = self.connection();
+            let series_id = create_series(&conn, name)?;
+            conn.execute(
+                "UPDATE episode SET series_id = ?1 WHERE id = ?2",
+                params![series_id, episode_id],
+            )?;
+        }
+        self.get_episode_detail(episode_id)
+    }
+
+    pub fn assign_series(&self, series_id: usize, episode_id: usize) -> DbResult<EpisodeDetail> {
+        {
+            // Create a new scope to release the lock after the transaction
+            let conn = self.connection();
+            conn.execute(
+                "UPDATE episode SET series_id = ?1 WHERE id = ?2",
+                params![series_id, episode_id],
+            )?;
+        }
+        self.get_episode_detail(episode_id)
+    }
+
+    // can_create_season is a helper function to determine if a season can be created for the series
+    // it takes a series_id and a season_number (int) and returns a boolean
+    // special rules:
+    //  if the series does not exist, return false
+    //  if the season_number is 1 or 0, return true
+    //  if the season_number is greater than 1, check if the previous season exists
+    //  if the previous season exists, return true
+    //  otherwise, return false
+    pub fn can_create_season(&self, series_id: Option<usize>, season_number: usize) -> DbResult<bool> {
+        let conn = self.connection();
+
+        if series_id.is_none() {
+            crate::logger::log_debug("Cannot create season: no series_id provided");
+            return Ok(false);
+        }
+
+        if season_number <= 1 {
+            crate::logger::log_debug(&format!(
+                "Can create season {} for series {}: season number <= 1",
+                season_number, series_id.unwrap()
+            ));
+            return Ok(true);
+        }
+
+        let mut stmt = conn.prepare("SELECT 1 FROM season WHERE series_id = ?1 AND number = ?2")?;
+        let exists: bool = stmt.query_row(params![series_id, season_number - 1], |row| row.get(0))?;
+
         crate::logger::log_debug(&format!(
-            "Can create season {} for series {}: season number <= 1",
-            season_number, series_id.unwrap()
+            "Can create season {} for series {}: previous season exists={}",
+            season_number, series_id.unwrap(), exists
         ));
-        return Ok(true);
-    }
-
-    let mut stmt = conn.prepare("SELECT 1 FROM season WHERE series_id = ?1 AND number = ?2")?;
-    let exists: bool = stmt.query_row(params![series_id, season_number - 1], |row| row.get(0))?;
-    
-    crate::logger::log_debug(&format!(
-        "Can create season {} for series {}: previous season exists={}",
-        season_number, series_id.unwrap(), exists
-    ));
-    
-    Ok(exists)
-}
-
-// create_season_and_assign is a function to create a new season for a series and assign an episode to it
-// it takes a series_id, season_number, and episode_id
-// it returns the season_id of the newly created season
-// it is private and only called from the update_episode_detail function
-pub fn create_season_and_assign(
-    series_id: usize,
-    season_number: usize,
-    episode_id: usize,
-) -> Result<usize> {
-    let conn = get_connection().lock().unwrap();
 
-    //first, try to retrieve an existing season based on the series_id and season_number
-    let mut stmt = conn.prepare("SELECT id FROM season WHERE series_id = ?1 AND number = ?2")?;
-    let season_id: Option<usize> = stmt
-        .query_row(params![series_id, season_number], |row| row.get(0))
-        .ok();
+        Ok(exists)
+    }
 
-    if let Some(season_id) = season_id {
+    // create_season_and_assign is a function to create a new season for a series and assign an episode to it
+    // it takes a series_id, season_number, and episode_id
+    // it returns the season_id of the newly created season
+    // it is private and only called from the update_episode_detail function
+    pub fn create_season_and_assign(
+        &self,
+        series_id: usize,
+        season_number: usize,
+        episode_id: usize,
+    ) -> DbResult<usize> {
+        let conn = self.connection();
+
+        let season_id = find_or_create_season(&conn, series_id, season_number)?;
         conn.execute(
             "UPDATE episode SET season_id = ?1 WHERE id = ?2",
             params![season_id, episode_id],
         )?;
         Ok(season_id)
-    } else {
-        //if the season does not exist, create it
+    }
+
+    /// Get the name and total/unwatched/new episode counts of every series and season in one query
+    /// An episode is new while its name is still the file name it was imported with
+    pub fn get_category_counts(&self) -> DbResult<HashMap<EntryKey, CategoryCounts>> {
+        let conn = self.connection();
+
+        let mut stmt = conn.prepare(
+            "SELECT 'series', series.id, series.name,
+                COUNT(episode.id),
+                TOTAL(CASE WHEN episode.id IS NOT NULL AND (episode.watched = 0 OR episode.watched IS NULL) THEN 1 ELSE 0 END),
+                TOTAL(CASE WHEN episode.name = episode.location
+                    OR substr(episode.location, -length(episode.name) - 1) = '/' || episode.name THEN 1 ELSE 0 END)
+             FROM series
+             LEFT JOIN episode ON episode.series_id = series.id AND episode.extra = 0
+             GROUP BY series.id
+             UNION ALL
+             SELECT 'season', season.id, 'Season ' || season.number,
+                COUNT(episode.id),
+                TOTAL(CASE WHEN episode.id IS NOT NULL AND (episode.watched = 0 OR episode.watched IS NULL) THEN 1 ELSE 0 END),
+                TOTAL(CASE WHEN episode.name = episode.location
+                    OR substr(episode.location, -length(episode.name) - 1) = '/' || episode.name THEN 1 ELSE 0 END)
+             FROM season
+             LEFT JOIN episode ON episode.season_id = season.id AND episode.extra = 0
+             GROUP BY season.id"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            let id = row.get::<_, usize>(1)?;
+            let key = if row.get::<_, String>(0)? == "series" {
+                EntryKey::Series(id)
+            } else {
+                EntryKey::Season(id)
+            };
+            Ok((
+                key,
+                CategoryCounts {
+                    name: row.get(2)?,
+                    total: row.get::<_, i64>(3)? as usize,
+                    unwatched: row.get::<_, f64>(4)? as usize,
+                    new: row.get::<_, f64>(5)? as usize,
+                },
+            ))
+        })?;
+
+        let mut counts = HashMap::new();
+        for row in rows {
+            let (key, category) = row?;
+            counts.insert(key, category);
+        }
+        Ok(counts)
+    }
+
+    /// Calculate the next available episode number for a series and optional season
+    pub fn get_next_available_episode_number(
+        &self,
+        series_id: usize,
+        season_number: Option<usize>,
+    ) -> DbResult<usize> {
+        let conn = self.connection();
+
+        // Query episode numbers based on whether season_number is provided
+        let episode_numbers: Vec<usize> = if let Some(season_num) = season_number {
+            // Query episodes for specific season
+            let mut stmt = conn.prepare(
+                "SELECT CAST(episode_number AS INTEGER) as ep_num
+                 FROM episode
+                 WHERE series_id = ?1
+                   AND season_id = (SELECT id FROM season WHERE series_id = ?1 AND number = ?2)
+                   AND episode_number IS NOT NULL
+                   AND episode_number != ''
+                 ORDER BY ep_num"
+            )?;
+
+            let rows = stmt.query_map(params![series_id, season_num], |row| {
+                row.get::<_, i64>(0).map(|n| n as usize)
+            })?;
+
+            rows.filter_map(|r| r.ok()).collect()
+        } else {
+            // Query episodes without season (season_id IS NULL)
+            let mut stmt = conn.prepare(
+                "SELECT CAST(episode_number AS INTEGER) as ep_num
+                 FROM episode
+                 WHERE series_id = ?1
+                   AND season_id IS NULL
+                   AND episode_number IS NOT NULL
+                   AND episode_number != ''
+                 ORDER BY ep_num"
+            )?;
+
+            let rows = stmt.query_map(params![series_id], |row| {
+                row.get::<_, i64>(0).map(|n| n as usize)
+            })?;
+
+            rows.filter_map(|r| r.ok()).collect()
+        };
+
+        // Find the first gap in the sequence starting from 1
+        let mut expected = 1;
+        for &num in &episode_numbers {
+            if num == expected {
+                expected += 1;
+            } else if num > expected {
+                // Found a gap, return the first missing number
+                return Ok(expected);
+            }
+            // If num < expected, skip it (duplicate or out of order, shouldn't happen with ORDER BY)
+        }
+
+        // No gaps found, return the next sequential number
+        Ok(expected)
+    }
+
+    /// Location of every episode, sorted, as `get_entries` hands them out
+    pub fn get_episode_locations(&self) -> DbResult<Vec<String>> {
+        let conn = self.connection();
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {EPISODE_LOCATION} AS location FROM episode {ROOT_JOIN} ORDER BY location"
+        ))?;
+        let locations = stmt.query_map([], |row| row.get(0))?.collect::<Result<Vec<String>>>()?;
+        Ok(locations)
+    }
+
+    /// Id and stored location of every episode that isn't in a series, sorted by location
+    pub fn get_unassigned_episodes(&self) -> DbResult<Vec<(usize, String)>> {
+        let conn = self.connection();
+
+        let mut stmt = conn.prepare("SELECT id, location FROM episode WHERE series_id IS NULL ORDER BY location")?;
+        let episodes = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<(usize, String)>>>()?;
+        Ok(episodes)
+    }
+
+    /// Put each episode in the series and season proposed for it, creating those that don't exist yet
+    /// Series are matched by name regardless of case. Returns how many series were created
+    pub fn assign_folder_series(&self, proposals: &[SeriesProposal]) -> DbResult<usize> {
+        let mut conn = self.connection();
+        let tx = conn.transaction()?;
+        let mut created = 0;
+        for proposal in proposals {
+            let existing: Option<usize> = tx
+                .query_row(
+                    "SELECT id FROM series WHERE name = ?1 COLLATE NOCASE",
+                    params![proposal.series],
+                    |row| row.get(0),
+                )
+                .ok();
+            let series_id = match existing {
+                Some(series_id) => series_id,
+                None => {
+                    tx.execute("INSERT INTO series (name) VALUES (?1)", params![proposal.series])?;
+                    created += 1;
+                    tx.last_insert_rowid() as usize
+                }
+            };
+            let existing: Option<usize> = tx
+                .query_row(
+                    "SELECT id FROM season WHERE series_id = ?1 AND number = ?2",
+                    params![series_id, proposal.season],
+                    |row| row.get(0),
+                )
+                .ok();
+            let season_id = match existing {
+                Some(season_id) => season_id,
+                None => {
+                    tx.execute(
+                        "INSERT INTO season (series_id, number) VALUES (?1, ?2)",
+                        params![series_id, proposal.season],
+                    )?;
+                    tx.last_insert_rowid() as usize
+                }
+            };
+            tx.execute(
+                "UPDATE episode SET series_id = ?1, season_id = ?2 WHERE id = ?3",
+                params![series_id, season_id, proposal.episode_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(created)
+    }
+
+    /// Get episodes with missing length (NULL or 0)
+    /// Episodes whose file has the same size as another episode's, oldest first, for the duplicate finder
+    pub fn get_duplicate_candidates(&self, resolver: &PathResolver) -> DbResult<Vec<DuplicateCopy>> {
+        let conn = self.connection();
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT episode.id, episode.name, {EPISODE_LOCATION}, episode.file_size, episode.length FROM episode {ROOT_JOIN}
+             WHERE episode.file_size > 0
+               AND episode.file_size IN (SELECT file_size FROM episode GROUP BY file_size HAVING COUNT(*) > 1)
+             ORDER BY episode.file_size, episode.id"
+        ))?;
+        let candidates = stmt
+            .query_map([], |row| {
+                let location: String = row.get(2)?;
+                Ok(DuplicateCopy {
+                    episode_id: row.get(0)?,
+                    name: row.get(1)?,
+                    path: resolver.to_absolute(&location::decode(&location)),
+                    size: row.get::<_, i64>(3)? as u64,
+                    length: row.get::<_, Option<i64>>(4)?.unwrap_or(0).max(0) as u64,
+                    keep: true,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(candidates)
+    }
+
+    pub fn get_episodes_with_missing_length(&self) -> DbResult<Vec<(usize, String)>> {
+        let conn = self.connection();
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT episode.id, {EPISODE_LOCATION} FROM episode {ROOT_JOIN} WHERE length IS NULL OR length = 0"
+        ))?;
+
+        let episodes = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, usize>(0)?,
+                row.get::<_, String>(1)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+        Ok(episodes)
+    }
+
+    /// Update episode progress time in seconds
+    pub fn update_episode_progress(&self, episode_id: usize, progress_seconds: u64) -> DbResult<()> {
+        let conn = self.connection();
+
         conn.execute(
-            "INSERT INTO season (series_id, number) VALUES (?1, ?2)",
-            params![series_id, season_number],
+            "UPDATE episode SET last_progress_time = ?1 WHERE id = ?2",
+            params![progress_seconds as i64, episode_id],
         )?;
-        let season_id = conn.last_insert_rowid() as usize;
+
+        Ok(())
+    }
+
+    /// Get episode progress time in seconds
+    pub fn get_episode_progress(&self, episode_id: usize) -> DbResult<Option<u64>> {
+        let conn = self.connection();
+
+        let mut stmt = conn.prepare("SELECT last_progress_time FROM episode WHERE id = ?1")?;
+        let progress: Option<i64> = stmt.query_row(params![episode_id], |row| row.get(0))?;
+
+        Ok(progress.map(|p| p as u64))
+    }
+
+    /// Mark episode as watched with current timestamp and reset progress
+    pub fn mark_episode_watched_with_timestamp(&self, episode_id: usize) -> DbResult<()> {
+        let conn = self.connection();
+
+        // Get current timestamp in ISO 8601 format
+        let now = chrono::Utc::now().to_rfc3339();
+
         conn.execute(
-            "UPDATE episode SET season_id = ?1 WHERE id = ?2",
-            params![season_id, episode_id],
+            "UPDATE episode SET watched = true, last_watched_time = ?1, last_progress_time = 0 WHERE id = ?2",
+            params![now, episode_id],
         )?;
-        Ok(season_id)
+
+        Ok(())
     }
-}
 
-/// Get episode counts for a series
-pub fn get_series_episode_counts(series_id: usize) -> Result<(usize, usize), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
-    
-    let mut stmt = conn.prepare(
-        "SELECT 
-            COUNT(*) as total,
-            SUM(CASE WHEN watched = 0 OR watched IS NULL THEN 1 ELSE 0 END) as unwatched
-         FROM episode
-         WHERE series_id = ?1"
-    )?;
-    
-    let (total, unwatched) = stmt.query_row(params![series_id], |row| {
-        Ok((
-            row.get::<_, i64>(0)? as usize,
-            row.get::<_, i64>(1)? as usize,
-        ))
-    })?;
-    
-    Ok((total, unwatched))
-}
+    /// Record that playback of an episode finished, whether or not it reached the watched threshold
+    /// Stamps the last watched time and counts the play
+    pub fn record_playback_finished(&self, episode_id: usize) -> DbResult<()> {
+        let conn = self.connection();
 
-/// Get episode counts for a season
-pub fn get_season_episode_counts(season_id: usize) -> Result<(usize, usize), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
-    
-    let mut stmt = conn.prepare(
-        "SELECT 
-            COUNT(*) as total,
-            SUM(CASE WHEN watched = 0 OR watched IS NULL THEN 1 ELSE 0 END) as unwatched
-         FROM episode
-         WHERE season_id = ?1"
-    )?;
-    
-    let (total, unwatched) = stmt.query_row(params![season_id], |row| {
-        Ok((
-            row.get::<_, i64>(0)? as usize,
-            row.get::<_, i64>(1)? as usize,
-        ))
-    })?;
-    
-    Ok((total, unwatched))
-}
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE episode SET last_watched_time = ?1, play_count = play_count + 1 WHERE id = ?2",
+            params![now, episode_id],
+        )?;
+
+        Ok(())
+    }
+
+    /// Load the watched state and length of every episode for the filter expressions
+    pub fn get_episode_facts(&self) -> DbResult<Vec<EpisodeFacts>> {
+        let conn = self.connection();
+        let mut stmt = conn.prepare(
+            "SELECT id, series_id, season_id, watched,
+                CASE WHEN length IS NULL OR length = '' THEN NULL ELSE CAST(length AS INTEGER) END
+             FROM episode",
+        )?;
+        let facts = stmt
+            .query_map([], |row| {
+                Ok(EpisodeFacts {
+                    episode_id: row.get(0)?,
+                    series_id: row.get(1)?,
+                    season_id: row.get(2)?,
+                    watched: row.get::<_, Option<bool>>(3)?.unwrap_or(false),
+                    length: row.get::<_, Option<i64>>(4)?.map(|length| length.max(0) as u64),
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(facts)
+    }
 
-/// Calculate the next available episode number for a series and optional season
-pub fn get_next_available_episode_number(
-    series_id: usize,
-    season_number: Option<usize>,
-) -> Result<usize> {
-    let conn = get_connection().lock().unwrap();
+    /// Gather the weekly digest as of `now`
+    pub fn get_weekly_digest(&self, now: chrono::DateTime<chrono::Utc>) -> DbResult<Digest> {
+        let conn = self.connection();
+        let mut digest = Digest::default();
+        let week_ago = now - chrono::Duration::days(7);
+        let stale_before = now - chrono::Duration::days(STALE_SERIES_DAYS);
+        // Timestamps may carry different offsets, so compare them as instants
+        let parse = |value: &str| chrono::DateTime::parse_from_rfc3339(value).ok().map(|dt| dt.with_timezone(&chrono::Utc));
 
-    // Query episode numbers based on whether season_number is provided
-    let episode_numbers: Vec<usize> = if let Some(season_num) = season_number {
-        // Query episodes for specific season
         let mut stmt = conn.prepare(
-            "SELECT CAST(episode_number AS INTEGER) as ep_num
-             FROM episode
-             WHERE series_id = ?1
-               AND season_id = (SELECT id FROM season WHERE series_id = ?1 AND number = ?2)
-               AND episode_number IS NOT NULL
-               AND episode_number != ''
-             ORDER BY ep_num"
+            "SELECT id, name, added_time FROM episode WHERE added_time IS NOT NULL ORDER BY added_time DESC",
         )?;
-        
-        let rows = stmt.query_map(params![series_id, season_num], |row| {
-            row.get::<_, i64>(0).map(|n| n as usize)
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, usize>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
         })?;
-        
-        rows.filter_map(|r| r.ok()).collect()
-    } else {
-        // Query episodes without season (season_id IS NULL)
+        let mut new_items = Vec::new();
+        for row in rows {
+            let (id, name, added) = row?;
+            if let Some(added) = parse(&added).filter(|added| *added >= week_ago) {
+                new_items.push((added, DigestItem { label: name, target: DigestTarget::Episode(id) }));
+            }
+        }
+        new_items.sort_by_key(|(added, _)| std::cmp::Reverse(*added));
+        digest.new_items = new_items.into_iter().take(DIGEST_SECTION_SIZE).map(|(_, item)| item).collect();
+
         let mut stmt = conn.prepare(
-            "SELECT CAST(episode_number AS INTEGER) as ep_num
-             FROM episode
-             WHERE series_id = ?1
-               AND season_id IS NULL
-               AND episode_number IS NOT NULL
-               AND episode_number != ''
-             ORDER BY ep_num"
+            "SELECT last_watched_time,
+                CASE WHEN length IS NULL OR length = '' THEN 0 ELSE CAST(length AS INTEGER) END
+             FROM episode WHERE last_watched_time IS NOT NULL",
         )?;
-        
-        let rows = stmt.query_map(params![series_id], |row| {
-            row.get::<_, i64>(0).map(|n| n as usize)
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+        for row in rows {
+            let (watched_at, length) = row?;
+            if parse(&watched_at).is_some_and(|watched_at| watched_at >= week_ago) {
+                digest.watch_seconds_last_week += length.max(0) as u64;
+            }
+        }
+
+        // Series that have been started but not finished, with their latest watch time
+        let mut stmt = conn.prepare(
+            "SELECT series.id, series.name,
+                SUM(CASE WHEN episode.watched = 0 OR episode.watched IS NULL THEN 1 ELSE 0 END),
+                SUM(CASE WHEN episode.watched = 1 THEN 1 ELSE 0 END),
+                GROUP_CONCAT(episode.last_watched_time, '|')
+             FROM series
+             JOIN episode ON episode.series_id = series.id
+             GROUP BY series.id
+             ORDER BY series.name",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, usize>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
         })?;
-        
-        rows.filter_map(|r| r.ok()).collect()
-    };
+        let mut in_progress = Vec::new();
+        for row in rows {
+            let (id, name, unwatched, watched, watch_times) = row?;
+            let last_watched = watch_times
+                .as_deref()
+                .unwrap_or_default()
+                .split('|')
+                .filter_map(parse)
+                .max();
+            if unwatched > 0 && (watched > 0 || last_watched.is_some()) {
+                in_progress.push((id, name, unwatched, last_watched));
+            }
+        }
 
-    // Find the first gap in the sequence starting from 1
-    let mut expected = 1;
-    for &num in &episode_numbers {
-        if num == expected {
-            expected += 1;
-        } else if num > expected {
-            // Found a gap, return the first missing number
-            return Ok(expected);
+        let mut closest: Vec<_> = in_progress
+            .iter()
+            .filter(|(_, _, _, last)| last.is_none_or(|last| last >= stale_before))
+            .collect();
+        closest.sort_by_key(|(_, _, unwatched, _)| *unwatched);
+        digest.closest_to_finishing = closest
+            .into_iter()
+            .take(DIGEST_SECTION_SIZE)
+            .map(|(id, name, unwatched, _)| DigestItem {
+                label: format!("{} ({} left)", name, unwatched),
+                target: DigestTarget::Series(*id),
+            })
+            .collect();
+
+        let mut stale: Vec<_> = in_progress
+            .iter()
+            .filter_map(|(id, name, _, last)| last.filter(|last| *last < stale_before).map(|last| (id, name, last)))
+            .collect();
+        stale.sort_by_key(|(_, _, last)| *last);
+        digest.stale_series = stale
+            .into_iter()
+            .take(DIGEST_SECTION_SIZE)
+            .map(|(id, name, last)| DigestItem {
+                label: format!("{} (last watched {})", name, last.with_timezone(&chrono::Local).format("%Y-%m-%d")),
+                target: DigestTarget::Series(*id),
+            })
+            .collect();
+
+        Ok(digest)
+    }
+
+    /// Id of the episode played most recently, leaving out extras; None before anything is played
+    pub fn get_last_watched_episode(&self) -> DbResult<Option<usize>> {
+        let conn = self.connection();
+
+        let mut stmt = conn.prepare(
+            "SELECT id FROM episode WHERE last_watched_time IS NOT NULL AND extra = 0
+             ORDER BY last_watched_time DESC LIMIT 1",
+        )?;
+        let mut rows = stmt.query_map([], |row| row.get::<_, usize>(0))?;
+        Ok(rows.next().transpose()?)
+    }
+
+    /// Collect the values used by the non-default Browse sort modes
+    pub fn get_sort_keys(&self) -> DbResult<SortKeys> {
+        let conn = self.connection();
+        let mut keys = SortKeys::default();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, series_id, season_id, last_watched_time FROM episode WHERE last_watched_time IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, usize>(0)?,
+                row.get::<_, Option<usize>>(1)?,
+                row.get::<_, Option<usize>>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        for row in rows {
+            let (episode_id, series_id, season_id, last_watched) = row?;
+            // Timestamps may carry different offsets, so compare them as instants
+            let timestamp = match chrono::DateTime::parse_from_rfc3339(&last_watched) {
+                Ok(datetime) => datetime.timestamp(),
+                Err(_) => continue,
+            };
+
+            let mut parents = vec![EntryKey::Episode(episode_id)];
+            parents.extend(series_id.map(EntryKey::Series));
+            parents.extend(season_id.map(EntryKey::Season));
+            for key in parents {
+                let latest = keys.last_watched.entry(key).or_insert(timestamp);
+                *latest = (*latest).max(timestamp);
+            }
+        }
+
+        // Series and seasons add up the plays of their episodes
+        let mut stmt = conn.prepare("SELECT id, series_id, season_id, play_count FROM episode WHERE play_count > 0")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, usize>(0)?,
+                row.get::<_, Option<usize>>(1)?,
+                row.get::<_, Option<usize>>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+
+        for row in rows {
+            let (episode_id, series_id, season_id, play_count) = row?;
+            let mut parents = vec![EntryKey::Episode(episode_id)];
+            parents.extend(series_id.map(EntryKey::Series));
+            parents.extend(season_id.map(EntryKey::Season));
+            for key in parents {
+                *keys.play_count.entry(key).or_insert(0) += play_count.max(0) as u32;
+            }
         }
-        // If num < expected, skip it (duplicate or out of order, shouldn't happen with ORDER BY)
+
+        // Values for the library sorts, rolled up into series and seasons
+        let mut stmt = conn.prepare(
+            "SELECT
+                episode.id,
+                episode.series_id,
+                episode.season_id,
+                CASE WHEN episode.year IS NULL OR episode.year = '' THEN NULL ELSE CAST(episode.year AS INTEGER) END,
+                episode.added_time,
+                CASE WHEN episode.episode_number IS NULL OR episode.episode_number = '' THEN NULL ELSE CAST(episode.episode_number AS INTEGER) END,
+                episode.watched,
+                episode.file_size,
+                season.number
+            FROM episode
+            LEFT JOIN season ON season.id = episode.season_id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, usize>(0)?,
+                row.get::<_, Option<usize>>(1)?,
+                row.get::<_, Option<usize>>(2)?,
+                row.get::<_, Option<i64>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<i64>>(5)?,
+                row.get::<_, bool>(6)?,
+                row.get::<_, Option<i64>>(7)?,
+                row.get::<_, Option<i64>>(8)?,
+            ))
+        })?;
+
+        for row in rows {
+            let (episode_id, series_id, season_id, year, added_time, episode_number, watched, file_size, season_number) = row?;
+            let added = added_time
+                .and_then(|added| chrono::DateTime::parse_from_rfc3339(&added).ok())
+                .map(|datetime| datetime.timestamp());
+            if let Some(number) = episode_number {
+                keys.episode_number.insert(EntryKey::Episode(episode_id), number);
+            }
+            if let (Some(season_id), Some(number)) = (season_id, season_number) {
+                keys.episode_number.insert(EntryKey::Season(season_id), number);
+            }
+
+            let mut parents = vec![EntryKey::Episode(episode_id)];
+            parents.extend(series_id.map(EntryKey::Series));
+            parents.extend(season_id.map(EntryKey::Season));
+            for key in parents {
+                if let Some(year) = year.filter(|year| *year > 0) {
+                    let earliest = keys.year.entry(key).or_insert(year);
+                    *earliest = (*earliest).min(year);
+                }
+                if let Some(added) = added {
+                    let latest = keys.added.entry(key).or_insert(added);
+                    *latest = (*latest).max(added);
+                }
+                let all_watched = keys.watched.entry(key).or_insert(watched);
+                *all_watched = *all_watched && watched;
+                if let Some(size) = file_size {
+                    *keys.file_size.entry(key).or_insert(0) += size;
+                }
+            }
+        }
+
+        Ok(keys)
+    }
+
+    /// Mark episode as unwatched (preserves last_watched_time)
+    pub fn mark_episode_unwatched(&self, episode_id: usize) -> DbResult<()> {
+        let conn = self.connection();
+
+        conn.execute(
+            "UPDATE episode SET watched = false WHERE id = ?1",
+            params![episode_id],
+        )?;
+
+        Ok(())
+    }
+
+    /// Reset episode progress to zero
+    pub fn reset_episode_progress(&self, episode_id: usize) -> DbResult<()> {
+        let conn = self.connection();
+
+        conn.execute(
+            "UPDATE episode SET last_progress_time = 0 WHERE id = ?1",
+            params![episode_id],
+        )?;
+
+        Ok(())
     }
 
-    // No gaps found, return the next sequential number
-    Ok(expected)
+    /// Export every episode with its series, season, and watch state to a JSON or CSV file
+    pub fn export_library(&self, format: ExportFormat, path: &Path) -> DbResult<usize> {
+        let records = self.get_library_records()?;
+
+        let contents = match format {
+            ExportFormat::Json => records_to_json(&records)?,
+            ExportFormat::Csv => records_to_csv(&records),
+        };
+
+        if let Err(e) = std::fs::write(path, contents) {
+            crate::logger::log_error(&format!("Failed to write library export to {}: {}", path.display(), e));
+            return Err(e.into());
+        }
+
+        crate::logger::log_info(&format!(
+            "Exported {} episodes as {} to {}",
+            records.len(),
+            format.label(),
+            path.display()
+        ));
+
+        Ok(records.len())
+    }
+
+    /// Load every episode as a flat export record ordered by series, season, and episode number
+    pub fn get_library_records(&self) -> DbResult<Vec<LibraryRecord>> {
+        let conn = self.connection();
+
+        let records = query_library_records(&conn)?
+            .into_iter()
+            .map(|(_, record)| record)
+            .collect();
+
+        Ok(records)
+    }
+
+    /// Merge metadata from a JSON or CSV export into the library, matching episodes by root and relative location
+    /// Falls back to a unique file name match when the location changed; a dry run only reports changes
+    pub fn import_library(&self, path: &Path, dry_run: bool) -> DbResult<ImportReport> {
+        let format = ExportFormat::from_path(path)
+            .ok_or_else(|| DatabaseError::Import(format!("Unsupported import file: {}", path.display())))?;
+        let content = std::fs::read_to_string(path)?;
+        let (incoming, columns) = match format {
+            ExportFormat::Json => parse_json(&content)?,
+            ExportFormat::Csv => parse_csv(&content).map_err(DatabaseError::Import)?,
+        };
+
+        let mut conn = self.connection();
+        let current = query_library_records(&conn)?;
+
+        // Index current episodes by root and location and by file name for fallback matching
+        // The same relative location can be under several roots, so it only identifies an episode with its root
+        let mut by_location = std::collections::HashMap::new();
+        let mut by_file_name: std::collections::HashMap<String, Vec<usize>> = std::collections::HashMap::new();
+        for (index, (_, record)) in current.iter().enumerate() {
+            by_location.insert((record.root.as_deref(), record.location.as_str()), index);
+            if let Some(file_name) = Path::new(&record.location).file_name() {
+                by_file_name
+                    .entry(file_name.to_string_lossy().to_string())
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        let mut report = ImportReport {
+            dry_run,
+            total: incoming.len(),
+            ..Default::default()
+        };
+
+        let tx = conn.transaction()?;
+        for record in &incoming {
+            let matched = by_location.get(&(record.root.as_deref(), record.location.as_str())).copied().or_else(|| {
+                let file_name = Path::new(&record.location).file_name()?.to_string_lossy().to_string();
+                match by_file_name.get(&file_name) {
+                    Some(indices) if indices.len() == 1 => Some(indices[0]),
+                    _ => None,
+                }
+            });
+
+            let (episode_id, existing) = match matched {
+                Some(index) => (current[index].0, &current[index].1),
+                None => {
+                    report.unmatched.push(record.location.clone());
+                    continue;
+                }
+            };
+
+            let changes = describe_changes(existing, record, &columns);
+            if changes.is_empty() {
+                report.unchanged += 1;
+                continue;
+            }
+
+            report.updated += 1;
+            report.changes.push(format!("{}: {}", existing.location, changes.join(", ")));
+
+            if !dry_run {
+                apply_library_record(&tx, episode_id, &merge_record(existing, record, &columns))?;
+            }
+        }
+        if !dry_run {
+            // Episodes moved to another series or none can leave the old one empty
+            delete_orphaned_series(&tx)?;
+        }
+        tx.commit()?;
+
+        crate::logger::log_info(&format!(
+            "Library import from {} (dry run: {}): {} updated, {} unchanged, {} unmatched",
+            path.display(),
+            dry_run,
+            report.updated,
+            report.unchanged,
+            report.unmatched.len()
+        ));
+
+        Ok(report)
+    }
+
+    /// Apply watch history from another media center, matching episodes by file name
+    /// A file name several episodes share goes to the one whose location ends in the most folders of the
+    /// record's path; when that doesn't single one out the record is counted as ambiguous and left alone
+    /// Episodes are only ever marked watched, never unwatched; resume points fill unwatched episodes without progress
+    pub fn apply_watch_history(&self, records: &[WatchRecord]) -> DbResult<WatchImportReport> {
+        let mut conn = self.connection();
+
+        // Index episodes by the file name portion of their relative location
+        let mut by_file_name: std::collections::HashMap<String, Vec<(usize, String)>> = std::collections::HashMap::new();
+        {
+            let mut stmt = conn.prepare("SELECT id, location FROM episode")?;
+            let rows = stmt.query_map([], |row| Ok((row.get::<_, usize>(0)?, row.get::<_, String>(1)?)))?;
+            for row in rows {
+                let (id, location) = row?;
+                by_file_name.entry(file_name_of(&location)).or_default().push((id, location));
+            }
+        }
+
+        let mut report = WatchImportReport {
+            total: records.len(),
+            ..Default::default()
+        };
+
+        let tx = conn.transaction()?;
+        for record in records {
+            let candidates = match by_file_name.get(&file_name_of(&record.path)) {
+                Some(candidates) => candidates,
+                None => continue,
+            };
+            let episode_id = match candidates.as_slice() {
+                [(id, _)] => *id,
+                _ => {
+                    let scored: Vec<(usize, usize)> = candidates
+                        .iter()
+                        .map(|(id, location)| (common_suffix_len(&record.path, location), *id))
+                        .collect();
+                    let best = scored.iter().map(|(score, _)| *score).max().unwrap_or(0);
+                    let mut best_ids = scored.iter().filter(|(score, _)| *score == best).map(|(_, id)| *id);
+                    match (best_ids.next(), best_ids.next()) {
+                        (Some(id), None) => id,
+                        _ => {
+                            report.ambiguous += 1;
+                            continue;
+                        }
+                    }
+                }
+            };
+            report.matched += 1;
+
+            // Never lower a play count that was already tracked here
+            tx.execute(
+                "UPDATE episode SET play_count = MAX(play_count, ?1) WHERE id = ?2",
+                params![record.play_count, episode_id],
+            )?;
+
+            if record.play_count > 0 {
+                let changed = tx.execute(
+                    "UPDATE episode SET watched = true, last_watched_time = COALESCE(last_watched_time, ?1)
+                     WHERE id = ?2 AND watched = false",
+                    params![record.last_played, episode_id],
+                )?;
+                report.marked_watched += changed;
+            } else if let Some(resume) = record.resume_seconds {
+                let changed = tx.execute(
+                    "UPDATE episode SET last_progress_time = ?1
+                     WHERE id = ?2 AND watched = false AND COALESCE(last_progress_time, 0) = 0",
+                    params![resume as i64, episode_id],
+                )?;
+                report.resumed += changed;
+            }
+        }
+        tx.commit()?;
+
+        crate::logger::log_info(&format!(
+            "Watch history import: {} of {} records matched, {} ambiguous, {} marked watched, {} resume points",
+            report.matched, report.total, report.ambiguous, report.marked_watched, report.resumed
+        ));
+
+        Ok(report)
+    }
+
+    /// Mark episodes watched by relative location, keeping any existing last watched time
+    /// Returns the number of episodes that changed from unwatched to watched
+    #[cfg_attr(not(any(feature = "trakt", feature = "jellyfin")), allow(dead_code))]
+    pub fn mark_locations_watched(&self, locations: &[(String, Option<String>)]) -> DbResult<usize> {
+        let mut conn = self.connection();
+        let tx = conn.transaction()?;
+        let mut changed = 0;
+        for (location, watched_at) in locations {
+            changed += tx.execute(
+                "UPDATE episode SET watched = true, last_watched_time = COALESCE(last_watched_time, ?1)
+                 WHERE location = ?2 AND watched = false",
+                params![watched_at, location],
+            )?;
+        }
+        tx.commit()?;
+        Ok(changed)
+    }
+
+    /// Set the playback position of unwatched episodes by relative location, returning how many changed
+    #[cfg_attr(not(feature = "jellyfin"), allow(dead_code))]
+    pub fn set_locations_progress(&self, positions: &[(String, u64)]) -> DbResult<usize> {
+        let mut conn = self.connection();
+        let tx = conn.transaction()?;
+        let mut changed = 0;
+        for (location, seconds) in positions {
+            changed += tx.execute(
+                "UPDATE episode SET last_progress_time = ?1 WHERE location = ?2 AND watched = false",
+                params![*seconds as i64, location],
+            )?;
+        }
+        tx.commit()?;
+        Ok(changed)
+    }
 }
 
-/// Get episodes with missing length (NULL or 0)
-pub fn get_episodes_with_missing_length() -> Result<Vec<(usize, String)>, Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
-    
-    let mut stmt = conn.prepare(
-        "SELECT id, location FROM episode WHERE length IS NULL OR length = 0"
-    )?;
-    
-    let episodes = stmt.query_map([], |row| {
-        Ok((
-            row.get::<_, usize>(0)?,
-            row.get::<_, String>(1)?,
-        ))
-    })?
-    .collect::<Result<Vec<_>>>()?;
-    
-    Ok(episodes)
+impl Writes<'_> {
+    /// Create a series called `name`, returning its id
+    pub fn create_series(&self, name: &str) -> DbResult<usize> {
+        create_series(self.conn, name)
+    }
+
+    /// Id of season `number` of the series, created when it doesn't exist yet
+    pub fn find_or_create_season(&self, series_id: usize, number: usize) -> DbResult<usize> {
+        find_or_create_season(self.conn, series_id, number)
+    }
+
+    pub fn update_episode_detail(&self, id: usize, details: &EpisodeDetail) -> DbResult<()> {
+        update_episode_detail(self.conn, id, details)
+    }
+
+    pub fn toggle_watched_status(&self, id: usize) -> DbResult<bool> {
+        toggle_watched_status(self.conn, id)
+    }
+
+    pub fn update_episode_notes(&self, id: usize, notes: &str) -> DbResult<()> {
+        update_episode_notes(self.conn, id, notes)
+    }
+
+    pub fn update_episode_alt_titles(&self, id: usize, alt_titles: &AltTitles) -> DbResult<()> {
+        update_episode_alt_titles(self.conn, id, alt_titles)
+    }
 }
 
-/// Update episode progress time in seconds
-pub fn update_episode_progress(episode_id: usize, progress_seconds: u64) -> Result<(), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
-    
-    conn.execute(
-        "UPDATE episode SET last_progress_time = ?1 WHERE id = ?2",
-        params![progress_seconds as i64, episode_id],
-    )?;
-    
+fn create_series(conn: &Connection, name: &str) -> DbResult<usize> {
+    conn.execute("INSERT INTO series (name) VALUES (?1)", params![name])?;
+    Ok(conn.last_insert_rowid() as usize)
+}
+
+fn find_or_create_season(conn: &Connection, series_id: usize, number: usize) -> DbResult<usize> {
+    //first, try to retrieve an existing season based on the series_id and season_number
+    let mut stmt = conn.prepare("SELECT id FROM season WHERE series_id = ?1 AND number = ?2")?;
+    if let Ok(season_id) = stmt.query_row(params![series_id, number], |row| row.get(0)) {
+        return Ok(season_id);
+    }
+    //if the season does not exist, create it
+    conn.execute("INSERT INTO season (series_id, number) VALUES (?1, ?2)", params![series_id, number])?;
+    Ok(conn.last_insert_rowid() as usize)
+}
+
+fn update_episode_detail(conn: &Connection, id: usize, details: &EpisodeDetail) -> DbResult<()> {
+    if let Err(e) = conn.execute(
+        "UPDATE episode SET name = ?1, year = ?2, watched = ?3, length = ?4, series_id = ?5, season_id = ?6, episode_number = ?7 WHERE id = ?8",
+        params![
+            details.title,
+            details.year,
+            details.watched == "true",
+            details.length,
+            details.series.as_ref().map(|s| &s.id),
+            details.season.as_ref().map(|s| &s.id),
+            details.episode_number,
+            id
+        ],
+    ) {
+        crate::logger::log_error(&format!("Failed to update episode {}: {}", id, e));
+        return Err(e.into());
+    }
     Ok(())
 }
 
-/// Get episode progress time in seconds
-pub fn get_episode_progress(episode_id: usize) -> Result<Option<u64>, Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
-    
-    let mut stmt = conn.prepare("SELECT last_progress_time FROM episode WHERE id = ?1")?;
-    let progress: Option<i64> = stmt.query_row(params![episode_id], |row| row.get(0))?;
-    
-    Ok(progress.map(|p| p as u64))
+fn toggle_watched_status(conn: &Connection, id: usize) -> DbResult<bool> {
+    // First, get the current watched status
+    let mut stmt = conn.prepare("SELECT watched FROM episode WHERE id = ?1")?;
+    let current_watched: bool = stmt.query_row(params![id], |row| row.get(0))?;
+
+    if current_watched {
+        // If currently watched, mark as unwatched, preserve last_watched_time, and reset progress
+        conn.execute(
+            "UPDATE episode SET watched = false, last_progress_time = 0 WHERE id = ?1",
+            params![id],
+        )?;
+        Ok(false) // Now unwatched
+    } else {
+        // If currently unwatched, mark as watched with timestamp and reset progress
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE episode SET watched = true, last_watched_time = ?1, last_progress_time = 0 WHERE id = ?2",
+            params![now, id],
+        )?;
+        Ok(true) // Now watched
+    }
 }
 
-/// Mark episode as watched with current timestamp and reset progress
-pub fn mark_episode_watched_with_timestamp(episode_id: usize) -> Result<(), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
-    
-    // Get current timestamp in ISO 8601 format
-    let now = chrono::Utc::now().to_rfc3339();
-    
-    conn.execute(
-        "UPDATE episode SET watched = true, last_watched_time = ?1, last_progress_time = 0 WHERE id = ?2",
-        params![now, episode_id],
-    )?;
-    
+fn update_episode_notes(conn: &Connection, id: usize, notes: &str) -> DbResult<()> {
+    let notes = notes.trim();
+    let value = if notes.is_empty() { None } else { Some(notes) };
+    if let Err(e) = conn.execute("UPDATE episode SET notes = ?1 WHERE id = ?2", params![value, id]) {
+        crate::logger::log_error(&format!("Failed to update notes for episode {}: {}", id, e));
+        return Err(e.into());
+    }
     Ok(())
 }
 
-/// Mark episode as unwatched (preserves last_watched_time)
-pub fn mark_episode_unwatched(episode_id: usize) -> Result<(), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
-    
-    conn.execute(
-        "UPDATE episode SET watched = false WHERE id = ?1",
-        params![episode_id],
-    )?;
-    
+fn update_episode_alt_titles(conn: &Connection, id: usize, alt_titles: &AltTitles) -> DbResult<()> {
+    let value = if alt_titles.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(alt_titles).map_err(|e| DatabaseError::Import(e.to_string()))?)
+    };
+    if let Err(e) = conn.execute("UPDATE episode SET alt_titles = ?1 WHERE id = ?2", params![value, id]) {
+        crate::logger::log_error(&format!("Failed to update alternate titles for episode {}: {}", id, e));
+        return Err(e.into());
+    }
     Ok(())
 }
 
-/// Reset episode progress to zero
-pub fn reset_episode_progress(episode_id: usize) -> Result<(), Box<dyn std::error::Error>> {
-    let conn = get_connection().lock().unwrap();
-    
+/// Read the stored alternate titles, ignoring malformed values
+fn parse_alt_titles(json: Option<&str>) -> AltTitles {
+    json.and_then(|json| serde_json::from_str(json).ok()).unwrap_or_default()
+}
+
+/// Query every episode with its id as a flat export record
+fn query_library_records(conn: &Connection) -> Result<Vec<(usize, LibraryRecord)>> {
+    let mut stmt = conn.prepare(
+        "SELECT 
+                episode.id,
+                episode.location,
+                episode.name,
+                CASE WHEN episode.year IS NULL OR episode.year = '' THEN NULL ELSE CAST(episode.year AS INTEGER) END,
+                episode.watched,
+                CASE WHEN episode.length IS NULL OR episode.length = '' THEN NULL ELSE CAST(episode.length AS INTEGER) END,
+                series.name,
+                season.number,
+                CASE WHEN episode.episode_number IS NULL OR episode.episode_number = '' THEN NULL ELSE CAST(episode.episode_number AS INTEGER) END,
+                episode.last_watched_time,
+                episode.last_progress_time,
+                root.path
+            FROM episode
+            LEFT JOIN root ON root.id = episode.root_id
+            LEFT JOIN season ON season.id = episode.season_id AND season.series_id = episode.series_id
+            LEFT JOIN series ON series.id = episode.series_id
+            ORDER BY 
+                CASE WHEN series.name IS NULL THEN 1 ELSE 0 END,
+                series.name,
+                season.number,
+                CAST(episode.episode_number AS INTEGER),
+                episode.name"
+    )?;
+
+    let records = stmt.query_map([], |row| {
+        Ok((
+            row.get(0)?,
+            LibraryRecord {
+                location: row.get(1)?,
+                title: row.get(2)?,
+                year: row.get(3)?,
+                watched: row.get(4)?,
+                length: row.get(5)?,
+                series: row.get(6)?,
+                season: row.get(7)?,
+                episode_number: row.get(8)?,
+                last_watched_time: row.get(9)?,
+                last_progress_time: row.get(10)?,
+                root: row.get(11)?,
+            },
+        ))
+    })?
+    .collect::<Result<Vec<_>>>()?;
+
+    Ok(records)
+}
+
+/// Write imported metadata, merged with the current values, onto an episode, creating its series
+/// and season when needed
+fn apply_library_record(conn: &Connection, episode_id: usize, record: &LibraryRecord) -> Result<()> {
+    let series_id: Option<i64> = match record.series {
+        Some(ref name) => {
+            let found: Option<i64> = conn
+                .query_row("SELECT id FROM series WHERE name = ?1", params![name], |row| row.get(0))
+                .ok();
+            match found {
+                Some(id) => Some(id),
+                None => {
+                    conn.execute("INSERT INTO series (name) VALUES (?1)", params![name])?;
+                    Some(conn.last_insert_rowid())
+                }
+            }
+        }
+        None => None,
+    };
+
+    let season_id: Option<i64> = match (series_id, record.season) {
+        (Some(series_id), Some(number)) => {
+            let found: Option<i64> = conn
+                .query_row(
+                    "SELECT id FROM season WHERE series_id = ?1 AND number = ?2",
+                    params![series_id, number],
+                    |row| row.get(0),
+                )
+                .ok();
+            match found {
+                Some(id) => Some(id),
+                None => {
+                    conn.execute(
+                        "INSERT INTO season (series_id, number) VALUES (?1, ?2)",
+                        params![series_id, number],
+                    )?;
+                    Some(conn.last_insert_rowid())
+                }
+            }
+        }
+        _ => None,
+    };
+
     conn.execute(
-        "UPDATE episode SET last_progress_time = 0 WHERE id = ?1",
-        params![episode_id],
+        "UPDATE episode SET name = ?1, year = ?2, watched = ?3, length = ?4, series_id = ?5, season_id = ?6,
+            episode_number = ?7, last_watched_time = ?8, last_progress_time = ?9 WHERE id = ?10",
+        params![
+            record.title,
+            record.year,
+            record.watched,
+            record.length.unwrap_or(0),
+            series_id,
+            season_id,
+            record.episode_number,
+            record.last_watched_time,
+            record.last_progress_time,
+            episode_id
+        ],
     )?;
-    
+
     Ok(())
 }
@@ -0,0 +1,190 @@
+use crate::logger::{log_error, log_info, log_warn};
+use rusqlite::{Connection, OpenFlags};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Outcome of copying the readable rows of a damaged database into a fresh file
+#[derive(Debug, Clone, Default)]
+pub struct RecoveryReport {
+    pub tables: usize,
+    pub rows_recovered: usize,
+    pub rows_lost: usize,
+}
+
+impl RecoveryReport {
+    /// One-line summary for the console and log
+    pub fn summary(&self) -> String {
+        format!(
+            "Recovered {} rows from {} tables ({} unreadable rows dropped)",
+            self.rows_recovered, self.tables, self.rows_lost
+        )
+    }
+}
+
+/// Path of the rolling backup kept next to the database
+pub fn backup_path(db_path: &Path) -> PathBuf {
+    with_suffix(db_path, ".bak")
+}
+
+/// Append a suffix to the full file name (videos.sqlite -> videos.sqlite.bak)
+fn with_suffix(db_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    db_path.with_file_name(name)
+}
+
+/// Run `PRAGMA quick_check` and return the problems it reports (empty when healthy)
+/// A file SQLite cannot read at all is reported as a single problem rather than an error
+pub fn quick_check(db_path: &Path) -> Vec<String> {
+    let result = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY).and_then(|conn| {
+        let mut stmt = conn.prepare("PRAGMA quick_check")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.collect::<rusqlite::Result<Vec<String>>>()
+    });
+
+    match result {
+        Ok(rows) if rows.len() == 1 && rows[0] == "ok" => Vec::new(),
+        Ok(rows) => rows,
+        Err(e) => vec![e.to_string()],
+    }
+}
+
+/// Check the database before it is opened, backing it up when healthy, and return the problems found
+/// A file that doesn't exist yet is a new library, with nothing to check or back up
+pub fn check_at_startup(db_path: &Path) -> Vec<String> {
+    if !db_path.exists() {
+        return Vec::new();
+    }
+    let problems = quick_check(db_path);
+    if problems.is_empty() {
+        // Keep a known-good copy to restore from if the file is damaged later
+        if let Err(e) = create_backup(db_path) {
+            log_warn(&format!("Could not back up database {}: {}", db_path.display(), e));
+        }
+    }
+    problems
+}
+
+/// Snapshot a healthy database to the rolling backup file
+pub fn create_backup(db_path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    let backup = backup_path(db_path);
+    let temp = with_suffix(db_path, ".bak.tmp");
+    let _ = std::fs::remove_file(&temp);
+
+    // VACUUM INTO writes a consistent, compacted copy even while the file is open elsewhere
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    conn.execute("VACUUM INTO ?1", [temp.to_string_lossy().as_ref()])?;
+    drop(conn);
+
+    std::fs::rename(&temp, &backup)?;
+    log_info(&format!("Database backup written to {}", backup.display()));
+    Ok(backup)
+}
+
+/// Move the damaged file aside so it is never overwritten, returning its new path
+fn quarantine(db_path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let corrupt = with_suffix(db_path, &format!(".corrupt-{}", stamp));
+    std::fs::rename(db_path, &corrupt)?;
+    log_warn(&format!("Moved damaged database to {}", corrupt.display()));
+    Ok(corrupt)
+}
+
+/// Rebuild the database by copying every readable row into a fresh file
+/// `progress` is called with a message before each table is copied
+pub fn dump_and_reload(db_path: &Path, progress: &mut dyn FnMut(&str)) -> Result<RecoveryReport, Box<dyn Error>> {
+    let recovered_path = with_suffix(db_path, ".recovered");
+    let _ = std::fs::remove_file(&recovered_path);
+
+    let source = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut target = Connection::open(&recovered_path)?;
+    let mut report = RecoveryReport::default();
+
+    // Recreate tables first, indexes after the data is in place
+    let schema: Vec<(String, String, String)> = {
+        let mut stmt = source.prepare(
+            "SELECT type, name, sql FROM sqlite_master
+             WHERE sql IS NOT NULL AND name NOT LIKE 'sqlite_%'
+             ORDER BY CASE type WHEN 'table' THEN 0 ELSE 1 END",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    for (kind, name, sql) in &schema {
+        if kind != "table" {
+            if let Err(e) = target.execute_batch(sql) {
+                log_warn(&format!("Recovery: could not recreate {} {}: {}", kind, name, e));
+            }
+            continue;
+        }
+
+        progress(&format!("Recovering table {}...", name));
+        target.execute_batch(sql)?;
+        report.tables += 1;
+
+        let (copied, lost) = copy_table_rows(&source, &mut target, name)?;
+        log_info(&format!("Recovery: table {} copied {} rows, lost {}", name, copied, lost));
+        report.rows_recovered += copied;
+        report.rows_lost += lost;
+    }
+    drop(source);
+    drop(target);
+
+    // Swap the rebuilt file in, keeping the damaged one for inspection
+    quarantine(db_path)?;
+    std::fs::rename(&recovered_path, db_path)?;
+    log_info(&format!("Database recovery complete: {}", report.summary()));
+    Ok(report)
+}
+
+/// Copy rows one at a time so a damaged page only loses the rows stored on it
+fn copy_table_rows(source: &Connection, target: &mut Connection, table: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    let quoted = format!("\"{}\"", table.replace('"', "\"\""));
+    let mut stmt = source.prepare(&format!("SELECT * FROM {}", quoted))?;
+    let column_count = stmt.column_count();
+    let placeholders = vec!["?"; column_count].join(", ");
+    let insert = format!("INSERT INTO {} VALUES ({})", quoted, placeholders);
+
+    let tx = target.transaction()?;
+    let mut copied = 0;
+    let mut lost = 0;
+    let mut rows = stmt.query([])?;
+    loop {
+        match rows.next() {
+            Ok(Some(row)) => {
+                let values: rusqlite::Result<Vec<rusqlite::types::Value>> =
+                    (0..column_count).map(|i| row.get(i)).collect();
+                match values.and_then(|values| tx.execute(&insert, rusqlite::params_from_iter(values))) {
+                    Ok(_) => copied += 1,
+                    Err(_) => lost += 1,
+                }
+            }
+            Ok(None) => break,
+            Err(e) => {
+                // The cursor cannot move past a damaged page; keep what we have
+                log_error(&format!("Recovery: stopped reading table {}: {}", table, e));
+                lost += 1;
+                break;
+            }
+        }
+    }
+    tx.commit()?;
+    Ok((copied, lost))
+}
+
+/// Replace the damaged database with the rolling backup
+pub fn restore_backup(db_path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    let backup = backup_path(db_path);
+    if !backup.is_file() {
+        return Err(format!("No backup found at {}", backup.display()).into());
+    }
+    if !quick_check(&backup).is_empty() {
+        return Err(format!("Backup at {} is also damaged", backup.display()).into());
+    }
+
+    quarantine(db_path)?;
+    std::fs::copy(&backup, db_path)?;
+    log_info(&format!("Restored database from backup {}", backup.display()));
+    Ok(backup)
+}
@@ -0,0 +1,270 @@
+use crate::app_state::{AppState, DrawContext};
+use crate::buffer::BufferManager;
+use crate::database::{self, Database};
+use crate::display::{self, draw_screen};
+use crate::dto::EpisodeDetail;
+use crate::path_resolver::PathResolver;
+use crate::theme::Theme;
+use crate::util::{Entry, Mode, ViewContext};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Screen size of the screenshots, matching the size requested at startup
+pub const DEMO_WIDTH: usize = 110;
+pub const DEMO_HEIGHT: usize = 30;
+
+/// One video of the synthetic demo library
+struct DemoVideo {
+    file: &'static str,
+    title: &'static str,
+    series: Option<&'static str>,
+    season: usize,
+    episode: usize,
+    year: u32,
+    minutes: u32,
+    watched: bool,
+}
+
+const fn episode(
+    file: &'static str,
+    title: &'static str,
+    series: &'static str,
+    season: usize,
+    episode: usize,
+    year: u32,
+    watched: bool,
+) -> DemoVideo {
+    DemoVideo { file, title, series: Some(series), season, episode, year, minutes: 42, watched }
+}
+
+const fn movie(file: &'static str, title: &'static str, year: u32, minutes: u32, watched: bool) -> DemoVideo {
+    DemoVideo { file, title, series: None, season: 0, episode: 0, year, minutes, watched }
+}
+
+/// The bundled library: two series and a few movies, partly watched
+const DEMO_LIBRARY: &[DemoVideo] = &[
+    episode("Harbor Lights/S01E01.mkv", "Low Tide", "Harbor Lights", 1, 1, 2019, true),
+    episode("Harbor Lights/S01E02.mkv", "The Keeper", "Harbor Lights", 1, 2, 2019, true),
+    episode("Harbor Lights/S01E03.mkv", "Fog Signal", "Harbor Lights", 1, 3, 2019, false),
+    episode("Harbor Lights/S01E04.mkv", "Salt and Iron", "Harbor Lights", 1, 4, 2019, false),
+    episode("Harbor Lights/S02E01.mkv", "Return Voyage", "Harbor Lights", 2, 1, 2021, false),
+    episode("Harbor Lights/S02E02.mkv", "Breakwater", "Harbor Lights", 2, 2, 2021, false),
+    episode("Orbit Kitchen/S01E01.mp4", "Zero-G Pancakes", "Orbit Kitchen", 1, 1, 2022, true),
+    episode("Orbit Kitchen/S01E02.mp4", "Moon Cheese", "Orbit Kitchen", 1, 2, 2022, true),
+    episode("Orbit Kitchen/S01E03.mp4", "Comet Curry", "Orbit Kitchen", 1, 3, 2022, true),
+    movie("Movies/The Quiet Meridian (2016).mkv", "The Quiet Meridian", 2016, 118, true),
+    movie("Movies/Paper Satellites (2020).mkv", "Paper Satellites", 2020, 97, false),
+    movie("Movies/Glass Orchard (2023).mp4", "Glass Orchard", 2023, 131, false),
+];
+
+fn to_io_error(e: database::DatabaseError) -> io::Error {
+    io::Error::other(e.to_string())
+}
+
+/// Create the demo library under `root`, returning it with its path resolver
+fn build_library(root: &Path) -> io::Result<(Database, PathResolver)> {
+    let db_path = root.join("videos.sqlite");
+    let database = Database::open(&db_path).map_err(to_io_error)?;
+    let resolver = PathResolver::from_database_path(&db_path).map_err(|e| io::Error::other(e.to_string()))?;
+
+    for video in DEMO_LIBRARY {
+        let path = root.join(video.file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, b"")?;
+        database
+            .import_episode_relative(&path.to_string_lossy(), video.title, &resolver)
+            .map_err(to_io_error)?;
+    }
+
+    let ids: HashMap<String, usize> = database
+        .get_entries()
+        .map_err(to_io_error)?
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Entry::Episode { episode_id, location, .. } => Some((location, episode_id)),
+            _ => None,
+        })
+        .collect();
+
+    let mut series_ids: HashMap<&str, usize> = HashMap::new();
+    for video in DEMO_LIBRARY {
+        let id = ids[video.file];
+        if let Some(series) = video.series {
+            let series_id = match series_ids.get(series) {
+                Some(series_id) => {
+                    database.assign_series(*series_id, id).map_err(to_io_error)?;
+                    *series_id
+                }
+                None => {
+                    let detail = database.create_series_and_assign(series, id).map_err(to_io_error)?;
+                    let series_id = detail.series.map(|series| series.id).unwrap_or_default();
+                    series_ids.insert(series, series_id);
+                    series_id
+                }
+            };
+            database.create_season_and_assign(series_id, video.season, id).map_err(to_io_error)?;
+        }
+
+        let mut detail = database.get_episode_detail(id).map_err(to_io_error)?;
+        detail.year = video.year.to_string();
+        detail.length = (video.minutes * 60).to_string();
+        detail.watched = video.watched.to_string();
+        if video.episode > 0 {
+            detail.episode_number = video.episode.to_string();
+        }
+        database.update_episode_detail(id, &detail).map_err(to_io_error)?;
+    }
+
+    Ok((database, resolver))
+}
+
+/// A Browse-style screen to capture
+struct DemoScreen {
+    name: &'static str,
+    mode: Mode,
+    view_context: ViewContext,
+    entries: Vec<Entry>,
+    current_item: usize,
+}
+
+/// Draw a Browse-style screen into the buffer
+fn draw_demo_screen(
+    screen: &DemoScreen,
+    detail: &EpisodeDetail,
+    context: &DrawContext,
+    buffer_manager: &mut BufferManager,
+) -> io::Result<()> {
+    // The menu, when open, is for the selected entry
+    let mut state = AppState {
+        current_item: screen.current_item,
+        remembered_item: screen.current_item,
+        mode: screen.mode.clone(),
+        edit_details: detail.clone(),
+        edit_cursor_pos: detail.title.chars().count(),
+        view_context: screen.view_context.clone(),
+        ..AppState::new(screen.entries.clone())
+    };
+    draw_screen(&mut state, context, buffer_manager)?;
+    Ok(())
+}
+
+/// Write the buffer as `<name>.txt` and `<name>.ans` in the output directory
+fn save_screenshot(buffer_manager: &BufferManager, output_dir: &Path, name: &str) -> io::Result<PathBuf> {
+    fs::write(output_dir.join(format!("{}.txt", name)), buffer_manager.desired_text())?;
+    let ansi_path = output_dir.join(format!("{}.ans", name));
+    fs::write(&ansi_path, buffer_manager.desired_ansi())?;
+    Ok(ansi_path)
+}
+
+/// Render representative screens of the demo library with `theme` and save them as screenshots
+/// Returns the paths of the ANSI screenshots; each has a plain text twin
+pub fn run_demo(output_dir: &Path, theme: &Theme) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(output_dir)?;
+    let library_dir = std::env::temp_dir().join(format!("movies-demo-{}", std::process::id()));
+    if library_dir.exists() {
+        fs::remove_dir_all(&library_dir)?;
+    }
+    fs::create_dir_all(&library_dir)?;
+    let result = capture_screens(&library_dir, output_dir, theme);
+    fs::remove_dir_all(&library_dir).ok();
+    result
+}
+
+fn capture_screens(library_dir: &Path, output_dir: &Path, theme: &Theme) -> io::Result<Vec<PathBuf>> {
+    let (database, resolver) = build_library(library_dir)?;
+    crate::terminal::set_headless(DEMO_WIDTH, DEMO_HEIGHT);
+    let mut buffer_manager = BufferManager::new(DEMO_WIDTH, DEMO_HEIGHT);
+
+    let library = database.get_entries().map_err(to_io_error)?;
+    let first_movie = library
+        .iter()
+        .position(|entry| matches!(entry, Entry::Episode { .. }))
+        .unwrap_or(0);
+    let (series_id, series_name) = library
+        .iter()
+        .find_map(|entry| match entry {
+            Entry::Series { series_id, name } => Some((*series_id, name.clone())),
+            _ => None,
+        })
+        .ok_or_else(|| io::Error::other("demo library has no series"))?;
+    let seasons = database.get_entries_for_series(series_id).map_err(to_io_error)?;
+    let season_id = match seasons.first() {
+        Some(Entry::Season { season_id, .. }) => *season_id,
+        _ => return Err(io::Error::other("demo series has no seasons")),
+    };
+    let season_entries = database.get_entries_for_season(season_id).map_err(to_io_error)?;
+
+    // The first unwatched episode is selected inside the season
+    let season_item = season_entries.len().min(3).saturating_sub(1);
+    let season_view = ViewContext::Season { season_id, series_name: series_name.clone(), season_number: 1 };
+    let screens = vec![
+        DemoScreen {
+            name: "01-library",
+            mode: Mode::Browse,
+            view_context: ViewContext::TopLevel,
+            entries: library.clone(),
+            current_item: first_movie,
+        },
+        DemoScreen {
+            name: "02-series",
+            mode: Mode::Browse,
+            view_context: ViewContext::Series { series_id, series_name: series_name.clone() },
+            entries: seasons,
+            current_item: 0,
+        },
+        DemoScreen {
+            name: "03-season",
+            mode: Mode::Browse,
+            view_context: season_view.clone(),
+            entries: season_entries.clone(),
+            current_item: season_item,
+        },
+        DemoScreen {
+            name: "04-menu",
+            mode: Mode::Menu,
+            view_context: season_view.clone(),
+            entries: season_entries.clone(),
+            current_item: season_item,
+        },
+        DemoScreen {
+            name: "05-edit",
+            mode: Mode::Edit,
+            view_context: season_view,
+            entries: season_entries,
+            current_item: season_item,
+        },
+    ];
+
+    // Screens without a selected episode show the first movie's details
+    let movie_detail = match library.get(first_movie) {
+        Some(Entry::Episode { episode_id, .. }) => database.get_episode_detail(*episode_id).map_err(to_io_error)?,
+        _ => return Err(io::Error::other("demo library has no movies")),
+    };
+
+    let context = DrawContext {
+        theme,
+        database: &database,
+        resolver: &resolver,
+        scroll_margin: 0,
+        accessible: false,
+    };
+    let mut screenshots = Vec::new();
+    for screen in &screens {
+        let detail = match screen.entries.get(screen.current_item) {
+            Some(Entry::Episode { episode_id, .. }) => database.get_episode_detail(*episode_id).map_err(to_io_error)?,
+            _ => movie_detail.clone(),
+        };
+        draw_demo_screen(screen, &detail, &context, &mut buffer_manager)?;
+        screenshots.push(save_screenshot(&buffer_manager, output_dir, screen.name)?);
+    }
+
+    let mut help_scroll = 0;
+    display::draw_help(&mut buffer_manager, &crate::keybindings::help_lines(), &mut help_scroll, theme)?;
+    screenshots.push(save_screenshot(&buffer_manager, output_dir, "06-help")?);
+
+    Ok(screenshots)
+}
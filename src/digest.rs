@@ -0,0 +1,94 @@
+use chrono::{DateTime, Datelike, Local};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File in the data directory remembering the week the digest was last shown
+pub const DIGEST_STATE_FILE_NAME: &str = "digest_week";
+
+/// Items listed in each digest section
+pub const DIGEST_SECTION_SIZE: usize = 5;
+
+/// Days without watching before a series counts as stale
+pub const STALE_SERIES_DAYS: i64 = 30;
+
+/// Where selecting a digest item jumps to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestTarget {
+    Episode(usize),
+    Series(usize),
+}
+
+/// One selectable line of the digest
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigestItem {
+    pub label: String,
+    pub target: DigestTarget,
+}
+
+/// Summary of the library shown on the first launch of the week
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Digest {
+    /// Episodes added in the last seven days, newest first
+    pub new_items: Vec<DigestItem>,
+    /// Started series with the fewest unwatched episodes left
+    pub closest_to_finishing: Vec<DigestItem>,
+    /// Length of the episodes watched in the last seven days
+    pub watch_seconds_last_week: u64,
+    /// Unfinished series not watched for a month
+    pub stale_series: Vec<DigestItem>,
+}
+
+impl Digest {
+    /// Whether there is nothing worth showing
+    pub fn is_empty(&self) -> bool {
+        self.new_items.is_empty()
+            && self.closest_to_finishing.is_empty()
+            && self.stale_series.is_empty()
+            && self.watch_seconds_last_week == 0
+    }
+
+    /// Every selectable item in display order
+    pub fn items(&self) -> Vec<&DigestItem> {
+        self.new_items
+            .iter()
+            .chain(&self.closest_to_finishing)
+            .chain(&self.stale_series)
+            .collect()
+    }
+
+    /// The sections with their titles, in display order
+    pub fn sections(&self) -> [(&'static str, &[DigestItem]); 3] {
+        [
+            ("New this week", &self.new_items),
+            ("Closest to finishing", &self.closest_to_finishing),
+            ("Not touched in a month", &self.stale_series),
+        ]
+    }
+}
+
+/// ISO week of `date`, e.g. `2024-W07`
+pub fn week_key(date: DateTime<Local>) -> String {
+    let week = date.iso_week();
+    format!("{}-W{:02}", week.year(), week.week())
+}
+
+/// Default location of the digest state
+pub fn default_digest_state_path() -> Option<PathBuf> {
+    crate::paths::data_dir().map(|dir| dir.join(DIGEST_STATE_FILE_NAME))
+}
+
+/// Whether the digest has not been shown yet in the week of `now`
+pub fn is_due(path: &Path, now: DateTime<Local>) -> bool {
+    match std::fs::read_to_string(path) {
+        Ok(content) => content.trim() != week_key(now),
+        Err(_) => true,
+    }
+}
+
+/// Remember that the digest was shown in the week of `now`
+pub fn mark_shown(path: &Path, now: DateTime<Local>) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, week_key(now))
+}
@@ -1,82 +1,201 @@
-use crate::components::{Component, category::{Category, CategoryType}, Browser, DetailPanel, StatusBar, ContextMenu, SeriesSelectWindow};
+use crate::app_state::{AppState, DrawContext};
+use crate::archive::Archive;
+use crate::artwork::{self, ArtworkState};
+use crate::components::{Component, category::{Category, CategoryType}, Browser, DetailPanel, StatusBar, ContextMenu, SeriesSelectWindow, NotesEditor, TutorialOverlay, AltTitlesEditor, GridBrowser, EmptyState, HelpBar, Toast, toast::TOAST_HEIGHT};
+use crate::components::confirm_dialog::CONFIRM_WINDOW_WIDTH;
+use crate::database::Database;
+use crate::components::tutorial_overlay::{wrap_words, TUTORIAL_HIGHLIGHT_BG};
+use crate::components::notes_editor::NOTES_WINDOW_HEIGHT;
+use crate::components::browser::BrowserRow;
 use crate::components::episode::Episode;
+use crate::components::grid_browser::{TILE_HEIGHT, TILE_WIDTH};
 use crate::components::header::{Header, HeaderContext};
-use crate::dto::{EpisodeDetail, Series};
-use crate::episode_field::EpisodeField;
-use crate::menu::MenuItem;
+use crate::digest::Digest;
+use crate::dto::EpisodeDetail;
+use crate::duplicates::DuplicateGroup;
+use crate::keybindings;
+use crate::keymap::{self, KeySequence};
+use crate::menu::{self, MenuContext};
+use crate::notifications::{Notification, NotificationLevel, Notifications};
+use crate::path_completion::{self, PathStatus};
+use crate::query::{match_ranges, Query};
+use crate::relocate::RelocationCheck;
+use crate::rename::RenamePreview;
+use crate::text;
 use crate::terminal::{
-    get_terminal_size, hide_cursor, move_cursor, show_cursor,
+    get_terminal_size, write_graphics, GraphicsProtocol,
 };
-use crate::theme::Theme;
-use crate::util::{Entry, LastAction, Mode, ViewContext};
+use crate::theme::{string_to_color, BorderChars, Theme};
+use crate::tutorial::TutorialRegion;
+use crate::util::{CategoryCounts, Entry, EntryKey, Mode, ViewContext};
+use crate::version::EpisodeVersion;
 
 
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::convert::From;
 use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
 
-const FOOTER_SIZE: usize = 1; // Reserve 1 line for status line at bottom
-const COL1_WIDTH: usize = 45;
+const FOOTER_SIZE: usize = 2; // Reserve the key help line and the status line at the bottom
+pub const COL1_WIDTH: usize = 45; // List width when no split is set
+const MIN_COL1_WIDTH: usize = 20;
 const MIN_COL2_WIDTH: usize = 20;
-const DETAIL_HEIGHT: usize = 13; // Increased from 11 to accommodate progress tracking fields
+const LIST_SPLIT_STEP: u8 = 5; // Percent added or taken by Ctrl+Left/Right
+const LIST_SPLIT_RANGE: (u8, u8) = (10, 90);
+
+/// Percent of the terminal width taken by the list, None for COL1_WIDTH columns
+static LIST_SPLIT: RwLock<Option<u8>> = RwLock::new(None);
+
+/// Set the share of the terminal width taken by the list, None for COL1_WIDTH columns
+pub fn set_list_split(split: Option<u8>) {
+    *LIST_SPLIT.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = split;
+}
+
+/// The share of the terminal width taken by the list, as set by `set_list_split`
+pub fn list_split() -> Option<u8> {
+    *LIST_SPLIT.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Width of the list column in a terminal `cols` wide
+/// A split always leaves the detail column at least MIN_COL2_WIDTH wide
+pub fn list_width_for(cols: usize, split: Option<u8>) -> usize {
+    match split {
+        None => COL1_WIDTH,
+        Some(percent) => {
+            let max_width = cols.saturating_sub(MIN_COL2_WIDTH + 2).max(MIN_COL1_WIDTH);
+            (cols * percent as usize / 100).clamp(MIN_COL1_WIDTH, max_width)
+        }
+    }
+}
+
+/// Width of the list column in the current terminal
+pub fn list_width() -> io::Result<usize> {
+    let (cols, _) = get_terminal_size()?;
+    Ok(list_width_for(cols, list_split()))
+}
+
+/// Widen or narrow the list by one step, starting from its current share of the terminal
+pub fn adjust_list_split(wider: bool) -> io::Result<()> {
+    let (cols, _) = get_terminal_size()?;
+    let current = list_split().unwrap_or_else(|| ((COL1_WIDTH * 100 + cols / 2) / cols.max(1)).min(100) as u8);
+    let split = if wider {
+        current.saturating_add(LIST_SPLIT_STEP)
+    } else {
+        current.saturating_sub(LIST_SPLIT_STEP)
+    };
+    set_list_split(Some(split.clamp(LIST_SPLIT_RANGE.0, LIST_SPLIT_RANGE.1)));
+    Ok(())
+}
+const DETAIL_HEIGHT: usize = 15; // Progress tracking and play count fields plus the optional notes line
+
+/// Whether the files of listed episodes exist, looked for once until the library changes or a
+/// scan finishes, since each look can be slow on a network share
+static FILE_CHECKS: RwLock<Option<FileChecks>> = RwLock::new(None);
+
+struct FileChecks {
+    /// `Database::change_count` when the files were looked for
+    changes: i64,
+    exists: HashMap<PathBuf, bool>,
+}
+
+/// Forget which files exist, so the list looks for them again
+pub fn forget_file_checks() {
+    *FILE_CHECKS.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+/// Whether the file at `path` exists, looked for only when it isn't known since `changes`
+fn file_exists(path: &Path, changes: i64) -> bool {
+    if let Some(checks) = FILE_CHECKS.read().unwrap_or_else(|poisoned| poisoned.into_inner()).as_ref() {
+        if checks.changes == changes {
+            if let Some(exists) = checks.exists.get(path) {
+                return *exists;
+            }
+        }
+    }
+    let exists = path.exists();
+    let mut checks = FILE_CHECKS.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let checks = checks.get_or_insert_with(|| FileChecks { changes, exists: HashMap::new() });
+    if checks.changes != changes {
+        checks.changes = changes;
+        checks.exists.clear();
+    }
+    checks.exists.insert(path.to_path_buf(), exists);
+    exists
+}
 
 /// Convert Entry objects to Browser component data
+/// Names are highlighted where they match the text of the filter
 fn entries_to_browser_data(
     entries: &[Entry],
     edit_details: &EpisodeDetail,
+    database: &Database,
     resolver: &crate::path_resolver::PathResolver,
+    filter: &str,
+    counts: &HashMap<EntryKey, CategoryCounts>,
 ) -> (Vec<Category>, Vec<Episode>) {
     let mut categories = Vec::new();
     let mut episodes = Vec::new();
+    let filter_query = Query::parse(filter);
+    let filter_terms = filter_query.text_terms();
+    let offline_roots = resolver.offline_roots();
+    let changes = database.change_count().unwrap_or_default();
+
+    let counts_for = |key: EntryKey| {
+        counts
+            .get(&key)
+            .map_or((0, 0, 0), |category| (category.total, category.unwatched, category.new))
+    };
     
     for entry in entries {
         match entry {
             Entry::Series { name, series_id } => {
-                // Get episode counts from database
-                let (total, unwatched) = crate::database::get_series_episode_counts(*series_id)
-                    .unwrap_or_else(|e| {
-                        crate::logger::log_warn(&format!("Failed to get episode counts for series '{}' (id: {}): {}", name, series_id, e));
-                        (0, 0)
-                    });
+                let (total, unwatched, new) = counts_for(EntryKey::Series(*series_id));
                 let watched = total.saturating_sub(unwatched);
                 
                 // Create Category component with brackets around series name
+                let title = format!("[{}]", name);
+                let highlights = match_ranges(&title, &filter_terms);
                 let category = Category::new(
-                    format!("[{}]", name),
+                    title,
                     total,
                     watched,
                     CategoryType::Series,
-                );
+                )
+                .with_highlights(highlights)
+                .with_new_count(new);
                 categories.push(category);
             }
             Entry::Season { number, season_id } => {
-                // Get episode counts from database
-                let (total, unwatched) = crate::database::get_season_episode_counts(*season_id)
-                    .unwrap_or_else(|e| {
-                        crate::logger::log_warn(&format!("Failed to get episode counts for season {} (id: {}): {}", number, season_id, e));
-                        (0, 0)
-                    });
+                let (total, unwatched, new) = counts_for(EntryKey::Season(*season_id));
                 let watched = total.saturating_sub(unwatched);
                 
                 // Create Category component
+                let title = format!("Season {}", number);
+                let highlights = match_ranges(&title, &filter_terms);
                 let category = Category::new(
-                    format!("Season {}", number),
+                    title,
                     total,
                     watched,
                     CategoryType::Season,
-                );
+                )
+                .with_highlights(highlights)
+                .with_new_count(new);
                 categories.push(category);
             }
             Entry::Episode { episode_id, name, location, .. } => {
                 // Fetch episode details for this specific episode
-                let episode_detail = crate::database::get_episode_detail(*episode_id)
+                let episode_detail = database.cached_episode_detail(*episode_id)
                     .unwrap_or_else(|_| edit_details.clone());
                 
                 // Check individual conditions for combined state handling
-                let absolute_path = resolver.to_absolute(std::path::Path::new(location));
-                let file_exists = absolute_path.exists();
-                let filename = location.rsplit('/').next().unwrap_or("");
+                // Files on a folder that isn't mounted aren't looked for
+                let absolute_path = resolver.to_absolute(&crate::location::decode(location));
+                let is_offline = offline_roots.iter().any(|root| absolute_path.starts_with(root));
+                let file_exists = is_offline || file_exists(&absolute_path, changes);
+                let shown_location = crate::location::display(location);
+                let filename = shown_location.rsplit('/').next().unwrap_or("");
                 let is_new = episode_detail.title == filename;
                 let is_watched = episode_detail.watched == "true";
                 
@@ -86,7 +205,9 @@ fn entries_to_browser_data(
                     is_watched,
                     file_exists,
                     is_new,
-                );
+                )
+                .with_offline(is_offline)
+                .with_highlights(match_ranges(name, &filter_terms));
                 episodes.push(episode_component);
             }
         }
@@ -95,12 +216,62 @@ fn entries_to_browser_data(
     (categories, episodes)
 }
 
+/// Group headers of the Browse list: inside a series its seasons, specials (season 0) and
+/// episodes without a season are listed under their own header, other views have none
+pub fn group_headers(entries: &[Entry], view_context: &ViewContext) -> Vec<(usize, String)> {
+    if !matches!(view_context, ViewContext::Series { .. }) {
+        return Vec::new();
+    }
+    let mut headers: Vec<(usize, String)> = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let label = match entry {
+            Entry::Season { number: 0, .. } => "Specials",
+            Entry::Season { .. } => "Seasons",
+            Entry::Episode { .. } => "Unassigned episodes",
+            Entry::Series { .. } => continue,
+        };
+        if headers.last().is_none_or(|(_, last)| last != label) {
+            headers.push((index, label.to_string()));
+        }
+    }
+    headers
+}
+
 fn get_sidebar_width() -> io::Result<usize> {
     let (cols, _) = get_terminal_size()?;
-    let sidebar_width = cols.saturating_sub(COL1_WIDTH + 2);
+    let sidebar_width = cols.saturating_sub(list_width_for(cols, list_split()) + 2);
     Ok(sidebar_width.max(MIN_COL2_WIDTH))
 }
 
+/// The path typed in Entry mode at `row`, with whether it is a folder after it and the folders
+/// an ambiguous Tab matched below it
+fn draw_entry_path(
+    writer: &mut crate::buffer::BufferWriter,
+    entry_path: &str,
+    entry_completions: &[String],
+    row: usize,
+    theme: &Theme,
+) -> io::Result<()> {
+    let (terminal_width, _) = get_terminal_size()?;
+    let help_fg = string_to_color(&theme.help_fg).unwrap_or(crossterm::style::Color::Reset);
+    let warn_fg = string_to_color(&theme.status_warn_fg).unwrap_or(crossterm::style::Color::Reset);
+
+    writer.move_to(0, row);
+    writer.write_str(&format!("Path: {}", entry_path));
+    let status = path_completion::check_path(entry_path);
+    let ready = matches!(status, PathStatus::Empty | PathStatus::Ready);
+    writer.set_fg_color(if ready { help_fg } else { warn_fg });
+    writer.write_str(&format!("  ({})", status.message()));
+    if !entry_completions.is_empty() {
+        writer.move_to(0, row + 1);
+        writer.set_fg_color(help_fg);
+        let matches = entry_completions.join("  ");
+        writer.write_str(&text::truncate_to_width(&format!("Matches: {}", matches), terminal_width));
+    }
+    writer.set_fg_color(crossterm::style::Color::Reset);
+    Ok(())
+}
+
 fn draw_detail_panel_border_to_buffer(
     writer: &mut crate::buffer::BufferWriter,
     left: usize,
@@ -108,13 +279,11 @@ fn draw_detail_panel_border_to_buffer(
     width: usize,
     height: usize,
     thick: bool,
+    theme: &Theme,
 ) {
-    // Choose border characters based on the thickness
-    let (top_left, top_right, bottom_left, bottom_right, horizontal, vertical) = if thick {
-        ('╔', '╗', '╚', '╝', '═', '║')
-    } else {
-        ('┌', '┐', '└', '┘', '─', '│')
-    };
+    // Choose the theme's border characters based on the thickness
+    let border = if thick { theme.active_border_chars() } else { theme.border_chars() };
+    let BorderChars { top_left, top_right, bottom_left, bottom_right, horizontal, vertical } = border;
 
     // Draw top border
     writer.move_to(left, top);
@@ -178,44 +347,83 @@ fn draw_detail_panel_border_to_buffer(
 /// - Component rendering logic remains unchanged
 /// - Direct terminal writes replaced with BufferWriter calls
 /// - Cursor visibility still handled via direct terminal calls
+///
+/// Returns the row the entry list starts on, used to map mouse clicks to entries
 pub fn draw_screen(
-    entries: &[Entry],
-    current_item: usize,
-    first_entry: &mut usize,
-    filter: &str,
-    theme: &Theme,
-    mode: &Mode,
-    entry_path: &String,
-    edit_details: &EpisodeDetail,
-    edit_field: EpisodeField,
-    edit_cursor_pos: usize,
-    series: &[Series],
-    series_selection: &mut Option<usize>,
-    new_series: &str,
-    season_number: Option<usize>,
-    last_action: &Option<LastAction>,
-    dirty_fields: &HashSet<EpisodeField>,
-    menu_items: &[MenuItem],
-    menu_selection: usize,
-    filter_mode: bool,
-    first_series: &mut usize,
-    view_context: &ViewContext,
-    status_message: &str,
-    resolver: &crate::path_resolver::PathResolver,
+    state: &mut AppState,
+    context: &DrawContext,
     buffer_manager: &mut crate::buffer::BufferManager,
-) -> io::Result<()> {
+) -> io::Result<usize> {
+    // Menu items are only listed while the menu is open
+    let menu_items = if state.mode == Mode::Menu { state.menu_items() } else { Vec::new() };
+    // A running rescan takes over the status bar
+    let scan_status = state
+        .scan_progress
+        .map(|progress| Notification::new(NotificationLevel::Info, format!("{}  (Esc to cancel)", progress.status_line(state.scan_frame))));
+    // Then a running sync
+    let scan_status = scan_status.or_else(|| {
+        state.sync_status.as_ref().map(|line| Notification::new(NotificationLevel::Info, line.clone()))
+    });
+    // So does a macro recording, which is easy to forget about
+    let status = scan_status.or_else(|| {
+        state.macros.is_recording().then(|| {
+            let stop_key = keymap::keymap().keys_for("record_macro").first().map(KeySequence::label).unwrap_or_default();
+            Notification::new(NotificationLevel::Info, format!("Recording a macro  ({} to stop)", stop_key))
+        })
+    });
+    let pinned_filters = state.pinned_filters.label();
+    let tabs = state.tabs.label();
+    let total_entries = state.entries.len();
+    let DrawContext { theme, database, resolver, scroll_margin, accessible } = *context;
+    let AppState {
+        filtered_entries: ref entries,
+        current_item,
+        ref mut first_entry,
+        search: ref filter,
+        ref mode,
+        ref entry_path,
+        ref entry_completions,
+        ref edit_details,
+        edit_field,
+        edit_cursor_pos,
+        ref series,
+        ref mut series_selection,
+        ref new_series,
+        season_number,
+        ref last_action,
+        ref dirty_fields,
+        menu_selection,
+        filter_mode,
+        ref mut first_series,
+        ref view_context,
+        ref notifications,
+        sort_mode,
+        ref marked_episodes,
+        ref alt_titles_draft,
+        ref pending_confirmation,
+        ref theme_picker,
+        ref tutorial,
+        grid_view,
+        ref original_edit_details,
+        ref command_line,
+        count,
+        ..
+    } = *state;
+    let notification = status.as_ref().or(notifications.current());
+    let toast = notifications.current_toast();
+    let confirm_dialog = pending_confirmation.as_ref().map(|pending| &pending.dialog);
+
     // Clear desired buffer to start with empty slate
     buffer_manager.clear_desired_buffer();
     
     // Get writer for this frame
     let mut writer = buffer_manager.get_writer();
 
-    hide_cursor()?;
-
     //browse_series is true if the mode is browse and the current item in entries is a series
-    let series_selected = matches!(mode, Mode::Browse)
+    // The theme picker previews the Browse screen, so it is laid out the same way
+    let series_selected = matches!(mode, Mode::Browse | Mode::ThemeSelect)
         && matches!(entries.get(current_item), Some(Entry::Series { .. }));
-    let season_selected = matches!(mode, Mode::Browse)
+    let season_selected = matches!(mode, Mode::Browse | Mode::ThemeSelect)
         && matches!(entries.get(current_item), Some(Entry::Season { .. }));
 
     //series_filter is true if the mode is browse and the current item in entries is an episode and the series field is not empty
@@ -232,8 +440,36 @@ pub fn draw_screen(
     // Determine if we're in first-run state (Entry mode with no entries)
     let is_first_run = matches!(mode, Mode::Entry) && entries.is_empty();
 
-    // Get terminal width for header
+    // Cursor position inside the edit field or text window being drawn, if any
+    let mut input_cursor: Option<(usize, usize)> = None;
+    // Screen position of the selected entry, where the cursor is left in accessible mode
+    let mut selection_cell: Option<(usize, usize)> = None;
+
+    // Get terminal width for header, and the width of the list column in it
     let (terminal_width, _) = get_terminal_size()?;
+    let col1_width = list_width_for(terminal_width, list_split());
+
+    // The counts of every series and season, for the list rows and the header; they are only
+    // counted again after the library changes
+    let needs_counts = !matches!(view_context, ViewContext::TopLevel) || !entries.is_empty();
+    let category_counts = if needs_counts {
+        database.cached_category_counts().unwrap_or_else(|e| {
+            crate::logger::log_warn(&format!("Failed to get episode counts: {}", e));
+            Default::default()
+        })
+    } else {
+        Default::default()
+    };
+    let view_key = match view_context {
+        ViewContext::TopLevel | ViewContext::Extras => None,
+        ViewContext::Series { series_id, .. } => Some(EntryKey::Series(*series_id)),
+        ViewContext::Season { season_id, .. } => Some(EntryKey::Season(*season_id)),
+    };
+    let view_counts = view_key
+        .and_then(|key| category_counts.get(&key))
+        .map(|counts| (counts.total, counts.unwatched));
+    let filter_active = !filter.trim().is_empty() || !pinned_filters.is_empty();
+    let entry_counts = filter_active.then_some((entries.len(), total_entries));
 
     // Create HeaderContext with all required data
     let header_context = HeaderContext::new(
@@ -248,6 +484,12 @@ pub fn draw_screen(
         view_context.clone(),
         filter.to_owned(),
         filter_mode, // filter_focused is same as filter_mode for now
+        pinned_filters.to_owned(),
+        tabs.to_owned(),
+        count.map(|count| count.label()).unwrap_or_default(),
+        sort_mode,
+        entry_counts,
+        view_counts,
     );
 
     // Create and render Header component
@@ -272,14 +514,12 @@ pub fn draw_screen(
             writer.write_str("  • If videos.sqlite exists in that directory, it will be used (preserving your data)");
             writer.move_to(0, header_height + 6);
             writer.write_str("  • If not, a new database will be created and your videos will be scanned");
-            writer.move_to(0, header_height + 8);
-            writer.write_str(&format!("Path: {}", entry_path));
+            draw_entry_path(&mut writer, entry_path, entry_completions, header_height + 8, theme)?;
         } else {
-            // Rescan scenario - show simpler prompt
+            // Scan Folder from the menu - show simpler prompt
             writer.move_to(0, header_height + 1);
-            writer.write_str("Enter the path to a directory to scan for video files.");
-            writer.move_to(0, header_height + 3);
-            writer.write_str(&format!("Path: {}", entry_path));
+            writer.write_str("Enter a folder of the library to scan for new videos. Only that folder is walked.");
+            draw_entry_path(&mut writer, entry_path, entry_completions, header_height + 3, theme)?;
         }
     } else if !entries.is_empty() {
         let max_lines = get_max_displayed_items_with_header_height(header_height)?;
@@ -291,29 +531,72 @@ pub fn draw_screen(
             *first_entry = current_item - max_lines as usize + 1;
         }
 
-        // Convert entries to Browser component data
-        let (categories, episodes) = entries_to_browser_data(entries, edit_details, resolver);
-        
-        // Create Browser component
-        let mut browser = Browser::new(
+        // Work out the scroll position from the number of entries alone, so only the entries
+        // on screen are turned into components below
+        let mut browser = Browser::windowed(
             (0, header_height),  // top_left position
-            COL1_WIDTH,        // width
-            categories,
-            episodes,
+            col1_width,        // width
+            entries.len(),
         );
-        
+
         // Set the current selection and first visible item
         browser.set_selected_item(current_item);
         browser.first_visible_item = *first_entry;
-        
-        // Ensure selection is visible and bounds are correct
-        browser.ensure_selection_visible(max_lines);
-        
+        browser.scroll_margin = scroll_margin;
+        if !grid_view {
+            browser.group_headers = group_headers(entries, view_context);
+        }
+        let page = if grid_view {
+            // Tiles scroll a whole row at a time
+            let mut tiles = GridBrowser::new(browser);
+            tiles.ensure_selection_visible(max_lines);
+            browser = tiles.browser;
+            GridBrowser::page_size(col1_width, max_lines)
+        } else {
+            // Ensure selection is visible and bounds are correct
+            browser.ensure_selection_visible(max_lines);
+            max_lines
+        };
+
         // Update first_entry to match browser's scroll position
         *first_entry = browser.first_visible_item;
-        
-        // Render the browser component
-        let browser_cells = browser.render(COL1_WIDTH, max_lines, theme, true);
+        let window = *first_entry..(*first_entry + page).min(entries.len());
+
+        // Convert the entries on screen to Browser component data
+        let (categories, episodes) =
+            entries_to_browser_data(&entries[window.clone()], edit_details, database, resolver, filter, &category_counts);
+        browser.set_window(window.start, categories, episodes);
+
+        // Mark the episodes picked for batch actions; the list is only gone through while some are
+        if !marked_episodes.is_empty() {
+            browser.marked_items = entries
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| matches!(entry, Entry::Episode { episode_id, .. } if marked_episodes.contains(episode_id)))
+                .map(|(index, _)| index)
+                .collect();
+        }
+
+        let browser_cells = if grid_view {
+            // Lay the same entries out as tiles
+            let mut grid = GridBrowser::new(browser);
+            // Only the tiles on screen are checked for artwork
+            grid.artwork_items = window
+                .filter(|index| artwork::find_artwork(&entries[*index], database, resolver).is_some())
+                .collect();
+            let offset = current_item.saturating_sub(*first_entry);
+            let columns = GridBrowser::columns_for(col1_width);
+            selection_cell = Some((offset % columns * TILE_WIDTH, header_height + offset / columns * TILE_HEIGHT));
+            grid.render(col1_width, max_lines, theme, true)
+        } else {
+            selection_cell = Browser::layout_rows(&browser.group_headers, *first_entry, browser.total_items(), max_lines)
+                .iter()
+                .position(|row| *row == BrowserRow::Item(current_item))
+                .map(|row| (0, header_height + row));
+
+            // Render the browser component
+            browser.render(col1_width, max_lines, theme, true)
+        };
         
         // Write browser cells to buffer
         write_cells_to_buffer(&mut writer, &browser_cells, 0, header_height);
@@ -325,16 +608,11 @@ pub fn draw_screen(
             };
             
             // Calculate detail panel position and dimensions
-            let start_col: usize = COL1_WIDTH + 2;
+            let start_col: usize = col1_width + 2;
             let start_row = header_height;
             let sidebar_width = get_sidebar_width()?;
             let edit_mode = matches!(mode, Mode::Edit);
             
-            // Show or hide the cursor based on edit_mode
-            if edit_mode {
-                show_cursor()?;
-            }
-            
             // Draw the window border for detail panel
             draw_detail_panel_border_to_buffer(
                 &mut writer,
@@ -343,6 +621,7 @@ pub fn draw_screen(
                 sidebar_width,
                 DETAIL_HEIGHT,
                 edit_mode,
+                theme,
             );
             
             // Create and render DetailPanel component
@@ -354,7 +633,8 @@ pub fn draw_screen(
                 season_number,
                 dirty_fields.clone(),
                 entry_location,
-            );
+            )
+            .with_original(original_edit_details.clone());
             
             // Calculate content area (inside the border)
             let content_width = sidebar_width.saturating_sub(2); // Subtract left and right borders
@@ -366,13 +646,18 @@ pub fn draw_screen(
             // Write detail panel cells to buffer (inside the border)
             write_cells_to_buffer(&mut writer, &detail_cells, start_col + 1, start_row + 1);
             
-            // Position cursor for Edit mode
-            if edit_mode && edit_field.is_editable() {
-                let edit_cursor_min = edit_field.display_name().len() + 2;
-                move_cursor(
-                    start_col + 1 + edit_cursor_min + edit_cursor_pos,
+            // Cursor goes in the field being edited, after the field name when it has one
+            if edit_mode {
+                let edit_cursor_min = if edit_field.is_editable() {
+                    edit_field.display_name().len() + 2
+                } else {
+                    0
+                };
+                let edit_column = text::column_at(&edit_field.get_field_value(edit_details), edit_cursor_pos);
+                input_cursor = Some((
+                    start_col + 1 + edit_cursor_min + edit_column,
                     start_row + 1 + usize::from(edit_field),
-                )?;
+                ));
             }
         }
         if let Mode::SeriesSelect | Mode::SeriesCreate = mode {
@@ -415,14 +700,50 @@ pub fn draw_screen(
             // Write series window cells to buffer
             write_cells_to_buffer(&mut writer, &series_cells, window_x, window_y);
             
-            // Handle cursor positioning for SeriesCreate mode
+            // Cursor goes in the text input field (row 2, after the prompt)
             if let Mode::SeriesCreate = mode {
-                use crate::terminal::{show_cursor, move_cursor};
-                show_cursor()?;
-                // Position cursor in the text input field (row 2, after the prompt)
-                move_cursor(window_x + 1 + edit_cursor_pos, window_y + 2)?;
+                input_cursor = Some((window_x + 1 + text::column_at(new_series, edit_cursor_pos), window_y + 2));
             }
         }
+        if let Mode::NotesEdit = mode {
+            // Notes window spans most of the sidebar, below the detail panel
+            let window_width = get_sidebar_width()?.saturating_sub(4).max(20);
+            let (window_x, window_y) = SeriesSelectWindow::calculate_horizontal_position(
+                window_width,
+                header_height,
+            )?;
+            
+            let notes_editor = NotesEditor::new(
+                edit_details.notes.clone().unwrap_or_default(),
+                edit_cursor_pos,
+                window_width,
+            );
+            let notes_cells = notes_editor.render(window_width, NOTES_WINDOW_HEIGHT, theme, false);
+            write_cells_to_buffer(&mut writer, &notes_cells, window_x, window_y);
+            
+            // Cursor goes in the input row once the frame has been written
+            input_cursor = Some((window_x + notes_editor.cursor_column(), window_y + 2));
+        }
+        if let (Mode::AltTitlesEdit, Some(draft)) = (mode, alt_titles_draft) {
+            // Same placement as the notes window, growing with the number of titles
+            let window_width = get_sidebar_width()?.saturating_sub(4).max(20);
+            let (window_x, window_y) = SeriesSelectWindow::calculate_horizontal_position(
+                window_width,
+                header_height,
+            )?;
+
+            let editor = AltTitlesEditor::new(draft, edit_cursor_pos, window_width);
+            let editor_cells = editor.render(window_width, editor.height(), theme, false);
+            write_cells_to_buffer(&mut writer, &editor_cells, window_x, window_y);
+
+            input_cursor = Some((window_x + editor.cursor_column(), window_y + editor.cursor_row()));
+        }
+    } else {
+        // Nothing to list: say why and how to get back to something that has entries
+        let max_lines = get_max_displayed_items_with_header_height(header_height)?;
+        let empty_state = EmptyState::for_view(view_context, filter, !pinned_filters.is_empty());
+        let empty_cells = empty_state.render(col1_width, max_lines, theme, false);
+        write_cells_to_buffer(&mut writer, &empty_cells, 0, header_height);
     }
 
     // Draw context menu if in Menu mode
@@ -447,12 +768,102 @@ pub fn draw_screen(
         write_cells_to_buffer(&mut writer, &menu_cells, start_col, start_row);
     }
 
-    // Draw status line at the bottom using StatusBar component
+    // Draw the confirmation dialog in the middle of the screen
+    if let (Mode::Confirm, Some(dialog)) = (mode, confirm_dialog) {
+        let (terminal_width, terminal_height) = get_terminal_size()?;
+        let window_width = terminal_width.min(CONFIRM_WINDOW_WIDTH);
+        let window_height = dialog.height(window_width);
+        let dialog_cells = dialog.render(window_width, window_height, theme, false);
+        write_cells_to_buffer(
+            &mut writer,
+            &dialog_cells,
+            terminal_width.saturating_sub(window_width) / 2,
+            terminal_height.saturating_sub(window_height) / 2,
+        );
+    }
+
+    // Draw the theme picker at the top-right, over a screen drawn with the highlighted theme
+    if let (Mode::ThemeSelect, Some(picker)) = (mode, theme_picker) {
+        let (terminal_width, terminal_height) = get_terminal_size()?;
+        let picker_cells = picker.render(terminal_width, terminal_height.saturating_sub(1), theme, false);
+        let picker_width = picker_cells.first().map(|row| row.len()).unwrap_or(0);
+        write_cells_to_buffer(&mut writer, &picker_cells, terminal_width.saturating_sub(picker_width), 0);
+    }
+
+    // Draw the tutorial window and highlight the region its step points at
+    if let (Some(tutorial), Mode::Browse) = (tutorial, mode) {
+        if !filter_mode {
+            let (terminal_width, terminal_height) = get_terminal_size()?;
+            let region = match tutorial.step().region() {
+                Some(TutorialRegion::Header) => Some((0, 0, terminal_width, header_height)),
+                Some(TutorialRegion::Browser) if !entries.is_empty() => {
+                    let max_lines = get_max_displayed_items_with_header_height(header_height)?;
+                    Some((0, header_height, col1_width, max_lines))
+                }
+                Some(TutorialRegion::DetailPanel) if !series_selected && !season_selected && !entries.is_empty() => {
+                    Some((col1_width + 2, header_height, get_sidebar_width()?, DETAIL_HEIGHT))
+                }
+                _ => None,
+            };
+            if let Some((x, y, width, height)) = region {
+                writer.set_region_bg(x, y, width, height, TUTORIAL_HIGHLIGHT_BG);
+            }
+
+            // Bottom-right corner, clear of the list and the top of the detail panel
+            let window_width = get_sidebar_width()?.min(50);
+            let overlay = TutorialOverlay::new(tutorial, window_width);
+            let window_height = overlay.height();
+            let overlay_cells = overlay.render(window_width, window_height, theme, false);
+            write_cells_to_buffer(
+                &mut writer,
+                &overlay_cells,
+                terminal_width.saturating_sub(window_width),
+                terminal_height.saturating_sub(FOOTER_SIZE + window_height),
+            );
+        }
+    }
+
+    // A toast sits in the bottom-right corner, above the key help
     let (terminal_width, terminal_height) = get_terminal_size()?;
+    if let Some(toast) = toast {
+        let toast = Toast::new(toast.message.clone());
+        let window_width = toast.width(terminal_width);
+        let toast_cells = toast.render(terminal_width, TOAST_HEIGHT, theme, false);
+        write_cells_to_buffer(
+            &mut writer,
+            &toast_cells,
+            terminal_width.saturating_sub(window_width),
+            terminal_height.saturating_sub(FOOTER_SIZE + TOAST_HEIGHT),
+        );
+    }
+
+    // Draw status line at the bottom using StatusBar component
     let status_row = terminal_height - 1; // Last row (0-indexed)
+
+    // Key help for the mode and selection goes just above it
+    let browse_menu_items = if matches!(mode, Mode::Browse) && !filter_mode {
+        menu::get_context_menu_items(&MenuContext {
+            selected_entry: selected_entry.cloned(),
+            episode_detail: edit_details.clone(),
+            mode: mode.clone(),
+            last_action: last_action.clone(),
+            marked_count: marked_episodes.len(),
+            extras_view: *view_context == ViewContext::Extras,
+        })
+    } else {
+        Vec::new()
+    };
+    let help_bar = HelpBar::new(keybindings::footer_bindings(mode, filter_mode, selected_entry, &browse_menu_items));
+    let help_cells = help_bar.render(terminal_width, 1, theme, false);
+    write_cells_to_buffer(&mut writer, &help_cells, 0, status_row.saturating_sub(1));
     
     // Create and render StatusBar component
-    let status_bar = StatusBar::new(status_message.to_string());
+    let status_bar = match notification {
+        // A command being typed after `:` takes the status line, as in vim
+        _ if command_line.is_some() => StatusBar::new(format!(":{}", command_line.as_deref().unwrap_or_default())),
+        Some(notification) => StatusBar::new(notification.message.clone()).with_level(notification.level),
+        None => StatusBar::new(String::new()),
+    };
     let status_cells = status_bar.render(terminal_width, 1, theme, false);
     
     // Write status bar to buffer
@@ -460,34 +871,24 @@ pub fn draw_screen(
     
     // Drop the writer to release the mutable borrow
     drop(writer);
-    
+
+    // The cursor is shown in the line being typed, and hidden otherwise
+    let cursor = if let (Some(line), Mode::Browse) = (command_line, mode) {
+        Some((1 + text::column_at(line, line.chars().count()), status_row))
+    } else if filter_mode && matches!(mode, Mode::Browse) {
+        Some((8 + text::column_at(filter, edit_cursor_pos), 2)) // "filter: " is 8 chars, row 2 is filter line
+    } else if input_cursor.is_some() {
+        input_cursor
+    } else {
+        // Screen readers follow the cursor, so it is left on the selected entry
+        selection_cell.filter(|_| accessible && matches!(mode, Mode::Browse))
+    };
+    buffer_manager.set_cursor(cursor);
+
     // Compare buffers and write differences to terminal
     buffer_manager.render_to_terminal()?;
 
-    // Position cursor when in filter mode or edit mode
-    // This must be done AFTER all other drawing to ensure cursor is in the right place
-    if filter_mode && matches!(mode, Mode::Browse) {
-        show_cursor()?;
-        move_cursor(8 + edit_cursor_pos, 2)?; // "filter: " is 8 chars, row 2 is filter line
-    } else if matches!(mode, Mode::Edit) && !entries.is_empty() {
-        // In Edit mode, reposition the cursor to the edit field
-        // The cursor was already shown and positioned in the DetailPanel rendering,
-        // but we need to ensure it stays visible after drawing the status line
-        show_cursor()?;
-        let start_col: usize = COL1_WIDTH + 2;
-        let start_row = header_height;
-        let edit_cursor_min = if edit_field.is_editable() {
-            edit_field.display_name().len() + 2
-        } else {
-            0
-        };
-        move_cursor(
-            start_col + 1 + edit_cursor_min + edit_cursor_pos,
-            start_row + 1 + usize::from(edit_field),
-        )?;
-    }
-
-    Ok(())
+    Ok(header_height)
 }
 
 pub fn get_max_displayed_items_with_header_height(header_height: usize) -> io::Result<usize> {
@@ -496,6 +897,75 @@ pub fn get_max_displayed_items_with_header_height(header_height: usize) -> io::R
     Ok(max_lines)
 }
 
+/// Area of the artwork pane (column, row, width, height): the sidebar below the detail panel
+/// None when the terminal is too small to fit a useful image
+fn artwork_area(header_height: usize) -> io::Result<Option<(usize, usize, usize, usize)>> {
+    let (cols, rows) = get_terminal_size()?;
+    let col = list_width_for(cols, list_split()) + 2;
+    let row = header_height + DETAIL_HEIGHT + 1;
+    let width = cols.saturating_sub(col + 1);
+    let height = rows.saturating_sub(row + FOOTER_SIZE + 1);
+    Ok((width >= 8 && height >= 4).then_some((col, row, width, height)))
+}
+
+/// Show `poster` in the artwork pane, replacing or removing the image shown before
+/// Call after the frame is rendered; images are drawn on top of the buffer's cells
+pub fn draw_artwork(
+    protocol: GraphicsProtocol,
+    poster: Option<&Path>,
+    header_height: usize,
+    state: &mut ArtworkState,
+    buffer_manager: &mut crate::buffer::BufferManager,
+) -> io::Result<()> {
+    let area = artwork_area(header_height)?;
+    let wanted = poster.zip(area).map(|(path, area)| (path.to_path_buf(), area));
+
+    if wanted != state.shown {
+        // Remove the previous image; sixel pixels are only cleared by redrawing the cells under them
+        if state.shown.is_some() {
+            match protocol {
+                GraphicsProtocol::Kitty => write_graphics(0, 0, &artwork::kitty_delete_sequence())?,
+                GraphicsProtocol::Sixel => {
+                    buffer_manager.force_full_redraw();
+                    buffer_manager.render_to_terminal()?;
+                }
+            }
+        }
+        state.sequence.clear();
+
+        if let Some((path, (col, row, width, height))) = &wanted {
+            let png = std::fs::read(path).ok();
+            let size = png.as_deref().and_then(artwork::png_dimensions);
+            match (png, size) {
+                (Some(png), Some((image_width, image_height))) => {
+                    let (cols, rows) = artwork::fit_cells(image_width, image_height, *width, *height);
+                    state.position = (col + (width - cols) / 2, *row);
+                    match protocol {
+                        GraphicsProtocol::Kitty => {
+                            // Kitty keeps the image until it is deleted, so it is sent once
+                            write_graphics(state.position.0, state.position.1, &artwork::kitty_sequence(&png, cols, rows))?;
+                        }
+                        GraphicsProtocol::Sixel => {
+                            state.sequence = artwork::sixel_sequence(&png, cols, rows).unwrap_or_else(|| {
+                                crate::logger::log_warn(&format!("Could not decode artwork {}", path.display()));
+                                String::new()
+                            });
+                        }
+                    }
+                }
+                _ => crate::logger::log_warn(&format!("Artwork {} is not a readable PNG file", path.display())),
+            }
+        }
+        state.shown = wanted;
+    }
+
+    // Sixel images are part of the screen contents, so they are drawn again with every frame
+    if !state.sequence.is_empty() {
+        write_graphics(state.position.0, state.position.1, &state.sequence)?;
+    }
+    Ok(())
+}
+
 /// Render the torrent search input screen
 pub fn draw_torrent_search_input(
     buffer_manager: &mut crate::buffer::BufferManager,
@@ -508,8 +978,6 @@ pub fn draw_torrent_search_input(
     // Get writer for this frame
     let mut writer = buffer_manager.get_writer();
     
-    hide_cursor()?;
-    
     let (terminal_width, _) = get_terminal_size()?;
     
     // Parse theme colors
@@ -548,13 +1016,166 @@ pub fn draw_torrent_search_input(
     // Drop the writer to release the mutable borrow
     drop(writer);
     
+    // Show cursor at the end of the query
+    buffer_manager.set_cursor(Some((7 + search_query.len(), 2))); // "Query: " is 7 chars, row 2
+
     // Compare buffers and write differences to terminal
     buffer_manager.render_to_terminal()?;
     
-    // Show cursor at the end of the query
-    show_cursor()?;
-    move_cursor(7 + search_query.len(), 2)?; // "Query: " is 7 chars, row 2
-    
+    Ok(())
+}
+
+/// Render the screen where the library is pointed at the folder it moved to, with the result
+/// of checking the folder once Enter has been pressed
+pub fn draw_relocate(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    relocate_path: &str,
+    check: Option<&RelocationCheck>,
+    theme: &Theme,
+) -> io::Result<()> {
+    buffer_manager.clear_desired_buffer();
+    let mut writer = buffer_manager.get_writer();
+
+    let (terminal_width, terminal_height) = get_terminal_size()?;
+    let header_fg = string_to_color(&theme.header_fg).unwrap_or(crossterm::style::Color::Reset);
+    let help_fg = string_to_color(&theme.help_fg).unwrap_or(crossterm::style::Color::Reset);
+    let warn_fg = string_to_color(&theme.status_warn_fg).unwrap_or(crossterm::style::Color::Reset);
+
+    writer.move_to(0, 0);
+    writer.set_fg_color(header_fg);
+    writer.set_bg_color(crossterm::style::Color::Reset);
+    writer.set_bold(true);
+    writer.write_str("Relocate Library");
+    writer.set_bold(false);
+
+    writer.move_to(0, 2);
+    writer.set_fg_color(crossterm::style::Color::Reset);
+    writer.write_str("New library folder: ");
+    writer.write_str(relocate_path);
+
+    let mut row = 4;
+    let mut line = |writer: &mut crate::buffer::BufferWriter, text: &str, color: crossterm::style::Color| {
+        if row + 1 < terminal_height {
+            writer.move_to(0, row);
+            writer.set_fg_color(color);
+            writer.write_str(&text::truncate_to_width(text, terminal_width));
+            row += 1;
+        }
+    };
+    match check {
+        None => line(&mut writer, "Enter: look for the library's videos in this folder | ESC: Cancel", help_fg),
+        Some(check) => {
+            let color = if check.looks_right() { crossterm::style::Color::Reset } else { warn_fg };
+            line(&mut writer, &check.summary(), color);
+            for location in &check.missing {
+                line(&mut writer, &format!("  not found: {}", location), warn_fg);
+            }
+            line(&mut writer, "", help_fg);
+            if check.looks_right() {
+                let database = if check.has_database {
+                    "The database already in that folder will be used"
+                } else {
+                    "The database will be copied into that folder; the old one is left in place"
+                };
+                line(&mut writer, database, help_fg);
+                line(&mut writer, "Enter: move the library | ESC: Cancel", help_fg);
+            } else {
+                line(&mut writer, "Too few videos were found there. Type another folder | ESC: Cancel", help_fg);
+            }
+        }
+    }
+
+    let status_bar = StatusBar::new("Type the folder the library was moved to".to_string());
+    let status_cells = status_bar.render(terminal_width, 1, theme, false);
+    write_cells_to_buffer(&mut writer, &status_cells, 0, terminal_height - 1);
+    buffer_manager.set_cursor(Some((20 + relocate_path.chars().count(), 2))); // "New library folder: " is 20 chars, row 2
+    buffer_manager.render_to_terminal()?;
+    Ok(())
+}
+
+/// Render the screen where the URL of a web video to download is typed
+pub fn draw_add_from_url(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    download_url: &str,
+    download_folder: &Path,
+    theme: &Theme,
+) -> io::Result<()> {
+    buffer_manager.clear_desired_buffer();
+    let mut writer = buffer_manager.get_writer();
+
+    let (terminal_width, terminal_height) = get_terminal_size()?;
+    let header_fg = string_to_color(&theme.header_fg).unwrap_or(crossterm::style::Color::Reset);
+    let help_fg = string_to_color(&theme.help_fg).unwrap_or(crossterm::style::Color::Reset);
+
+    writer.move_to(0, 0);
+    writer.set_fg_color(header_fg);
+    writer.set_bg_color(crossterm::style::Color::Reset);
+    writer.set_bold(true);
+    writer.write_str("Add from URL");
+    writer.set_bold(false);
+
+    writer.move_to(0, 2);
+    writer.set_fg_color(crossterm::style::Color::Reset);
+    writer.write_str("URL: ");
+    writer.write_str(download_url);
+
+    writer.move_to(0, 4);
+    writer.set_fg_color(help_fg);
+    let folder = format!("yt-dlp saves the video in {}, then it is added to the library", download_folder.display());
+    writer.write_str(&text::truncate_to_width(&folder, terminal_width));
+    writer.move_to(0, 5);
+    writer.write_str("Enter: Download | ESC: Cancel");
+
+    let status_bar = StatusBar::new("Type or paste the address of the video's page".to_string());
+    let status_cells = status_bar.render(terminal_width, 1, theme, false);
+    write_cells_to_buffer(&mut writer, &status_cells, 0, terminal_height - 1);
+    buffer_manager.set_cursor(Some((5 + download_url.chars().count(), 2))); // "URL: " is 5 chars, row 2
+    buffer_manager.render_to_terminal()?;
+    Ok(())
+}
+
+/// Render the preview of renaming an episode's file, with its current and new paths
+pub fn draw_rename_preview(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    preview: &RenamePreview,
+    theme: &Theme,
+) -> io::Result<()> {
+    buffer_manager.clear_desired_buffer();
+    let mut writer = buffer_manager.get_writer();
+
+    let (terminal_width, terminal_height) = get_terminal_size()?;
+    let header_fg = string_to_color(&theme.header_fg).unwrap_or(crossterm::style::Color::Reset);
+    let help_fg = string_to_color(&theme.help_fg).unwrap_or(crossterm::style::Color::Reset);
+    let warn_fg = string_to_color(&theme.status_warn_fg).unwrap_or(crossterm::style::Color::Reset);
+
+    writer.move_to(0, 0);
+    writer.set_fg_color(header_fg);
+    writer.set_bg_color(crossterm::style::Color::Reset);
+    writer.set_bold(true);
+    writer.write_str("Rename File");
+    writer.set_bold(false);
+
+    let mut row = 2;
+    let mut line = |writer: &mut crate::buffer::BufferWriter, text: &str, color: crossterm::style::Color| {
+        if row + 1 < terminal_height {
+            writer.move_to(0, row);
+            writer.set_fg_color(color);
+            writer.write_str(&text::truncate_to_width(text, terminal_width));
+            row += 1;
+        }
+    };
+    line(&mut writer, &format!("From: {}", preview.from.display()), crossterm::style::Color::Reset);
+    line(&mut writer, &format!("To:   {}", preview.to.display()), crossterm::style::Color::Reset);
+    line(&mut writer, "", help_fg);
+    if preview.to.exists() {
+        line(&mut writer, "There is already a file with that name; it won't be replaced", warn_fg);
+    }
+    line(&mut writer, "Enter: rename the file | ESC: Cancel", help_fg);
+
+    let status_bar = StatusBar::new("Unsaved changes in the editor are used for the name".to_string());
+    let status_cells = status_bar.render(terminal_width, 1, theme, false);
+    write_cells_to_buffer(&mut writer, &status_cells, 0, terminal_height - 1);
+    buffer_manager.render_to_terminal()?;
     Ok(())
 }
 
@@ -571,8 +1192,6 @@ pub fn draw_torrent_search_results(
     // Get writer for this frame
     let mut writer = buffer_manager.get_writer();
     
-    hide_cursor()?;
-    
     let (terminal_width, terminal_height) = get_terminal_size()?;
     
     // Parse theme colors
@@ -674,28 +1293,498 @@ pub fn draw_torrent_search_results(
     Ok(())
 }
 
-/// Convert a color string to a Color enum
-fn string_to_color(color: &str) -> Option<crossterm::style::Color> {
-    match color.to_lowercase().as_str() {
-        "black" => Some(crossterm::style::Color::Black),
-        "red" => Some(crossterm::style::Color::Red),
-        "green" => Some(crossterm::style::Color::Green),
-        "yellow" => Some(crossterm::style::Color::Yellow),
-        "blue" => Some(crossterm::style::Color::Blue),
-        "magenta" => Some(crossterm::style::Color::Magenta),
-        "cyan" => Some(crossterm::style::Color::Cyan),
-        "white" => Some(crossterm::style::Color::White),
-        "darkgray" | "dark_gray" => Some(crossterm::style::Color::DarkGrey),
-        "reset" => Some(crossterm::style::Color::Reset),
-        _ => None,
-    }
-}
-
-/// Write component cells to buffer at specified position.
-///
-/// This is a key integration point between the component system and the buffer layer.
-/// Components render to their own Cell arrays, which are then written to the buffer
-/// at the appropriate screen position.
+/// Draw the weekly digest with its sections and the selected item highlighted
+pub fn draw_digest(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    digest: &Digest,
+    selected_index: usize,
+    theme: &Theme,
+) -> io::Result<()> {
+    // Clear desired buffer to start with empty slate
+    buffer_manager.clear_desired_buffer();
+    let mut writer = buffer_manager.get_writer();
+
+    let (terminal_width, terminal_height) = get_terminal_size()?;
+
+    // Parse theme colors
+    let header_fg = string_to_color(&theme.header_fg).unwrap_or(crossterm::style::Color::Reset);
+    let help_fg = string_to_color(&theme.help_fg).unwrap_or(crossterm::style::Color::Reset);
+    let selected_fg = string_to_color(&theme.current_fg).unwrap_or(crossterm::style::Color::Black);
+    let selected_bg = string_to_color(&theme.current_bg).unwrap_or(crossterm::style::Color::White);
+    let normal_fg = string_to_color(&theme.episode_fg).unwrap_or(crossterm::style::Color::Reset);
+    let normal_bg = string_to_color(&theme.episode_bg).unwrap_or(crossterm::style::Color::Reset);
+
+    writer.move_to(0, 0);
+    writer.set_fg_color(header_fg);
+    writer.set_bg_color(crossterm::style::Color::Reset);
+    writer.set_bold(true);
+    writer.write_str("Your week in movies");
+    writer.set_bold(false);
+
+    writer.move_to(0, 2);
+    writer.set_fg_color(normal_fg);
+    writer.write_str(&format!(
+        "Watched last week: {}",
+        crate::video_metadata::format_duration_hms(digest.watch_seconds_last_week)
+    ));
+
+    // Sections, numbering items across sections to match the selection
+    let mut row = 4;
+    let mut item_index = 0;
+    for (title, items) in digest.sections() {
+        if row >= terminal_height.saturating_sub(2) {
+            break;
+        }
+        writer.move_to(0, row);
+        writer.set_fg_color(header_fg);
+        writer.set_bg_color(crossterm::style::Color::Reset);
+        writer.set_bold(true);
+        writer.write_str(title);
+        writer.set_bold(false);
+        row += 1;
+
+        if items.is_empty() {
+            writer.move_to(2, row);
+            writer.set_fg_color(help_fg);
+            writer.write_str("nothing here");
+            row += 1;
+        }
+        for item in items {
+            writer.move_to(2, row);
+            if item_index == selected_index {
+                writer.set_fg_color(selected_fg);
+                writer.set_bg_color(selected_bg);
+            } else {
+                writer.set_fg_color(normal_fg);
+                writer.set_bg_color(normal_bg);
+            }
+            let label: String = item.label.chars().take(terminal_width.saturating_sub(2)).collect();
+            writer.write_str(&label);
+            writer.set_bg_color(crossterm::style::Color::Reset);
+            item_index += 1;
+            row += 1;
+        }
+        row += 1;
+    }
+
+    // Draw status line at the bottom
+    let status_bar = StatusBar::new("[ENTER] jump to item, [ESC] close the digest".to_string());
+    let status_cells = status_bar.render(terminal_width, 1, theme, false);
+    write_cells_to_buffer(&mut writer, &status_cells, 0, terminal_height - 1);
+
+    buffer_manager.render_to_terminal()?;
+
+    Ok(())
+}
+
+pub fn draw_help(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    lines: &[String],
+    scroll: &mut usize,
+    theme: &Theme,
+) -> io::Result<()> {
+    // Clear desired buffer to start with empty slate
+    buffer_manager.clear_desired_buffer();
+    let mut writer = buffer_manager.get_writer();
+
+    let (terminal_width, terminal_height) = get_terminal_size()?;
+
+    // Parse theme colors
+    let header_fg = string_to_color(&theme.header_fg).unwrap_or(crossterm::style::Color::Reset);
+    let normal_fg = string_to_color(&theme.help_fg).unwrap_or(crossterm::style::Color::Reset);
+
+    writer.move_to(0, 0);
+    writer.set_fg_color(header_fg);
+    writer.set_bg_color(crossterm::style::Color::Reset);
+    writer.set_bold(true);
+    writer.write_str("Keyboard reference");
+    writer.set_bold(false);
+
+    // Keep the scroll position within the text
+    let visible_rows = get_max_displayed_items_with_header_height(2)?;
+    *scroll = (*scroll).min(lines.len().saturating_sub(visible_rows));
+
+    for (offset, line) in lines.iter().skip(*scroll).take(visible_rows).enumerate() {
+        writer.move_to(0, 2 + offset);
+        // Section titles are the unindented lines
+        let is_title = !line.is_empty() && !line.starts_with(' ');
+        writer.set_fg_color(if is_title { header_fg } else { normal_fg });
+        writer.set_bold(is_title);
+        let text: String = line.chars().take(terminal_width).collect();
+        writer.write_str(&text);
+        writer.set_bold(false);
+    }
+
+    // Draw status line at the bottom
+    let status_bar = StatusBar::new(format!(
+        "[\u{2191}]/[\u{2193}] scroll, [ESC] close help ({}-{} of {})",
+        (*scroll + 1).min(lines.len()),
+        (*scroll + visible_rows).min(lines.len()),
+        lines.len()
+    ));
+    let status_cells = status_bar.render(terminal_width, 1, theme, false);
+    write_cells_to_buffer(&mut writer, &status_cells, 0, terminal_height - 1);
+
+    buffer_manager.render_to_terminal()?;
+
+    Ok(())
+}
+
+/// How long ago something happened, in the largest whole unit
+fn format_age(age: std::time::Duration) -> String {
+    let seconds = age.as_secs();
+    match seconds {
+        0..=59 => format!("{}s ago", seconds),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        _ => format!("{}h ago", seconds / 3600),
+    }
+}
+
+pub fn draw_notifications(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    notifications: &Notifications,
+    scroll: &mut usize,
+    theme: &Theme,
+) -> io::Result<()> {
+    // Clear desired buffer to start with empty slate
+    buffer_manager.clear_desired_buffer();
+    let mut writer = buffer_manager.get_writer();
+
+    let (terminal_width, terminal_height) = get_terminal_size()?;
+
+    // Parse theme colors
+    let header_fg = string_to_color(&theme.header_fg).unwrap_or(crossterm::style::Color::Reset);
+    let normal_fg = string_to_color(&theme.help_fg).unwrap_or(crossterm::style::Color::Reset);
+    let warn_fg = string_to_color(&theme.status_warn_fg).unwrap_or(crossterm::style::Color::Reset);
+    let error_fg = string_to_color(&theme.status_error_fg).unwrap_or(crossterm::style::Color::Reset);
+
+    writer.move_to(0, 0);
+    writer.set_fg_color(header_fg);
+    writer.set_bg_color(crossterm::style::Color::Reset);
+    writer.set_bold(true);
+    writer.write_str("Recent messages");
+    writer.set_bold(false);
+
+    // Newest first, kept within the list when it shrinks
+    let history: Vec<&Notification> = notifications.history().rev().collect();
+    let visible_rows = get_max_displayed_items_with_header_height(2)?;
+    *scroll = (*scroll).min(history.len().saturating_sub(visible_rows));
+
+    if history.is_empty() {
+        writer.move_to(0, 2);
+        writer.set_fg_color(normal_fg);
+        writer.write_str("No messages yet");
+    }
+
+    for (offset, notification) in history.iter().skip(*scroll).take(visible_rows).enumerate() {
+        writer.move_to(0, 2 + offset);
+        writer.set_fg_color(match notification.level {
+            NotificationLevel::Info => normal_fg,
+            NotificationLevel::Warn => warn_fg,
+            NotificationLevel::Error => error_fg,
+        });
+        let line = format!(
+            "{:>8}  {:<5}  {}",
+            format_age(notification.created.elapsed()),
+            notification.level.label(),
+            notification.message
+        );
+        let text: String = line.chars().take(terminal_width).collect();
+        writer.write_str(&text);
+    }
+
+    // Draw status line at the bottom
+    let status_bar = StatusBar::new(format!(
+        "[\u{2191}]/[\u{2193}] scroll, [ESC] close ({} messages)",
+        history.len()
+    ));
+    let status_cells = status_bar.render(terminal_width, 1, theme, false);
+    write_cells_to_buffer(&mut writer, &status_cells, 0, terminal_height - 1);
+
+    buffer_manager.render_to_terminal()?;
+
+    Ok(())
+}
+
+/// Draw the report of the last rescan, one line of `lines` per row from `scroll`
+pub fn draw_scan_report(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    lines: &[String],
+    scroll: &mut usize,
+    theme: &Theme,
+) -> io::Result<()> {
+    draw_report(buffer_manager, "Scan report", lines, scroll, theme)
+}
+
+/// Problems found in config.yaml at startup, each wrapped to the terminal's width
+pub fn draw_config_report(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    problems: &[String],
+    scroll: &mut usize,
+    theme: &Theme,
+) -> io::Result<()> {
+    let (terminal_width, _) = get_terminal_size()?;
+    let mut lines = vec!["These settings can't be used as written; fix them and restart".to_string()];
+    for problem in problems {
+        for (index, line) in wrap_words(problem, terminal_width.saturating_sub(4)).into_iter().enumerate() {
+            lines.push(format!("{}{}", if index == 0 { "  - " } else { "    " }, line));
+        }
+    }
+    draw_report(buffer_manager, "Startup report", &lines, scroll, theme)
+}
+
+/// A scrolling list of `lines` under `title`, where lines starting with a space are items of
+/// the section title above them
+fn draw_report(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    title: &str,
+    lines: &[String],
+    scroll: &mut usize,
+    theme: &Theme,
+) -> io::Result<()> {
+    // Clear desired buffer to start with empty slate
+    buffer_manager.clear_desired_buffer();
+    let mut writer = buffer_manager.get_writer();
+
+    let (terminal_width, terminal_height) = get_terminal_size()?;
+
+    // Parse theme colors
+    let header_fg = string_to_color(&theme.header_fg).unwrap_or(crossterm::style::Color::Reset);
+    let normal_fg = string_to_color(&theme.help_fg).unwrap_or(crossterm::style::Color::Reset);
+
+    writer.move_to(0, 0);
+    writer.set_fg_color(header_fg);
+    writer.set_bg_color(crossterm::style::Color::Reset);
+    writer.set_bold(true);
+    writer.write_str(title);
+    writer.set_bold(false);
+
+    // Kept within the report when the terminal grows
+    let visible_rows = get_max_displayed_items_with_header_height(2)?;
+    *scroll = (*scroll).min(lines.len().saturating_sub(visible_rows));
+
+    for (offset, line) in lines.iter().skip(*scroll).take(visible_rows).enumerate() {
+        writer.move_to(0, 2 + offset);
+        // Section titles start the line, their items are indented
+        let title = !line.starts_with(' ');
+        writer.set_fg_color(if title { header_fg } else { normal_fg });
+        writer.set_bold(title);
+        let text: String = line.chars().take(terminal_width).collect();
+        writer.write_str(&text);
+        writer.set_bold(false);
+    }
+
+    // Draw status line at the bottom
+    let status_bar = StatusBar::new("[\u{2191}]/[\u{2193}] scroll, [ESC] close".to_string());
+    let status_cells = status_bar.render(terminal_width, 1, theme, false);
+    write_cells_to_buffer(&mut writer, &status_cells, 0, terminal_height - 1);
+
+    buffer_manager.render_to_terminal()?;
+
+    Ok(())
+}
+
+pub fn draw_archive_review(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    archives: &[Archive],
+    selected_index: usize,
+    theme: &Theme,
+) -> io::Result<()> {
+    // Clear desired buffer to start with empty slate
+    buffer_manager.clear_desired_buffer();
+    let mut writer = buffer_manager.get_writer();
+
+    let (terminal_width, terminal_height) = get_terminal_size()?;
+
+    // Parse theme colors
+    let header_fg = string_to_color(&theme.header_fg).unwrap_or(crossterm::style::Color::Reset);
+    let help_fg = string_to_color(&theme.help_fg).unwrap_or(crossterm::style::Color::Reset);
+    let selected_fg = string_to_color(&theme.current_fg).unwrap_or(crossterm::style::Color::Black);
+    let selected_bg = string_to_color(&theme.current_bg).unwrap_or(crossterm::style::Color::White);
+    let normal_fg = string_to_color(&theme.episode_fg).unwrap_or(crossterm::style::Color::Reset);
+    let normal_bg = string_to_color(&theme.episode_bg).unwrap_or(crossterm::style::Color::Reset);
+
+    writer.move_to(0, 0);
+    writer.set_fg_color(header_fg);
+    writer.set_bg_color(crossterm::style::Color::Reset);
+    writer.set_bold(true);
+    writer.write_str("Archives with videos");
+    writer.set_bold(false);
+
+    writer.move_to(0, 1);
+    writer.set_fg_color(help_fg);
+    writer.write_str("Extracted videos are imported into the library");
+
+    // Keep the selection visible when the list is longer than the screen
+    let visible_rows = terminal_height.saturating_sub(5).max(1);
+    let first_row = selected_index.saturating_sub(visible_rows - 1);
+    for (offset, archive) in archives.iter().enumerate().skip(first_row).take(visible_rows) {
+        writer.move_to(2, 3 + offset - first_row);
+        if offset == selected_index {
+            writer.set_fg_color(selected_fg);
+            writer.set_bg_color(selected_bg);
+        } else {
+            writer.set_fg_color(normal_fg);
+            writer.set_bg_color(normal_bg);
+        }
+        let label: String = archive.label().chars().take(terminal_width.saturating_sub(2)).collect();
+        writer.write_str(&label);
+        writer.set_bg_color(crossterm::style::Color::Reset);
+    }
+
+    // Draw status line at the bottom
+    let status_bar = StatusBar::new("[ENTER] extract and import, [S] skip, [ESC] close".to_string());
+    let status_cells = status_bar.render(terminal_width, 1, theme, false);
+    write_cells_to_buffer(&mut writer, &status_cells, 0, terminal_height - 1);
+
+    buffer_manager.render_to_terminal()?;
+
+    Ok(())
+}
+
+/// Render the review of the duplicate finder: each video's copies under a heading, with the
+/// copy at `selected_index` among all of them highlighted
+pub fn draw_duplicate_review(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    groups: &[DuplicateGroup],
+    selected_index: usize,
+    theme: &Theme,
+) -> io::Result<()> {
+    buffer_manager.clear_desired_buffer();
+    let mut writer = buffer_manager.get_writer();
+
+    let (terminal_width, terminal_height) = get_terminal_size()?;
+
+    let header_fg = string_to_color(&theme.header_fg).unwrap_or(crossterm::style::Color::Reset);
+    let help_fg = string_to_color(&theme.help_fg).unwrap_or(crossterm::style::Color::Reset);
+    let selected_fg = string_to_color(&theme.current_fg).unwrap_or(crossterm::style::Color::Black);
+    let selected_bg = string_to_color(&theme.current_bg).unwrap_or(crossterm::style::Color::White);
+    let normal_fg = string_to_color(&theme.episode_fg).unwrap_or(crossterm::style::Color::Reset);
+    let normal_bg = string_to_color(&theme.episode_bg).unwrap_or(crossterm::style::Color::Reset);
+
+    writer.move_to(0, 0);
+    writer.set_fg_color(header_fg);
+    writer.set_bg_color(crossterm::style::Color::Reset);
+    writer.set_bold(true);
+    writer.write_str("Duplicates");
+    writer.set_bold(false);
+
+    writer.move_to(0, 1);
+    writer.set_fg_color(help_fg);
+    writer.write_str("Copies marked trash go to the trash and leave the library");
+
+    // Headings and copies as rows, with the index among the copies for the copy rows
+    let mut rows: Vec<(Option<usize>, String)> = Vec::new();
+    let mut copy_index = 0;
+    for group in groups {
+        rows.push((None, group.heading()));
+        for copy in &group.copies {
+            rows.push((Some(copy_index), format!("  {}", copy.label())));
+            copy_index += 1;
+        }
+    }
+
+    // Keep the selection visible when the list is longer than the screen
+    let visible_rows = terminal_height.saturating_sub(5).max(1);
+    let selected_row = rows.iter().position(|(index, _)| *index == Some(selected_index)).unwrap_or(0);
+    let first_row = selected_row.saturating_sub(visible_rows - 1);
+    for (row, (index, text)) in rows.iter().enumerate().skip(first_row).take(visible_rows) {
+        writer.move_to(2, 3 + row - first_row);
+        match index {
+            Some(index) if *index == selected_index => {
+                writer.set_fg_color(selected_fg);
+                writer.set_bg_color(selected_bg);
+            }
+            Some(_) => {
+                writer.set_fg_color(normal_fg);
+                writer.set_bg_color(normal_bg);
+            }
+            None => {
+                writer.set_fg_color(header_fg);
+                writer.set_bg_color(crossterm::style::Color::Reset);
+            }
+        }
+        writer.write_str(&text::truncate_to_width(text, terminal_width.saturating_sub(2)));
+        writer.set_bg_color(crossterm::style::Color::Reset);
+    }
+
+    let status_bar = StatusBar::new("[SPACE] keep or trash, [ENTER] trash the marked copies, [ESC] close".to_string());
+    let status_cells = status_bar.render(terminal_width, 1, theme, false);
+    write_cells_to_buffer(&mut writer, &status_cells, 0, terminal_height - 1);
+
+    buffer_manager.render_to_terminal()?;
+
+    Ok(())
+}
+
+/// Draw the picker listing the files of an episode
+pub fn draw_version_select(
+    buffer_manager: &mut crate::buffer::BufferManager,
+    episode_name: &str,
+    versions: &[EpisodeVersion],
+    selected_index: usize,
+    theme: &Theme,
+) -> io::Result<()> {
+    buffer_manager.clear_desired_buffer();
+    let mut writer = buffer_manager.get_writer();
+
+    let (terminal_width, terminal_height) = get_terminal_size()?;
+
+    let header_fg = string_to_color(&theme.header_fg).unwrap_or(crossterm::style::Color::Reset);
+    let help_fg = string_to_color(&theme.help_fg).unwrap_or(crossterm::style::Color::Reset);
+    let selected_fg = string_to_color(&theme.current_fg).unwrap_or(crossterm::style::Color::Black);
+    let selected_bg = string_to_color(&theme.current_bg).unwrap_or(crossterm::style::Color::White);
+    let normal_fg = string_to_color(&theme.episode_fg).unwrap_or(crossterm::style::Color::Reset);
+    let normal_bg = string_to_color(&theme.episode_bg).unwrap_or(crossterm::style::Color::Reset);
+
+    writer.move_to(0, 0);
+    writer.set_fg_color(header_fg);
+    writer.set_bg_color(crossterm::style::Color::Reset);
+    writer.set_bold(true);
+    let title: String = format!("Play {}", episode_name).chars().take(terminal_width).collect();
+    writer.write_str(&title);
+    writer.set_bold(false);
+
+    writer.move_to(0, 1);
+    writer.set_fg_color(help_fg);
+    writer.write_str("Every version shares the episode's watched state");
+
+    let visible_rows = terminal_height.saturating_sub(5).max(1);
+    let first_row = selected_index.saturating_sub(visible_rows - 1);
+    for (offset, version) in versions.iter().enumerate().skip(first_row).take(visible_rows) {
+        writer.move_to(2, 3 + offset - first_row);
+        if offset == selected_index {
+            writer.set_fg_color(selected_fg);
+            writer.set_bg_color(selected_bg);
+        } else {
+            writer.set_fg_color(normal_fg);
+            writer.set_bg_color(normal_bg);
+        }
+        let file_name = crate::location::decode(&version.location)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let line = format!("{}  {}", version.label, file_name);
+        let line: String = line.chars().take(terminal_width.saturating_sub(2)).collect();
+        writer.write_str(&line);
+        writer.set_bg_color(crossterm::style::Color::Reset);
+    }
+
+    let status_bar = StatusBar::new("[ENTER] play, [ESC] cancel".to_string());
+    let status_cells = status_bar.render(terminal_width, 1, theme, false);
+    write_cells_to_buffer(&mut writer, &status_cells, 0, terminal_height - 1);
+
+    buffer_manager.render_to_terminal()?;
+
+    Ok(())
+}
+
+
+/// Write component cells to buffer at specified position.
+///
+/// This is a key integration point between the component system and the buffer layer.
+/// Components render to their own Cell arrays, which are then written to the buffer
+/// at the appropriate screen position.
 ///
 /// # Buffer Integration
 ///
@@ -722,5 +1811,3 @@ fn write_cells_to_buffer(
         }
     }
 }
-
-
@@ -1,4 +1,9 @@
-#[derive(Clone)]
+use std::collections::BTreeMap;
+
+/// Alternate titles of an episode, keyed by language code
+pub type AltTitles = BTreeMap<String, String>;
+
+#[derive(Clone, Default)]
 pub struct EpisodeDetail {
     pub title: String,
     pub year: String,
@@ -9,6 +14,9 @@ pub struct EpisodeDetail {
     pub episode_number: String,
     pub last_watched_time: Option<String>,
     pub last_progress_time: Option<String>,
+    pub notes: Option<String>,
+    pub play_count: u32,
+    pub alt_titles: AltTitles,
 }
 
 #[derive(Clone)]
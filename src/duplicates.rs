@@ -0,0 +1,119 @@
+//! Finding episodes whose files are copies of the same video
+//!
+//! Only files of the same size can be copies. Among those, files that can be read are told
+//! apart by a hash of their size, beginning and end, and files that can't be read, such as
+//! ones on an offline drive, go by their size and length.
+
+use crate::torrent_search::format_size;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Bytes read from the beginning and from the end of a file for its hash
+pub const HASH_SAMPLE_BYTES: u64 = 1024 * 1024;
+
+/// An episode found by the duplicate finder, with whether the review keeps it
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateCopy {
+    pub episode_id: usize,
+    pub name: String,
+    /// Absolute path of the episode's own file
+    pub path: PathBuf,
+    pub size: u64,
+    /// Length in seconds, 0 when unknown
+    pub length: u64,
+    /// False once marked to go to the trash
+    pub keep: bool,
+}
+
+/// Copies of one video, the first kept and the others marked for the trash to start with
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateGroup {
+    pub copies: Vec<DuplicateCopy>,
+}
+
+impl DuplicateCopy {
+    /// Line shown for the copy in the review, e.g. "[keep]  Movie  movies/Movie.mkv"
+    pub fn label(&self) -> String {
+        let mark = if self.keep { "[keep] " } else { "[trash]" };
+        format!("{} {}  {}", mark, self.name, self.path.display())
+    }
+}
+
+impl DuplicateGroup {
+    /// Line shown above the copies in the review, e.g. "3 copies of 1.37 GB"
+    pub fn heading(&self) -> String {
+        let size = self.copies.first().map(|copy| copy.size).unwrap_or(0);
+        format!("{} copies of {}", self.copies.len(), format_size(size))
+    }
+
+    /// True when at least one copy is kept
+    pub fn keeps_one(&self) -> bool {
+        self.copies.iter().any(|copy| copy.keep)
+    }
+
+    /// Copies marked for the trash
+    pub fn to_trash(&self) -> impl Iterator<Item = &DuplicateCopy> {
+        self.copies.iter().filter(|copy| !copy.keep)
+    }
+}
+
+/// What the files of a group have in common
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CopyKey {
+    Hash([u8; 16]),
+    Length(u64),
+}
+
+/// Hash of the size of the file at `path` and of its first and last `HASH_SAMPLE_BYTES`
+pub fn quick_hash(path: &Path) -> io::Result<[u8; 16]> {
+    let mut file = File::open(path)?;
+    let size = file.metadata()?.len();
+    let mut context = md5::Context::new();
+    context.consume(size.to_le_bytes());
+    let mut buffer = Vec::new();
+    (&mut file).take(HASH_SAMPLE_BYTES).read_to_end(&mut buffer)?;
+    if size > HASH_SAMPLE_BYTES {
+        file.seek(SeekFrom::Start(size.saturating_sub(HASH_SAMPLE_BYTES).max(HASH_SAMPLE_BYTES)))?;
+        file.read_to_end(&mut buffer)?;
+    }
+    context.consume(&buffer);
+    Ok(context.compute().0)
+}
+
+/// Group `candidates` into copies of the same video, leaving out the ones without a copy
+///
+/// Groups are in the order of their first candidate and copies keep the order they came in,
+/// so the copy kept to start with is the one listed first.
+pub fn find_duplicates(candidates: Vec<DuplicateCopy>) -> Vec<DuplicateGroup> {
+    let mut by_size: Vec<(u64, Vec<DuplicateCopy>)> = Vec::new();
+    for copy in candidates.into_iter().filter(|copy| copy.size > 0) {
+        match by_size.iter_mut().find(|(size, _)| *size == copy.size) {
+            Some((_, copies)) => copies.push(copy),
+            None => by_size.push((copy.size, vec![copy])),
+        }
+    }
+
+    let mut groups = Vec::new();
+    for (_, same_size) in by_size.into_iter().filter(|(_, copies)| copies.len() > 1) {
+        let mut by_key: Vec<(CopyKey, Vec<DuplicateCopy>)> = Vec::new();
+        for copy in same_size {
+            let key = match quick_hash(&copy.path) {
+                Ok(hash) => CopyKey::Hash(hash),
+                Err(_) if copy.length > 0 => CopyKey::Length(copy.length),
+                Err(_) => continue,
+            };
+            match by_key.iter_mut().find(|(existing, _)| *existing == key) {
+                Some((_, copies)) => copies.push(copy),
+                None => by_key.push((key, vec![copy])),
+            }
+        }
+        for (_, mut copies) in by_key.into_iter().filter(|(_, copies)| copies.len() > 1) {
+            for (index, copy) in copies.iter_mut().enumerate() {
+                copy.keep = index == 0;
+            }
+            groups.push(DuplicateGroup { copies });
+        }
+    }
+    groups
+}
@@ -18,6 +18,7 @@ pub enum EpisodeField {
     EpisodeNumber = 8,
     LastWatchedTime = 9,
     LastProgressTime = 10,
+    PlayCount = 11,
 }
 
 impl From<usize> for EpisodeField {
@@ -34,6 +35,7 @@ impl From<usize> for EpisodeField {
             8 => EpisodeField::EpisodeNumber,
             9 => EpisodeField::LastWatchedTime,
             10 => EpisodeField::LastProgressTime,
+            11 => EpisodeField::PlayCount,
             _ => panic!("Invalid EditField value"),
         }
     }
@@ -62,7 +64,8 @@ impl EpisodeField {
             | EpisodeField::Length
             | EpisodeField::Series
             | EpisodeField::LastWatchedTime
-            | EpisodeField::LastProgressTime => false,
+            | EpisodeField::LastProgressTime
+            | EpisodeField::PlayCount => false,
             _ => true,
         }
     }
@@ -129,6 +132,7 @@ impl EpisodeField {
                     String::new()
                 }
             }
+            EpisodeField::PlayCount => details.play_count.to_string(),
         }
     }
 }
@@ -147,6 +151,7 @@ impl EpisodeField {
             EpisodeField::EpisodeNumber => "Ep #",
             EpisodeField::LastWatchedTime => "Last Watched",
             EpisodeField::LastProgressTime => "Progress",
+            EpisodeField::PlayCount => "Plays",
         }
     }
 }
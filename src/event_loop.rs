@@ -0,0 +1,813 @@
+//! The main loop: draws the screen when something changed, hands key presses to the
+//! handlers and picks up messages from background threads until the user quits
+//!
+//! Terminal events come from an `EventSource`: the terminal when the application runs,
+//! and a script of events in tests, which drive whole flows headlessly and look at the
+//! screen buffer and the database afterwards.
+
+use crate::app_state::{AppContext, AppState, DrawContext};
+use crate::artwork::{self, ArtworkState};
+use crate::buffer::BufferManager;
+use crate::components::alt_titles_editor::AltTitlesDraft;
+use crate::components::ThemePicker;
+use crate::config::{save_config, Config};
+use crate::custom_commands;
+use crate::database::Database;
+use crate::digest;
+use crate::display::{self, draw_screen};
+use crate::filter_history::{self, FilterHistory};
+use crate::handlers;
+use crate::hooks::{self, HookEvent};
+use crate::input::{self, EventSource, ScrollAccelerator};
+use crate::keybindings;
+use crate::keymap::{self, Key, Keymap};
+use crate::location;
+use crate::logger;
+use crate::path_resolver::PathResolver;
+use crate::query::{PinnedFilters, Query};
+use crate::remote::{self, RemoteServer};
+use crate::scan::{self, RescanSchedule};
+use crate::session::{self, StartupView};
+use crate::shutdown;
+use crate::terminal;
+use crate::theme::{self, Theme};
+use crate::tutorial::{Tutorial, TutorialKey};
+use crate::util::{self, AppEvent, Entry, Mode, SortMode};
+use crate::web_video;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// What the main loop starts with
+pub struct Startup {
+    pub entries: Vec<Entry>,
+    pub config: Config,
+    pub theme: Theme,
+    /// The library's database, None until a library is set up in Entry mode
+    pub database: Option<Arc<Database>>,
+    /// None until a library is set up in Entry mode
+    pub resolver: Option<PathResolver>,
+    /// Where changes to the config, such as pinned filters, are saved
+    pub config_path: PathBuf,
+    /// First message in the status bar
+    pub startup_message: String,
+    pub show_tutorial: bool,
+    /// Quit cleanly on SIGTERM and SIGINT; left off in tests, which share their process
+    pub handle_signals: bool,
+    /// Problems found in config.yaml and the theme, shown in a panel when the app opens
+    pub config_problems: Vec<String>,
+}
+
+/// Run the application on `buffer_manager` with events from `events`, until the user quits
+pub fn run(startup: Startup, events: &mut dyn EventSource, buffer_manager: &mut BufferManager) -> io::Result<()> {
+    let Startup {
+        entries,
+        mut config,
+        mut theme,
+        mut database,
+        mut resolver,
+        config_path,
+        startup_message,
+        show_tutorial,
+        handle_signals,
+        config_problems,
+    } = startup;
+    let mut state = AppState {
+        sort_mode: SortMode::from_config(&config.default_sort).unwrap_or_else(|| {
+            logger::log_warn(&format!("Unknown default_sort '{}', using the default order", config.default_sort));
+            SortMode::Default
+        }),
+        pinned_filters: PinnedFilters::new(&config.pinned_filters),
+        tutorial: show_tutorial.then(Tutorial::new),
+        ..AppState::new(entries)
+    };
+    state.notifications.info(startup_message);
+    for folder in resolver.as_ref().map(PathResolver::offline_roots).unwrap_or_default() {
+        logger::log_warn(&format!("Library folder offline: {}", folder.display()));
+        state.notifications.warn(format!("Library folder offline: {}", folder.display()));
+    }
+    if let Some(db) = database.as_deref() {
+        state.series = db.get_all_series().unwrap_or_else(|e| {
+            logger::log_error(&format!("Failed to load series: {}", e));
+            state.notifications.error(format!("Failed to load series: {}", e));
+            Vec::new()
+        });
+    }
+    display::set_list_split(config.list_split);
+    let (keymap, mut problems) = Keymap::from_config(&config.keybindings);
+    problems.extend(custom_commands::install(&config.custom_commands, &keymap));
+    for problem in problems {
+        logger::log_warn(&problem);
+        state.notifications.warn(problem);
+    }
+    keymap::set_keymap(keymap);
+    let hook_script = config.hook_script.as_deref().map(|setting| {
+        hooks::script_path(setting, config_path.parent().unwrap_or(Path::new(".")))
+    });
+    if let Err(e) = hooks::install(hook_script.as_deref()) {
+        logger::log_warn(&e);
+        state.notifications.warn(e);
+    }
+    if config.accessible {
+        theme.make_accessible();
+    }
+
+    // Filters kept in earlier sessions, recalled with Up/Down while typing the filter
+    if let Some(path) = filter_history::default_filter_history_path() {
+        state.filter_history = FilterHistory::load(path);
+    }
+
+    // The view startup_view asks for, by default the one left at the last exit; the tutorial
+    // starts from the library instead
+    if let (Some(db), Some(_), false) = (database.as_deref(), resolver.as_ref(), show_tutorial) {
+        let view = StartupView::from_config(&config.startup_view).unwrap_or(StartupView::LastSession);
+        session::open_startup_view(view, &mut state, db, config.restore_session);
+    }
+
+    // Weekly digest, opened on the first launch of the week when enabled
+    if let (Some(db), true, false) = (database.as_deref(), config.weekly_digest, show_tutorial) {
+        if let Some(digest_path) = digest::default_digest_state_path() {
+            let now = chrono::Local::now();
+            if digest::is_due(&digest_path, now) {
+                match db.get_weekly_digest(chrono::Utc::now()) {
+                    Ok(loaded) if !loaded.is_empty() => {
+                        state.weekly_digest = loaded;
+                        state.open_modal(Mode::Digest);
+                    }
+                    Ok(_) => {}
+                    Err(e) => logger::log_error(&format!("Failed to build the weekly digest: {}", e)),
+                }
+                if let Err(e) = digest::mark_shown(&digest_path, now) {
+                    logger::log_warn(&format!("Failed to save the weekly digest state: {}", e));
+                }
+            }
+        }
+    }
+
+    // Problems with config.yaml, on top of everything else so they're seen first; they were
+    // logged when the config was read
+    if !config_problems.is_empty() {
+        for problem in &config_problems {
+            state.notifications.warn(problem.clone());
+        }
+        state.config_problems = config_problems;
+        if !show_tutorial {
+            state.open_modal(Mode::ConfigReport);
+        }
+    }
+
+    // Mode last drawn, to redraw everything when the screen changes
+    let mut drawn_mode = state.mode.clone();
+
+    // Create a channel to communicate between the background threads and the main loop
+    let (tx, rx): (Sender<AppEvent>, Receiver<AppEvent>) = mpsc::channel();
+    if handle_signals {
+        shutdown::listen_for_signals(tx.clone());
+    }
+
+    // Commands from other programs, and the events they are sent, when remote_control is on
+    let remote = if config.remote_control {
+        let socket = config.remote_socket.as_ref().map(PathBuf::from).or_else(remote::default_socket_path);
+        match socket.map(|path| RemoteServer::start(&path, tx.clone())) {
+            Some(Ok(server)) => {
+                logger::log_info(&format!("Remote control listening on {}", server.path().display()));
+                Some(server)
+            }
+            Some(Err(e)) => {
+                logger::log_warn(&format!("Failed to start the remote control: {}", e));
+                state.notifications.warn(format!("Remote control is off: {}", e));
+                None
+            }
+            None => None,
+        }
+    } else {
+        None
+    };
+    // File last reported to the remote control clients as playing
+    let mut reported_playing: Option<String> = None;
+
+    // Held-key scroll acceleration and an event read ahead while coalescing repeats
+    let mut scroll_accelerator = ScrollAccelerator::new(
+        config.scroll_acceleration.clone(),
+        config.scroll_acceleration_repeats,
+    );
+    let mut pending_event: Option<Event> = None;
+
+    if config.mouse {
+        terminal::enable_mouse_capture()?;
+    }
+
+    // Posters are drawn with the terminal's image protocol, when it has one
+    let artwork_protocol = if config.artwork { terminal::detect_graphics_protocol() } else { None };
+    if config.artwork {
+        logger::log_info(&format!("Artwork graphics protocol: {:?}", artwork_protocol));
+    }
+    let mut artwork_state = ArtworkState::default();
+
+    let mut rescan_schedule = RescanSchedule::new(config.rescan_interval_minutes, Instant::now());
+
+    loop {
+        // Move on to the next status message once the current one has been up long enough
+        if state.notifications.expire(Instant::now()) {
+            state.redraw = true;
+        }
+
+        // While the filter is typed, handle the keys already on their way before filtering again
+        let typing_ahead = state.redraw
+            && state.filter_mode
+            && pending_event.is_none()
+            && events.poll(input::FILTER_DEBOUNCE)?;
+        if state.redraw && !typing_ahead {
+            // Check if mode has changed and trigger full redraw if needed
+            if state.mode != drawn_mode {
+                buffer_manager.force_full_redraw();
+                drawn_mode = state.mode.clone();
+            }
+
+            // Screens stacked over the one now on screen have been closed
+            state.modals.follow(&state.mode);
+            
+            // A tab stays active only while its view is on screen
+            state.tabs.follow(&state.view_context);
+
+            // Persist pins added or cleared from the menu
+            let pinned = state.pinned_filters.expressions();
+            if pinned != config.pinned_filters {
+                config.pinned_filters = pinned;
+                save_config(&config, &config_path);
+            }
+
+            // Persist the split between the list and the details after Ctrl+Left/Right
+            if display::list_split() != config.list_split {
+                config.list_split = display::list_split();
+                save_config(&config, &config_path);
+            }
+
+            // Filter entries by the typed filter and any pinned filters that are switched on,
+            // unless the keys since the last redraw only moved the selection
+            if let (false, Some(db)) = (std::mem::take(&mut state.list_unchanged), database.as_deref()) {
+                let typed_filter = Query::parse(&state.search);
+                let mut queries = state.pinned_filters.active_queries();
+                queries.push(&typed_filter);
+                let facts = if queries.iter().any(|query| query.needs_facts()) {
+                    db.get_episode_facts().unwrap_or_else(|e| {
+                        logger::log_error(&format!("Failed to load episode facts for filtering: {}", e));
+                        Vec::new()
+                    })
+                } else {
+                    Vec::new()
+                };
+                let preferred_language = config.preferred_title_language.as_deref().filter(|l| !l.is_empty());
+                let alt_titles = if preferred_language.is_some() || queries.iter().any(|query| query.has_text()) {
+                    db.get_all_alt_titles().unwrap_or_else(|e| {
+                        logger::log_error(&format!("Failed to load alternate titles: {}", e));
+                        HashMap::new()
+                    })
+                } else {
+                    HashMap::new()
+                };
+                // Narrowing the last result only holds while the filter is typed over the same entries
+                if !state.filter_mode {
+                    state.filter_cache.clear();
+                }
+                state.filtered_entries = state.filter_cache.filter(&state.entries, &queries, &facts, &alt_titles).to_vec();
+                if let Some(language) = preferred_language {
+                    util::apply_preferred_titles(&mut state.filtered_entries, &alt_titles, language);
+                }
+
+                // Apply the Browse sort order
+                if state.sort_mode != SortMode::Default {
+                    match db.get_sort_keys() {
+                        Ok(keys) => util::sort_entries(&mut state.filtered_entries, state.sort_mode, &keys),
+                        Err(e) => logger::log_error(&format!("Failed to load sort keys: {}", e)),
+                    }
+                }
+            }
+
+            // Ensure current_item is within bounds
+            if state.current_item >= state.filtered_entries.len() {
+                state.current_item = if state.filtered_entries.is_empty() {
+                    0
+                } else {
+                    state.filtered_entries.len() - 1
+                };
+            }
+
+            //if we're in Browse mode, we need to populate edit_details before calling draw_screen
+            if let (Mode::Browse, Some(db)) = (&state.mode, database.as_deref()) {
+                if !state.filtered_entries.is_empty() {
+                    if let Entry::Episode { episode_id, .. } = &state.filtered_entries[state.current_item] {
+                        state.selected_entry_id = Some(*episode_id);
+                        if let Some(id) = state.selected_entry_id {
+                            match db.get_episode_detail(id) {
+                                Ok(details) => state.edit_details = details,
+                                Err(e) => {
+                                    logger::log_error(&format!("Failed to load episode {}: {}", id, e));
+                                    state.notifications.error(format!("Failed to load episode {}: {}", id, e));
+                                }
+                            }
+                        }
+                    } else {
+                        state.selected_entry_id = None;
+                    }
+                }
+            }
+
+            // Start the alternate titles draft from the loaded details when the editor opens
+            if state.mode == Mode::AltTitlesEdit {
+                state.alt_titles_draft.get_or_insert_with(|| AltTitlesDraft::from_titles(&state.edit_details.alt_titles));
+            } else {
+                state.alt_titles_draft = None;
+            }
+
+            // List the theme files when the picker opens, starting on the active theme
+            if state.mode == Mode::ThemeSelect {
+                state.theme_picker.get_or_insert_with(|| {
+                    let config_dir = config_path.parent().unwrap_or(Path::new("."));
+                    ThemePicker::new(theme::list_theme_files(config_dir), &config.active_theme, theme.clone())
+                });
+            } else {
+                state.theme_picker = None;
+            }
+
+            // Call appropriate display function based on mode
+            match state.mode {
+                Mode::TorrentSearchInput => {
+                    display::draw_torrent_search_input(
+                        buffer_manager,
+                        &state.search_query,
+                        &theme,
+                    )?;
+                }
+                Mode::TorrentSearchResults => {
+                    display::draw_torrent_search_results(
+                        buffer_manager,
+                        &state.torrent_results,
+                        state.selected_torrent_result,
+                        &theme,
+                    )?;
+                }
+                Mode::Digest => {
+                    display::draw_digest(
+                        buffer_manager,
+                        &state.weekly_digest,
+                        state.selected_digest_item,
+                        &theme,
+                    )?;
+                }
+                Mode::Help => {
+                    display::draw_help(
+                        buffer_manager,
+                        &keybindings::help_lines(),
+                        &mut state.help_scroll,
+                        &theme,
+                    )?;
+                }
+                Mode::Notifications => {
+                    display::draw_notifications(
+                        buffer_manager,
+                        &state.notifications,
+                        &mut state.notification_scroll,
+                        &theme,
+                    )?;
+                }
+                Mode::ScanReport => {
+                    display::draw_scan_report(
+                        buffer_manager,
+                        &state.scan_report.lines(),
+                        &mut state.scan_report_scroll,
+                        &theme,
+                    )?;
+                }
+                Mode::ConfigReport => {
+                    display::draw_config_report(
+                        buffer_manager,
+                        &state.config_problems,
+                        &mut state.config_report_scroll,
+                        &theme,
+                    )?;
+                }
+                Mode::Relocate => {
+                    display::draw_relocate(
+                        buffer_manager,
+                        &state.relocate_path,
+                        state.relocation_check.as_ref(),
+                        &theme,
+                    )?;
+                }
+                Mode::AddFromUrl => {
+                    let download_folder = resolver
+                        .as_ref()
+                        .map(|res| web_video::download_folder(res.get_root_dir(), &config.download_folder))
+                        .unwrap_or_default();
+                    display::draw_add_from_url(
+                        buffer_manager,
+                        &state.download_url,
+                        &download_folder,
+                        &theme,
+                    )?;
+                }
+                Mode::DuplicateReview => {
+                    display::draw_duplicate_review(
+                        buffer_manager,
+                        &state.duplicate_groups,
+                        state.selected_duplicate,
+                        &theme,
+                    )?;
+                }
+                Mode::RenameFile => {
+                    if let Some(preview) = &state.rename_preview {
+                        display::draw_rename_preview(buffer_manager, preview, &theme)?;
+                    }
+                }
+                Mode::ArchiveReview => {
+                    display::draw_archive_review(
+                        buffer_manager,
+                        &state.pending_archives,
+                        state.selected_archive,
+                        &theme,
+                    )?;
+                }
+                Mode::VersionSelect => {
+                    let episode_name = match state.filtered_entries.get(state.current_item) {
+                        Some(Entry::Episode { name, .. }) => name.as_str(),
+                        _ => "",
+                    };
+                    display::draw_version_select(
+                        buffer_manager,
+                        episode_name,
+                        &state.versions,
+                        state.selected_version,
+                        &theme,
+                    )?;
+                }
+                _ => {
+                    let draw_context = DrawContext {
+                        theme: &theme,
+                        database: database.as_deref().expect("Database should be open"),
+                        resolver: resolver.as_ref().expect("PathResolver should be initialized"),
+                        scroll_margin: config.scroll_margin,
+                        accessible: config.accessible,
+                    };
+                    state.list_top = draw_screen(&mut state, &draw_context, buffer_manager)?;
+                }
+            }
+
+            // Poster of the selected entry, drawn over the finished frame
+            if let Some(protocol) = artwork_protocol {
+                let poster = match (database.as_deref(), resolver.as_ref()) {
+                    (Some(db), Some(res)) if state.mode == Mode::Browse && state.tutorial.is_none() => state.filtered_entries
+                        .get(state.current_item)
+                        .and_then(|entry| artwork::find_artwork(entry, db, res)),
+                    _ => None,
+                };
+                display::draw_artwork(protocol, poster.as_deref(), state.list_top, &mut artwork_state, buffer_manager)?;
+            }
+            state.redraw = false;
+        }
+
+        // Rescan in the background when it is time, unless one is already running
+        if rescan_schedule.is_due(Instant::now()) {
+            rescan_schedule.restart(Instant::now());
+            if let (Some(db), Some(res)) = (database.as_ref(), resolver.as_ref()) {
+                let root = res.get_root_dir().to_path_buf();
+                if scan::spawn_rescan(root, &config, Arc::clone(db), res.clone(), tx.clone()) {
+                    logger::log_info("Automatic rescan started");
+                    state.automatic_rescan = true;
+                }
+            }
+        }
+
+        // Check for messages from the background threads
+        while let Ok(message) = rx.try_recv() {
+            state.list_unchanged = false;
+            match message {
+                AppEvent::PlaybackFinished => state.playing_file = None,
+                AppEvent::ScanProgress(progress) => {
+                    state.scan_progress = Some(progress);
+                    state.scan_frame += 1;
+                }
+                AppEvent::ScanFinished(report) => {
+                    if let Some(ref remote) = remote {
+                        remote.emit(&format!("scan-finished {}", report.imported));
+                    }
+                    state.scan_progress = None;
+                    rescan_schedule.restart(Instant::now());
+                    display::forget_file_checks();
+                    match (database.as_deref(), resolver.as_ref()) {
+                        (Some(db), Some(res)) => {
+                            if let Err(e) = hooks::fire_for_files(db, HookEvent::Import, &report.imported_files, res) {
+                                state.notifications.warn(e);
+                            }
+                            handlers::finish_rescan(*report, &mut state, &config, db);
+                        }
+                        _ => logger::log_warn("A rescan finished with no library open"),
+                    }
+                }
+                AppEvent::Remote(request) => {
+                    let result = match (database.as_ref(), resolver.as_ref()) {
+                        (Some(db), Some(res)) => {
+                            let context = AppContext {
+                                config: &config,
+                                database: db,
+                                resolver: res,
+                                tx: &tx,
+                                config_path: &config_path,
+                            };
+                            handlers::handle_remote_command(request.command, &mut state, &context)
+                        }
+                        _ => Err("no library is open yet".to_string()),
+                    };
+                    request.reply.send(result).ok();
+                }
+                AppEvent::DownloadFinished(result) => match (database.as_deref(), resolver.as_ref()) {
+                    (Some(db), Some(res)) => handlers::finish_download(result, &mut state, db, res),
+                    _ => logger::log_warn("A download finished with no library open"),
+                },
+                AppEvent::SyncProgress(line) => state.sync_status = Some(line),
+                AppEvent::SyncFinished(result) => match database.as_deref() {
+                    Some(db) => handlers::finish_sync(result, &mut state, db),
+                    None => logger::log_warn("A sync finished with no library open"),
+                },
+                AppEvent::CommandFinished { label, result } => match result {
+                    Ok(()) => state.notifications.info(format!("{} finished; its output is in the log", label)),
+                    Err(e) => {
+                        logger::log_error(&format!("{} failed: {}", label, e));
+                        state.notifications.error(format!("{} failed: {}", label, e));
+                    }
+                },
+                // Quit as Esc would, leaving the terminal to be restored by the caller
+                AppEvent::Shutdown(signal) => {
+                    logger::log_info(&format!("Received {}, quitting", signal));
+                    if config.restore_session {
+                        session::save_last_session(&state);
+                    }
+                    return Ok(());
+                }
+            }
+            state.filter_cache.clear();
+            state.redraw = true;
+        }
+
+        // Tell the remote control clients when a video starts or stops playing
+        if let Some(ref remote) = remote {
+            if state.playing_file != reported_playing {
+                match state.playing_file {
+                    Some(ref file) => remote.emit(&format!("playing {}", location::display(file))),
+                    None => remote.emit("stopped"),
+                }
+                reported_playing = state.playing_file.clone();
+            }
+        }
+
+        // Use an event read ahead during coalescing, then the keys of a macro being replayed,
+        // otherwise poll with a timeout
+        let next_event = match pending_event.take() {
+            Some(event) => Some(event),
+            None => match state.macros.next_key() {
+                Some(key) => Some(Event::Key(KeyEvent::new(key.code, key.modifiers))),
+                None if events.poll(Duration::from_millis(100))? => Some(events.read()?),
+                None => None,
+            },
+        };
+        if let Some(event) = next_event {
+            // Only a key that moves the selection keeps the list as it is
+            state.list_unchanged = false;
+
+            // Handle terminal resize events
+            if let Event::Resize(width, height) = event {
+                buffer_manager.resize(width as usize, height as usize);
+                state.redraw = true;
+                continue;
+            }
+            
+            // Mouse events are turned into the equivalent key and handled on the next pass
+            if let Event::Mouse(mouse) = event {
+                if config.mouse {
+                    if let Some(code) = handlers::handle_mouse(mouse, &mut state)? {
+                        pending_event = Some(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+                    }
+                }
+                continue;
+            }
+
+            if let Event::Key(KeyEvent {
+                code, modifiers, ..
+            }) = event
+            {
+                // Handlers that need the library only run once it has been set up in Entry mode
+                let context = database.as_ref().zip(resolver.as_ref()).map(|(db, res)| AppContext {
+                    config: &config,
+                    database: db,
+                    resolver: res,
+                    tx: &tx,
+                    config_path: &config_path,
+                });
+                let recording = state.macros.is_recording();
+                match state.mode {
+                    Mode::Entry if state.scan_folder_entry => {
+                        if let Some(context) = context {
+                            handlers::handle_scan_folder_entry(code, &mut state, &context);
+                        } else {
+                            state.scan_folder_entry = false;
+                            state.mode = Mode::Entry;
+                            state.redraw = true;
+                        }
+                    }
+                    Mode::Entry => {
+                        handlers::handle_entry_mode(code, &mut state, &mut config, &config_path, &mut database, &mut resolver);
+                    }
+                    Mode::Edit if code == KeyCode::F(3) => {
+                        if let Some(context) = context {
+                            handlers::open_rename_preview(&mut state, &context);
+                        }
+                    }
+                    Mode::Edit => {
+                        if let Some(context) = context {
+                            handlers::handle_edit_mode(code, modifiers, &mut state, &context);
+                        } else {
+                            state.close_modal();
+                        }
+                    }
+                    Mode::Browse => {
+                        // If resolver is None, we need to enter Entry mode for setup
+                        if let Some(context) = context {
+                            // The tutorial sees keys first so it can advance alongside the real action
+                            let typing = state.filter_mode || state.command_line.is_some();
+                            if let (Some(active), false) = (state.tutorial.as_mut(), typing) {
+                                match active.handle_key(code) {
+                                    TutorialKey::Ignored => {}
+                                    TutorialKey::Advanced => state.redraw = true,
+                                    TutorialKey::Consumed => {
+                                        state.redraw = true;
+                                        continue;
+                                    }
+                                    TutorialKey::Finished => {
+                                        logger::log_info("Tutorial closed");
+                                        state.tutorial = None;
+                                        buffer_manager.force_full_redraw();
+                                        state.redraw = true;
+                                        continue;
+                                    }
+                                }
+                            }
+
+                            // Accelerate held Up/Down and merge queued repeats into one move
+                            let scroll_step = if !typing && input::is_scroll_key(code) {
+                                input::coalesce_scroll_repeats(events, code, &mut scroll_accelerator, &mut pending_event)?
+                            } else {
+                                scroll_accelerator.reset();
+                                1
+                            };
+                            if !handlers::handle_browse_mode(code, modifiers, &mut state, &context, scroll_step)? {
+                                if config.restore_session {
+                                    session::save_last_session(&state);
+                                }
+                                break Ok(());
+                            }
+                        } else {
+                            state.mode = Mode::Entry;
+                            state.redraw = true;
+                        }
+                    }
+                    Mode::SeriesSelect => {
+                        if let Some(context) = context {
+                            handlers::handle_series_select_mode(code, &mut state, &context);
+                        } else {
+                            state.close_modal();
+                        }
+                    }
+                    Mode::SeriesCreate => {
+                        if let Some(context) = context {
+                            handlers::handle_series_create_mode(code, modifiers, &mut state, &context);
+                        } else {
+                            state.close_modal();
+                        }
+                    }
+                    Mode::NotesEdit => {
+                        if let Some(context) = context {
+                            handlers::handle_notes_edit_mode(code, modifiers, &mut state, &context);
+                        } else {
+                            state.close_modal();
+                        }
+                    }
+                    Mode::AltTitlesEdit => {
+                        if let Some(context) = context {
+                            handlers::handle_alt_titles_edit_mode(code, &mut state, &context);
+                        } else {
+                            state.close_modal();
+                        }
+                    }
+                    Mode::Menu => {
+                        if let Some(context) = context {
+                            handlers::handle_menu_mode(code, &mut state, &context, buffer_manager);
+                        } else {
+                            // If resolver is None, exit menu and enter Entry mode
+                            state.mode = Mode::Entry;
+                            state.redraw = true;
+                        }
+                    }
+                    Mode::TorrentSearchInput => {
+                        handlers::handle_torrent_search_input(code, &mut state);
+                    }
+                    Mode::TorrentSearchResults => {
+                        handlers::handle_torrent_search_results(code, &mut state);
+                    }
+                    Mode::Digest => {
+                        if let Some(context) = context {
+                            handlers::handle_digest_mode(code, &mut state, &context);
+                        } else {
+                            state.close_modal();
+                        }
+                    }
+                    Mode::Confirm => {
+                        if let Some(context) = context {
+                            handlers::handle_confirm_mode(code, &mut state, &context);
+                        } else {
+                            state.pending_confirmation = None;
+                            state.mode = Mode::Browse;
+                            state.redraw = true;
+                        }
+                    }
+                    Mode::Help => {
+                        handlers::handle_help_mode(code, &mut state)?;
+                    }
+                    Mode::Notifications => {
+                        handlers::handle_notifications_mode(code, &mut state)?;
+                    }
+                    Mode::VersionSelect => {
+                        if let Some(context) = context {
+                            handlers::handle_version_select_mode(code, &mut state, &context)?;
+                        } else {
+                            state.mode = Mode::Browse;
+                            state.redraw = true;
+                        }
+                    }
+                    Mode::ThemeSelect => {
+                        handlers::handle_theme_select_mode(code, &mut state, &mut theme, &mut config, &config_path);
+                    }
+                    Mode::ScanReport => {
+                        if let Some(context) = context {
+                            handlers::handle_scan_report_mode(code, &mut state, &context)?;
+                        } else {
+                            state.close_modal();
+                        }
+                    }
+                    Mode::ConfigReport => {
+                        handlers::handle_config_report_mode(code, &mut state)?;
+                    }
+                    Mode::Relocate => {
+                        handlers::handle_relocate_mode(code, &mut state, &mut config, &config_path, &mut database, &mut resolver);
+                    }
+                    Mode::AddFromUrl => {
+                        if let Some(context) = context {
+                            handlers::handle_add_from_url_mode(code, &mut state, &context);
+                        } else {
+                            state.close_modal();
+                        }
+                    }
+                    Mode::DuplicateReview => {
+                        if let Some(context) = context {
+                            handlers::handle_duplicate_review_mode(code, &mut state, &context);
+                        } else {
+                            state.duplicate_groups.clear();
+                            state.close_modal();
+                        }
+                    }
+                    Mode::RenameFile => {
+                        if let Some(context) = context {
+                            handlers::handle_rename_file_mode(code, &mut state, &context);
+                        } else {
+                            state.rename_preview = None;
+                            state.close_modal();
+                        }
+                    }
+                    Mode::ArchiveReview => {
+                        if let Some(context) = context {
+                            handlers::handle_archive_review_mode(code, &mut state, &context);
+                        } else {
+                            state.mode = Mode::Browse;
+                            state.redraw = true;
+                        }
+                    }
+                }
+
+                // Clear dirty state when exiting EDIT mode; the rename preview goes back to it
+                if !matches!(state.mode, Mode::Edit | Mode::RenameFile)
+                    && state.original_edit_details.is_some() {
+                        state.original_edit_details = None;
+                        state.dirty_fields.clear();
+                    }
+
+                // Keys between the ones starting and stopping a recording make up the macro
+                if recording && state.macros.is_recording() {
+                    state.macros.record(Key { code, modifiers });
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Supported formats for library export
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    /// Default file name used when exporting into the library root
+    pub fn default_file_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "library_export.json",
+            ExportFormat::Csv => "library_export.csv",
+        }
+    }
+
+    /// Detect the format from a file extension
+    pub fn from_path(path: &Path) -> Option<ExportFormat> {
+        match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()) {
+            Some(ext) if ext == "json" => Some(ExportFormat::Json),
+            Some(ext) if ext == "csv" => Some(ExportFormat::Csv),
+            _ => None,
+        }
+    }
+
+    /// Short label used in menus and status messages
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Csv => "CSV",
+        }
+    }
+}
+
+/// A single exported episode with its series/season placement and watch state
+/// `root` is the library folder `location` is relative to, None for the library root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibraryRecord {
+    pub location: String,
+    pub title: String,
+    pub year: Option<i64>,
+    pub watched: bool,
+    pub length: Option<i64>,
+    pub series: Option<String>,
+    pub season: Option<usize>,
+    pub episode_number: Option<i64>,
+    pub last_watched_time: Option<String>,
+    pub last_progress_time: Option<i64>,
+    #[serde(default)]
+    pub root: Option<String>,
+}
+
+/// Summary of a library import; in a dry run nothing is written
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub total: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub unmatched: Vec<String>,
+    pub changes: Vec<String>,
+}
+
+impl ImportReport {
+    /// One-line summary suitable for the status bar
+    pub fn summary(&self) -> String {
+        if self.dry_run {
+            format!(
+                "Import preview: {} of {} videos would change, {} unmatched (details in log)",
+                self.updated,
+                self.total,
+                self.unmatched.len()
+            )
+        } else {
+            format!(
+                "Imported metadata for {} of {} videos, {} unmatched",
+                self.updated,
+                self.total,
+                self.unmatched.len()
+            )
+        }
+    }
+}
+
+/// Column order used for CSV export
+pub const CSV_HEADER: [&str; 11] = [
+    "location",
+    "title",
+    "year",
+    "watched",
+    "length",
+    "series",
+    "season",
+    "episode_number",
+    "last_watched_time",
+    "last_progress_time",
+    "root",
+];
+
+/// The fields of CSV_HEADER an import file has; the ones it lacks keep the values in the library
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportColumns(Vec<&'static str>);
+
+impl ImportColumns {
+    /// The fields named in `names`
+    pub fn named<'a>(names: impl IntoIterator<Item = &'a str>) -> Self {
+        let names: Vec<&str> = names.into_iter().map(str::trim).collect();
+        ImportColumns(CSV_HEADER.iter().copied().filter(|column| names.contains(column)).collect())
+    }
+
+    pub fn has(&self, column: &str) -> bool {
+        self.0.contains(&column)
+    }
+}
+
+/// Serialize records to a pretty-printed JSON array
+pub fn records_to_json(records: &[LibraryRecord]) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(records)
+}
+
+/// Serialize records to CSV with a header row
+pub fn records_to_csv(records: &[LibraryRecord]) -> String {
+    let mut output = CSV_HEADER.join(",");
+    output.push('\n');
+
+    for record in records {
+        let fields = [
+            record.location.clone(),
+            record.title.clone(),
+            optional_to_string(&record.year),
+            record.watched.to_string(),
+            optional_to_string(&record.length),
+            record.series.clone().unwrap_or_default(),
+            optional_to_string(&record.season),
+            optional_to_string(&record.episode_number),
+            record.last_watched_time.clone().unwrap_or_default(),
+            optional_to_string(&record.last_progress_time),
+            record.root.clone().unwrap_or_default(),
+        ];
+        let escaped: Vec<String> = fields.iter().map(|f| escape_csv_field(f)).collect();
+        output.push_str(&escaped.join(","));
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break
+pub fn escape_csv_field(field: &str) -> String {
+    if field.contains(',') || field.contains('"') || field.contains('\n') || field.contains('\r') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Locate an export file in the library root to import from, preferring JSON over CSV
+pub fn find_import_file(root: &Path) -> Option<PathBuf> {
+    [ExportFormat::Json, ExportFormat::Csv]
+        .iter()
+        .map(|format| root.join(format.default_file_name()))
+        .find(|path| path.is_file())
+}
+
+/// Parse records from a JSON export, with the fields every record has
+pub fn parse_json(content: &str) -> Result<(Vec<LibraryRecord>, ImportColumns), serde_json::Error> {
+    let values: Vec<serde_json::Value> = serde_json::from_str(content)?;
+    let columns = CSV_HEADER
+        .iter()
+        .copied()
+        .filter(|column| values.iter().all(|value| value.get(column).is_some()));
+    let columns = ImportColumns::named(columns);
+    let records = values.into_iter().map(serde_json::from_value).collect::<Result<_, _>>()?;
+    Ok((records, columns))
+}
+
+/// Parse records from a CSV export, matching columns by header name, with the columns it has
+pub fn parse_csv(content: &str) -> Result<(Vec<LibraryRecord>, ImportColumns), String> {
+    let mut rows = split_csv_rows(content).into_iter();
+    let (_, header) = rows.next().ok_or("CSV file is empty")?;
+    let columns = ImportColumns::named(header.iter().map(String::as_str));
+
+    let column = |name: &str| header.iter().position(|h| h.trim() == name);
+    let location_col = column("location").ok_or("CSV file is missing a 'location' column")?;
+    let title_col = column("title");
+    let year_col = column("year");
+    let watched_col = column("watched");
+    let length_col = column("length");
+    let series_col = column("series");
+    let season_col = column("season");
+    let episode_number_col = column("episode_number");
+    let last_watched_col = column("last_watched_time");
+    let last_progress_col = column("last_progress_time");
+    let root_col = column("root");
+
+    let mut records = Vec::new();
+    for (line, row) in rows {
+        let field = |col: Option<usize>| {
+            col.and_then(|c| row.get(c))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        let location = match field(Some(location_col)) {
+            Some(location) => location,
+            None => continue,
+        };
+
+        records.push(LibraryRecord {
+            title: field(title_col).unwrap_or_default(),
+            year: parse_number(field(year_col), "year", line)?,
+            watched: match field(watched_col).map(|w| w.to_lowercase()) {
+                Some(w) => w == "true" || w == "1" || w == "yes",
+                None => false,
+            },
+            length: parse_number(field(length_col), "length", line)?,
+            series: field(series_col),
+            season: parse_number(field(season_col), "season", line)?,
+            episode_number: parse_number(field(episode_number_col), "episode_number", line)?,
+            last_watched_time: field(last_watched_col),
+            last_progress_time: parse_number(field(last_progress_col), "last_progress_time", line)?,
+            root: field(root_col),
+            location,
+        });
+    }
+
+    Ok((records, columns))
+}
+
+/// The metadata an episode has once `incoming` is imported over `current`
+///
+/// Fields missing from `columns` keep their current values, as do an empty title and a missing
+/// length, which is read from the file itself.
+pub fn merge_record(current: &LibraryRecord, incoming: &LibraryRecord, columns: &ImportColumns) -> LibraryRecord {
+    let pick = |column: &str| columns.has(column);
+    let series = if pick("series") { incoming.series.clone() } else { current.series.clone() };
+    // A season only exists within a series, so it doesn't follow the episode to another one
+    let season = match (pick("season"), series == current.series) {
+        (true, _) => incoming.season,
+        (false, true) => current.season,
+        (false, false) => None,
+    };
+    LibraryRecord {
+        location: current.location.clone(),
+        title: if pick("title") && !incoming.title.is_empty() { incoming.title.clone() } else { current.title.clone() },
+        year: if pick("year") { incoming.year } else { current.year },
+        watched: if pick("watched") { incoming.watched } else { current.watched },
+        length: incoming.length.filter(|_| pick("length")).or(current.length),
+        season: season.filter(|_| series.is_some()),
+        series,
+        episode_number: if pick("episode_number") { incoming.episode_number } else { current.episode_number },
+        last_watched_time: if pick("last_watched_time") {
+            incoming.last_watched_time.clone()
+        } else {
+            current.last_watched_time.clone()
+        },
+        last_progress_time: if pick("last_progress_time") { incoming.last_progress_time } else { current.last_progress_time },
+        root: current.root.clone(),
+    }
+}
+
+/// Describe the differences between the current metadata of an episode and what importing
+/// `incoming` with `columns` would leave it with
+pub fn describe_changes(current: &LibraryRecord, incoming: &LibraryRecord, columns: &ImportColumns) -> Vec<String> {
+    let incoming = merge_record(current, incoming, columns);
+    let mut changes = Vec::new();
+    let mut compare = |field: &str, old: String, new: String| {
+        if old != new {
+            changes.push(format!("{}: '{}' -> '{}'", field, old, new));
+        }
+    };
+
+    compare("title", current.title.clone(), incoming.title.clone());
+    compare("year", optional_to_string(&current.year), optional_to_string(&incoming.year));
+    compare("watched", current.watched.to_string(), incoming.watched.to_string());
+    compare("length", optional_to_string(&current.length), optional_to_string(&incoming.length));
+    compare("series", optional_to_string(&current.series), optional_to_string(&incoming.series));
+    compare("season", optional_to_string(&current.season), optional_to_string(&incoming.season));
+    compare(
+        "episode_number",
+        optional_to_string(&current.episode_number),
+        optional_to_string(&incoming.episode_number),
+    );
+    compare(
+        "last_watched_time",
+        optional_to_string(&current.last_watched_time),
+        optional_to_string(&incoming.last_watched_time),
+    );
+    compare(
+        "last_progress_time",
+        optional_to_string(&current.last_progress_time),
+        optional_to_string(&incoming.last_progress_time),
+    );
+
+    changes
+}
+
+/// Split CSV content into rows of unescaped fields, honoring quoted fields, each with the line it
+/// starts on; a quoted field can hold line breaks, so a row can span several lines
+fn split_csv_rows(content: &str) -> Vec<(usize, Vec<String>)> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+    let mut line = 1;
+    let mut row_start = 1;
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push((row_start, std::mem::take(&mut row)));
+                row_start = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    // Final row without a trailing newline
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push((row_start, row));
+    }
+
+    rows
+}
+
+fn parse_number<T: std::str::FromStr>(value: Option<String>, name: &str, line: usize) -> Result<Option<T>, String> {
+    match value {
+        Some(v) => v
+            .parse::<T>()
+            .map(Some)
+            .map_err(|_| format!("Invalid {} '{}' on line {}", name, v, line)),
+        None => Ok(None),
+    }
+}
+
+fn optional_to_string<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(|v| v.to_string()).unwrap_or_default()
+}
@@ -0,0 +1,285 @@
+//! Editing an episode's details as YAML in the user's own editor
+//!
+//! "edit in $EDITOR" writes the details of the selected episode to a temporary YAML file and
+//! hands the terminal to `$VISUAL` or `$EDITOR` until it exits. A file that can't be read back,
+//! or asks for something the library can't do, is opened again with the problem written at the
+//! top, so nothing typed is lost; saving it unchanged leaves the episode as it was.
+
+use crate::database::{Database, DatabaseError};
+use crate::dto::{AltTitles, EpisodeDetail, Season, Series};
+use crate::terminal;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::hash_map::RandomState;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Start of the lines written above the details when the edited file had a problem
+const ERROR_PREFIX: &str = "# ERROR: ";
+
+/// The details of an episode as they are written to the file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EditableDetails {
+    #[serde(default, deserialize_with = "scalar_string")]
+    pub title: String,
+    #[serde(default)]
+    pub year: Option<u32>,
+    #[serde(default)]
+    pub watched: bool,
+    /// Name of the series; one that doesn't exist yet is created
+    #[serde(default, deserialize_with = "optional_scalar_string")]
+    pub series: Option<String>,
+    #[serde(default)]
+    pub season: Option<usize>,
+    #[serde(default)]
+    pub episode_number: Option<usize>,
+    #[serde(default, deserialize_with = "optional_scalar_string")]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub alt_titles: AltTitles,
+}
+
+impl EditableDetails {
+    pub fn from_detail(details: &EpisodeDetail) -> Self {
+        EditableDetails {
+            title: details.title.clone(),
+            year: details.year.trim().parse().ok(),
+            watched: details.watched == "true",
+            series: details.series.as_ref().map(|series| series.name.clone()),
+            season: details.season.as_ref().map(|season| season.number),
+            episode_number: details.episode_number.trim().parse().ok(),
+            notes: details.notes.clone().filter(|notes| !notes.trim().is_empty()),
+            alt_titles: details.alt_titles.clone(),
+        }
+    }
+
+    /// Problems that stop the details from being saved, checked before anything is written
+    pub fn validate(&self) -> Result<(), String> {
+        if self.title.trim().is_empty() {
+            return Err("title can't be empty".to_string());
+        }
+        if self.year.is_some_and(|year| !(1000..=9999).contains(&year)) {
+            return Err("year must have four digits".to_string());
+        }
+        if self.series.as_deref().is_some_and(|series| series.trim().is_empty()) {
+            return Err("series can't be blank; leave it empty (null) for none".to_string());
+        }
+        if self.season.is_some() && self.series.is_none() {
+            return Err("season needs a series".to_string());
+        }
+        if self.episode_number.is_some() && self.season.is_none() {
+            return Err("episode_number needs a season".to_string());
+        }
+        if self.alt_titles.iter().any(|(language, title)| language.trim().is_empty() || title.trim().is_empty()) {
+            return Err("alt_titles needs a language and a title on each line".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Accept numbers and booleans where text is expected, so `title: 1984` needs no quotes
+fn scalar_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(optional_scalar_string(deserializer)?.unwrap_or_default())
+}
+
+fn optional_scalar_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    match serde_yaml::Value::deserialize(deserializer)? {
+        serde_yaml::Value::Null => Ok(None),
+        serde_yaml::Value::String(text) => Ok(Some(text)),
+        serde_yaml::Value::Number(number) => Ok(Some(number.to_string())),
+        serde_yaml::Value::Bool(value) => Ok(Some(value.to_string())),
+        _ => Err(D::Error::custom("expected text")),
+    }
+}
+
+/// The file given to the editor: comments saying how it works, then the details
+pub fn to_yaml(details: &EditableDetails, file_name: &str) -> String {
+    let mut yaml = format!("# Details of {}\n", file_name);
+    yaml.push_str("# Save and quit to apply the changes; quit without saving to leave the episode as it was.\n");
+    yaml.push_str("# series is created when there is none by that name; leave series, season or episode_number\n");
+    yaml.push_str("# empty for none. alt_titles holds a title per language, e.g. de: \"Die Brücke\"\n");
+    yaml.push_str(&serde_yaml::to_string(details).unwrap_or_default());
+    yaml
+}
+
+/// Read the edited file, ignoring the error lines written above it
+pub fn parse(text: &str) -> Result<EditableDetails, String> {
+    let details: EditableDetails = serde_yaml::from_str(text).map_err(|e| e.to_string())?;
+    details.validate()?;
+    Ok(details)
+}
+
+/// The edited file again, with `error` at the top in place of the last one
+pub fn with_error(text: &str, error: &str) -> String {
+    let mut lines: Vec<&str> = text.lines().skip_while(|line| line.starts_with(ERROR_PREFIX)).collect();
+    let error_lines: Vec<String> = error.lines().map(|line| format!("{}{}", ERROR_PREFIX, line)).collect();
+    for line in error_lines.iter().rev() {
+        lines.insert(0, line);
+    }
+    format!("{}\n", lines.join("\n"))
+}
+
+/// The editor to run: $VISUAL, then $EDITOR, read from `env`
+pub fn editor_command(env: impl Fn(&str) -> Option<String>) -> String {
+    let default = if cfg!(windows) { "notepad" } else { "vi" };
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| env(name))
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Check what saving `edited` needs from the library: a season after the last one needs the one before it
+pub fn check_library(database: &Database, edited: &EditableDetails) -> Result<(), String> {
+    let (Some(name), Some(season)) = (&edited.series, edited.season) else {
+        return Ok(());
+    };
+    let series = find_series(database, name)?;
+    let allowed = match series {
+        Some(series) => database.can_create_season(Some(series.id), season).map_err(|e| e.to_string())?,
+        None => season <= 1,
+    };
+    if allowed {
+        Ok(())
+    } else {
+        Err(format!("{} has no season {} before season {}", name.trim(), season - 1, season))
+    }
+}
+
+/// The series called `name`, ignoring case
+fn find_series(database: &Database, name: &str) -> Result<Option<Series>, String> {
+    let name = name.trim().to_lowercase();
+    let series = database.get_all_series().map_err(|e| e.to_string())?;
+    Ok(series.into_iter().find(|series| series.name.trim().to_lowercase() == name))
+}
+
+/// Save the changes from `original` to `edited`, returning the names of the changed fields
+pub fn apply(database: &Database, episode_id: usize, original: &EditableDetails, edited: &EditableDetails) -> Result<Vec<&'static str>, String> {
+    let db_error = |e: DatabaseError| e.to_string();
+    let mut details = database.get_episode_detail(episode_id).map_err(db_error)?;
+
+    let edited_series = edited.series.as_deref().map(str::trim);
+    let series_changed = edited_series != original.series.as_deref().map(str::trim);
+    // Looked up first: the writes below hold the connection until they are all saved
+    let existing_series = match edited_series {
+        Some(name) if series_changed => find_series(database, name)?,
+        _ => None,
+    };
+    database
+        .write_together(|writes| {
+            if series_changed {
+                details.series = match (edited_series, existing_series) {
+                    (Some(_), Some(series)) => Some(series),
+                    (Some(name), None) => Some(Series { id: writes.create_series(name)?, name: name.to_string() }),
+                    (None, _) => None,
+                };
+            }
+            details.title = edited.title.trim().to_string();
+            details.year = edited.year.map(|year| year.to_string()).unwrap_or_default();
+            details.episode_number = edited.episode_number.map(|number| number.to_string()).unwrap_or_default();
+            details.season = match (&details.series, edited.season) {
+                (Some(series), Some(number)) => Some(Season { id: writes.find_or_create_season(series.id, number)?, number }),
+                _ => None,
+            };
+            writes.update_episode_detail(episode_id, &details)?;
+            if edited.watched != original.watched {
+                writes.toggle_watched_status(episode_id)?;
+            }
+            if edited.notes != original.notes {
+                writes.update_episode_notes(episode_id, edited.notes.as_deref().unwrap_or_default())?;
+            }
+            if edited.alt_titles != original.alt_titles {
+                writes.update_episode_alt_titles(episode_id, &edited.alt_titles)?;
+            }
+            Ok(())
+        })
+        .map_err(db_error)?;
+
+    let changes = [
+        ("title", edited.title.trim() != original.title.trim()),
+        ("year", edited.year != original.year),
+        ("watched", edited.watched != original.watched),
+        ("series", series_changed),
+        ("season", edited.season != original.season),
+        ("episode number", edited.episode_number != original.episode_number),
+        ("notes", edited.notes != original.notes),
+        ("alternate titles", edited.alt_titles != original.alt_titles),
+    ];
+    Ok(changes.iter().filter(|(_, changed)| *changed).map(|(name, _)| *name).collect())
+}
+
+/// Edit the details of the episode in the editor, returning the names of the changed fields;
+/// none when the file was left unchanged
+pub fn edit_episode(database: &Database, episode_id: usize, location: &Path, mouse: bool) -> Result<Vec<&'static str>, String> {
+    let details = database.get_episode_detail(episode_id).map_err(|e| e.to_string())?;
+    let original = EditableDetails::from_detail(&details);
+    let file_name = location.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let editor = editor_command(|name| std::env::var(name).ok());
+
+    let path = create_temp_file(episode_id)?;
+    let edited = edit_until_valid(database, &editor, &path, to_yaml(&original, &file_name), mouse);
+    let _ = fs::remove_file(&path);
+    match edited? {
+        Some(edited) => apply(database, episode_id, &original, &edited),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Create a file for the details of `episode_id` in the temporary folder, readable only by the user
+/// Its name ends in a random suffix and it must not exist yet, so nothing can have put a file or
+/// link there first
+fn create_temp_file(episode_id: usize) -> Result<PathBuf, String> {
+    for _ in 0..16 {
+        let suffix = RandomState::new().build_hasher().finish();
+        let path = std::env::temp_dir().join(format!("movies-episode-{}-{:016x}.yaml", episode_id, suffix));
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        match options.open(&path) {
+            Ok(_) => return Ok(path),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Couldn't create {}: {}", path.display(), e)),
+        }
+    }
+    Err("Couldn't create a temporary file for the editor".to_string())
+}
+
+/// Open `text` in the editor until what is saved can be read back and saved to the library,
+/// returning None when it was saved unchanged
+fn edit_until_valid(database: &Database, editor: &str, path: &Path, mut text: String, mouse: bool) -> Result<Option<EditableDetails>, String> {
+    loop {
+        let edited_text = run_editor(editor, path, &text, mouse)?;
+        if edited_text == text {
+            return Ok(None);
+        }
+        match parse(&edited_text).and_then(|edited| check_library(database, &edited).map(|_| edited)) {
+            Ok(edited) => return Ok(Some(edited)),
+            Err(e) => text = with_error(&edited_text, &e),
+        }
+    }
+}
+
+/// Write `text` to `path`, run the editor on it with the terminal handed over, and read it back
+fn run_editor(editor: &str, path: &Path, text: &str, mouse: bool) -> Result<String, String> {
+    fs::write(path, text).map_err(|e| format!("Couldn't write {}: {}", path.display(), e))?;
+    // The command may carry options, as in "code --wait"
+    let mut parts = editor.split_whitespace();
+    let mut command = Command::new(parts.next().unwrap_or("vi"));
+    command.args(parts).arg(path);
+
+    terminal::restore_terminal().map_err(|e| e.to_string())?;
+    let status = command.status();
+    terminal::resume_terminal(mouse).map_err(|e| e.to_string())?;
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => return Err(format!("{} exited with {}; nothing was changed", editor, status)),
+        Err(e) => return Err(format!("Couldn't start {}: {}; set $EDITOR to your editor", editor, e)),
+    }
+    fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))
+}
@@ -0,0 +1,65 @@
+//! Sample and trailer files that come along with downloaded videos
+//!
+//! A file counts as one when its name starts or ends with "sample" or ends with "trailer",
+//! when it is in a folder named for them, or when its video is no longer than
+//! `sample_max_seconds`. Depending on `sample_files`, a rescan imports them like any other
+//! video, imports them as extras kept out of the library's views, or leaves them out.
+
+use std::path::Path;
+
+/// What a rescan does with sample and trailer files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SampleFiles {
+    /// Import them like any other video
+    Import,
+    /// Import them as extras, which are only listed by Show Extras
+    #[default]
+    Flag,
+    /// Leave them out of the library and list them as skipped
+    Skip,
+}
+
+impl SampleFiles {
+    /// Parse a `sample_files` config value such as "skip", None for an unknown one
+    pub fn from_config(value: &str) -> Option<SampleFiles> {
+        match value.trim().to_lowercase().as_str() {
+            "import" => Some(SampleFiles::Import),
+            "flag" => Some(SampleFiles::Flag),
+            "skip" => Some(SampleFiles::Skip),
+            _ => None,
+        }
+    }
+}
+
+/// Folder names that only hold samples or trailers
+const SAMPLE_FOLDERS: [&str; 4] = ["sample", "samples", "trailer", "trailers"];
+
+/// True when the name of the file at `path`, or of the folder holding it, marks it as a sample or trailer
+///
+/// Only the first and last words of the file name count, so "Movie-sample.mkv" and
+/// "Movie (2020) - Trailer.mkv" are samples but "Trailer Park Boys S01E01.mkv" isn't.
+pub fn is_sample_name(path: &Path) -> bool {
+    let in_sample_folder = path
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| SAMPLE_FOLDERS.contains(&name.trim().to_lowercase().as_str()));
+    if in_sample_folder {
+        return true;
+    }
+    let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return false;
+    };
+    let stem = stem.to_lowercase();
+    let words: Vec<&str> = stem.split(|c: char| !c.is_alphanumeric()).filter(|word| !word.is_empty()).collect();
+    match (words.first(), words.last()) {
+        (Some(first), Some(last)) => *first == "sample" || *last == "sample" || *last == "trailer",
+        _ => false,
+    }
+}
+
+/// True when a video of `seconds` is short enough to be a sample, with 0 for either meaning
+/// the length is unknown or the check is off
+pub fn is_sample_length(seconds: u64, max_seconds: u64) -> bool {
+    seconds > 0 && seconds <= max_seconds
+}
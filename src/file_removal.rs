@@ -0,0 +1,67 @@
+//! Removing an episode's video files from disk
+//!
+//! Files go to the trash (the XDG trash on Linux, the Recycle Bin or the macOS Trash), so a
+//! mistake can be undone from there. A file that can't be moved to a trash, such as one on a
+//! drive without a trash folder, is deleted for good, which the confirmation dialog warns about.
+
+use crate::logger;
+use crate::path_resolver::{self, PathResolver};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How many files of an episode went to the trash and how many were deleted for good
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Removal {
+    pub trashed: usize,
+    pub deleted: usize,
+}
+
+impl Removal {
+    /// Status line for the removed files of `name`
+    pub fn summary(&self, name: &str) -> String {
+        match (self.trashed, self.deleted) {
+            (0, 0) => format!("Removed '{}' from the library; its file was already gone", name),
+            (_, 0) => format!("Moved '{}' to the trash", name),
+            (0, _) => format!("Deleted '{}' for good; there was no trash to move it to", name),
+            (trashed, deleted) => format!("Moved {} files of '{}' to the trash and deleted {} for good", trashed, name, deleted),
+        }
+    }
+}
+
+/// Move the file at `path` to the trash, or delete it when it can't be, returning true if it went to the trash
+pub fn remove_file(path: &Path) -> std::io::Result<bool> {
+    match trash::delete(path) {
+        Ok(()) => Ok(true),
+        Err(e) => {
+            logger::log_warn(&format!("Couldn't move {} to the trash, deleting it: {}", path.display(), e));
+            fs::remove_file(path).map(|()| false)
+        }
+    }
+}
+
+/// Remove the files of an episode, given as the absolute paths of its versions
+///
+/// Every path has to be under a folder of the library that is there right now, so nothing is
+/// removed from an unplugged drive's mount point or outside the library. Files already gone are
+/// passed over. Stops at the first file that can't be removed.
+pub fn remove_episode_files(paths: &[PathBuf], resolver: &PathResolver) -> Result<Removal, String> {
+    for path in paths {
+        match resolver.root_of(path) {
+            None => return Err(format!("Not in a folder of the library: {}", path.display())),
+            Some(folder) if path_resolver::is_offline(folder) => {
+                return Err(format!("Library folder offline: {}", folder.display()));
+            }
+            Some(_) => {}
+        }
+    }
+    let mut removal = Removal::default();
+    for path in paths.iter().filter(|path| path.exists()) {
+        match remove_file(path) {
+            Ok(true) => removal.trashed += 1,
+            Ok(false) => removal.deleted += 1,
+            Err(e) => return Err(format!("Failed to remove {}: {}", path.display(), e)),
+        }
+        logger::log_info(&format!("Removed video file {}", path.display()));
+    }
+    Ok(removal)
+}
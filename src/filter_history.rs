@@ -0,0 +1,108 @@
+use crate::logger;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File name of the filter history in the data directory
+pub const FILTER_HISTORY_FILE_NAME: &str = "filter_history.json";
+
+/// Most filters kept in the history; older ones are forgotten
+pub const MAX_FILTER_HISTORY: usize = 20;
+
+/// Filters kept with Enter, recalled with Up/Down while the filter is typed
+///
+/// Recalling starts from the newest filter. What was typed before the first Up is kept,
+/// and Down past the newest filter brings it back.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FilterHistory {
+    /// Oldest first
+    #[serde(default)]
+    filters: Vec<String>,
+    /// Index of the filter being recalled, None while typing
+    #[serde(skip)]
+    recalled: Option<usize>,
+    /// Filter typed before recalling started
+    #[serde(skip)]
+    draft: String,
+    /// Where the history is saved when a filter is added, None to keep it in memory
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl FilterHistory {
+    /// Load the history from `path`, starting empty if the file is missing or unreadable,
+    /// and save it there as filters are added
+    pub fn load(path: PathBuf) -> Self {
+        let loaded: FilterHistory = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        FilterHistory { path: Some(path), ..loaded }
+    }
+
+    /// Add a kept filter as the newest, moving it up if it was already there
+    pub fn add(&mut self, filter: &str) {
+        self.stop_recalling();
+        let filter = filter.trim();
+        if filter.is_empty() {
+            return;
+        }
+        self.filters.retain(|kept| kept != filter);
+        self.filters.push(filter.to_string());
+        if self.filters.len() > MAX_FILTER_HISTORY {
+            self.filters.remove(0);
+        }
+        if let Some(path) = &self.path {
+            if let Err(e) = save_filter_history(self, path) {
+                logger::log_warn(&format!("Failed to save the filter history: {}", e));
+            }
+        }
+    }
+
+    /// The filter before the one recalled, starting from the newest; `typed` is kept to come back to
+    /// None when there is nothing older
+    pub fn older(&mut self, typed: &str) -> Option<&str> {
+        let index = match self.recalled {
+            None => {
+                self.draft = typed.to_string();
+                self.filters.len().checked_sub(1)?
+            }
+            Some(index) => index.checked_sub(1)?,
+        };
+        self.recalled = Some(index);
+        Some(&self.filters[index])
+    }
+
+    /// The filter after the one recalled, or what was typed before recalling past the newest
+    /// None when not recalling
+    pub fn newer(&mut self) -> Option<&str> {
+        let index = self.recalled?;
+        if index + 1 < self.filters.len() {
+            self.recalled = Some(index + 1);
+            Some(&self.filters[index + 1])
+        } else {
+            self.recalled = None;
+            Some(&self.draft)
+        }
+    }
+
+    /// Go back to typing; the next Up starts again from the newest filter
+    pub fn stop_recalling(&mut self) {
+        self.recalled = None;
+        self.draft.clear();
+    }
+}
+
+/// Default location of the filter history
+pub fn default_filter_history_path() -> Option<PathBuf> {
+    crate::paths::data_dir().map(|dir| dir.join(FILTER_HISTORY_FILE_NAME))
+}
+
+/// Write the history to disk
+pub fn save_filter_history(history: &FilterHistory, path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(history).map_err(io::Error::other)?;
+    std::fs::write(path, json)
+}
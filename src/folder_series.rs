@@ -0,0 +1,62 @@
+use std::path::{Component, Path};
+
+/// An episode that isn't in a series yet, with the series and season its folders suggest
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesProposal {
+    pub episode_id: usize,
+    /// Location as stored in the library, relative to the folder the episode was found in
+    pub location: String,
+    pub series: String,
+    pub season: usize,
+}
+
+/// Series name and season number taken from the folders of `location`, as in `Show Name/Season 02/file.mkv`
+///
+/// The file has to be in a season folder ("Season 2", "Series 2", "S02", or "Specials" for
+/// season 0), and the folder holding that one names the series. None otherwise.
+pub fn infer_series(location: &str) -> Option<(String, usize)> {
+    let names: Vec<&str> = Path::new(location)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+    let [.., series, season, _file] = names.as_slice() else {
+        return None;
+    };
+    let season = season_number(season)?;
+    let series = series.trim();
+    if series.is_empty() {
+        return None;
+    }
+    Some((series.to_string(), season))
+}
+
+/// Season number of a folder named like a season, ignoring case
+fn season_number(folder: &str) -> Option<usize> {
+    let folder = folder.trim().to_lowercase();
+    if folder == "specials" {
+        return Some(0);
+    }
+    let digits = ["season", "series", "s"]
+        .iter()
+        .find_map(|prefix| folder.strip_prefix(prefix))?
+        .trim_start_matches([' ', '_', '-', '.']);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Proposals for the episodes in `unassigned`, given as id and stored location,
+/// leaving out those whose folders don't look like a series and season
+pub fn series_proposals(unassigned: Vec<(usize, String)>) -> Vec<SeriesProposal> {
+    unassigned
+        .into_iter()
+        .filter_map(|(episode_id, location)| {
+            let (series, season) = infer_series(&location)?;
+            Some(SeriesProposal { episode_id, location, series, season })
+        })
+        .collect()
+}
@@ -1,32 +1,75 @@
-use crossterm::event::{self, KeyCode};
+use crossterm::event::{self, KeyCode, MouseButton, MouseEvent, MouseEventKind};
 use std::collections::HashSet;
 use std::io;
-use std::path::Path;
-use std::sync::mpsc::Sender;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::thread;
-use walkdir::WalkDir;
+use std::time::Instant;
 
+use crate::action::{self, Action, Count};
+use crate::app_state::{AppContext, AppState};
+use crate::archive;
+use crate::clipboard;
+use crate::components::{Browser, ContextMenu, GridBrowser, ThemePicker};
 use crate::config::Config;
-use crate::database;
+use crate::custom_commands;
+use crate::database::{Database, DatabaseError};
+use crate::digest::DigestTarget;
 use crate::display;
 use crate::dto::EpisodeDetail;
-use crate::dto::Series;
+use crate::duplicates::{self, DuplicateGroup};
 use crate::episode_field::EpisodeField;
+use crate::export::ExportFormat;
+use crate::external_editor;
+use crate::extras::{self, SampleFiles};
+use crate::folder_series;
+use crate::hooks::{self, HookEvent};
+use crate::input;
+use crate::location;
 use crate::logger;
-use crate::menu::{MenuAction, MenuItem};
+use crate::keymap;
+use crate::menu::{self, confirmation_dialog, is_batch_action, MenuAction, MenuContext, MenuItem, PendingConfirmation};
+use crate::notifications::Notifications;
+use crate::now_playing::{self, NowPlaying};
+use crate::path_completion;
 use crate::path_resolver::PathResolver;
+use crate::remote::RemoteCommand;
 use crate::player_plugin::create_player_plugin;
-use crate::util::{Entry, Mode, ViewContext};
+use crate::relocate::{self, RelocationCheck};
+use crate::rename;
+use crate::tutorial::Tutorial;
+use crate::scan::{HardLinks, ScanReport};
+use crate::tabs::Tab;
+use crate::text_input::TextInput;
+use crate::theme::{self, Theme};
+use crate::util::{AppEvent, Entry, EntryKey, Mode, ViewContext};
+use crate::version::preferred_version_index;
 use crate::video_metadata;
+use crate::web_video::{self, WebVideo};
 use display::get_max_displayed_items_with_header_height;
 
 // Find the index of the first unwatched entry in a list
-pub fn find_first_unwatched_index(entries: &[Entry]) -> Option<usize> {
+/// Log a database failure and show it on the status line instead of aborting
+fn report_database_error(context: &str, error: &DatabaseError, notifications: &mut Notifications) {
+    logger::log_error(&format!("{}: {}", context, error));
+    notifications.error(format!("{}: {}", context, error));
+}
+
+/// Reload the entries for the current view, keeping the previous entries on failure
+fn reload_entries(database: &Database, view_context: &ViewContext, entries: &mut Vec<Entry>, notifications: &mut Notifications) {
+    match database.get_entries_for_view(view_context) {
+        Ok(loaded) => *entries = loaded,
+        Err(e) => report_database_error("Failed to load entries", &e, notifications),
+    }
+}
+
+pub fn find_first_unwatched_index(database: &Database, entries: &[Entry]) -> Option<usize> {
+    let counts = database.get_category_counts().unwrap_or_default();
     for (index, entry) in entries.iter().enumerate() {
         match entry {
             Entry::Episode { episode_id, .. } => {
                 // Check if episode is unwatched
-                if let Ok(details) = database::get_episode_detail(*episode_id) {
+                if let Ok(details) = database.get_episode_detail(*episode_id) {
                     if details.watched != "true" {
                         return Some(index);
                     }
@@ -34,18 +77,14 @@ pub fn find_first_unwatched_index(entries: &[Entry]) -> Option<usize> {
             }
             Entry::Series { series_id, .. } => {
                 // Check if series has any unwatched episodes
-                if let Ok((_, unwatched)) = database::get_series_episode_counts(*series_id) {
-                    if unwatched > 0 {
-                        return Some(index);
-                    }
+                if counts.get(&EntryKey::Series(*series_id)).is_some_and(|c| c.unwatched > 0) {
+                    return Some(index);
                 }
             }
             Entry::Season { season_id, .. } => {
                 // Check if season has any unwatched episodes
-                if let Ok((_, unwatched)) = database::get_season_episode_counts(*season_id) {
-                    if unwatched > 0 {
-                        return Some(index);
-                    }
+                if counts.get(&EntryKey::Season(*season_id)).is_some_and(|c| c.unwatched > 0) {
+                    return Some(index);
                 }
             }
         }
@@ -55,22 +94,28 @@ pub fn find_first_unwatched_index(entries: &[Entry]) -> Option<usize> {
 
 pub fn handle_entry_mode(
     code: KeyCode,
-    entry_path: &mut String,
-    entries: &mut Vec<Entry>,
-    filtered_entries: &mut Vec<Entry>,
-    mode: &mut Mode,
-    redraw: &mut bool,
+    state: &mut AppState,
     config: &mut Config,
     config_path: &std::path::PathBuf,
+    database: &mut Option<Arc<Database>>,
     resolver: &mut Option<PathResolver>,
-    status_message: &mut String,
 ) {
+    let AppState {
+        ref mut entry_path,
+        ref mut entry_completions,
+        ref mut entries,
+        ref mut filtered_entries,
+        ref mut mode,
+        ref mut redraw,
+        ref mut notifications,
+        ..
+    } = *state;
     match code {
         KeyCode::Enter => {
             // Validate the directory exists
             let path = Path::new(&entry_path);
             if !path.exists() {
-                eprintln!("Error: Directory does not exist: {}", entry_path);
+                notifications.error(format!("Directory does not exist: {}", entry_path));
                 *redraw = true;
                 return;
             }
@@ -82,32 +127,27 @@ pub fn handle_entry_mode(
             let db_path = canonical_path.join("videos.sqlite");
             let db_exists = db_path.exists();
             
-            // Set status message based on whether database exists
-            if db_exists {
-                *status_message = format!("Connected to existing database at {}", db_path.display());
-            } else {
-                *status_message = "Creating new database...".to_string();
-            }
-            *redraw = true;
-            
-            // Initialize database (creates if doesn't exist, opens if exists)
-            if let Err(e) = database::initialize_database(&db_path) {
-                logger::log_error(&format!("Failed to initialize database at {}: {}", db_path.display(), e));
-                eprintln!("\nError: Failed to initialize database: {}", e);
-                
-                // Check for common error types and provide specific guidance
-                let error_str = e.to_string().to_lowercase();
-                if error_str.contains("permission") || error_str.contains("access") {
-                    eprintln!("Permission denied. Please ensure you have write permissions to this directory.");
-                } else if error_str.contains("no space") || error_str.contains("disk full") {
-                    eprintln!("Insufficient disk space. Please free up space and try again.");
-                } else {
-                    eprintln!("Please check the error details above and try again.");
+            // Open the database (creates if doesn't exist, opens if exists)
+            let opened = match Database::open(&db_path) {
+                Ok(opened) => Arc::new(opened),
+                Err(e) => {
+                    logger::log_error(&format!("Failed to initialize database at {}: {}", db_path.display(), e));
+                    
+                    // Check for common error types and provide specific guidance
+                    let error_str = e.to_string().to_lowercase();
+                    let hint = if error_str.contains("permission") || error_str.contains("access") {
+                        "Please ensure you have write permissions to this directory."
+                    } else if error_str.contains("no space") || error_str.contains("disk full") {
+                        "Please free up space and try again."
+                    } else {
+                        "Please check the log for details."
+                    };
+                    notifications.error(format!("Failed to initialize database: {}. {}", e, hint));
+                    
+                    *redraw = true;
+                    return;
                 }
-                
-                *redraw = true;
-                return;
-            }
+            };
             
             // Update config with db_location and save to file
             config.set_database_path(db_path.clone());
@@ -115,33 +155,29 @@ pub fn handle_entry_mode(
             
             // Create PathResolver from database path
             match PathResolver::from_database_path(&db_path) {
-                Ok(new_resolver) => {
+                Ok(mut new_resolver) => {
+                    opened.add_library_roots(&mut new_resolver, &config.library_roots);
+                    *database = Some(Arc::clone(&opened));
                     *resolver = Some(new_resolver);
                     
-                    // Set scanning status
-                    *status_message = format!("Scanning {}...", canonical_path.display());
-                    *redraw = true;
-                    
                     // Perform scan of the directory
-                    let new_entries: Vec<_> = WalkDir::new(&canonical_path)
+                    let scan_rules = config.scan_rules_for(&canonical_path);
+                    let mut hard_links = HardLinks::default();
+                    let (new_entries, excluded): (Vec<_>, Vec<_>) = scan_rules
+                        .walk(&canonical_path)
                         .into_iter()
                         .filter_map(|e| e.ok())
                         .filter(|e| e.file_type().is_file())
-                        .filter(|e| {
-                            e.path()
-                                .extension()
-                                .and_then(|ext| ext.to_str())
-                                .is_some_and(|ext| {
-                                    config.video_extensions.contains(&ext.to_lowercase())
-                                })
-                        })
+                        .filter(|e| scan_rules.accepts(e.path()))
+                        .filter(|e| !scan_rules.dedupe_hardlinks || hard_links.earlier_link(e.path()).is_none())
+                        .filter(|e| scan_rules.samples != SampleFiles::Skip || !extras::is_sample_name(e.path()))
                         .map(|e| e.into_path())
-                        .collect();
+                        .partition(|path| !scan_rules.excludes(&canonical_path, path));
                     
-                    let mut imported_count = 0;
+                    let mut imported_files = Vec::new();
                     
                     for entry in &new_entries {
-                        let location = entry.to_string_lossy().to_string();
+                        let location = location::encode(entry);
                         let name = entry
                             .file_name()
                             .unwrap_or_default()
@@ -150,57 +186,64 @@ pub fn handle_entry_mode(
 
                         // Use import_episode_relative with error handling for files outside root
                         if let Some(ref res) = resolver {
-                            match database::import_episode_relative(&location, &name, res) {
-                                Ok(true) => imported_count += 1,  // Only count if actually inserted
+                            match opened.import_episode_relative(&location, &name, res) {
+                                Ok(true) => {
+                                    // Only count if actually inserted
+                                    imported_files.push(entry.clone());
+                                    if scan_rules.samples == SampleFiles::Flag && extras::is_sample_name(entry) {
+                                        opened.set_extra_location(entry, true, res).ok();
+                                    }
+                                }
                                 Ok(false) => {},  // Already exists, don't count
                                 Err(e) => {
-                                    eprintln!("Warning: Skipping file outside configured root directory: {} - {}", location, e);
+                                    logger::log_warn(&format!("Skipping file outside configured root directory: {} - {}", location, e));
                                 }
                             }
                         }
                     }
                     
+                    let imported_count = imported_files.len();
+                    if let Some(ref res) = resolver {
+                        if let Err(e) = hooks::fire_for_files(&opened, HookEvent::Import, &imported_files, res) {
+                            notifications.warn(e);
+                        }
+                    }
+
                     // Update status after scan
                     if db_exists {
                         if imported_count > 0 {
-                            *status_message = format!("Connected to existing database. Found {} new videos", imported_count);
+                            notifications.info(format!("Connected to existing database. Found {} new videos", imported_count));
                         } else {
-                            *status_message = format!("Connected to existing database at {}", db_path.display());
+                            notifications.info(format!("Connected to existing database at {}", db_path.display()));
                         }
                     } else {
-                        *status_message = format!("Created new database and imported {} videos", imported_count);
+                        notifications.info(format!("Created new database and imported {} videos", imported_count));
+                    }
+                    if !excluded.is_empty() {
+                        notifications.info(format!("Left out {} excluded videos", excluded.len()));
                     }
                     *redraw = true;
 
                     // Load entries and switch to Browse mode
-                    *entries = database::get_entries().expect("Failed to get entries");
+                    reload_entries(&opened, &ViewContext::TopLevel, entries, notifications);
                     *filtered_entries = entries.clone();
                     *mode = Mode::Browse;
                     *redraw = true;
                 }
                 Err(e) => {
-                    eprintln!("\nError: Failed to create PathResolver: {}", e);
-                    
-                    match e {
-                        crate::path_resolver::PathResolverError::DatabaseNotFound(path) => {
-                            eprintln!("Database not found at {}", path.display());
-                        }
-                        crate::path_resolver::PathResolverError::InvalidDatabasePath(path) => {
-                            eprintln!("Invalid database path: {}", path.display());
-                            eprintln!("The database path must have a valid parent directory.");
-                        }
-                        crate::path_resolver::PathResolverError::IoError(io_err) => {
-                            eprintln!("IO error: {}", io_err);
-                            
-                            let error_str = io_err.to_string().to_lowercase();
-                            if error_str.contains("permission") || error_str.contains("access") {
-                                eprintln!("Permission denied. Please ensure you have read permissions.");
-                            }
+                    logger::log_error(&format!("Failed to create PathResolver for {}: {}", db_path.display(), e));
+                    let hint = match &e {
+                        crate::path_resolver::PathResolverError::InvalidDatabasePath(_) => {
+                            " The database path must have a valid parent directory."
                         }
-                        _ => {
-                            eprintln!("Please check the error details above and try again.");
+                        crate::path_resolver::PathResolverError::IoError(io_err)
+                            if io_err.to_string().to_lowercase().contains("permission") =>
+                        {
+                            " Please ensure you have read permissions."
                         }
-                    }
+                        _ => "",
+                    };
+                    notifications.error(format!("Failed to open the library: {}.{}", e, hint));
                     
                     *redraw = true;
                 }
@@ -208,19 +251,87 @@ pub fn handle_entry_mode(
         }
         KeyCode::Esc => {
             // reload entries from the database (if database is initialized)
-            if resolver.is_some() {
-                *entries = database::get_entries().expect("Failed to get entries");
+            if let Some(database) = database {
+                reload_entries(database, &ViewContext::TopLevel, entries, notifications);
                 *filtered_entries = entries.clone();
             }
             *mode = Mode::Browse;
             *redraw = true;
         }
+        KeyCode::Tab => {
+            complete_entry_path(entry_path, entry_completions);
+            *redraw = true;
+        }
+        KeyCode::Backspace => {
+            entry_path.pop();
+            entry_completions.clear();
+            *redraw = true;
+        }
+        KeyCode::Char(c) => {
+            entry_path.push(c);
+            entry_completions.clear();
+            *redraw = true;
+        }
+        _ => (),
+    }
+}
+
+/// Tab in Entry mode: complete the typed folder, listing the matches when there is more than one
+fn complete_entry_path(entry_path: &mut String, entry_completions: &mut Vec<String>) {
+    let completion = path_completion::complete(entry_path);
+    *entry_path = completion.path;
+    *entry_completions = completion.candidates;
+}
+
+// Handle Entry mode opened by Scan Folder - user types a folder of the library to scan by itself
+pub fn handle_scan_folder_entry(code: KeyCode, state: &mut AppState, context: &AppContext) {
+    let AppContext { config, database, resolver, tx, .. } = *context;
+    let AppState {
+        ref mut entry_path,
+        ref mut entry_completions,
+        ref mut scan_folder_entry,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
+    match code {
+        KeyCode::Enter => {
+            let folder = match Path::new(entry_path.trim()).canonicalize() {
+                Ok(folder) if folder.is_dir() => folder,
+                _ => {
+                    notifications.error(format!("Directory does not exist: {}", entry_path));
+                    *redraw = true;
+                    return;
+                }
+            };
+            let Some(library_folder) = resolver.root_of(&folder) else {
+                notifications.error(format!("Not a folder of the library: {}", folder.display()));
+                *redraw = true;
+                return;
+            };
+            let scan_rules = config.scan_rules_for(library_folder);
+            if !crate::scan::spawn_folder_rescan(folder, scan_rules, Arc::clone(database), resolver.clone(), tx.clone()) {
+                notifications.warn("A rescan is already running");
+            }
+            *scan_folder_entry = false;
+            state.close_modal();
+        }
+        KeyCode::Esc => {
+            *scan_folder_entry = false;
+            state.close_modal();
+        }
+        KeyCode::Tab => {
+            complete_entry_path(entry_path, entry_completions);
+            *redraw = true;
+        }
         KeyCode::Backspace => {
             entry_path.pop();
+            entry_completions.clear();
             *redraw = true;
         }
         KeyCode::Char(c) => {
             entry_path.push(c);
+            entry_completions.clear();
             *redraw = true;
         }
         _ => (),
@@ -252,23 +363,56 @@ fn update_dirty_state(
     }
 }
 
-pub fn handle_edit_mode(
-    code: KeyCode,
-    modifiers: event::KeyModifiers,
-    current_item: usize,
-    filtered_entries: &mut Vec<Entry>,
-    edit_details: &mut EpisodeDetail,
+/// Put a field back to its value from before editing
+fn revert_field(
+    field: EpisodeField,
+    details: &mut EpisodeDetail,
+    original_details: &EpisodeDetail,
     season_number: &mut Option<usize>,
-    entries: &mut Vec<Entry>,
-    mode: &mut Mode,
-    edit_field: &mut EpisodeField,
-    edit_cursor_pos: &mut usize,
-    redraw: &mut bool,
-    view_context: &ViewContext,
-    last_action: &mut Option<crate::util::LastAction>,
-    original_edit_details: &EpisodeDetail,
-    dirty_fields: &mut HashSet<EpisodeField>,
 ) {
+    if field == EpisodeField::Season {
+        *season_number = original_details.season.as_ref().map(|season| season.number);
+        return;
+    }
+    let mut original_details = original_details.clone();
+    if let (Some(value), Some(original)) = (editable_value(field, details), editable_value(field, &mut original_details)) {
+        *value = std::mem::take(original);
+    }
+}
+
+/// The text of a field that is typed into, None for fields changed with +/- or not editable
+fn editable_value(field: EpisodeField, details: &mut EpisodeDetail) -> Option<&mut String> {
+    match field {
+        EpisodeField::Title => Some(&mut details.title),
+        EpisodeField::Year => Some(&mut details.year),
+        EpisodeField::Watched => Some(&mut details.watched),
+        EpisodeField::Length => Some(&mut details.length),
+        EpisodeField::EpisodeNumber => Some(&mut details.episode_number),
+        _ => None,
+    }
+}
+
+pub fn handle_edit_mode(code: KeyCode, modifiers: event::KeyModifiers, state: &mut AppState, context: &AppContext) {
+    let database = context.database;
+    let AppState {
+        current_item,
+        ref mut filtered_entries,
+        ref mut edit_details,
+        ref mut season_number,
+        ref mut entries,
+        ref mut mode,
+        ref mut edit_field,
+        ref mut edit_cursor_pos,
+        ref mut redraw,
+        ref view_context,
+        ref mut last_action,
+        ref original_edit_details,
+        ref mut dirty_fields,
+        ref mut notifications,
+        ..
+    } = *state;
+    // Edit mode is entered with the details it started from; nothing is dirty without them
+    let original_edit_details = &original_edit_details.clone().unwrap_or_default();
     match code {
         KeyCode::F(2) => {
             logger::log_debug(&format!(
@@ -282,9 +426,9 @@ pub fn handle_edit_mode(
             };
             
             // Save episode details
-            if let Err(e) = database::update_episode_detail(episode_id, edit_details) {
-                logger::log_error(&format!("Failed to save episode details for episode {}: {}", episode_id, e));
-                eprintln!("Error: Failed to save episode details: {}", e);
+            if let Err(e) = database.update_episode_detail(episode_id, edit_details) {
+                report_database_error(&format!("Failed to save episode {}", episode_id), &e, notifications);
+                *redraw = true;
                 return;
             }
             
@@ -300,8 +444,14 @@ pub fn handle_edit_mode(
             // Handle season creation if season_number is set
             if let Some(series) = &edit_details.series {
                 if let Some(season_num) = season_number {
-                    let season_id = database::create_season_and_assign(series.id, *season_num, episode_id)
-                        .expect("Failed to create season and assign");
+                    let season_id = match database.create_season_and_assign(series.id, *season_num, episode_id) {
+                        Ok(season_id) => season_id,
+                        Err(e) => {
+                            report_database_error("Failed to assign season", &e, notifications);
+                            *redraw = true;
+                            return;
+                        }
+                    };
                     
                     // Log season assignment
                     logger::log_info(&format!("Assigned episode {} to series '{}' season {}", 
@@ -318,23 +468,7 @@ pub fn handle_edit_mode(
             }
             
             // Reload entries based on current view context
-            *entries = match view_context {
-                ViewContext::TopLevel => {
-                    database::get_entries().unwrap_or_else(|_| {
-                        database::get_entries().expect("Failed to get entries")
-                    })
-                }
-                ViewContext::Series { series_id, .. } => {
-                    database::get_entries_for_series(*series_id).unwrap_or_else(|_| {
-                        database::get_entries().expect("Failed to get entries")
-                    })
-                }
-                ViewContext::Season { season_id, .. } => {
-                    database::get_entries_for_season(*season_id).unwrap_or_else(|_| {
-                        database::get_entries().expect("Failed to get entries")
-                    })
-                }
-            };
+            reload_entries(database, view_context, entries, notifications);
             // Clear dirty fields when saving
             dirty_fields.clear();
             // let's set edit_field back to the first field
@@ -388,106 +522,47 @@ pub fn handle_edit_mode(
             *edit_cursor_pos = 0;
             *redraw = true;
         }
+        // The cursor moves over the field as shown, which read-only fields have too
         KeyCode::Left if modifiers.contains(event::KeyModifiers::CONTROL) => {
             // jump back in the current field by words (separated by spaces)
-            let field = edit_field.get_field_value(edit_details);
-            if *edit_cursor_pos > 0 {
-                let mut i = *edit_cursor_pos - 1;
-                while i > 0 && field.chars().nth(i - 1).unwrap() == ' ' {
-                    i -= 1;
-                }
-                while i > 0 && field.chars().nth(i - 1).unwrap() != ' ' {
-                    i -= 1;
-                }
-                *edit_cursor_pos = i;
-                *redraw = true;
-            }
+            TextInput::new(&mut edit_field.get_field_value(edit_details), edit_cursor_pos).word_left();
+            *redraw = true;
         }
         KeyCode::Left => {
-            if *edit_cursor_pos > 0 {
-                *edit_cursor_pos -= 1;
-            }
+            TextInput::new(&mut edit_field.get_field_value(edit_details), edit_cursor_pos).left();
             *redraw = true;
         }
         KeyCode::Right if modifiers.contains(event::KeyModifiers::CONTROL) => {
             // jump forward in the current field by words (separated by spaces)
-            let field = edit_field.get_field_value(edit_details);
-            if *edit_cursor_pos < field.len() {
-                let mut i = *edit_cursor_pos;
-                while i < field.len() && field.chars().nth(i).unwrap() != ' ' {
-                    i += 1;
-                }
-                while i < field.len() && field.chars().nth(i).unwrap() == ' ' {
-                    i += 1;
-                }
-                *edit_cursor_pos = i;
-                *redraw = true;
-            }
+            TextInput::new(&mut edit_field.get_field_value(edit_details), edit_cursor_pos).word_right();
+            *redraw = true;
         }
         KeyCode::Right => {
-            let field_length = edit_field.get_field_value(edit_details).len();
-            if *edit_cursor_pos < field_length {
-                *edit_cursor_pos += 1;
-            }
+            TextInput::new(&mut edit_field.get_field_value(edit_details), edit_cursor_pos).right();
             *redraw = true;
         }
         KeyCode::Home => {
-            *edit_cursor_pos = 0;
+            TextInput::new(&mut edit_field.get_field_value(edit_details), edit_cursor_pos).home();
             *redraw = true;
         }
         KeyCode::End => {
-            let field_length = edit_field.get_field_value(edit_details).len();
-            *edit_cursor_pos = field_length;
+            TextInput::new(&mut edit_field.get_field_value(edit_details), edit_cursor_pos).end();
             *redraw = true;
         }
         KeyCode::Backspace => {
-            // removes the character BEFORE the edit_cursor_pos as long as edit_cursor_pos is > 0, otherwise it does nothing
-            if *edit_cursor_pos > 0 {
-                match *edit_field {
-                    EpisodeField::Title => {
-                        edit_details.title.remove(*edit_cursor_pos - 1);
-                    }
-                    EpisodeField::Year => {
-                        edit_details.year.remove(*edit_cursor_pos - 1);
-                    }
-                    EpisodeField::Watched => {
-                        edit_details.watched.remove(*edit_cursor_pos - 1);
-                    }
-                    EpisodeField::Length => {
-                        edit_details.length.remove(*edit_cursor_pos - 1);
-                    }
-                    EpisodeField::EpisodeNumber => {
-                        edit_details.episode_number.remove(*edit_cursor_pos - 1);
-                    }
-                    _ => {}
-                }
-                *edit_cursor_pos -= 1;
+            // removes the character BEFORE the edit_cursor_pos, if there is one
+            let removed = editable_value(*edit_field, edit_details)
+                .is_some_and(|value| TextInput::new(value, edit_cursor_pos).backspace());
+            if removed {
                 update_dirty_state(*edit_field, edit_details, original_edit_details, dirty_fields, season_number);
                 *redraw = true;
             }
         }
         KeyCode::Delete => {
-            // removes the character AT the edit_cursor_pos as long as edit_cursor_pos is < the length of the field, otherwise it does nothing
-            let field_length = edit_field.get_field_value(edit_details).len();
-            if *edit_cursor_pos < field_length {
-                match *edit_field {
-                    EpisodeField::Title => {
-                        edit_details.title.remove(*edit_cursor_pos);
-                    }
-                    EpisodeField::Year => {
-                        edit_details.year.remove(*edit_cursor_pos);
-                    }
-                    EpisodeField::Watched => {
-                        edit_details.watched.remove(*edit_cursor_pos);
-                    }
-                    EpisodeField::Length => {
-                        edit_details.length.remove(*edit_cursor_pos);
-                    }
-                    EpisodeField::EpisodeNumber => {
-                        edit_details.episode_number.remove(*edit_cursor_pos);
-                    }
-                    _ => {}
-                }
+            // removes the character AT the edit_cursor_pos, if there is one
+            let removed = editable_value(*edit_field, edit_details)
+                .is_some_and(|value| TextInput::new(value, edit_cursor_pos).delete());
+            if removed {
                 update_dirty_state(*edit_field, edit_details, original_edit_details, dirty_fields, season_number);
                 *redraw = true;
             }
@@ -500,9 +575,8 @@ pub fn handle_edit_mode(
             ));
             dirty_fields.clear();
             *edit_field = EpisodeField::Title;
-            *mode = Mode::Browse;
             *edit_cursor_pos = 0;
-            *redraw = true;
+            state.close_modal();
         }
         KeyCode::Char('+') if *edit_field == EpisodeField::EpisodeNumber => {
             if let Ok(mut episode_number) = edit_details.episode_number.parse::<i32>() {
@@ -515,26 +589,18 @@ pub fn handle_edit_mode(
             *redraw = true;
         }
         KeyCode::Char('+') if *edit_field == EpisodeField::Season => {
-            // database::can_create_season returns a boolean indicating whether a season can be created
+            // Database::can_create_season returns a boolean indicating whether a season can be created
             // we need to increment seaons_number first, then pass it to the function
             // if the function returns false, we need to set season_number back to its original value
             let original_season_number = *season_number;
-            if season_number.is_none() {
-                *season_number = Some(0);
+            let next_season = season_number.map_or(0, |number| number + 1);
+            if database.can_create_season(edit_details.series.as_ref().map(|s| s.id), next_season).unwrap_or(false) {
+                *season_number = Some(next_season);
             } else {
-                *season_number = Some(season_number.unwrap() + 1_usize);
-            }
-            if !database::can_create_season(
-                edit_details.series.as_ref().map(|s| s.id),
-                season_number.unwrap(),
-            )
-            .unwrap_or(false)
-            {
                 logger::log_debug(&format!(
-                    "Edit mode: Cannot increment season to {} (previous season doesn't exist), reverting to {:?}",
-                    season_number.unwrap(), original_season_number
+                    "Edit mode: Cannot increment season to {} (previous season doesn't exist), keeping {:?}",
+                    next_season, original_season_number
                 ));
-                *season_number = original_season_number;
             }
             update_dirty_state(*edit_field, edit_details, original_edit_details, dirty_fields, season_number);
             *redraw = true;
@@ -554,30 +620,23 @@ pub fn handle_edit_mode(
         KeyCode::Char('-') if *edit_field == EpisodeField::Season => {
             // the decrement will always be valid, so we don't need to check
             // but it must be >= 0, and if it was None, then it should be set to 0
-            if season_number.is_none() {
-                *season_number = Some(0);
-            } else {
-                *season_number = Some(season_number.unwrap().saturating_sub(1));
+            *season_number = Some(season_number.map_or(0, |number| number.saturating_sub(1)));
+            update_dirty_state(*edit_field, edit_details, original_edit_details, dirty_fields, season_number);
+            *redraw = true;
+        }
+        KeyCode::Char('z') if modifiers.contains(event::KeyModifiers::CONTROL) => {
+            // Undo every change to the field, leaving the other fields as they are
+            if !dirty_fields.contains(edit_field) {
+                return;
             }
+            revert_field(*edit_field, edit_details, original_edit_details, season_number);
+            *edit_cursor_pos = edit_field.get_field_value(edit_details).chars().count();
             update_dirty_state(*edit_field, edit_details, original_edit_details, dirty_fields, season_number);
             *redraw = true;
         }
         KeyCode::Char(c) => {
-            let mut allow_edit = true;
-            match *edit_field {
-                EpisodeField::Title => edit_details.title.insert(*edit_cursor_pos, c),
-                EpisodeField::Year => edit_details.year.insert(*edit_cursor_pos, c),
-                EpisodeField::Watched => edit_details.watched.insert(*edit_cursor_pos, c),
-                EpisodeField::Length => edit_details.length.insert(*edit_cursor_pos, c),
-                EpisodeField::EpisodeNumber => {
-                    edit_details.episode_number.insert(*edit_cursor_pos, c)
-                }
-                _ => {
-                    allow_edit = false;
-                }
-            }
-            if allow_edit {
-                *edit_cursor_pos += 1;
+            if let Some(value) = editable_value(*edit_field, edit_details) {
+                TextInput::new(value, edit_cursor_pos).insert(c);
                 update_dirty_state(*edit_field, edit_details, original_edit_details, dirty_fields, season_number);
                 *redraw = true;
             }
@@ -586,190 +645,608 @@ pub fn handle_edit_mode(
     }
 }
 
+// Translate a mouse event in Browse or Menu mode into the key that does the same thing
+pub fn handle_mouse(mouse: MouseEvent, state: &mut AppState) -> io::Result<Option<KeyCode>> {
+    let menu_items = if state.mode == Mode::Menu { state.menu_items() } else { Vec::new() };
+    let group_headers = display::group_headers(&state.filtered_entries, &state.view_context);
+    let entry_count = state.filtered_entries.len();
+    let AppState {
+        ref mode,
+        filter_mode,
+        list_top,
+        ref mut current_item,
+        first_entry,
+        ref mut menu_selection,
+        ref mut clicks,
+        grid_view,
+        ref mut redraw,
+        ..
+    } = *state;
+    let column = mouse.column as usize;
+    let row = mouse.row as usize;
+    match (mode, mouse.kind) {
+        (Mode::Browse, _) if filter_mode => Ok(None),
+        (Mode::Browse | Mode::Menu | Mode::ThemeSelect, MouseEventKind::ScrollUp) => Ok(Some(KeyCode::Up)),
+        (Mode::Browse | Mode::Menu | Mode::ThemeSelect, MouseEventKind::ScrollDown) => Ok(Some(KeyCode::Down)),
+        (Mode::Browse, MouseEventKind::Down(MouseButton::Left)) => {
+            let list_width = display::list_width()?;
+            if column >= list_width {
+                return Ok(None);
+            }
+            let visible_rows = get_max_displayed_items_with_header_height(list_top)?;
+            let index = if grid_view {
+                if row < list_top || row >= list_top + visible_rows {
+                    return Ok(None);
+                }
+                GridBrowser::item_at(list_width, column, row - list_top, first_entry, entry_count)
+            } else if group_headers.is_empty() {
+                input::entry_at_row(row, list_top, visible_rows, first_entry, entry_count)
+            } else {
+                // Group header rows belong to no entry
+                row.checked_sub(list_top)
+                    .filter(|&offset| offset < visible_rows)
+                    .and_then(|offset| Browser::item_at_row(&group_headers, first_entry, entry_count, offset))
+            };
+            let Some(index) = index else {
+                return Ok(None);
+            };
+            *current_item = index;
+            *redraw = true;
+            // A double-click opens the entry like Enter
+            Ok(clicks.click(index, Instant::now()).then_some(KeyCode::Enter))
+        }
+        (Mode::Menu, MouseEventKind::Down(MouseButton::Left)) => {
+            let (terminal_width, _) = crate::terminal::get_terminal_size()?;
+            let menu = ContextMenu::new(menu_items.to_vec(), *menu_selection);
+            match menu.item_at(terminal_width, column, row) {
+                Some(index) => {
+                    *menu_selection = index;
+                    *redraw = true;
+                    Ok(Some(KeyCode::Enter))
+                }
+                // Clicking outside the menu closes it
+                None => Ok(Some(KeyCode::Esc)),
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Entries moved by PageUp/PageDown: a screenful of rows, or of tiles in the grid, less one
+/// row so the row at the edge stays on screen
+fn page_size(grid_view: bool) -> io::Result<usize> {
+    // Use fixed header height for PageUp/PageDown
+    let max_lines = get_max_displayed_items_with_header_height(4)?;
+    Ok(if grid_view {
+        let list_width = display::list_width()?;
+        let columns = GridBrowser::columns_for(list_width);
+        (GridBrowser::page_size(list_width, max_lines) - columns).max(columns)
+    } else {
+        max_lines.saturating_sub(1).max(1)
+    })
+}
+
 pub fn handle_browse_mode(
     code: KeyCode,
     modifiers: event::KeyModifiers,
-    current_item: &mut usize,
-    first_entry: &mut usize,
-    filtered_entries: &mut Vec<Entry>,
-    entries: &mut Vec<Entry>,
-    search: &mut String,
-    playing_file: &mut Option<String>,
-    mode: &mut Mode,
-    edit_details: &mut EpisodeDetail,
-    season_number: &mut Option<usize>,
-    redraw: &mut bool,
-    config: &Config,
-    resolver: &PathResolver,
-    tx: &Sender<()>,
-    view_context: &mut ViewContext,
-    last_action: &mut Option<crate::util::LastAction>,
-    edit_field: &mut EpisodeField,
-    edit_cursor_pos: &mut usize,
-    original_edit_details: &mut Option<EpisodeDetail>,
-    dirty_fields: &mut HashSet<EpisodeField>,
-    remembered_item: &mut usize,
-    menu_selection: &mut usize,
-    series: &mut Vec<Series>,
-    series_selection: &mut Option<usize>,
-    filter_mode: &mut bool,
-    first_series: &mut usize,
-    status_message: &mut String,
-    search_query: &mut String,
+    state: &mut AppState,
+    context: &AppContext,
+    scroll_step: usize,
 ) -> io::Result<bool> {
-    // Check for context menu hotkeys first (F2-F5) - but not in filter mode
-    // Build menu context to check if actions are available
-    if !*filter_mode {
-        let menu_context = crate::menu::MenuContext {
-            selected_entry: filtered_entries.get(*current_item).cloned(),
-            episode_detail: edit_details.clone(),
-            mode: mode.clone(),
-            last_action: last_action.clone(),
+    // A command typed after `:` takes every key until it is run or cancelled
+    if state.command_line.is_some() {
+        return match action::command_line_action(code) {
+            Some(action) => apply_browse_action(action, state, context, scroll_step),
+            None => Ok(true),
         };
-        let menu_items = crate::menu::get_context_menu_items(&menu_context);
-        
+    }
+
+    if !state.filter_mode && state.pending_keys.is_empty() && type_count(code, modifiers, state) {
+        return Ok(true);
+    }
+    // A count is only for the key after it
+    let count = state.count.take();
+    state.redraw |= count.is_some();
+
+    // Check for context menu hotkeys first (F2-F5) - but not in filter mode or partway through a key sequence
+    if !state.filter_mode && state.pending_keys.is_empty() {
         // Check if the pressed key matches any available menu item hotkey
-        for item in &menu_items {
+        for item in &browse_menu_items(state) {
             if let Some(hotkey) = &item.hotkey {
                 if *hotkey == code {
                     // Execute the menu action directly
-                    execute_menu_action(
-                        &item.action,
-                        mode,
-                        redraw,
-                        *current_item,
-                        filtered_entries,
-                        entries,
-                        edit_details,
-                        season_number,
-                        view_context,
-                        last_action,
-                        edit_field,
-                        edit_cursor_pos,
-                        original_edit_details,
-                        dirty_fields,
-                        series,
-                        series_selection,
-                        first_series,
-                        config,
-                        resolver,
-                        status_message,
-                        search_query,
-                    );
+                    execute_menu_action(&item.action, state.current_item, state, context);
                     return Ok(true);
                 }
             }
         }
     }
-    
-    match code {
-        // When in filter mode, only allow filter-related keys
-        KeyCode::F(1) if !*filter_mode => {
-            // Open context menu
-            *mode = Mode::Menu;
+
+    match action::browse_key_action(&keymap::keymap(), &mut state.pending_keys, code, modifiers, state.filter_mode) {
+        Some(action) => {
+            let step = match count {
+                Some(Count::Repeat(times)) => scroll_step * times,
+                _ => scroll_step,
+            };
+            apply_browse_action(action, state, context, step)
+        }
+        None => {
+            // Partway through a sequence such as gg the count waits for its last key
+            if !state.pending_keys.is_empty() {
+                state.count = count;
+            }
+            Ok(true)
+        }
+    }
+}
+
+/// Type a count for the next move (15j) or, after #, an entry number to select with Enter,
+/// returning false when the key isn't part of one
+///
+/// Digits and # start a count only when no Browse action has them, and 1-3 only when their
+/// pinned filter slot is empty, so the pinned filters keep their keys.
+fn type_count(code: KeyCode, modifiers: event::KeyModifiers, state: &mut AppState) -> bool {
+    if modifiers.intersects(event::KeyModifiers::CONTROL | event::KeyModifiers::ALT) {
+        return false;
+    }
+    let unbound = keymap::keymap().browse_action(code, modifiers).is_none();
+    state.count = match (state.count, code) {
+        (Some(Count::Jump(number)), KeyCode::Enter) => {
+            if let Some(number) = number {
+                select_entry_number(state, number);
+            }
+            None
+        }
+        (Some(_), KeyCode::Esc) => None,
+        (Some(count), KeyCode::Backspace) => count.pop_digit(),
+        (Some(count), KeyCode::Char(c @ '0'..='9')) => Some(count.push_digit(c as usize - '0' as usize)),
+        (None, KeyCode::Char(c @ '1'..='9')) if unbound => {
+            let digit = c as usize - '0' as usize;
+            if digit <= 3 && state.pinned_filters.get(digit).is_some() {
+                return false;
+            }
+            Some(Count::Repeat(digit))
+        }
+        (None, KeyCode::Char('#')) if unbound => Some(Count::Jump(None)),
+        _ => return false,
+    };
+    state.redraw = true;
+    true
+}
+
+/// Select the entry with a 1-based number, clamped to the list, as :N and #N do
+fn select_entry_number(state: &mut AppState, number: usize) {
+    if !state.filtered_entries.is_empty() {
+        state.current_item = number.clamp(1, state.filtered_entries.len()) - 1;
+        state.first_entry = state.first_entry.min(state.current_item);
+    }
+}
+
+/// Menu actions available for the entry selected in Browse mode
+fn browse_menu_items(state: &AppState) -> Vec<MenuItem> {
+    menu::get_context_menu_items(&MenuContext {
+        selected_entry: state.filtered_entries.get(state.current_item).cloned(),
+        episode_detail: state.edit_details.clone(),
+        mode: state.mode.clone(),
+        last_action: state.last_action.clone(),
+        marked_count: state.marked_episodes.len(),
+        extras_view: state.view_context == ViewContext::Extras,
+    })
+}
+
+/// Run a command typed after `:`: an entry number, q to quit, or a Browse or menu action by name
+fn run_command(command: &str, state: &mut AppState, context: &AppContext, scroll_step: usize) -> io::Result<bool> {
+    if command.is_empty() {
+        return Ok(true);
+    }
+    if let Ok(number) = command.parse::<usize>() {
+        // As :N in vim, select the Nth entry
+        select_entry_number(state, number);
+        return Ok(true);
+    }
+    if matches!(command, "q" | "quit") {
+        return Ok(false);
+    }
+
+    let name = command.to_lowercase().replace([' ', '-'], "_");
+    let name = match name.as_str() {
+        "help" => "show_help",
+        "messages" => "show_notifications",
+        "grid" => "toggle_grid",
+        "menu" => "open_menu",
+        "filter" => "start_filter",
+        name => name,
+    };
+    if let Some(action) = keymap::keymap().action_named(name) {
+        return apply_browse_action(action, state, context, scroll_step);
+    }
+    if let Some(item) = browse_menu_items(state).into_iter().find(|item| item.action.usage_key() == name) {
+        execute_menu_action(&item.action, state.current_item, state, context);
+    } else if menu::all_menu_items().iter().any(|item| item.action.usage_key() == name) {
+        state.notifications.info(format!("{} isn't available for the selection", command));
+    } else {
+        state.notifications.warn(format!("Not a command: {}", command));
+    }
+    Ok(true)
+}
+
+/// Carry out an action in Browse mode, returning false when the application should quit
+pub fn apply_browse_action(
+    action: Action,
+    state: &mut AppState,
+    context: &AppContext,
+    scroll_step: usize,
+) -> io::Result<bool> {
+    if action == Action::RunCommand {
+        let command = state.command_line.take().unwrap_or_default();
+        state.redraw = true;
+        return run_command(command.trim(), state, context, scroll_step);
+    }
+    state.list_unchanged = action.only_moves();
+
+    let AppContext { config, database, resolver, .. } = *context;
+    let AppState {
+        ref mut current_item,
+        ref mut first_entry,
+        ref mut filtered_entries,
+        ref mut entries,
+        ref mut search,
+        ref mut playing_file,
+        ref mut mode,
+        ref mut edit_details,
+        ref mut redraw,
+        ref mut view_context,
+        ref mut edit_cursor_pos,
+        ref mut remembered_item,
+        ref mut menu_selection,
+        ref mut filter_mode,
+        ref mut filter_history,
+        ref mut notifications,
+        ref mut sort_mode,
+        ref mut pinned_filters,
+        ref mut versions,
+        ref mut selected_version,
+        ref mut marked_episodes,
+        ref mut grid_view,
+        ref mut tabs,
+        ref mut command_line,
+        ref scan_progress,
+        ref mut modals,
+        ref mut macros,
+        ..
+    } = *state;
+
+    // In the grid, up and down move a whole row of tiles
+    let step = if *grid_view {
+        scroll_step * GridBrowser::columns_for(display::list_width()?)
+    } else {
+        scroll_step
+    };
+
+    match action {
+        Action::OpenMenu => {
+            // Open context menu over Browse, which is kept to return to
+            modals.open(mode, Mode::Menu, *current_item, *first_entry, *menu_selection);
             *remembered_item = *current_item;
             *menu_selection = 0;
             *redraw = true;
         }
-        KeyCode::Up if !*filter_mode => {
-            if *current_item > 0 {
-                *current_item -= 1;
-                if *current_item < *first_entry {
-                    *first_entry = *current_item;
-                }
-                *redraw = true;
+        Action::MoveUp if *current_item > 0 => {
+            *current_item = current_item.saturating_sub(step);
+            if *current_item < *first_entry {
+                *first_entry = *current_item;
             }
+            *redraw = true;
         }
-        KeyCode::Down if !*filter_mode => {
-            if *current_item < filtered_entries.len() - 1 {
-                *current_item += 1;
-                *redraw = true;
+        Action::MoveDown if *current_item + 1 < filtered_entries.len() => {
+            *current_item = (*current_item + step).min(filtered_entries.len() - 1);
+            *redraw = true;
+        }
+        // Paging moves the list and the selection together, so the selection stays on the same row
+        Action::PageUp => {
+            let page = page_size(*grid_view)?;
+            *current_item = (*current_item).saturating_sub(page);
+            *first_entry = (*first_entry).saturating_sub(page);
+            *redraw = true;
+        }
+        Action::PageDown if !filtered_entries.is_empty() => {
+            let page = page_size(*grid_view)?;
+            *current_item = (*current_item + page).min(filtered_entries.len() - 1);
+            *first_entry += page;
+            *redraw = true;
+        }
+        Action::HalfPageUp => {
+            let half = (page_size(*grid_view)? / 2).max(1);
+            *current_item = (*current_item).saturating_sub(half);
+            *first_entry = (*first_entry).saturating_sub(half);
+            *redraw = true;
+        }
+        Action::HalfPageDown if !filtered_entries.is_empty() => {
+            let half = (page_size(*grid_view)? / 2).max(1);
+            *current_item = (*current_item + half).min(filtered_entries.len() - 1);
+            *first_entry += half;
+            *redraw = true;
+        }
+        Action::MoveTop => {
+            *current_item = 0;
+            *first_entry = 0;
+            *redraw = true;
+        }
+        Action::MoveBottom if !filtered_entries.is_empty() => {
+            *current_item = filtered_entries.len() - 1;
+            *redraw = true;
+        }
+        Action::NextMatch | Action::PreviousMatch => {
+            // The list only holds the entries matching the kept filter, so step through it and wrap around
+            if search.trim().is_empty() {
+                notifications.info("No filter to jump through, / to filter");
+            } else if !filtered_entries.is_empty() {
+                let count = filtered_entries.len();
+                let next = if action == Action::NextMatch {
+                    if *current_item + 1 >= count {
+                        notifications.info("Search hit bottom, continuing at top");
+                    }
+                    (*current_item + 1) % count
+                } else {
+                    if *current_item == 0 {
+                        notifications.info("Search hit top, continuing at bottom");
+                    }
+                    (*current_item + count - 1) % count
+                };
+                *current_item = next;
+                *first_entry = (*first_entry).min(next);
             }
+            *redraw = true;
         }
-        KeyCode::Char('k') if !*filter_mode => {
-            if *current_item > 0 {
-                *current_item -= 1;
-                if *current_item < *first_entry {
-                    *first_entry = *current_item;
-                }
-                *redraw = true;
+        Action::OpenCommandLine => {
+            *command_line = Some(String::new());
+            *redraw = true;
+        }
+        Action::CommandInsert(c) => {
+            if let Some(line) = command_line.as_mut() {
+                line.push(c);
             }
+            *redraw = true;
         }
-        KeyCode::Char('j') if !*filter_mode => {
-            if *current_item < filtered_entries.len() - 1 {
-                *current_item += 1;
-                *redraw = true;
+        Action::CommandBackspace => {
+            // Backspace on an empty command line closes it, as in vim
+            match command_line.as_mut() {
+                Some(line) if !line.is_empty() => {
+                    line.pop();
+                }
+                _ => *command_line = None,
             }
+            *redraw = true;
         }
-        KeyCode::PageUp if !*filter_mode => {
-            // Use fixed header height for PageUp/PageDown
-            let max_lines = get_max_displayed_items_with_header_height(4)?;
-            if *current_item > *first_entry {
-                *current_item = *first_entry;
-            } else {
-                *current_item = (*current_item).saturating_sub(max_lines);
+        Action::CancelCommand => {
+            *command_line = None;
+            *redraw = true;
+        }
+        Action::RecordMacro if macros.is_recording() => {
+            match macros.stop_recording() {
+                0 => notifications.info("Nothing was recorded"),
+                1 => notifications.info("Recorded a macro of 1 key"),
+                count => notifications.info(format!("Recorded a macro of {} keys", count)),
             }
             *redraw = true;
         }
-        KeyCode::PageDown if !*filter_mode => {
-            // Use fixed header height for PageUp/PageDown
-            let max_lines = get_max_displayed_items_with_header_height(4)?;
-            if *current_item < *first_entry + max_lines - 1 {
-                *current_item = *first_entry + max_lines - 1;
+        Action::RecordMacro => {
+            macros.start_recording();
+            *redraw = true;
+        }
+        Action::ReplayMacro => {
+            // Ignored while a replay is running, so a macro with @ in it doesn't replay itself
+            if macros.is_recording() {
+                notifications.info("Stop recording before replaying the macro");
+            } else if !macros.has_macro() {
+                notifications.info("No macro recorded yet");
             } else {
-                *current_item = (*current_item).saturating_add(max_lines);
+                macros.replay();
             }
             *redraw = true;
         }
-        KeyCode::Char('/') if !*filter_mode => {
-            // Enter filter mode and set cursor to end of search string
-            *filter_mode = true;
-            *edit_cursor_pos = search.len();
+        // Ctrl+Left/Right move the split between the list and the details
+        Action::ResizeSplit(wider) => {
+            display::adjust_list_split(wider)?;
             *redraw = true;
         }
-        KeyCode::Enter if *filter_mode => {
-            // Accept filter and exit filter mode
-            logger::log_debug(&format!(
-                "Browse mode: Accepting filter '{}', {} entries match",
-                search, filtered_entries.len()
-            ));
-            *filter_mode = false;
-            *edit_cursor_pos = 0;
+        Action::MoveLeft if *grid_view && *current_item > 0 => {
+            *current_item = current_item.saturating_sub(scroll_step);
             *redraw = true;
         }
-        KeyCode::Enter if !*filter_mode => {
-            let selected = *current_item;
-            let selected_entry = &filtered_entries[selected].clone();
-            match selected_entry {
-                Entry::Series { series_id, name } => {
-                    search.clear();
-                    // If a series is selected, reload the entries with the series filter
-                    *entries = database::get_entries_for_series(*series_id)
-                        .expect("Failed to get entries for series");
+        Action::MoveRight if *grid_view && *current_item + 1 < filtered_entries.len() => {
+            *current_item = (*current_item + scroll_step).min(filtered_entries.len() - 1);
+            *redraw = true;
+        }
+        Action::ToggleGrid => {
+            // Switch between the list and the grid of tiles
+            *grid_view = !*grid_view;
+            notifications.info(if *grid_view { "Grid view" } else { "List view" });
+            *redraw = true;
+        }
+        Action::SwitchTab(slot) => {
+            // Switch to a tab, remembering where this one was left
+            let current = Tab {
+                view_context: view_context.clone(),
+                filter: search.clone(),
+                sort_mode: *sort_mode,
+                current_item: *current_item,
+                first_entry: *first_entry,
+            };
+            match tabs.switch_to(slot, current) {
+                Some(tab) => {
+                    *view_context = tab.view_context;
+                    *search = tab.filter;
+                    *sort_mode = tab.sort_mode;
+                    reload_entries(database, view_context, entries, notifications);
                     *filtered_entries = entries.clone();
-                    // Auto-select first unwatched entry, or default to 0
-                    *current_item = find_first_unwatched_index(&entries).unwrap_or(0);
-                    *view_context = ViewContext::Series { 
-                        series_id: *series_id, 
-                        series_name: name.clone() 
-                    };
-                    *redraw = true;
+                    *current_item = tab.current_item;
+                    *first_entry = tab.first_entry;
                 }
-                Entry::Episode { location, episode_id, name, .. } => {
+                None => notifications.info(format!("No view pinned to tab {}", slot)),
+            }
+            *redraw = true;
+        }
+        Action::PinTab => {
+            // Pin the view as it is now to a new tab
+            let tab = Tab {
+                view_context: view_context.clone(),
+                filter: search.clone(),
+                sort_mode: *sort_mode,
+                current_item: *current_item,
+                first_entry: *first_entry,
+            };
+            let label = tab.label();
+            match tabs.pin(tab) {
+                Ok(slot) => {
+                    logger::log_info(&format!("Pinned '{}' to tab {}", label, slot));
+                    notifications.toast(format!("Pinned {} to tab {}, Alt+{} to return", label, slot, slot));
+                }
+                Err(message) => notifications.warn(message),
+            }
+            *redraw = true;
+        }
+        Action::CloseTab => {
+            // Unpin the tab of the view on screen
+            match tabs.close_active() {
+                Some(slot) => notifications.toast(format!("Closed tab {}", slot)),
+                None => notifications.info("This view isn't pinned to a tab"),
+            }
+            *redraw = true;
+        }
+        Action::TogglePinnedFilter(slot) => {
+            // Switch a pinned filter on or off
+            match pinned_filters.toggle(slot) {
+                Some(active) => {
+                    let expression = pinned_filters.get(slot).map(|q| q.expression()).unwrap_or_default();
+                    notifications.info(format!(
+                        "Pinned filter [{}] \"{}\" {}",
+                        slot,
+                        expression,
+                        if active { "on" } else { "off" }
+                    ));
+                    *current_item = 0;
+                    *first_entry = 0;
+                }
+                None => notifications.info(format!("No filter pinned to [{}]", slot)),
+            }
+            *redraw = true;
+        }
+        Action::ToggleMark => {
+            // Mark or unmark the episode for batch actions, then move to the next entry
+            if let Some(Entry::Episode { episode_id, .. }) = filtered_entries.get(*current_item) {
+                if !marked_episodes.remove(episode_id) {
+                    marked_episodes.insert(*episode_id);
+                }
+                if !marked_episodes.is_empty() {
+                    notifications.info(format!("{} marked, F1 for batch actions, Esc to clear", marked_episodes.len()));
+                }
+                if *current_item + 1 < filtered_entries.len() {
+                    *current_item += 1;
+                }
+                *redraw = true;
+            }
+        }
+        Action::CopyPath | Action::CopyTitle => {
+            // Copy the selected episode's file or title, saying what was copied
+            if let Some(Entry::Episode { episode_id, .. }) = filtered_entries.get(*current_item) {
+                let copied = if action == Action::CopyPath {
+                    database.get_episode_absolute_location(*episode_id, resolver).map(|location| location::display(&location))
+                } else {
+                    database.get_episode_detail(*episode_id).map(|details| clipboard::episode_title(&details))
+                };
+                match copied {
+                    Ok(text) => match clipboard::copy(&text) {
+                        Ok(()) => notifications.toast(format!("Copied {}", text)),
+                        Err(e) => notifications.warn(format!("Failed to copy to the clipboard: {}", e)),
+                    },
+                    Err(e) => report_database_error("Failed to look up the episode to copy", &e, notifications),
+                }
+            } else {
+                notifications.info("Select an episode to copy its path or title");
+            }
+            *redraw = true;
+        }
+        Action::ShowHelp => {
+            // Show the keybinding reference
+            modals.open(mode, Mode::Help, *current_item, *first_entry, *menu_selection);
+            *redraw = true;
+        }
+        Action::ShowNotifications => {
+            // Show the recent status messages
+            modals.open(mode, Mode::Notifications, *current_item, *first_entry, *menu_selection);
+            *redraw = true;
+        }
+        Action::StartFilter => {
+            // Enter filter mode and set cursor to end of search string
+            *filter_mode = true;
+            *edit_cursor_pos = search.chars().count();
+            filter_history.stop_recalling();
+            *redraw = true;
+        }
+        Action::AcceptFilter => {
+            // Accept filter and exit filter mode
+            logger::log_debug(&format!(
+                "Browse mode: Accepting filter '{}', {} entries match",
+                search, filtered_entries.len()
+            ));
+            filter_history.add(search);
+            *filter_mode = false;
+            *edit_cursor_pos = 0;
+            *redraw = true;
+        }
+        Action::FilterHistoryPrevious | Action::FilterHistoryNext => {
+            let recalled = if action == Action::FilterHistoryPrevious {
+                filter_history.older(search)
+            } else {
+                filter_history.newer()
+            };
+            if let Some(recalled) = recalled {
+                *search = recalled.to_string();
+                *edit_cursor_pos = search.chars().count();
+                *redraw = true;
+            }
+        }
+        Action::PlaySelected if !filtered_entries.is_empty() => {
+            let selected = *current_item;
+            let selected_entry = &filtered_entries[selected].clone();
+            match selected_entry {
+                Entry::Series { series_id, name } => {
+                    search.clear();
+                    // If a series is selected, reload the entries with the series filter
+                    match database.get_entries_for_series(*series_id) {
+                        Ok(loaded) => *entries = loaded,
+                        Err(e) => {
+                            report_database_error(&format!("Failed to open series '{}'", name), &e, notifications);
+                            *redraw = true;
+                            return Ok(true);
+                        }
+                    }
+                    *filtered_entries = entries.clone();
+                    // Auto-select first unwatched entry, or default to 0
+                    *current_item = find_first_unwatched_index(database, entries).unwrap_or(0);
+                    *view_context = ViewContext::Series { 
+                        series_id: *series_id, 
+                        series_name: name.clone() 
+                    };
+                    *redraw = true;
+                }
+                Entry::Episode { episode_id, name, .. } => {
                     // If an episode is selected, play the video
                     if playing_file.is_none() {
                         // Check if episode has length = 0 or NULL, and extract if needed
                         if edit_details.length.is_empty() || edit_details.length == "0" {
                             // Resolve relative path to absolute path for extraction
-                            match database::get_episode_absolute_location(*episode_id, resolver) {
+                            match database.get_episode_absolute_location(*episode_id, resolver) {
                                 Ok(absolute_location) => {
                                     // Attempt to extract and update episode length (fails silently with log warning)
                                     if video_metadata::extract_and_update_episode_length(
+                                        database,
                                         *episode_id,
-                                        Path::new(&absolute_location)
+                                        &location::decode(&absolute_location)
                                     ).is_ok() {
                                         // Reload episode details to get updated length
-                                        if let Ok(updated_details) = database::get_episode_detail(*episode_id) {
+                                        if let Ok(updated_details) = database.get_episode_detail(*episode_id) {
                                             *edit_details = updated_details;
                                         }
                                     }
@@ -780,172 +1257,22 @@ pub fn handle_browse_mode(
                             }
                         }
                         
-                        // Resolve relative path to absolute path for video playback
-                        match database::get_episode_absolute_location(*episode_id, resolver) {
-                            Ok(absolute_location) => {
-                                // Log video playback
-                                logger::log_info(&format!("Playing video: {} ({})", name, absolute_location));
-                                
-                                // Mark episode as unwatched when starting playback
-                                if let Err(e) = database::mark_episode_unwatched(*episode_id) {
-                                    logger::log_warn(&format!("Failed to mark episode {} as unwatched: {}", episode_id, e));
-                                }
-                                
-                                // Set status message
-                                *status_message = format!("Playing video: {}", name);
+                        // Several files of the episode open the version picker
+                        match database.get_episode_versions(*episode_id, resolver) {
+                            Ok(found) if found.len() > 1 => {
+                                *selected_version = preferred_version_index(&found, config.preferred_version.as_deref());
+                                *versions = found;
+                                modals.open(mode, Mode::VersionSelect, *current_item, *first_entry, *menu_selection);
                                 *redraw = true;
-                                
-                                // Create player plugin based on configured video player
-                                let plugin = create_player_plugin(&config.video_player);
-                                
-                                // Query existing progress for resume functionality
-                                let start_time = match database::get_episode_progress(*episode_id) {
-                                    Ok(Some(0)) => {
-                                        // Progress is explicitly 0 - start from beginning and override any watch-later file
-                                        logger::log_info("Starting video from beginning (progress reset)");
-                                        Some(0)
-                                    }
-                                    Ok(Some(progress_seconds)) if progress_seconds > 0 => {
-                                        // Non-zero progress - let Celluloid handle resume from watch-later file
-                                        logger::log_info(&format!("Resuming video (progress: {}s, using Celluloid's watch-later)", progress_seconds));
-                                        None
-                                    }
-                                    Ok(Some(_)) => {
-                                        // Shouldn't reach here, but handle it
-                                        logger::log_info("Starting video from beginning");
-                                        Some(0)
-                                    }
-                                    Ok(None) => {
-                                        // No progress data - let Celluloid handle resume from its watch-later file
-                                        logger::log_info("Starting video (no progress data)");
-                                        None
-                                    }
-                                    Err(e) => {
-                                        logger::log_warn(&format!("Failed to get progress for episode {}: {}. Starting from beginning.", episode_id, e));
-                                        None
-                                    }
-                                };
-                                
-                                // Launch player using plugin
-                                let (command, args) = plugin.launch_command(Path::new(&absolute_location), start_time);
-                                
-                                match std::process::Command::new(&command)
-                                    .args(&args)
-                                    .stdout(std::process::Stdio::null())
-                                    .stderr(std::process::Stdio::null())
-                                    .spawn()
-                                {
-                                    Ok(player_process) => {
-                                        *playing_file = Some(location.to_string());
-                                        logger::log_info(&format!("Video player launched: {} {:?}", command, args));
-                                        
-                                        // Spawn a thread to monitor progress and wait for player to finish
-                                        let tx_clone = tx.clone();
-                                        let episode_id_clone = *episode_id;
-                                        let absolute_location_clone = absolute_location.clone();
-                                        let watched_threshold = config.watched_threshold;
-                                        let episode_duration = if !edit_details.length.is_empty() && edit_details.length != "0" {
-                                            edit_details.length.parse::<u64>().unwrap_or(0)
-                                        } else {
-                                            0
-                                        };
-                                        
-                                        thread::spawn(move || {
-                                            use std::time::Duration;
-                                            
-                                            let mut player_process = player_process;
-                                            let plugin = create_player_plugin(&command);
-                                            
-                                            // Monitor progress while player is running
-                                            loop {
-                                                // Check if player is still running
-                                                match player_process.try_wait() {
-                                                    Ok(Some(exit_status)) => {
-                                                        // Player has exited
-                                                        logger::log_info(&format!("Video player exited with status: {}", exit_status));
-                                                        
-                                                        // Give mpv a moment to write the watch-later file
-                                                        thread::sleep(Duration::from_millis(500));
-                                                        
-                                                        // Get final position
-                                                        match plugin.get_final_position(Path::new(&absolute_location_clone)) {
-                                                            Ok(Some(final_position)) => {
-                                                                logger::log_info(&format!("Retrieved final position: {}s", final_position));
-                                                                
-                                                                // Update database with final progress
-                                                                if let Err(e) = crate::database::update_episode_progress(episode_id_clone, final_position) {
-                                                                    logger::log_error(&format!("Failed to update progress for episode {}: {}", episode_id_clone, e));
-                                                                }
-                                                                
-                                                                // Check if watched threshold is met
-                                                                if episode_duration > 0 {
-                                                                    let progress_percentage = (final_position as f64 / episode_duration as f64) * 100.0;
-                                                                    let threshold = watched_threshold as f64;
-                                                                    
-                                                                    if progress_percentage >= threshold {
-                                                                        logger::log_info(&format!(
-                                                                            "Episode {} reached watched threshold ({:.1}% >= {:.1}%), marking as watched",
-                                                                            episode_id_clone, progress_percentage, threshold
-                                                                        ));
-                                                                        
-                                                                        if let Err(e) = crate::database::mark_episode_watched_with_timestamp(episode_id_clone) {
-                                                                            logger::log_error(&format!("Failed to mark episode {} as watched: {}", episode_id_clone, e));
-                                                                        } else {
-                                                                            // Delete watch-later file so next playback starts from beginning
-                                                                            if let Err(e) = plugin.delete_watch_later_file(Path::new(&absolute_location_clone)) {
-                                                                                logger::log_warn(&format!("Failed to delete watch-later file: {}", e));
-                                                                            }
-                                                                        }
-                                                                    } else {
-                                                                        logger::log_info(&format!(
-                                                                            "Episode {} progress: {:.1}% (threshold: {:.1}%)",
-                                                                            episode_id_clone, progress_percentage, threshold
-                                                                        ));
-                                                                    }
-                                                                }
-                                                            }
-                                                            Ok(None) => {
-                                                                logger::log_info("No final position available from player plugin");
-                                                            }
-                                                            Err(e) => {
-                                                                logger::log_warn(&format!("Failed to retrieve final position: {}", e));
-                                                            }
-                                                        }
-                                                        
-                                                        // Always clean up watch-later files, even if we couldn't read position
-                                                        // This prevents stale/empty files from causing issues
-                                                        if let Err(e) = plugin.cleanup_progress_files() {
-                                                            logger::log_warn(&format!("Failed to cleanup progress files: {}", e));
-                                                        }
-                                                        
-                                                        // Notify main thread that playback is complete
-                                                        tx_clone.send(()).ok();
-                                                        break;
-                                                    }
-                                                    Ok(None) => {
-                                                        // Player is still running, just sleep and check again
-                                                        // Don't read watch-later file while player is running to avoid file locking issues
-                                                        thread::sleep(Duration::from_secs(10));
-                                                    }
-                                                    Err(e) => {
-                                                        logger::log_error(&format!("Error checking player status: {}", e));
-                                                        tx_clone.send(()).ok();
-                                                        break;
-                                                    }
-                                                }
-                                            }
-                                        });
-                                    }
-                                    Err(e) => {
-                                        logger::log_error(&format!("Failed to start video player for {}: {}", name, e));
-                                        eprintln!("Error: Failed to start video player: {}", e);
-                                        return Err(e);
-                                    }
+                            }
+                            Ok(found) => {
+                                if let Some(version) = found.first() {
+                                    let details = edit_details.clone();
+                                    play_episode(*episode_id, name, &version.location, &details, state, context);
                                 }
                             }
                             Err(e) => {
-                                logger::log_error(&format!("Failed to resolve video path for episode {}: {}", episode_id, e));
-                                eprintln!("Error resolving video path: {}", e);
+                                report_database_error(&format!("Failed to resolve video path for episode {}", episode_id), &e, notifications);
                             }
                         }
                     }
@@ -953,12 +1280,18 @@ pub fn handle_browse_mode(
                 Entry::Season { season_id, number } => {
                     search.clear();
                     // If a season is selected, reload the entries with the season filter
-                    *entries = database::get_entries_for_season(*season_id)
-                        .expect("Failed to get entries for season");
+                    match database.get_entries_for_season(*season_id) {
+                        Ok(loaded) => *entries = loaded,
+                        Err(e) => {
+                            report_database_error(&format!("Failed to open season {}", number), &e, notifications);
+                            *redraw = true;
+                            return Ok(true);
+                        }
+                    }
                     *filtered_entries = entries.clone();
                     
                     // Auto-select first unwatched episode
-                    *current_item = find_first_unwatched_index(entries).unwrap_or(0);
+                    *current_item = find_first_unwatched_index(database, entries).unwrap_or(0);
                     
                     // Get series info from current view context (we must be in a series view)
                     let series_name = match view_context {
@@ -966,11 +1299,15 @@ pub fn handle_browse_mode(
                         _ => {
                             // Fallback: get series info from database
                             // This can happen if navigating directly to a season (e.g., after app restart)
-                            let (_season, series_id_from_db) = database::get_season_by_id(*season_id)
-                                .expect("Failed to get season");
-                            let series = database::get_series_by_id(series_id_from_db)
-                                .expect("Failed to get series");
-                            series.name
+                            let series = database.get_season_by_id(*season_id)
+                                .and_then(|(_season, series_id_from_db)| database.get_series_by_id(series_id_from_db));
+                            match series {
+                                Ok(series) => series.name,
+                                Err(e) => {
+                                    report_database_error("Failed to look up the season's series", &e, notifications);
+                                    String::new()
+                                }
+                            }
                         }
                     };
                     
@@ -982,9 +1319,9 @@ pub fn handle_browse_mode(
                     *redraw = true;
                 }
             }
-            *redraw = true;
+            state.redraw = true;
         }
-        KeyCode::Esc if *filter_mode => {
+        Action::CancelFilter => {
             // Cancel filter: clear search string and exit filter mode
             logger::log_debug("Browse mode: Canceling filter, clearing search");
             search.clear();
@@ -992,22 +1329,61 @@ pub fn handle_browse_mode(
             *edit_cursor_pos = 0;
             *redraw = true;
         }
-        KeyCode::Esc
-            if !*filter_mode && !filtered_entries.is_empty() 
-                && matches!(filtered_entries[*current_item], Entry::Episode { .. })
+        Action::Back if scan_progress.is_some() => {
+            // Stop the background rescan before anything else Esc would do
+            if crate::scan::cancel_rescan() {
+                notifications.info("Cancelling rescan...");
+            }
+            *redraw = true;
+        }
+        Action::Back if !marked_episodes.is_empty() => {
+            // Clear the marks before leaving the view
+            marked_episodes.clear();
+            notifications.dismiss();
+            *redraw = true;
+        }
+        Action::Back if *view_context == ViewContext::Extras => {
+            // Extras may be in a series, so go straight back to the library
+            logger::log_debug("Browse mode: Leaving the extras");
+            search.clear();
+            match database.get_entries() {
+                Ok(loaded) => *entries = loaded,
+                Err(e) => {
+                    report_database_error("Failed to load entries", &e, notifications);
+                    *redraw = true;
+                    return Ok(true);
+                }
+            }
+            *filtered_entries = entries.clone();
+            *current_item = 0;
+            *view_context = ViewContext::TopLevel;
+            *redraw = true;
+        }
+        Action::Back
+            if matches!(filtered_entries.get(*current_item), Some(Entry::Episode { .. }))
+                && edit_details.series.is_some()
                 && edit_details.season.is_some() =>
         {
             //go back to the season view
+            let (Some(series), Some(season)) = (&edit_details.series, &edit_details.season) else {
+                return Ok(true);
+            };
             logger::log_debug(&format!(
                 "Browse mode: Navigating from season view to series view (series_id={})",
-                edit_details.series.as_ref().unwrap().id
+                series.id
             ));
             search.clear();
-            let series_id = edit_details.series.as_ref().unwrap().id;
-            let series_name = edit_details.series.as_ref().unwrap().name.clone();
-            let season_id = edit_details.season.as_ref().unwrap().id;
-            *entries = database::get_entries_for_series(series_id)
-                .expect("Failed to get entries for series");
+            let series_id = series.id;
+            let series_name = series.name.clone();
+            let season_id = season.id;
+            match database.get_entries_for_series(series_id) {
+                Ok(loaded) => *entries = loaded,
+                Err(e) => {
+                    report_database_error(&format!("Failed to open series '{}'", series_name), &e, notifications);
+                    *redraw = true;
+                    return Ok(true);
+                }
+            }
             *filtered_entries = entries.clone();
             // Find and select the season we just came from
             *current_item = entries.iter().position(|e| {
@@ -1016,16 +1392,22 @@ pub fn handle_browse_mode(
             *view_context = ViewContext::Series { series_id, series_name };
             *redraw = true;
         }
-        KeyCode::Esc
-            if !*filter_mode && !filtered_entries.is_empty() 
-                && (matches!(filtered_entries[*current_item], Entry::Season { .. })
-                || matches!(filtered_entries[*current_item], Entry::Episode { .. })
-                    && edit_details.series.is_some()) =>
+        Action::Back
+            if matches!(filtered_entries.get(*current_item), Some(Entry::Season { .. }))
+                || matches!(filtered_entries.get(*current_item), Some(Entry::Episode { .. }))
+                    && edit_details.series.is_some() =>
         {
             logger::log_debug("Browse mode: Navigating from series/season view to top level");
             search.clear();
             let series_id = edit_details.series.as_ref().map(|s| s.id);
-            *entries = database::get_entries().expect("Failed to get entries");
+            match database.get_entries() {
+                Ok(loaded) => *entries = loaded,
+                Err(e) => {
+                    report_database_error("Failed to load entries", &e, notifications);
+                    *redraw = true;
+                    return Ok(true);
+                }
+            }
             *filtered_entries = entries.clone();
             // Find and select the series we just came from
             if let Some(sid) = series_id {
@@ -1038,74 +1420,74 @@ pub fn handle_browse_mode(
             *view_context = ViewContext::TopLevel;
             *redraw = true;
         }
-        KeyCode::Esc if !*filter_mode => return Ok(false),
-        KeyCode::Left if modifiers.contains(event::KeyModifiers::CONTROL) && *filter_mode => {
-            // Jump back by words (separated by spaces)
-            if *edit_cursor_pos > 0 {
-                let mut i = *edit_cursor_pos - 1;
-                while i > 0 && search.chars().nth(i - 1).unwrap() == ' ' {
-                    i -= 1;
+        Action::Back if filtered_entries.is_empty() && !search.is_empty() => {
+            // Nothing matched the kept filter, so clear it rather than quit
+            search.clear();
+            *redraw = true;
+        }
+        Action::Back if filtered_entries.is_empty() && !matches!(view_context, ViewContext::TopLevel) => {
+            // An empty series or season has no entry to go back from, so use the view itself
+            logger::log_debug("Browse mode: Leaving an empty view");
+            let (loaded, back_to) = match view_context.clone() {
+                ViewContext::Season { season_id, series_name, .. } => {
+                    let loaded = database.get_season_by_id(season_id).and_then(|(_season, series_id)| {
+                        database.get_entries_for_series(series_id)
+                            .map(|loaded| (loaded, ViewContext::Series { series_id, series_name }))
+                    });
+                    (loaded, "series")
                 }
-                while i > 0 && search.chars().nth(i - 1).unwrap() != ' ' {
-                    i -= 1;
+                _ => (database.get_entries().map(|loaded| (loaded, ViewContext::TopLevel)), "library"),
+            };
+            match loaded {
+                Ok((loaded, context)) => {
+                    *entries = loaded;
+                    *filtered_entries = entries.clone();
+                    *current_item = 0;
+                    *view_context = context;
                 }
-                *edit_cursor_pos = i;
-                *redraw = true;
+                Err(e) => report_database_error(&format!("Failed to open the {}", back_to), &e, notifications),
             }
+            *redraw = true;
         }
-        KeyCode::Left if *filter_mode => {
-            if *edit_cursor_pos > 0 {
-                *edit_cursor_pos -= 1;
-            }
+        // Esc in the library with nothing to clear quits
+        Action::Back => return Ok(false),
+        Action::FilterWordLeft => {
+            // Jump back by words (separated by spaces)
+            TextInput::new(search, edit_cursor_pos).word_left();
+            *redraw = true;
+        }
+        Action::FilterCursorLeft => {
+            TextInput::new(search, edit_cursor_pos).left();
             *redraw = true;
         }
-        KeyCode::Right if modifiers.contains(event::KeyModifiers::CONTROL) && *filter_mode => {
+        Action::FilterWordRight => {
             // Jump forward by words (separated by spaces)
-            if *edit_cursor_pos < search.len() {
-                let mut i = *edit_cursor_pos;
-                while i < search.len() && search.chars().nth(i).unwrap() != ' ' {
-                    i += 1;
-                }
-                while i < search.len() && search.chars().nth(i).unwrap() == ' ' {
-                    i += 1;
-                }
-                *edit_cursor_pos = i;
-                *redraw = true;
-            }
+            TextInput::new(search, edit_cursor_pos).word_right();
+            *redraw = true;
         }
-        KeyCode::Right if *filter_mode => {
-            if *edit_cursor_pos < search.len() {
-                *edit_cursor_pos += 1;
-            }
+        Action::FilterCursorRight => {
+            TextInput::new(search, edit_cursor_pos).right();
             *redraw = true;
         }
-        KeyCode::Home if *filter_mode => {
-            *edit_cursor_pos = 0;
+        Action::FilterHome => {
+            TextInput::new(search, edit_cursor_pos).home();
             *redraw = true;
         }
-        KeyCode::End if *filter_mode => {
-            *edit_cursor_pos = search.len();
+        Action::FilterEnd => {
+            TextInput::new(search, edit_cursor_pos).end();
             *redraw = true;
         }
-        KeyCode::Backspace if *filter_mode => {
+        Action::FilterBackspace => {
             // Remove the character BEFORE the cursor position
-            if *edit_cursor_pos > 0 {
-                search.remove(*edit_cursor_pos - 1);
-                *edit_cursor_pos -= 1;
-                *redraw = true;
-            }
+            *redraw |= TextInput::new(search, edit_cursor_pos).backspace();
         }
-        KeyCode::Delete if *filter_mode => {
+        Action::FilterDelete => {
             // Remove the character AT the cursor position
-            if *edit_cursor_pos < search.len() {
-                search.remove(*edit_cursor_pos);
-                *redraw = true;
-            }
+            *redraw |= TextInput::new(search, edit_cursor_pos).delete();
         }
-        KeyCode::Char(c) if *filter_mode => {
+        Action::FilterInsert(c) => {
             // Insert character at cursor position
-            search.insert(*edit_cursor_pos, c);
-            *edit_cursor_pos += 1;
+            TextInput::new(search, edit_cursor_pos).insert(c);
             *redraw = true;
         }
         _ => {}
@@ -1113,22 +1495,298 @@ pub fn handle_browse_mode(
     Ok(true)
 }
 
-pub fn handle_series_select_mode(
-    code: KeyCode,
-    series_selection: &mut Option<usize>,
-    mode: &mut Mode,
-    redraw: &mut bool,
-    series: &mut [Series],
+/// Carry out a command sent through the remote control socket, returning the text of its reply
+pub fn handle_remote_command(command: RemoteCommand, state: &mut AppState, context: &AppContext) -> Result<String, String> {
+    let AppContext { config, database, resolver, tx, .. } = *context;
+    let AppState {
+        ref mut entries,
+        ref mut filtered_entries,
+        ref view_context,
+        ref mut playing_file,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
+    match command {
+        RemoteCommand::Play(episode_id) => {
+            if playing_file.is_some() {
+                return Err("a video is already playing".to_string());
+            }
+            let details = database.get_episode_detail(episode_id).map_err(|e| format!("no episode {}: {}", episode_id, e))?;
+            let versions = database.get_episode_versions(episode_id, resolver).map_err(|e| e.to_string())?;
+            let version = versions
+                .get(preferred_version_index(&versions, config.preferred_version.as_deref()))
+                .ok_or_else(|| format!("episode {} has no file", episode_id))?;
+            let location = version.location.clone();
+            play_episode(episode_id, &details.title, &location, &details, state, context);
+            match state.playing_file {
+                Some(_) => Ok(format!("playing {}", episode_id)),
+                None => Err("the video player couldn't be started".to_string()),
+            }
+        }
+        RemoteCommand::ToggleWatched(episode_id) => {
+            let now_watched = database.toggle_watched_status(episode_id).map_err(|e| format!("no episode {}: {}", episode_id, e))?;
+            logger::log_info(&format!("Toggled watched status for episode {} from the remote control (now: {})", episode_id, now_watched));
+            if now_watched {
+                fire_watched_hook(database, episode_id, resolver, notifications);
+            }
+            reload_entries(database, view_context, entries, notifications);
+            *filtered_entries = entries.clone();
+            *redraw = true;
+            Ok(if now_watched { "watched".to_string() } else { "unwatched".to_string() })
+        }
+        RemoteCommand::Rescan => {
+            let root = resolver.get_root_dir();
+            if crate::scan::spawn_rescan(root.to_path_buf(), config, Arc::clone(database), resolver.clone(), tx.clone()) {
+                Ok("rescan started".to_string())
+            } else {
+                Err("a rescan is already running".to_string())
+            }
+        }
+        RemoteCommand::Status => Ok(match playing_file {
+            Some(file) => format!("playing {}", location::display(file)),
+            None => "idle".to_string(),
+        }),
+    }
+}
+
+/// Run the on_watched hook for an episode just marked watched
+fn fire_watched_hook(database: &Database, episode_id: usize, resolver: &PathResolver, notifications: &mut Notifications) {
+    if !hooks::handles(HookEvent::Watched) {
+        return;
+    }
+    let result = database.get_episode_absolute_location(episode_id, resolver)
+        .map_err(|e| e.to_string())
+        .and_then(|absolute_location| hooks::fire(database, HookEvent::Watched, episode_id, &location::decode(&absolute_location)));
+    if let Err(e) = result {
+        notifications.warn(e);
+    }
+}
+
+/// Launch the video player on one file of an episode and track its progress until the player exits
+fn play_episode(
     episode_id: usize,
-    episode_detail: &mut EpisodeDetail,
-    entries: &mut Vec<Entry>,
-    filtered_entries: &mut Vec<Entry>,
-    view_context: &ViewContext,
-    last_action: &mut Option<crate::util::LastAction>,
-    new_series: &mut String,
-    edit_cursor_pos: &mut usize,
-    _first_series: &mut usize,
+    name: &str,
+    absolute_location: &str,
+    edit_details: &EpisodeDetail,
+    state: &mut AppState,
+    context: &AppContext,
 ) {
+    let AppContext { config, database, tx, .. } = *context;
+    let AppState {
+        ref mut playing_file,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
+    // Log video playback
+    logger::log_info(&format!("Playing video: {} ({})", name, absolute_location));
+
+    // Mark episode as unwatched when starting playback
+    if let Err(e) = database.mark_episode_unwatched(episode_id) {
+        logger::log_warn(&format!("Failed to mark episode {} as unwatched: {}", episode_id, e));
+    }
+    if let Err(e) = hooks::fire(database, HookEvent::Play, episode_id, &location::decode(absolute_location)) {
+        notifications.warn(e);
+    }
+
+    // Set status message
+    notifications.info(format!("Playing video: {}", name));
+    *redraw = true;
+
+    // Create player plugin based on configured video player
+    let plugin = create_player_plugin(&config.video_player);
+
+    // Query existing progress for resume functionality
+    let mut resume_from = 0;
+    let start_time = match database.get_episode_progress(episode_id) {
+        Ok(Some(0)) => {
+            // Progress is explicitly 0 - start from beginning and override any watch-later file
+            logger::log_info("Starting video from beginning (progress reset)");
+            Some(0)
+        }
+        Ok(Some(progress_seconds)) if progress_seconds > 0 => {
+            // Non-zero progress - let Celluloid handle resume from watch-later file
+            logger::log_info(&format!("Resuming video (progress: {}s, using Celluloid's watch-later)", progress_seconds));
+            resume_from = progress_seconds;
+            None
+        }
+        Ok(Some(_)) => {
+            // Shouldn't reach here, but handle it
+            logger::log_info("Starting video from beginning");
+            Some(0)
+        }
+        Ok(None) => {
+            // No progress data - let Celluloid handle resume from its watch-later file
+            logger::log_info("Starting video (no progress data)");
+            None
+        }
+        Err(e) => {
+            logger::log_warn(&format!("Failed to get progress for episode {}: {}. Starting from beginning.", episode_id, e));
+            None
+        }
+    };
+
+    // Launch player using plugin
+    let (command, args) = plugin.launch_command(&location::decode(absolute_location), start_time);
+
+    match std::process::Command::new(&command)
+        .args(&args)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+    {
+        Ok(player_process) => {
+            *playing_file = Some(absolute_location.to_string());
+            logger::log_info(&format!("Video player launched: {} {:?}", command, args));
+            crate::usage::record_action("play");
+
+            // Spawn a thread to monitor progress and wait for player to finish
+            let tx_clone = tx.clone();
+            let database = Arc::clone(database);
+            let episode_id_clone = episode_id;
+            let absolute_location_clone = absolute_location.to_string();
+            let watched_threshold = config.watched_threshold;
+            let episode_duration = if !edit_details.length.is_empty() && edit_details.length != "0" {
+                edit_details.length.parse::<u64>().unwrap_or(0)
+            } else {
+                0
+            };
+            let playing = NowPlaying::new(episode_id, edit_details, &location::decode(absolute_location), resume_from);
+
+            thread::spawn(move || {
+                use std::time::Duration;
+
+                let mut player_process = player_process;
+                let plugin = create_player_plugin(&command);
+
+                // Listed on the desktop until the player exits and this is dropped
+                let _now_playing = now_playing::publish(playing);
+
+                // Monitor progress while player is running
+                loop {
+                    // Check if player is still running
+                    match player_process.try_wait() {
+                        Ok(Some(exit_status)) => {
+                            // Player has exited
+                            logger::log_info(&format!("Video player exited with status: {}", exit_status));
+
+                            // Give mpv a moment to write the watch-later file
+                            thread::sleep(Duration::from_millis(500));
+
+                            if let Err(e) = database.record_playback_finished(episode_id_clone) {
+                                logger::log_error(&format!("Failed to record playback for episode {}: {}", episode_id_clone, e));
+                            }
+
+                            // Get final position
+                            match plugin.get_final_position(&location::decode(&absolute_location_clone)) {
+                                Ok(Some(final_position)) => {
+                                    logger::log_info(&format!("Retrieved final position: {}s", final_position));
+
+                                    // Update database with final progress
+                                    if let Err(e) = database.update_episode_progress(episode_id_clone, final_position) {
+                                        logger::log_error(&format!("Failed to update progress for episode {}: {}", episode_id_clone, e));
+                                    }
+
+                                    // Check if watched threshold is met
+                                    if episode_duration > 0 {
+                                        let progress_percentage = (final_position as f64 / episode_duration as f64) * 100.0;
+                                        let threshold = watched_threshold as f64;
+
+                                        if progress_percentage >= threshold {
+                                            logger::log_info(&format!(
+                                                "Episode {} reached watched threshold ({:.1}% >= {:.1}%), marking as watched",
+                                                episode_id_clone, progress_percentage, threshold
+                                            ));
+
+                                            if let Err(e) = database.mark_episode_watched_with_timestamp(episode_id_clone) {
+                                                logger::log_error(&format!("Failed to mark episode {} as watched: {}", episode_id_clone, e));
+                                            } else {
+                                                // Hook failures are in the log; the player thread can't show them
+                                                let path = location::decode(&absolute_location_clone);
+                                                let _ = hooks::fire(&database, HookEvent::Watched, episode_id_clone, &path);
+
+                                                // Delete watch-later file so next playback starts from beginning
+                                                if let Err(e) = plugin.delete_watch_later_file(&location::decode(&absolute_location_clone)) {
+                                                    logger::log_warn(&format!("Failed to delete watch-later file: {}", e));
+                                                }
+                                            }
+                                        } else {
+                                            logger::log_info(&format!(
+                                                "Episode {} progress: {:.1}% (threshold: {:.1}%)",
+                                                episode_id_clone, progress_percentage, threshold
+                                            ));
+                                        }
+                                    }
+                                }
+                                Ok(None) => {
+                                    logger::log_info("No final position available from player plugin");
+                                }
+                                Err(e) => {
+                                    logger::log_warn(&format!("Failed to retrieve final position: {}", e));
+                                }
+                            }
+
+                            // Always clean up watch-later files, even if we couldn't read position
+                            // This prevents stale/empty files from causing issues
+                            if let Err(e) = plugin.cleanup_progress_files() {
+                                logger::log_warn(&format!("Failed to cleanup progress files: {}", e));
+                            }
+
+                            // Notify main thread that playback is complete
+                            tx_clone.send(AppEvent::PlaybackFinished).ok();
+                            break;
+                        }
+                        Ok(None) => {
+                            // Player is still running, just sleep and check again
+                            // Don't read watch-later file while player is running to avoid file locking issues
+                            thread::sleep(Duration::from_secs(10));
+                        }
+                        Err(e) => {
+                            logger::log_error(&format!("Error checking player status: {}", e));
+                            tx_clone.send(AppEvent::PlaybackFinished).ok();
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+        Err(e) => {
+            // Keep browsing; the player setting is the usual culprit
+            logger::log_error(&format!("Failed to start video player for {}: {}", name, e));
+            notifications.error(format!("Failed to start video player '{}': {}", config.video_player, e));
+        }
+    }
+}
+
+pub fn handle_series_select_mode(code: KeyCode, state: &mut AppState, context: &AppContext) {
+    let database = context.database;
+    let Some(episode_id) = state.selected_entry_id.or_else(|| state.marked_episodes.iter().next().copied()) else {
+        // selected entry is a series, change mode back to browse
+        state.mode = Mode::Browse;
+        state.redraw = true;
+        return;
+    };
+    let AppState {
+        ref mut series_selection,
+        ref mut mode,
+        ref mut redraw,
+        ref mut series,
+        edit_details: ref mut episode_detail,
+        ref mut entries,
+        ref mut filtered_entries,
+        ref view_context,
+        ref mut last_action,
+        ref mut new_series,
+        ref mut edit_cursor_pos,
+        ref mut notifications,
+        ref mut marked_episodes,
+        ref mut modals,
+        current_item,
+        first_entry,
+        menu_selection,
+        ..
+    } = *state;
     match code {
         KeyCode::Up | KeyCode::Char('k') => {
             if !series.is_empty() {
@@ -1151,15 +1809,38 @@ pub fn handle_series_select_mode(
         }
         KeyCode::Enter => {
             // save the series id to the episode, then return to browse mode
-            let selected_series = &series[series_selection.unwrap()];
+            let Some(selected_series) = series_selection.and_then(|index| series.get(index)) else {
+                // Nothing to assign until a series is created with +
+                return;
+            };
             let series_id = selected_series.id;
             let series_name = selected_series.name.clone();
             
-            *episode_detail =
-                database::assign_series(series_id, episode_id).expect("Failed to assign series");
-            
-            // Log series assignment
-            logger::log_info(&format!("Assigned episode {} to series '{}'", episode_id, series_name));
+            // Marked episodes are all assigned; otherwise just the selected one
+            let mut episode_ids: Vec<usize> = marked_episodes.drain().collect();
+            episode_ids.sort_unstable();
+            if episode_ids.is_empty() {
+                episode_ids.push(episode_id);
+            }
+            for episode_id in &episode_ids {
+                *episode_detail = match database.assign_series(series_id, *episode_id) {
+                    Ok(detail) => detail,
+                    Err(e) => {
+                        report_database_error(&format!("Failed to assign series '{}'", series_name), &e, notifications);
+                        reload_entries(database, view_context, entries, notifications);
+                        *filtered_entries = entries.clone();
+                        *mode = Mode::Browse;
+                        *redraw = true;
+                        return;
+                    }
+                };
+
+                // Log series assignment
+                logger::log_info(&format!("Assigned episode {} to series '{}'", episode_id, series_name));
+            }
+            if episode_ids.len() > 1 {
+                notifications.toast(format!("Assigned {} episodes to '{}'", episode_ids.len(), series_name));
+            }
             
             // Update last_action with the series assignment
             *last_action = Some(crate::util::LastAction::SeriesAssignment {
@@ -1168,23 +1849,7 @@ pub fn handle_series_select_mode(
             });
             
             // Reload entries based on current view context
-            *entries = match view_context {
-                ViewContext::TopLevel => {
-                    database::get_entries().unwrap_or_else(|_| {
-                        database::get_entries().expect("Failed to get entries")
-                    })
-                }
-                ViewContext::Series { series_id, .. } => {
-                    database::get_entries_for_series(*series_id).unwrap_or_else(|_| {
-                        database::get_entries().expect("Failed to get entries")
-                    })
-                }
-                ViewContext::Season { season_id, .. } => {
-                    database::get_entries_for_season(*season_id).unwrap_or_else(|_| {
-                        database::get_entries().expect("Failed to get entries")
-                    })
-                }
-            };
+            reload_entries(database, view_context, entries, notifications);
             *filtered_entries = entries.clone();
             *mode = Mode::Browse;
             *redraw = true;
@@ -1194,45 +1859,76 @@ pub fn handle_series_select_mode(
             *series_selection = None;
             *new_series = String::new();
             *edit_cursor_pos = 0;
-            *mode = Mode::SeriesCreate;
+            modals.open(mode, Mode::SeriesCreate, current_item, first_entry, menu_selection);
             *redraw = true;
         }
         KeyCode::Esc => {
             *series_selection = None;
-            // Return to browse mode
-            *mode = Mode::Browse;
-            *redraw = true;
+            // Return to the screen the picker was opened from
+            state.close_modal();
         }
         _ => {}
     }
 }
 
-pub fn handle_series_create_mode(
-    code: KeyCode,
-    modifiers: event::KeyModifiers,
-    mode: &mut Mode,
-    redraw: &mut bool,
-    new_series: &mut String,
-    edit_cursor_pos: &mut usize,
-    series: &mut Vec<Series>,
-    episode_id: usize,
-    episode_detail: &mut EpisodeDetail,
-    entries: &mut Vec<Entry>,
-    filtered_entries: &mut Vec<Entry>,
-    view_context: &ViewContext,
-    last_action: &mut Option<crate::util::LastAction>,
-    first_series: &mut usize,
-) {
+pub fn handle_series_create_mode(code: KeyCode, modifiers: event::KeyModifiers, state: &mut AppState, context: &AppContext) {
+    let database = context.database;
+    let Some(episode_id) = state.selected_entry_id.or_else(|| state.marked_episodes.iter().next().copied()) else {
+        // selected entry is a series, change mode back to browse
+        state.mode = Mode::Browse;
+        state.redraw = true;
+        return;
+    };
+    let AppState {
+        ref mut mode,
+        ref mut redraw,
+        ref mut new_series,
+        ref mut edit_cursor_pos,
+        ref mut series,
+        edit_details: ref mut episode_detail,
+        ref mut entries,
+        ref mut filtered_entries,
+        ref view_context,
+        ref mut last_action,
+        ref mut first_series,
+        ref mut notifications,
+        ref mut marked_episodes,
+        ..
+    } = *state;
     match code {
         KeyCode::Enter => {
+            // Marked episodes are all assigned; otherwise just the selected one
+            let mut episode_ids: Vec<usize> = marked_episodes.drain().collect();
+            episode_ids.sort_unstable();
+            let episode_id = episode_ids.first().copied().unwrap_or(episode_id);
+
             // save the new series to the database
-            *episode_detail = database::create_series_and_assign(new_series, episode_id)
-                .expect("Failed to create series");
+            *episode_detail = match database.create_series_and_assign(new_series, episode_id) {
+                Ok(detail) => detail,
+                Err(e) => {
+                    report_database_error(&format!("Failed to create series '{}'", new_series), &e, notifications);
+                    *mode = Mode::Browse;
+                    *redraw = true;
+                    return;
+                }
+            };
 
             // Log series creation
             if let Some(series) = &episode_detail.series {
                 logger::log_info(&format!("Created new series '{}' and assigned episode {}", 
                     series.name, episode_id));
+
+                let mut assigned = 1;
+                for other_id in episode_ids.iter().skip(1) {
+                    if let Err(e) = database.assign_series(series.id, *other_id) {
+                        report_database_error(&format!("Failed to assign series '{}'", series.name), &e, notifications);
+                        break;
+                    }
+                    assigned += 1;
+                }
+                if episode_ids.len() > 1 && assigned == episode_ids.len() {
+                    notifications.toast(format!("Assigned {} episodes to '{}'", episode_ids.len(), series.name));
+                }
             }
 
             // Update last_action with the series assignment
@@ -1244,25 +1940,12 @@ pub fn handle_series_create_mode(
             }
 
             // reload the series list
-            *series = database::get_all_series().expect("Failed to get series");
+            match database.get_all_series() {
+                Ok(loaded) => *series = loaded,
+                Err(e) => report_database_error("Failed to load series", &e, notifications),
+            }
             // Reload entries based on current view context
-            *entries = match view_context {
-                ViewContext::TopLevel => {
-                    database::get_entries().unwrap_or_else(|_| {
-                        database::get_entries().expect("Failed to get entries")
-                    })
-                }
-                ViewContext::Series { series_id, .. } => {
-                    database::get_entries_for_series(*series_id).unwrap_or_else(|_| {
-                        database::get_entries().expect("Failed to get entries")
-                    })
-                }
-                ViewContext::Season { season_id, .. } => {
-                    database::get_entries_for_season(*season_id).unwrap_or_else(|_| {
-                        database::get_entries().expect("Failed to get entries")
-                    })
-                }
-            };
+            reload_entries(database, view_context, entries, notifications);
             *filtered_entries = entries.clone();
             *mode = Mode::Browse;
             *redraw = true;
@@ -1272,75 +1955,44 @@ pub fn handle_series_create_mode(
             *new_series = String::new();
             *edit_cursor_pos = 0;
             *first_series = 0;
-            *mode = Mode::SeriesSelect;
-            *redraw = true;
+            state.close_modal();
         }
         KeyCode::Left if modifiers.contains(event::KeyModifiers::CONTROL) => {
             // jump back in the current field by words (separated by spaces)
-            if *edit_cursor_pos > 0 {
-                let mut i = *edit_cursor_pos - 1;
-                while i > 0 && new_series.chars().nth(i - 1).unwrap() == ' ' {
-                    i -= 1;
-                }
-                while i > 0 && new_series.chars().nth(i - 1).unwrap() != ' ' {
-                    i -= 1;
-                }
-                *edit_cursor_pos = i;
-                *redraw = true;
-            }
+            TextInput::new(new_series, edit_cursor_pos).word_left();
+            *redraw = true;
         }
         KeyCode::Left => {
-            if *edit_cursor_pos > 0 {
-                *edit_cursor_pos -= 1;
-            }
+            TextInput::new(new_series, edit_cursor_pos).left();
             *redraw = true;
         }
         KeyCode::Right if modifiers.contains(event::KeyModifiers::CONTROL) => {
             // jump forward in the current field by words (separated by spaces)
-            if *edit_cursor_pos < new_series.len() {
-                let mut i = *edit_cursor_pos;
-                while i < new_series.len() && new_series.chars().nth(i).unwrap() != ' ' {
-                    i += 1;
-                }
-                while i < new_series.len() && new_series.chars().nth(i).unwrap() == ' ' {
-                    i += 1;
-                }
-                *edit_cursor_pos = i;
-                *redraw = true;
-            }
+            TextInput::new(new_series, edit_cursor_pos).word_right();
+            *redraw = true;
         }
         KeyCode::Right => {
-            if *edit_cursor_pos < new_series.len() {
-                *edit_cursor_pos += 1;
-            }
+            TextInput::new(new_series, edit_cursor_pos).right();
             *redraw = true;
         }
         KeyCode::Home => {
-            *edit_cursor_pos = 0;
+            TextInput::new(new_series, edit_cursor_pos).home();
             *redraw = true;
         }
         KeyCode::End => {
-            *edit_cursor_pos = new_series.len();
+            TextInput::new(new_series, edit_cursor_pos).end();
             *redraw = true;
         }
         KeyCode::Backspace => {
-            // removes the character BEFORE the edit_cursor_pos as long as edit_cursor_pos is > 0, otherwise it does nothing
-            if *edit_cursor_pos > 0 {
-                new_series.remove(*edit_cursor_pos - 1);
-                *edit_cursor_pos -= 1;
-                *redraw = true;
-            }
+            // removes the character BEFORE the edit_cursor_pos, if there is one
+            *redraw |= TextInput::new(new_series, edit_cursor_pos).backspace();
         }
         KeyCode::Delete => {
-            // removes the character AT the edit_cursor_pos as long as edit_cursor_pos is < the length of the field, otherwise it does nothing
-            if *edit_cursor_pos < new_series.len() {
-                new_series.remove(*edit_cursor_pos);
-                *redraw = true;
-            }
+            // removes the character AT the edit_cursor_pos, if there is one
+            *redraw |= TextInput::new(new_series, edit_cursor_pos).delete();
         }
         KeyCode::Char(c) => {
-            new_series.insert(*edit_cursor_pos, c);
-            *edit_cursor_pos += 1;
+            TextInput::new(new_series, edit_cursor_pos).insert(c);
             *redraw = true;
         }
         _ => {}
@@ -1349,86 +2001,47 @@ pub fn handle_series_create_mode(
 
 pub fn handle_menu_mode(
     code: KeyCode,
-    menu_items: &[MenuItem],
-    menu_selection: &mut usize,
-    mode: &mut Mode,
-    redraw: &mut bool,
-    remembered_item: usize,
-    filtered_entries: &mut Vec<Entry>,
-    entries: &mut Vec<Entry>,
-    edit_details: &mut EpisodeDetail,
-    season_number: &mut Option<usize>,
-    view_context: &ViewContext,
-    last_action: &mut Option<crate::util::LastAction>,
-    edit_field: &mut EpisodeField,
-    edit_cursor_pos: &mut usize,
-    original_edit_details: &mut Option<EpisodeDetail>,
-    dirty_fields: &mut HashSet<EpisodeField>,
-    series: &mut Vec<Series>,
-    series_selection: &mut Option<usize>,
-    first_series: &mut usize,
-    config: &Config,
-    resolver: &PathResolver,
-    status_message: &mut String,
+    state: &mut AppState,
+    context: &AppContext,
     buffer_manager: &mut crate::buffer::BufferManager,
-    search_query: &mut String,
 ) {
+    let menu_items = state.menu_items();
+    let remembered_item = state.remembered_item;
+
     // Handle navigation
     match code {
         KeyCode::Up => {
             if menu_items.is_empty() {
                 return;
             }
-            if *menu_selection == 0 {
-                *menu_selection = menu_items.len() - 1;
+            if state.menu_selection == 0 {
+                state.menu_selection = menu_items.len() - 1;
             } else {
-                *menu_selection -= 1;
+                state.menu_selection -= 1;
             }
-            *redraw = true;
+            state.redraw = true;
         }
         KeyCode::Down => {
             if menu_items.is_empty() {
                 return;
             }
-            *menu_selection = (*menu_selection + 1) % menu_items.len();
-            *redraw = true;
+            state.menu_selection = (state.menu_selection + 1) % menu_items.len();
+            state.redraw = true;
         }
         KeyCode::Enter => {
             if menu_items.is_empty() {
                 return;
             }
             // Execute the selected menu item
-            let selected_action = &menu_items[*menu_selection].action;
-            execute_menu_action(
-                selected_action,
-                mode,
-                redraw,
-                remembered_item,
-                filtered_entries,
-                entries,
-                edit_details,
-                season_number,
-                view_context,
-                last_action,
-                edit_field,
-                edit_cursor_pos,
-                original_edit_details,
-                dirty_fields,
-                series,
-                series_selection,
-                first_series,
-                config,
-                resolver,
-                status_message,
-                search_query,
-            );
+            let selected_action = &menu_items[state.menu_selection].action;
+            execute_menu_action(selected_action, remembered_item, state, context);
         }
         KeyCode::Esc => {
-            // Close menu and return to Browse mode
-            *mode = Mode::Browse;
+            // Close menu and return to the screen under it
+            state.close_modal();
             // Force full redraw to clear menu artifacts from detail panel
             buffer_manager.force_full_redraw();
-            *redraw = true;
+            state.redraw = true;
         }
         _ => {
             // Check if the key matches any hotkey
@@ -1436,31 +2049,9 @@ pub fn handle_menu_mode(
                 if let Some(hotkey) = &item.hotkey {
                     if *hotkey == code {
                         // Execute this menu item
-                        execute_menu_action(
-                            &item.action,
-                            mode,
-                            redraw,
-                            remembered_item,
-                            filtered_entries,
-                            entries,
-                            edit_details,
-                            season_number,
-                            view_context,
-                            last_action,
-                            edit_field,
-                            edit_cursor_pos,
-                            original_edit_details,
-                            dirty_fields,
-                            series,
-                            series_selection,
-                            first_series,
-                            config,
-                            resolver,
-                            status_message,
-                            search_query,
-                        );
+                        execute_menu_action(&item.action, remembered_item, state, context);
                         // Update menu selection to match the executed item
-                        *menu_selection = index;
+                        state.menu_selection = index;
                         return;
                     }
                 }
@@ -1469,39 +2060,321 @@ pub fn handle_menu_mode(
     }
 }
 
-fn execute_menu_action(
-    action: &MenuAction,
-    mode: &mut Mode,
-    redraw: &mut bool,
-    remembered_item: usize,
-    filtered_entries: &mut Vec<Entry>,
-    entries: &mut Vec<Entry>,
-    edit_details: &mut EpisodeDetail,
-    season_number: &mut Option<usize>,
-    view_context: &ViewContext,
-    last_action: &mut Option<crate::util::LastAction>,
-    edit_field: &mut EpisodeField,
-    edit_cursor_pos: &mut usize,
-    original_edit_details: &mut Option<EpisodeDetail>,
-    dirty_fields: &mut HashSet<EpisodeField>,
-    series: &mut Vec<Series>,
-    series_selection: &mut Option<usize>,
-    first_series: &mut usize,
-    config: &Config,
-    resolver: &PathResolver,
-    status_message: &mut String,
-    search_query: &mut String,
-) {
+/// Apply a batch menu action to every marked episode, then clear the marks
+fn execute_batch_action(action: &MenuAction, state: &mut AppState, context: &AppContext) {
+    let AppContext { config, database, resolver, .. } = *context;
+    let AppState {
+        ref mut marked_episodes,
+        ref mut entries,
+        ref mut filtered_entries,
+        ref view_context,
+        ref mut notifications,
+        ..
+    } = *state;
+    let mut episode_ids: Vec<usize> = marked_episodes.iter().copied().collect();
+    episode_ids.sort_unstable();
+
+    // Toggling a mixed selection marks everything watched; an all-watched selection is unwatched
+    let is_watched = |episode_id: usize| {
+        database.get_episode_detail(episode_id)
+            .map(|detail| detail.watched == "true")
+            .unwrap_or(false)
+    };
+    let watch = episode_ids.iter().any(|episode_id| !is_watched(*episode_id));
+
+    let mut changed = 0;
+    for episode_id in &episode_ids {
+        let result = match action {
+            MenuAction::ToggleWatched if is_watched(*episode_id) == watch => Ok(()),
+            MenuAction::ToggleWatched => database.toggle_watched_status(*episode_id).map(|now_watched| {
+                if now_watched {
+                    fire_watched_hook(database, *episode_id, resolver, notifications);
+                }
+                // Start the next playback from the beginning
+                if let Ok(absolute_location) = database.get_episode_absolute_location(*episode_id, resolver) {
+                    let plugin = create_player_plugin(&config.video_player);
+                    if let Err(e) = plugin.delete_watch_later_file(&location::decode(&absolute_location)) {
+                        logger::log_warn(&format!("Failed to delete watch-later file: {}", e));
+                    }
+                }
+            }),
+            MenuAction::ClearSeriesData => database.clear_series_data(*episode_id),
+            MenuAction::Delete => database.delete_episode(*episode_id),
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            let verb = match action {
+                MenuAction::ClearSeriesData => "clear series data of",
+                MenuAction::Delete => "delete",
+                _ => "update",
+            };
+            report_database_error(&format!("Failed to {} episode {}", verb, episode_id), &e, notifications);
+            break;
+        }
+        changed += 1;
+    }
+
+    if changed == episode_ids.len() {
+        notifications.toast(match action {
+            MenuAction::ToggleWatched if watch => format!("Marked {} episodes as watched", changed),
+            MenuAction::ToggleWatched => format!("Marked {} episodes as unwatched", changed),
+            MenuAction::ClearSeriesData => format!("Cleared series data from {} episodes", changed),
+            _ => format!("Deleted {} episodes", changed),
+        });
+    }
+    logger::log_info(&format!("Batch {} applied to {} of {} marked episodes", action.usage_key(), changed, episode_ids.len()));
+
+    marked_episodes.clear();
+    reload_entries(database, view_context, entries, notifications);
+    *filtered_entries = entries.clone();
+}
+
+/// Run a destructive action once the user has confirmed it
+fn execute_destructive_action(action: &MenuAction, remembered_item: usize, state: &mut AppState, context: &AppContext) {
+    let AppContext { database, resolver, .. } = *context;
+    let AppState {
+        ref mut mode,
+        ref mut redraw,
+        ref mut filtered_entries,
+        ref mut entries,
+        ref view_context,
+        ref mut notifications,
+        ref playing_file,
+        ..
+    } = *state;
+    match action {
+        MenuAction::ClearSeriesData => {
+            // Clear series, season, and episode number for the remembered episode
+            if let Some(&Entry::Episode { episode_id, .. }) = filtered_entries.get(remembered_item) {
+                if let Err(e) = database.clear_series_data(episode_id) {
+                    report_database_error(&format!("Failed to clear series data for episode {}", episode_id), &e, notifications);
+                    *mode = Mode::Browse;
+                    *redraw = true;
+                    return;
+                }
+
+                // Reload entries based on current view context
+                reload_entries(database, view_context, entries, notifications);
+                *filtered_entries = entries.clone();
+                *mode = Mode::Browse;
+                *redraw = true;
+            }
+        }
+        MenuAction::UnwatchAll => {
+            // Determine scope based on view_context
+            let result = match view_context {
+                ViewContext::Season { season_id, .. } => database.unwatch_all_in_season(*season_id),
+                ViewContext::Series { series_id, .. } => database.unwatch_all_in_series(*series_id),
+                ViewContext::TopLevel => database.unwatch_all_standalone(),
+                ViewContext::Extras => database.unwatch_all_extras(),
+            };
+            if let Err(e) = result {
+                report_database_error("Failed to unwatch episodes", &e, notifications);
+            }
+
+            // Reload entries based on current view context
+            reload_entries(database, view_context, entries, notifications);
+            *filtered_entries = entries.clone();
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::Delete => {
+            // Delete the episode from the database
+            if let Some(Entry::Episode { episode_id, name, .. }) = filtered_entries.get(remembered_item) {
+                // Delete the episode
+                if let Err(e) = database.delete_episode(*episode_id) {
+                    report_database_error(&format!("Failed to delete '{}'", name), &e, notifications);
+                    *mode = Mode::Browse;
+                    *redraw = true;
+                    return;
+                }
+            
+                // Log deletion with episode details
+                logger::log_info(&format!("Deleted episode {} ({})", episode_id, name));
+
+                // Reload entries based on current view context
+                reload_entries(database, view_context, entries, notifications);
+                *filtered_entries = entries.clone();
+                *mode = Mode::Browse;
+                *redraw = true;
+            }
+        }
+        MenuAction::DeleteFile => {
+            // Remove the remembered episode's files from disk, then the episode from the library
+            if let Some(Entry::Episode { episode_id, name, .. }) = filtered_entries.get(remembered_item) {
+                let versions = match database.get_episode_versions(*episode_id, resolver) {
+                    Ok(versions) => versions,
+                    Err(e) => {
+                        report_database_error(&format!("Failed to find the files of '{}'", name), &e, notifications);
+                        return;
+                    }
+                };
+                let paths: Vec<PathBuf> = versions.into_iter().map(|version| location::decode(&version.location)).collect();
+                if playing_file.as_deref().is_some_and(|playing| paths.iter().any(|path| *path == location::decode(playing))) {
+                    notifications.warn(format!("'{}' is playing; close the player before deleting it", name));
+                    return;
+                }
+                match crate::file_removal::remove_episode_files(&paths, resolver) {
+                    Ok(removal) => {
+                        if let Err(e) = database.delete_episode(*episode_id) {
+                            report_database_error(&format!("Removed the files of '{}' but failed to delete it", name), &e, notifications);
+                        } else {
+                            notifications.info(removal.summary(name));
+                        }
+                    }
+                    Err(message) => {
+                        logger::log_warn(&message);
+                        notifications.error(message);
+                        return;
+                    }
+                }
+                reload_entries(database, view_context, entries, notifications);
+                *filtered_entries = entries.clone();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Report a finished background rescan, reload the view, and offer any archives it turned up
+/// Over Browse the report screen opens, followed by the archive review once it is closed
+/// An automatic rescan leaves the screen and archives alone, and only notifies about new videos
+/// or a change in the missing files
+pub fn finish_rescan(mut report: ScanReport, state: &mut AppState, config: &Config, database: &Database) {
+    let automatic = std::mem::take(&mut state.automatic_rescan);
+    let AppState {
+        ref mut mode,
+        ref mut entries,
+        ref mut filtered_entries,
+        ref view_context,
+        ref mut notifications,
+        ref mut scan_report,
+        ref mut scan_report_scroll,
+        ref mut pending_archives,
+        ref mut selected_archive,
+        ref mut redraw,
+        ..
+    } = *state;
+    if !automatic {
+        notifications.info(report.summary());
+    } else if report.imported > 0 || report.missing_files != scan_report.missing_files {
+        notifications.info(format!(
+            "Automatic rescan found {} new videos and {} missing files",
+            report.imported,
+            report.missing_files.len()
+        ));
+    }
+
+    // Reload entries based on current view context
+    reload_entries(database, view_context, entries, notifications);
+    *filtered_entries = entries.clone();
+
+    // Offer to extract archives holding videos instead of skipping them
+    if !automatic {
+        pending_archives.clear();
+    }
+    if !report.cancelled && !automatic && !config.archive_list_command.trim().is_empty() {
+        let scan_rules = config.scan_rules_for(&report.root);
+        let scanned = report.folder.as_deref().unwrap_or(&report.root);
+        *pending_archives = archive::find_pending_archives(scanned, &config.archive_list_command, &scan_rules);
+        if !pending_archives.is_empty() {
+            logger::log_info(&format!("Rescan found {} archives with videos", pending_archives.len()));
+            notifications.info(format!("{} archives with videos found", pending_archives.len()));
+            *selected_archive = 0;
+        }
+    }
+
+    // Offer the series and seasons the folders suggest, to be confirmed on the report
+    if !report.cancelled && !automatic && config.infer_series_from_folders {
+        match database.get_unassigned_episodes() {
+            Ok(unassigned) => report.series_proposals = folder_series::series_proposals(unassigned),
+            Err(e) => report_database_error("Failed to look for series in folder names", &e, notifications),
+        }
+    }
+
+    *scan_report = report;
+    *scan_report_scroll = 0;
+    if *mode == Mode::Browse && !automatic {
+        *mode = Mode::ScanReport;
+        *redraw = true;
+    }
+}
+
+fn execute_menu_action(action: &MenuAction, remembered_item: usize, state: &mut AppState, context: &AppContext) {
+    let AppContext { config, database, resolver, tx, config_path } = *context;
+    let AppState {
+        ref mut mode,
+        ref mut redraw,
+        ref mut filtered_entries,
+        ref mut entries,
+        ref mut edit_details,
+        ref mut season_number,
+        ref view_context,
+        ref mut last_action,
+        ref mut edit_field,
+        ref mut edit_cursor_pos,
+        ref mut original_edit_details,
+        ref mut dirty_fields,
+        ref mut series,
+        ref mut series_selection,
+        ref mut first_series,
+        ref mut notifications,
+        ref mut search_query,
+        ref mut sort_mode,
+        ref mut search,
+        ref mut pinned_filters,
+        ref mut tutorial,
+        ref mut marked_episodes,
+        ref mut pending_confirmation,
+        ref mut modals,
+        ref mut relocate_path,
+        ref mut relocation_check,
+        ref mut download_url,
+        ref mut entry_path,
+        ref mut entry_completions,
+        ref mut scan_folder_entry,
+        ref mut duplicate_groups,
+        ref mut selected_duplicate,
+        current_item,
+        first_entry,
+        menu_selection,
+        ..
+    } = *state;
+    // Destructive actions wait for a yes in the confirmation dialog, unless the config turns it off
+    let dialog = confirmation_dialog(action, filtered_entries.get(remembered_item), view_context, marked_episodes.len())
+        .filter(|_| menu::asks_confirmation(action, config));
+    if let Some(dialog) = dialog {
+        *pending_confirmation = Some(PendingConfirmation {
+            action: action.clone(),
+            remembered_item,
+            dialog,
+        });
+        modals.open(mode, Mode::Confirm, current_item, first_entry, menu_selection);
+        *redraw = true;
+        return;
+    }
+
+    crate::usage::record_action(action.usage_key());
+
+    // Batch actions apply to the marked episodes instead of the remembered one
+    if !marked_episodes.is_empty() && is_batch_action(action) && !matches!(action, MenuAction::AssignToSeries) {
+        *mode = Mode::Browse;
+        *redraw = true;
+        execute_batch_action(action, state, context);
+        return;
+    }
+
     match action {
         MenuAction::Edit => {
             // Enter edit mode for the remembered episode
-            if let Entry::Episode { episode_id, .. } = filtered_entries[remembered_item] {
+            if let Some(&Entry::Episode { episode_id, .. }) = filtered_entries.get(remembered_item) {
                 *mode = Mode::Edit;
-                *edit_details = match database::get_episode_detail(episode_id) {
+                *edit_details = match database.get_episode_detail(episode_id) {
                     Ok(details) => details,
                     Err(e) => {
-                        logger::log_error(&format!("Failed to get episode details for episode {}: {}", episode_id, e));
-                        eprintln!("Error: Failed to load episode details: {}", e);
+                        report_database_error(&format!("Failed to load episode {}", episode_id), &e, notifications);
+                        *mode = Mode::Browse;
+                        *redraw = true;
                         return;
                     }
                 };
@@ -1510,15 +2383,16 @@ fn execute_menu_action(
                 // Check if episode has length = 0 or NULL, and extract if needed
                 if edit_details.length.is_empty() || edit_details.length == "0" {
                     // Resolve relative path to absolute path for extraction
-                    match database::get_episode_absolute_location(episode_id, resolver) {
+                    match database.get_episode_absolute_location(episode_id, resolver) {
                         Ok(absolute_location) => {
                             // Attempt to extract and update episode length (fails silently with log warning)
                             if video_metadata::extract_and_update_episode_length(
+                                database,
                                 episode_id,
-                                Path::new(&absolute_location)
+                                &location::decode(&absolute_location)
                             ).is_ok() {
                                 // Reload episode details to get updated length
-                                if let Ok(updated_details) = database::get_episode_detail(episode_id) {
+                                if let Ok(updated_details) = database.get_episode_detail(episode_id) {
                                     *edit_details = updated_details;
                                     *season_number = edit_details.season.as_ref().map(|season| season.number);
                                 }
@@ -1535,16 +2409,12 @@ fn execute_menu_action(
                 dirty_fields.clear();
 
                 // Auto-fill episode number if series is assigned but episode number is not
-                if edit_details.series.is_some()
-                    && season_number.is_some()
-                    && (edit_details.episode_number.is_empty() || edit_details.episode_number == "0")
-                {
+                let autofill_series = edit_details.series.as_ref().map(|series| series.id).filter(|_| {
+                    season_number.is_some() && (edit_details.episode_number.is_empty() || edit_details.episode_number == "0")
+                });
+                if let Some(series_id) = autofill_series {
                     // Calculate next available episode number
-                    let next_episode = database::get_next_available_episode_number(
-                        edit_details.series.as_ref().unwrap().id,
-                        *season_number,
-                    )
-                    .unwrap_or(1);
+                    let next_episode = database.get_next_available_episode_number(series_id, *season_number).unwrap_or(1);
 
                     // Pre-fill the episode number
                     edit_details.episode_number = next_episode.to_string();
@@ -1564,58 +2434,150 @@ fn execute_menu_action(
         }
         MenuAction::ToggleWatched => {
             // Toggle watched status for the remembered episode
-            if let Entry::Episode { episode_id, location, .. } = &filtered_entries[remembered_item] {
-                match database::toggle_watched_status(*episode_id) {
+            if let Some(Entry::Episode { episode_id, location, .. }) = filtered_entries.get(remembered_item) {
+                match database.toggle_watched_status(*episode_id) {
                     Ok(now_watched) => {
                         // Log watched status toggle
                         logger::log_info(&format!("Toggled watched status for episode {} (now: {})", episode_id, now_watched));
+                        if now_watched {
+                            fire_watched_hook(database, *episode_id, resolver, notifications);
+                        }
                         
                         // Always delete watch-later file when toggling so next playback starts from beginning
-                        let absolute_location = resolver.to_absolute(Path::new(location));
+                        let absolute_location = resolver.to_absolute(&location::decode(location));
                         let plugin = create_player_plugin(&config.video_player);
                         if let Err(e) = plugin.delete_watch_later_file(&absolute_location) {
                             logger::log_warn(&format!("Failed to delete watch-later file: {}", e));
                         }
                     }
                     Err(e) => {
-                        logger::log_error(&format!("Failed to toggle watched status for episode {}: {}", episode_id, e));
-                        eprintln!("Error: Failed to toggle watched status: {}", e);
+                        report_database_error(&format!("Failed to toggle watched for episode {}", episode_id), &e, notifications);
+                        *mode = Mode::Browse;
+                        *redraw = true;
                         return;
                     }
                 }
 
                 // Reload entries based on current view context
-                *entries = match view_context {
-                    ViewContext::TopLevel => database::get_entries().expect("Failed to get entries"),
-                    ViewContext::Series { series_id, .. } => database::get_entries_for_series(*series_id)
-                        .expect("Failed to get entries for series"),
-                    ViewContext::Season { season_id, .. } => database::get_entries_for_season(*season_id)
-                        .expect("Failed to get entries for season"),
-                };
+                reload_entries(database, view_context, entries, notifications);
                 *filtered_entries = entries.clone();
                 *mode = Mode::Browse;
                 *redraw = true;
             }
         }
         MenuAction::AssignToSeries => {
-            // Enter series selection mode for the remembered episode
-            if let Entry::Episode { .. } = filtered_entries[remembered_item] {
+            // Enter series selection mode for the marked episodes or the remembered episode
+            if !marked_episodes.is_empty() || matches!(filtered_entries.get(remembered_item), Some(Entry::Episode { .. })) {
                 // Reload series list
-                *series = database::get_all_series().expect("Failed to get series");
+                match database.get_all_series() {
+                    Ok(loaded) => *series = loaded,
+                    Err(e) => {
+                        report_database_error("Failed to load series", &e, notifications);
+                        *mode = Mode::Browse;
+                        *redraw = true;
+                        return;
+                    }
+                }
                 *series_selection = if series.is_empty() { None } else { Some(0) };
                 *first_series = 0;
                 *mode = Mode::SeriesSelect;
                 *redraw = true;
             }
         }
+        MenuAction::CycleSort => {
+            *sort_mode = sort_mode.next();
+            logger::log_info(&format!("Browse sort changed to {}", sort_mode.label()));
+            notifications.info(format!("Sorted by {}", sort_mode.label()));
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::PinFilter => {
+            // Move the typed filter to the next free pin and switch it on
+            match pinned_filters.pin(search) {
+                Ok(slot) => {
+                    pinned_filters.toggle(slot);
+                    logger::log_info(&format!("Pinned filter '{}' to [{}]", search.trim(), slot));
+                    notifications.info(format!("Pinned \"{}\" to [{}]", search.trim(), slot));
+                    search.clear();
+                }
+                Err(message) => notifications.warn(message),
+            }
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::ClearPinnedFilters => {
+            pinned_filters.clear();
+            logger::log_info("Cleared pinned filters");
+            notifications.info("Cleared pinned filters");
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::EditNotes => {
+            // Open the notes window for the remembered episode, cursor at the end
+            if let Some(&Entry::Episode { episode_id, .. }) = filtered_entries.get(remembered_item) {
+                match database.get_episode_detail(episode_id) {
+                    Ok(details) => {
+                        *edit_details = details;
+                        *edit_cursor_pos = edit_details.notes.as_deref().map_or(0, |n| n.chars().count());
+                        *mode = Mode::NotesEdit;
+                    }
+                    Err(e) => {
+                        logger::log_error(&format!("Failed to get episode details for episode {}: {}", episode_id, e));
+                        *mode = Mode::Browse;
+                    }
+                }
+                *redraw = true;
+            }
+        }
+        MenuAction::EditAltTitles => {
+            // Load the episode's titles; main starts the draft from them when the editor opens
+            if let Some(&Entry::Episode { episode_id, .. }) = filtered_entries.get(remembered_item) {
+                match database.get_episode_detail(episode_id) {
+                    Ok(details) => {
+                        *edit_details = details;
+                        *edit_cursor_pos = 0;
+                        *mode = Mode::AltTitlesEdit;
+                    }
+                    Err(e) => {
+                        logger::log_error(&format!("Failed to get episode details for episode {}: {}", episode_id, e));
+                        *mode = Mode::Browse;
+                    }
+                }
+                *redraw = true;
+            }
+        }
+        MenuAction::EditInEditor => {
+            // The editor has the terminal until it exits; the screen is drawn again after
+            if let Some(&Entry::Episode { episode_id, ref location, .. }) = filtered_entries.get(remembered_item) {
+                match external_editor::edit_episode(database, episode_id, &location::decode(location), config.mouse) {
+                    Ok(changes) if changes.is_empty() => notifications.info("Nothing changed"),
+                    Ok(changes) => {
+                        logger::log_info(&format!("Edited episode {} in the editor: {}", episode_id, changes.join(", ")));
+                        notifications.info(format!("Saved {}", changes.join(", ")));
+                        if let Ok(details) = database.get_episode_detail(episode_id) {
+                            *edit_details = details;
+                        }
+                        *series = database.get_all_series().unwrap_or_default();
+                        reload_entries(database, view_context, entries, notifications);
+                        *filtered_entries = entries.clone();
+                    }
+                    Err(e) => {
+                        logger::log_error(&format!("Editing episode {} in the editor failed: {}", episode_id, e));
+                        notifications.error(e);
+                    }
+                }
+            }
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
         MenuAction::RepeatAction => {
             // Repeat the last action on the remembered episode
             if let Some(action) = last_action {
-                if let Entry::Episode { episode_id, .. } = filtered_entries[remembered_item] {
+                if let Some(&Entry::Episode { episode_id, .. }) = filtered_entries.get(remembered_item) {
                     match action {
                         crate::util::LastAction::SeriesAssignment { series_id, .. } => {
                             // Assign the episode to the series
-                            let _ = database::assign_series(*series_id, episode_id);
+                            let _ = database.assign_series(*series_id, episode_id);
                         }
                         crate::util::LastAction::SeasonAssignment {
                             series_id,
@@ -1623,7 +2585,7 @@ fn execute_menu_action(
                             ..
                         } => {
                             // Assign the episode to the series and season
-                            let _ = database::create_season_and_assign(
+                            let _ = database.create_season_and_assign(
                                 *series_id,
                                 *season_num,
                                 episode_id,
@@ -1632,19 +2594,7 @@ fn execute_menu_action(
                     }
 
                     // Reload entries based on current view context
-                    *entries = match view_context {
-                        ViewContext::TopLevel => {
-                            database::get_entries().expect("Failed to get entries")
-                        }
-                        ViewContext::Series { series_id, .. } => {
-                            database::get_entries_for_series(*series_id)
-                                .expect("Failed to get entries for series")
-                        }
-                        ViewContext::Season { season_id, .. } => {
-                            database::get_entries_for_season(*season_id)
-                                .expect("Failed to get entries for season")
-                        }
-                    };
+                    reload_entries(database, view_context, entries, notifications);
                     *filtered_entries = entries.clone();
                     *mode = Mode::Browse;
                     *redraw = true;
@@ -1653,242 +2603,529 @@ fn execute_menu_action(
         }
         MenuAction::Rescan => {
             // Check if db_location is None (shouldn't happen but handle gracefully)
-            if config.db_location.is_none() {
+            if config.is_first_run() {
                 // Enter Entry mode for first-run setup
                 *entries = Vec::new();
                 *filtered_entries = Vec::new();
                 *mode = Mode::Entry;
                 *redraw = true;
             } else {
-                // Get root directory from PathResolver and scan automatically
+                // Walk the library root in the background; progress shows in the status bar
                 let scan_dir = resolver.get_root_dir();
-                
-                // Log rescan start
-                logger::log_info(&format!("Rescan started: {}", scan_dir.display()));
-                
-                // Set scanning status and force immediate display
-                *status_message = format!("Rescanning {}...", scan_dir.display());
-                
-                // Force immediate terminal update to show "Rescanning..." message
-                use crossterm::{cursor, terminal, ExecutableCommand};
-                use std::io::{stdout, Write};
-                let mut stdout = stdout();
-                let _ = stdout.execute(cursor::MoveTo(0, terminal::size().unwrap_or((80, 24)).1 - 1));
-                let _ = stdout.execute(terminal::Clear(terminal::ClearType::CurrentLine));
-                print!("{}", status_message);
-                let _ = stdout.flush();
-                
-                *redraw = true;
-                
-                // Scan the directory for video files
-                let new_entries: Vec<_> = WalkDir::new(scan_dir)
-                    .into_iter()
-                    .filter_map(|e| e.ok())
-                    .filter(|e| e.file_type().is_file())
-                    .filter(|e| {
-                        e.path()
-                            .extension()
-                            .and_then(|ext| ext.to_str())
-                            .is_some_and(|ext| {
-                                config.video_extensions.contains(&ext.to_lowercase())
-                            })
-                    })
-                    .map(|e| e.into_path())
-                    .collect();
-                
-                let mut imported_count = 0;
-                
-                for entry in &new_entries {
-                    let location = entry.to_string_lossy().to_string();
-                    let name = entry
-                        .file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string();
-
-                    match database::import_episode_relative(&location, &name, resolver) {
-                        Ok(true) => imported_count += 1,  // Only count if actually inserted
-                        Ok(false) => {},  // Already exists, don't count
-                        Err(e) => {
-                            eprintln!("Warning: Skipping file: {} - {}", location, e);
-                        }
-                    }
-                }
-                
-                // Update status after scan
-                if imported_count > 0 {
-                    *status_message = format!("Rescan complete. Found {} new videos", imported_count);
-                    // Log rescan completion
-                    logger::log_info(&format!("Rescan completed: imported {} new videos", imported_count));
-                } else {
-                    *status_message = "Rescan complete. No new videos found".to_string();
-                    // Log rescan completion
-                    logger::log_info("Rescan completed: no new videos found");
+                if !crate::scan::spawn_rescan(scan_dir.to_path_buf(), config, Arc::clone(database), resolver.clone(), tx.clone()) {
+                    notifications.warn("A rescan is already running");
                 }
+                *mode = Mode::Browse;
                 *redraw = true;
-
-                // Extract missing lengths for episodes with NULL or 0 length
-                match database::get_episodes_with_missing_length() {
-                    Ok(episodes_to_process) => {
-                        let mut extracted_count = 0;
-                        let mut unsupported_extensions = std::collections::HashSet::new();
-                        
-                        for (episode_id, relative_location) in episodes_to_process {
-                            // Convert relative path to absolute path
-                            let relative_path = Path::new(&relative_location);
-                            let absolute_path = resolver.to_absolute(relative_path);
-                            
-                            // Attempt to extract and update episode length
-                            match video_metadata::extract_and_update_episode_length(episode_id, &absolute_path) {
-                                Ok(_) => {
-                                    extracted_count += 1;
-                                }
-                                Err(e) => {
-                                    // Check if error is due to unsupported format
-                                    let error_msg = e.to_string();
-                                    if error_msg.contains("Unsupported video format:") {
-                                        // Extract the extension from the error message
-                                        if let Some(ext) = absolute_path.extension().and_then(|e| e.to_str()) {
-                                            unsupported_extensions.insert(ext.to_lowercase());
-                                        }
-                                    }
-                                    // Suppress warnings - don't print to stderr
-                                }
-                            }
-                        }
-                        
-                        // Update status message with extraction results
-                        if extracted_count > 0 || !unsupported_extensions.is_empty() {
-                            let mut status_parts = vec![status_message.clone()];
-                            
-                            if extracted_count > 0 {
-                                status_parts.push(format!("Extracted {} video lengths", extracted_count));
-                            }
-                            
-                            if !unsupported_extensions.is_empty() {
-                                let mut exts: Vec<_> = unsupported_extensions.into_iter().collect();
-                                exts.sort();
-                                status_parts.push(format!("{} unsupported format(s): [{}]", 
-                                    exts.len(), 
-                                    exts.join(", ")));
-                            }
-                            
-                            *status_message = status_parts.join(". ");
-                        }
+            }
+        }
+        MenuAction::ClearSeriesData | MenuAction::UnwatchAll | MenuAction::Delete | MenuAction::DeleteFile => {
+            // Only reached when the config turned the confirmation dialog off
+            execute_destructive_action(action, remembered_item, state, context);
+        }
+        MenuAction::MergeVersions => {
+            // Attach other files of the same episode as versions of the selected one
+            if let Some(Entry::Episode { episode_id, name, .. }) = filtered_entries.get(remembered_item) {
+                match database.merge_versions(*episode_id) {
+                    Ok(0) => {
+                        notifications.warn(format!("No other files of '{}' found. Give them the same name or episode number first", name));
+                    }
+                    Ok(merged) => {
+                        notifications.toast(format!("Attached {} versions to '{}'", merged, name));
+                        reload_entries(database, view_context, entries, notifications);
+                        *filtered_entries = entries.clone();
                     }
                     Err(e) => {
-                        eprintln!("Warning: Failed to query episodes with missing length: {}", e);
+                        report_database_error(&format!("Failed to merge versions of '{}'", name), &e, notifications);
                     }
                 }
-
-                // Reload entries based on current view context
-                *entries = match view_context {
-                    ViewContext::TopLevel => database::get_entries().expect("Failed to get entries"),
-                    ViewContext::Series { series_id, .. } => database::get_entries_for_series(*series_id)
-                        .expect("Failed to get entries for series"),
-                    ViewContext::Season { season_id, .. } => database::get_entries_for_season(*season_id)
-                        .expect("Failed to get entries for season"),
-                };
-                *filtered_entries = entries.clone();
                 *mode = Mode::Browse;
                 *redraw = true;
             }
         }
-        MenuAction::ClearSeriesData => {
-            // Clear series, season, and episode number for the remembered episode
-            if let Entry::Episode { episode_id, .. } = filtered_entries[remembered_item] {
-                if let Err(e) = database::clear_series_data(episode_id) {
-                    logger::log_error(&format!("Failed to clear series data for episode {}: {}", episode_id, e));
-                    eprintln!("Error: Failed to clear series data: {}", e);
-                    return;
+        MenuAction::SearchOnline => {
+            // Transition to TorrentSearchInput mode and initialize search query
+            *mode = Mode::TorrentSearchInput;
+            search_query.clear();
+            *redraw = true;
+        }
+        MenuAction::ExportLibrary(format) => {
+            // Write the export file into the library root
+            let export_path = resolver.get_root_dir().join(format.default_file_name());
+            match database.export_library(*format, &export_path) {
+                Ok(count) => {
+                    notifications.info(format!("Exported {} videos to {}", count, export_path.display()));
+                }
+                Err(e) => {
+                    logger::log_error(&format!("Library export failed: {}", e));
+                    notifications.error(format!("Export failed: {}", e));
                 }
-
-                // Reload entries based on current view context
-                *entries = match view_context {
-                    ViewContext::TopLevel => database::get_entries().expect("Failed to get entries"),
-                    ViewContext::Series { series_id, .. } => database::get_entries_for_series(*series_id)
-                        .expect("Failed to get entries for series"),
-                    ViewContext::Season { season_id, .. } => database::get_entries_for_season(*season_id)
-                        .expect("Failed to get entries for season"),
-                };
-                *filtered_entries = entries.clone();
-                *mode = Mode::Browse;
-                *redraw = true;
             }
+            *mode = Mode::Browse;
+            *redraw = true;
         }
-        MenuAction::UnwatchAll => {
-            // Determine scope based on view_context
-            match view_context {
-                ViewContext::Season { season_id, .. } => {
-                    database::unwatch_all_in_season(*season_id)
-                        .expect("Failed to unwatch all episodes in season");
+        MenuAction::ExportReport(format) => {
+            // Write the report into the library root
+            let report_path = resolver.get_root_dir().join(format.default_file_name());
+            let generated = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+            let written = database.get_library_records()
+                .map_err(|e| e.to_string())
+                .map(|records| crate::library_report::render(&records, *format, &generated))
+                .and_then(|report| std::fs::write(&report_path, report).map_err(|e| e.to_string()));
+            match written {
+                Ok(()) => {
+                    notifications.info(format!("Wrote {} report to {}", format.label(), report_path.display()));
+                }
+                Err(e) => {
+                    logger::log_error(&format!("Library report failed: {}", e));
+                    notifications.error(format!("Report failed: {}", e));
+                }
+            }
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::ImportLibrary { dry_run } => {
+            // Import from an export file placed in the library root
+            let import_path = match crate::export::find_import_file(resolver.get_root_dir()) {
+                Some(path) => path,
+                None => {
+                    notifications.warn(format!(
+                        "No {} or {} found in {}",
+                        ExportFormat::Json.default_file_name(),
+                        ExportFormat::Csv.default_file_name(),
+                        resolver.get_root_dir().display()
+                    ));
+                    *mode = Mode::Browse;
+                    *redraw = true;
+                    return;
                 }
-                ViewContext::Series { series_id, .. } => {
-                    database::unwatch_all_in_series(*series_id)
-                        .expect("Failed to unwatch all episodes in series");
+            };
+
+            match database.import_library(&import_path, *dry_run) {
+                Ok(report) => {
+                    for change in &report.changes {
+                        logger::log_info(&format!("Import change: {}", change));
+                    }
+                    for location in &report.unmatched {
+                        logger::log_info(&format!("Import unmatched: {}", location));
+                    }
+                    notifications.info(report.summary());
                 }
-                ViewContext::TopLevel => {
-                    database::unwatch_all_standalone()
-                        .expect("Failed to unwatch all standalone episodes");
+                Err(e) => {
+                    logger::log_error(&format!("Library import failed: {}", e));
+                    notifications.error(format!("Import failed: {}", e));
                 }
             }
 
             // Reload entries based on current view context
-            *entries = match view_context {
-                ViewContext::TopLevel => database::get_entries().expect("Failed to get entries"),
-                ViewContext::Series { series_id, .. } => database::get_entries_for_series(*series_id)
-                    .expect("Failed to get entries for series"),
-                ViewContext::Season { season_id, .. } => database::get_entries_for_season(*season_id)
-                    .expect("Failed to get entries for season"),
-            };
+            reload_entries(database, view_context, entries, notifications);
             *filtered_entries = entries.clone();
             *mode = Mode::Browse;
             *redraw = true;
         }
-        MenuAction::Delete => {
-            // Delete the episode from the database
-            if let Entry::Episode { episode_id, name, .. } = &filtered_entries[remembered_item] {
-                // Delete the episode
-                if let Err(e) = database::delete_episode(*episode_id) {
-                    logger::log_error(&format!("Failed to delete episode {} ({}): {}", episode_id, name, e));
-                    eprintln!("Error: Failed to delete episode: {}", e);
+        MenuAction::ImportWatchHistory => {
+            // Look for a Kodi or Plex database in the library root or Kodi's default location
+            let database_path = match crate::watch_import::find_watch_database(resolver.get_root_dir()) {
+                Some(path) => path,
+                None => {
+                    notifications.warn(format!(
+                        "No Kodi MyVideos*.db or {} found",
+                        crate::watch_import::PLEX_DATABASE_NAME
+                    ));
+                    *mode = Mode::Browse;
+                    *redraw = true;
                     return;
                 }
-                
-                // Log deletion with episode details
-                logger::log_info(&format!("Deleted episode {} ({})", episode_id, name));
+            };
 
-                // Reload entries based on current view context
-                *entries = match view_context {
-                    ViewContext::TopLevel => database::get_entries().expect("Failed to get entries"),
-                    ViewContext::Series { series_id, .. } => database::get_entries_for_series(*series_id)
-                        .expect("Failed to get entries for series"),
-                    ViewContext::Season { season_id, .. } => database::get_entries_for_season(*season_id)
-                        .expect("Failed to get entries for season"),
-                };
-                *filtered_entries = entries.clone();
-                *mode = Mode::Browse;
-                *redraw = true;
+            let result = crate::watch_import::read_watch_records(&database_path).and_then(|(source, records)| {
+                database.apply_watch_history(&records).map(|report| report.summary(source)).map_err(Into::into)
+            });
+            match result {
+                Ok(summary) => notifications.info(summary),
+                Err(e) => {
+                    logger::log_error(&format!("Watch history import from {} failed: {}", database_path.display(), e));
+                    notifications.error(format!("Watch history import failed: {}", e));
+                }
             }
-        }
-        MenuAction::SearchOnline => {
-            // Transition to TorrentSearchInput mode and initialize search query
-            *mode = Mode::TorrentSearchInput;
-            search_query.clear();
+
+            // Reload entries based on current view context
+            reload_entries(database, view_context, entries, notifications);
+            *filtered_entries = entries.clone();
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::TraktSync => {
+            // Runs in the background; progress shows in the status bar
+            start_trakt_sync(context, notifications);
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::JellyfinSync { dry_run } => {
+            // Runs in the background like Trakt sync
+            start_jellyfin_sync(context, *dry_run, notifications);
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::Tutorial => {
+            // Restart the guided tour from the first step
+            *tutorial = Some(Tutorial::new());
+            logger::log_info("Tutorial started from the menu");
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::UsageInsights => {
+            notifications.info(crate::usage::current_usage().summary());
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::ExportUsage => {
+            // Opt-in export: write the counters next to the library for the user to share
+            let export_path = resolver.get_root_dir().join(crate::usage::USAGE_EXPORT_FILE_NAME);
+            match crate::usage::save_usage(&crate::usage::current_usage(), &export_path) {
+                Ok(()) => {
+                    logger::log_info(&format!("Exported usage counters to {}", export_path.display()));
+                    notifications.info(format!("Usage counters exported to {}", export_path.display()));
+                }
+                Err(e) => {
+                    logger::log_error(&format!("Failed to export usage counters: {}", e));
+                    notifications.error(format!("Usage export failed: {}", e));
+                }
+            }
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::Theme => {
+            // The picker is filled from the config directory when it is first drawn
+            *mode = Mode::ThemeSelect;
+            *redraw = true;
+        }
+        MenuAction::InstallThemes => {
+            // Then straight into the picker, to try the themes just installed
+            let config_dir = config_path.parent().unwrap_or(Path::new("."));
+            match theme::install_builtin_themes(config_dir) {
+                Ok(installed) if installed.is_empty() => notifications.info("The built-in themes are already installed"),
+                Ok(installed) => notifications.toast(format!("Installed {} themes", installed.len())),
+                Err(e) => {
+                    logger::log_error(&format!("Failed to install the built-in themes in {:?}: {}", config_dir, e));
+                    notifications.error(format!("Failed to install themes: {}", e));
+                }
+            }
+            *mode = Mode::ThemeSelect;
+            *redraw = true;
+        }
+        MenuAction::ShowExtras => {
+            // Samples and trailers imported as extras, listed on their own until Esc
+            match database.get_extras() {
+                Ok(extras) => {
+                    state.search.clear();
+                    state.entries = extras;
+                    state.filtered_entries = state.entries.clone();
+                    state.current_item = 0;
+                    state.first_entry = 0;
+                    state.view_context = ViewContext::Extras;
+                }
+                Err(e) => report_database_error("Failed to load the extras", &e, &mut state.notifications),
+            }
+            state.mode = Mode::Browse;
+            state.redraw = true;
+        }
+        MenuAction::NotAnExtra => {
+            // Put the remembered extra back in the library's views
+            if let Some(Entry::Episode { episode_id, name, .. }) = filtered_entries.get(remembered_item) {
+                match database.set_extra(*episode_id, false) {
+                    Ok(()) => notifications.info(format!("{} is back in the library", name)),
+                    Err(e) => report_database_error("Failed to update the episode", &e, notifications),
+                }
+                reload_entries(database, view_context, entries, notifications);
+                *filtered_entries = entries.clone();
+            }
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::ScanFolder => {
+            // Entry mode, starting from the root, for the folder to scan by itself
+            *entry_path = format!("{}/", resolver.get_root_dir().display());
+            entry_completions.clear();
+            *scan_folder_entry = true;
+            *mode = Mode::Entry;
+            *redraw = true;
+        }
+        MenuAction::Custom(index) => {
+            // Runs in the background; the main loop hears when it exits
+            let command = custom_commands::installed().into_iter().nth(*index);
+            if let (Some(command), Some(&Entry::Episode { episode_id, ref location, .. })) =
+                (command, filtered_entries.get(remembered_item))
+            {
+                let details = database.get_episode_detail(episode_id).unwrap_or_default();
+                let series_name = details.series.as_ref().map(|series| series.name.as_str()).unwrap_or_default();
+                let path = resolver.to_absolute(&location::decode(location));
+                match custom_commands::build_command(&command.template, &path, &details.title, series_name) {
+                    Some(program) => {
+                        notifications.info(format!("Running {}", command.label));
+                        custom_commands::spawn(command.label, program, tx.clone());
+                    }
+                    None => notifications.error(format!("{} has no command", command.label)),
+                }
+            }
+            *mode = Mode::Browse;
+            *redraw = true;
+        }
+        MenuAction::AddFromUrl => {
+            download_url.clear();
+            *mode = Mode::AddFromUrl;
+            *redraw = true;
+        }
+        MenuAction::FindDuplicates => {
+            // Copies are grouped by size first, so only files sharing a size are read
+            match database.get_duplicate_candidates(resolver) {
+                Ok(candidates) => {
+                    *duplicate_groups = duplicates::find_duplicates(candidates);
+                    *selected_duplicate = 0;
+                    if duplicate_groups.is_empty() {
+                        notifications.info("No duplicates found");
+                        *mode = Mode::Browse;
+                    } else {
+                        logger::log_info(&format!("Found {} videos with duplicate copies", duplicate_groups.len()));
+                        *mode = Mode::DuplicateReview;
+                    }
+                }
+                Err(e) => {
+                    report_database_error("Failed to look for duplicates", &e, notifications);
+                    *mode = Mode::Browse;
+                }
+            }
+            *redraw = true;
+        }
+        MenuAction::RelocateLibrary => {
+            // Start from the current root, to be edited into the folder it moved to
+            *relocate_path = resolver.get_root_dir().to_string_lossy().to_string();
+            *relocation_check = None;
+            *mode = Mode::Relocate;
+            *redraw = true;
+        }
+    }
+}
+
+// Start a Trakt sync on a background thread; its result reaches the main loop as `AppEvent::SyncFinished`
+#[cfg(feature = "trakt")]
+fn start_trakt_sync(context: &AppContext, notifications: &mut Notifications) {
+    let AppContext { config, database, tx, .. } = *context;
+    let credentials = match (&config.trakt_client_id, &config.trakt_client_secret) {
+        (Some(id), Some(secret)) => crate::sync::trakt::TraktCredentials {
+            client_id: id.clone(),
+            client_secret: secret.clone(),
+        },
+        _ => return notifications.warn("Set trakt_client_id and trakt_client_secret in config.yaml to use Trakt sync"),
+    };
+    let database = Arc::clone(database);
+    if !crate::sync::spawn(tx.clone(), move |progress| crate::sync::trakt::run_from_menu(&database, credentials, progress)) {
+        notifications.warn("A sync is already running");
+    }
+}
+
+#[cfg(not(feature = "trakt"))]
+fn start_trakt_sync(_context: &AppContext, notifications: &mut Notifications) {
+    notifications.warn("Trakt sync is not available in this build (enable the trakt feature)");
+}
+
+// Start a Jellyfin sync or its preview on a background thread, like `start_trakt_sync`
+#[cfg(feature = "jellyfin")]
+fn start_jellyfin_sync(context: &AppContext, dry_run: bool, notifications: &mut Notifications) {
+    let AppContext { config, database, tx, .. } = *context;
+    let credentials = match (&config.jellyfin_url, &config.jellyfin_api_key) {
+        (Some(url), Some(api_key)) => crate::sync::jellyfin::JellyfinCredentials {
+            url: url.clone(),
+            api_key: api_key.clone(),
+            user: config.jellyfin_user.clone(),
+        },
+        _ => return notifications.warn("Set jellyfin_url and jellyfin_api_key in config.yaml to use Jellyfin sync"),
+    };
+    let database = Arc::clone(database);
+    let started = crate::sync::spawn(tx.clone(), move |progress| {
+        crate::sync::jellyfin::run_from_menu(&database, credentials, dry_run, progress)
+    });
+    if !started {
+        notifications.warn("A sync is already running");
+    }
+}
+
+#[cfg(not(feature = "jellyfin"))]
+fn start_jellyfin_sync(_context: &AppContext, _dry_run: bool, notifications: &mut Notifications) {
+    notifications.warn("Jellyfin sync is not available in this build (enable the jellyfin feature)");
+}
+
+// Handle NotesEdit mode - the draft is kept in episode_detail.notes until saved
+pub fn handle_notes_edit_mode(code: KeyCode, modifiers: event::KeyModifiers, state: &mut AppState, context: &AppContext) {
+    let database = context.database;
+    let AppState {
+        ref mut mode,
+        ref mut redraw,
+        ref mut edit_cursor_pos,
+        selected_entry_id,
+        edit_details: ref mut episode_detail,
+        ref mut notifications,
+        ..
+    } = *state;
+    let Some(episode_id) = selected_entry_id else {
+        *mode = Mode::Browse;
+        *redraw = true;
+        return;
+    };
+    let mut notes = episode_detail.notes.clone().unwrap_or_default();
+
+    match code {
+        KeyCode::Enter => {
+            match database.update_episode_notes(episode_id, &notes) {
+                Ok(()) => {
+                    logger::log_info(&format!("Updated notes for episode {}", episode_id));
+                    notifications.toast("Notes saved");
+                }
+                Err(e) => notifications.error(format!("Failed to save notes: {}", e)),
+            }
+            if let Ok(details) = database.get_episode_detail(episode_id) {
+                *episode_detail = details;
+            }
+            *edit_cursor_pos = 0;
+            state.close_modal();
+            return;
+        }
+        KeyCode::Esc => {
+            // Discard the draft
+            if let Ok(details) = database.get_episode_detail(episode_id) {
+                *episode_detail = details;
+            }
+            *edit_cursor_pos = 0;
+            *mode = Mode::Browse;
+            *redraw = true;
+            return;
+        }
+        KeyCode::Left if modifiers.contains(event::KeyModifiers::CONTROL) => {
+            // jump back by words (separated by spaces)
+            TextInput::new(&mut notes, edit_cursor_pos).word_left();
+        }
+        KeyCode::Left => TextInput::new(&mut notes, edit_cursor_pos).left(),
+        KeyCode::Right if modifiers.contains(event::KeyModifiers::CONTROL) => {
+            // jump forward by words (separated by spaces)
+            TextInput::new(&mut notes, edit_cursor_pos).word_right();
+        }
+        KeyCode::Right => TextInput::new(&mut notes, edit_cursor_pos).right(),
+        KeyCode::Home => TextInput::new(&mut notes, edit_cursor_pos).home(),
+        KeyCode::End => TextInput::new(&mut notes, edit_cursor_pos).end(),
+        KeyCode::Backspace => {
+            if !TextInput::new(&mut notes, edit_cursor_pos).backspace() {
+                return;
+            }
+        }
+        KeyCode::Delete => {
+            if !TextInput::new(&mut notes, edit_cursor_pos).delete() {
+                return;
+            }
+        }
+        KeyCode::Char(c) => TextInput::new(&mut notes, edit_cursor_pos).insert(c),
+        _ => return,
+    }
+
+    episode_detail.notes = Some(notes);
+    *redraw = true;
+}
+
+// Handle AltTitlesEdit mode - rows are edited in the draft and parsed when saved
+pub fn handle_alt_titles_edit_mode(code: KeyCode, state: &mut AppState, context: &AppContext) {
+    let database = context.database;
+    let AppState {
+        ref mut mode,
+        ref mut redraw,
+        ref mut edit_cursor_pos,
+        selected_entry_id,
+        ref mut alt_titles_draft,
+        edit_details: ref mut episode_detail,
+        ref mut notifications,
+        ..
+    } = *state;
+    let (Some(episode_id), Some(draft)) = (selected_entry_id, alt_titles_draft.as_mut()) else {
+        *mode = Mode::Browse;
+        *redraw = true;
+        return;
+    };
+    draft.normalize();
+    let mut row = draft.rows[draft.selected].clone();
+
+    match code {
+        KeyCode::Enter => {
+            let titles = match draft.to_titles() {
+                Ok(titles) => titles,
+                Err(e) => {
+                    // Keep the editor open so the row can be fixed
+                    notifications.warn(e);
+                    *redraw = true;
+                    return;
+                }
+            };
+            match database.update_episode_alt_titles(episode_id, &titles) {
+                Ok(()) => {
+                    logger::log_info(&format!("Updated alternate titles for episode {}", episode_id));
+                    notifications.toast("Alternate titles saved");
+                }
+                Err(e) => notifications.error(format!("Failed to save alternate titles: {}", e)),
+            }
+            if let Ok(details) = database.get_episode_detail(episode_id) {
+                *episode_detail = details;
+            }
+            *edit_cursor_pos = 0;
+            *mode = Mode::Browse;
+            *redraw = true;
+            return;
+        }
+        KeyCode::Esc => {
+            *edit_cursor_pos = 0;
+            state.close_modal();
+            return;
+        }
+        KeyCode::Up | KeyCode::Down => {
+            draft.selected = if code == KeyCode::Up {
+                draft.selected.saturating_sub(1)
+            } else {
+                (draft.selected + 1).min(draft.rows.len() - 1)
+            };
+            *edit_cursor_pos = draft.rows[draft.selected].chars().count();
             *redraw = true;
+            return;
+        }
+        KeyCode::Left => TextInput::new(&mut row, edit_cursor_pos).left(),
+        KeyCode::Right => TextInput::new(&mut row, edit_cursor_pos).right(),
+        KeyCode::Home => TextInput::new(&mut row, edit_cursor_pos).home(),
+        KeyCode::End => TextInput::new(&mut row, edit_cursor_pos).end(),
+        KeyCode::Backspace => {
+            if !TextInput::new(&mut row, edit_cursor_pos).backspace() {
+                return;
+            }
+        }
+        KeyCode::Delete => {
+            if !TextInput::new(&mut row, edit_cursor_pos).delete() {
+                return;
+            }
         }
+        KeyCode::Char(c) => TextInput::new(&mut row, edit_cursor_pos).insert(c),
+        _ => return,
     }
+
+    draft.rows[draft.selected] = row;
+    draft.normalize();
+    *redraw = true;
 }
 
 // Handle TorrentSearchInput mode - user enters search query
-pub fn handle_torrent_search_input(
-    code: KeyCode,
-    mode: &mut Mode,
-    search_query: &mut String,
-    torrent_results: &mut Vec<crate::torrent_search::TorrentResult>,
-    selected_torrent_result: &mut usize,
-    redraw: &mut bool,
-) {
+pub fn handle_torrent_search_input(code: KeyCode, state: &mut AppState) {
+    let AppState {
+        ref mut mode,
+        ref mut search_query,
+        ref mut torrent_results,
+        ref mut selected_torrent_result,
+        ref mut redraw,
+        ref mut notifications,
+        ..
+    } = *state;
     match code {
         KeyCode::Char(c) => {
             search_query.push(c);
@@ -1904,7 +3141,15 @@ pub fn handle_torrent_search_input(
             
             // Execute async search using tokio runtime
             let query = search_query.clone();
-            let runtime = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
+            let runtime = match tokio::runtime::Runtime::new() {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    logger::log_error(&format!("Failed to create tokio runtime: {}", e));
+                    notifications.error(format!("Search failed: {}", e));
+                    *redraw = true;
+                    return;
+                }
+            };
             
             match runtime.block_on(crate::torrent_search::search_torrents(&query)) {
                 Ok(results) => {
@@ -1915,7 +3160,7 @@ pub fn handle_torrent_search_input(
                 }
                 Err(e) => {
                     logger::log_error(&format!("Torrent search failed: {}", e));
-                    eprintln!("Error: Search failed: {}", e);
+                    notifications.error(format!("Search failed: {}", e));
                     *mode = Mode::Browse;
                 }
             }
@@ -1923,22 +3168,22 @@ pub fn handle_torrent_search_input(
         }
         KeyCode::Esc => {
             logger::log_debug("Torrent search canceled by user");
-            *mode = Mode::Browse;
-            *redraw = true;
+            state.close_modal();
         }
         _ => {}
     }
 }
 
 // Handle TorrentSearchResults mode - user navigates and selects results
-pub fn handle_torrent_search_results(
-    code: KeyCode,
-    mode: &mut Mode,
-    torrent_results: &[crate::torrent_search::TorrentResult],
-    selected_result: &mut usize,
-    status_message: &mut String,
-    redraw: &mut bool,
-) {
+pub fn handle_torrent_search_results(code: KeyCode, state: &mut AppState) {
+    let AppState {
+        ref mut mode,
+        ref torrent_results,
+        selected_torrent_result: ref mut selected_result,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
     match code {
         KeyCode::Up => {
             if *selected_result > 0 {
@@ -1962,12 +3207,12 @@ pub fn handle_torrent_search_results(
                 // Attempt to open magnet link
                 match crate::torrent_search::open_magnet_link(&result.magnet_link) {
                     Ok(_) => {
-                        *status_message = format!("Initiated download: {}", result.name);
+                        notifications.info(format!("Initiated download: {}", result.name));
                         logger::log_info(&format!("Successfully opened magnet link for: {}", result.name));
                         *mode = Mode::Browse;
                     }
                     Err(e) => {
-                        *status_message = format!("Error opening magnet link: {}", e);
+                        notifications.error(format!("Error opening magnet link: {}", e));
                         logger::log_error(&format!("Failed to open magnet link: {}", e));
                         // Remain in results mode so user can try another result
                     }
@@ -1977,9 +3222,868 @@ pub fn handle_torrent_search_results(
         }
         KeyCode::Esc => {
             logger::log_debug("Torrent search results canceled by user");
+            state.close_modal();
+        }
+        _ => {}
+    }
+}
+
+// Handle Digest mode - user navigates the digest and jumps to an item
+pub fn handle_digest_mode(code: KeyCode, state: &mut AppState, context: &AppContext) {
+    let AppState {
+        ref mut mode,
+        weekly_digest: ref digest,
+        selected_digest_item: ref mut selected_item,
+        ref mut entries,
+        ref mut current_item,
+        ref mut first_entry,
+        ref mut search,
+        ref mut view_context,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
+    let items = digest.items();
+    match code {
+        KeyCode::Up if *selected_item > 0 => {
+            *selected_item -= 1;
+            *redraw = true;
+        }
+        KeyCode::Down if *selected_item + 1 < items.len() => {
+            *selected_item += 1;
+            *redraw = true;
+        }
+        KeyCode::Enter if *selected_item < items.len() => {
+            let item = items[*selected_item];
+            match jump_to_digest_item(context.database, item.target, entries, view_context) {
+                Ok(index) => {
+                    logger::log_info(&format!("Jumped to digest item '{}'", item.label));
+                    search.clear();
+                    *current_item = index;
+                    *first_entry = 0;
+                    *mode = Mode::Browse;
+                }
+                Err(e) => report_database_error(&format!("Failed to open '{}'", item.label), &e, notifications),
+            }
+            *redraw = true;
+        }
+        KeyCode::Esc => {
+            logger::log_debug("Weekly digest closed by user");
+            state.close_modal();
+        }
+        _ => {}
+    }
+}
+
+// Handle Help mode - user scrolls the keybinding reference
+pub fn handle_help_mode(code: KeyCode, state: &mut AppState) -> io::Result<()> {
+    let AppState { ref mut help_scroll, ref mut redraw, .. } = *state;
+    let page = get_max_displayed_items_with_header_height(2)?;
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => *help_scroll = help_scroll.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => *help_scroll += 1,
+        KeyCode::PageUp => *help_scroll = help_scroll.saturating_sub(page),
+        KeyCode::PageDown => *help_scroll += page,
+        KeyCode::Esc | KeyCode::Char('?') => {
+            *help_scroll = 0;
+            state.close_modal();
+            return Ok(());
+        }
+        _ => return Ok(()),
+    }
+    *redraw = true;
+    Ok(())
+}
+
+// Handle Confirm mode - user answers the dialog guarding a destructive action
+pub fn handle_confirm_mode(code: KeyCode, state: &mut AppState, context: &AppContext) {
+    let AppState {
+        ref mut mode,
+        ref mut redraw,
+        ref mut pending_confirmation,
+        ref mut marked_episodes,
+        ..
+    } = *state;
+    let Some(pending) = pending_confirmation else {
+        *mode = Mode::Browse;
+        *redraw = true;
+        return;
+    };
+    let confirmed = match code {
+        KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab => {
+            pending.dialog.toggle();
+            *redraw = true;
+            return;
+        }
+        KeyCode::Char('y') | KeyCode::Char('Y') => true,
+        KeyCode::Enter => pending.dialog.yes_selected,
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => false,
+        _ => return,
+    };
+
+    let Some(pending) = pending_confirmation.take() else {
+        return;
+    };
+    if !confirmed {
+        // Back to the menu, or Browse for a hotkey, that the dialog was opened over
+        state.close_modal();
+        return;
+    }
+    *mode = Mode::Browse;
+    *redraw = true;
+
+    crate::usage::record_action(pending.action.usage_key());
+    if !marked_episodes.is_empty() && is_batch_action(&pending.action) {
+        execute_batch_action(&pending.action, state, context);
+    } else {
+        execute_destructive_action(&pending.action, pending.remembered_item, state, context);
+    }
+}
+
+// Handle Notifications mode - user scrolls the recent status messages
+pub fn handle_notifications_mode(code: KeyCode, state: &mut AppState) -> io::Result<()> {
+    let AppState {
+        notification_scroll: ref mut scroll,
+        ref mut redraw,
+        ..
+    } = *state;
+    let page = get_max_displayed_items_with_header_height(2)?;
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => *scroll += 1,
+        KeyCode::PageUp => *scroll = scroll.saturating_sub(page),
+        KeyCode::PageDown => *scroll += page,
+        KeyCode::Esc | KeyCode::Char('n') => {
+            *scroll = 0;
+            state.close_modal();
+            return Ok(());
+        }
+        _ => return Ok(()),
+    }
+    *redraw = true;
+    Ok(())
+}
+
+// Handle ScanReport mode - user reads what the last rescan did, then reviews any archives it found
+pub fn handle_scan_report_mode(code: KeyCode, state: &mut AppState, context: &AppContext) -> io::Result<()> {
+    let database = context.database;
+    let AppState {
+        ref mut mode,
+        scan_report_scroll: ref mut scroll,
+        ref mut scan_report,
+        ref pending_archives,
+        ref mut entries,
+        ref mut filtered_entries,
+        ref view_context,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
+    let page = get_max_displayed_items_with_header_height(2)?;
+    // Enter confirms the series found in folder names and Esc drops them, before going on
+    if matches!(code, KeyCode::Esc | KeyCode::Enter) && !scan_report.series_proposals.is_empty() {
+        let proposals = std::mem::take(&mut scan_report.series_proposals);
+        if code == KeyCode::Enter {
+            match database.assign_folder_series(&proposals) {
+                Ok(created) => {
+                    logger::log_info(&format!(
+                        "Assigned {} episodes to series from their folders, creating {} series",
+                        proposals.len(),
+                        created
+                    ));
+                    notifications.info(format!("Put {} episodes in their series, {} new", proposals.len(), created));
+                    reload_entries(database, view_context, entries, notifications);
+                    *filtered_entries = entries.clone();
+                }
+                Err(e) => report_database_error("Failed to assign series from folder names", &e, notifications),
+            }
+        }
+    }
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => *scroll += 1,
+        KeyCode::PageUp => *scroll = scroll.saturating_sub(page),
+        KeyCode::PageDown => *scroll += page,
+        KeyCode::Esc | KeyCode::Enter if !pending_archives.is_empty() => *mode = Mode::ArchiveReview,
+        KeyCode::Esc | KeyCode::Enter => {
+            state.close_modal();
+            return Ok(());
+        }
+        _ => return Ok(()),
+    }
+    *redraw = true;
+    Ok(())
+}
+
+// Handle ConfigReport mode - user reads the problems found in config.yaml at startup
+pub fn handle_config_report_mode(code: KeyCode, state: &mut AppState) -> io::Result<()> {
+    let AppState {
+        config_report_scroll: ref mut scroll,
+        ref mut redraw,
+        ..
+    } = *state;
+    let page = get_max_displayed_items_with_header_height(2)?;
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => *scroll = scroll.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => *scroll += 1,
+        KeyCode::PageUp => *scroll = scroll.saturating_sub(page),
+        KeyCode::PageDown => *scroll += page,
+        KeyCode::Esc | KeyCode::Enter => {
+            state.close_modal();
+            return Ok(());
+        }
+        _ => return Ok(()),
+    }
+    *redraw = true;
+    Ok(())
+}
+
+// Handle ArchiveReview mode - user extracts or skips archives found by a rescan
+pub fn handle_archive_review_mode(code: KeyCode, state: &mut AppState, context: &AppContext) {
+    let AppContext { config, database, resolver, .. } = *context;
+    let AppState {
+        ref mut mode,
+        pending_archives: ref mut archives,
+        ref mut selected_archive,
+        ref mut entries,
+        ref mut filtered_entries,
+        ref view_context,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
+    match code {
+        KeyCode::Up if *selected_archive > 0 => {
+            *selected_archive -= 1;
+            *redraw = true;
+        }
+        KeyCode::Down if *selected_archive + 1 < archives.len() => {
+            *selected_archive += 1;
+            *redraw = true;
+        }
+        KeyCode::Enter if *selected_archive < archives.len() => {
+            let selected = archives[*selected_archive].clone();
+            match archive::extract(&selected, &config.archive_extract_command) {
+                Ok(()) => {
+                    let imported = import_directory(selected.dir(), config, database, resolver);
+                    logger::log_info(&format!(
+                        "Extracted {} and imported {} videos",
+                        selected.path.display(),
+                        imported
+                    ));
+                    notifications.info(format!("Extracted {}. Imported {} videos", selected.label(), imported));
+                    archives.remove(*selected_archive);
+                    reload_entries(database, view_context, entries, notifications);
+                    *filtered_entries = entries.clone();
+                }
+                Err(e) => {
+                    logger::log_error(&format!("Failed to extract {}: {}", selected.path.display(), e));
+                    notifications.error(format!("Failed to extract {}: {}", selected.label(), e));
+                }
+            }
+            *redraw = true;
+        }
+        KeyCode::Char('s') if *selected_archive < archives.len() => {
+            let skipped = archives.remove(*selected_archive);
+            logger::log_debug(&format!("Skipped archive {}", skipped.path.display()));
+            *redraw = true;
+        }
+        KeyCode::Esc => {
+            archives.clear();
+            state.close_modal();
+            return;
+        }
+        _ => {}
+    }
+
+    if archives.is_empty() {
+        *mode = Mode::Browse;
+    } else if *selected_archive >= archives.len() {
+        *selected_archive = archives.len() - 1;
+    }
+}
+
+// Handle VersionSelect mode - user picks which file of the selected episode to play
+pub fn handle_version_select_mode(code: KeyCode, state: &mut AppState, context: &AppContext) -> io::Result<()> {
+    let AppState {
+        ref mut mode,
+        ref mut versions,
+        ref mut selected_version,
+        ref filtered_entries,
+        current_item,
+        ref edit_details,
+        ref playing_file,
+        ref mut redraw,
+        ..
+    } = *state;
+    let selected_entry = filtered_entries.get(current_item);
+    match code {
+        KeyCode::Up if *selected_version > 0 => {
+            *selected_version -= 1;
+            *redraw = true;
+        }
+        KeyCode::Down if *selected_version + 1 < versions.len() => {
+            *selected_version += 1;
+            *redraw = true;
+        }
+        KeyCode::Enter => {
+            let chosen = match (selected_entry, versions.get(*selected_version)) {
+                (Some(Entry::Episode { episode_id, name, .. }), Some(version)) if playing_file.is_none() => Some((
+                    *episode_id,
+                    format!("{} ({})", name, version.label),
+                    version.location.clone(),
+                    edit_details.clone(),
+                )),
+                _ => None,
+            };
+            versions.clear();
+            *mode = Mode::Browse;
+            *redraw = true;
+            if let Some((episode_id, label, location, details)) = chosen {
+                play_episode(episode_id, &label, &location, &details, state, context);
+            }
+        }
+        KeyCode::Esc => {
+            versions.clear();
+            state.close_modal();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+// Handle ThemeSelect mode - user previews the theme files and keeps one
+pub fn handle_theme_select_mode(
+    code: KeyCode,
+    state: &mut AppState,
+    theme: &mut Theme,
+    config: &mut Config,
+    config_path: &std::path::PathBuf,
+) {
+    let AppState {
+        ref mut mode,
+        ref mut theme_picker,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
+    let Some(picker) = theme_picker.as_mut() else {
+        *mode = Mode::Browse;
+        *redraw = true;
+        return;
+    };
+    let config_dir = config_path.parent().unwrap_or(Path::new("."));
+    match code {
+        KeyCode::Up | KeyCode::Down | KeyCode::Char('k') | KeyCode::Char('j') => {
+            let down = matches!(code, KeyCode::Down | KeyCode::Char('j'));
+            if picker.move_selection(down) {
+                if let Some(name) = picker.selected_name() {
+                    *theme = theme::load_theme(&config_dir.join(name));
+                    if config.accessible {
+                        theme.make_accessible();
+                    }
+                }
+                *redraw = true;
+            }
+        }
+        KeyCode::Enter => {
+            if let Some(name) = picker.selected_name() {
+                if name != config.active_theme {
+                    config.active_theme = name.to_string();
+                    crate::config::save_config(config, config_path);
+                    logger::log_info(&format!("Active theme set to {}", name));
+                }
+                notifications.toast(format!("Theme set to {}", ThemePicker::display_name(name)));
+            }
             *mode = Mode::Browse;
             *redraw = true;
         }
+        KeyCode::Esc => {
+            *theme = picker.original.clone();
+            state.close_modal();
+        }
+        _ => {}
+    }
+}
+
+// Handle Relocate mode - user types the folder the library moved to, checks it, then moves the library there
+pub fn handle_relocate_mode(
+    code: KeyCode,
+    state: &mut AppState,
+    config: &mut Config,
+    config_path: &std::path::PathBuf,
+    database: &mut Option<Arc<Database>>,
+    resolver: &mut Option<PathResolver>,
+) {
+    let AppState {
+        ref mut relocate_path,
+        ref mut relocation_check,
+        ref mut entries,
+        ref mut filtered_entries,
+        ref mut view_context,
+        ref mut series,
+        ref mut marked_episodes,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
+    match code {
+        KeyCode::Char(c) => {
+            relocate_path.push(c);
+            *relocation_check = None;
+        }
+        KeyCode::Backspace => {
+            relocate_path.pop();
+            *relocation_check = None;
+        }
+        // Enter checks the folder first, and moves the library once the check looks right
+        KeyCode::Enter => match relocation_check.take() {
+            Some(check) if check.looks_right() => match relocate_library(&check, config, config_path, database, resolver) {
+                Ok((db_path, database)) => {
+                    logger::log_info(&format!("Library relocated to {}", db_path.display()));
+                    notifications.info(format!("Library moved to {}", check.new_root.display()));
+                    *view_context = ViewContext::TopLevel;
+                    marked_episodes.clear();
+                    reload_entries(&database, view_context, entries, notifications);
+                    *filtered_entries = entries.clone();
+                    *series = database.get_all_series().unwrap_or_default();
+                    state.close_modal();
+                    return;
+                }
+                Err(e) => {
+                    logger::log_error(&format!("Failed to relocate the library to {}: {}", check.new_root.display(), e));
+                    notifications.error(format!("Failed to move the library: {}", e));
+                }
+            },
+            Some(check) => {
+                notifications.warn(format!("{}. Type another folder", check.summary()));
+                *relocation_check = Some(check);
+            }
+            None => {
+                let new_root = Path::new(relocate_path.trim());
+                let current_root = resolver.as_ref().map(|resolver| resolver.get_root_dir().to_path_buf());
+                let database_name = config
+                    .get_database_path()
+                    .and_then(|path| path.file_name().map(|name| name.to_string_lossy().to_string()))
+                    .unwrap_or_else(|| "videos.sqlite".to_string());
+                if new_root.canonicalize().ok() == current_root {
+                    notifications.warn("The library is already in that folder");
+                } else if let Some(database) = database {
+                    let checked = database
+                        .get_episode_locations()
+                        .map_err(|e| e.to_string())
+                        .and_then(|locations| relocate::check_relocation(new_root, &locations, &database_name));
+                    match checked {
+                        Ok(check) => *relocation_check = Some(check),
+                        Err(e) => notifications.error(e),
+                    }
+                }
+            }
+        },
+        KeyCode::Esc => {
+            *relocation_check = None;
+            state.close_modal();
+            return;
+        }
+        _ => return,
+    }
+    *redraw = true;
+}
+
+// Handle AddFromUrl mode - user types the URL of a web video, downloaded in the background
+pub fn handle_add_from_url_mode(code: KeyCode, state: &mut AppState, context: &AppContext) {
+    let AppContext { config, resolver, tx, .. } = *context;
+    let AppState {
+        ref mut download_url,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
+    match code {
+        KeyCode::Char(c) => download_url.push(c),
+        KeyCode::Backspace => {
+            download_url.pop();
+        }
+        KeyCode::Enter => match web_video::check_url(download_url) {
+            Ok(url) => {
+                let folder = web_video::download_folder(resolver.get_root_dir(), &config.download_folder);
+                notifications.info(format!("Downloading {} into {}", url, folder.display()));
+                web_video::spawn_download(config.ytdlp_path.clone(), url, folder, tx.clone());
+                download_url.clear();
+                state.close_modal();
+                return;
+            }
+            Err(message) => notifications.warn(message),
+        },
+        KeyCode::Esc => {
+            download_url.clear();
+            state.close_modal();
+            return;
+        }
+        _ => return,
+    }
+    *redraw = true;
+}
+
+/// Add a video downloaded by "Add from URL" to the library, or tell why the download failed
+pub fn finish_download(result: Result<WebVideo, String>, state: &mut AppState, database: &Database, resolver: &PathResolver) {
+    let AppState {
+        ref mut entries,
+        ref mut filtered_entries,
+        ref view_context,
+        ref mut notifications,
+        ..
+    } = *state;
+    let video = match result {
+        Ok(video) => video,
+        Err(e) => {
+            logger::log_error(&format!("Download failed: {}", e));
+            notifications.error(format!("Download failed: {}", e));
+            return;
+        }
+    };
+    match web_video::import(&video, database, resolver) {
+        Ok(episode_id) => {
+            logger::log_info(&format!("Added {} from the web as episode {}", video.path.display(), episode_id));
+            notifications.info(format!("Added {}", video.title));
+            if let Err(e) = hooks::fire(database, HookEvent::Import, episode_id, &video.path) {
+                notifications.warn(e);
+            }
+            reload_entries(database, view_context, entries, notifications);
+            *filtered_entries = entries.clone();
+        }
+        Err(e) => report_database_error(&format!("Failed to add {}", video.path.display()), &e, notifications),
+    }
+}
+
+/// Show how a background Trakt or Jellyfin sync ended, reloading the list for what it marked watched
+pub fn finish_sync(result: Result<String, String>, state: &mut AppState, database: &Database) {
+    let AppState {
+        ref mut entries,
+        ref mut filtered_entries,
+        ref view_context,
+        ref mut notifications,
+        ref mut sync_status,
+        ..
+    } = *state;
+    *sync_status = None;
+    match result {
+        Ok(summary) => {
+            logger::log_info(&summary);
+            notifications.info(summary);
+            reload_entries(database, view_context, entries, notifications);
+            *filtered_entries = entries.clone();
+        }
+        Err(e) => {
+            logger::log_error(&e);
+            notifications.error(e);
+        }
+    }
+}
+
+/// Move the library to the root folder `check` looked at: put the database there and open it,
+/// and only then save its new location in the config, returning the new database path and handle
+fn relocate_library(
+    check: &RelocationCheck,
+    config: &mut Config,
+    config_path: &std::path::PathBuf,
+    database: &mut Option<Arc<Database>>,
+    resolver: &mut Option<PathResolver>,
+) -> Result<(PathBuf, Arc<Database>), String> {
+    let old_db_path = config.get_database_path().ok_or("No library is open")?;
+    let (db_path, copied) = relocate::move_database(&old_db_path, &check.new_root).map_err(|e| e.to_string())?;
+    let opened = Database::open(&db_path)
+        .map_err(|e| e.to_string())
+        .and_then(|database| PathResolver::from_database_path(&db_path).map(|resolver| (database, resolver)).map_err(|e| e.to_string()));
+    let (new_database, mut new_resolver) = match opened {
+        Ok(opened) => opened,
+        Err(e) => {
+            // Leave nothing behind in the new folder when the copy can't be used
+            if copied {
+                let _ = std::fs::remove_file(&db_path);
+            }
+            return Err(e);
+        }
+    };
+    let new_database = Arc::new(new_database);
+    config.set_database_path(db_path.clone());
+    crate::config::save_config(config, config_path);
+    new_database.add_library_roots(&mut new_resolver, &config.library_roots);
+    *database = Some(Arc::clone(&new_database));
+    *resolver = Some(new_resolver);
+    Ok((db_path, new_database))
+}
+
+/// Import the videos under a directory, returning how many were new
+fn import_directory(dir: &Path, config: &Config, database: &Database, resolver: &PathResolver) -> usize {
+    let scan_rules = config.scan_rules_for(resolver.get_root_dir());
+    let mut imported_count = 0;
+    for entry in scan_rules
+        .walk(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| scan_rules.accepts(e.path()) && !scan_rules.excludes(resolver.get_root_dir(), e.path()))
+    {
+        let location = location::encode(entry.path());
+        let name = entry.file_name().to_string_lossy().to_string();
+        match database.import_episode_relative(&location, &name, resolver) {
+            Ok(true) => imported_count += 1,
+            Ok(false) => {}
+            Err(e) => logger::log_warn(&format!("Skipping file: {} - {}", location, e)),
+        }
+    }
+    imported_count
+}
+
+/// Load the view containing a digest item, returning the item's index in it
+pub fn jump_to_digest_item(
+    database: &Database,
+    target: DigestTarget,
+    entries: &mut Vec<Entry>,
+    view_context: &mut ViewContext,
+) -> Result<usize, DatabaseError> {
+    match target {
+        DigestTarget::Series(series_id) => {
+            let series = database.get_series_by_id(series_id)?;
+            *entries = database.get_entries_for_series(series_id)?;
+            *view_context = ViewContext::Series {
+                series_id,
+                series_name: series.name,
+            };
+            Ok(find_first_unwatched_index(database, entries).unwrap_or(0))
+        }
+        DigestTarget::Episode(episode_id) => {
+            let details = database.get_episode_detail(episode_id)?;
+            match (details.series, details.season) {
+                (Some(series), Some(season)) => {
+                    *entries = database.get_entries_for_season(season.id)?;
+                    *view_context = ViewContext::Season {
+                        season_id: season.id,
+                        series_name: series.name,
+                        season_number: season.number,
+                    };
+                }
+                (Some(series), None) => {
+                    *entries = database.get_entries_for_series(series.id)?;
+                    *view_context = ViewContext::Series {
+                        series_id: series.id,
+                        series_name: series.name,
+                    };
+                }
+                _ => {
+                    *entries = database.get_entries()?;
+                    *view_context = ViewContext::TopLevel;
+                }
+            }
+            Ok(entries
+                .iter()
+                .position(|entry| matches!(entry, Entry::Episode { episode_id: id, .. } if *id == episode_id))
+                .unwrap_or(0))
+        }
+    }
+}
+
+/// Work out the name the rename template gives the file of the episode being edited, from its
+/// saved details, and open the preview
+/// Unsaved changes in the editor have to be saved first, so the file never gets a name that
+/// leaving the editor with Esc would take back
+pub fn open_rename_preview(state: &mut AppState, context: &AppContext) {
+    let AppContext { config, database, resolver, .. } = *context;
+    let AppState {
+        current_item,
+        ref filtered_entries,
+        ref dirty_fields,
+        ref mut rename_preview,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
+    *redraw = true;
+    let Some(Entry::Episode { episode_id, .. }) = filtered_entries.get(current_item) else {
+        return;
+    };
+    if !dirty_fields.is_empty() {
+        notifications.warn("Save the changes with [F2] before renaming the file");
+        return;
+    }
+    let edit_details = match database.get_episode_detail(*episode_id) {
+        Ok(details) => details,
+        Err(e) => {
+            report_database_error("Failed to load the episode's details", &e, notifications);
+            return;
+        }
+    };
+    let from = match database.get_episode_absolute_location(*episode_id, resolver) {
+        Ok(location) => location::decode(&location),
+        Err(e) => {
+            report_database_error("Failed to find the episode's file", &e, notifications);
+            return;
+        }
+    };
+    let fields = rename::RenameFields {
+        series: edit_details.series.as_ref().map(|series| series.name.clone()),
+        season: edit_details.season.as_ref().map(|season| season.number),
+        episode: edit_details.episode_number.trim().parse().ok(),
+        title: edit_details.title.clone(),
+        year: edit_details.year.clone(),
+        ext: from.extension().map(|ext| ext.to_string_lossy().to_string()).unwrap_or_default(),
+    };
+    let template = if fields.series.is_some() { &config.rename_template } else { &config.rename_movie_template };
+    match rename::rename_target(&from, template, &fields) {
+        Ok(to) if to == from => notifications.info("The file already has that name"),
+        Ok(to) => {
+            *rename_preview = Some(rename::RenamePreview { episode_id: *episode_id, from, to });
+            state.open_modal(Mode::RenameFile);
+        }
+        Err(e) => notifications.error(e),
+    }
+}
+
+// Handle RenameFile mode - the file's current and new paths are shown, Enter renames it
+pub fn handle_rename_file_mode(code: KeyCode, state: &mut AppState, context: &AppContext) {
+    let AppState {
+        ref mut rename_preview,
+        ref mut entries,
+        ref mut filtered_entries,
+        ref playing_file,
+        ref mut notifications,
+        ..
+    } = *state;
+    match code {
+        KeyCode::Enter => {
+            let Some(preview) = rename_preview.take() else {
+                state.close_modal();
+                return;
+            };
+            if playing_file.as_deref().is_some_and(|playing| location::decode(playing) == preview.from) {
+                notifications.warn("Stop playing the video before renaming its file");
+            } else {
+                match rename_episode_file(&preview, context.database, context.resolver) {
+                    Ok(location) => {
+                        for entry in entries.iter_mut().chain(filtered_entries.iter_mut()) {
+                            if let Entry::Episode { episode_id, location: entry_location, .. } = entry {
+                                if *episode_id == preview.episode_id {
+                                    *entry_location = location.clone();
+                                }
+                            }
+                        }
+                        let name = preview.to.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                        notifications.info(format!("Renamed the file to {}", name));
+                    }
+                    Err(e) => {
+                        logger::log_error(&format!("Failed to rename {}: {}", preview.from.display(), e));
+                        notifications.error(e);
+                    }
+                }
+            }
+            state.close_modal();
+        }
+        KeyCode::Esc => {
+            *rename_preview = None;
+            state.close_modal();
+        }
         _ => {}
     }
 }
+
+/// Rename the file of `preview` and point its episode at the new name, returning the location now stored
+/// The file is put back when the library can't be updated, so the two never disagree
+fn rename_episode_file(preview: &rename::RenamePreview, database: &Database, resolver: &PathResolver) -> Result<String, String> {
+    rename::rename_file(&preview.from, &preview.to)?;
+    if let Err(e) = database.update_episode_location(preview.episode_id, &preview.to, resolver) {
+        let _ = std::fs::rename(&preview.to, &preview.from);
+        return Err(format!("Failed to update the library: {}", e));
+    }
+    logger::log_info(&format!("Renamed {} to {}", preview.from.display(), preview.to.display()));
+    let relative = resolver.locate(&preview.to).map(|(_, relative)| relative).unwrap_or_else(|_| preview.to.clone());
+    Ok(location::encode(&relative))
+}
+
+// Handle DuplicateReview mode - user marks which copies of each video to keep, and Enter trashes the rest
+pub fn handle_duplicate_review_mode(code: KeyCode, state: &mut AppState, context: &AppContext) {
+    let AppState {
+        ref mut duplicate_groups,
+        ref mut selected_duplicate,
+        ref mut entries,
+        ref mut filtered_entries,
+        ref view_context,
+        ref playing_file,
+        ref mut notifications,
+        ref mut redraw,
+        ..
+    } = *state;
+    let copy_count: usize = duplicate_groups.iter().map(|group| group.copies.len()).sum();
+    match code {
+        KeyCode::Up if *selected_duplicate > 0 => *selected_duplicate -= 1,
+        KeyCode::Down if *selected_duplicate + 1 < copy_count => *selected_duplicate += 1,
+        KeyCode::Char(' ') => {
+            if let Some(copy) = duplicate_groups.iter_mut().flat_map(|group| group.copies.iter_mut()).nth(*selected_duplicate) {
+                copy.keep = !copy.keep;
+            }
+        }
+        KeyCode::Enter => {
+            if duplicate_groups.iter().any(|group| !group.keeps_one()) {
+                notifications.warn("Keep at least one copy of each video");
+                *redraw = true;
+                return;
+            }
+            let trashed = trash_duplicates(duplicate_groups, playing_file.as_deref(), context.database, context.resolver, notifications);
+            if trashed > 0 {
+                notifications.info(format!("Moved {} duplicate copies to the trash", trashed));
+            }
+            duplicate_groups.clear();
+            reload_entries(context.database, view_context, entries, notifications);
+            *filtered_entries = entries.clone();
+            state.close_modal();
+            return;
+        }
+        KeyCode::Esc => {
+            duplicate_groups.clear();
+            state.close_modal();
+            return;
+        }
+        _ => return,
+    }
+    *redraw = true;
+}
+
+/// Remove the files of the copies in `groups` marked for the trash, and their episodes from the
+/// library, returning how many were removed
+/// A copy that is playing or can't be removed is left in place with a message
+fn trash_duplicates(
+    groups: &[DuplicateGroup],
+    playing_file: Option<&str>,
+    database: &Database,
+    resolver: &PathResolver,
+    notifications: &mut Notifications,
+) -> usize {
+    let mut trashed = 0;
+    for copy in groups.iter().flat_map(DuplicateGroup::to_trash) {
+        let paths: Vec<PathBuf> = match database.get_episode_versions(copy.episode_id, resolver) {
+            Ok(versions) => versions.into_iter().map(|version| location::decode(&version.location)).collect(),
+            Err(e) => {
+                report_database_error(&format!("Failed to find the files of '{}'", copy.name), &e, notifications);
+                continue;
+            }
+        };
+        if playing_file.is_some_and(|playing| paths.iter().any(|path| *path == location::decode(playing))) {
+            notifications.warn(format!("'{}' is playing; it was left in place", copy.name));
+            continue;
+        }
+        match crate::file_removal::remove_episode_files(&paths, resolver) {
+            Ok(_) => match database.delete_episode(copy.episode_id) {
+                Ok(()) => {
+                    logger::log_info(&format!("Removed duplicate copy {}", copy.path.display()));
+                    trashed += 1;
+                }
+                Err(e) => report_database_error(&format!("Removed the files of '{}' but failed to delete it", copy.name), &e, notifications),
+            },
+            Err(message) => {
+                logger::log_warn(&message);
+                notifications.error(message);
+            }
+        }
+    }
+    trashed
+}
@@ -0,0 +1,223 @@
+// Hooks: functions of a user script run when videos are imported, played or watched
+// The scripting engine lives in its own submodule behind the `scripting` cargo feature
+
+#[cfg(feature = "scripting")]
+mod script;
+
+use crate::database::{Database, DbResult};
+use crate::path_resolver::PathResolver;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "scripting")]
+use std::sync::RwLock;
+
+/// Events a hook script can handle, each with a function of its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    Import,
+    Play,
+    Watched,
+}
+
+impl HookEvent {
+    /// Name of the script function run for the event
+    pub fn function_name(&self) -> &'static str {
+        match self {
+            HookEvent::Import => "on_import",
+            HookEvent::Play => "on_play",
+            HookEvent::Watched => "on_watched",
+        }
+    }
+}
+
+/// An episode as a hook sees it
+///
+/// A hook may change the title, year, episode number, watched status and notes; the id, path,
+/// series and season are there to read.
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HookEpisode {
+    pub id: usize,
+    pub path: String,
+    pub title: String,
+    pub year: String,
+    pub series: Option<String>,
+    pub season: Option<usize>,
+    pub episode_number: String,
+    pub watched: bool,
+    pub notes: String,
+}
+
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+impl HookEpisode {
+    /// The episode with the file at `path`, as it is in `database` now
+    pub fn load(database: &Database, episode_id: usize, path: &Path) -> DbResult<Self> {
+        let details = database.get_episode_detail(episode_id)?;
+        Ok(HookEpisode {
+            id: episode_id,
+            path: path.to_string_lossy().to_string(),
+            title: details.title,
+            year: details.year,
+            series: details.series.map(|series| series.name),
+            season: details.season.map(|season| season.number),
+            episode_number: details.episode_number,
+            watched: details.watched == "true",
+            notes: details.notes.unwrap_or_default(),
+        })
+    }
+}
+
+/// Save what a hook changed from `original` to `edited`, returning the names of the changed fields
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+pub fn save_changes(database: &Database, original: &HookEpisode, edited: &HookEpisode) -> DbResult<Vec<&'static str>> {
+    let changes = [
+        ("title", edited.title.trim() != original.title.trim() && !edited.title.trim().is_empty()),
+        ("year", edited.year.trim() != original.year.trim()),
+        ("episode number", edited.episode_number.trim() != original.episode_number.trim()),
+        ("watched", edited.watched != original.watched),
+        ("notes", edited.notes.trim() != original.notes.trim()),
+    ];
+    let changes: Vec<&'static str> = changes.iter().filter(|(_, changed)| *changed).map(|(name, _)| *name).collect();
+
+    if changes.iter().any(|name| ["title", "year", "episode number"].contains(name)) {
+        let mut details = database.get_episode_detail(original.id)?;
+        if changes.contains(&"title") {
+            details.title = edited.title.trim().to_string();
+        }
+        details.year = edited.year.trim().to_string();
+        details.episode_number = edited.episode_number.trim().to_string();
+        database.update_episode_detail(original.id, &details)?;
+    }
+    if changes.contains(&"watched") {
+        database.toggle_watched_status(original.id)?;
+    }
+    if changes.contains(&"notes") {
+        database.update_episode_notes(original.id, &edited.notes)?;
+    }
+    Ok(changes)
+}
+
+/// Where the `hook_script` setting points: relative paths are in the folder of config.yaml
+pub fn script_path(setting: &str, config_dir: &Path) -> PathBuf {
+    config_dir.join(setting.trim())
+}
+
+#[cfg(feature = "scripting")]
+static SCRIPT: RwLock<Option<script::Script>> = RwLock::new(None);
+
+/// Compile the hook script at `path` and install it for `fire`, or remove the installed one for None
+#[cfg(feature = "scripting")]
+pub fn install(path: Option<&Path>) -> Result<(), String> {
+    let script = match path {
+        Some(path) => {
+            let source = std::fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+            let script = script::Script::compile(&source).map_err(|e| format!("Error in {}: {}", path.display(), e))?;
+            let events: Vec<&str> = [HookEvent::Import, HookEvent::Play, HookEvent::Watched]
+                .into_iter()
+                .filter(|event| script.handles(*event))
+                .map(|event| event.function_name())
+                .collect();
+            crate::logger::log_info(&format!("Loaded hook script {} with {}", path.display(), events.join(", ")));
+            Some(script)
+        }
+        None => None,
+    };
+    *SCRIPT.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = script;
+    Ok(())
+}
+
+#[cfg(not(feature = "scripting"))]
+pub fn install(path: Option<&Path>) -> Result<(), String> {
+    match path {
+        Some(_) => Err("hook_script is not available in this build (enable the scripting feature)".to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Whether the installed script has a function for `event`
+#[cfg(feature = "scripting")]
+pub fn handles(event: HookEvent) -> bool {
+    SCRIPT
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+        .is_some_and(|script| script.handles(event))
+}
+
+#[cfg(not(feature = "scripting"))]
+pub fn handles(_event: HookEvent) -> bool {
+    false
+}
+
+/// Run the hook for `event` on an episode, returning the names of the fields it changed
+///
+/// Changes and failures are written to the log; nothing happens when no script handles the event.
+pub fn fire(database: &Database, event: HookEvent, episode_id: usize, path: &Path) -> Result<Vec<&'static str>, String> {
+    if !handles(event) {
+        return Ok(Vec::new());
+    }
+    let result = run(database, event, episode_id, path);
+    match result {
+        Ok(ref changes) if !changes.is_empty() => crate::logger::log_info(&format!(
+            "{} changed the {} of episode {}",
+            event.function_name(),
+            changes.join(", "),
+            episode_id
+        )),
+        Ok(_) => {}
+        Err(ref e) => crate::logger::log_warn(e),
+    }
+    result
+}
+
+#[cfg(feature = "scripting")]
+fn run(database: &Database, event: HookEvent, episode_id: usize, path: &Path) -> Result<Vec<&'static str>, String> {
+    let failed = |e: String| format!("{} failed for episode {}: {}", event.function_name(), episode_id, e);
+    let original = HookEpisode::load(database, episode_id, path).map_err(|e| failed(e.to_string()))?;
+    let edited = {
+        let script = SCRIPT.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match script.as_ref() {
+            Some(script) => script.call(event, &original).map_err(failed)?,
+            None => None,
+        }
+    };
+    match edited {
+        Some(edited) => save_changes(database, &original, &edited).map_err(|e| failed(e.to_string())),
+        None => Ok(Vec::new()),
+    }
+}
+
+#[cfg(not(feature = "scripting"))]
+fn run(_database: &Database, _event: HookEvent, _episode_id: usize, _path: &Path) -> Result<Vec<&'static str>, String> {
+    Ok(Vec::new())
+}
+
+/// Run the hook for `event` on each of `files`, found in the library by path, returning how
+/// many episodes it changed; the error names the first failure and how many more there were
+pub fn fire_for_files(database: &Database, event: HookEvent, files: &[PathBuf], resolver: &PathResolver) -> Result<usize, String> {
+    if !handles(event) {
+        return Ok(0);
+    }
+    let mut changed = 0;
+    let mut errors = Vec::new();
+    for path in files {
+        let result = match database.find_episode_id(path, resolver) {
+            Ok(Some(episode_id)) => fire(database, event, episode_id, path),
+            Ok(None) => continue,
+            Err(e) => {
+                let message = format!("{} couldn't find {}: {}", event.function_name(), path.display(), e);
+                crate::logger::log_warn(&message);
+                Err(message)
+            }
+        };
+        match result {
+            Ok(changes) if !changes.is_empty() => changed += 1,
+            Ok(_) => {}
+            Err(e) => errors.push(e),
+        }
+    }
+    match errors.as_slice() {
+        [] => Ok(changed),
+        [error] => Err(error.clone()),
+        [error, rest @ ..] => Err(format!("{} (and {} more, see the log)", error, rest.len())),
+    }
+}
@@ -0,0 +1,126 @@
+// Hook scripts written in Rhai (https://rhai.rs)
+//
+// Each hook is a function taking the episode as a map, such as `fn on_import(episode)`. Returning
+// the map, changed or not, saves the changes to the fields a hook may change; returning nothing
+// leaves the episode as it was. `print` writes to the log and `run` starts a program.
+
+use super::{HookEpisode, HookEvent};
+use crate::custom_commands;
+use crate::logger;
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+use std::process::Command;
+use std::thread;
+
+/// Steps a hook may take before it is stopped, so a script stuck in a loop can't hang the app
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// A compiled hook script with the engine that runs it
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+}
+
+impl Script {
+    pub fn compile(source: &str) -> Result<Script, String> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.on_print(|text| logger::log_info(&format!("[hook] {}", text)));
+        engine.on_debug(|text, _, _| logger::log_debug(&format!("[hook] {}", text)));
+        engine.register_fn("run", |program: &str| spawn(program, Array::new()));
+        engine.register_fn("run", spawn);
+        let ast = engine.compile(source).map_err(|e| e.to_string())?;
+        Ok(Script { engine, ast })
+    }
+
+    /// Whether the script has a function for `event` taking the episode
+    pub fn handles(&self, event: HookEvent) -> bool {
+        self.ast
+            .iter_functions()
+            .any(|function| function.name == event.function_name() && function.params.len() == 1)
+    }
+
+    /// Run the function for `event` on `episode`, returning the episode it gave back, if any
+    pub fn call(&self, event: HookEvent, episode: &HookEpisode) -> Result<Option<HookEpisode>, String> {
+        let result: Dynamic = self
+            .engine
+            .call_fn(&mut Scope::new(), &self.ast, event.function_name(), (to_map(episode),))
+            .map_err(|e| e.to_string())?;
+        if result.is_unit() {
+            return Ok(None);
+        }
+        match result.try_cast::<Map>() {
+            Some(map) => from_map(episode, &map).map(Some),
+            None => Err(format!("{} must return the episode or nothing", event.function_name())),
+        }
+    }
+}
+
+/// Start `program` with `args` in the background, its output going to the log
+fn spawn(program: &str, args: Array) {
+    let mut command = Command::new(program);
+    command.args(args.iter().map(|arg| arg.to_string()));
+    let label = format!("hook {}", program);
+    thread::spawn(move || {
+        if let Err(e) = custom_commands::run(&label, command) {
+            logger::log_warn(&format!("{} failed: {}", label, e));
+        }
+    });
+}
+
+fn to_map(episode: &HookEpisode) -> Map {
+    let number = |text: &str| text.trim().parse::<rhai::INT>().map(Dynamic::from_int).unwrap_or(Dynamic::UNIT);
+    let mut map = Map::new();
+    map.insert("id".into(), Dynamic::from_int(episode.id as rhai::INT));
+    map.insert("path".into(), episode.path.clone().into());
+    map.insert("title".into(), episode.title.clone().into());
+    map.insert("year".into(), number(&episode.year));
+    map.insert("series".into(), episode.series.clone().map(Dynamic::from).unwrap_or(Dynamic::UNIT));
+    map.insert(
+        "season".into(),
+        episode.season.map(|season| Dynamic::from_int(season as rhai::INT)).unwrap_or(Dynamic::UNIT),
+    );
+    map.insert("episode_number".into(), number(&episode.episode_number));
+    map.insert("watched".into(), Dynamic::from_bool(episode.watched));
+    map.insert("notes".into(), episode.notes.clone().into());
+    map
+}
+
+/// The episode as the hook left `map`; fields it can't change are taken from `original`
+fn from_map(original: &HookEpisode, map: &Map) -> Result<HookEpisode, String> {
+    let mut edited = original.clone();
+    if let Some(value) = map.get("title") {
+        edited.title = text(value).ok_or("title must be text")?;
+    }
+    if let Some(value) = map.get("year") {
+        edited.year = number(value).ok_or("year must be a number or ()")?;
+    }
+    if let Some(value) = map.get("episode_number") {
+        edited.episode_number = number(value).ok_or("episode_number must be a number or ()")?;
+    }
+    if let Some(value) = map.get("watched") {
+        edited.watched = value.as_bool().map_err(|_| "watched must be true or false")?;
+    }
+    if let Some(value) = map.get("notes") {
+        edited.notes = text(value).ok_or("notes must be text")?;
+    }
+    Ok(edited)
+}
+
+/// Text of a string or number, empty for ()
+fn text(value: &Dynamic) -> Option<String> {
+    if value.is_unit() {
+        Some(String::new())
+    } else if value.is_string() || value.is_int() {
+        Some(value.to_string())
+    } else {
+        None
+    }
+}
+
+/// A number, or text holding one, as the library keeps it; empty for ()
+fn number(value: &Dynamic) -> Option<String> {
+    match text(value) {
+        Some(text) if text.trim().is_empty() || text.trim().parse::<u64>().is_ok() => Some(text.trim().to_string()),
+        _ => None,
+    }
+}
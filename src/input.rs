@@ -0,0 +1,135 @@
+use crossterm::event::{self, Event, KeyCode, KeyEvent};
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Key presses closer together than this are treated as a held key
+pub const REPEAT_WINDOW: Duration = Duration::from_millis(150);
+
+/// Clicks on the same item closer together than this count as a double-click
+pub const DOUBLE_CLICK_WINDOW: Duration = Duration::from_millis(400);
+
+/// While the filter is typed, the list is filtered again once no key has come for this long
+pub const FILTER_DEBOUNCE: Duration = Duration::from_millis(30);
+
+/// Where the main loop reads terminal events from: the terminal, or a script of events in tests
+pub trait EventSource {
+    /// Wait up to `timeout` for an event, returning whether one is ready
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool>;
+    /// The next event, waiting for it if none is ready
+    fn read(&mut self) -> io::Result<Event>;
+}
+
+/// Events from the terminal
+pub struct TerminalEvents;
+
+impl EventSource for TerminalEvents {
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        event::poll(timeout)
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        event::read()
+    }
+}
+
+/// Check whether a key moves the selection one row at a time
+pub fn is_scroll_key(code: KeyCode) -> bool {
+    matches!(code, KeyCode::Up | KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('k'))
+}
+
+/// Tracks how long a scroll key has been held and grows the scroll step along a curve
+pub struct ScrollAccelerator {
+    curve: Vec<usize>,
+    repeats_per_level: usize,
+    last_key: Option<KeyCode>,
+    last_time: Option<Instant>,
+    streak: usize,
+}
+
+impl ScrollAccelerator {
+    pub fn new(curve: Vec<usize>, repeats_per_level: usize) -> Self {
+        // An empty curve or zero steps would stop scrolling entirely
+        let mut curve: Vec<usize> = curve.into_iter().filter(|step| *step > 0).collect();
+        if curve.is_empty() {
+            curve.push(1);
+        }
+        ScrollAccelerator {
+            curve,
+            repeats_per_level: repeats_per_level.max(1),
+            last_key: None,
+            last_time: None,
+            streak: 0,
+        }
+    }
+
+    /// Rows to move for a key press at the given time
+    pub fn step_for(&mut self, code: KeyCode, now: Instant) -> usize {
+        let held = self.last_key == Some(code)
+            && self
+                .last_time
+                .is_some_and(|last| now.duration_since(last) <= REPEAT_WINDOW);
+
+        self.streak = if held { self.streak + 1 } else { 0 };
+        self.last_key = Some(code);
+        self.last_time = Some(now);
+
+        let level = (self.streak / self.repeats_per_level).min(self.curve.len() - 1);
+        self.curve[level]
+    }
+
+    /// Forget the current streak, e.g. after any non-scroll key
+    pub fn reset(&mut self) {
+        self.last_key = None;
+        self.last_time = None;
+        self.streak = 0;
+    }
+}
+
+/// Merge already-queued repeats of a scroll key into one step count so a held key redraws once per batch
+/// The first different event is handed back through `pending` to be processed next
+pub fn coalesce_scroll_repeats(
+    events: &mut dyn EventSource,
+    code: KeyCode,
+    accelerator: &mut ScrollAccelerator,
+    pending: &mut Option<Event>,
+) -> io::Result<usize> {
+    let mut steps = accelerator.step_for(code, Instant::now());
+
+    while events.poll(Duration::ZERO)? {
+        match events.read()? {
+            Event::Key(KeyEvent { code: next, .. }) if next == code => {
+                steps += accelerator.step_for(code, Instant::now());
+            }
+            other => {
+                *pending = Some(other);
+                break;
+            }
+        }
+    }
+
+    Ok(steps)
+}
+
+/// Remembers the last click so a second click on the same item can be recognised as a double-click
+#[derive(Default)]
+pub struct ClickTracker {
+    last: Option<(usize, Instant)>,
+}
+
+impl ClickTracker {
+    /// Record a click on an item, returning true when it completes a double-click
+    pub fn click(&mut self, item: usize, now: Instant) -> bool {
+        let double = self
+            .last
+            .is_some_and(|(last_item, last_time)| last_item == item && now.duration_since(last_time) <= DOUBLE_CLICK_WINDOW);
+        self.last = if double { None } else { Some((item, now)) };
+        double
+    }
+}
+
+/// Index of the entry drawn on a screen row of the Browse list, if any
+pub fn entry_at_row(row: usize, list_top: usize, visible_rows: usize, first_entry: usize, entry_count: usize) -> Option<usize> {
+    let offset = row.checked_sub(list_top)?;
+    let index = first_entry + offset;
+    (offset < visible_rows && index < entry_count).then_some(index)
+}
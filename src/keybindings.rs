@@ -0,0 +1,346 @@
+use crate::keymap::{self, Key, KeySequence, Keymap};
+use crate::menu::{self, MenuItem};
+use crate::util::{Entry, Mode};
+use crossterm::event::KeyCode;
+
+/// A key (or group of keys) and what it does
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBinding {
+    pub keys: String,
+    pub description: String,
+}
+
+/// A titled group of key bindings shown on the help screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct HelpSection {
+    pub title: &'static str,
+    pub bindings: Vec<KeyBinding>,
+}
+
+/// Browse actions of the keymap and the keys Browse mode keeps for itself, in the order
+/// the help screen lists them; actions sharing a line have their keys written together
+const BROWSE_KEYS: &[(BrowseKeys, &str)] = &[
+    (BrowseKeys::Actions(&["move_up", "move_down"]), "move the selection (hold to scroll faster)"),
+    (BrowseKeys::Actions(&["page_up", "page_down"]), "move a page at a time"),
+    (BrowseKeys::Actions(&["half_page_up", "half_page_down"]), "move half a page at a time"),
+    (BrowseKeys::Actions(&["move_top", "move_bottom"]), "jump to the first or last entry"),
+    (BrowseKeys::Fixed("number, move"), "move that many times, as in 15j (1-3 start a number when nothing is pinned there)"),
+    (BrowseKeys::Fixed("#number, Enter"), "select the entry with that number"),
+    (BrowseKeys::Actions(&["move_left", "move_right"]), "move between tiles in the grid view"),
+    (BrowseKeys::Actions(&["narrow_list", "widen_list"]), "make the list narrower or wider"),
+    (BrowseKeys::Actions(&["play_selected"]), "play an episode or open a series or season"),
+    (BrowseKeys::Actions(&["toggle_mark"]), "mark an episode for batch actions in the menu"),
+    (BrowseKeys::Actions(&["copy_path", "copy_title"]), "copy the episode's file path or title to the clipboard"),
+    (BrowseKeys::Actions(&["back"]), "go back a level, or quit at the top level (stops a running rescan or clears any marks first)"),
+    (BrowseKeys::Actions(&["start_filter"]), "filter the list"),
+    (BrowseKeys::Actions(&["next_match", "previous_match"]), "jump to the next or previous match of the filter"),
+    (BrowseKeys::Fixed("1-3"), "switch a pinned filter on or off"),
+    (BrowseKeys::Actions(&["pin_tab"]), "pin the view, its filter and sort to a new tab"),
+    (BrowseKeys::Fixed("Alt+1-9"), "switch to a tab"),
+    (BrowseKeys::Actions(&["close_tab"]), "close the tab of the view"),
+    (BrowseKeys::Actions(&["toggle_grid"]), "switch between the list and a grid of tiles"),
+    (BrowseKeys::Actions(&["open_menu"]), "open the menu"),
+    (BrowseKeys::Actions(&["show_help"]), "show this help"),
+    (BrowseKeys::Actions(&["show_notifications"]), "show recent status messages"),
+    (BrowseKeys::Actions(&["record_macro"]), "start recording the keys pressed as a macro, or stop"),
+    (BrowseKeys::Actions(&["replay_macro"]), "replay the recorded macro"),
+    (BrowseKeys::Actions(&["command_line"]), "run an action by name (:rescan), go to an entry (:12) or quit (:q)"),
+];
+
+enum BrowseKeys {
+    Actions(&'static [&'static str]),
+    Fixed(&'static str),
+}
+
+/// Keys bound to Browse actions as one help line, e.g. "↑/↓, k/j" for moving up and down
+/// The first keys of each action are written together, then the second keys, and so on
+fn browse_keys_label(keymap: &Keymap, actions: &[&str]) -> String {
+    let keys: Vec<&[KeySequence]> = actions.iter().map(|action| keymap.keys_for(action)).collect();
+    let rows = keys.iter().map(|keys| keys.len()).max().unwrap_or(0);
+    (0..rows)
+        .map(|row| {
+            let row_keys: Vec<&KeySequence> = keys.iter().filter_map(|keys| keys.get(row)).collect();
+            let single: Option<Vec<&Key>> = row_keys.iter().map(|keys| keys.single()).collect();
+            match single {
+                Some(single) if single.windows(2).all(|pair| pair[0].modifiers == pair[1].modifiers) => {
+                    let codes: Vec<String> = single.iter().map(|key| key.code_label()).collect();
+                    format!("{}{}", single[0].modifier_label(), codes.join("/"))
+                }
+                _ => row_keys.iter().map(|keys| keys.label()).collect::<Vec<_>>().join("/"),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The first keys bound to a Browse action, for the footer
+fn first_key_label(keymap: &Keymap, action: &str) -> Option<String> {
+    keymap.keys_for(action).first().map(KeySequence::label)
+}
+
+/// The help section of the Browse keys, from the installed keymap
+fn browse_section() -> HelpSection {
+    let keymap = keymap::keymap();
+    HelpSection {
+        title: "Browse",
+        bindings: BROWSE_KEYS
+            .iter()
+            .map(|(keys, description)| {
+                let keys = match keys {
+                    BrowseKeys::Actions(actions) => browse_keys_label(&keymap, actions),
+                    BrowseKeys::Fixed(keys) => keys.to_string(),
+                };
+                KeyBinding {
+                    keys,
+                    description: description.to_string(),
+                }
+            })
+            .filter(|binding| !binding.keys.is_empty())
+            .collect(),
+    }
+}
+
+/// Keys handled by the other modes' handlers, in the order the help screen lists them
+const MODE_KEYS: &[(&str, &[(&str, &str)])] = &[
+    ("Filter", &[
+        ("type", "narrow the list as you type"),
+        ("←/→, Home/End", "move the cursor"),
+        ("Ctrl+←/→", "move the cursor a word at a time"),
+        ("↑/↓", "recall recently kept filters"),
+        ("Enter", "keep the filter"),
+        ("Esc", "clear the filter"),
+    ]),
+    ("Edit", &[
+        ("↑/↓", "change field"),
+        ("←/→, Home/End", "move the cursor"),
+        ("Ctrl+←/→", "move the cursor a word at a time"),
+        ("+/-", "change the episode or season number"),
+        ("Ctrl+Z", "put the field back the way it was"),
+        ("F2", "save changes"),
+        ("F3", "rename the file after the details, using the rename template"),
+        ("Esc", "cancel"),
+    ]),
+    ("Series selection", &[
+        ("↑/↓", "choose a series"),
+        ("Enter", "assign the episode (or the marked episodes) to the series"),
+        ("+", "create a new series"),
+        ("Esc", "cancel"),
+    ]),
+    ("Text windows (series name, notes, alternate titles)", &[
+        ("←/→, Home/End", "move the cursor"),
+        ("↑/↓", "change row (alternate titles)"),
+        ("Enter", "save"),
+        ("Esc", "cancel"),
+    ]),
+    ("Menu", &[
+        ("↑/↓", "choose an action"),
+        ("Enter", "run the action"),
+        ("Esc", "close the menu"),
+    ]),
+    ("Confirmation", &[
+        ("←/→, Tab", "choose Yes or No"),
+        ("Enter", "answer with the highlighted button"),
+        ("y", "yes, go ahead"),
+        ("n, Esc", "no, leave everything as it is"),
+    ]),
+    ("Weekly digest and archive review", &[
+        ("↑/↓", "choose an item"),
+        ("Enter", "jump to the item, or extract the archive"),
+        ("S", "skip the archive"),
+        ("Esc", "close"),
+    ]),
+    ("Version picker", &[
+        ("↑/↓", "choose a version"),
+        ("Enter", "play it"),
+        ("Esc", "cancel"),
+    ]),
+    ("Theme picker", &[
+        ("↑/↓", "preview the next theme"),
+        ("Enter", "keep the theme"),
+        ("Esc", "go back to the previous theme"),
+    ]),
+    ("Torrent search", &[
+        ("Enter", "search, or download the selected result"),
+        ("↑/↓", "choose a result"),
+        ("Esc", "cancel"),
+    ]),
+    ("Help", &[
+        ("↑/↓, PgUp/PgDn", "scroll"),
+        ("Esc, ?", "close the help"),
+    ]),
+    ("Recent messages", &[
+        ("↑/↓, PgUp/PgDn", "scroll"),
+        ("Esc, n", "close the list"),
+    ]),
+    ("Scan report", &[
+        ("↑/↓, PgUp/PgDn", "scroll"),
+        ("Enter", "put episodes in the series found in folder names, and close the report"),
+        ("Esc", "close the report, leaving the series found in folder names"),
+    ]),
+    ("Relocate library", &[
+        ("Enter", "look for the videos in the folder typed, then move the library there"),
+        ("Esc", "leave the library where it is"),
+    ]),
+    ("Add from URL", &[
+        ("Enter", "download the video with yt-dlp in the background and add it to the library"),
+        ("Esc", "cancel"),
+    ]),
+    ("Duplicate review", &[
+        ("↑/↓", "choose a copy"),
+        ("Space", "switch the copy between keep and trash"),
+        ("Enter", "move the copies marked trash to the trash and remove them from the library"),
+        ("Esc", "close the review, leaving every copy"),
+    ]),
+    ("Startup report", &[
+        ("↑/↓, PgUp/PgDn", "scroll"),
+        ("Enter/Esc", "close the report; the problems stay in the log until config.yaml is fixed"),
+    ]),
+    ("Rename file", &[
+        ("Enter", "rename the file to the name shown and update the library"),
+        ("Esc", "keep the file's name and go back to the editor"),
+    ]),
+];
+
+/// Format a menu hotkey the way the header and menu show it
+pub fn format_hotkey(hotkey: &KeyCode) -> String {
+    match hotkey {
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::Char(c) => c.to_uppercase().to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Menu actions, listed by hotkey when they have one and otherwise as F1 menu entries
+fn menu_bindings(items: &[MenuItem]) -> Vec<KeyBinding> {
+    items
+        .iter()
+        .map(|item| KeyBinding {
+            keys: item
+                .hotkey
+                .as_ref()
+                .map(format_hotkey)
+                .unwrap_or_else(|| "F1 menu".to_string()),
+            description: item.label.to_lowercase(),
+        })
+        .collect()
+}
+
+/// Every help section: the Browse keys, the menu actions, then the keys of the other modes
+pub fn help_sections() -> Vec<HelpSection> {
+    let mut sections = vec![
+        browse_section(),
+        HelpSection {
+            title: "Menu actions (hotkeys work while browsing)",
+            bindings: menu_bindings(&menu::all_menu_items()),
+        },
+    ];
+    sections.extend(MODE_KEYS.iter().map(|&(title, keys)| HelpSection {
+        title,
+        bindings: keys
+            .iter()
+            .map(|(keys, description)| KeyBinding {
+                keys: keys.to_string(),
+                description: description.to_string(),
+            })
+            .collect(),
+    }));
+    sections
+}
+
+/// The help screen as lines of text, one per section title or key binding
+pub fn help_lines() -> Vec<String> {
+    let sections = help_sections();
+    let key_width = sections
+        .iter()
+        .flat_map(|section| &section.bindings)
+        .map(|binding| binding.keys.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = Vec::new();
+    for section in sections {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(section.title.to_string());
+        for binding in section.bindings {
+            let padding = key_width - binding.keys.chars().count();
+            lines.push(format!("  {}{}  {}", binding.keys, " ".repeat(padding), binding.description));
+        }
+    }
+    lines
+}
+
+/// Title of the help section holding a mode's keys
+fn mode_section(mode: &Mode, filter_mode: bool) -> Option<&'static str> {
+    match mode {
+        Mode::Browse if filter_mode => Some("Filter"),
+        Mode::Browse => Some("Browse"),
+        Mode::Edit => Some("Edit"),
+        Mode::SeriesSelect => Some("Series selection"),
+        Mode::SeriesCreate | Mode::NotesEdit | Mode::AltTitlesEdit => {
+            Some("Text windows (series name, notes, alternate titles)")
+        }
+        Mode::Menu => Some("Menu"),
+        Mode::Confirm => Some("Confirmation"),
+        Mode::Digest | Mode::ArchiveReview => Some("Weekly digest and archive review"),
+        Mode::VersionSelect => Some("Version picker"),
+        Mode::ThemeSelect => Some("Theme picker"),
+        Mode::TorrentSearchInput | Mode::TorrentSearchResults => Some("Torrent search"),
+        Mode::Help => Some("Help"),
+        Mode::Notifications => Some("Recent messages"),
+        Mode::ScanReport => Some("Scan report"),
+        Mode::Relocate => Some("Relocate library"),
+        Mode::AddFromUrl => Some("Add from URL"),
+        Mode::RenameFile => Some("Rename file"),
+        Mode::DuplicateReview => Some("Duplicate review"),
+        Mode::ConfigReport => Some("Startup report"),
+        Mode::Entry => None,
+    }
+}
+
+/// Keys for the footer help bar, most relevant first
+/// While browsing these are the keys for the selected entry followed by the hotkeys of the
+/// menu actions in `menu_items`; other modes list their section of the help screen
+pub fn footer_bindings(mode: &Mode, filter_mode: bool, selected_entry: Option<&Entry>, menu_items: &[MenuItem]) -> Vec<KeyBinding> {
+    let binding = |keys: &str, description: &str| KeyBinding {
+        keys: keys.to_string(),
+        description: description.to_string(),
+    };
+    if !matches!(mode, Mode::Browse) || filter_mode {
+        let Some(title) = mode_section(mode, filter_mode) else {
+            return Vec::new();
+        };
+        return MODE_KEYS
+            .iter()
+            .filter(|(section, _)| *section == title)
+            .flat_map(|(_, keys)| keys.iter())
+            .map(|(keys, description)| binding(keys, description))
+            .collect();
+    }
+
+    let keymap = keymap::keymap();
+    let action_binding = |action: &str, description: &str| {
+        first_key_label(&keymap, action).map(|keys| binding(&keys, description))
+    };
+    let mut bindings: Vec<KeyBinding> = match selected_entry {
+        Some(Entry::Episode { .. }) => vec![action_binding("play_selected", "play"), action_binding("toggle_mark", "mark")],
+        Some(_) => vec![action_binding("play_selected", "open")],
+        None => Vec::new(),
+    }
+    .into_iter()
+    .flatten()
+    .collect();
+    let with_hotkeys: Vec<MenuItem> = menu_items.iter().filter(|item| item.hotkey.is_some()).cloned().collect();
+    bindings.extend(menu_bindings(&with_hotkeys));
+    bindings.extend(
+        [
+            action_binding("start_filter", "filter"),
+            action_binding("open_menu", "menu"),
+            action_binding("show_help", "help"),
+        ]
+        .into_iter()
+        .flatten(),
+    );
+    bindings
+}
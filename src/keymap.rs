@@ -0,0 +1,483 @@
+//! Keys bound to Browse mode actions and menu hotkeys, with the user's rebindings from the config
+//!
+//! The keymap is built once at startup with `Keymap::from_config`, starting from a preset
+//! ("default", or "vim" for gg/G, Ctrl+D/U, n/N and a `:` command line), and installed with
+//! `set_keymap`. Key handling, the menu's hotkey hints and the help screen all read it through `keymap()`.
+
+use crate::action::Action;
+use crate::config::KeybindingsConfig;
+use crate::menu;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Browse actions by config name, with their default keys, in the order the help screen lists them
+const BROWSE_DEFAULTS: &[(&str, Action, &[&str])] = &[
+    ("move_up", Action::MoveUp, &["Up", "k"]),
+    ("move_down", Action::MoveDown, &["Down", "j"]),
+    ("page_up", Action::PageUp, &["PgUp"]),
+    ("page_down", Action::PageDown, &["PgDn"]),
+    ("move_left", Action::MoveLeft, &["Left"]),
+    ("move_right", Action::MoveRight, &["Right"]),
+    ("narrow_list", Action::ResizeSplit(false), &["Ctrl+Left"]),
+    ("widen_list", Action::ResizeSplit(true), &["Ctrl+Right"]),
+    ("play_selected", Action::PlaySelected, &["Enter"]),
+    ("toggle_mark", Action::ToggleMark, &["Space"]),
+    ("copy_path", Action::CopyPath, &["y"]),
+    ("copy_title", Action::CopyTitle, &["Y"]),
+    ("back", Action::Back, &["Esc"]),
+    ("start_filter", Action::StartFilter, &["/"]),
+    ("pin_tab", Action::PinTab, &["t"]),
+    ("close_tab", Action::CloseTab, &["T"]),
+    ("toggle_grid", Action::ToggleGrid, &["g"]),
+    ("open_menu", Action::OpenMenu, &["F1"]),
+    ("show_help", Action::ShowHelp, &["?"]),
+    ("show_notifications", Action::ShowNotifications, &["n"]),
+    ("record_macro", Action::RecordMacro, &["q"]),
+    ("replay_macro", Action::ReplayMacro, &["@"]),
+    ("move_top", Action::MoveTop, &[]),
+    ("move_bottom", Action::MoveBottom, &[]),
+    ("half_page_up", Action::HalfPageUp, &[]),
+    ("half_page_down", Action::HalfPageDown, &[]),
+    ("next_match", Action::NextMatch, &[]),
+    ("previous_match", Action::PreviousMatch, &[]),
+    ("command_line", Action::OpenCommandLine, &[]),
+];
+
+/// Keys the vim preset changes from the defaults; g and n make way for gg and the match jumps
+const VIM_PRESET: &[(&str, &[&str])] = &[
+    ("toggle_grid", &["Ctrl+g"]),
+    ("show_notifications", &["M"]),
+    ("move_top", &["g g", "Home"]),
+    ("move_bottom", &["G", "End"]),
+    ("half_page_up", &["Ctrl+u"]),
+    ("half_page_down", &["Ctrl+d"]),
+    ("next_match", &["n"]),
+    ("previous_match", &["N"]),
+    ("command_line", &[":"]),
+];
+
+/// Actions by config name with the keys a preset gives them
+type PresetKeys = &'static [(&'static str, &'static [&'static str])];
+
+/// Names accepted for `keybindings.preset`, with the keys each changes from the defaults
+const PRESETS: &[(&str, PresetKeys)] = &[("default", &[]), ("vim", VIM_PRESET)];
+
+/// Keys Browse mode keeps for itself: 1-3 for the pinned filters and Alt+1-9 for the tabs
+const RESERVED_KEYS: &[(&str, &str)] = &[
+    ("1", "pinned filter 1"),
+    ("2", "pinned filter 2"),
+    ("3", "pinned filter 3"),
+    ("Alt+1", "tab 1"),
+    ("Alt+2", "tab 2"),
+    ("Alt+3", "tab 3"),
+    ("Alt+4", "tab 4"),
+    ("Alt+5", "tab 5"),
+    ("Alt+6", "tab 6"),
+    ("Alt+7", "tab 7"),
+    ("Alt+8", "tab 8"),
+    ("Alt+9", "tab 9"),
+];
+
+/// A key and the Ctrl and Alt modifiers held with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// Parse a key as written in the config: "g", "F2", "Space", "PgDn", "Ctrl+Left", "Alt+x"
+    pub fn parse(text: &str) -> Result<Key, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text.trim();
+        loop {
+            let lower = rest.to_lowercase();
+            if lower.starts_with("ctrl+") && rest.len() > 5 {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = &rest[5..];
+            } else if lower.starts_with("alt+") && rest.len() > 4 {
+                modifiers |= KeyModifiers::ALT;
+                rest = &rest[4..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => match c {
+                '↑' => KeyCode::Up,
+                '↓' => KeyCode::Down,
+                '←' => KeyCode::Left,
+                '→' => KeyCode::Right,
+                c => KeyCode::Char(c),
+            },
+            _ => match rest.to_lowercase().as_str() {
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdn" | "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "enter" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "space" => KeyCode::Char(' '),
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "del" | "delete" => KeyCode::Delete,
+                "ins" | "insert" => KeyCode::Insert,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("Unknown key '{}'", text.trim())),
+                },
+            },
+        };
+        Ok(Key { code, modifiers })
+    }
+
+    /// A key as pressed; Shift is left out, since it is part of the character typed
+    pub fn pressed(code: KeyCode, modifiers: KeyModifiers) -> Key {
+        Key {
+            code,
+            modifiers: modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+
+    /// "Ctrl+", "Alt+" or nothing, as the help screen writes the modifiers
+    pub fn modifier_label(&self) -> String {
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        label
+    }
+
+    /// The key without its modifiers, as the help screen writes it
+    pub fn code_label(&self) -> String {
+        match self.code {
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Insert => "Ins".to_string(),
+            other => format!("{:?}", other),
+        }
+    }
+
+    /// The key as the help screen writes it, e.g. "Ctrl+←"
+    pub fn label(&self) -> String {
+        format!("{}{}", self.modifier_label(), self.code_label())
+    }
+}
+
+/// Keys pressed one after the other to run an action, usually just one
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeySequence(pub Vec<Key>);
+
+impl KeySequence {
+    /// Parse keys separated by spaces, e.g. "g g" or "Ctrl+d"
+    pub fn parse(text: &str) -> Result<KeySequence, String> {
+        let keys = text.split_whitespace().map(Key::parse).collect::<Result<Vec<Key>, String>>()?;
+        if keys.is_empty() {
+            return Err("Empty key".to_string());
+        }
+        Ok(KeySequence(keys))
+    }
+
+    /// The single key of a one-key sequence
+    pub fn single(&self) -> Option<&Key> {
+        match self.0.as_slice() {
+            [key] => Some(key),
+            _ => None,
+        }
+    }
+
+    /// Whether `keys` starts this sequence, or is all of it
+    fn starts_with(&self, keys: &[Key]) -> bool {
+        self.0.len() >= keys.len() && self.0.iter().zip(keys).all(|(key, pressed)| key == pressed)
+    }
+
+    /// The sequence as the help screen writes it, e.g. "gg" or "Ctrl+d"
+    pub fn label(&self) -> String {
+        let labels: Vec<String> = self.0.iter().map(Key::label).collect();
+        if labels.iter().all(|label| label.chars().count() == 1) {
+            labels.concat()
+        } else {
+            labels.join(" ")
+        }
+    }
+}
+
+/// What the keys pressed so far in Browse mode are bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup {
+    Action(Action),
+    /// The keys start a longer sequence, so wait for the next key
+    Pending,
+    Unbound,
+}
+
+/// Keys of the Browse actions and the menu hotkeys
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    browse: Vec<(&'static str, Action, Vec<KeySequence>)>,
+    /// Menu hotkeys the user changed, by the action's usage key; None removes the hotkey
+    menu: HashMap<&'static str, Option<KeyCode>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap {
+            browse: BROWSE_DEFAULTS
+                .iter()
+                .map(|&(name, action, keys)| (name, action, preset_keys(keys)))
+                .collect(),
+            menu: HashMap::new(),
+        }
+    }
+}
+
+fn preset_keys(keys: &[&str]) -> Vec<KeySequence> {
+    keys.iter().filter_map(|key| KeySequence::parse(key).ok()).collect()
+}
+
+impl Keymap {
+    /// The keys of a preset by name, None for an unknown preset
+    pub fn preset(name: &str) -> Option<Keymap> {
+        let (_, changes) = PRESETS.iter().find(|(preset, _)| preset.eq_ignore_ascii_case(name.trim()))?;
+        let mut keymap = Keymap::default();
+        for (name, keys) in changes.iter() {
+            if let Some(binding) = keymap.browse.iter_mut().find(|(action, _, _)| action == name) {
+                binding.2 = preset_keys(keys);
+            }
+        }
+        Some(keymap)
+    }
+
+    /// Build the keymap from the `keybindings` section of the config
+    ///
+    /// Also returns a message for each rebinding that was left out: an unknown preset, action or
+    /// key, or a key already used by another action. Actions left out keep the preset's keys.
+    pub fn from_config(config: &KeybindingsConfig) -> (Keymap, Vec<String>) {
+        let mut problems = Vec::new();
+        let preset = config.preset.as_deref().unwrap_or("default");
+        let mut keymap = Keymap::preset(preset).unwrap_or_else(|| {
+            problems.push(format!("Unknown keybindings preset '{}', using the default keys", preset));
+            Keymap::default()
+        });
+        let preset = keymap.clone();
+        let mut rebound_browse = Vec::new();
+        let mut rebound_menu = Vec::new();
+
+        for (name, keys) in &config.browse {
+            let Some(index) = keymap.browse.iter().position(|(action, _, _)| action == name) else {
+                problems.push(format!("Unknown browse action '{}' in keybindings", name));
+                continue;
+            };
+            match keys.keys().iter().map(|key| KeySequence::parse(key)).collect::<Result<Vec<KeySequence>, String>>() {
+                Ok(parsed) => {
+                    keymap.browse[index].2 = parsed;
+                    rebound_browse.push(index);
+                }
+                Err(message) => problems.push(format!("{} for {} in keybindings", message, name)),
+            }
+        }
+
+        let menu_items = menu::all_menu_items();
+        for (name, key) in &config.menu {
+            let matching: Vec<&'static str> = menu_items
+                .iter()
+                .map(|item| item.action.usage_key())
+                .filter(|usage_key| usage_key == name)
+                .collect();
+            let usage_key = match matching.as_slice() {
+                [usage_key] => *usage_key,
+                [] => {
+                    problems.push(format!("Unknown menu action '{}' in keybindings", name));
+                    continue;
+                }
+                _ => {
+                    problems.push(format!("'{}' names several menu actions and can't have a hotkey", name));
+                    continue;
+                }
+            };
+            let hotkey = match key.as_deref().map(Key::parse) {
+                None => None,
+                Some(Ok(Key { code: code @ (KeyCode::Char(_) | KeyCode::F(_)), modifiers })) if modifiers.is_empty() => {
+                    Some(code)
+                }
+                Some(Ok(_)) => {
+                    problems.push(format!("The hotkey for {} must be a letter or function key", name));
+                    continue;
+                }
+                Some(Err(message)) => {
+                    problems.push(format!("{} for {} in keybindings", message, name));
+                    continue;
+                }
+            };
+            keymap.menu.insert(usage_key, hotkey);
+            rebound_menu.push(usage_key);
+        }
+
+        // Undo rebindings that clash until none do; a preset's keys never clash with each other
+        loop {
+            let owners = keymap.key_owners();
+            let clash = |name: &str, keys: &KeySequence, is_menu: bool| {
+                owners
+                    .iter()
+                    .find(|owner| owner.name != name && owner.clashes_with(keys, is_menu))
+                    .map(|owner| format!("{} is bound to both {} and {}", keys.label(), name, owner.name))
+            };
+            let mut undone = false;
+            for index in rebound_browse.clone() {
+                let name = keymap.browse[index].0;
+                if let Some(message) = keymap.browse[index].2.iter().find_map(|keys| clash(name, keys, false)) {
+                    problems.push(format!("{}; {} keeps its default keys", message, name));
+                    keymap.browse[index].2 = preset.browse[index].2.clone();
+                    rebound_browse.retain(|rebound| *rebound != index);
+                    undone = true;
+                }
+            }
+            for usage_key in rebound_menu.clone() {
+                let Some(Some(code)) = keymap.menu.get(usage_key) else {
+                    continue;
+                };
+                let hotkey = KeySequence(vec![Key { code: *code, modifiers: KeyModifiers::NONE }]);
+                if let Some(message) = clash(usage_key, &hotkey, true) {
+                    problems.push(format!("{}; {} keeps its default hotkey", message, usage_key));
+                    keymap.menu.remove(usage_key);
+                    rebound_menu.retain(|rebound| *rebound != usage_key);
+                    undone = true;
+                }
+            }
+            if !undone {
+                break;
+            }
+        }
+
+        (keymap, problems)
+    }
+
+    /// Every bound key with the name of what it is bound to
+    fn key_owners(&self) -> Vec<KeyOwner> {
+        let mut owners: Vec<KeyOwner> = RESERVED_KEYS
+            .iter()
+            .filter_map(|&(keys, name)| {
+                KeySequence::parse(keys).ok().map(|keys| KeyOwner { name: name.to_string(), keys, is_menu: false })
+            })
+            .collect();
+        for (name, _, sequences) in &self.browse {
+            owners.extend(sequences.iter().map(|keys| KeyOwner { name: name.to_string(), keys: keys.clone(), is_menu: false }));
+        }
+        for item in menu::all_menu_items() {
+            if let Some(code) = self.menu_hotkey(&item.action, item.hotkey) {
+                let keys = KeySequence(vec![Key { code, modifiers: KeyModifiers::NONE }]);
+                owners.push(KeyOwner { name: item.action.usage_key().to_string(), keys, is_menu: true });
+            }
+        }
+        owners
+    }
+
+    /// What the keys pressed so far in Browse mode are bound to
+    pub fn lookup(&self, keys: &[Key]) -> Lookup {
+        let mut lookup = Lookup::Unbound;
+        for (_, action, sequences) in &self.browse {
+            for sequence in sequences.iter().filter(|sequence| sequence.starts_with(keys)) {
+                if sequence.0.len() == keys.len() {
+                    return Lookup::Action(*action);
+                }
+                lookup = Lookup::Pending;
+            }
+        }
+        lookup
+    }
+
+    /// The Browse action bound to a single key press
+    pub fn browse_action(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<Action> {
+        match self.lookup(&[Key::pressed(code, modifiers)]) {
+            Lookup::Action(action) => Some(action),
+            Lookup::Pending | Lookup::Unbound => None,
+        }
+    }
+
+    /// The Browse action with a config name, such as "toggle_grid"
+    pub fn action_named(&self, name: &str) -> Option<Action> {
+        self.browse.iter().find(|(action, _, _)| *action == name).map(|(_, action, _)| *action)
+    }
+
+    /// Keys bound to a Browse action by its config name
+    pub fn keys_for(&self, name: &str) -> &[KeySequence] {
+        self.browse
+            .iter()
+            .find(|(action, _, _)| *action == name)
+            .map(|(_, _, keys)| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// The hotkey of a menu action, `default` unless the user rebound it
+    pub fn menu_hotkey(&self, action: &menu::MenuAction, default: Option<KeyCode>) -> Option<KeyCode> {
+        self.menu.get(action.usage_key()).copied().unwrap_or(default)
+    }
+
+    /// Name of what a new menu hotkey on `code` would clash with, None when the key is free
+    pub fn hotkey_owner(&self, code: KeyCode) -> Option<String> {
+        let hotkey = KeySequence(vec![Key { code, modifiers: KeyModifiers::NONE }]);
+        self.key_owners().into_iter().find(|owner| owner.clashes_with(&hotkey, true)).map(|owner| owner.name)
+    }
+}
+
+struct KeyOwner {
+    name: String,
+    keys: KeySequence,
+    is_menu: bool,
+}
+
+impl KeyOwner {
+    /// Sequences clash when one starts the other, since the longer one could never be finished
+    /// Menu hotkeys run before the Browse keys whatever modifiers are held, so they clash on the key alone
+    fn clashes_with(&self, keys: &KeySequence, is_menu: bool) -> bool {
+        if self.is_menu || is_menu {
+            self.keys.0.first().map(|key| key.code) == keys.0.first().map(|key| key.code)
+        } else {
+            self.keys.starts_with(&keys.0) || keys.starts_with(&self.keys.0)
+        }
+    }
+}
+
+/// Names accepted for `keybindings.preset`
+pub fn preset_names() -> Vec<&'static str> {
+    PRESETS.iter().map(|(name, _)| *name).collect()
+}
+
+/// Config names of the Browse actions, in the order the help screen lists them
+pub fn browse_action_names() -> Vec<&'static str> {
+    BROWSE_DEFAULTS.iter().map(|(name, _, _)| *name).collect()
+}
+
+static KEYMAP: RwLock<Option<Keymap>> = RwLock::new(None);
+
+/// Install the keymap used by key handling, the menu and the help screen
+pub fn set_keymap(keymap: Keymap) {
+    *KEYMAP.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(keymap);
+}
+
+/// The keymap installed by `set_keymap`, or the default keys before one is
+pub fn keymap() -> Keymap {
+    KEYMAP
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
@@ -1,23 +1,69 @@
 // Library interface for integration testing
 // This module exposes the internal modules for testing purposes
 
+pub mod action;
+pub mod app_state;
+pub mod archive;
+pub mod artwork;
 pub mod buffer;
+pub mod cli;
+pub mod clipboard;
 pub mod components;
 pub mod config;
+pub mod config_check;
+pub mod custom_commands;
 pub mod database;
+pub mod demo;
+pub mod db_repair;
+pub mod digest;
 pub mod display;
 pub mod dto;
+pub mod duplicates;
 pub mod episode_field;
+pub mod event_loop;
+pub mod export;
+pub mod external_editor;
+pub mod extras;
+pub mod file_removal;
+pub mod filter_history;
+pub mod folder_series;
 pub mod handlers;
+pub mod hooks;
+pub mod input;
+pub mod keybindings;
+pub mod keymap;
+pub mod library_report;
+pub mod location;
 pub mod logger;
+pub mod macros;
 pub mod menu;
+pub mod modal;
+pub mod notifications;
+pub mod now_playing;
+pub mod path_completion;
 pub mod path_resolver;
 pub mod paths;
 pub mod player_plugin;
 pub mod progress_tracker;
+pub mod query;
+pub mod relocate;
+pub mod remote;
+pub mod rename;
+pub mod scan;
+pub mod session;
+pub mod shutdown;
 pub mod splash;
+pub mod sync;
+pub mod tabs;
 pub mod terminal;
+pub mod text;
+pub mod text_input;
 pub mod theme;
 pub mod torrent_search;
+pub mod tutorial;
+pub mod usage;
 pub mod util;
+pub mod version;
 pub mod video_metadata;
+pub mod watch_import;
+pub mod web_video;
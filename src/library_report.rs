@@ -0,0 +1,290 @@
+//! A printable overview of the collection, written as Markdown or HTML
+//!
+//! The report lists every series with its seasons, how many of their episodes are watched and
+//! how long they run, followed by the videos outside a series and the totals for the whole
+//! library. Unlike the JSON and CSV exports it is meant to be read, not imported again.
+
+use crate::export::LibraryRecord;
+use std::collections::BTreeMap;
+
+/// Supported formats for the library report
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    /// Default file name used when writing the report into the library root
+    pub fn default_file_name(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "library_report.md",
+            ReportFormat::Html => "library_report.html",
+        }
+    }
+
+    /// Short label used in menus and status messages
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "Markdown",
+            ReportFormat::Html => "HTML",
+        }
+    }
+}
+
+/// Watched count and running time of a group of videos
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    pub videos: usize,
+    pub watched: usize,
+    /// Sum of the known lengths, in seconds
+    pub seconds: i64,
+}
+
+impl Progress {
+    fn add(&mut self, record: &LibraryRecord) {
+        self.videos += 1;
+        if record.watched {
+            self.watched += 1;
+        }
+        self.seconds += record.length.unwrap_or(0).max(0);
+    }
+
+    /// Watched share in whole percent, 0 for an empty group
+    pub fn percent(&self) -> usize {
+        (self.watched * 100).checked_div(self.videos).unwrap_or(0)
+    }
+}
+
+/// A season of a series; `number` is None for the episodes without a season
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeasonSummary {
+    pub number: Option<usize>,
+    pub progress: Progress,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesSummary {
+    pub name: String,
+    pub seasons: Vec<SeasonSummary>,
+    pub progress: Progress,
+}
+
+/// The collection grouped for the report
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LibraryReport {
+    /// Series sorted by name, their seasons by number with the unnumbered ones last
+    pub series: Vec<SeriesSummary>,
+    /// Videos outside a series, sorted by title
+    pub standalone: Vec<LibraryRecord>,
+    pub total: Progress,
+}
+
+/// Group the exported `records` by series and season
+pub fn build_report(records: &[LibraryRecord]) -> LibraryReport {
+    let mut series: BTreeMap<String, BTreeMap<(bool, usize), Progress>> = BTreeMap::new();
+    let mut standalone = Vec::new();
+    let mut total = Progress::default();
+
+    for record in records {
+        total.add(record);
+        match record.series {
+            Some(ref name) => {
+                // (true, 0) sorts the episodes without a season after the numbered ones
+                let key = match record.season {
+                    Some(number) => (false, number),
+                    None => (true, 0),
+                };
+                series.entry(name.clone()).or_default().entry(key).or_default().add(record);
+            }
+            None => standalone.push(record.clone()),
+        }
+    }
+    standalone.sort_by_key(|record: &LibraryRecord| record.title.to_lowercase());
+
+    let series = series
+        .into_iter()
+        .map(|(name, seasons)| {
+            let mut progress = Progress::default();
+            let seasons = seasons
+                .into_iter()
+                .map(|((unnumbered, number), season)| {
+                    progress.videos += season.videos;
+                    progress.watched += season.watched;
+                    progress.seconds += season.seconds;
+                    SeasonSummary { number: (!unnumbered).then_some(number), progress: season }
+                })
+                .collect();
+            SeriesSummary { name, seasons, progress }
+        })
+        .collect();
+
+    LibraryReport { series, standalone, total }
+}
+
+/// Write the report for `records` in `format`; `generated` is the date shown under the heading
+pub fn render(records: &[LibraryRecord], format: ReportFormat, generated: &str) -> String {
+    let report = build_report(records);
+    match format {
+        ReportFormat::Markdown => render_markdown(&report, generated),
+        ReportFormat::Html => render_html(&report, generated),
+    }
+}
+
+/// Running time as it is shown in the report, e.g. "3 h 05 min" or "48 min"
+pub fn format_runtime(seconds: i64) -> String {
+    let minutes = seconds.max(0) / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{} min", minutes),
+        (hours, minutes) => format!("{} h {:02} min", hours, minutes),
+    }
+}
+
+fn season_label(number: Option<usize>) -> String {
+    match number {
+        Some(number) => format!("Season {}", number),
+        None => "No season".to_string(),
+    }
+}
+
+fn watched_label(progress: &Progress) -> String {
+    format!("{} of {} ({}%)", progress.watched, progress.videos, progress.percent())
+}
+
+fn summary_line(total: &Progress) -> String {
+    format!(
+        "{} videos, {} watched ({}%), {} in total",
+        total.videos,
+        total.watched,
+        total.percent(),
+        format_runtime(total.seconds)
+    )
+}
+
+fn year_label(record: &LibraryRecord) -> String {
+    record.year.map(|year| year.to_string()).unwrap_or_default()
+}
+
+fn render_markdown(report: &LibraryReport, generated: &str) -> String {
+    // Pipes would end a table cell early
+    let cell = |text: &str| text.replace('|', "\\|");
+    let mut out = String::new();
+    out.push_str("# Video Library\n\n");
+    out.push_str(&format!("Generated {}\n\n", generated));
+    out.push_str(&format!("{}\n", summary_line(&report.total)));
+
+    if !report.series.is_empty() {
+        out.push_str("\n## Series\n\n");
+        out.push_str("| Series | Seasons | Watched | Runtime |\n|---|---:|---:|---:|\n");
+        for series in &report.series {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                cell(&series.name),
+                series.seasons.len(),
+                watched_label(&series.progress),
+                format_runtime(series.progress.seconds)
+            ));
+        }
+        for series in &report.series {
+            out.push_str(&format!("\n### {}\n\n", cell(&series.name)));
+            out.push_str("| Season | Watched | Runtime |\n|---|---:|---:|\n");
+            for season in &series.seasons {
+                out.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    season_label(season.number),
+                    watched_label(&season.progress),
+                    format_runtime(season.progress.seconds)
+                ));
+            }
+        }
+    }
+
+    if !report.standalone.is_empty() {
+        out.push_str("\n## Other Videos\n\n");
+        out.push_str("| Title | Year | Watched | Runtime |\n|---|---:|:---:|---:|\n");
+        for record in &report.standalone {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                cell(&record.title),
+                year_label(record),
+                if record.watched { "yes" } else { "" },
+                record.length.map(format_runtime).unwrap_or_default()
+            ));
+        }
+    }
+    out
+}
+
+const HTML_STYLE: &str = "body { font-family: sans-serif; max-width: 60em; margin: 2em auto; color: #222; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1.5em; }
+th, td { border-bottom: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+td.number { text-align: right; }
+progress { width: 6em; vertical-align: middle; }
+@media print { body { margin: 0; } h3 { page-break-after: avoid; } }";
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn progress_cells(progress: &Progress) -> String {
+    format!(
+        "<td><progress value=\"{}\" max=\"{}\"></progress> {}</td><td class=\"number\">{}</td>",
+        progress.watched,
+        progress.videos.max(1),
+        watched_label(progress),
+        format_runtime(progress.seconds)
+    )
+}
+
+fn render_html(report: &LibraryReport, generated: &str) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Video Library</title>\n");
+    out.push_str(&format!("<style>\n{}\n</style>\n</head>\n<body>\n", HTML_STYLE));
+    out.push_str("<h1>Video Library</h1>\n");
+    out.push_str(&format!("<p>Generated {}</p>\n", escape_html(generated)));
+    out.push_str(&format!("<p>{}</p>\n", summary_line(&report.total)));
+
+    if !report.series.is_empty() {
+        out.push_str("<h2>Series</h2>\n<table>\n<tr><th>Series</th><th>Seasons</th><th>Watched</th><th>Runtime</th></tr>\n");
+        for series in &report.series {
+            out.push_str(&format!(
+                "<tr><td>{}</td><td class=\"number\">{}</td>{}</tr>\n",
+                escape_html(&series.name),
+                series.seasons.len(),
+                progress_cells(&series.progress)
+            ));
+        }
+        out.push_str("</table>\n");
+        for series in &report.series {
+            out.push_str(&format!("<h3>{}</h3>\n<table>\n", escape_html(&series.name)));
+            out.push_str("<tr><th>Season</th><th>Watched</th><th>Runtime</th></tr>\n");
+            for season in &series.seasons {
+                out.push_str(&format!(
+                    "<tr><td>{}</td>{}</tr>\n",
+                    season_label(season.number),
+                    progress_cells(&season.progress)
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+    }
+
+    if !report.standalone.is_empty() {
+        out.push_str("<h2>Other Videos</h2>\n<table>\n<tr><th>Title</th><th>Year</th><th>Watched</th><th>Runtime</th></tr>\n");
+        for record in &report.standalone {
+            out.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"number\">{}</td></tr>\n",
+                escape_html(&record.title),
+                year_label(record),
+                if record.watched { "&#10003;" } else { "" },
+                record.length.map(format_runtime).unwrap_or_default()
+            ));
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
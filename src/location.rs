@@ -0,0 +1,110 @@
+//! Episode locations as text, without losing file names that aren't valid UTF-8
+//!
+//! Locations are stored in the database as text. A path that is valid UTF-8 is stored as it
+//! is, so existing libraries read the same. Each byte of a path that isn't valid UTF-8 is
+//! stored as a character of the private use area, U+EF80 to U+EFFF for the bytes 0x80 to 0xFF,
+//! and turned back into that byte when the location is used as a path again. Such names are
+//! shown with replacement characters, while playing and renaming use the real file.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// First character standing for a byte that isn't valid UTF-8, for the byte 0x80
+const ESCAPE_BASE: u32 = 0xEF80;
+
+/// Location of `path` as text that `decode` turns back into the same path
+pub fn encode(path: &Path) -> String {
+    match path.to_str() {
+        Some(text) if !text.chars().any(is_escape) => text.to_string(),
+        _ => encode_bytes(&path_bytes(path)),
+    }
+}
+
+/// Path of a location written by `encode`
+pub fn decode(location: &str) -> PathBuf {
+    if !location.chars().any(is_escape) {
+        return PathBuf::from(location);
+    }
+    let mut bytes = Vec::with_capacity(location.len());
+    for c in location.chars() {
+        if is_escape(c) {
+            bytes.push((c as u32 - ESCAPE_BASE + 0x80) as u8);
+        } else {
+            let mut buffer = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+        }
+    }
+    path_from_bytes(bytes)
+}
+
+/// A location as it is shown, with replacement characters for the bytes that aren't valid UTF-8
+pub fn display(location: &str) -> String {
+    location.chars().map(|c| if is_escape(c) { char::REPLACEMENT_CHARACTER } else { c }).collect()
+}
+
+/// Bytes of `path` as the system knows them
+pub fn path_bytes(path: &Path) -> Vec<u8> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes().to_vec()
+    }
+    #[cfg(not(unix))]
+    {
+        path.to_string_lossy().as_bytes().to_vec()
+    }
+}
+
+fn is_escape(c: char) -> bool {
+    (ESCAPE_BASE..ESCAPE_BASE + 0x80).contains(&(c as u32))
+}
+
+fn push_escaped(text: &mut String, bytes: &[u8]) {
+    for &byte in bytes {
+        text.push(char::from_u32(ESCAPE_BASE + u32::from(byte) - 0x80).unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+}
+
+/// Valid text as it is, except for escape characters, which are escaped byte by byte
+fn push_valid(text: &mut String, valid: &str) {
+    for c in valid.chars() {
+        if is_escape(c) {
+            let mut buffer = [0; 4];
+            push_escaped(text, c.encode_utf8(&mut buffer).as_bytes());
+        } else {
+            text.push(c);
+        }
+    }
+}
+
+fn encode_bytes(mut bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len());
+    loop {
+        match std::str::from_utf8(bytes) {
+            Ok(valid) => {
+                push_valid(&mut text, valid);
+                return text;
+            }
+            Err(e) => {
+                let (valid, rest) = bytes.split_at(e.valid_up_to());
+                push_valid(&mut text, std::str::from_utf8(valid).unwrap_or_default());
+                // Bytes of an invalid sequence are never ASCII
+                let invalid = e.error_len().unwrap_or(rest.len());
+                push_escaped(&mut text, &rest[..invalid]);
+                bytes = &rest[invalid..];
+            }
+        }
+    }
+}
+
+fn path_from_bytes(bytes: Vec<u8>) -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        PathBuf::from(OsString::from_vec(bytes))
+    }
+    #[cfg(not(unix))]
+    {
+        PathBuf::from(OsString::from(String::from_utf8_lossy(&bytes).into_owned()))
+    }
+}
@@ -1,6 +1,6 @@
 use std::fs::{File, OpenOptions};
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use chrono::Local;
 use lazy_static::lazy_static;
@@ -14,19 +14,59 @@ pub enum LogLevel {
     Debug = 3,
 }
 
+/// When the log is rotated and how many earlier logs are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogRotation {
+    /// Size in bytes past which the log is rotated while the app runs, 0 for no limit
+    pub max_size: u64,
+    /// Earlier logs kept as movies.log.1 (the newest), movies.log.2 and so on; at least one is kept
+    pub keep_files: usize,
+}
+
+impl Default for LogRotation {
+    fn default() -> Self {
+        LogRotation {
+            max_size: 10 * 1024 * 1024,
+            keep_files: 3,
+        }
+    }
+}
+
+/// The log file's path, with its rotation and the bytes written to it so far
+struct LogTarget {
+    path: PathBuf,
+    rotation: LogRotation,
+    size: u64,
+}
+
 lazy_static! {
     pub static ref LOG_FILE: Mutex<Option<File>> = Mutex::new(None);
     pub static ref LOG_LEVEL: Mutex<LogLevel> = Mutex::new(LogLevel::Info);
+    static ref LOG_ROTATION: Mutex<LogRotation> = Mutex::new(LogRotation::default());
+    static ref LOG_TARGET: Mutex<Option<LogTarget>> = Mutex::new(None);
+}
+
+/// Set how logs are rotated from the next `initialize_logger` on
+pub fn set_log_rotation(rotation: LogRotation) {
+    *LOG_ROTATION.lock().unwrap() = rotation;
 }
 
 /// Initialize the logger with a log file path and log level
+///
+/// The log of the last session is kept as `<log_file>.1` rather than overwritten, so a crash
+/// at startup can still be looked into after the next start.
 pub fn initialize_logger(log_file: PathBuf, log_level: LogLevel) -> io::Result<()> {
+    let rotation = *LOG_ROTATION.lock().unwrap();
+
     // Create parent directory if it doesn't exist
     if let Some(parent) = log_file.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
-    // Create or truncate the log file
+    // Move the last session's log aside, then start a new one
+    if std::fs::metadata(&log_file).is_ok_and(|metadata| metadata.len() > 0) {
+        rotate_log_files(&log_file, rotation.keep_files)?;
+    }
     let file = OpenOptions::new()
         .create(true)
         .write(true)
@@ -34,12 +74,41 @@ pub fn initialize_logger(log_file: PathBuf, log_level: LogLevel) -> io::Result<(
         .open(&log_file)?;
 
     // Set the global log file and log level
-    *LOG_FILE.lock().unwrap() = Some(file);
+    let mut log_file_guard = LOG_FILE.lock().unwrap();
+    *log_file_guard = Some(file);
+    *LOG_TARGET.lock().unwrap() = Some(LogTarget {
+        path: log_file,
+        rotation,
+        size: 0,
+    });
     *LOG_LEVEL.lock().unwrap() = log_level;
 
     Ok(())
 }
 
+/// Path of the earlier log kept as number `index`, e.g. movies.log.2
+pub fn rotated_log_path(log_file: &Path, index: usize) -> PathBuf {
+    let mut name = log_file.as_os_str().to_os_string();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Shift the earlier logs up by one, dropping the oldest past `keep_files`, and move `log_file` to `.1`
+pub fn rotate_log_files(log_file: &Path, keep_files: usize) -> io::Result<()> {
+    let keep_files = keep_files.max(1);
+    let oldest = rotated_log_path(log_file, keep_files);
+    if oldest.exists() {
+        std::fs::remove_file(&oldest)?;
+    }
+    for index in (1..keep_files).rev() {
+        let from = rotated_log_path(log_file, index);
+        if from.exists() {
+            std::fs::rename(&from, rotated_log_path(log_file, index + 1))?;
+        }
+    }
+    std::fs::rename(log_file, rotated_log_path(log_file, 1))
+}
+
 /// Write a log entry with timestamp and level
 fn write_log(level: LogLevel, message: &str) {
     // Check if this message should be logged based on configured level
@@ -63,9 +132,32 @@ fn write_log(level: LogLevel, message: &str) {
     let log_entry = format!("[{}] [{}] {}\n", timestamp, level_str, message);
 
     // Write to log file
-    if let Some(ref mut file) = *LOG_FILE.lock().unwrap() {
+    let mut log_file = LOG_FILE.lock().unwrap();
+    if let Some(ref mut file) = *log_file {
         let _ = file.write_all(log_entry.as_bytes());
         let _ = file.flush();
+    } else {
+        return;
+    }
+
+    // Start a new file once this one is past the size cap
+    if let Some(ref mut target) = *LOG_TARGET.lock().unwrap() {
+        target.size += log_entry.len() as u64;
+        if target.rotation.max_size > 0 && target.size >= target.rotation.max_size {
+            *log_file = None;
+            let reopened = rotate_log_files(&target.path, target.rotation.keep_files)
+                .and_then(|()| OpenOptions::new().create(true).write(true).truncate(true).open(&target.path));
+            // Keep writing to the same file when it couldn't be moved aside
+            *log_file = reopened.or_else(|_| OpenOptions::new().append(true).open(&target.path)).ok();
+            target.size = 0;
+        }
+    }
+}
+
+/// Make sure everything logged so far is on disk
+pub fn flush_logger() {
+    if let Some(ref mut file) = *LOG_FILE.lock().unwrap() {
+        let _ = file.sync_all();
     }
 }
 
@@ -0,0 +1,72 @@
+use crate::keymap::Key;
+use std::collections::VecDeque;
+
+/// Keys recorded once and replayed on demand, for curation that repeats the same steps
+/// across many episodes, such as assigning a series, setting the season and moving down
+///
+/// A macro is the keys pressed between starting and stopping the recording, in every mode,
+/// so it can go through the menu, the series picker and the editor. Replaying feeds the keys
+/// back to the main loop, which handles them as if they were typed.
+#[derive(Debug, Clone, Default)]
+pub struct MacroRecorder {
+    /// Keys pressed since the recording started, None while not recording
+    recording: Option<Vec<Key>>,
+    /// The last macro recorded
+    recorded: Vec<Key>,
+    /// Keys of the replay still to be handled
+    queue: VecDeque<Key>,
+    /// True from the start of a replay until all its keys have been handled
+    replaying: bool,
+}
+
+impl MacroRecorder {
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    /// Keep the keys recorded as the macro and return how many there are
+    /// An empty recording leaves the previous macro in place
+    pub fn stop_recording(&mut self) -> usize {
+        let keys = self.recording.take().unwrap_or_default();
+        let count = keys.len();
+        if count > 0 {
+            self.recorded = keys;
+        }
+        count
+    }
+
+    pub fn has_macro(&self) -> bool {
+        !self.recorded.is_empty()
+    }
+
+    /// Add a key to the recording, if one is running
+    pub fn record(&mut self, key: Key) {
+        if let Some(keys) = self.recording.as_mut() {
+            keys.push(key);
+        }
+    }
+
+    /// Queue the keys of the macro to be handled again
+    /// False when nothing was recorded, or while recording or replaying, so a macro can't replay itself
+    pub fn replay(&mut self) -> bool {
+        if self.recorded.is_empty() || self.is_recording() || self.replaying {
+            return false;
+        }
+        self.queue.extend(self.recorded.iter().copied());
+        self.replaying = true;
+        true
+    }
+
+    /// The next key of the replay, None once it has run out
+    pub fn next_key(&mut self) -> Option<Key> {
+        let key = self.queue.pop_front();
+        if key.is_none() {
+            self.replaying = false;
+        }
+        key
+    }
+}
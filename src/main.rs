@@ -1,48 +1,151 @@
+mod action;
+mod app_state;
+mod archive;
+mod artwork;
 mod buffer;
+mod cli;
+mod clipboard;
 mod components;
 mod config;
+mod config_check;
+mod custom_commands;
 mod database;
+mod demo;
+mod db_repair;
+mod digest;
 mod display;
 mod dto;
+mod duplicates;
 mod episode_field;
+mod event_loop;
+mod export;
+mod external_editor;
+mod extras;
+mod file_removal;
+mod filter_history;
+mod folder_series;
 mod handlers;
+mod hooks;
+mod input;
+mod keybindings;
+mod keymap;
+mod library_report;
+mod location;
 mod logger;
+mod macros;
 mod menu;
+mod modal;
+mod notifications;
+mod now_playing;
+mod path_completion;
 mod path_resolver;
 mod paths;
 mod player_plugin;
 mod progress_tracker;
+mod query;
+mod relocate;
+mod remote;
+mod rename;
+mod scan;
+mod session;
+mod shutdown;
 mod splash;
+mod sync;
+mod tabs;
 mod terminal;
+mod text;
+mod text_input;
 mod theme;
 mod torrent_search;
+mod tutorial;
+mod usage;
 mod util;
+mod version;
 mod video_metadata;
+mod watch_import;
+mod web_video;
 
-use config::{read_config, save_config, Config};
+use config::{load_config, save_config, Config};
 use crossterm::event::{self, Event, KeyCode, KeyEvent};
-use database::get_entries;
-use display::draw_screen;
-use dto::EpisodeDetail;
-use episode_field::EpisodeField;
+use database::Database;
+use event_loop::Startup;
 use path_resolver::PathResolver;
 use buffer::BufferManager;
-use std::collections::HashSet;
 use std::io;
 use std::panic;
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
 use std::time::Duration;
+use input::TerminalEvents;
 use terminal::{initialize_terminal, restore_terminal, get_terminal_size};
-use theme::Theme;
-use util::{Entry, LastAction, Mode, ViewContext};
-use walkdir::WalkDir;
+use util::Entry;
+use scan::HardLinks;
+use extras::SampleFiles;
+
+/// Run `PRAGMA quick_check` before opening the database and walk the user through recovery if it fails
+/// Returns false if the user chose to quit
+fn check_database_integrity(db_path: &Path) -> io::Result<bool> {
+    let problems = db_repair::check_at_startup(db_path);
+    if problems.is_empty() {
+        return Ok(true);
+    }
+
+    logger::log_error(&format!(
+        "Database integrity check failed for {}: {}",
+        db_path.display(),
+        problems.join("; ")
+    ));
+    println!("The database at {} appears to be damaged:", db_path.display());
+    for problem in problems.iter().take(5) {
+        println!("  {}", problem);
+    }
+    println!();
+
+    let backup = db_repair::backup_path(db_path);
+    loop {
+        println!("  [1] Rebuild the database from its readable data");
+        if backup.is_file() {
+            println!("  [2] Restore the backup from {}", backup.display());
+        }
+        println!("  [q] Quit without changing anything");
+        print!("Choose an option: ");
+        io::Write::flush(&mut io::stdout())?;
+
+        let mut choice = String::new();
+        io::stdin().read_line(&mut choice)?;
+        let result = match choice.trim() {
+            "1" => {
+                println!("Rebuilding database...");
+                db_repair::dump_and_reload(db_path, &mut |message| println!("  {}", message)).map(|report| report.summary())
+            }
+            "2" if backup.is_file() => {
+                println!("Restoring backup...");
+                db_repair::restore_backup(db_path).map(|path| format!("Restored backup from {}", path.display()))
+            }
+            "q" | "Q" => return Ok(false),
+            _ => continue,
+        };
+
+        match result {
+            Ok(summary) => {
+                println!("{}", summary);
+                println!("The damaged file was kept next to the database with a .corrupt suffix.");
+                return Ok(true);
+            }
+            Err(e) => {
+                logger::log_error(&format!("Database recovery failed: {}", e));
+                println!("Recovery failed: {}", e);
+                println!();
+            }
+        }
+    }
+}
 
 /// Handle first-run setup flow for new users
 fn first_run_flow(
     config: &mut Config,
     config_path: &Path,
-) -> io::Result<(Vec<Entry>, PathResolver, String)> {
+) -> io::Result<(Vec<Entry>, Database, PathResolver, String)> {
     let mut entry_path = String::new();
     let mut redraw = true;
     
@@ -52,8 +155,13 @@ fn first_run_flow(
     
     loop {
         if redraw {
-            // Draw a simple prompt
-            print!("\rVideo collection directory: {}", entry_path);
+            // Draw a simple prompt, saying whether the path is a folder yet
+            let status = path_completion::check_path(&entry_path);
+            crossterm::execute!(io::stdout(), crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine))?;
+            let status_text = format!("  ({})", status.message());
+            print!("\rVideo collection directory: {}{}", entry_path, status_text);
+            // Leave the cursor at the end of the path, where typing goes
+            crossterm::execute!(io::stdout(), crossterm::cursor::MoveLeft(status_text.len() as u16))?;
             io::Write::flush(&mut io::stdout())?;
             redraw = false;
         }
@@ -95,33 +203,41 @@ fn first_run_flow(
                         
                         if db_exists {
                             println!("Connected to existing database at {}", db_path.display());
+                            if !check_database_integrity(&db_path)? {
+                                entry_path.clear();
+                                redraw = true;
+                                continue;
+                            }
                         } else {
                             println!("Creating new database...");
                         }
                         
-                        // Initialize database
-                        if let Err(e) = database::initialize_database(&db_path) {
-                            println!("\nError: Failed to initialize database: {}", e);
-                            
-                            // Check for common error types and provide specific guidance
-                            let error_str = e.to_string().to_lowercase();
-                            if error_str.contains("permission") || error_str.contains("access") {
-                                println!("Permission denied. Please ensure you have write permissions to this directory.");
-                            } else if error_str.contains("no space") || error_str.contains("disk full") {
-                                println!("Insufficient disk space. Please free up space and try again.");
+                        // Open the database
+                        let database = match Database::open(&db_path) {
+                            Ok(database) => database,
+                            Err(e) => {
+                                println!("\nError: Failed to initialize database: {}", e);
+                                
+                                // Check for common error types and provide specific guidance
+                                let error_str = e.to_string().to_lowercase();
+                                if error_str.contains("permission") || error_str.contains("access") {
+                                    println!("Permission denied. Please ensure you have write permissions to this directory.");
+                                } else if error_str.contains("no space") || error_str.contains("disk full") {
+                                    println!("Insufficient disk space. Please free up space and try again.");
+                                }
+                                
+                                entry_path.clear();
+                                redraw = true;
+                                continue;
                             }
-                            
-                            entry_path.clear();
-                            redraw = true;
-                            continue;
-                        }
+                        };
                         
                         // Update config with db_location
                         config.set_database_path(db_path.clone());
                         save_config(config, &config_path.to_path_buf());
                         
                         // Create PathResolver from database path
-                        let resolver = match PathResolver::from_database_path(&db_path) {
+                        let mut resolver = match PathResolver::from_database_path(&db_path) {
                             Ok(r) => r,
                             Err(e) => {
                                 let error_msg = e.to_string();
@@ -143,37 +259,42 @@ fn first_run_flow(
                                 return Err(io::Error::other(error_msg));
                             }
                         };
+                        database.add_library_roots(&mut resolver, &config.library_roots);
                         
                         // Perform initial scan
+                        let scan_rules = config.scan_rules_for(&path);
                         println!("Scanning directory for video files...");
-                        let video_files: Vec<_> = WalkDir::new(&path)
+                        let mut hard_links = HardLinks::default();
+                        let (video_files, excluded): (Vec<_>, Vec<_>) = scan_rules
+                            .walk(&path)
                             .into_iter()
                             .filter_map(|e| e.ok())
                             .filter(|e| e.file_type().is_file())
-                            .filter(|e| {
-                                e.path()
-                                    .extension()
-                                    .and_then(|ext| ext.to_str())
-                                    .is_some_and(|ext| {
-                                        config.video_extensions.contains(&ext.to_lowercase())
-                                    })
-                            })
+                            .filter(|e| scan_rules.accepts(e.path()))
+                            .filter(|e| !scan_rules.dedupe_hardlinks || hard_links.earlier_link(e.path()).is_none())
+                            .filter(|e| scan_rules.samples != SampleFiles::Skip || !extras::is_sample_name(e.path()))
                             .map(|e| e.into_path())
-                            .collect();
+                            .partition(|video_path| !scan_rules.excludes(&path, video_path));
                         
                         let mut imported_count = 0;
                         let mut skipped_count = 0;
                         
                         for video_path in &video_files {
-                            let location = video_path.to_string_lossy().to_string();
+                            let location = location::encode(video_path);
                             let name = video_path
                                 .file_name()
                                 .unwrap_or_default()
                                 .to_string_lossy()
                                 .to_string();
                             
-                            match database::import_episode_relative(&location, &name, &resolver) {
-                                Ok(true) => imported_count += 1,  // Only count if actually inserted
+                            match database.import_episode_relative(&location, &name, &resolver) {
+                                Ok(true) => {
+                                    // Only count if actually inserted
+                                    imported_count += 1;
+                                    if scan_rules.samples == SampleFiles::Flag && extras::is_sample_name(video_path) {
+                                        database.set_extra_location(video_path, true, &resolver).ok();
+                                    }
+                                }
                                 Ok(false) => {},  // Already exists, don't count
                                 Err(_) => skipped_count += 1,
                             }
@@ -191,9 +312,12 @@ fn first_run_flow(
                         if skipped_count > 0 {
                             println!("Note: {} files were skipped.", skipped_count);
                         }
+                        if !excluded.is_empty() {
+                            println!("Left out {} videos matching scan_exclude.", excluded.len());
+                        }
                         
                         // Load entries from database
-                        let entries = get_entries().expect("Failed to get entries");
+                        let entries = database.get_entries().map_err(|e| io::Error::other(e.to_string()))?;
                         
                         // Calculate appropriate status message based on whether DB existed and how many videos were imported
                         let status_message = if db_exists {
@@ -206,12 +330,20 @@ fn first_run_flow(
                             format!("Created new database and imported {} videos", imported_count)
                         };
                         
-                        return Ok((entries, resolver, status_message));
+                        return Ok((entries, database, resolver, status_message));
                     }
                     KeyCode::Esc => {
                         println!("\nSetup cancelled. Exiting...");
                         return Err(io::Error::new(io::ErrorKind::Interrupted, "Setup cancelled"));
                     }
+                    KeyCode::Tab => {
+                        let completion = path_completion::complete(&entry_path);
+                        if !completion.candidates.is_empty() {
+                            println!("\n{}", completion.candidates.join("  "));
+                        }
+                        entry_path = completion.path;
+                        redraw = true;
+                    }
                     KeyCode::Backspace => {
                         entry_path.pop();
                         redraw = true;
@@ -227,409 +359,27 @@ fn first_run_flow(
     }
 }
 
-fn main_loop(mut entries: Vec<Entry>, mut config: Config, theme: Theme, mut resolver: Option<PathResolver>, config_path: PathBuf, mut status_message: String) -> io::Result<()> {
-    let mut current_item = 0;
-    let mut redraw = true;
-    let mut search: String = String::new();
-    let mut filtered_entries: Vec<Entry> = entries.clone();
-    let mut playing_file: Option<String> = None;
-    let mut mode = Mode::Browse;
-    let mut first_entry = 0;
-    let mut edit_field = EpisodeField::Title;
-    let mut edit_cursor_pos: usize = 0;
-    let mut edit_details = EpisodeDetail {
-        title: String::new(),
-        year: String::new(),
-        watched: String::new(),
-        length: String::new(),
-        series: None,
-        season: None,
-        episode_number: String::new(),
-        last_watched_time: None,
-        last_progress_time: None,
+/// Show the splash screen unless `show_splash` is off, with the art of `splash_art` when it is set
+fn show_splash(config: &Config, config_dir: &Path, config_problems: &mut Vec<String>) -> io::Result<()> {
+    if !config.show_splash {
+        return Ok(());
+    }
+    let art = match config.splash_art {
+        Some(ref file) => splash::load_ascii_art(&config_dir.join(file)).unwrap_or_else(|problem| {
+            logger::log_warn(&problem);
+            config_problems.push(problem);
+            splash::get_ascii_art().to_string()
+        }),
+        None => splash::get_ascii_art().to_string(),
     };
-    let mut series = database::get_all_series().expect("Failed to get series");
-    let mut series_selection: Option<usize> = None;
-    let mut new_series = String::new();
-    let mut selected_entry_id: Option<usize> = None;
-    let mut season_number: Option<usize> = None;
-    let mut view_context = ViewContext::TopLevel;
-    let mut last_action: Option<LastAction> = None;
-    let mut original_edit_details: Option<EpisodeDetail> = None;
-    let mut dirty_fields: HashSet<EpisodeField> = HashSet::new();
-    let mut menu_selection: usize = 0;
-    let mut remembered_item: usize = 0;
-    let mut filter_mode: bool = false;
-    let mut first_series: usize = 0;
-
-    // Torrent search state variables
-    let mut search_query = String::new();
-    let mut torrent_results: Vec<crate::torrent_search::TorrentResult> = Vec::new();
-    let mut selected_torrent_result: usize = 0;
+    splash::show_splash_screen(&art).map_err(|e| io::Error::other(e.to_string()))
+}
 
-    // Initialize BufferManager with terminal dimensions
+/// Run the main loop with the terminal's events, drawing to a buffer of the terminal's size
+fn run_on_terminal(startup: Startup) -> io::Result<()> {
     let (terminal_width, terminal_height) = get_terminal_size()?;
     let mut buffer_manager = BufferManager::new(terminal_width, terminal_height);
-    
-    // Track previous mode for detecting mode changes
-    let mut previous_mode = mode.clone();
-
-    // Create a channel to communicate between the thread and the main loop
-    let (tx, rx): (Sender<()>, Receiver<()>) = mpsc::channel();
-
-    // Entry path for manual scans (not used for first-run, which is handled separately)
-    let mut entry_path = String::new();
-
-    loop {
-        if redraw {
-            // Check if mode has changed and trigger full redraw if needed
-            if mode != previous_mode {
-                buffer_manager.force_full_redraw();
-                // Only update previous_mode if we're not entering Menu mode
-                // (we want to remember what mode we were in before Menu)
-                if mode != Mode::Menu {
-                    previous_mode = mode.clone();
-                }
-            }
-            
-            // Split the search string into terms
-            let search_terms: Vec<String> = search
-                .to_lowercase()
-                .split_whitespace()
-                .map(String::from)
-                .collect();
-
-            // Filter entries based on the search terms (case-insensitive)
-            filtered_entries = entries
-                .iter()
-                .filter(|entry| {
-                    let name = match entry {
-                        Entry::Series { name, .. } => name,
-                        Entry::Episode { name, .. } => name,
-                        Entry::Season { number, .. } => &format!("Season {}", number),
-                    };
-                    let name_lowercase = name.to_lowercase();
-                    search_terms
-                        .iter()
-                        .all(|term| name_lowercase.contains(term))
-                })
-                .cloned()
-                .collect();
-
-            // Ensure current_item is within bounds
-            if current_item >= filtered_entries.len() {
-                current_item = if filtered_entries.is_empty() {
-                    0
-                } else {
-                    filtered_entries.len() - 1
-                };
-            }
-
-            //if we're in Browse mode, we need to populate edit_details before calling draw_screen
-            if let Mode::Browse = mode {
-                if !filtered_entries.is_empty() {
-                    if let Entry::Episode { episode_id, .. } = &filtered_entries[current_item] {
-                        selected_entry_id = Some(*episode_id);
-                        if let Some(id) = selected_entry_id {
-                            edit_details = database::get_episode_detail(id)
-                                .expect("Failed to get entry details");
-                        }
-                    } else {
-                        selected_entry_id = None;
-                    }
-                }
-            }
-
-            // Get menu items for Menu mode
-            let menu_items = if let Mode::Menu = mode {
-                let menu_context = menu::MenuContext {
-                    selected_entry: filtered_entries.get(remembered_item).cloned(),
-                    episode_detail: edit_details.clone(),
-                    mode: previous_mode.clone(),
-                    last_action: last_action.clone(),
-                };
-                menu::get_context_menu_items(&menu_context)
-            } else {
-                Vec::new()
-            };
-
-            // Call appropriate display function based on mode
-            match mode {
-                Mode::TorrentSearchInput => {
-                    display::draw_torrent_search_input(
-                        &mut buffer_manager,
-                        &search_query,
-                        &theme,
-                    )?;
-                }
-                Mode::TorrentSearchResults => {
-                    display::draw_torrent_search_results(
-                        &mut buffer_manager,
-                        &torrent_results,
-                        selected_torrent_result,
-                        &theme,
-                    )?;
-                }
-                _ => {
-                    draw_screen(
-                        &filtered_entries,
-                        current_item,
-                        &mut first_entry,
-                        &search,
-                        &theme,
-                        &mode,
-                        &entry_path,
-                        &edit_details,
-                        edit_field,
-                        edit_cursor_pos,
-                        &series,
-                        &mut series_selection,
-                        &new_series,
-                        season_number,
-                        &last_action,
-                        &dirty_fields,
-                        &menu_items,
-                        menu_selection,
-                        filter_mode,
-                        &mut first_series,
-                        &view_context,
-                        &status_message,
-                        resolver.as_ref().expect("PathResolver should be initialized"),
-                        &mut buffer_manager,
-                    )?;
-                }
-            }
-            redraw = false;
-        }
-
-        // Check for messages from the thread (video playback completed)
-        if rx.try_recv().is_ok() {
-            playing_file = None;
-            status_message = String::new();
-            redraw = true;
-        }
-
-        // Poll for events with a timeout
-        if event::poll(Duration::from_millis(100))? {
-            let event = event::read()?;
-            
-            // Handle terminal resize events
-            if let Event::Resize(width, height) = event {
-                buffer_manager.resize(width as usize, height as usize);
-                redraw = true;
-                continue;
-            }
-            
-            if let Event::Key(KeyEvent {
-                code, modifiers, ..
-            }) = event
-            {
-                match mode {
-                    Mode::Entry => {
-                        handlers::handle_entry_mode(
-                            code,
-                            &mut entry_path,
-                            &mut entries,
-                            &mut filtered_entries,
-                            &mut mode,
-                            &mut redraw,
-                            &mut config,
-                            &config_path,
-                            &mut resolver,
-                            &mut status_message,
-                        );
-                    }
-                    Mode::Edit => {
-                        handlers::handle_edit_mode(
-                            code,
-                            modifiers,
-                            current_item,
-                            &mut filtered_entries,
-                            &mut edit_details,
-                            &mut season_number,
-                            &mut entries,
-                            &mut mode,
-                            &mut edit_field,
-                            &mut edit_cursor_pos,
-                            &mut redraw,
-                            &view_context,
-                            &mut last_action,
-                            original_edit_details.as_ref().unwrap_or(&EpisodeDetail {
-                                title: String::new(),
-                                year: String::new(),
-                                watched: String::new(),
-                                length: String::new(),
-                                series: None,
-                                season: None,
-                                episode_number: String::new(),
-                                last_watched_time: None,
-                                last_progress_time: None,
-                            }),
-                            &mut dirty_fields,
-                        );
-                    }
-                    Mode::Browse => {
-                        // If resolver is None, we need to enter Entry mode for setup
-                        if resolver.is_none() {
-                            mode = Mode::Entry;
-                            redraw = true;
-                        } else if let Some(ref res) = resolver {
-                            if !handlers::handle_browse_mode(
-                                code,
-                                modifiers,
-                                &mut current_item,
-                                &mut first_entry,
-                                &mut filtered_entries,
-                                &mut entries,
-                                &mut search,
-                                &mut playing_file,
-                                &mut mode,
-                                &mut edit_details,
-                                &mut season_number,
-                                &mut redraw,
-                                &config,
-                                res,
-                                &tx,
-                                &mut view_context,
-                                &mut last_action,
-                                &mut edit_field,
-                                &mut edit_cursor_pos,
-                                &mut original_edit_details,
-                                &mut dirty_fields,
-                                &mut remembered_item,
-                                &mut menu_selection,
-                                &mut series,
-                                &mut series_selection,
-                                &mut filter_mode,
-                                &mut first_series,
-                                &mut status_message,
-                                &mut search_query,
-                            )? {
-                                break Ok(());
-                            }
-                        }
-                    }
-                    Mode::SeriesSelect => {
-                        if let Some(id) = selected_entry_id {
-                            handlers::handle_series_select_mode(
-                                code,
-                                &mut series_selection,
-                                &mut mode,
-                                &mut redraw,
-                                &mut series,
-                                id,
-                                &mut edit_details,
-                                &mut entries,
-                                &mut filtered_entries,
-                                &view_context,
-                                &mut last_action,
-                                &mut new_series,
-                                &mut edit_cursor_pos,
-                                &mut first_series,
-                            );
-                        } else {
-                            // selected entry is a series, change mode back to browse
-                            mode = Mode::Browse;
-                            redraw = true;
-                        }
-                    }
-                    Mode::SeriesCreate => {
-                        if let Some(id) = selected_entry_id {
-                            handlers::handle_series_create_mode(
-                                code,
-                                modifiers,
-                                &mut mode,
-                                &mut redraw,
-                                &mut new_series,
-                                &mut edit_cursor_pos,
-                                &mut series,
-                                id,
-                                &mut edit_details,
-                                &mut entries,
-                                &mut filtered_entries,
-                                &view_context,
-                                &mut last_action,
-                                &mut first_series,
-                            );
-                        } else {
-                            // selected entry is a series, change mode back to browse
-                            mode = Mode::Browse;
-                            redraw = true;
-                        }
-                    }
-                    Mode::Menu => {
-                        let menu_context = menu::MenuContext {
-                            selected_entry: filtered_entries.get(remembered_item).cloned(),
-                            episode_detail: edit_details.clone(),
-                            mode: previous_mode.clone(),
-                            last_action: last_action.clone(),
-                        };
-                        let menu_items = menu::get_context_menu_items(&menu_context);
-
-                        if let Some(ref res) = resolver {
-                            handlers::handle_menu_mode(
-                                code,
-                                &menu_items,
-                                &mut menu_selection,
-                                &mut mode,
-                                &mut redraw,
-                                remembered_item,
-                                &mut filtered_entries,
-                                &mut entries,
-                                &mut edit_details,
-                                &mut season_number,
-                                &view_context,
-                                &mut last_action,
-                                &mut edit_field,
-                                &mut edit_cursor_pos,
-                                &mut original_edit_details,
-                                &mut dirty_fields,
-                                &mut series,
-                                &mut series_selection,
-                                &mut first_series,
-                                &config,
-                                res,
-                                &mut status_message,
-                                &mut buffer_manager,
-                                &mut search_query,
-                            );
-                        } else {
-                            // If resolver is None, exit menu and enter Entry mode
-                            mode = Mode::Entry;
-                            redraw = true;
-                        }
-                    }
-                    Mode::TorrentSearchInput => {
-                        handlers::handle_torrent_search_input(
-                            code,
-                            &mut mode,
-                            &mut search_query,
-                            &mut torrent_results,
-                            &mut selected_torrent_result,
-                            &mut redraw,
-                        );
-                    }
-                    Mode::TorrentSearchResults => {
-                        handlers::handle_torrent_search_results(
-                            code,
-                            &mut mode,
-                            &torrent_results,
-                            &mut selected_torrent_result,
-                            &mut status_message,
-                            &mut redraw,
-                        );
-                    }
-                }
-
-                // Clear dirty state when exiting EDIT mode
-                if !matches!(mode, Mode::Edit)
-                    && original_edit_details.is_some() {
-                        original_edit_details = None;
-                        dirty_fields.clear();
-                    }
-            }
-        }
-    }
+    event_loop::run(startup, &mut TerminalEvents, &mut buffer_manager)
 }
 
 fn main() -> io::Result<()> {
@@ -638,6 +388,28 @@ fn main() -> io::Result<()> {
         eprintln!("Application crashed: {:?}", info);
     }));
 
+    // Flags and MOVIES_* variables win over config.yaml for this run only
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match cli::parse_args(&args, |name| std::env::var(name).ok()) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    let overrides = match command {
+        cli::Command::Help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        cli::Command::Run(ref overrides) => overrides.clone(),
+        cli::Command::Demo(_) => cli::Overrides::default(),
+    };
+
+    // Portable mode keeps everything in the executable's folder, for a copy living on the videos' drive
+    paths::set_portable_dir(paths::detect_portable_dir(overrides.portable));
+
     // Initialize application paths
     let app_paths = match paths::AppPaths::new() {
         Ok(paths) => paths,
@@ -649,65 +421,102 @@ fn main() -> io::Result<()> {
         }
     };
     
-    let mut config = read_config(&app_paths.config_file);
+    let config_path = match overrides.config {
+        Some(path) => {
+            if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            path
+        }
+        None => app_paths.config_file,
+    };
+
+    // Problems are logged once the logger is up, and shown when the app opens
+    let (mut config, mut config_problems) = load_config(&config_path);
+    config.db_override = overrides.db.as_ref().map(|path| {
+        std::path::absolute(path).unwrap_or_else(|_| path.clone()).to_string_lossy().to_string()
+    });
 
     // Initialize logger
     // Determine log file path (custom from config or default)
     let log_file_path = if let Some(ref custom_path) = config.log_file {
         PathBuf::from(custom_path)
     } else {
-        // Use default location: ~/.local/share/movies/movies.log
-        let proj_dirs = directories::ProjectDirs::from("", "", "movies")
+        // Use default location: ~/.local/share/movies/movies.log, or next to the executable when portable
+        let data_dir = paths::data_dir()
             .expect("Failed to determine application directories");
-        let data_dir = proj_dirs.data_dir();
-        std::fs::create_dir_all(data_dir)
+        std::fs::create_dir_all(&data_dir)
             .expect("Failed to create data directory");
         data_dir.join("movies.log")
     };
 
     // Parse log level from config
-    let log_level = config::parse_log_level(&config.log_level);
-    
-    // Check if the log level was invalid (will be Info if invalid)
-    let was_invalid = !["error", "warn", "info", "debug"].contains(&config.log_level.to_lowercase().as_str());
+    let log_level = config::parse_log_level(overrides.log_level.as_deref().unwrap_or(&config.log_level));
 
     // Initialize the logger
+    logger::set_log_rotation(config.log_rotation());
     if let Err(e) = logger::initialize_logger(log_file_path.clone(), log_level) {
         eprintln!("Error: Failed to initialize logger: {}", e);
         eprintln!("Continuing without logging...");
         // Can't log this error since logger failed to initialize
     } else {
-        // Log warning if invalid log level was provided
-        if was_invalid {
-            logger::log_warn(&format!(
-                "Invalid log level '{}' in configuration, defaulting to 'info'",
-                config.log_level
-            ));
+        // Log what was wrong in config.yaml, such as an invalid log level
+        for problem in &config_problems {
+            logger::log_warn(problem);
         }
         
         // Log application startup
         logger::log_info("Application started");
     }
 
+    // Local usage counters live next to the log in the data directory
+    if let Some(usage_path) = usage::default_usage_path() {
+        usage::initialize_usage(usage_path);
+    }
+
+    // Hidden --demo [DIR] flag: render screenshots of a sample library and exit
+    if let cli::Command::Demo(output_dir) = command {
+        let config_dir = config_path.parent()
+            .expect("Config file should have a parent directory");
+        let theme = theme::load_theme(&config_dir.join(&config.active_theme));
+        logger::log_info(&format!("Writing demo screenshots to {:?}", output_dir));
+        for screenshot in demo::run_demo(&output_dir, &theme)? {
+            println!("{}", screenshot.display());
+        }
+        return Ok(());
+    }
+
     // Check if this is a first run (no database location configured)
     if config.is_first_run() {
         // First run - handle setup before initializing terminal
-        let (entries, resolver, initial_status) = first_run_flow(&mut config, &app_paths.config_file)?;
+        let (entries, database, resolver, initial_status) = first_run_flow(&mut config, &config_path)?;
         
         // Load theme from config directory
-        let config_dir = app_paths.config_file.parent()
+        let config_dir = config_path.parent()
             .expect("Config file should have a parent directory");
         let theme_path = config_dir.join(&config.active_theme);
         logger::log_info(&format!("Loading theme from {:?}", theme_path));
         let theme = theme::load_theme(&theme_path);
+        config_problems.extend(theme::theme_problems(&theme, &theme_path));
         
         // Now start the main loop with the configured database
         initialize_terminal()?;
-        splash::show_splash_screen()
-            .map_err(|e| io::Error::other(e.to_string()))?;
+        show_splash(&config, config_dir, &mut config_problems)?;
         terminal::clear_screen()?;
-        let result = main_loop(entries, config, theme, Some(resolver), app_paths.config_file.clone(), initial_status);
+        let result = run_on_terminal(Startup {
+            entries,
+            config,
+            theme,
+            database: Some(Arc::new(database)),
+            resolver: Some(resolver),
+            config_path: config_path.clone(),
+            startup_message: initial_status,
+            show_tutorial: true,
+            handle_signals: true,
+            config_problems,
+        });
         restore_terminal()?;
+        shutdown::finish();
         return result;
     }
 
@@ -716,21 +525,29 @@ fn main() -> io::Result<()> {
         Some(path) => path,
         None => {
             eprintln!("Error: Database location not configured");
-            eprintln!("Please check your config file at: {}", app_paths.config_file.display());
+            eprintln!("Please check your config file at: {}", config_path.display());
             std::process::exit(1);
         }
     };
 
-    // Check if database file exists
-    if !db_path.exists() {
+    // Check if database file exists; one given with --db is created to start a new library
+    if config.db_override.is_some() && !db_path.exists() && db_path.parent().is_some_and(Path::exists) {
+        println!("Creating new database at {}", db_path.display());
+    } else if !db_path.exists() {
         eprintln!("Error: Database not found at {}", db_path.display());
         eprintln!("The database file may have been moved or deleted.");
         eprintln!("Please update your config file or delete it to run first-time setup again.");
         std::process::exit(1);
     }
 
-    // Initialize database
-    if let Err(e) = database::initialize_database(&db_path) {
+    // Check for corruption before handing the file to rusqlite; a new database has nothing to check
+    if !check_database_integrity(&db_path)? {
+        logger::log_info("User quit after failed database integrity check");
+        std::process::exit(1);
+    }
+
+    // Open the database
+    let database = Database::open(&db_path).unwrap_or_else(|e| {
         logger::log_error(&format!("Critical: Failed to initialize database at {}: {}", db_path.display(), e));
         eprintln!("Error: Failed to initialize database at {}", db_path.display());
         eprintln!("Details: {}", e);
@@ -750,10 +567,10 @@ fn main() -> io::Result<()> {
         }
         
         std::process::exit(1);
-    }
+    });
 
     // Initialize PathResolver from database location
-    let resolver = match PathResolver::from_database_path(&db_path) {
+    let mut resolver = match PathResolver::from_database_path(&db_path) {
         Ok(r) => r,
         Err(e) => {
             logger::log_error(&format!("Critical: Failed to initialize PathResolver from {}: {}", db_path.display(), e));
@@ -784,26 +601,42 @@ fn main() -> io::Result<()> {
             std::process::exit(1);
         }
     };
+    database.add_library_roots(&mut resolver, &config.library_roots);
 
-    // Load entries from database
-    let entries = get_entries().expect("Failed to get entries");
+    // Load entries from database; a failure is shown in the status line rather than aborting
+    let (entries, initial_status) = match database.get_entries() {
+        Ok(entries) => (entries, String::new()),
+        Err(e) => {
+            logger::log_error(&format!("Failed to load entries: {}", e));
+            (Vec::new(), format!("Error: Failed to load entries: {}", e))
+        }
+    };
     
     // Load theme from config directory
-    let config_dir = app_paths.config_file.parent()
+    let config_dir = config_path.parent()
         .expect("Config file should have a parent directory");
     let theme_path = config_dir.join(&config.active_theme);
     logger::log_info(&format!("Loading theme from {:?}", theme_path));
     let theme = theme::load_theme(&theme_path);
-    
-    // Create empty initial status for non-first-run path
-    let initial_status = String::new();
+    config_problems.extend(theme::theme_problems(&theme, &theme_path));
 
     // Start main loop
     initialize_terminal()?;
-    splash::show_splash_screen()
-        .map_err(|e| io::Error::other(e.to_string()))?;
+    show_splash(&config, config_dir, &mut config_problems)?;
     terminal::clear_screen()?;
-    let result = main_loop(entries, config, theme, Some(resolver), app_paths.config_file, initial_status);
+    let result = run_on_terminal(Startup {
+        entries,
+        config,
+        theme,
+        database: Some(Arc::new(database)),
+        resolver: Some(resolver),
+        config_path,
+        startup_message: initial_status,
+        show_tutorial: false,
+        handle_signals: true,
+        config_problems,
+    });
     restore_terminal()?;
+    shutdown::finish();
     result
 }
@@ -1,6 +1,10 @@
 use crossterm::event::KeyCode;
+use crate::components::ConfirmDialog;
+use crate::config::Config;
 use crate::dto::EpisodeDetail;
-use crate::util::{can_repeat_action, Entry, LastAction, Mode};
+use crate::export::ExportFormat;
+use crate::library_report::ReportFormat;
+use crate::util::{can_repeat_action, Entry, LastAction, Mode, ViewContext};
 
 #[derive(Debug, Clone)]
 pub struct MenuItem {
@@ -21,12 +25,82 @@ pub enum MenuAction {
     Edit,
     ToggleWatched,
     AssignToSeries,
+    EditNotes,
+    EditAltTitles,
+    EditInEditor,
+    CycleSort,
+    PinFilter,
+    ClearPinnedFilters,
     RepeatAction,
     Rescan,
+    ScanFolder,
+    AddFromUrl,
+    ShowExtras,
+    NotAnExtra,
+    FindDuplicates,
     ClearSeriesData,
     UnwatchAll,
     Delete,
+    DeleteFile,
+    MergeVersions,
     SearchOnline,
+    ExportLibrary(ExportFormat),
+    ExportReport(ReportFormat),
+    ImportLibrary { dry_run: bool },
+    ImportWatchHistory,
+    TraktSync,
+    JellyfinSync { dry_run: bool },
+    Tutorial,
+    UsageInsights,
+    ExportUsage,
+    Theme,
+    InstallThemes,
+    RelocateLibrary,
+    /// The custom command at this index of `custom_commands::installed`
+    Custom(usize),
+}
+
+impl MenuAction {
+    /// Stable name used for the local usage counters
+    pub fn usage_key(&self) -> &'static str {
+        match self {
+            MenuAction::Edit => "edit",
+            MenuAction::ToggleWatched => "toggle_watched",
+            MenuAction::AssignToSeries => "assign_series",
+            MenuAction::EditNotes => "edit_notes",
+            MenuAction::EditAltTitles => "edit_alt_titles",
+            MenuAction::EditInEditor => "edit_in_editor",
+            MenuAction::CycleSort => "cycle_sort",
+            MenuAction::PinFilter => "pin_filter",
+            MenuAction::ClearPinnedFilters => "clear_pinned_filters",
+            MenuAction::RepeatAction => "repeat_action",
+            MenuAction::Rescan => "rescan",
+            MenuAction::ScanFolder => "scan_folder",
+            MenuAction::AddFromUrl => "add_from_url",
+            MenuAction::ShowExtras => "show_extras",
+            MenuAction::NotAnExtra => "not_an_extra",
+            MenuAction::FindDuplicates => "find_duplicates",
+            MenuAction::ClearSeriesData => "clear_series",
+            MenuAction::UnwatchAll => "unwatch_all",
+            MenuAction::Delete => "delete",
+            MenuAction::DeleteFile => "delete_file",
+            MenuAction::MergeVersions => "merge_versions",
+            MenuAction::SearchOnline => "search_online",
+            MenuAction::ExportLibrary(_) => "export_library",
+            MenuAction::ExportReport(_) => "export_report",
+            MenuAction::ImportLibrary { .. } => "import_library",
+            MenuAction::ImportWatchHistory => "import_watch_history",
+            MenuAction::TraktSync => "trakt_sync",
+            MenuAction::JellyfinSync { .. } => "jellyfin_sync",
+            MenuAction::Tutorial => "tutorial",
+            MenuAction::UsageInsights => "usage_insights",
+            MenuAction::ExportUsage => "export_usage",
+            MenuAction::Theme => "theme",
+            MenuAction::InstallThemes => "install_themes",
+            MenuAction::RelocateLibrary => "relocate_library",
+            MenuAction::Custom(_) => "custom_command",
+        }
+    }
 }
 
 pub struct MenuContext {
@@ -34,6 +108,10 @@ pub struct MenuContext {
     pub episode_detail: EpisodeDetail,
     pub mode: crate::util::Mode,
     pub last_action: Option<LastAction>,
+    /// Number of episodes marked with Space; batch actions apply to them instead of the selected entry
+    pub marked_count: usize,
+    /// The Extras view is open
+    pub extras_view: bool,
 }
 
 /// Define all menu items with their properties
@@ -57,6 +135,42 @@ fn define_all_menu_items() -> Vec<MenuItem> {
             action: MenuAction::AssignToSeries,
             location: MenuLocation::ContextMenu,
         },
+        MenuItem {
+            label: "edit notes".to_string(),
+            hotkey: None,
+            action: MenuAction::EditNotes,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "edit alternate titles".to_string(),
+            hotkey: None,
+            action: MenuAction::EditAltTitles,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "edit in $EDITOR".to_string(),
+            hotkey: None,
+            action: MenuAction::EditInEditor,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "cycle sort order".to_string(),
+            hotkey: Some(KeyCode::Char('o')),
+            action: MenuAction::CycleSort,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "pin filter".to_string(),
+            hotkey: None,
+            action: MenuAction::PinFilter,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "clear pinned filters".to_string(),
+            hotkey: None,
+            action: MenuAction::ClearPinnedFilters,
+            location: MenuLocation::ContextMenu,
+        },
         MenuItem {
             label: "Repeat action".to_string(),
             hotkey: Some(KeyCode::F(5)),
@@ -87,15 +201,173 @@ fn define_all_menu_items() -> Vec<MenuItem> {
             action: MenuAction::Rescan,
             location: MenuLocation::ContextMenu,
         },
+        MenuItem {
+            label: "Scan Folder".to_string(),
+            hotkey: None,
+            action: MenuAction::ScanFolder,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Add from URL".to_string(),
+            hotkey: None,
+            action: MenuAction::AddFromUrl,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Show Extras".to_string(),
+            hotkey: None,
+            action: MenuAction::ShowExtras,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Not an Extra".to_string(),
+            hotkey: None,
+            action: MenuAction::NotAnExtra,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Find Duplicates".to_string(),
+            hotkey: None,
+            action: MenuAction::FindDuplicates,
+            location: MenuLocation::ContextMenu,
+        },
         MenuItem {
             label: "Delete".to_string(),
             hotkey: None,
             action: MenuAction::Delete,
             location: MenuLocation::ContextMenu,
         },
+        MenuItem {
+            label: "Delete File".to_string(),
+            hotkey: None,
+            action: MenuAction::DeleteFile,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "merge versions".to_string(),
+            hotkey: None,
+            action: MenuAction::MergeVersions,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Export Library (JSON)".to_string(),
+            hotkey: None,
+            action: MenuAction::ExportLibrary(ExportFormat::Json),
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Export Library (CSV)".to_string(),
+            hotkey: None,
+            action: MenuAction::ExportLibrary(ExportFormat::Csv),
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Export Report (Markdown)".to_string(),
+            hotkey: None,
+            action: MenuAction::ExportReport(ReportFormat::Markdown),
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Export Report (HTML)".to_string(),
+            hotkey: None,
+            action: MenuAction::ExportReport(ReportFormat::Html),
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Preview Import".to_string(),
+            hotkey: None,
+            action: MenuAction::ImportLibrary { dry_run: true },
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Import Library".to_string(),
+            hotkey: None,
+            action: MenuAction::ImportLibrary { dry_run: false },
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Import Kodi/Plex Watched".to_string(),
+            hotkey: None,
+            action: MenuAction::ImportWatchHistory,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Trakt Sync".to_string(),
+            hotkey: None,
+            action: MenuAction::TraktSync,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Preview Jellyfin Sync".to_string(),
+            hotkey: None,
+            action: MenuAction::JellyfinSync { dry_run: true },
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Jellyfin Sync".to_string(),
+            hotkey: None,
+            action: MenuAction::JellyfinSync { dry_run: false },
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Tutorial".to_string(),
+            hotkey: None,
+            action: MenuAction::Tutorial,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Usage Insights".to_string(),
+            hotkey: None,
+            action: MenuAction::UsageInsights,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Export Usage".to_string(),
+            hotkey: None,
+            action: MenuAction::ExportUsage,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Theme".to_string(),
+            hotkey: None,
+            action: MenuAction::Theme,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Install Themes".to_string(),
+            hotkey: None,
+            action: MenuAction::InstallThemes,
+            location: MenuLocation::ContextMenu,
+        },
+        MenuItem {
+            label: "Relocate Library".to_string(),
+            hotkey: None,
+            action: MenuAction::RelocateLibrary,
+            location: MenuLocation::ContextMenu,
+        },
     ]
 }
 
+/// Every menu item with the hotkeys of the installed keymap
+fn menu_items_with_hotkeys() -> Vec<MenuItem> {
+    let keymap = crate::keymap::keymap();
+    let mut items: Vec<MenuItem> = define_all_menu_items()
+        .into_iter()
+        .map(|mut item| {
+            item.hotkey = keymap.menu_hotkey(&item.action, item.hotkey);
+            item
+        })
+        .collect();
+    // Custom commands were checked against the keymap when they were installed
+    items.extend(crate::custom_commands::installed().into_iter().enumerate().map(|(index, command)| MenuItem {
+        label: command.label,
+        hotkey: command.hotkey,
+        action: MenuAction::Custom(index),
+        location: MenuLocation::ContextMenu,
+    }));
+    items
+}
+
 /// Check if a menu item should be available based on context
 fn is_item_available(item: &MenuItem, context: &MenuContext) -> bool {
     match &item.action {
@@ -104,13 +376,26 @@ fn is_item_available(item: &MenuItem, context: &MenuContext) -> bool {
             matches!(context.selected_entry, Some(Entry::Episode { .. }))
         }
         MenuAction::ToggleWatched => {
-            // Available only when selected entry is an Episode
-            matches!(context.selected_entry, Some(Entry::Episode { .. }))
+            // Available when episodes are marked or the selected entry is an Episode
+            context.marked_count > 0 || matches!(context.selected_entry, Some(Entry::Episode { .. }))
         }
         MenuAction::AssignToSeries => {
-            // Available only when selected entry is an Episode without a series
+            // Available when episodes are marked or the selected entry is an Episode without a series
+            context.marked_count > 0
+                || (matches!(context.selected_entry, Some(Entry::Episode { .. }))
+                    && context.episode_detail.series.is_none())
+        }
+        MenuAction::EditNotes | MenuAction::EditAltTitles | MenuAction::EditInEditor => {
+            // Available only when selected entry is an Episode
             matches!(context.selected_entry, Some(Entry::Episode { .. }))
-                && context.episode_detail.series.is_none()
+        }
+        MenuAction::CycleSort => {
+            // Always available
+            true
+        }
+        MenuAction::PinFilter | MenuAction::ClearPinnedFilters => {
+            // Pinned filters apply to the Browse list
+            matches!(context.mode, Mode::Browse)
         }
         MenuAction::RepeatAction => {
             // Available only when can_repeat_action returns true
@@ -120,13 +405,23 @@ fn is_item_available(item: &MenuItem, context: &MenuContext) -> bool {
                 false
             }
         }
-        MenuAction::Rescan => {
+        MenuAction::Rescan | MenuAction::ScanFolder | MenuAction::AddFromUrl | MenuAction::FindDuplicates => {
             // Always available
             true
         }
+        MenuAction::ShowExtras => {
+            // Available outside the Extras view
+            !context.extras_view
+        }
+        MenuAction::NotAnExtra => {
+            // Available only for an Episode in the Extras view
+            context.extras_view && matches!(context.selected_entry, Some(Entry::Episode { .. }))
+        }
         MenuAction::ClearSeriesData => {
-            // Available only when selected entry is an Episode with series data
-            if let Some(Entry::Episode { .. }) = context.selected_entry {
+            // Available when episodes are marked or the selected entry is an Episode with series data
+            if context.marked_count > 0 {
+                true
+            } else if let Some(Entry::Episode { .. }) = context.selected_entry {
                 // Check if any series-related field is populated
                 context.episode_detail.series.is_some()
                     || context.episode_detail.season.is_some()
@@ -141,6 +436,14 @@ fn is_item_available(item: &MenuItem, context: &MenuContext) -> bool {
             true
         }
         MenuAction::Delete => {
+            // Available when episodes are marked or the selected entry is an Episode
+            context.marked_count > 0 || matches!(context.selected_entry, Some(Entry::Episode { .. }))
+        }
+        MenuAction::DeleteFile => {
+            // Available only for the selected Episode, never for the marked ones
+            context.marked_count == 0 && matches!(context.selected_entry, Some(Entry::Episode { .. }))
+        }
+        MenuAction::MergeVersions => {
             // Available only when selected entry is an Episode
             matches!(context.selected_entry, Some(Entry::Episode { .. }))
         }
@@ -148,14 +451,142 @@ fn is_item_available(item: &MenuItem, context: &MenuContext) -> bool {
             // Available only in Browse mode
             matches!(context.mode, Mode::Browse)
         }
+        MenuAction::ExportLibrary(_) => {
+            // Always available
+            true
+        }
+        MenuAction::ExportReport(_) => {
+            // Always available
+            true
+        }
+        MenuAction::ImportLibrary { .. } => {
+            // Always available
+            true
+        }
+        MenuAction::ImportWatchHistory => {
+            // Always available
+            true
+        }
+        MenuAction::TraktSync => {
+            // Available only when built with the trakt feature
+            cfg!(feature = "trakt")
+        }
+        MenuAction::JellyfinSync { .. } => {
+            // Available only when built with the jellyfin feature
+            cfg!(feature = "jellyfin")
+        }
+        MenuAction::Tutorial
+        | MenuAction::UsageInsights
+        | MenuAction::ExportUsage
+        | MenuAction::Theme
+        | MenuAction::InstallThemes
+        | MenuAction::RelocateLibrary => {
+            // Always available
+            true
+        }
+        MenuAction::Custom(_) => {
+            // Available only when selected entry is an Episode
+            matches!(context.selected_entry, Some(Entry::Episode { .. }))
+        }
+    }
+}
+
+/// Every menu item this build supports, regardless of context
+pub fn all_menu_items() -> Vec<MenuItem> {
+    menu_items_with_hotkeys()
+        .into_iter()
+        .filter(|item| !matches!(item.action, MenuAction::TraktSync) || cfg!(feature = "trakt"))
+        .filter(|item| !matches!(item.action, MenuAction::JellyfinSync { .. }) || cfg!(feature = "jellyfin"))
+        .collect()
+}
+
+/// Whether the action applies to every marked episode when some are marked
+pub fn is_batch_action(action: &MenuAction) -> bool {
+    matches!(
+        action,
+        MenuAction::ToggleWatched | MenuAction::AssignToSeries | MenuAction::ClearSeriesData | MenuAction::Delete
+    )
+}
+
+/// A destructive action waiting for the user to answer its confirmation dialog
+#[derive(Debug, Clone)]
+pub struct PendingConfirmation {
+    pub action: MenuAction,
+    /// Index of the entry the action was chosen for
+    pub remembered_item: usize,
+    pub dialog: ConfirmDialog,
+}
+
+/// Whether `config` wants `action`'s confirmation dialog shown; Delete File always asks, as files
+/// that can't go to a trash are deleted for good
+pub fn asks_confirmation(action: &MenuAction, config: &Config) -> bool {
+    match action {
+        MenuAction::Delete => config.confirm_delete,
+        MenuAction::UnwatchAll => config.confirm_unwatch_all,
+        MenuAction::ClearSeriesData => config.confirm_clear_series,
+        _ => true,
+    }
+}
+
+/// Dialog to show before a destructive action, or None when the action runs straight away
+/// Batch actions describe the marked episodes instead of the selected entry
+pub fn confirmation_dialog(
+    action: &MenuAction,
+    entry: Option<&Entry>,
+    view_context: &ViewContext,
+    marked_count: usize,
+) -> Option<ConfirmDialog> {
+    let target = if marked_count > 0 && is_batch_action(action) {
+        format!("the {} marked episodes", marked_count)
+    } else {
+        match entry {
+            Some(Entry::Episode { name, .. }) => format!("'{}'", name),
+            _ => "this episode".to_string(),
+        }
+    };
+    match action {
+        MenuAction::Delete => Some(ConfirmDialog::new(
+            "Delete",
+            format!("Remove {} from the library? Video files stay on disk.", target),
+        )),
+        MenuAction::DeleteFile => Some(ConfirmDialog::new(
+            "Delete file",
+            format!(
+                "Move the video files of {} to the trash and remove it from the library? Files that can't go to a trash are deleted for good.",
+                target
+            ),
+        )),
+        MenuAction::ClearSeriesData => Some(ConfirmDialog::new(
+            "Clear series data",
+            format!("Clear the series, season and episode number of {}?", target),
+        )),
+        MenuAction::UnwatchAll => {
+            let scope = match view_context {
+                ViewContext::TopLevel => "every standalone episode".to_string(),
+                ViewContext::Extras => "every extra".to_string(),
+                ViewContext::Series { series_name, .. } => format!("every episode of '{}'", series_name),
+                ViewContext::Season { series_name, season_number, .. } => {
+                    format!("every episode of '{}' season {}", series_name, season_number)
+                }
+            };
+            Some(ConfirmDialog::new("Unwatch all", format!("Mark {} as unwatched?", scope)))
+        }
+        _ => None,
     }
 }
 
 /// Get all menu items available for the current context
+/// Batch actions name the number of marked episodes they will change
 pub fn get_available_menu_items(context: &MenuContext) -> Vec<MenuItem> {
-    define_all_menu_items()
+    menu_items_with_hotkeys()
         .into_iter()
         .filter(|item| is_item_available(item, context))
+        .map(|mut item| {
+            if context.marked_count > 0 && is_batch_action(&item.action) {
+                item.label = format!("{} ({} marked)", item.label, context.marked_count);
+            }
+            item
+        })
         .collect()
 }
 
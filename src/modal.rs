@@ -0,0 +1,52 @@
+use crate::util::Mode;
+
+/// A screen left open under a modal, with the selection and scroll to put back when it shows again
+#[derive(Debug, Clone, PartialEq)]
+pub struct Screen {
+    pub mode: Mode,
+    pub current_item: usize,
+    pub first_entry: usize,
+    pub menu_selection: usize,
+}
+
+/// Screens under the one on screen, the oldest first
+///
+/// Opening a modal (the menu, a dialog, the help) pushes the screen it covers, and closing it
+/// pops that screen back, so Esc returns to exactly where things were and a dialog can open
+/// over another one, such as the confirmation over the menu. A handler that finishes its work
+/// by setting the mode to a screen further down, usually Browse, drops everything above it.
+#[derive(Debug, Clone, Default)]
+pub struct ModalStack {
+    screens: Vec<Screen>,
+}
+
+impl ModalStack {
+    /// Show `next` over the screen in `mode`, keeping that screen and its selection to return to
+    pub fn open(&mut self, mode: &mut Mode, next: Mode, current_item: usize, first_entry: usize, menu_selection: usize) {
+        self.screens.push(Screen {
+            mode: std::mem::replace(mode, next),
+            current_item,
+            first_entry,
+            menu_selection,
+        });
+    }
+
+    pub fn pop(&mut self) -> Option<Screen> {
+        self.screens.pop()
+    }
+
+    /// Screen directly under the one on screen
+    pub fn top(&self) -> Option<&Screen> {
+        self.screens.last()
+    }
+
+    /// Drop the screens from the first one in `mode` up, once `mode` is back on screen
+    /// Browse is always at the bottom, so returning to it empties the stack
+    pub fn follow(&mut self, mode: &Mode) {
+        if *mode == Mode::Browse {
+            self.screens.clear();
+        } else if let Some(index) = self.screens.iter().position(|screen| screen.mode == *mode) {
+            self.screens.truncate(index);
+        }
+    }
+}
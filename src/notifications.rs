@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Number of past notifications kept for review
+pub const HISTORY_SIZE: usize = 50;
+
+/// Shortest time a notification stays up when others are waiting behind it
+pub const MIN_SHOWN: Duration = Duration::from_secs(2);
+
+/// Time a toast stays in the corner of the screen
+pub const TOAST_DURATION: Duration = Duration::from_millis(2500);
+
+/// How serious a notification is; decides its color and how long it stays in the status bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl NotificationLevel {
+    /// Time in the status bar when nothing else is waiting
+    pub fn timeout(&self) -> Duration {
+        match self {
+            NotificationLevel::Info => Duration::from_secs(5),
+            NotificationLevel::Warn => Duration::from_secs(10),
+            NotificationLevel::Error => Duration::from_secs(15),
+        }
+    }
+
+    /// Short name shown in the notification history
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationLevel::Info => "info",
+            NotificationLevel::Warn => "warn",
+            NotificationLevel::Error => "error",
+        }
+    }
+}
+
+/// One status bar message
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub level: NotificationLevel,
+    pub message: String,
+    pub created: Instant,
+}
+
+impl Notification {
+    pub fn new(level: NotificationLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+            created: Instant::now(),
+        }
+    }
+}
+
+/// Status bar messages shown one at a time, in order, each until it expires
+#[derive(Debug, Default)]
+pub struct Notifications {
+    /// The first one is on screen; the rest wait their turn
+    pending: VecDeque<Notification>,
+    /// When the first pending notification appeared
+    shown_since: Option<Instant>,
+    /// Recent notifications, oldest first
+    history: VecDeque<Notification>,
+    /// Short confirmation drawn in a corner, apart from the status bar
+    toast: Option<Notification>,
+}
+
+impl Notifications {
+    /// Queue a message; empty messages and repeats of the last queued one are ignored
+    pub fn push(&mut self, level: NotificationLevel, message: impl Into<String>) {
+        let notification = Notification::new(level, message);
+        if notification.message.is_empty()
+            || self
+                .pending
+                .back()
+                .is_some_and(|last| last.level == level && last.message == notification.message)
+        {
+            return;
+        }
+        self.record(&notification);
+        self.pending.push_back(notification);
+    }
+
+    /// Keep a notification in the history, dropping the oldest when it is full
+    fn record(&mut self, notification: &Notification) {
+        if self.history.len() == HISTORY_SIZE {
+            self.history.pop_front();
+        }
+        self.history.push_back(notification.clone());
+    }
+
+    /// Show a confirmation as a toast for TOAST_DURATION, replacing the one up
+    /// It goes into the history but leaves the status bar to longer-lived messages
+    pub fn toast(&mut self, message: impl Into<String>) {
+        let notification = Notification::new(NotificationLevel::Info, message);
+        if notification.message.is_empty() {
+            return;
+        }
+        self.record(&notification);
+        self.toast = Some(notification);
+    }
+
+    /// The toast on screen, if any
+    pub fn current_toast(&self) -> Option<&Notification> {
+        self.toast.as_ref()
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(NotificationLevel::Info, message);
+    }
+
+    pub fn warn(&mut self, message: impl Into<String>) {
+        self.push(NotificationLevel::Warn, message);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(NotificationLevel::Error, message);
+    }
+
+    /// The notification on screen, if any
+    pub fn current(&self) -> Option<&Notification> {
+        self.pending.front()
+    }
+
+    /// Drop the notification and the toast on screen once their time is up
+    /// Returns true when the screen needs redrawing
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let toast_expired = self
+            .toast
+            .as_ref()
+            .is_some_and(|toast| now.duration_since(toast.created) >= TOAST_DURATION);
+        if toast_expired {
+            self.toast = None;
+        }
+        let Some(current) = self.pending.front() else {
+            return toast_expired;
+        };
+        let shown_since = *self.shown_since.get_or_insert(now);
+        let timeout = if self.pending.len() > 1 {
+            MIN_SHOWN.min(current.level.timeout())
+        } else {
+            current.level.timeout()
+        };
+        if now.duration_since(shown_since) < timeout {
+            return toast_expired;
+        }
+        self.dismiss();
+        self.shown_since = self.pending.front().map(|_| now);
+        true
+    }
+
+    /// Remove the notification on screen, showing the next one
+    pub fn dismiss(&mut self) {
+        self.pending.pop_front();
+        self.shown_since = None;
+    }
+
+    /// Recent notifications, oldest first
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &Notification> + ExactSizeIterator {
+        self.history.iter()
+    }
+}
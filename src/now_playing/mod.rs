@@ -0,0 +1,95 @@
+// Now playing: shows the desktop what the launched player is playing, so media widgets list it
+// The MPRIS interface on D-Bus lives in its own submodule behind the `mpris` cargo feature
+
+#[cfg(feature = "mpris")]
+mod mpris;
+
+use crate::dto::EpisodeDetail;
+use std::path::{Path, PathBuf};
+
+/// The video the player was launched on, as the desktop is told about it
+#[cfg_attr(not(feature = "mpris"), allow(dead_code))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NowPlaying {
+    pub episode_id: usize,
+    pub title: String,
+    pub series: Option<String>,
+    pub season: Option<usize>,
+    pub episode_number: Option<u32>,
+    pub path: PathBuf,
+    /// Length of the video in seconds, 0 when unknown
+    pub length: u64,
+    /// Where playback starts, in seconds
+    pub start: u64,
+}
+
+#[cfg_attr(not(feature = "mpris"), allow(dead_code))]
+impl NowPlaying {
+    pub fn new(episode_id: usize, details: &EpisodeDetail, path: &Path, start: u64) -> NowPlaying {
+        NowPlaying {
+            episode_id,
+            title: details.title.clone(),
+            series: details.series.as_ref().map(|series| series.name.clone()),
+            season: details.season.as_ref().map(|season| season.number),
+            episode_number: details.episode_number.trim().parse().ok(),
+            path: path.to_path_buf(),
+            length: details.length.trim().parse().unwrap_or(0),
+            start,
+        }
+    }
+
+    /// The series and season as an album name, such as "The Wire, Season 1"
+    pub fn album(&self) -> Option<String> {
+        match (&self.series, self.season) {
+            (Some(series), Some(season)) => Some(format!("{}, Season {}", series, season)),
+            (Some(series), None) => Some(series.clone()),
+            (None, _) => None,
+        }
+    }
+
+    /// The file as a file:// URL
+    pub fn url(&self) -> String {
+        let path = self.path.to_string_lossy().replace('\\', "/");
+        let mut url = String::from("file://");
+        if !path.starts_with('/') {
+            url.push('/');
+        }
+        for byte in path.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => url.push(byte as char),
+                _ => url.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        url
+    }
+
+    /// D-Bus object path naming the episode
+    pub fn track_id(&self) -> String {
+        format!("/org/movies/episode/{}", self.episode_id)
+    }
+}
+
+/// Keeps the video listed on the desktop until dropped
+pub struct Publication {
+    #[cfg(feature = "mpris")]
+    _server: mpris::Server,
+}
+
+/// List `now_playing` on the desktop; None when there is no session bus to list it on
+///
+/// Failures are written to the log only, since playback goes on without the listing.
+#[cfg(feature = "mpris")]
+pub fn publish(now_playing: NowPlaying) -> Option<Publication> {
+    match mpris::Server::start(now_playing) {
+        Ok(server) => Some(Publication { _server: server }),
+        Err(e) => {
+            crate::logger::log_debug(&format!("Couldn't publish now playing over MPRIS: {}", e));
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "mpris"))]
+pub fn publish(_now_playing: NowPlaying) -> Option<Publication> {
+    None
+}
@@ -0,0 +1,189 @@
+// MPRIS (https://specifications.freedesktop.org/mpris-spec/latest/) player on the session bus
+//
+// The player the app launches does the playing, so the interface only describes the video:
+// CanControl is false and the control methods do nothing.
+
+use super::NowPlaying;
+use std::collections::HashMap;
+use std::time::Instant;
+use zbus::blocking::{connection, Connection};
+use zbus::interface;
+use zbus::zvariant::{ObjectPath, OwnedValue, Value};
+
+const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+
+/// The bus connection serving the interface; dropping it takes the player off the bus
+pub struct Server {
+    _connection: Connection,
+}
+
+impl Server {
+    pub fn start(now_playing: NowPlaying) -> zbus::Result<Server> {
+        let player = Player { now_playing, started: Instant::now() };
+        let connection = connection::Builder::session()?
+            .name(format!("org.mpris.MediaPlayer2.movies.instance{}", std::process::id()))?
+            .serve_at(OBJECT_PATH, Root)?
+            .serve_at(OBJECT_PATH, player)?
+            .build()?;
+        Ok(Server { _connection: connection })
+    }
+}
+
+struct Root;
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl Root {
+    fn raise(&self) {}
+
+    fn quit(&self) {}
+
+    #[zbus(property)]
+    fn can_quit(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_raise(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn has_track_list(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn identity(&self) -> String {
+        "movies".to_string()
+    }
+
+    #[zbus(property)]
+    fn supported_uri_schemes(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[zbus(property)]
+    fn supported_mime_types(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+struct Player {
+    now_playing: NowPlaying,
+    started: Instant,
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl Player {
+    fn next(&self) {}
+
+    fn previous(&self) {}
+
+    fn pause(&self) {}
+
+    fn play_pause(&self) {}
+
+    fn stop(&self) {}
+
+    fn play(&self) {}
+
+    fn seek(&self, _offset: i64) {}
+
+    fn set_position(&self, _track_id: ObjectPath<'_>, _position: i64) {}
+
+    fn open_uri(&self, _uri: String) {}
+
+    #[zbus(property)]
+    fn playback_status(&self) -> String {
+        "Playing".to_string()
+    }
+
+    #[zbus(property)]
+    fn rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    fn minimum_rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    fn maximum_rate(&self) -> f64 {
+        1.0
+    }
+
+    #[zbus(property)]
+    fn volume(&self) -> f64 {
+        1.0
+    }
+
+    /// Microseconds into the video, guessed from where playback started and the time since
+    #[zbus(property)]
+    fn position(&self) -> i64 {
+        let seconds = self.now_playing.start + self.started.elapsed().as_secs();
+        let seconds = if self.now_playing.length > 0 { seconds.min(self.now_playing.length) } else { seconds };
+        seconds as i64 * 1_000_000
+    }
+
+    #[zbus(property)]
+    fn metadata(&self) -> HashMap<String, OwnedValue> {
+        let now_playing = &self.now_playing;
+        let mut metadata: Vec<(&str, Value<'static>)> = vec![
+            ("xesam:title", now_playing.title.clone().into()),
+            ("xesam:url", now_playing.url().into()),
+        ];
+        if let Ok(track_id) = ObjectPath::try_from(now_playing.track_id()) {
+            metadata.push(("mpris:trackid", track_id.into()));
+        }
+        if now_playing.length > 0 {
+            metadata.push(("mpris:length", (now_playing.length as i64 * 1_000_000).into()));
+        }
+        if let Some(series) = &now_playing.series {
+            metadata.push(("xesam:artist", vec![series.clone()].into()));
+        }
+        if let Some(album) = now_playing.album() {
+            metadata.push(("xesam:album", album.into()));
+        }
+        if let Some(season) = now_playing.season {
+            metadata.push(("xesam:discNumber", (season as i32).into()));
+        }
+        if let Some(episode_number) = now_playing.episode_number {
+            metadata.push(("xesam:trackNumber", (episode_number as i32).into()));
+        }
+        metadata
+            .into_iter()
+            .filter_map(|(key, value)| OwnedValue::try_from(value).ok().map(|value| (key.to_string(), value)))
+            .collect()
+    }
+
+    #[zbus(property)]
+    fn can_go_next(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_go_previous(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_play(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_pause(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_seek(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_control(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,132 @@
+//! Tab-completion and checking of the folder paths typed in Entry mode and the first-run prompt
+//!
+//! Tab completes the last part of the path to the folders of its parent whose names start with
+//! it: a single match is completed with a separator after it, several are completed as far as
+//! they agree and listed. Hidden folders are only offered once a '.' is typed. While typing, the
+//! path is checked so a typo shows before Enter is pressed.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, MAIN_SEPARATOR};
+
+/// What a typed path points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStatus {
+    /// Nothing typed yet
+    Empty,
+    Missing,
+    NotADirectory,
+    /// The folder, or one on the way to it, can't be read
+    NoPermission,
+    /// An existing folder that can be read
+    Ready,
+}
+
+impl PathStatus {
+    /// Text shown after the path, e.g. "no such folder"
+    pub fn message(&self) -> &'static str {
+        match self {
+            PathStatus::Empty => "type a folder, [TAB] completes",
+            PathStatus::Missing => "no such folder",
+            PathStatus::NotADirectory => "not a folder",
+            PathStatus::NoPermission => "permission denied",
+            PathStatus::Ready => "folder found",
+        }
+    }
+}
+
+/// Result of completing a typed path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// The path with as much of the last part filled in as the matches agree on
+    pub path: String,
+    /// Names of the matching folders, when there is more than one
+    pub candidates: Vec<String>,
+}
+
+/// Check what the folder path `text` points at
+pub fn check_path(text: &str) -> PathStatus {
+    let text = text.trim();
+    if text.is_empty() {
+        return PathStatus::Empty;
+    }
+    let path = Path::new(text);
+    match fs::metadata(path) {
+        Ok(metadata) if !metadata.is_dir() => PathStatus::NotADirectory,
+        Ok(_) => match fs::read_dir(path) {
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => PathStatus::NoPermission,
+            _ => PathStatus::Ready,
+        },
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => PathStatus::NoPermission,
+        Err(_) => PathStatus::Missing,
+    }
+}
+
+/// Complete the last part of the folder path `text`; the path comes back as it is without a match
+pub fn complete(text: &str) -> Completion {
+    let split = text.rfind(is_separator).map(|index| index + 1).unwrap_or(0);
+    let (parent, partial) = text.split_at(split);
+    let mut names = matching_folders(parent, partial);
+    let path = match names.as_slice() {
+        [] => text.to_string(),
+        [name] => format!("{}{}{}", parent, name, MAIN_SEPARATOR),
+        _ => format!("{}{}", parent, common_prefix(&names)),
+    };
+    if names.len() == 1 {
+        names.clear();
+    }
+    Completion { path, candidates: names }
+}
+
+/// Sorted names of the folders in `parent` starting with `partial`
+fn matching_folders(parent: &str, partial: &str) -> Vec<String> {
+    let folder = if parent.is_empty() { Path::new(".") } else { Path::new(parent) };
+    let Ok(read_dir) = fs::read_dir(folder) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = read_dir
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| partial.starts_with('.') || !name.starts_with('.'))
+        .filter(|name| starts_with(name, partial))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Longest start all of `names` share
+fn common_prefix(names: &[String]) -> String {
+    let Some(first) = names.first() else {
+        return String::new();
+    };
+    let mut length = first.len();
+    for name in &names[1..] {
+        length = first
+            .char_indices()
+            .zip(name.chars())
+            .take_while(|((_, a), b)| same_char(*a, *b))
+            .last()
+            .map(|((index, a), _)| index + a.len_utf8())
+            .unwrap_or(0)
+            .min(length);
+    }
+    first[..length].to_string()
+}
+
+fn is_separator(c: char) -> bool {
+    c == '/' || c == MAIN_SEPARATOR
+}
+
+/// Folder names match ignoring case on Windows, where the file system does too
+fn same_char(a: char, b: char) -> bool {
+    if cfg!(windows) {
+        a.to_lowercase().eq(b.to_lowercase())
+    } else {
+        a == b
+    }
+}
+
+fn starts_with(name: &str, partial: &str) -> bool {
+    name.chars().count() >= partial.chars().count() && name.chars().zip(partial.chars()).all(|(a, b)| same_char(a, b))
+}
@@ -42,11 +42,31 @@ impl From<io::Error> for PathResolverError {
     }
 }
 
+/// Another folder of the library, such as one on an external drive
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibraryRoot {
+    /// Id of the folder in the database, stored with the episodes under it
+    pub id: usize,
+    pub path: PathBuf,
+}
+
+/// True when a library folder looks like a drive or share that isn't mounted: it can't be listed,
+/// or it is empty, as the mount point of an unplugged drive usually is
+pub fn is_offline(folder: &Path) -> bool {
+    match std::fs::read_dir(folder) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(_) => true,
+    }
+}
+
 /// PathResolver handles all path resolution logic for the application
 /// 
-/// It maintains the root directory for video files (from config.yaml)
+/// It maintains the root directory for video files (the database's directory), and any
+/// other folders of the library. Episodes store their path relative to the root they are under.
+#[derive(Clone)]
 pub struct PathResolver {
     root_dir: PathBuf,
+    extra_roots: Vec<LibraryRoot>,
 }
 
 impl PathResolver {
@@ -87,9 +107,39 @@ impl PathResolver {
         
         Ok(PathResolver {
             root_dir: canonical_root,
+            extra_roots: Vec::new(),
         })
     }
 
+    /// Add another folder of the library
+    /// Its path is canonicalized when it exists; a drive that isn't mounted keeps the path as given
+    pub fn add_root(&mut self, id: usize, path: &Path) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.extra_roots.retain(|root| root.id != id);
+        self.extra_roots.push(LibraryRoot { id, path });
+    }
+
+    /// Folders of the library other than the root directory
+    pub fn extra_roots(&self) -> &[LibraryRoot] {
+        &self.extra_roots
+    }
+
+    /// The folder of the library `path` is under, the root directory or another one
+    pub fn root_of(&self, path: &Path) -> Option<&Path> {
+        std::iter::once(self.root_dir.as_path())
+            .chain(self.extra_roots.iter().map(|root| root.path.as_path()))
+            .find(|folder| path.starts_with(folder))
+    }
+
+    /// Folders of the library that aren't there right now, the root directory first
+    pub fn offline_roots(&self) -> Vec<PathBuf> {
+        std::iter::once(self.root_dir.as_path())
+            .chain(self.extra_roots.iter().map(|root| root.path.as_path()))
+            .filter(|folder| is_offline(folder))
+            .map(Path::to_path_buf)
+            .collect()
+    }
+
 
 
 
@@ -140,7 +190,33 @@ impl PathResolver {
         Ok(relative_path)
     }
     
+    /// The root holding `absolute_path`, None for the root directory, and the path relative to it
+    /// The root directory is tried first, then the other folders in the order they were added
+    ///
+    /// Symbolic links are resolved first. A path that only leads out of the roots through a
+    /// link, as scans following links find, is kept relative to the root it was found under.
+    pub fn locate(&self, absolute_path: &Path) -> Result<(Option<usize>, PathBuf), PathResolverError> {
+        let canonical_path = absolute_path.canonicalize()?;
+        if canonical_path.starts_with(&self.root_dir) {
+            return self.to_relative(absolute_path).map(|relative_path| (None, relative_path));
+        }
+        let in_extra_root = |path: &Path| {
+            self.extra_roots.iter().find_map(|root| {
+                let relative_path = path.strip_prefix(&root.path).ok()?;
+                Some((Some(root.id), relative_path.to_path_buf()))
+            })
+        };
+        if let Some(found) = in_extra_root(&canonical_path) {
+            return Ok(found);
+        }
+        if let Ok(relative_path) = absolute_path.strip_prefix(&self.root_dir) {
+            return Ok((None, relative_path.to_path_buf()));
+        }
+        in_extra_root(absolute_path).ok_or(PathResolverError::PathNotUnderRoot(canonical_path))
+    }
+
     /// Convert a relative path to an absolute path using the configured root directory
+    /// An absolute path, as the database gives for episodes under another folder, is kept as it is
     pub fn to_absolute(&self, relative_path: &Path) -> PathBuf {
         let absolute_path = self.root_dir.join(relative_path);
         crate::logger::log_debug(&format!(
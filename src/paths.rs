@@ -1,5 +1,12 @@
 use directories::ProjectDirs;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
+use std::sync::RwLock;
+
+/// File next to the executable that turns on portable mode, like the --portable flag
+pub const PORTABLE_MARKER: &str = "movies.portable";
+
+/// Folder holding everything in portable mode, None for the system's config and data folders
+static PORTABLE_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
 
 pub struct AppPaths {
     pub config_file: PathBuf,
@@ -7,18 +14,81 @@ pub struct AppPaths {
 
 impl AppPaths {
     pub fn new() -> Result<Self, String> {
-        let proj_dirs = ProjectDirs::from("", "", "movies")
-            .ok_or("Failed to determine application directories")?;
-        
-        let config_dir = proj_dirs.config_dir();
-        
+        let config_dir = config_dir().ok_or("Failed to determine application directories")?;
+
         // Create config directory if it doesn't exist
-        std::fs::create_dir_all(config_dir)
-            .map_err(|e| format!("Failed to create config directory {}: {}", 
+        std::fs::create_dir_all(&config_dir)
+            .map_err(|e| format!("Failed to create config directory {}: {}",
                 config_dir.display(), e))?;
-        
+
         Ok(AppPaths {
             config_file: config_dir.join("config.yaml"),
         })
     }
 }
+
+/// Keep the config, themes, log, session and other state in `dir` instead of the system's folders
+pub fn set_portable_dir(dir: Option<PathBuf>) {
+    *PORTABLE_DIR.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = dir;
+}
+
+/// The folder set by `set_portable_dir`, when running in portable mode
+pub fn portable_dir() -> Option<PathBuf> {
+    PORTABLE_DIR.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Folder of the running executable, when `flag` or a PORTABLE_MARKER file next to it asks for portable mode
+pub fn detect_portable_dir(flag: bool) -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    (flag || exe_dir.join(PORTABLE_MARKER).exists()).then_some(exe_dir)
+}
+
+/// Folder of config.yaml, the themes and the Trakt token
+pub fn config_dir() -> Option<PathBuf> {
+    portable_dir().or_else(|| ProjectDirs::from("", "", "movies").map(|dirs| dirs.config_dir().to_path_buf()))
+}
+
+/// Folder of the log, the session, the filter history and the other state kept between runs
+pub fn data_dir() -> Option<PathBuf> {
+    portable_dir().or_else(|| ProjectDirs::from("", "", "movies").map(|dirs| dirs.data_dir().to_path_buf()))
+}
+
+/// A stored location as a path: in portable mode, relative ones are relative to the portable folder
+pub fn resolve_location(location: &str) -> PathBuf {
+    let path = PathBuf::from(location);
+    match portable_dir() {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path,
+    }
+}
+
+/// `path` as it is stored: in portable mode, relative to the portable folder when both are on the
+/// same drive, so the library still opens when the drive is mounted somewhere else
+pub fn store_location(path: &Path) -> String {
+    match portable_dir().and_then(|dir| relative_path(path, &dir)) {
+        Some(relative) => relative.to_string_lossy().to_string(),
+        None => path.to_string_lossy().to_string(),
+    }
+}
+
+/// `path` relative to the folder `base`, going up with ".." where needed; None when the two
+/// don't share a root, like paths on different Windows drives
+pub fn relative_path(path: &Path, base: &Path) -> Option<PathBuf> {
+    if !path.is_absolute() || !base.is_absolute() {
+        return None;
+    }
+    let path: Vec<Component> = path.components().collect();
+    let base: Vec<Component> = base.components().collect();
+    if path.first() != base.first() {
+        return None;
+    }
+    let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    for component in &path[common..] {
+        relative.push(component);
+    }
+    Some(relative)
+}
@@ -1,10 +1,12 @@
+use std::ffi::OsString;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 /// Trait for video player plugins that support progress tracking
 pub trait PlayerPlugin {
     /// Returns the command and arguments to launch the player with optional resume position
-    fn launch_command(&self, file_path: &Path, start_time: Option<u64>) -> (String, Vec<String>);
+    /// The file is passed as the system knows it, so a name that isn't valid UTF-8 still plays
+    fn launch_command(&self, file_path: &Path, start_time: Option<u64>) -> (String, Vec<OsString>);
     
     /// Retrieves the final playback position after the player exits
     /// Returns None if position couldn't be determined
@@ -51,15 +53,13 @@ impl CelluloidPlugin {
         let absolute_path = file_path.canonicalize()
             .unwrap_or_else(|_| file_path.to_path_buf());
         
-        // Calculate MD5 hash of the absolute path string
-        let path_str = absolute_path.to_string_lossy();
-        
+        // Calculate MD5 hash of the absolute path's bytes, as mpv does
         crate::logger::log_debug(&format!(
             "Calculating hash for path: {}",
-            path_str
+            absolute_path.display()
         ));
         
-        let digest = md5::compute(path_str.as_bytes());
+        let digest = md5::compute(crate::location::path_bytes(&absolute_path));
         
         // Convert to uppercase hex string
         let hash = format!("{:X}", digest);
@@ -146,7 +146,7 @@ impl Default for CelluloidPlugin {
 }
 
 impl PlayerPlugin for CelluloidPlugin {
-    fn launch_command(&self, file_path: &Path, start_time: Option<u64>) -> (String, Vec<String>) {
+    fn launch_command(&self, file_path: &Path, start_time: Option<u64>) -> (String, Vec<OsString>) {
         // Let Celluloid/mpv manage resume position, but pass --mpv-start=0
         // when we explicitly want to start from the beginning (e.g., after marking as watched)
         
@@ -163,7 +163,8 @@ impl PlayerPlugin for CelluloidPlugin {
         // If start_time is None, let Celluloid handle resume automatically
         // If start_time is Some(non-zero), ignore it and let Celluloid handle it
         
-        args.push(file_path.to_string_lossy().to_string());
+        let mut args: Vec<OsString> = args.into_iter().map(OsString::from).collect();
+        args.push(file_path.as_os_str().to_os_string());
         
         ("celluloid".to_string(), args)
     }
@@ -249,7 +250,7 @@ impl GenericPlayerPlugin {
 }
 
 impl PlayerPlugin for GenericPlayerPlugin {
-    fn launch_command(&self, file_path: &Path, start_time: Option<u64>) -> (String, Vec<String>) {
+    fn launch_command(&self, file_path: &Path, start_time: Option<u64>) -> (String, Vec<OsString>) {
         let mut args = vec![];
         
         // Try to add resume position based on common player formats
@@ -278,7 +279,8 @@ impl PlayerPlugin for GenericPlayerPlugin {
             }
         }
         
-        args.push(file_path.to_string_lossy().to_string());
+        let mut args: Vec<OsString> = args.into_iter().map(OsString::from).collect();
+        args.push(file_path.as_os_str().to_os_string());
         
         (self.player_command.clone(), args)
     }
@@ -1,4 +1,4 @@
-use crate::database;
+use crate::database::Database;
 use crate::config::Config;
 use std::sync::{Arc, Mutex, mpsc};
 use std::thread;
@@ -41,8 +41,8 @@ impl ProgressTracker {
         }
     }
 
-    /// Start progress tracking in a background thread
-    pub fn start_tracking(&mut self, player_process: Arc<Mutex<Child>>) -> Result<(), Box<dyn std::error::Error>> {
+    /// Start progress tracking in a background thread, saving the progress to `database`
+    pub fn start_tracking(&mut self, database: Arc<Database>, player_process: Arc<Mutex<Child>>) -> Result<(), Box<dyn std::error::Error>> {
         // Create communication channel for stopping the tracker
         let (stop_sender, stop_receiver) = mpsc::channel();
         self.stop_sender = Some(stop_sender);
@@ -116,7 +116,7 @@ impl ProgressTracker {
                 let current_progress = current_progress.min(total_duration);
 
                 // Update progress in database
-                if let Err(e) = database::update_episode_progress(episode_id, current_progress) {
+                if let Err(e) = database.update_episode_progress(episode_id, current_progress) {
                     crate::logger::log_error(&format!(
                         "Failed to update progress for episode {}: {}",
                         episode_id, e
@@ -135,7 +135,7 @@ impl ProgressTracker {
                 if !auto_watched_triggered && total_duration > 0 {
                     let progress_percentage = (current_progress * 100) / total_duration;
                     if progress_percentage >= watched_threshold as u64 {
-                        if let Err(e) = database::mark_episode_watched_with_timestamp(episode_id) {
+                        if let Err(e) = database.mark_episode_watched_with_timestamp(episode_id) {
                             crate::logger::log_error(&format!(
                                 "Failed to auto-mark episode {} as watched: {}",
                                 episode_id, e
@@ -196,9 +196,10 @@ pub fn start_progress_tracking(
     episode_id: usize,
     total_duration: u64,
     config: &Config,
+    database: Arc<Database>,
     player_process: Arc<Mutex<Child>>,
 ) -> Result<ProgressTracker, Box<dyn std::error::Error>> {
     let mut tracker = ProgressTracker::new(episode_id, total_duration, config.watched_threshold);
-    tracker.start_tracking(player_process)?;
+    tracker.start_tracking(database, player_process)?;
     Ok(tracker)
 }
\ No newline at end of file
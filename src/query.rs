@@ -0,0 +1,341 @@
+use crate::dto::AltTitles;
+use crate::util::{Entry, EntryKey};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// Most filters that can be pinned at once, toggled with the number keys
+pub const MAX_PINNED_FILTERS: usize = 3;
+
+/// One whitespace-separated term of a filter expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryTerm {
+    /// Case-insensitive substring of the entry name
+    Text(String),
+    /// `watched` or `unwatched`
+    Watched(bool),
+    /// `<30min`: length in seconds below the limit
+    ShorterThan(u64),
+    /// `>90min`: length in seconds above the limit
+    LongerThan(u64),
+}
+
+/// Episode values the non-text terms are checked against
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpisodeFacts {
+    pub episode_id: usize,
+    pub series_id: Option<usize>,
+    pub season_id: Option<usize>,
+    pub watched: bool,
+    pub length: Option<u64>,
+}
+
+/// A parsed filter expression; every term must match
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    expression: String,
+    terms: Vec<QueryTerm>,
+}
+
+impl Query {
+    /// Parse a filter expression; anything that is not a known keyword matches the name
+    pub fn parse(expression: &str) -> Self {
+        let terms = expression
+            .split_whitespace()
+            .map(|word| parse_term(&word.to_lowercase()))
+            .collect();
+        Query {
+            expression: expression.trim().to_string(),
+            terms,
+        }
+    }
+
+    /// The expression as typed
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// The parsed terms
+    pub fn terms(&self) -> &[QueryTerm] {
+        &self.terms
+    }
+
+    /// The name text terms, lowercased
+    pub fn text_terms(&self) -> Vec<&str> {
+        self.terms
+            .iter()
+            .filter_map(|term| match term {
+                QueryTerm::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether the query has any name text to match
+    pub fn has_text(&self) -> bool {
+        self.terms.iter().any(|term| matches!(term, QueryTerm::Text(_)))
+    }
+
+    /// Whether the query needs episode facts from the database
+    pub fn needs_facts(&self) -> bool {
+        self.terms.iter().any(|term| !matches!(term, QueryTerm::Text(_)))
+    }
+
+    /// Whether every entry matching this query also matches `previous`, as when characters are
+    /// typed onto the end of it, so the entries `previous` matched can be narrowed down instead
+    pub fn narrows(&self, previous: &Query) -> bool {
+        previous.terms.iter().all(|old| {
+            self.terms.iter().any(|new| match (old, new) {
+                (QueryTerm::Text(old), QueryTerm::Text(new)) => new.contains(old.as_str()),
+                _ => old == new,
+            })
+        })
+    }
+
+    /// Check the non-text terms against one episode
+    pub fn matches_episode(&self, facts: &EpisodeFacts) -> bool {
+        self.terms.iter().all(|term| match term {
+            QueryTerm::Text(_) => true,
+            QueryTerm::Watched(watched) => facts.watched == *watched,
+            QueryTerm::ShorterThan(limit) => facts.length.is_some_and(|length| length > 0 && length < *limit),
+            QueryTerm::LongerThan(limit) => facts.length.is_some_and(|length| length > *limit),
+        })
+    }
+
+    /// Check the text terms against an entry's display name and any alternate titles
+    pub fn matches_name(&self, entry: &Entry, alt_titles: Option<&AltTitles>) -> bool {
+        let mut names = vec![match entry {
+            Entry::Series { name, .. } | Entry::Episode { name, .. } => name.to_lowercase(),
+            Entry::Season { number, .. } => format!("season {}", number),
+        }];
+        names.extend(alt_titles.into_iter().flat_map(|titles| titles.values().map(|t| t.to_lowercase())));
+        self.terms.iter().all(|term| match term {
+            QueryTerm::Text(text) => names.iter().any(|name| name.contains(text.as_str())),
+            _ => true,
+        })
+    }
+
+    /// Entries matching the query
+    /// A series or season matches a non-text term when any of its episodes does
+    pub fn matching_keys(&self, facts: &[EpisodeFacts]) -> HashSet<EntryKey> {
+        let mut keys = HashSet::new();
+        for episode in facts.iter().filter(|episode| self.matches_episode(episode)) {
+            keys.insert(EntryKey::Episode(episode.episode_id));
+            keys.extend(episode.series_id.map(EntryKey::Series));
+            keys.extend(episode.season_id.map(EntryKey::Season));
+        }
+        keys
+    }
+}
+
+fn parse_term(word: &str) -> QueryTerm {
+    match word {
+        "watched" => return QueryTerm::Watched(true),
+        "unwatched" => return QueryTerm::Watched(false),
+        _ => {}
+    }
+    if let Some(limit) = word.strip_prefix('<').and_then(parse_minutes) {
+        return QueryTerm::ShorterThan(limit);
+    }
+    if let Some(limit) = word.strip_prefix('>').and_then(parse_minutes) {
+        return QueryTerm::LongerThan(limit);
+    }
+    QueryTerm::Text(word.to_string())
+}
+
+/// Parse `30`, `30m`, `30min`, or `2h` into seconds
+fn parse_minutes(value: &str) -> Option<u64> {
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => value.split_at(index),
+        None => (value, ""),
+    };
+    let number: u64 = number.parse().ok()?;
+    match unit {
+        "" | "m" | "min" | "mins" => Some(number * 60),
+        "h" | "hr" | "hrs" => Some(number * 3600),
+        _ => None,
+    }
+}
+
+/// Character ranges of `text` that contain one of the lowercased terms, sorted and merged
+/// Used to highlight why a list entry matched the filter
+pub fn match_ranges(text: &str, terms: &[&str]) -> Vec<Range<usize>> {
+    let lowercase = |s: &str| -> Vec<char> { s.chars().map(|c| c.to_lowercase().next().unwrap_or(c)).collect() };
+    let haystack = lowercase(text);
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for term in terms {
+        let needle = lowercase(term);
+        if needle.is_empty() || needle.len() > haystack.len() {
+            continue;
+        }
+        for start in 0..=haystack.len() - needle.len() {
+            if haystack[start..start + needle.len()] == needle[..] {
+                ranges.push(start..start + needle.len());
+            }
+        }
+    }
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Keep the entries that match every query
+pub fn filter_entries(
+    entries: &[Entry],
+    queries: &[&Query],
+    facts: &[EpisodeFacts],
+    alt_titles: &HashMap<usize, AltTitles>,
+) -> Vec<Entry> {
+    let matches = matcher(queries, facts, alt_titles);
+    entries.iter().filter(|entry| matches(entry)).cloned().collect()
+}
+
+/// Check for an entry matching every query
+fn matcher<'a>(
+    queries: &'a [&Query],
+    facts: &[EpisodeFacts],
+    alt_titles: &'a HashMap<usize, AltTitles>,
+) -> impl Fn(&Entry) -> bool + 'a {
+    let keys: Vec<Option<HashSet<EntryKey>>> = queries
+        .iter()
+        .map(|query| query.needs_facts().then(|| query.matching_keys(facts)))
+        .collect();
+    move |entry| {
+        let titles = match entry {
+            Entry::Episode { episode_id, .. } => alt_titles.get(episode_id),
+            _ => None,
+        };
+        queries.iter().zip(&keys).all(|(query, keys)| {
+            query.matches_name(entry, titles) && keys.as_ref().is_none_or(|keys| keys.contains(&entry.key()))
+        })
+    }
+}
+
+/// Entries matched by the last filter, kept while the filter is typed
+///
+/// Each character typed onto the filter can only narrow what it matches, so the last result
+/// is filtered again instead of the whole list. Anything else, such as a deleted character,
+/// filters the whole list. The cache has to be cleared when the entries themselves change.
+#[derive(Debug, Clone, Default)]
+pub struct FilterCache {
+    queries: Vec<Query>,
+    matches: Option<Vec<Entry>>,
+}
+
+impl FilterCache {
+    /// Entries matching every query, before preferred titles and sorting are applied
+    pub fn filter(
+        &mut self,
+        entries: &[Entry],
+        queries: &[&Query],
+        facts: &[EpisodeFacts],
+        alt_titles: &HashMap<usize, AltTitles>,
+    ) -> &[Entry] {
+        let narrows = self.queries.len() == queries.len()
+            && queries.iter().zip(&self.queries).all(|(query, previous)| query.narrows(previous));
+        match self.matches.as_mut() {
+            Some(previous) if narrows => {
+                let matches = matcher(queries, facts, alt_titles);
+                previous.retain(|entry| matches(entry));
+            }
+            _ => self.matches = Some(filter_entries(entries, queries, facts, alt_titles)),
+        }
+        self.queries = queries.iter().map(|query| (*query).clone()).collect();
+        self.matches.as_deref().unwrap_or_default()
+    }
+
+    /// Filter the whole list next time
+    pub fn clear(&mut self) {
+        self.matches = None;
+    }
+}
+
+/// Filters pinned from the filter line, switched on and off for the session with the number keys
+#[derive(Debug, Clone, Default)]
+pub struct PinnedFilters {
+    filters: Vec<Query>,
+    active: Vec<bool>,
+}
+
+impl PinnedFilters {
+    /// Load pinned expressions, all switched off
+    pub fn new(expressions: &[String]) -> Self {
+        let filters: Vec<Query> = expressions
+            .iter()
+            .map(|expression| Query::parse(expression))
+            .filter(|query| !query.terms().is_empty())
+            .take(MAX_PINNED_FILTERS)
+            .collect();
+        let active = vec![false; filters.len()];
+        PinnedFilters { filters, active }
+    }
+
+    /// The pinned expressions, for saving
+    pub fn expressions(&self) -> Vec<String> {
+        self.filters.iter().map(|query| query.expression().to_string()).collect()
+    }
+
+    /// Pin an expression, returning its slot number (1-based)
+    pub fn pin(&mut self, expression: &str) -> Result<usize, String> {
+        let query = Query::parse(expression);
+        if query.terms().is_empty() {
+            return Err("Type a filter with [/] before pinning it".to_string());
+        }
+        if let Some(index) = self.filters.iter().position(|pinned| pinned.expression() == query.expression()) {
+            return Err(format!("Filter is already pinned to [{}]", index + 1));
+        }
+        if self.filters.len() >= MAX_PINNED_FILTERS {
+            return Err(format!("Only {} filters can be pinned; clear the pins first", MAX_PINNED_FILTERS));
+        }
+        self.filters.push(query);
+        self.active.push(false);
+        Ok(self.filters.len())
+    }
+
+    /// Remove every pin
+    pub fn clear(&mut self) {
+        self.filters.clear();
+        self.active.clear();
+    }
+
+    /// Switch the pin in `slot` (1-based) on or off, returning whether it is now on
+    pub fn toggle(&mut self, slot: usize) -> Option<bool> {
+        let active = self.active.get_mut(slot.checked_sub(1)?)?;
+        *active = !*active;
+        Some(*active)
+    }
+
+    /// The pin in `slot` (1-based)
+    pub fn get(&self, slot: usize) -> Option<&Query> {
+        self.filters.get(slot.checked_sub(1)?)
+    }
+
+    /// Pins that are switched on
+    pub fn active_queries(&self) -> Vec<&Query> {
+        self.filters
+            .iter()
+            .zip(&self.active)
+            .filter(|(_, active)| **active)
+            .map(|(query, _)| query)
+            .collect()
+    }
+
+    /// Header text listing the pins, with the active ones marked
+    pub fn label(&self) -> String {
+        self.filters
+            .iter()
+            .zip(&self.active)
+            .enumerate()
+            .map(|(index, (query, active))| {
+                format!("[{}]{}{}", index + 1, if *active { "*" } else { " " }, query.expression())
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+}
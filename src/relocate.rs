@@ -0,0 +1,97 @@
+//! Pointing the library at its root folder after the folder has moved
+//!
+//! The new folder is checked first by looking for a sample of the episodes' relative paths
+//! under it. Moving then puts the database in the new folder, copying the open one unless the
+//! folder already has its own, and only once that database opens is the config changed to it.
+
+use crate::logger;
+use rusqlite::{Connection, OpenFlags};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Most episodes looked for under the new root before moving the library there
+pub const RELOCATION_SAMPLE_SIZE: usize = 20;
+
+/// What was found when looking for the library's videos under a new root folder
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelocationCheck {
+    pub new_root: PathBuf,
+    /// Episodes looked for, and how many of them were there
+    pub sampled: usize,
+    pub found: usize,
+    /// Relative paths of the sampled episodes that weren't there
+    pub missing: Vec<String>,
+    /// The new root already has a database, which is used instead of a copy of the open one
+    pub has_database: bool,
+}
+
+impl RelocationCheck {
+    /// True when most of the sample was found, or the library has no episodes to look for
+    pub fn looks_right(&self) -> bool {
+        self.found * 2 > self.sampled || self.sampled == 0
+    }
+
+    /// One line telling how the check went
+    pub fn summary(&self) -> String {
+        if self.sampled == 0 {
+            return "No episodes to look for; the library can be moved".to_string();
+        }
+        format!("Found {} of {} sampled videos under {}", self.found, self.sampled, self.new_root.display())
+    }
+}
+
+/// Up to `size` of `locations`, spread evenly from the first to the last
+pub fn sample_locations(locations: &[String], size: usize) -> Vec<&String> {
+    if locations.len() <= size {
+        return locations.iter().collect();
+    }
+    (0..size).map(|index| &locations[index * locations.len() / size]).collect()
+}
+
+/// Look for a sample of the episodes at `locations` under `new_root`, where `database_name` is
+/// the file name of the library's database
+/// Locations that aren't relative belong to other folders of the library and are left out
+pub fn check_relocation(new_root: &Path, locations: &[String], database_name: &str) -> Result<RelocationCheck, String> {
+    if !new_root.is_dir() {
+        return Err(format!("Not a folder: {}", new_root.display()));
+    }
+    let new_root = new_root.canonicalize().unwrap_or_else(|_| new_root.to_path_buf());
+    let relative: Vec<String> = locations.iter().filter(|location| Path::new(location).is_relative()).cloned().collect();
+    let sample = sample_locations(&relative, RELOCATION_SAMPLE_SIZE);
+    let missing: Vec<String> = sample.iter().filter(|location| !new_root.join(crate::location::decode(location)).exists()).map(|location| location.to_string()).collect();
+    Ok(RelocationCheck {
+        sampled: sample.len(),
+        found: sample.len() - missing.len(),
+        missing,
+        has_database: new_root.join(database_name).is_file(),
+        new_root,
+    })
+}
+
+/// Put the database at `db_path` into `new_root`, unless one is already there, and return the
+/// path of the database to use from now on with whether it was copied
+///
+/// The copy is written next to its final name and renamed into place, so a failed copy leaves nothing behind.
+pub fn move_database(db_path: &Path, new_root: &Path) -> Result<(PathBuf, bool), Box<dyn Error>> {
+    let name = db_path.file_name().ok_or_else(|| format!("Not a database file: {}", db_path.display()))?;
+    let new_db_path = new_root.join(name);
+    if new_db_path.is_file() {
+        logger::log_info(&format!("Using the database already in {}", new_root.display()));
+        return Ok((new_db_path, false));
+    }
+    let mut temp_name = name.to_os_string();
+    temp_name.push(".tmp");
+    let temp = new_root.join(temp_name);
+    let _ = std::fs::remove_file(&temp);
+
+    // VACUUM INTO writes a consistent copy while the library is still open
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    if let Err(e) = conn.execute("VACUUM INTO ?1", [temp.to_string_lossy().as_ref()]) {
+        let _ = std::fs::remove_file(&temp);
+        return Err(e.into());
+    }
+    drop(conn);
+    std::fs::rename(&temp, &new_db_path)?;
+    logger::log_info(&format!("Copied the database from {} to {}", db_path.display(), new_db_path.display()));
+    Ok((new_db_path, true))
+}
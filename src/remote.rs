@@ -0,0 +1,256 @@
+//! Remote control of the running instance through a Unix domain socket
+//!
+//! With `remote_control` on, the app listens on a socket, by default `movies.sock` in the data
+//! directory. Clients write one command per line and get one line back for each, `ok ...` or
+//! `error ...`:
+//!
+//! - `play <episode id>` plays the episode, as Enter on it would
+//! - `toggle-watched <episode id>` marks the episode watched or unwatched
+//! - `rescan` rescans the library in the background
+//! - `status` answers `ok playing <file>` or `ok idle`
+//!
+//! Every client is also sent `event ...` lines as things happen: `event playing <file>`,
+//! `event stopped` and `event scan-finished <videos imported>`. Commands are carried out by the
+//! main loop, which gets them as `AppEvent::Remote`.
+//!
+//! Lines for a client are queued for a thread of its own that writes them, so a client that stops
+//! reading never holds up the main loop; once its queue is full it is disconnected.
+
+use crate::util::AppEvent;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+/// File name of the socket in the data directory
+pub const SOCKET_FILE_NAME: &str = "movies.sock";
+
+/// How long a client waits for the main loop to carry out its command
+pub const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Lines waiting to be written to a client before it counts as stuck and is dropped
+pub const CLIENT_QUEUE_LINES: usize = 64;
+
+/// How long writing one line to a client may take before it is disconnected
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A command a client can send
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteCommand {
+    Play(usize),
+    ToggleWatched(usize),
+    Rescan,
+    Status,
+}
+
+/// A command read from a client, with the channel its reply goes back through
+#[derive(Debug)]
+pub struct RemoteRequest {
+    pub command: RemoteCommand,
+    pub reply: Sender<Result<String, String>>,
+}
+
+/// Parse a line sent by a client, such as "play 42"
+pub fn parse_command(line: &str) -> Result<RemoteCommand, String> {
+    let mut words = line.split_whitespace();
+    let name = words.next().unwrap_or_default().to_lowercase();
+    let argument = words.next();
+    if words.next().is_some() {
+        return Err(format!("too many arguments to '{}'", name));
+    }
+    let episode_id = || {
+        let argument = argument.ok_or_else(|| format!("'{}' needs an episode id", name))?;
+        argument.parse::<usize>().map_err(|_| format!("'{}' is not an episode id", argument))
+    };
+    let command = match name.as_str() {
+        "play" => RemoteCommand::Play(episode_id()?),
+        "toggle-watched" => RemoteCommand::ToggleWatched(episode_id()?),
+        "rescan" => RemoteCommand::Rescan,
+        "status" => RemoteCommand::Status,
+        "" => return Err("empty command".to_string()),
+        _ => return Err(format!("unknown command '{}'; use play, toggle-watched, rescan or status", name)),
+    };
+    if argument.is_some() && matches!(command, RemoteCommand::Rescan | RemoteCommand::Status) {
+        return Err(format!("'{}' takes no arguments", name));
+    }
+    Ok(command)
+}
+
+/// Line sent back for the result of a command
+pub fn format_reply(result: &Result<String, String>) -> String {
+    match result {
+        Ok(message) if message.is_empty() => "ok".to_string(),
+        Ok(message) => format!("ok {}", message),
+        Err(message) => format!("error {}", message),
+    }
+}
+
+/// Default location of the socket
+pub fn default_socket_path() -> Option<PathBuf> {
+    crate::paths::data_dir().map(|dir| dir.join(SOCKET_FILE_NAME))
+}
+
+/// Each connected client by id, with the queue of lines its writer thread sends it
+#[cfg(unix)]
+type Clients = std::sync::Mutex<Vec<(usize, std::sync::mpsc::SyncSender<String>)>>;
+
+/// The listening socket, removed when dropped
+pub struct RemoteServer {
+    path: PathBuf,
+    #[cfg(unix)]
+    clients: std::sync::Arc<Clients>,
+}
+
+#[cfg(unix)]
+impl RemoteServer {
+    /// Listen on `path`, sending the commands read to the main loop through `tx`
+    ///
+    /// A socket left behind by an instance that is gone is replaced; one that another
+    /// instance is still listening on is an error.
+    pub fn start(path: &Path, tx: Sender<AppEvent>) -> io::Result<Self> {
+        use std::os::unix::net::{UnixListener, UnixStream};
+        use std::sync::{Arc, Mutex};
+
+        if path.exists() {
+            if UnixStream::connect(path).is_ok() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AddrInUse,
+                    format!("another instance is listening on {}", path.display()),
+                ));
+            }
+            std::fs::remove_file(path)?;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let listener = UnixListener::bind(path)?;
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let server = RemoteServer { path: path.to_path_buf(), clients: clients.clone() };
+
+        std::thread::Builder::new().name("remote".to_string()).spawn(move || {
+            for (client_id, stream) in listener.incoming().enumerate() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        crate::logger::log_warn(&format!("Failed to accept a remote control client: {}", e));
+                        continue;
+                    }
+                };
+                match stream.try_clone() {
+                    Ok(writer) => {
+                        let (lines, queue) = std::sync::mpsc::sync_channel(CLIENT_QUEUE_LINES);
+                        lock(&clients).push((client_id, lines));
+                        std::thread::spawn(move || write_lines(writer, queue));
+                    }
+                    Err(e) => {
+                        crate::logger::log_warn(&format!("Failed to accept a remote control client: {}", e));
+                        continue;
+                    }
+                }
+                let clients = clients.clone();
+                let tx = tx.clone();
+                std::thread::spawn(move || {
+                    serve_client(client_id, stream, &clients, &tx);
+                    lock(&clients).retain(|(id, _)| *id != client_id);
+                });
+            }
+        })?;
+        Ok(server)
+    }
+
+    /// Queue `event` for every client as an "event ..." line, dropping the ones that are gone or stuck
+    /// Never waits on a client
+    pub fn emit(&self, event: &str) {
+        let line = format!("event {}\n", event);
+        lock(&self.clients).retain(|(_, lines)| lines.try_send(line.clone()).is_ok());
+    }
+}
+
+#[cfg(not(unix))]
+impl RemoteServer {
+    /// Unix domain sockets are only available on Unix
+    pub fn start(path: &Path, _tx: Sender<AppEvent>) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("remote control needs Unix domain sockets, so {} can't be used here", path.display()),
+        ))
+    }
+
+    pub fn emit(&self, _event: &str) {}
+}
+
+impl RemoteServer {
+    /// Where the socket is
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for RemoteServer {
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.path).ok();
+    }
+}
+
+/// Read the commands of one client until it disconnects or is dropped, queueing a reply line for each
+#[cfg(unix)]
+fn serve_client(
+    client_id: usize,
+    stream: std::os::unix::net::UnixStream,
+    clients: &Clients,
+    tx: &Sender<AppEvent>,
+) {
+    use std::io::{BufRead, BufReader};
+
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let result = match parse_command(&line) {
+            Ok(command) => {
+                let (reply, replies) = std::sync::mpsc::channel();
+                if tx.send(AppEvent::Remote(RemoteRequest { command, reply })).is_err() {
+                    break;
+                }
+                replies
+                    .recv_timeout(REPLY_TIMEOUT)
+                    .unwrap_or_else(|_| Err("the app didn't answer in time".to_string()))
+            }
+            Err(message) => Err(message),
+        };
+        // Replies and events share the client's queue, so they are written one whole line at a time
+        let line = format!("{}\n", format_reply(&result));
+        let queued = lock(clients)
+            .iter()
+            .find(|(id, _)| *id == client_id)
+            .is_some_and(|(_, lines)| lines.try_send(line).is_ok());
+        if !queued {
+            break;
+        }
+    }
+}
+
+/// Write the lines queued for a client until it is dropped or a write fails, then disconnect it
+#[cfg(unix)]
+fn write_lines(mut stream: std::os::unix::net::UnixStream, queue: std::sync::mpsc::Receiver<String>) {
+    use std::io::Write;
+
+    if let Err(e) = stream.set_write_timeout(Some(WRITE_TIMEOUT)) {
+        crate::logger::log_warn(&format!("Failed to set a write timeout for a remote control client: {}", e));
+    }
+    for line in queue {
+        if stream.write_all(line.as_bytes()).is_err() {
+            break;
+        }
+    }
+    // Also ends the client's `serve_client`, which takes it out of the list
+    stream.shutdown(std::net::Shutdown::Both).ok();
+}
+
+#[cfg(unix)]
+fn lock<T>(mutex: &std::sync::Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
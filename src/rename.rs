@@ -0,0 +1,98 @@
+//! Renaming an episode's file after the details typed in the editor
+//!
+//! The new name comes from `rename_template` for episodes in a series and from
+//! `rename_movie_template` for the others. Placeholders are `{series}`, `{season}`,
+//! `{episode}`, `{title}`, `{year}` and `{ext}`, and a number can be padded with zeros as in
+//! `{season:02}`. A `/` in a template puts the file in a folder below the one it is in now.
+
+use std::path::{Component, Path, PathBuf};
+
+/// An episode's file with the path it would be renamed to, shown before the rename is applied
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenamePreview {
+    pub episode_id: usize,
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// Values put in the rename template for an episode
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenameFields {
+    pub series: Option<String>,
+    pub season: Option<usize>,
+    pub episode: Option<usize>,
+    pub title: String,
+    pub year: String,
+    /// Extension of the file now, without the dot
+    pub ext: String,
+}
+
+/// Characters that can't be in a file name on some system, and what takes their place
+fn safe_name(value: &str) -> String {
+    value
+        .replace(':', " -")
+        .replace(['/', '\\', '*', '?', '"', '<', '>', '|'], "-")
+        .trim()
+        .to_string()
+}
+
+/// Fill in `template` with `fields`, returning the path relative to the file's folder
+///
+/// Values are made safe for file names, a missing season or episode number counts as 0, and
+/// an unknown placeholder or a path leaving the folder is an error.
+pub fn render_template(template: &str, fields: &RenameFields) -> Result<PathBuf, String> {
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            return Err(format!("Unclosed {{ in the rename template: {}", template));
+        };
+        let placeholder = &rest[start + 1..start + end];
+        let (name, width) = match placeholder.split_once(':') {
+            Some((name, width)) => (name, Some(width)),
+            None => (placeholder, None),
+        };
+        let value = match name {
+            "series" => safe_name(fields.series.as_deref().unwrap_or_default()),
+            "season" => fields.season.unwrap_or(0).to_string(),
+            "episode" => fields.episode.unwrap_or(0).to_string(),
+            "title" => safe_name(&fields.title),
+            "year" => safe_name(&fields.year),
+            "ext" => fields.ext.clone(),
+            _ => return Err(format!("Unknown placeholder {{{}}} in the rename template", placeholder)),
+        };
+        match width.map(|width| width.parse::<usize>()) {
+            None => rendered.push_str(&value),
+            Some(Ok(width)) => rendered.push_str(&format!("{:0>width$}", value, width = width)),
+            Some(Err(_)) => return Err(format!("Bad width in {{{}}} in the rename template", placeholder)),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    rendered.push_str(rest);
+
+    let path = PathBuf::from(rendered.trim());
+    let stays_in_folder = path.components().all(|component| matches!(component, Component::Normal(_)));
+    if path.as_os_str().is_empty() || !stays_in_folder {
+        return Err(format!("The rename template has to name a file in the episode's folder: {}", template));
+    }
+    Ok(path)
+}
+
+/// Path the file at `from` would be renamed to with `template`
+pub fn rename_target(from: &Path, template: &str, fields: &RenameFields) -> Result<PathBuf, String> {
+    let folder = from.parent().ok_or_else(|| format!("Not a file: {}", from.display()))?;
+    Ok(folder.join(render_template(template, fields)?))
+}
+
+/// Move the file at `from` to `to`, creating the folders a template put in its path
+/// Refuses to replace a file that is already there
+pub fn rename_file(from: &Path, to: &Path) -> Result<(), String> {
+    if to.exists() {
+        return Err(format!("There is already a file at {}", to.display()));
+    }
+    if let Some(folder) = to.parent() {
+        std::fs::create_dir_all(folder).map_err(|e| format!("Failed to create {}: {}", folder.display(), e))?;
+    }
+    std::fs::rename(from, to).map_err(|e| format!("Failed to rename {}: {}", from.display(), e))
+}
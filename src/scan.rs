@@ -0,0 +1,565 @@
+use crate::config::{Config, ScanRules};
+use crate::database::{Database, DatabaseError};
+use crate::extras::{self, SampleFiles};
+use crate::folder_series::SeriesProposal;
+use crate::location;
+use crate::logger;
+use crate::path_resolver::{self, PathResolver, PathResolverError};
+use crate::util::AppEvent;
+use crate::video_metadata;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Shortest time between progress events, so a fast scan doesn't flood the main loop
+pub const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Spinner frames shown in front of the progress line
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Width of the progress bar while video lengths are read
+const BAR_WIDTH: usize = 20;
+
+/// Set while a background rescan is running, so a second one isn't started on top of it
+static SCAN_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Set to ask the running background rescan to stop at the next file
+static SCAN_CANCEL: AtomicBool = AtomicBool::new(false);
+
+/// How far a rescan has got
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScanProgress {
+    /// Files looked at so far, videos or not
+    pub scanned: usize,
+    /// New videos added to the library
+    pub imported: usize,
+    /// Video lengths read so far, out of `lengths_total`; both zero while files are still being walked
+    pub lengths_done: usize,
+    pub lengths_total: usize,
+}
+
+impl ScanProgress {
+    /// Status line for the progress, with the spinner at `frame`
+    /// Walking the files has no known end, so only reading lengths shows a bar
+    pub fn status_line(&self, frame: usize) -> String {
+        let spinner = SPINNER[frame % SPINNER.len()];
+        let counts = format!("Scanned {} files, imported {}", self.scanned, self.imported);
+        if self.lengths_total == 0 {
+            return format!("{} {}", spinner, counts);
+        }
+        let filled = BAR_WIDTH * self.lengths_done.min(self.lengths_total) / self.lengths_total;
+        format!(
+            "{} {}. Reading video lengths {}{} {}/{}",
+            spinner,
+            counts,
+            "█".repeat(filled),
+            "░".repeat(BAR_WIDTH - filled),
+            self.lengths_done,
+            self.lengths_total
+        )
+    }
+}
+
+/// Why a rescan left a file out of the library
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkipReason {
+    /// Not under the library root or another folder of the library
+    OutsideRoot,
+    /// Matched one of the `scan_exclude` patterns
+    Excluded,
+    /// Couldn't be read or added to the library, with the error
+    Unreadable(String),
+    /// Another hard link to a file found earlier at this path
+    SameFile(PathBuf),
+    /// A sample or trailer, by its name or its length, with `sample_files: skip`
+    Sample,
+}
+
+impl SkipReason {
+    pub fn label(&self) -> String {
+        match self {
+            SkipReason::OutsideRoot => "outside the library folders".to_string(),
+            SkipReason::Excluded => "excluded by scan_exclude".to_string(),
+            SkipReason::Unreadable(error) => format!("unreadable: {}", error),
+            SkipReason::SameFile(first) => format!("same file as {}", first.display()),
+            SkipReason::Sample => "sample or trailer".to_string(),
+        }
+    }
+}
+
+/// A file a rescan left out, and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: SkipReason,
+}
+
+/// What a finished rescan found
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanReport {
+    pub root: PathBuf,
+    pub scanned: usize,
+    pub imported: usize,
+    /// Videos added to the library, in the order they were found
+    pub imported_files: Vec<PathBuf>,
+    /// Videos and folders left out, in the order they were found
+    pub skipped: Vec<SkippedFile>,
+    /// Imported videos flagged as extras because they look like samples or trailers
+    pub extras: Vec<PathBuf>,
+    /// Episodes whose file wasn't found, outside missing library folders
+    pub missing_files: Vec<PathBuf>,
+    /// Video lengths read for episodes that had none
+    pub extracted: usize,
+    /// Extensions of videos whose length couldn't be read, sorted
+    pub unsupported_extensions: Vec<String>,
+    /// Stopped before the end; videos imported until then are kept
+    pub cancelled: bool,
+    /// Other folders of the library that were offline, such as an unplugged drive
+    pub missing_roots: Vec<PathBuf>,
+    /// Episodes to put in the series their folders suggest, once the report is confirmed
+    pub series_proposals: Vec<SeriesProposal>,
+    /// The one folder of the library that was scanned, None for the whole library
+    pub folder: Option<PathBuf>,
+}
+
+impl ScanReport {
+    /// One-line summary for the status line
+    pub fn summary(&self) -> String {
+        let scan = match &self.folder {
+            Some(folder) => format!("Scan of {}", folder.strip_prefix(&self.root).unwrap_or(folder).display()),
+            None => "Rescan".to_string(),
+        };
+        if self.cancelled {
+            format!("{} cancelled. Found {} new videos", scan, self.imported)
+        } else if self.imported > 0 {
+            format!("{} complete. Found {} new videos", scan, self.imported)
+        } else {
+            format!("{} complete. No new videos found", scan)
+        }
+    }
+
+    /// Lines of the report screen: the counts, then a section for each list that isn't empty
+    /// Section titles start the line and their items are indented; paths under the root are shown relative to it
+    pub fn lines(&self) -> Vec<String> {
+        let show = |path: &Path| path.strip_prefix(&self.root).unwrap_or(path).display().to_string();
+        let mut lines = vec![format!("{}. Scanned {} files", self.summary(), self.scanned)];
+        if self.extracted > 0 {
+            lines.push(format!("Read {} video lengths", self.extracted));
+        }
+        let mut section = |title: &str, items: Vec<String>| {
+            if !items.is_empty() {
+                lines.push(String::new());
+                lines.push(format!("{} ({})", title, items.len()));
+                lines.extend(items.into_iter().map(|item| format!("  {}", item)));
+            }
+        };
+        section("Imported", self.imported_files.iter().map(|path| show(path)).collect());
+        section("Imported as extras", self.extras.iter().map(|path| show(path)).collect());
+        section(
+            "Skipped",
+            self.skipped.iter().map(|skipped| format!("{}  ({})", show(&skipped.path), skipped.reason.label())).collect(),
+        );
+        section("Missing files", self.missing_files.iter().map(|path| show(path)).collect());
+        section("Library folders offline", self.missing_roots.iter().map(|path| show(path)).collect());
+        section(
+            "Formats whose length couldn't be read",
+            self.unsupported_extensions.iter().map(|extension| format!(".{}", extension)).collect(),
+        );
+        section(
+            "Series from folder names",
+            self.series_proposals
+                .iter()
+                .map(|proposal| format!("{}  → {}, season {}", proposal.location, proposal.series, proposal.season))
+                .collect(),
+        );
+        if !self.series_proposals.is_empty() {
+            lines.push("Enter puts these episodes in their series, Esc leaves them as they are".to_string());
+        }
+        lines
+    }
+}
+
+/// Files with several hard links seen during a scan, by device and inode
+#[derive(Debug, Default)]
+pub struct HardLinks {
+    seen: HashMap<(u64, u64), PathBuf>,
+}
+
+impl HardLinks {
+    /// The path a file was first seen at, when `path` is another hard link to it
+    /// Always None where hard links can't be told apart
+    pub fn earlier_link(&mut self, path: &Path) -> Option<PathBuf> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let metadata = std::fs::metadata(path).ok().filter(|metadata| metadata.nlink() > 1)?;
+            let first = self.seen.entry((metadata.dev(), metadata.ino())).or_insert_with(|| path.to_path_buf());
+            (first != path).then(|| first.clone())
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (&self.seen, path);
+            None
+        }
+    }
+}
+
+/// When the next automatic rescan is due, from `rescan_interval_minutes`
+#[derive(Debug, Clone, Copy)]
+pub struct RescanSchedule {
+    /// None when automatic rescans are off
+    interval: Option<Duration>,
+    /// When the interval started counting
+    since: Instant,
+}
+
+impl RescanSchedule {
+    /// A rescan every `interval_minutes` counted from `now`, or never when 0
+    pub fn new(interval_minutes: u64, now: Instant) -> Self {
+        let interval = (interval_minutes > 0).then(|| Duration::from_secs(interval_minutes * 60));
+        RescanSchedule { interval, since: now }
+    }
+
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.interval.is_some_and(|interval| now.duration_since(self.since) >= interval)
+    }
+
+    /// Count the interval again from `now`, after any rescan finishes or one is started
+    pub fn restart(&mut self, now: Instant) {
+        self.since = now;
+    }
+}
+
+/// Rescan the library root and its other folders on a background thread, each with its own scan rules
+/// Progress and the final report go to the main loop over `tx`
+/// Returns false without starting when a rescan is already running
+pub fn spawn_rescan(
+    root: PathBuf,
+    config: &Config,
+    database: Arc<Database>,
+    resolver: PathResolver,
+    tx: Sender<AppEvent>,
+) -> bool {
+    let rules_for = library_rules(config, &root, &resolver);
+    spawn_scan(tx, move |on_progress| rescan(&root, rules_for, &database, &resolver, &SCAN_CANCEL, on_progress))
+}
+
+/// The scan rules of each folder of the library with its root override applied, for `rescan`
+/// Resolved up front so the rescan thread doesn't need the config; any other folder gets the root's rules
+pub fn library_rules(config: &Config, root: &Path, resolver: &PathResolver) -> impl Fn(&Path) -> ScanRules + Send + 'static {
+    let root_rules = config.scan_rules_for(root);
+    let folder_rules: Vec<(PathBuf, ScanRules)> = resolver
+        .extra_roots()
+        .iter()
+        .map(|extra| (extra.path.clone(), config.scan_rules_for(&extra.path)))
+        .collect();
+    move |folder: &Path| {
+        folder_rules
+            .iter()
+            .find(|(path, _)| path == folder)
+            .map_or_else(|| root_rules.clone(), |(_, rules)| rules.clone())
+    }
+}
+
+/// Scan one folder of the library on a background thread, like `spawn_rescan`
+pub fn spawn_folder_rescan(
+    folder: PathBuf,
+    rules: ScanRules,
+    database: Arc<Database>,
+    resolver: PathResolver,
+    tx: Sender<AppEvent>,
+) -> bool {
+    spawn_scan(tx, move |on_progress| rescan_folder(&folder, &rules, &database, &resolver, &SCAN_CANCEL, on_progress))
+}
+
+/// Run `scan` on a background thread, unless one is already running
+fn spawn_scan(
+    tx: Sender<AppEvent>,
+    scan: impl FnOnce(&mut dyn FnMut(ScanProgress)) -> ScanReport + Send + 'static,
+) -> bool {
+    if SCAN_RUNNING.swap(true, Ordering::SeqCst) {
+        return false;
+    }
+    SCAN_CANCEL.store(false, Ordering::SeqCst);
+    thread::spawn(move || {
+        let progress_tx = tx.clone();
+        let report = scan(&mut |progress| {
+            let _ = progress_tx.send(AppEvent::ScanProgress(progress));
+        });
+        SCAN_RUNNING.store(false, Ordering::SeqCst);
+        let _ = tx.send(AppEvent::ScanFinished(Box::new(report)));
+    });
+    true
+}
+
+/// Ask the background rescan to stop
+/// Returns false when no rescan is running
+pub fn cancel_rescan() -> bool {
+    if !SCAN_RUNNING.load(Ordering::SeqCst) {
+        return false;
+    }
+    SCAN_CANCEL.store(true, Ordering::SeqCst);
+    true
+}
+
+/// Import new videos under `root` and the other folders of the library, and read the lengths of episodes without one
+/// Each folder is walked with `rules_for(folder)`, so root overrides apply to it
+/// A folder that isn't there is skipped and listed in the report, leaving its episodes in the library
+/// `on_progress` is called at most once per PROGRESS_INTERVAL, and when reading lengths starts
+/// Setting `cancel` stops the scan before the next file
+pub fn rescan(
+    root: &Path,
+    rules_for: impl Fn(&Path) -> ScanRules,
+    database: &Database,
+    resolver: &PathResolver,
+    cancel: &AtomicBool,
+    on_progress: impl FnMut(ScanProgress),
+) -> ScanReport {
+    let extra_roots = resolver.extra_roots().iter().map(|extra| extra.path.as_path());
+    let folders: Vec<(&Path, &Path, ScanRules)> = std::iter::once(root)
+        .chain(extra_roots)
+        .map(|folder| (folder, folder, rules_for(folder)))
+        .collect();
+    scan_folders(root, &folders, None, database, resolver, cancel, on_progress)
+}
+
+/// Like `rescan`, for the videos under `folder` of the library only
+/// Only episodes under the folder are looked for in the report's missing files
+pub fn rescan_folder(
+    folder: &Path,
+    rules: &ScanRules,
+    database: &Database,
+    resolver: &PathResolver,
+    cancel: &AtomicBool,
+    on_progress: impl FnMut(ScanProgress),
+) -> ScanReport {
+    let root = resolver.get_root_dir();
+    let library_folder = resolver.root_of(folder).unwrap_or(folder);
+    scan_folders(root, &[(library_folder, folder, rules.clone())], Some(folder), database, resolver, cancel, on_progress)
+}
+
+/// True when `a` and `b` are the same file, even when one of them goes through a symbolic link
+fn same_path(a: &Path, b: &Path) -> bool {
+    a == b || a.canonicalize().ok().is_some_and(|a| b.canonicalize().is_ok_and(|b| a == b))
+}
+
+/// Flag the just imported video at `index` of `imported_files` as an extra, or with `Skip`
+/// take it back out of the library, after its length showed it to be a sample
+fn set_aside_sample(
+    database: &Database,
+    episode_id: usize,
+    index: usize,
+    samples: SampleFiles,
+    imported_files: &mut Vec<PathBuf>,
+    extras: &mut Vec<PathBuf>,
+    skipped: &mut Vec<SkippedFile>,
+) {
+    if samples == SampleFiles::Skip {
+        match database.delete_episode(episode_id) {
+            Ok(()) => {
+                let path = imported_files.remove(index);
+                skipped.push(SkippedFile { path, reason: SkipReason::Sample });
+            }
+            Err(e) => logger::log_warn(&format!("Failed to remove sample {}: {}", imported_files[index].display(), e)),
+        }
+    } else {
+        match database.set_extra(episode_id, true) {
+            Ok(()) => extras.push(imported_files[index].clone()),
+            Err(e) => logger::log_warn(&format!("Failed to flag {} as an extra: {}", imported_files[index].display(), e)),
+        }
+    }
+}
+
+/// Walk each (library folder, folder to walk, rules) entry of `folders`; the library folder is what
+/// `scan_exclude` patterns are matched against, and a missing one after the first is skipped
+/// Lengths read afterwards go by the rules of the folder the episode is under, the first one's otherwise
+fn scan_folders(
+    root: &Path,
+    folders: &[(&Path, &Path, ScanRules)],
+    only_folder: Option<&Path>,
+    database: &Database,
+    resolver: &PathResolver,
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(ScanProgress),
+) -> ScanReport {
+    logger::log_info(&format!("Rescan started: {}", only_folder.unwrap_or(root).display()));
+    let mut progress = ScanProgress::default();
+    let mut last_sent = Instant::now();
+    let mut report_progress = |progress: ScanProgress, force: bool| {
+        if force || last_sent.elapsed() >= PROGRESS_INTERVAL {
+            last_sent = Instant::now();
+            on_progress(progress);
+        }
+    };
+    report_progress(progress, true);
+
+    let mut cancelled = false;
+    let mut imported_files = Vec::new();
+    let mut skipped = Vec::new();
+    let mut extras = Vec::new();
+    let mut missing_roots = Vec::new();
+    let mut hard_links = HardLinks::default();
+    'roots: for (index, (library_folder, folder, rules)) in folders.iter().enumerate() {
+        if index > 0 && path_resolver::is_offline(folder) {
+            logger::log_warn(&format!("Library folder offline, skipping it: {}", folder.display()));
+            missing_roots.push(folder.to_path_buf());
+            continue;
+        }
+        for entry in rules.walk(folder) {
+            if cancel.load(Ordering::SeqCst) {
+                cancelled = true;
+                break 'roots;
+            }
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    logger::log_warn(&format!("Skipping unreadable path: {}", e));
+                    if let Some(path) = e.path() {
+                        let reason = SkipReason::Unreadable(e.io_error().map(|io| io.to_string()).unwrap_or_else(|| e.to_string()));
+                        skipped.push(SkippedFile { path: path.to_path_buf(), reason });
+                    }
+                    continue;
+                }
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            progress.scanned += 1;
+            if rules.accepts(entry.path()) {
+                let same_file = if rules.dedupe_hardlinks { hard_links.earlier_link(entry.path()) } else { None };
+                if rules.excludes(library_folder, entry.path()) {
+                    skipped.push(SkippedFile { path: entry.into_path(), reason: SkipReason::Excluded });
+                } else if let Some(first) = same_file {
+                    skipped.push(SkippedFile { path: entry.into_path(), reason: SkipReason::SameFile(first) });
+                } else if rules.samples == SampleFiles::Skip && extras::is_sample_name(entry.path()) {
+                    skipped.push(SkippedFile { path: entry.into_path(), reason: SkipReason::Sample });
+                } else {
+                    let location = location::encode(entry.path());
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    match database.import_episode_relative(&location, &name, resolver) {
+                        Ok(true) => {
+                            progress.imported += 1;
+                            if rules.samples == SampleFiles::Flag && extras::is_sample_name(entry.path()) {
+                                match database.set_extra_location(entry.path(), true, resolver) {
+                                    Ok(()) => extras.push(entry.path().to_path_buf()),
+                                    Err(e) => logger::log_warn(&format!("Failed to flag {} as an extra: {}", entry.path().display(), e)),
+                                }
+                            }
+                            imported_files.push(entry.into_path());
+                        }
+                        Ok(false) => {}
+                        Err(e) => {
+                            logger::log_warn(&format!("Skipping file: {} - {}", location, e));
+                            let reason = match e {
+                                DatabaseError::Path(PathResolverError::PathNotUnderRoot(_)) => SkipReason::OutsideRoot,
+                                other => SkipReason::Unreadable(other.to_string()),
+                            };
+                            skipped.push(SkippedFile { path: entry.into_path(), reason });
+                        }
+                    }
+                }
+            }
+            report_progress(progress, false);
+        }
+    }
+
+    if cancelled {
+        logger::log_info(&format!("Rescan cancelled after {} files: imported {} new videos", progress.scanned, progress.imported));
+    } else if progress.imported > 0 {
+        logger::log_info(&format!("Rescan completed: imported {} new videos", progress.imported));
+    } else {
+        logger::log_info("Rescan completed: no new videos found");
+    }
+    if !skipped.is_empty() {
+        logger::log_info(&format!("Rescan skipped {} files", skipped.len()));
+    }
+
+    // Episodes whose file is gone, leaving out those under a folder that isn't there at all
+    let mut missing_files = Vec::new();
+    if !cancelled {
+        match database.get_episode_locations() {
+            Ok(locations) => {
+                for location in locations {
+                    let path = resolver.to_absolute(&location::decode(&location));
+                    let in_scan = only_folder.is_none_or(|folder| path.starts_with(folder));
+                    if in_scan && !path.exists() && !missing_roots.iter().any(|folder| path.starts_with(folder)) {
+                        missing_files.push(path);
+                    }
+                }
+            }
+            Err(e) => logger::log_warn(&format!("Failed to query episode locations: {}", e)),
+        }
+        if !missing_files.is_empty() {
+            logger::log_warn(&format!("Rescan found {} episodes whose file is missing", missing_files.len()));
+        }
+    }
+
+    // Extract missing lengths for episodes with NULL or 0 length
+    // A cancelled scan leaves them for the next rescan
+    // Videos imported by this scan that turn out to be short enough are samples too
+    let rules_of = |path: &Path| {
+        let (_, _, rules) = folders.iter().find(|(library_folder, _, _)| path.starts_with(library_folder)).unwrap_or(&folders[0]);
+        rules
+    };
+    let mut extracted = 0;
+    let mut unsupported = BTreeSet::new();
+    if !cancelled {
+        match database.get_episodes_with_missing_length() {
+            Ok(episodes) => {
+                progress.lengths_total = episodes.len();
+                report_progress(progress, true);
+                for (episode_id, relative_location) in episodes {
+                    if cancel.load(Ordering::SeqCst) {
+                        cancelled = true;
+                        logger::log_info("Rescan cancelled while reading video lengths");
+                        break;
+                    }
+                    let absolute_path = resolver.to_absolute(&location::decode(&relative_location));
+                    match video_metadata::extract_and_update_episode_length(database, episode_id, &absolute_path) {
+                        Ok(seconds) => {
+                            extracted += 1;
+                            let rules = rules_of(&absolute_path);
+                            let short_is_sample = rules.samples != SampleFiles::Import && rules.sample_max_seconds > 0;
+                            if short_is_sample && extras::is_sample_length(seconds, rules.sample_max_seconds) {
+                                let imported_now = imported_files.iter().position(|path| same_path(path, &absolute_path));
+                                if let Some(index) = imported_now.filter(|index| !extras.contains(&imported_files[*index])) {
+                                    set_aside_sample(database, episode_id, index, rules.samples, &mut imported_files, &mut extras, &mut skipped);
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            if e.to_string().contains("Unsupported video format:") {
+                                if let Some(ext) = absolute_path.extension().and_then(|e| e.to_str()) {
+                                    unsupported.insert(ext.to_lowercase());
+                                }
+                            }
+                        }
+                    }
+                    progress.lengths_done += 1;
+                    report_progress(progress, false);
+                }
+            }
+            Err(e) => logger::log_warn(&format!("Failed to query episodes with missing length: {}", e)),
+        }
+    }
+
+    ScanReport {
+        root: root.to_path_buf(),
+        scanned: progress.scanned,
+        imported: imported_files.len(),
+        imported_files,
+        skipped,
+        extras,
+        missing_files,
+        extracted,
+        unsupported_extensions: unsupported.into_iter().collect(),
+        cancelled,
+        missing_roots,
+        series_proposals: Vec::new(),
+        folder: only_folder.map(Path::to_path_buf),
+    }
+}
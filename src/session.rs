@@ -0,0 +1,175 @@
+use crate::app_state::AppState;
+use crate::database::Database;
+use crate::digest::DigestTarget;
+use crate::handlers;
+use crate::logger;
+use crate::util::ViewContext;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File name of the last session in the data directory
+pub const SESSION_FILE_NAME: &str = "session.json";
+
+/// What Browse opens on, set by `startup_view`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartupView {
+    TopLevel,
+    /// The view, filter and selection left at the last exit
+    LastSession,
+    /// The view of the episode played last, at the next unwatched episode
+    ContinueWatching,
+    /// The series with this name
+    Series(String),
+}
+
+impl StartupView {
+    /// Parse a `startup_view` config value such as "continue_watching" or "series:The Office"
+    pub fn from_config(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some((key, name)) = value.split_once(':') {
+            let name = name.trim();
+            return (key.trim().eq_ignore_ascii_case("series") && !name.is_empty())
+                .then(|| StartupView::Series(name.to_string()));
+        }
+        match value.to_lowercase().replace(['-', ' '], "_").as_str() {
+            "top_level" | "library" => Some(StartupView::TopLevel),
+            "last_session" | "" => Some(StartupView::LastSession),
+            "continue_watching" => Some(StartupView::ContinueWatching),
+            _ => None,
+        }
+    }
+}
+
+/// Where Browse was left when the program last exited
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub view_context: ViewContext,
+    #[serde(default)]
+    pub filter: String,
+    #[serde(default)]
+    pub current_item: usize,
+    #[serde(default)]
+    pub first_entry: usize,
+}
+
+impl Session {
+    /// The view, filter, selection and scroll of `state`
+    pub fn from_state(state: &AppState) -> Self {
+        Self {
+            view_context: state.view_context.clone(),
+            filter: state.search.clone(),
+            current_item: state.current_item,
+            first_entry: state.first_entry,
+        }
+    }
+
+    /// Go back to the saved view in `state`
+    /// A series or season that has been emptied or removed since leaves the library on screen
+    /// The selection is clamped once the filtered list is drawn
+    pub fn restore(self, state: &mut AppState, database: &Database) {
+        let entries = match database.get_entries_for_view(&self.view_context) {
+            Ok(entries) if !entries.is_empty() || self.view_context == ViewContext::TopLevel => entries,
+            Ok(_) => {
+                logger::log_info("The view of the last session is empty, staying in the library");
+                return;
+            }
+            Err(e) => {
+                logger::log_warn(&format!("Failed to restore the last session: {}", e));
+                return;
+            }
+        };
+        state.filtered_entries = entries.clone();
+        state.entries = entries;
+        state.view_context = self.view_context;
+        state.search = self.filter;
+        state.current_item = self.current_item;
+        state.first_entry = self.first_entry.min(self.current_item);
+        state.redraw = true;
+    }
+}
+
+/// Default location of the last session
+pub fn default_session_path() -> Option<PathBuf> {
+    crate::paths::data_dir().map(|dir| dir.join(SESSION_FILE_NAME))
+}
+
+/// Load the last session, None when there is none or it can't be read
+pub fn load_session(path: &Path) -> Option<Session> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Write the session to disk
+pub fn save_session(session: &Session, path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(session).map_err(io::Error::other)?;
+    std::fs::write(path, json)
+}
+
+/// Open `view` in `state` when the program starts, staying in the library when it can't be opened
+///
+/// The last session is only restored while `restore_session` is on.
+pub fn open_startup_view(view: StartupView, state: &mut AppState, database: &Database, restore_session: bool) {
+    let target = match view {
+        StartupView::TopLevel => return,
+        StartupView::LastSession => {
+            if let Some(session) = default_session_path().and_then(|path| load_session(&path)).filter(|_| restore_session) {
+                session.restore(state, database);
+            }
+            return;
+        }
+        StartupView::ContinueWatching => match database.get_last_watched_episode() {
+            Ok(Some(episode_id)) => DigestTarget::Episode(episode_id),
+            Ok(None) => return,
+            Err(e) => {
+                logger::log_warn(&format!("Failed to find the episode played last: {}", e));
+                return;
+            }
+        },
+        StartupView::Series(name) => {
+            let series = database.get_all_series().unwrap_or_default();
+            match series.into_iter().find(|series| series.name.eq_ignore_ascii_case(&name)) {
+                Some(series) => DigestTarget::Series(series.id),
+                None => {
+                    state.notifications.warn(format!("startup_view: there is no series named '{}'", name));
+                    return;
+                }
+            }
+        }
+    };
+
+    let mut entries = Vec::new();
+    let mut view_context = ViewContext::TopLevel;
+    match handlers::jump_to_digest_item(database, target, &mut entries, &mut view_context) {
+        Ok(index) => {
+            // Continue at the first episode of the series or season not watched yet, from the one played last
+            let next = match target {
+                DigestTarget::Episode(_) if view_context != ViewContext::TopLevel => {
+                    handlers::find_first_unwatched_index(database, &entries[index..]).map(|offset| index + offset)
+                }
+                _ => None,
+            };
+            state.filtered_entries = entries.clone();
+            state.entries = entries;
+            state.view_context = view_context;
+            state.current_item = next.unwrap_or(index);
+            state.first_entry = 0;
+            state.redraw = true;
+        }
+        Err(e) => logger::log_warn(&format!("Failed to open the startup view: {}", e)),
+    }
+}
+
+/// Save where Browse is in `state` as the last session, logging a failure
+pub fn save_last_session(state: &AppState) {
+    let Some(path) = default_session_path() else {
+        return;
+    };
+    if let Err(e) = save_session(&Session::from_state(state), &path) {
+        logger::log_warn(&format!("Failed to save the session: {}", e));
+    }
+}
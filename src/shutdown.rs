@@ -0,0 +1,105 @@
+//! Quitting cleanly when the app is told to stop with SIGTERM or SIGINT
+//!
+//! A signal is turned into an `AppEvent::Shutdown` for the main loop, which saves the
+//! session and returns the same way quitting with Esc does, so the terminal is restored
+//! and `finish` runs. Should the main loop not get to it in time, or a second signal
+//! arrive, the listener restores the terminal and exits by itself.
+
+use crate::logger;
+use crate::terminal;
+use crate::usage;
+use crate::util::AppEvent;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+/// How long the main loop gets to stop after a signal before the process exits without it
+pub const SHUTDOWN_GRACE: Duration = Duration::from_secs(3);
+
+/// Start a thread waiting for SIGTERM and SIGINT, sent to the main loop through `tx`
+pub fn listen_for_signals(tx: Sender<AppEvent>) {
+    let spawned = thread::Builder::new().name("signals".to_string()).spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                logger::log_warn(&format!("Failed to listen for signals: {}", e));
+                return;
+            }
+        };
+        let exit_code = runtime.block_on(async move {
+            let mut signals = match Signals::new() {
+                Ok(signals) => signals,
+                Err(e) => {
+                    logger::log_warn(&format!("Failed to listen for signals: {}", e));
+                    return None;
+                }
+            };
+            let (name, exit_code) = signals.recv().await;
+            tx.send(AppEvent::Shutdown(name)).ok();
+            // Normally the process is gone before either of these happens
+            if tokio::time::timeout(SHUTDOWN_GRACE, signals.recv()).await.is_err() {
+                logger::log_warn(&format!("Received {}, but the main loop didn't stop in time", name));
+            }
+            Some(exit_code)
+        });
+        if let Some(exit_code) = exit_code {
+            terminal::restore_terminal().ok();
+            finish();
+            std::process::exit(exit_code);
+        }
+    });
+    if let Err(e) = spawned {
+        logger::log_warn(&format!("Failed to listen for signals: {}", e));
+    }
+}
+
+/// Last steps before the process exits: save the usage counters and flush the log
+pub fn finish() {
+    usage::finish_session();
+    logger::log_info("Application stopped");
+    logger::flush_logger();
+}
+
+/// SIGTERM and SIGINT, where the platform has them
+#[cfg(unix)]
+struct Signals {
+    terminate: tokio::signal::unix::Signal,
+    interrupt: tokio::signal::unix::Signal,
+}
+
+#[cfg(unix)]
+impl Signals {
+    fn new() -> std::io::Result<Self> {
+        use tokio::signal::unix::{signal, SignalKind};
+        Ok(Signals {
+            terminate: signal(SignalKind::terminate())?,
+            interrupt: signal(SignalKind::interrupt())?,
+        })
+    }
+
+    /// Wait for the next signal, returning its name and the exit code it is conventionally left with
+    async fn recv(&mut self) -> (&'static str, i32) {
+        tokio::select! {
+            _ = self.terminate.recv() => ("SIGTERM", 143),
+            _ = self.interrupt.recv() => ("SIGINT", 130),
+        }
+    }
+}
+
+/// Only Ctrl+C (as the console's interrupt) on other platforms
+#[cfg(not(unix))]
+struct Signals;
+
+#[cfg(not(unix))]
+impl Signals {
+    fn new() -> std::io::Result<Self> {
+        Ok(Signals)
+    }
+
+    async fn recv(&mut self) -> (&'static str, i32) {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+        ("SIGINT", 130)
+    }
+}
@@ -5,7 +5,9 @@ use crossterm::{
     execute,
     terminal,
 };
+use std::fs;
 use std::io::{self, Write};
+use std::path::Path;
 use unicode_width::UnicodeWidthStr;
 
 /// ASCII art representation of "movies" in script font style
@@ -32,6 +34,18 @@ pub fn get_ascii_art() -> &'static str {
     ASCII_ART
 }
 
+/// Reads the ASCII art at `path` to show in place of the logo
+pub fn load_ascii_art(path: &Path) -> Result<String, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Splash art {} can't be read ({}); the logo is shown instead", path.display(), e))?;
+    // Blank lines around the art would push it off center
+    let art = content.trim_start_matches(['\n', '\r']).trim_end();
+    if art.is_empty() {
+        return Err(format!("Splash art {} is empty; the logo is shown instead", path.display()));
+    }
+    Ok(art.to_string())
+}
+
 /// Renders the splash screen with ASCII art and press key prompt
 pub fn render_splash(
     ascii_art: &str,
@@ -89,8 +103,8 @@ pub fn render_splash(
     Ok(())
 }
 
-/// Displays the splash screen and waits for user input
-pub fn show_splash_screen() -> Result<(), Box<dyn std::error::Error>> {
+/// Displays the splash screen with `ascii_art` and waits for any key
+pub fn show_splash_screen(ascii_art: &str) -> Result<(), Box<dyn std::error::Error>> {
     let mut stdout = io::stdout();
     
     // Clear terminal and hide cursor
@@ -105,7 +119,7 @@ pub fn show_splash_screen() -> Result<(), Box<dyn std::error::Error>> {
     
     // Render the splash screen
     render_splash(
-        get_ascii_art(),
+        ascii_art,
         PRESS_KEY_PROMPT,
         terminal_width,
         terminal_height,
@@ -0,0 +1,345 @@
+use crate::database::Database;
+use crate::export::LibraryRecord;
+use crate::location;
+use crate::logger::log_info;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::path::{Component, Path};
+
+/// Jellyfin keeps playback positions in ticks of 100 nanoseconds
+const TICKS_PER_SECOND: u64 = 10_000_000;
+
+/// Positions closer than this many seconds are taken as the same
+const POSITION_TOLERANCE: u64 = 10;
+
+/// Server address, API key and the user whose watched state is synced
+pub struct JellyfinCredentials {
+    pub url: String,
+    pub api_key: String,
+    /// User name; None for the first user of the server
+    pub user: Option<String>,
+}
+
+/// A movie or episode on the server with the user's watched state
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JellyfinItem {
+    pub id: String,
+    pub name: String,
+    /// Path of the file on the server, which need not be where the library has it
+    pub path: Option<String>,
+    pub year: Option<i64>,
+    pub series: Option<String>,
+    pub season: Option<usize>,
+    pub episode_number: Option<i64>,
+    pub played: bool,
+    pub position_seconds: u64,
+    pub last_played: Option<String>,
+}
+
+/// Read the items of an /Items response
+pub fn parse_items(response: &Value) -> Vec<JellyfinItem> {
+    let text = |item: &Value, key: &str| item[key].as_str().map(str::to_string).filter(|value| !value.is_empty());
+    response["Items"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            Some(JellyfinItem {
+                id: text(item, "Id")?,
+                name: text(item, "Name").unwrap_or_default(),
+                path: text(item, "Path"),
+                year: item["ProductionYear"].as_i64(),
+                series: text(item, "SeriesName"),
+                season: item["ParentIndexNumber"].as_u64().map(|number| number as usize),
+                episode_number: item["IndexNumber"].as_i64(),
+                played: item["UserData"]["Played"].as_bool().unwrap_or(false),
+                position_seconds: item["UserData"]["PlaybackPositionTicks"].as_u64().unwrap_or(0) / TICKS_PER_SECOND,
+                last_played: text(&item["UserData"], "LastPlayedDate"),
+            })
+        })
+        .collect()
+}
+
+/// Components of a path, lowercased, from the file name up
+fn reversed_components(path: &str) -> Vec<String> {
+    // Server paths may come from Windows, whatever this machine is
+    let path = path.replace('\\', "/");
+    Path::new(&path)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().to_lowercase()),
+            _ => None,
+        })
+        .rev()
+        .collect()
+}
+
+/// Pair each local record with the server item for the same video
+///
+/// The library keeps no TMDb or IMDb ids to compare, so an item is found by its path first:
+/// the server path has to end with the record's path inside the library. Items whose path
+/// doesn't match, as when the server sees the files under other folder names, are matched
+/// like Trakt's by series, season and episode number, or by title and year. Returns the pairs
+/// and the number of server items left unmatched.
+pub fn match_items<'a>(records: &'a [LibraryRecord], items: &'a [JellyfinItem]) -> (Vec<(&'a LibraryRecord, &'a JellyfinItem)>, usize) {
+    let local_paths: Vec<Vec<String>> = records
+        .iter()
+        .map(|record| reversed_components(&location::decode(&record.location).to_string_lossy()))
+        .collect();
+    let mut taken = vec![false; records.len()];
+    let mut pairs = Vec::new();
+    let mut unmatched = 0;
+
+    for item in items {
+        let by_path = item.path.as_deref().map(reversed_components).and_then(|remote| {
+            let mut found = (0..records.len())
+                .filter(|index| !taken[*index] && !local_paths[*index].is_empty() && remote.starts_with(&local_paths[*index]));
+            // Two library files with the same path can't be told apart
+            match (found.next(), found.next()) {
+                (Some(index), None) => Some(index),
+                _ => None,
+            }
+        });
+        let by_details = || {
+            (0..records.len()).find(|index| {
+                let record = &records[*index];
+                !taken[*index]
+                    && match (&item.series, &record.series) {
+                        (Some(series), Some(local)) => {
+                            series.to_lowercase() == local.to_lowercase()
+                                && item.season.is_some()
+                                && record.season == item.season
+                                && item.episode_number.is_some()
+                                && record.episode_number == item.episode_number
+                        }
+                        (None, None) => {
+                            record.title.to_lowercase() == item.name.to_lowercase()
+                                && (record.year.is_none() || item.year.is_none() || record.year == item.year)
+                        }
+                        _ => false,
+                    }
+            })
+        };
+        match by_path.or_else(by_details) {
+            Some(index) => {
+                taken[index] = true;
+                pairs.push((&records[index], item));
+            }
+            None => unmatched += 1,
+        }
+    }
+    (pairs, unmatched)
+}
+
+/// A change that brings one side in line with the other
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncChange {
+    /// Watched on the server: mark it watched here
+    WatchedLocally { location: String, title: String, watched_at: Option<String> },
+    /// Watched here: mark it played on the server
+    PlayedRemotely { item_id: String, title: String },
+    /// Further along on the server: resume from there here
+    PositionLocally { location: String, title: String, seconds: u64 },
+    /// Further along here: resume from here on the server
+    PositionRemotely { item_id: String, title: String, seconds: u64 },
+}
+
+impl SyncChange {
+    /// The change as it is written to the log
+    pub fn describe(&self) -> String {
+        match self {
+            SyncChange::WatchedLocally { title, .. } => format!("{}: mark watched here", title),
+            SyncChange::PlayedRemotely { title, .. } => format!("{}: mark played on Jellyfin", title),
+            SyncChange::PositionLocally { title, seconds, .. } => format!("{}: resume here at {}", title, format_position(*seconds)),
+            SyncChange::PositionRemotely { title, seconds, .. } => {
+                format!("{}: resume on Jellyfin at {}", title, format_position(*seconds))
+            }
+        }
+    }
+}
+
+fn format_position(seconds: u64) -> String {
+    format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+/// The changes of a sync, worked out before any of them is made
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncPlan {
+    pub changes: Vec<SyncChange>,
+    /// Server items matching nothing in the library
+    pub unmatched: usize,
+}
+
+impl SyncPlan {
+    /// One-line summary suitable for the status bar; a dry run says what would change
+    pub fn summary(&self, dry_run: bool) -> String {
+        let count = |matches: fn(&SyncChange) -> bool| self.changes.iter().filter(|change| matches(change)).count();
+        let played = count(|change| matches!(change, SyncChange::PlayedRemotely { .. }));
+        let watched = count(|change| matches!(change, SyncChange::WatchedLocally { .. }));
+        let positions = count(|change| {
+            matches!(change, SyncChange::PositionLocally { .. } | SyncChange::PositionRemotely { .. })
+        });
+        if dry_run {
+            format!(
+                "Jellyfin sync preview: {} to mark played on Jellyfin, {} to mark watched locally, {} positions to update, {} server items unmatched (details in the log)",
+                played, watched, positions, self.unmatched
+            )
+        } else {
+            format!(
+                "Jellyfin sync complete: {} marked played on Jellyfin, {} marked watched locally, {} positions updated, {} server items unmatched",
+                played, watched, positions, self.unmatched
+            )
+        }
+    }
+}
+
+/// Work out the changes that sync `records` with the server's `items` both ways
+///
+/// Watched wins over unwatched on either side, so nothing is ever marked unwatched. For
+/// videos unwatched on both sides the further playback position is copied to the other side.
+pub fn plan_sync(records: &[LibraryRecord], items: &[JellyfinItem]) -> SyncPlan {
+    let (pairs, unmatched) = match_items(records, items);
+    let mut changes = Vec::new();
+    for (record, item) in pairs {
+        let local_position = record.last_progress_time.unwrap_or(0).max(0) as u64;
+        match (record.watched, item.played) {
+            (true, false) => changes.push(SyncChange::PlayedRemotely { item_id: item.id.clone(), title: record.title.clone() }),
+            (false, true) => changes.push(SyncChange::WatchedLocally {
+                location: record.location.clone(),
+                title: record.title.clone(),
+                watched_at: item.last_played.clone(),
+            }),
+            (true, true) => {}
+            (false, false) if item.position_seconds > local_position + POSITION_TOLERANCE => {
+                changes.push(SyncChange::PositionLocally {
+                    location: record.location.clone(),
+                    title: record.title.clone(),
+                    seconds: item.position_seconds,
+                })
+            }
+            (false, false) if local_position > item.position_seconds + POSITION_TOLERANCE => {
+                changes.push(SyncChange::PositionRemotely {
+                    item_id: item.id.clone(),
+                    title: record.title.clone(),
+                    seconds: local_position,
+                })
+            }
+            (false, false) => {}
+        }
+    }
+    SyncPlan { changes, unmatched }
+}
+
+/// Requests to the server as the configured user
+struct Server {
+    client: reqwest::Client,
+    url: String,
+    api_key: String,
+    user_id: String,
+}
+
+impl Server {
+    async fn connect(credentials: &JellyfinCredentials) -> Result<Server, Box<dyn Error>> {
+        let mut server = Server {
+            client: reqwest::Client::new(),
+            url: credentials.url.trim().trim_end_matches('/').to_string(),
+            api_key: credentials.api_key.trim().to_string(),
+            user_id: String::new(),
+        };
+        let users: Value = server.get("/Users").await?;
+        let users = users.as_array().cloned().unwrap_or_default();
+        let user = match credentials.user.as_deref().map(str::trim) {
+            Some(name) => users
+                .iter()
+                .find(|user| user["Name"].as_str().is_some_and(|user| user.eq_ignore_ascii_case(name)))
+                .ok_or_else(|| format!("Jellyfin has no user named {}", name))?,
+            None => users.first().ok_or("Jellyfin has no users")?,
+        };
+        server.user_id = user["Id"].as_str().unwrap_or_default().to_string();
+        Ok(server)
+    }
+
+    async fn get(&self, path: &str) -> Result<Value, Box<dyn Error>> {
+        Ok(self
+            .client
+            .get(format!("{}{}", self.url, path))
+            .header("X-Emby-Token", &self.api_key)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    async fn post(&self, path: &str, body: Value) -> Result<(), Box<dyn Error>> {
+        self.client
+            .post(format!("{}{}", self.url, path))
+            .header("X-Emby-Token", &self.api_key)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    async fn items(&self) -> Result<Vec<JellyfinItem>, Box<dyn Error>> {
+        let response = self
+            .get(&format!(
+                "/Users/{}/Items?Recursive=true&IncludeItemTypes=Movie,Episode,Video&Fields=Path&EnableUserData=true",
+                self.user_id
+            ))
+            .await?;
+        Ok(parse_items(&response))
+    }
+}
+
+/// Sync watched state and playback positions with the server both ways, telling `progress` each
+/// step; a dry run only plans
+pub async fn sync(
+    database: &Database,
+    credentials: &JellyfinCredentials,
+    dry_run: bool,
+    progress: &dyn Fn(&str),
+) -> Result<SyncPlan, Box<dyn Error>> {
+    let server = Server::connect(credentials).await?;
+    progress("Jellyfin: fetching the library...");
+    let items = server.items().await?;
+    let records = database.get_library_records()?;
+    let plan = plan_sync(&records, &items);
+    for change in &plan.changes {
+        log_info(&format!("Jellyfin{}: {}", if dry_run { " preview" } else { "" }, change.describe()));
+    }
+    if dry_run {
+        return Ok(plan);
+    }
+
+    let mut watched = Vec::new();
+    let mut positions = Vec::new();
+    progress(&format!("Jellyfin: applying {} changes...", plan.changes.len()));
+    for change in &plan.changes {
+        match change {
+            SyncChange::WatchedLocally { location, watched_at, .. } => watched.push((location.clone(), watched_at.clone())),
+            SyncChange::PositionLocally { location, seconds, .. } => positions.push((location.clone(), *seconds)),
+            SyncChange::PlayedRemotely { item_id, .. } => {
+                server.post(&format!("/Users/{}/PlayedItems/{}", server.user_id, item_id), json!({})).await?
+            }
+            SyncChange::PositionRemotely { item_id, seconds, .. } => {
+                let body = json!({ "PlaybackPositionTicks": seconds * TICKS_PER_SECOND });
+                server.post(&format!("/Users/{}/Items/{}/UserData", server.user_id, item_id), body).await?
+            }
+        }
+    }
+    database.mark_locations_watched(&watched)?;
+    database.set_locations_progress(&positions)?;
+    Ok(plan)
+}
+
+/// Run the menu action on the sync thread, returning the status line to display, or the failure
+pub fn run_from_menu(database: &Database, credentials: JellyfinCredentials, dry_run: bool, progress: &dyn Fn(&str)) -> Result<String, String> {
+    progress("Connecting to Jellyfin...");
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| format!("Jellyfin sync failed: {}", e))?;
+    runtime
+        .block_on(sync(database, &credentials, dry_run, progress))
+        .map(|plan| plan.summary(dry_run))
+        .map_err(|e| format!("Jellyfin sync failed: {}", e))
+}
@@ -0,0 +1,55 @@
+// Synchronization with external watch-tracking services
+// Each integration lives in its own submodule behind a cargo feature
+
+#[cfg(feature = "trakt")]
+pub mod trakt;
+
+#[cfg(feature = "jellyfin")]
+pub mod jellyfin;
+
+use crate::util::AppEvent;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::thread;
+
+/// Set while a sync is running, so a second one isn't started on top of it
+static SYNC_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Run `sync` on a background thread, like `scan::spawn_rescan`, unless one is already running
+/// The lines it passes to its progress function go to the main loop as `AppEvent::SyncProgress`,
+/// and the status line it returns, or the failure, as `AppEvent::SyncFinished`
+#[cfg_attr(not(any(feature = "trakt", feature = "jellyfin")), allow(dead_code))]
+pub fn spawn(tx: Sender<AppEvent>, sync: impl FnOnce(&dyn Fn(&str)) -> Result<String, String> + Send + 'static) -> bool {
+    if SYNC_RUNNING.swap(true, Ordering::SeqCst) {
+        return false;
+    }
+    thread::spawn(move || {
+        let progress_tx = tx.clone();
+        let result = sync(&|line| {
+            let _ = progress_tx.send(AppEvent::SyncProgress(line.to_string()));
+        });
+        SYNC_RUNNING.store(false, Ordering::SeqCst);
+        let _ = tx.send(AppEvent::SyncFinished(result));
+    });
+    true
+}
+
+/// Outcome of a sync run with an external service
+#[cfg_attr(not(feature = "trakt"), allow(dead_code))]
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    pub pushed: usize,
+    pub pulled: usize,
+    pub unmatched: usize,
+}
+
+#[cfg_attr(not(feature = "trakt"), allow(dead_code))]
+impl SyncReport {
+    /// One-line summary suitable for the status bar
+    pub fn summary(&self, service: &str) -> String {
+        format!(
+            "{} sync complete: {} watched sent, {} marked watched locally, {} remote items unmatched",
+            service, self.pushed, self.pulled, self.unmatched
+        )
+    }
+}
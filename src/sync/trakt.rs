@@ -0,0 +1,365 @@
+use crate::database::Database;
+use crate::export::LibraryRecord;
+use crate::logger::{log_debug, log_error, log_info};
+use crate::sync::SyncReport;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const API_URL: &str = "https://api.trakt.tv";
+const TOKEN_FILE_NAME: &str = "trakt_token.json";
+const SYNC_STATE_FILE_NAME: &str = "trakt_sync.json";
+
+/// Code the user enters at the verification URL to authorize this app
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceCode {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_url: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+/// OAuth token persisted in the config directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraktToken {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: i64,
+    pub created_at: i64,
+}
+
+impl TraktToken {
+    /// Check whether the token has expired (with a one-day margin)
+    pub fn is_expired(&self) -> bool {
+        chrono::Utc::now().timestamp() >= self.created_at + self.expires_in - 86_400
+    }
+}
+
+/// API credentials of the user's Trakt application
+pub struct TraktCredentials {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+/// Location of the stored token in the config directory
+pub fn token_path() -> Option<PathBuf> {
+    crate::paths::config_dir().map(|dir| dir.join(TOKEN_FILE_NAME))
+}
+
+/// Load the stored token, if the user has authorized the app
+pub fn load_token() -> Option<TraktToken> {
+    let content = std::fs::read_to_string(token_path()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Persist the token to the config directory
+pub fn save_token(token: &TraktToken) -> Result<(), Box<dyn Error>> {
+    let path = token_path().ok_or("Failed to determine config directory")?;
+    std::fs::write(&path, serde_json::to_string_pretty(token)?)?;
+    log_info(&format!("Saved Trakt token to {}", path.display()));
+    Ok(())
+}
+
+/// Sync bookkeeping persisted next to the token
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncState {
+    /// RFC3339 time the last successful push started
+    last_push: Option<String>,
+}
+
+fn sync_state_path() -> Option<PathBuf> {
+    crate::paths::config_dir().map(|dir| dir.join(SYNC_STATE_FILE_NAME))
+}
+
+/// Load the time of the last successful push, if any
+fn load_last_push() -> Option<DateTime<Utc>> {
+    let content = std::fs::read_to_string(sync_state_path()?).ok()?;
+    let state: SyncState = serde_json::from_str(&content).ok()?;
+    parse_time(state.last_push.as_deref()?)
+}
+
+/// Remember when the last successful push started
+fn save_last_push(time: DateTime<Utc>) -> Result<(), Box<dyn Error>> {
+    let path = sync_state_path().ok_or("Failed to determine config directory")?;
+    let state = SyncState {
+        last_push: Some(time.to_rfc3339()),
+    };
+    std::fs::write(&path, serde_json::to_string_pretty(&state)?)?;
+    Ok(())
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|time| time.with_timezone(&Utc))
+}
+
+/// Start the device-code flow
+pub async fn request_device_code(credentials: &TraktCredentials) -> Result<DeviceCode, Box<dyn Error>> {
+    let response = reqwest::Client::new()
+        .post(format!("{}/oauth/device/code", API_URL))
+        .json(&json!({ "client_id": credentials.client_id }))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.json::<DeviceCode>().await?)
+}
+
+/// Poll until the user approves the device code, it expires, or it is denied
+pub async fn poll_for_token(
+    credentials: &TraktCredentials,
+    device_code: &DeviceCode,
+) -> Result<TraktToken, Box<dyn Error>> {
+    let client = reqwest::Client::new();
+    let deadline = std::time::Instant::now() + Duration::from_secs(device_code.expires_in);
+
+    while std::time::Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_secs(device_code.interval.max(1))).await;
+
+        let response = client
+            .post(format!("{}/oauth/device/token", API_URL))
+            .json(&json!({
+                "code": device_code.device_code,
+                "client_id": credentials.client_id,
+                "client_secret": credentials.client_secret,
+            }))
+            .send()
+            .await?;
+
+        match response.status().as_u16() {
+            200 => return Ok(response.json::<TraktToken>().await?),
+            // 400 means the user has not approved yet, 429 means we polled too fast
+            400 | 429 => log_debug("Trakt authorization pending"),
+            status => return Err(format!("Trakt authorization failed with status {}", status).into()),
+        }
+    }
+
+    Err("Trakt device code expired before it was approved".into())
+}
+
+/// Exchange a refresh token for a new access token
+pub async fn refresh_token(credentials: &TraktCredentials, token: &TraktToken) -> Result<TraktToken, Box<dyn Error>> {
+    let response = reqwest::Client::new()
+        .post(format!("{}/oauth/token", API_URL))
+        .json(&json!({
+            "refresh_token": token.refresh_token,
+            "client_id": credentials.client_id,
+            "client_secret": credentials.client_secret,
+            "redirect_uri": "urn:ietf:wg:oauth:2.0:oob",
+            "grant_type": "refresh_token",
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.json::<TraktToken>().await?)
+}
+
+/// Build the /sync/history payload from locally watched records
+/// With `since` set, only plays recorded after it are included, so earlier plays are not re-added
+/// Episodes with a series, season, and number are sent as shows; everything else as movies
+pub fn build_history_payload(records: &[LibraryRecord], since: Option<DateTime<Utc>>) -> serde_json::Value {
+    let mut movies = Vec::new();
+    let mut shows: HashMap<String, HashMap<usize, Vec<serde_json::Value>>> = HashMap::new();
+
+    let is_new = |record: &LibraryRecord| match since {
+        None => true,
+        Some(since) => record.last_watched_time.as_deref().and_then(parse_time).is_some_and(|time| time > since),
+    };
+
+    for record in records.iter().filter(|r| r.watched && is_new(r)) {
+        let watched_at = record.last_watched_time.clone();
+        match (&record.series, record.season, record.episode_number) {
+            (Some(series), Some(season), Some(episode)) => {
+                shows
+                    .entry(series.clone())
+                    .or_default()
+                    .entry(season)
+                    .or_default()
+                    .push(json!({ "number": episode, "watched_at": watched_at }));
+            }
+            _ => {
+                movies.push(json!({ "title": record.title, "year": record.year, "watched_at": watched_at }));
+            }
+        }
+    }
+
+    let shows: Vec<serde_json::Value> = shows
+        .into_iter()
+        .map(|(title, seasons)| {
+            let seasons: Vec<serde_json::Value> = seasons
+                .into_iter()
+                .map(|(number, episodes)| json!({ "number": number, "episodes": episodes }))
+                .collect();
+            json!({ "title": title, "seasons": seasons })
+        })
+        .collect();
+
+    json!({ "movies": movies, "shows": shows })
+}
+
+/// Match remote watched items to local records, returning the locations to mark watched
+pub fn match_remote_watched(
+    records: &[LibraryRecord],
+    movies: &serde_json::Value,
+    shows: &serde_json::Value,
+) -> (Vec<(String, Option<String>)>, usize) {
+    let mut matched = Vec::new();
+    let mut unmatched = 0;
+
+    for item in movies.as_array().into_iter().flatten() {
+        let title = item["movie"]["title"].as_str().unwrap_or_default().to_lowercase();
+        let year = item["movie"]["year"].as_i64();
+        let watched_at = item["last_watched_at"].as_str().map(String::from);
+        let found = records.iter().find(|r| {
+            r.series.is_none() && r.title.to_lowercase() == title && (r.year.is_none() || year.is_none() || r.year == year)
+        });
+        match found {
+            Some(record) => matched.push((record.location.clone(), watched_at)),
+            None => unmatched += 1,
+        }
+    }
+
+    for item in shows.as_array().into_iter().flatten() {
+        let title = item["show"]["title"].as_str().unwrap_or_default().to_lowercase();
+        for season in item["seasons"].as_array().into_iter().flatten() {
+            let season_number = season["number"].as_u64().map(|n| n as usize);
+            for episode in season["episodes"].as_array().into_iter().flatten() {
+                let episode_number = episode["number"].as_i64();
+                let watched_at = episode["last_watched_at"].as_str().map(String::from);
+                let found = records.iter().find(|r| {
+                    r.series.as_deref().map(str::to_lowercase).as_deref() == Some(title.as_str())
+                        && r.season == season_number
+                        && r.episode_number == episode_number
+                });
+                match found {
+                    Some(record) => matched.push((record.location.clone(), watched_at)),
+                    None => unmatched += 1,
+                }
+            }
+        }
+    }
+
+    (matched, unmatched)
+}
+
+/// Push local watched state to Trakt and pull remote watched state back, telling `progress` each step
+pub async fn sync(
+    database: &Database,
+    credentials: &TraktCredentials,
+    token: &TraktToken,
+    progress: &dyn Fn(&str),
+) -> Result<SyncReport, Box<dyn Error>> {
+    let client = reqwest::Client::new();
+    let authorized = |builder: reqwest::RequestBuilder| {
+        builder
+            .header("Content-Type", "application/json")
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", credentials.client_id.as_str())
+            .bearer_auth(&token.access_token)
+    };
+
+    let records = database.get_library_records()?;
+    let mut report = SyncReport::default();
+
+    // Push only what was watched since the last successful push
+    let push_started = Utc::now();
+    let payload = build_history_payload(&records, load_last_push());
+    let is_empty = |key: &str| payload[key].as_array().is_none_or(|items| items.is_empty());
+    if is_empty("movies") && is_empty("shows") {
+        log_info("Trakt push: nothing new to send");
+    } else {
+        progress("Trakt: sending what you watched...");
+        let response: serde_json::Value = authorized(client.post(format!("{}/sync/history", API_URL)))
+            .json(&payload)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        report.pushed = (response["added"]["movies"].as_u64().unwrap_or(0)
+            + response["added"]["episodes"].as_u64().unwrap_or(0)) as usize;
+        log_info(&format!("Trakt push: {} items added", report.pushed));
+    }
+    if let Err(e) = save_last_push(push_started) {
+        log_error(&format!("Failed to save Trakt sync state: {}", e));
+    }
+
+    // Pull
+    progress("Trakt: fetching what you watched elsewhere...");
+    let movies: serde_json::Value = authorized(client.get(format!("{}/sync/watched/movies", API_URL)))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let shows: serde_json::Value = authorized(client.get(format!("{}/sync/watched/shows", API_URL)))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let (matched, unmatched) = match_remote_watched(&records, &movies, &shows);
+    report.pulled = database.mark_locations_watched(&matched)?;
+    report.unmatched = unmatched;
+    log_info(&format!(
+        "Trakt pull: {} marked watched, {} remote items unmatched",
+        report.pulled, report.unmatched
+    ));
+
+    Ok(report)
+}
+
+/// Run the menu action on the sync thread: authorize on first use, otherwise sync
+/// Returns the status line to display, or the failure
+pub fn run_from_menu(database: &Database, credentials: TraktCredentials, progress: &dyn Fn(&str)) -> Result<String, String> {
+    progress("Syncing with Trakt...");
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| format!("Trakt sync failed: {}", e))?;
+
+    let token = match load_token() {
+        Some(token) if token.is_expired() => {
+            progress("Trakt: refreshing the login...");
+            let refreshed = runtime
+                .block_on(refresh_token(&credentials, &token))
+                .map_err(|e| format!("Trakt token refresh failed: {}", e))?;
+            if let Err(e) = save_token(&refreshed) {
+                log_error(&format!("Failed to save refreshed Trakt token: {}", e));
+            }
+            refreshed
+        }
+        Some(token) => token,
+        None => return start_authorization(runtime, credentials),
+    };
+
+    runtime
+        .block_on(sync(database, &credentials, &token, progress))
+        .map(|report| report.summary("Trakt"))
+        .map_err(|e| format!("Trakt sync failed: {}", e))
+}
+
+/// Request a device code, returning the line that tells the user where to enter it, and wait for
+/// approval on another thread
+fn start_authorization(runtime: tokio::runtime::Runtime, credentials: TraktCredentials) -> Result<String, String> {
+    let device_code = runtime
+        .block_on(request_device_code(&credentials))
+        .map_err(|e| format!("Trakt authorization failed: {}", e))?;
+
+    let message = format!(
+        "Trakt: visit {} and enter code {}, then run Trakt Sync again",
+        device_code.verification_url, device_code.user_code
+    );
+
+    std::thread::spawn(move || match runtime.block_on(poll_for_token(&credentials, &device_code)) {
+        Ok(token) => {
+            if let Err(e) = save_token(&token) {
+                log_error(&format!("Failed to save Trakt token: {}", e));
+            }
+        }
+        Err(e) => log_error(&format!("Trakt authorization failed: {}", e)),
+    });
+
+    Ok(message)
+}
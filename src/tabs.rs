@@ -0,0 +1,98 @@
+use crate::util::{SortMode, ViewContext};
+
+/// Number of tabs, one for each of Alt+1 to Alt+9
+pub const MAX_TABS: usize = 9;
+
+/// A Browse view pinned to a tab: where it is, how it is filtered and sorted, and what is selected
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tab {
+    pub view_context: ViewContext,
+    pub filter: String,
+    pub sort_mode: SortMode,
+    pub current_item: usize,
+    pub first_entry: usize,
+}
+
+impl Tab {
+    /// Name shown in the header: the series or season, or "Library", followed by the filter
+    pub fn label(&self) -> String {
+        let view = match &self.view_context {
+            ViewContext::TopLevel => "Library".to_string(),
+            ViewContext::Extras => "Extras".to_string(),
+            ViewContext::Series { series_name, .. } => series_name.clone(),
+            ViewContext::Season { series_name, season_number, .. } => format!("{} S{}", series_name, season_number),
+        };
+        if self.filter.trim().is_empty() {
+            view
+        } else {
+            format!("{} \"{}\"", view, self.filter.trim())
+        }
+    }
+}
+
+/// Views pinned to numbered tabs for the session, switched with Alt+1..9
+///
+/// The active tab follows the selection and filter while its view is on screen, so switching
+/// back to it returns to the same place. Leaving its view for another one makes no tab active.
+#[derive(Debug, Clone, Default)]
+pub struct Tabs {
+    tabs: Vec<Tab>,
+    active: Option<usize>,
+}
+
+impl Tabs {
+    /// Pin a view to the next free tab and make it active, returning its slot number (1-based)
+    pub fn pin(&mut self, tab: Tab) -> Result<usize, String> {
+        if let Some(index) = self
+            .tabs
+            .iter()
+            .position(|pinned| pinned.view_context == tab.view_context && pinned.filter.trim() == tab.filter.trim())
+        {
+            return Err(format!("This view is already pinned to tab {}", index + 1));
+        }
+        if self.tabs.len() >= MAX_TABS {
+            return Err(format!("Only {} tabs can be pinned; close one first", MAX_TABS));
+        }
+        self.tabs.push(tab);
+        self.active = Some(self.tabs.len() - 1);
+        Ok(self.tabs.len())
+    }
+
+    /// Switch to the tab in `slot` (1-based), first saving `current` into the active tab
+    /// Returns the view to show, or None when no tab is pinned there
+    pub fn switch_to(&mut self, slot: usize, current: Tab) -> Option<Tab> {
+        let index = slot.checked_sub(1).filter(|index| *index < self.tabs.len())?;
+        if let Some(active) = self.active {
+            self.tabs[active] = current;
+        }
+        self.active = Some(index);
+        Some(self.tabs[index].clone())
+    }
+
+    /// Unpin the active tab, returning its slot number (1-based)
+    pub fn close_active(&mut self) -> Option<usize> {
+        let index = self.active.take()?;
+        self.tabs.remove(index);
+        Some(index + 1)
+    }
+
+    /// Stop following the active tab once the view on screen is no longer its view
+    pub fn follow(&mut self, view_context: &ViewContext) {
+        if self.active.is_some_and(|index| self.tabs[index].view_context != *view_context) {
+            self.active = None;
+        }
+    }
+
+    /// Header text listing the tabs, with the active one marked
+    pub fn label(&self) -> String {
+        self.tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| {
+                let marker = if self.active == Some(index) { "*" } else { " " };
+                format!("[{}]{}{}", index + 1, marker, tab.label())
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+}
@@ -6,6 +6,20 @@ use crossterm::{
     ExecutableCommand,
 };
 use std::io::{self, stdout, Write};
+use std::sync::RwLock;
+
+/// Fixed screen size used instead of the terminal when rendering headlessly
+static HEADLESS_SIZE: RwLock<Option<(usize, usize)>> = RwLock::new(None);
+
+/// Render into the screen buffer only, at a fixed size, without touching the terminal
+pub fn set_headless(width: usize, height: usize) {
+    *HEADLESS_SIZE.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some((width, height));
+}
+
+/// The fixed size set by `set_headless`, if rendering headlessly
+pub fn headless_size() -> Option<(usize, usize)> {
+    *HEADLESS_SIZE.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
 
 pub fn initialize_terminal() -> io::Result<()> {
     // Request terminal resize before entering alternate screen
@@ -15,11 +29,17 @@ pub fn initialize_terminal() -> io::Result<()> {
     let mut stdout = stdout();
     stdout.execute(terminal::EnterAlternateScreen)?;
     terminal::enable_raw_mode()?;
-    stdout.execute(EnableMouseCapture)?;
     stdout.execute(cursor::Hide)?;
     Ok(())
 }
 
+/// Start receiving mouse events; left off by default so the terminal keeps its own text selection
+pub fn enable_mouse_capture() -> io::Result<()> {
+    let mut stdout = stdout();
+    stdout.execute(EnableMouseCapture)?;
+    Ok(())
+}
+
 pub fn restore_terminal() -> io::Result<()> {
     let mut stdout = stdout();
     stdout.execute(terminal::LeaveAlternateScreen)?;
@@ -29,7 +49,22 @@ pub fn restore_terminal() -> io::Result<()> {
     Ok(())
 }
 
+/// Take the terminal back after `restore_terminal` handed it to another program, such as an editor
+pub fn resume_terminal(mouse: bool) -> io::Result<()> {
+    let mut stdout = stdout();
+    stdout.execute(terminal::EnterAlternateScreen)?;
+    terminal::enable_raw_mode()?;
+    stdout.execute(cursor::Hide)?;
+    if mouse {
+        stdout.execute(EnableMouseCapture)?;
+    }
+    Ok(())
+}
+
 pub fn get_terminal_size() -> io::Result<(usize, usize)> {
+    if let Some(size) = headless_size() {
+        return Ok(size);
+    }
     let (cols, rows) = size()?;
     Ok((cols as usize, rows as usize))
 }
@@ -40,36 +75,59 @@ pub fn clear_screen() -> io::Result<()> {
     Ok(())
 }
 
-pub fn clear_line(row: usize) -> io::Result<()> {
-    move_cursor(0, row)?;
+/// Write an image escape sequence with its top-left corner at the given cell
+pub fn write_graphics(col: usize, row: usize, sequence: &str) -> io::Result<()> {
+    if headless_size().is_some() {
+        return Ok(());
+    }
+    // Keep the cursor where the frame left it, e.g. in the filter or an edit field
     let mut stdout = stdout();
-    execute!(stdout, Clear(ClearType::CurrentLine))?;
+    execute!(stdout, cursor::SavePosition, cursor::MoveTo(col as u16, row as u16))?;
+    stdout.write_all(sequence.as_bytes())?;
+    execute!(stdout, cursor::RestorePosition)?;
     Ok(())
 }
 
-pub fn hide_cursor() -> io::Result<()> {
-    let mut stdout = stdout();
-    stdout.execute(cursor::Hide)?;
-    Ok(())
+/// Image protocol the terminal understands
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Sixel,
 }
 
-pub fn show_cursor() -> io::Result<()> {
-    let mut stdout = stdout();
-    stdout.execute(cursor::Show)?;
-    Ok(())
-}
-
-pub fn move_cursor(col: usize, row: usize) -> io::Result<()> {
-    let mut stdout = stdout();
-    execute!(stdout, cursor::MoveTo(col as u16, row as u16))?;
-    Ok(())
+/// Guess the terminal's image protocol from the environment it was started with
+pub fn detect_graphics_protocol() -> Option<GraphicsProtocol> {
+    if headless_size().is_some() {
+        return None;
+    }
+    graphics_protocol_from_env(|name| std::env::var(name).ok())
 }
 
-pub fn print_at(col: usize, row: usize, text: &dyn std::fmt::Display) -> io::Result<()> {
-    move_cursor(col, row)?;
-    print!("{}", text);
-    flush_stdout()?;
-    Ok(())
+/// Image protocol for the environment variables returned by `var`
+/// Terminal multiplexers don't pass images through, so none is used inside tmux or screen
+pub fn graphics_protocol_from_env(var: impl Fn(&str) -> Option<String>) -> Option<GraphicsProtocol> {
+    let term = var("TERM").unwrap_or_default().to_lowercase();
+    let program = var("TERM_PROGRAM").unwrap_or_default().to_lowercase();
+    if var("TMUX").is_some() || term.starts_with("screen") || term.starts_with("tmux") {
+        None
+    } else if var("KITTY_WINDOW_ID").is_some()
+        || term.contains("kitty")
+        || term.contains("ghostty")
+        || program == "wezterm"
+        || program == "ghostty"
+    {
+        Some(GraphicsProtocol::Kitty)
+    } else if term.contains("sixel")
+        || term.starts_with("foot")
+        || term.starts_with("mlterm")
+        || term.starts_with("contour")
+        || program == "iterm.app"
+        || var("KONSOLE_VERSION").is_some()
+    {
+        Some(GraphicsProtocol::Sixel)
+    } else {
+        None
+    }
 }
 
 pub fn flush_stdout() -> io::Result<()> {
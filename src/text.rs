@@ -0,0 +1,89 @@
+//! Terminal column widths of text
+//!
+//! CJK characters and most emoji take two columns, combining marks take none. Text is
+//! measured and cut in clusters: a character together with the zero-width characters
+//! after it and anything joined to it with a zero-width joiner, so an accent or a
+//! joined emoji is never split from its base. Cursor positions stay char indices, and
+//! are converted to columns only for drawing and to byte offsets only for editing.
+
+use unicode_width::UnicodeWidthChar;
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+/// Character of the screen cell covered by the wide character before it
+pub const WIDE_CHAR_TAIL: char = '\0';
+
+/// Columns a character takes in the terminal; control characters take none
+pub fn char_width(ch: char) -> usize {
+    ch.width().unwrap_or(0)
+}
+
+/// Columns a string takes in the terminal
+pub fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Char index of the start of every cluster, followed by the char count
+pub fn cluster_starts(s: &str) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut previous = None;
+    let mut count = 0;
+    for ch in s.chars() {
+        let joins_previous = previous.is_some() && (char_width(ch) == 0 || previous == Some(ZERO_WIDTH_JOINER));
+        if !joins_previous {
+            starts.push(count);
+        }
+        previous = Some(ch);
+        count += 1;
+    }
+    starts.push(count);
+    starts
+}
+
+/// The longest start of `s` that fits in `width` columns without splitting a cluster
+pub fn truncate_to_width(s: &str, width: usize) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut end = 0;
+    let mut used = 0;
+    for pair in cluster_starts(s).windows(2) {
+        let cluster_width: usize = chars[pair[0]..pair[1]].iter().map(|&ch| char_width(ch)).sum();
+        if used + cluster_width > width {
+            break;
+        }
+        used += cluster_width;
+        end = pair[1];
+    }
+    chars[..end].iter().collect()
+}
+
+/// Char index of the cluster before `char_index`, where the cursor lands moving left
+pub fn prev_boundary(s: &str, char_index: usize) -> usize {
+    cluster_starts(s)
+        .into_iter()
+        .rev()
+        .find(|&start| start < char_index)
+        .unwrap_or(0)
+}
+
+/// Char index of the cluster after `char_index`, where the cursor lands moving right
+pub fn next_boundary(s: &str, char_index: usize) -> usize {
+    let starts = cluster_starts(s);
+    let end = starts.last().copied().unwrap_or(0);
+    starts.into_iter().find(|&start| start > char_index).unwrap_or(end)
+}
+
+/// Column of the cursor when it is before the char at `char_index`
+pub fn column_at(s: &str, char_index: usize) -> usize {
+    s.chars().take(char_index).map(char_width).sum()
+}
+
+/// Byte offset of the char at `char_index`, or the length when past the end
+pub fn byte_offset(s: &str, char_index: usize) -> usize {
+    s.char_indices().nth(char_index).map_or(s.len(), |(offset, _)| offset)
+}
+
+/// Remove the chars between two char indices
+pub fn remove_chars(s: &mut String, start: usize, end: usize) {
+    let (start, end) = (byte_offset(s, start), byte_offset(s, end));
+    s.replace_range(start..end, "");
+}
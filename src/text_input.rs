@@ -0,0 +1,95 @@
+//! Editing a line of text at a cursor
+//!
+//! The episode fields, the filter, the new series name, notes and alternate titles are all
+//! edited the same way. The cursor is a char index, as in `text`, and moves and deletes whole
+//! clusters, so an accent or a joined emoji is never split. Every edit is one pass over the text.
+
+use crate::text;
+
+/// A line of text being typed, with the cursor that is edited along with it
+pub struct TextInput<'a> {
+    text: &'a mut String,
+    cursor: &'a mut usize,
+}
+
+impl<'a> TextInput<'a> {
+    /// Edit `text` at `cursor`, which is moved back to the end if it is past it
+    pub fn new(text: &'a mut String, cursor: &'a mut usize) -> Self {
+        *cursor = (*cursor).min(text.chars().count());
+        TextInput { text, cursor }
+    }
+
+    pub fn left(&mut self) {
+        *self.cursor = text::prev_boundary(self.text, *self.cursor);
+    }
+
+    pub fn right(&mut self) {
+        *self.cursor = text::next_boundary(self.text, *self.cursor);
+    }
+
+    pub fn home(&mut self) {
+        *self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        *self.cursor = self.text.chars().count();
+    }
+
+    /// Back to the start of the word before the cursor; words are separated by spaces
+    pub fn word_left(&mut self) {
+        let (chars, starts) = self.clusters();
+        let cursor = *self.cursor;
+        let mut clusters = starts.windows(2).rev().filter(|pair| pair[1] <= cursor).peekable();
+        while let Some(pair) = clusters.next_if(|pair| chars[pair[0]] == ' ') {
+            *self.cursor = pair[0];
+        }
+        while let Some(pair) = clusters.next_if(|pair| chars[pair[0]] != ' ') {
+            *self.cursor = pair[0];
+        }
+    }
+
+    /// On past the word at the cursor and the spaces after it
+    pub fn word_right(&mut self) {
+        let (chars, starts) = self.clusters();
+        let cursor = *self.cursor;
+        let mut clusters = starts.windows(2).filter(|pair| pair[0] >= cursor).peekable();
+        while let Some(pair) = clusters.next_if(|pair| chars[pair[0]] != ' ') {
+            *self.cursor = pair[1];
+        }
+        while let Some(pair) = clusters.next_if(|pair| chars[pair[0]] == ' ') {
+            *self.cursor = pair[1];
+        }
+    }
+
+    /// Remove the cluster before the cursor, returning whether there was one
+    pub fn backspace(&mut self) -> bool {
+        if *self.cursor == 0 {
+            return false;
+        }
+        let start = text::prev_boundary(self.text, *self.cursor);
+        text::remove_chars(self.text, start, *self.cursor);
+        *self.cursor = start;
+        true
+    }
+
+    /// Remove the cluster at the cursor, returning whether there was one
+    pub fn delete(&mut self) -> bool {
+        let end = text::next_boundary(self.text, *self.cursor);
+        if end == *self.cursor {
+            return false;
+        }
+        text::remove_chars(self.text, *self.cursor, end);
+        true
+    }
+
+    /// Type `ch` at the cursor
+    pub fn insert(&mut self, ch: char) {
+        self.text.insert(text::byte_offset(self.text, *self.cursor), ch);
+        *self.cursor += 1;
+    }
+
+    /// The chars of the text and the start of every cluster in it, ending with the char count
+    fn clusters(&self) -> (Vec<char>, Vec<usize>) {
+        (self.text.chars().collect(), text::cluster_starts(self.text))
+    }
+}
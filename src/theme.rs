@@ -1,6 +1,7 @@
+use crossterm::style::Color;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use crate::logger;
 
 /// Theme struct containing all color and style configuration
@@ -33,6 +34,10 @@ pub struct Theme {
     pub invalid_fg: String,
     pub invalid_bg: String,
     
+    // Offline episode colors
+    pub offline_fg: String,
+    pub offline_bg: String,
+    
     // Series entry colors
     pub series_fg: String,
     pub series_bg: String,
@@ -45,9 +50,15 @@ pub struct Theme {
     pub episode_fg: String,
     pub episode_bg: String,
     
+    // Filter match highlight colors
+    pub match_fg: String,
+    pub match_bg: String,
+    
     // Status line colors
     pub status_fg: String,
     pub status_bg: String,
+    pub status_warn_fg: String,
+    pub status_error_fg: String,
     
     // Scroll bar configuration
     pub scrollbar_track_char: String,
@@ -59,6 +70,18 @@ pub struct Theme {
     pub count_fg: String,
     pub count_style: String,
     
+    // Series/season completion display
+    pub progress_display: String,
+    pub progress_width: usize,
+    pub progress_filled_char: String,
+    pub progress_empty_char: String,
+    pub progress_fg: String,
+    pub progress_empty_fg: String,
+    
+    // Unwatched/new indicator on series and season rows
+    pub category_indicator: String,
+    pub category_indicator_fg: String,
+    
     // Header text styling
     pub header_fg: String,
     pub header_style: String,
@@ -66,6 +89,11 @@ pub struct Theme {
     // Help text styling
     pub help_fg: String,
     pub help_style: String,
+
+    // Window borders and the marked-episode indicator
+    pub border_style: String,
+    pub active_border_style: String,
+    pub mark_indicator: String,
 }
 
 impl Default for Theme {
@@ -85,26 +113,224 @@ impl Default for Theme {
             new_bg: "Reset".to_string(),
             invalid_fg: "Red".to_string(),
             invalid_bg: "Reset".to_string(),
+            offline_fg: "DarkGray".to_string(),
+            offline_bg: "Reset".to_string(),
             series_fg: "Blue".to_string(),
             series_bg: "Reset".to_string(),
             season_fg: "Blue".to_string(),
             season_bg: "Reset".to_string(),
             episode_fg: "Reset".to_string(),
             episode_bg: "Reset".to_string(),
+            match_fg: "Black".to_string(),
+            match_bg: "Yellow".to_string(),
             status_fg: "White".to_string(),
             status_bg: "DarkGray".to_string(),
+            status_warn_fg: "Yellow".to_string(),
+            status_error_fg: "Red".to_string(),
             scrollbar_track_char: "│".to_string(),
             scrollbar_indicator_char: "█".to_string(),
             scrollbar_fg: "White".to_string(),
             scrollbar_bg: "Reset".to_string(),
             count_fg: "DarkGray".to_string(),
             count_style: "italic".to_string(),
+            progress_display: "count".to_string(),
+            progress_width: 5,
+            progress_filled_char: "▓".to_string(),
+            progress_empty_char: "░".to_string(),
+            progress_fg: "Green".to_string(),
+            progress_empty_fg: "DarkGray".to_string(),
+            category_indicator: "●".to_string(),
+            category_indicator_fg: "Yellow".to_string(),
             header_fg: "Black".to_string(),
             header_style: "none".to_string(),
             help_fg: "Reset".to_string(),
             help_style: "none".to_string(),
+            border_style: "single".to_string(),
+            active_border_style: "double".to_string(),
+            mark_indicator: "*".to_string(),
+        }
+    }
+}
+
+/// Characters used to draw a window border
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderChars {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+impl BorderChars {
+    pub const SINGLE: BorderChars = BorderChars::new(['┌', '┐', '└', '┘', '─', '│']);
+    pub const DOUBLE: BorderChars = BorderChars::new(['╔', '╗', '╚', '╝', '═', '║']);
+    pub const ROUNDED: BorderChars = BorderChars::new(['╭', '╮', '╰', '╯', '─', '│']);
+    pub const ASCII: BorderChars = BorderChars::new(['+', '+', '+', '+', '-', '|']);
+
+    /// Corners clockwise from the top-left, then the horizontal and vertical edges
+    const fn new(chars: [char; 6]) -> Self {
+        BorderChars {
+            top_left: chars[0],
+            top_right: chars[1],
+            bottom_left: chars[2],
+            bottom_right: chars[3],
+            horizontal: chars[4],
+            vertical: chars[5],
+        }
+    }
+
+    /// Border set named by a theme: single, double, rounded or ascii
+    pub fn from_name(name: &str) -> Option<BorderChars> {
+        match name.trim().to_lowercase().as_str() {
+            "single" => Some(BorderChars::SINGLE),
+            "double" => Some(BorderChars::DOUBLE),
+            "rounded" => Some(BorderChars::ROUNDED),
+            "ascii" => Some(BorderChars::ASCII),
+            _ => None,
+        }
+    }
+}
+
+impl Theme {
+    /// Switch to plain ASCII borders, indicators and bars, and drop the colors that only decorate
+    /// Used by the `accessible` setting; the selection, matches and warnings keep their colors
+    pub fn make_accessible(&mut self) {
+        self.border_style = "ascii".to_string();
+        self.active_border_style = "ascii".to_string();
+        self.watched_indicator = "[WATCHED]".to_string();
+        self.unwatched_indicator = "[UNWATCHED]".to_string();
+        self.mark_indicator = "*".to_string();
+        self.category_indicator = "*".to_string();
+        self.scrollbar_track_char = "|".to_string();
+        self.scrollbar_indicator_char = "#".to_string();
+        self.progress_filled_char = "#".to_string();
+        self.progress_empty_char = "-".to_string();
+        for color in [
+            &mut self.watched_fg,
+            &mut self.unwatched_fg,
+            &mut self.series_fg,
+            &mut self.series_bg,
+            &mut self.season_fg,
+            &mut self.season_bg,
+            &mut self.episode_fg,
+            &mut self.episode_bg,
+            &mut self.count_fg,
+            &mut self.progress_fg,
+            &mut self.progress_empty_fg,
+            &mut self.category_indicator_fg,
+            &mut self.scrollbar_fg,
+            &mut self.scrollbar_bg,
+        ] {
+            *color = "Reset".to_string();
+        }
+    }
+
+    /// Borders of windows that aren't focused, like the detail panel while browsing
+    pub fn border_chars(&self) -> BorderChars {
+        BorderChars::from_name(&self.border_style).unwrap_or(BorderChars::SINGLE)
+    }
+
+    /// Borders of focused windows, menus and dialogs
+    pub fn active_border_chars(&self) -> BorderChars {
+        BorderChars::from_name(&self.active_border_style).unwrap_or(BorderChars::DOUBLE)
+    }
+
+    /// Color fields whose values can't be parsed, as (field, value) pairs
+    pub fn invalid_colors(&self) -> Vec<(&'static str, &str)> {
+        let colors: [(&'static str, &str); 32] = [
+            ("current_fg", &self.current_fg),
+            ("current_bg", &self.current_bg),
+            ("dirty_fg", &self.dirty_fg),
+            ("dirty_bg", &self.dirty_bg),
+            ("watched_fg", &self.watched_fg),
+            ("unwatched_fg", &self.unwatched_fg),
+            ("new_fg", &self.new_fg),
+            ("new_bg", &self.new_bg),
+            ("invalid_fg", &self.invalid_fg),
+            ("invalid_bg", &self.invalid_bg),
+            ("offline_fg", &self.offline_fg),
+            ("offline_bg", &self.offline_bg),
+            ("series_fg", &self.series_fg),
+            ("series_bg", &self.series_bg),
+            ("season_fg", &self.season_fg),
+            ("season_bg", &self.season_bg),
+            ("episode_fg", &self.episode_fg),
+            ("episode_bg", &self.episode_bg),
+            ("match_fg", &self.match_fg),
+            ("match_bg", &self.match_bg),
+            ("status_fg", &self.status_fg),
+            ("status_bg", &self.status_bg),
+            ("status_warn_fg", &self.status_warn_fg),
+            ("status_error_fg", &self.status_error_fg),
+            ("scrollbar_fg", &self.scrollbar_fg),
+            ("scrollbar_bg", &self.scrollbar_bg),
+            ("count_fg", &self.count_fg),
+            ("progress_fg", &self.progress_fg),
+            ("progress_empty_fg", &self.progress_empty_fg),
+            ("category_indicator_fg", &self.category_indicator_fg),
+            ("header_fg", &self.header_fg),
+            ("help_fg", &self.help_fg),
+        ];
+        colors
+            .into_iter()
+            .filter(|(_, value)| string_to_color(value).is_none())
+            .collect()
+    }
+}
+
+/// Convert a theme color value to a Color
+/// Accepts the named colors, `#RRGGBB` for true color and `ansi(N)` for the 256-color palette
+pub fn string_to_color(color: &str) -> Option<Color> {
+    let value = color.trim().to_lowercase();
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |start: usize| u8::from_str_radix(&hex[start..start + 2], 16).ok();
+        return Some(Color::Rgb {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        });
+    }
+    if let Some(index) = value.strip_prefix("ansi(").and_then(|rest| rest.strip_suffix(')')) {
+        return index.trim().parse::<u8>().ok().map(Color::AnsiValue);
+    }
+    match value.as_str() {
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "magenta" => Some(Color::Magenta),
+        "cyan" => Some(Color::Cyan),
+        "white" => Some(Color::White),
+        "darkgray" | "dark_gray" => Some(Color::DarkGrey),
+        "reset" => Some(Color::Reset),
+        _ => None,
+    }
+}
+
+/// Colors and border styles of `theme`, loaded from `theme_path`, that can't be used
+pub fn theme_problems(theme: &Theme, theme_path: &Path) -> Vec<String> {
+    let mut problems: Vec<String> = theme
+        .invalid_colors()
+        .into_iter()
+        .map(|(field, value)| {
+            format!("Unknown color '{}' for {} in {:?}; use a color name, #RRGGBB or ansi(0-255)", value, field, theme_path)
+        })
+        .collect();
+    for (field, value) in [("border_style", &theme.border_style), ("active_border_style", &theme.active_border_style)] {
+        if BorderChars::from_name(value).is_none() {
+            problems.push(format!(
+                "Unknown border style '{}' for {} in {:?}; use single, double, rounded or ascii",
+                value, field, theme_path
+            ));
         }
     }
+    problems
 }
 
 /// Load a theme from a YAML file
@@ -116,23 +342,246 @@ pub fn load_theme(theme_path: &PathBuf) -> Theme {
         return default_theme;
     }
 
-    match fs::read_to_string(theme_path) {
-        Ok(contents) => {
-            match serde_yaml::from_str::<Theme>(&contents) {
-                Ok(theme) => theme,
-                Err(e) => {
-                    logger::log_warn(&format!("Failed to parse theme file at {:?}: {}. Using default theme.", theme_path, e));
-                    Theme::default()
-                }
+    let values = match load_theme_values(theme_path, &mut Vec::new()) {
+        Ok(values) => values,
+        Err(message) => {
+            logger::log_warn(&format!("{}. Using default theme.", message));
+            return Theme::default();
+        }
+    };
+    // Go back through YAML text so plain scalars like `series_fg: 12345` still read as strings
+    let merged = serde_yaml::to_string(&values).unwrap_or_default();
+    match serde_yaml::from_str::<Theme>(&merged) {
+        Ok(theme) => {
+            for problem in theme_problems(&theme, theme_path) {
+                logger::log_warn(&problem);
             }
+            theme
         }
         Err(e) => {
-            logger::log_warn(&format!("Failed to read theme file at {:?}: {}. Using default theme.", theme_path, e));
+            logger::log_warn(&format!("Failed to parse theme file at {:?}: {}. Using default theme.", theme_path, e));
             Theme::default()
         }
     }
 }
 
+/// Keys of a theme file laid over the keys of the theme it `extends`, if any
+/// `chain` holds the files already being loaded, so a theme that extends itself
+/// (directly or through others) stops there instead of recursing forever
+fn load_theme_values(theme_path: &Path, chain: &mut Vec<PathBuf>) -> Result<serde_yaml::Mapping, String> {
+    let contents = fs::read_to_string(theme_path)
+        .map_err(|e| format!("Failed to read theme file at {:?}: {}", theme_path, e))?;
+    let mut values = match serde_yaml::from_str::<serde_yaml::Value>(&contents) {
+        Ok(serde_yaml::Value::Mapping(values)) => values,
+        Ok(serde_yaml::Value::Null) => serde_yaml::Mapping::new(),
+        Ok(_) => return Err(format!("Failed to parse theme file at {:?}: expected a list of keys", theme_path)),
+        Err(e) => return Err(format!("Failed to parse theme file at {:?}: {}", theme_path, e)),
+    };
+
+    let Some(extends) = values.remove("extends") else {
+        return Ok(values);
+    };
+    let Some(base_name) = extends.as_str() else {
+        logger::log_warn(&format!("Ignoring extends in {:?}: expected a theme file name", theme_path));
+        return Ok(values);
+    };
+    // Base themes are looked up next to the theme that names them
+    let base_path = theme_path.parent().unwrap_or(Path::new(".")).join(base_name);
+    chain.push(fs::canonicalize(theme_path).unwrap_or_else(|_| theme_path.to_path_buf()));
+    let canonical_base = fs::canonicalize(&base_path).unwrap_or_else(|_| base_path.clone());
+    if chain.contains(&canonical_base) {
+        logger::log_warn(&format!(
+            "Ignoring extends in {:?}: {} is already part of this theme",
+            theme_path, base_name
+        ));
+        return Ok(values);
+    }
+
+    match load_theme_values(&base_path, chain) {
+        Ok(mut base) => {
+            // Keys set in this file win over the ones it inherits
+            for (key, value) in values {
+                base.insert(key, value);
+            }
+            Ok(base)
+        }
+        Err(message) => {
+            logger::log_warn(&format!("{}. Ignoring extends in {:?}", message, theme_path));
+            Ok(values)
+        }
+    }
+}
+
+/// Themes that come with the program, by name; "Install Themes" writes each as THEME-<name>.yaml
+pub fn builtin_themes() -> Vec<(&'static str, Theme)> {
+    let color = |value: &str| value.to_string();
+    vec![
+        (
+            "dark",
+            Theme {
+                current_fg: color("Black"),
+                current_bg: color("Cyan"),
+                dirty_bg: color("Yellow"),
+                series_fg: color("Cyan"),
+                season_fg: color("ansi(111)"),
+                status_bg: color("ansi(236)"),
+                count_fg: color("ansi(244)"),
+                scrollbar_fg: color("DarkGray"),
+                border_style: color("rounded"),
+                ..Theme::default()
+            },
+        ),
+        (
+            "light",
+            Theme {
+                current_fg: color("White"),
+                current_bg: color("Blue"),
+                dirty_fg: color("Black"),
+                dirty_bg: color("Yellow"),
+                watched_fg: color("ansi(28)"),
+                new_fg: color("ansi(28)"),
+                offline_fg: color("ansi(246)"),
+                series_fg: color("ansi(25)"),
+                season_fg: color("ansi(31)"),
+                match_fg: color("Black"),
+                match_bg: color("ansi(229)"),
+                status_fg: color("Black"),
+                status_bg: color("ansi(252)"),
+                status_warn_fg: color("ansi(130)"),
+                status_error_fg: color("ansi(160)"),
+                scrollbar_fg: color("ansi(244)"),
+                count_fg: color("ansi(244)"),
+                progress_fg: color("ansi(28)"),
+                progress_empty_fg: color("ansi(250)"),
+                category_indicator_fg: color("ansi(130)"),
+                ..Theme::default()
+            },
+        ),
+        (
+            "solarized",
+            Theme {
+                current_fg: color("#fdf6e3"),
+                current_bg: color("#268bd2"),
+                dirty_fg: color("#002b36"),
+                dirty_bg: color("#b58900"),
+                watched_fg: color("#859900"),
+                unwatched_fg: color("#839496"),
+                new_fg: color("#2aa198"),
+                invalid_fg: color("#dc322f"),
+                offline_fg: color("#586e75"),
+                series_fg: color("#268bd2"),
+                season_fg: color("#6c71c4"),
+                episode_fg: color("#93a1a1"),
+                match_fg: color("#002b36"),
+                match_bg: color("#b58900"),
+                status_fg: color("#93a1a1"),
+                status_bg: color("#073642"),
+                status_warn_fg: color("#cb4b16"),
+                status_error_fg: color("#dc322f"),
+                scrollbar_fg: color("#586e75"),
+                count_fg: color("#586e75"),
+                progress_fg: color("#859900"),
+                progress_empty_fg: color("#586e75"),
+                category_indicator_fg: color("#b58900"),
+                help_fg: color("#839496"),
+                border_style: color("rounded"),
+                ..Theme::default()
+            },
+        ),
+        (
+            "high-contrast",
+            Theme {
+                current_fg: color("Black"),
+                current_bg: color("Yellow"),
+                dirty_fg: color("Black"),
+                dirty_bg: color("Cyan"),
+                watched_fg: color("Green"),
+                watched_style: color("bold"),
+                unwatched_fg: color("White"),
+                new_fg: color("Green"),
+                invalid_fg: color("Red"),
+                offline_fg: color("White"),
+                series_fg: color("Cyan"),
+                season_fg: color("Cyan"),
+                episode_fg: color("White"),
+                match_fg: color("Black"),
+                match_bg: color("Cyan"),
+                status_fg: color("Black"),
+                status_bg: color("White"),
+                scrollbar_fg: color("White"),
+                count_fg: color("White"),
+                count_style: color("none"),
+                progress_empty_fg: color("White"),
+                help_fg: color("White"),
+                border_style: color("double"),
+                ..Theme::default()
+            },
+        ),
+        (
+            "monochrome",
+            Theme {
+                current_fg: color("Black"),
+                current_bg: color("White"),
+                dirty_fg: color("Black"),
+                dirty_bg: color("White"),
+                watched_fg: color("Reset"),
+                unwatched_fg: color("Reset"),
+                new_fg: color("Reset"),
+                invalid_fg: color("Reset"),
+                offline_fg: color("Reset"),
+                series_fg: color("Reset"),
+                season_fg: color("Reset"),
+                match_fg: color("Reset"),
+                match_bg: color("Reset"),
+                status_fg: color("Black"),
+                status_bg: color("White"),
+                status_warn_fg: color("Black"),
+                status_error_fg: color("Black"),
+                count_fg: color("Reset"),
+                progress_fg: color("Reset"),
+                progress_empty_fg: color("Reset"),
+                category_indicator_fg: color("Reset"),
+                ..Theme::default()
+            },
+        ),
+    ]
+}
+
+/// Write the built-in themes into `config_dir`, returning the file names written
+/// A theme file already there is left as it is, so edits to an installed theme are kept
+pub fn install_builtin_themes(config_dir: &Path) -> std::io::Result<Vec<String>> {
+    let mut installed = Vec::new();
+    for (name, theme) in builtin_themes() {
+        let file_name = format!("THEME-{}.yaml", name);
+        let theme_path = config_dir.join(&file_name);
+        if theme_path.exists() {
+            continue;
+        }
+        fs::write(&theme_path, generate_theme_yaml_with_comments(&theme))?;
+        logger::log_info(&format!("Installed theme {:?}", theme_path));
+        installed.push(file_name);
+    }
+    Ok(installed)
+}
+
+/// Theme files (`THEME-*.yaml`) in the config directory, sorted by name
+pub fn list_theme_files(config_dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = match fs::read_dir(config_dir) {
+        Ok(dir) => dir
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with("THEME-") && name.ends_with(".yaml"))
+            .collect(),
+        Err(e) => {
+            logger::log_warn(&format!("Failed to list themes in {:?}: {}", config_dir, e));
+            Vec::new()
+        }
+    };
+    names.sort();
+    names
+}
+
 /// Save a theme to a YAML file with comments
 pub fn save_theme(theme: &Theme, theme_path: &PathBuf) {
     let yaml_content = generate_theme_yaml_with_comments(theme);
@@ -144,11 +593,21 @@ pub fn save_theme(theme: &Theme, theme_path: &PathBuf) {
     }
 }
 
+/// A color as a YAML value, quoting "#RRGGBB" so it isn't read as a comment
+fn yaml_color(color: &str) -> String {
+    if color.starts_with('#') {
+        format!("\"{}\"", color)
+    } else {
+        color.to_string()
+    }
+}
+
 /// Generate YAML content with inline documentation comments
 pub fn generate_theme_yaml_with_comments(theme: &Theme) -> String {
     format!(
         r##"# === Color Configuration ===
 # Valid colors: Black, Red, Green, Yellow, Blue, Magenta, Cyan, White, DarkGray, Reset
+# or "#RRGGBB" (quoted, since # starts a YAML comment) for true color, or ansi(0-255) for the 256-color palette
 # Reset means use the terminal's default color
 
 # Current selection colors (highlighted item in browse mode)
@@ -183,6 +642,10 @@ new_bg: {}
 invalid_fg: {}
 invalid_bg: {}
 
+# Offline episode colors (when the drive or share holding the video isn't mounted)
+offline_fg: {}
+offline_bg: {}
+
 # Series entry colors (for series items in browse mode)
 series_fg: {}
 series_bg: {}
@@ -195,9 +658,17 @@ season_bg: {}
 episode_fg: {}
 episode_bg: {}
 
+# Filter match colors (the parts of a name that match the filter)
+# On the selected row the matches are underlined instead
+match_fg: {}
+match_bg: {}
+
 # Status line colors (bottom status bar)
 status_fg: {}
 status_bg: {}
+# Foreground colors for warnings and errors on the status line
+status_warn_fg: {}
+status_error_fg: {}
 
 # Scroll bar configuration
 # Character used for the scroll bar track
@@ -215,6 +686,24 @@ count_fg: {}
 # Style for count text (none, bold, dim, italic, underline)
 count_style: {}
 
+# Completion display for series and seasons
+# count shows "3/5 watched", bar shows "▓▓▓░░ 60%", percent shows "60%"
+progress_display: {}
+# Number of characters in the completion bar
+progress_width: {}
+# Characters for the watched and unwatched parts of the bar
+progress_filled_char: "{}"
+progress_empty_char: "{}"
+# Foreground colors for the watched and unwatched parts of the bar
+progress_fg: {}
+progress_empty_fg: {}
+
+# Indicator on series and season rows that still have episodes to watch
+# Rows with newly imported episodes show "(3 new)" instead of the character
+category_indicator: "{}"
+# Foreground color for the indicator
+category_indicator_fg: {}
+
 # Header text styling
 # Foreground color for header text
 header_fg: {}
@@ -226,39 +715,64 @@ header_style: {}
 help_fg: {}
 # Style for help text (none, bold, dim, italic, underline)
 help_style: {}
+
+# Window borders (single, double, rounded, ascii)
+# Border of windows that aren't focused, like the detail panel while browsing
+border_style: {}
+# Border of focused windows, menus and dialogs
+active_border_style: {}
+# Character shown in front of episodes marked for batch actions
+mark_indicator: "{}"
 "##,
-        theme.current_fg,
-        theme.current_bg,
-        theme.dirty_fg,
-        theme.dirty_bg,
+        yaml_color(&theme.current_fg),
+        yaml_color(&theme.current_bg),
+        yaml_color(&theme.dirty_fg),
+        yaml_color(&theme.dirty_bg),
         theme.watched_indicator,
-        theme.watched_fg,
+        yaml_color(&theme.watched_fg),
         theme.watched_style,
         theme.unwatched_indicator,
-        theme.unwatched_fg,
+        yaml_color(&theme.unwatched_fg),
         theme.unwatched_style,
-        theme.new_fg,
-        theme.new_bg,
-        theme.invalid_fg,
-        theme.invalid_bg,
-        theme.series_fg,
-        theme.series_bg,
-        theme.season_fg,
-        theme.season_bg,
-        theme.episode_fg,
-        theme.episode_bg,
-        theme.status_fg,
-        theme.status_bg,
+        yaml_color(&theme.new_fg),
+        yaml_color(&theme.new_bg),
+        yaml_color(&theme.invalid_fg),
+        yaml_color(&theme.invalid_bg),
+        yaml_color(&theme.offline_fg),
+        yaml_color(&theme.offline_bg),
+        yaml_color(&theme.series_fg),
+        yaml_color(&theme.series_bg),
+        yaml_color(&theme.season_fg),
+        yaml_color(&theme.season_bg),
+        yaml_color(&theme.episode_fg),
+        yaml_color(&theme.episode_bg),
+        yaml_color(&theme.match_fg),
+        yaml_color(&theme.match_bg),
+        yaml_color(&theme.status_fg),
+        yaml_color(&theme.status_bg),
+        yaml_color(&theme.status_warn_fg),
+        yaml_color(&theme.status_error_fg),
         theme.scrollbar_track_char,
         theme.scrollbar_indicator_char,
-        theme.scrollbar_fg,
-        theme.scrollbar_bg,
-        theme.count_fg,
+        yaml_color(&theme.scrollbar_fg),
+        yaml_color(&theme.scrollbar_bg),
+        yaml_color(&theme.count_fg),
         theme.count_style,
-        theme.header_fg,
+        theme.progress_display,
+        theme.progress_width,
+        theme.progress_filled_char,
+        theme.progress_empty_char,
+        yaml_color(&theme.progress_fg),
+        yaml_color(&theme.progress_empty_fg),
+        theme.category_indicator,
+        yaml_color(&theme.category_indicator_fg),
+        yaml_color(&theme.header_fg),
         theme.header_style,
-        theme.help_fg,
+        yaml_color(&theme.help_fg),
         theme.help_style,
+        theme.border_style,
+        theme.active_border_style,
+        theme.mark_indicator,
     )
 }
 
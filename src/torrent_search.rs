@@ -77,7 +77,7 @@ pub async fn search_torrents(query: &str) -> Result<Vec<TorrentResult>, Box<dyn
 }
 
 /// Format bytes into human-readable size string
-fn format_size(bytes: u64) -> String {
+pub fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -0,0 +1,169 @@
+use crossterm::event::KeyCode;
+
+/// Part of the screen a tutorial step points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialRegion {
+    Header,
+    Browser,
+    DetailPanel,
+}
+
+/// One stop of the guided tour, in the order they are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialStep {
+    Welcome,
+    Navigate,
+    Filter,
+    MarkWatched,
+    AssignSeries,
+    OpenMenu,
+    Done,
+}
+
+impl TutorialStep {
+    /// Title shown at the top of the tutorial window
+    pub fn title(&self) -> &'static str {
+        match self {
+            TutorialStep::Welcome => "Welcome",
+            TutorialStep::Navigate => "Moving around",
+            TutorialStep::Filter => "Filtering",
+            TutorialStep::MarkWatched => "Marking watched",
+            TutorialStep::AssignSeries => "Organizing into series",
+            TutorialStep::OpenMenu => "The menu",
+            TutorialStep::Done => "All set",
+        }
+    }
+
+    /// Instructions for the step
+    pub fn text(&self) -> &'static str {
+        match self {
+            TutorialStep::Welcome => "This quick tour shows the core controls. Press [ENTER] to start or [ESC] to skip.",
+            TutorialStep::Navigate => "Your videos are listed here. Press [UP] or [DOWN] to move the selection.",
+            TutorialStep::Filter => "Press [/] to filter the list by name. Press [ESC] to leave the filter.",
+            TutorialStep::MarkWatched => "The selected video's details are shown here. Press [F3] to toggle watched.",
+            TutorialStep::AssignSeries => "Press [F4] on an episode to assign it to a series. [ESC] closes the window.",
+            TutorialStep::OpenMenu => "Press [F1] to open the menu of everything you can do with the selection.",
+            TutorialStep::Done => "You're ready! Reopen this tour from the [F1] menu anytime. Press [ENTER] to finish.",
+        }
+    }
+
+    /// Screen region highlighted while the step is shown
+    pub fn region(&self) -> Option<TutorialRegion> {
+        match self {
+            TutorialStep::Welcome | TutorialStep::Done => None,
+            TutorialStep::Navigate => Some(TutorialRegion::Browser),
+            TutorialStep::Filter | TutorialStep::OpenMenu => Some(TutorialRegion::Header),
+            TutorialStep::MarkWatched | TutorialStep::AssignSeries => Some(TutorialRegion::DetailPanel),
+        }
+    }
+
+    /// Whether `code` is the keypress this step waits for
+    pub fn accepts(&self, code: KeyCode) -> bool {
+        match self {
+            TutorialStep::Welcome | TutorialStep::Done => code == KeyCode::Enter,
+            TutorialStep::Navigate => matches!(
+                code,
+                KeyCode::Up | KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('k')
+            ),
+            TutorialStep::Filter => code == KeyCode::Char('/'),
+            TutorialStep::MarkWatched => code == KeyCode::F(3),
+            TutorialStep::AssignSeries => code == KeyCode::F(4),
+            TutorialStep::OpenMenu => code == KeyCode::F(1),
+        }
+    }
+
+    /// The step's keypress is only for the tutorial and is not passed on to the app
+    pub fn consumes_key(&self) -> bool {
+        matches!(self, TutorialStep::Welcome | TutorialStep::Done)
+    }
+
+    fn next(&self) -> Option<TutorialStep> {
+        match self {
+            TutorialStep::Welcome => Some(TutorialStep::Navigate),
+            TutorialStep::Navigate => Some(TutorialStep::Filter),
+            TutorialStep::Filter => Some(TutorialStep::MarkWatched),
+            TutorialStep::MarkWatched => Some(TutorialStep::AssignSeries),
+            TutorialStep::AssignSeries => Some(TutorialStep::OpenMenu),
+            TutorialStep::OpenMenu => Some(TutorialStep::Done),
+            TutorialStep::Done => None,
+        }
+    }
+}
+
+/// Outcome of offering a keypress to the tutorial
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialKey {
+    /// Not the step's key; handle it normally
+    Ignored,
+    /// Advanced to the next step; handle the key normally too
+    Advanced,
+    /// Used by the tutorial only; do not handle it further
+    Consumed,
+    /// The tutorial is over and should be closed
+    Finished,
+}
+
+/// Guided overlay state, waiting for the current step's keypress
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tutorial {
+    step: TutorialStep,
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tutorial {
+    /// Start the tour at the welcome step
+    pub fn new() -> Self {
+        Tutorial {
+            step: TutorialStep::Welcome,
+        }
+    }
+
+    /// The step currently shown
+    pub fn step(&self) -> TutorialStep {
+        self.step
+    }
+
+    /// Position of the current step, 1-based, and the number of steps
+    pub fn progress(&self) -> (usize, usize) {
+        let mut index = 1;
+        let mut step = TutorialStep::Welcome;
+        while step != self.step {
+            step = step.next().unwrap_or(self.step);
+            index += 1;
+        }
+        let mut total = index;
+        while let Some(next) = step.next() {
+            step = next;
+            total += 1;
+        }
+        (index, total)
+    }
+
+    /// Offer a Browse-mode keypress to the tutorial
+    pub fn handle_key(&mut self, code: KeyCode) -> TutorialKey {
+        if code == KeyCode::Esc {
+            return TutorialKey::Finished;
+        }
+        if !self.step.accepts(code) {
+            return TutorialKey::Ignored;
+        }
+
+        let consumed = self.step.consumes_key();
+        match self.step.next() {
+            Some(next) => {
+                self.step = next;
+                if consumed {
+                    TutorialKey::Consumed
+                } else {
+                    TutorialKey::Advanced
+                }
+            }
+            None => TutorialKey::Finished,
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// File name of the usage counters in the data directory
+pub const USAGE_FILE_NAME: &str = "usage.json";
+
+/// File name written next to the library by the opt-in export
+pub const USAGE_EXPORT_FILE_NAME: &str = "usage_export.json";
+
+/// Every action tracked by the counters, used to measure feature adoption
+pub const TRACKED_ACTIONS: &[&str] = &[
+    "play",
+    "edit",
+    "toggle_watched",
+    "assign_series",
+    "edit_notes",
+    "edit_alt_titles",
+    "cycle_sort",
+    "pin_filter",
+    "repeat_action",
+    "rescan",
+    "clear_series",
+    "unwatch_all",
+    "delete",
+    "search_online",
+    "export_library",
+    "import_library",
+    "import_watch_history",
+    "trakt_sync",
+    "merge_versions",
+];
+
+/// Local usage counters; nothing leaves the machine unless the user exports them
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UsageStats {
+    #[serde(default)]
+    pub actions: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub sessions: u64,
+    #[serde(default)]
+    pub total_session_seconds: u64,
+}
+
+impl UsageStats {
+    /// Count one use of an action
+    pub fn record_action(&mut self, action: &str) {
+        *self.actions.entry(action.to_string()).or_insert(0) += 1;
+    }
+
+    /// Count a finished session and its length
+    pub fn record_session(&mut self, length: Duration) {
+        self.sessions += 1;
+        self.total_session_seconds += length.as_secs();
+    }
+
+    /// Average session length, if any session has finished
+    pub fn average_session(&self) -> Option<Duration> {
+        (self.sessions > 0).then(|| Duration::from_secs(self.total_session_seconds / self.sessions))
+    }
+
+    /// The most used actions, most used first
+    pub fn top_actions(&self, count: usize) -> Vec<(&str, u64)> {
+        let mut actions: Vec<(&str, u64)> = self.actions.iter().map(|(name, n)| (name.as_str(), *n)).collect();
+        actions.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        actions.truncate(count);
+        actions
+    }
+
+    /// Number of tracked features used at least once, out of all tracked features
+    pub fn feature_adoption(&self) -> (usize, usize) {
+        let used = TRACKED_ACTIONS.iter().filter(|a| self.actions.contains_key(**a)).count();
+        (used, TRACKED_ACTIONS.len())
+    }
+
+    /// One-line summary suitable for the status bar
+    pub fn summary(&self) -> String {
+        let top: Vec<String> = self
+            .top_actions(3)
+            .iter()
+            .map(|(name, count)| format!("{} ({})", name, count))
+            .collect();
+        let average = self
+            .average_session()
+            .map(|d| crate::video_metadata::format_duration_hms(d.as_secs()))
+            .unwrap_or_else(|| "n/a".to_string());
+        let (used, total) = self.feature_adoption();
+        format!(
+            "Usage: {} sessions, avg {} | top: {} | {} of {} features used",
+            self.sessions,
+            average,
+            if top.is_empty() { "none yet".to_string() } else { top.join(", ") },
+            used,
+            total
+        )
+    }
+}
+
+/// Default location of the usage counters
+pub fn default_usage_path() -> Option<PathBuf> {
+    crate::paths::data_dir().map(|dir| dir.join(USAGE_FILE_NAME))
+}
+
+/// Load counters from disk, starting fresh if the file is missing or unreadable
+pub fn load_usage(path: &Path) -> UsageStats {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Write counters to disk
+pub fn save_usage(stats: &UsageStats, path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(stats).map_err(io::Error::other)?;
+    std::fs::write(path, json)
+}
+
+struct UsageState {
+    path: PathBuf,
+    stats: UsageStats,
+    session_start: Instant,
+}
+
+lazy_static! {
+    static ref USAGE: Mutex<Option<UsageState>> = Mutex::new(None);
+}
+
+/// Start tracking a session, loading existing counters from `path`
+pub fn initialize_usage(path: PathBuf) {
+    let stats = load_usage(&path);
+    *USAGE.lock().unwrap() = Some(UsageState {
+        path,
+        stats,
+        session_start: Instant::now(),
+    });
+}
+
+/// Count one use of an action and persist the counters
+pub fn record_action(action: &str) {
+    if let Some(state) = USAGE.lock().unwrap().as_mut() {
+        state.stats.record_action(action);
+        if let Err(e) = save_usage(&state.stats, &state.path) {
+            crate::logger::log_warn(&format!("Failed to save usage counters: {}", e));
+        }
+    }
+}
+
+/// Record the length of the current session and persist the counters
+pub fn finish_session() {
+    if let Some(state) = USAGE.lock().unwrap().as_mut() {
+        state.stats.record_session(state.session_start.elapsed());
+        if let Err(e) = save_usage(&state.stats, &state.path) {
+            crate::logger::log_warn(&format!("Failed to save usage counters: {}", e));
+        }
+    }
+}
+
+/// Snapshot of the counters, including the session in progress
+pub fn current_usage() -> UsageStats {
+    match USAGE.lock().unwrap().as_ref() {
+        Some(state) => {
+            let mut stats = state.stats.clone();
+            stats.record_session(state.session_start.elapsed());
+            stats
+        }
+        None => UsageStats::default(),
+    }
+}
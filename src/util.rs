@@ -1,5 +1,11 @@
 use crate::config::Config;
-use crate::dto::EpisodeDetail;
+use crate::dto::{AltTitles, EpisodeDetail};
+use crate::remote::RemoteRequest;
+use crate::scan::{ScanProgress, ScanReport};
+use crate::web_video::WebVideo;
+use crate::text;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io;
 use std::path::Path;
 use std::process::{Child, Command, Stdio};
@@ -21,6 +27,166 @@ pub enum Entry {
     },
 }
 
+/// Identifies a browse entry independent of its display name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryKey {
+    Series(usize),
+    Season(usize),
+    Episode(usize),
+}
+
+impl Entry {
+    pub fn key(&self) -> EntryKey {
+        match self {
+            Entry::Series { series_id, .. } => EntryKey::Series(*series_id),
+            Entry::Season { season_id, .. } => EntryKey::Season(*season_id),
+            Entry::Episode { episode_id, .. } => EntryKey::Episode(*episode_id),
+        }
+    }
+}
+
+/// Order of entries in Browse mode
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortMode {
+    #[default]
+    Default,
+    Name,
+    Year,
+    DateAdded,
+    EpisodeNumber,
+    Watched,
+    FileSize,
+    RecentlyWatched,
+    MostPlayed,
+}
+
+impl SortMode {
+    /// Display name used in status messages and the header
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortMode::Default => "default order",
+            SortMode::Name => "name",
+            SortMode::Year => "year",
+            SortMode::DateAdded => "date added",
+            SortMode::EpisodeNumber => "episode number",
+            SortMode::Watched => "watched state",
+            SortMode::FileSize => "file size",
+            SortMode::RecentlyWatched => "recently watched",
+            SortMode::MostPlayed => "most played",
+        }
+    }
+
+    /// The sort mode that follows this one when cycling
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Default => SortMode::Name,
+            SortMode::Name => SortMode::Year,
+            SortMode::Year => SortMode::DateAdded,
+            SortMode::DateAdded => SortMode::EpisodeNumber,
+            SortMode::EpisodeNumber => SortMode::Watched,
+            SortMode::Watched => SortMode::FileSize,
+            SortMode::FileSize => SortMode::RecentlyWatched,
+            SortMode::RecentlyWatched => SortMode::MostPlayed,
+            SortMode::MostPlayed => SortMode::Default,
+        }
+    }
+
+    /// Parse a `default_sort` config value such as "date_added"
+    pub fn from_config(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().replace(['-', ' '], "_").as_str() {
+            "default" | "" => Some(SortMode::Default),
+            "name" => Some(SortMode::Name),
+            "year" => Some(SortMode::Year),
+            "date_added" | "added" => Some(SortMode::DateAdded),
+            "episode_number" | "episode" => Some(SortMode::EpisodeNumber),
+            "watched" => Some(SortMode::Watched),
+            "file_size" | "size" => Some(SortMode::FileSize),
+            "recently_watched" => Some(SortMode::RecentlyWatched),
+            "most_played" => Some(SortMode::MostPlayed),
+            _ => None,
+        }
+    }
+}
+
+/// Per-entry values used by the non-default sort modes
+/// Series and seasons carry the latest watch time and total plays of their episodes
+#[derive(Debug, Clone, Default)]
+pub struct SortKeys {
+    pub last_watched: HashMap<EntryKey, i64>,
+    pub play_count: HashMap<EntryKey, u32>,
+    /// Earliest year among a series' or season's episodes
+    pub year: HashMap<EntryKey, i64>,
+    /// Import time as a Unix timestamp; series and seasons take their newest episode
+    pub added: HashMap<EntryKey, i64>,
+    /// Episode number, or the season number for seasons
+    pub episode_number: HashMap<EntryKey, i64>,
+    /// Series and seasons count as watched once every episode is
+    pub watched: HashMap<EntryKey, bool>,
+    /// File size in bytes; series and seasons add up their episodes
+    pub file_size: HashMap<EntryKey, i64>,
+}
+
+/// Show episodes under their title in the preferred language, when they have one
+pub fn apply_preferred_titles(entries: &mut [Entry], alt_titles: &HashMap<usize, AltTitles>, language: &str) {
+    let language = language.trim().to_lowercase();
+    for entry in entries.iter_mut() {
+        if let Entry::Episode { episode_id, name, .. } = entry {
+            if let Some(title) = alt_titles.get(episode_id).and_then(|titles| titles.get(&language)) {
+                *name = title.clone();
+            }
+        }
+    }
+}
+
+/// Name and episode totals of a series or season
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CategoryCounts {
+    pub name: String,
+    pub total: usize,
+    pub unwatched: usize,
+    /// Episodes still named after their file, i.e. not yet edited since import
+    pub new: usize,
+}
+
+/// Name an entry is sorted by; seasons sort by number
+fn sort_name(entry: &Entry) -> String {
+    match entry {
+        Entry::Series { name, .. } | Entry::Episode { name, .. } => name.to_lowercase(),
+        Entry::Season { number, .. } => format!("season {:05}", number),
+    }
+}
+
+/// Reorder entries for the sort mode
+/// Entries without a value keep their original relative order after the rest
+pub fn sort_entries(entries: &mut [Entry], sort_mode: SortMode, keys: &SortKeys) {
+    // Ascending order with missing values last
+    let ascending = |map: &HashMap<EntryKey, i64>, entry: &Entry| {
+        map.get(&entry.key()).map_or((1, 0), |value| (0, *value))
+    };
+    match sort_mode {
+        SortMode::Default => {}
+        SortMode::Name => entries.sort_by_cached_key(sort_name),
+        SortMode::Year => entries.sort_by_key(|entry| ascending(&keys.year, entry)),
+        SortMode::DateAdded => {
+            entries.sort_by_key(|entry| std::cmp::Reverse(keys.added.get(&entry.key()).copied()));
+        }
+        SortMode::EpisodeNumber => entries.sort_by_key(|entry| ascending(&keys.episode_number, entry)),
+        SortMode::Watched => {
+            // Unwatched first
+            entries.sort_by_key(|entry| keys.watched.get(&entry.key()).map_or(2, |watched| *watched as u8));
+        }
+        SortMode::FileSize => {
+            entries.sort_by_key(|entry| std::cmp::Reverse(keys.file_size.get(&entry.key()).copied()));
+        }
+        SortMode::RecentlyWatched => {
+            entries.sort_by_key(|entry| std::cmp::Reverse(keys.last_watched.get(&entry.key()).copied()));
+        }
+        SortMode::MostPlayed => {
+            entries.sort_by_key(|entry| std::cmp::Reverse(keys.play_count.get(&entry.key()).copied()));
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum LastAction {
     SeriesAssignment {
@@ -52,11 +218,38 @@ impl LastAction {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ViewContext {
     TopLevel,
     Series { series_id: usize, series_name: String },
     Season { season_id: usize, series_name: String, season_number: usize },
+    /// Sample and trailer files imported as extras, opened with Show Extras
+    Extras,
+}
+
+/// Messages sent to the main loop by background threads
+#[derive(Debug)]
+pub enum AppEvent {
+    /// The video player exited
+    PlaybackFinished,
+    /// A rescan got further
+    ScanProgress(ScanProgress),
+    /// A rescan finished, boxed as the report is much larger than the other events
+    ScanFinished(Box<ScanReport>),
+    /// The process was told to stop by the named signal
+    Shutdown(&'static str),
+    /// A command arrived on the remote control socket
+    Remote(RemoteRequest),
+    /// A download started by "Add from URL" finished, or failed with the message
+    DownloadFinished(Result<WebVideo, String>),
+    /// A custom command from the menu exited, or failed with the message
+    CommandFinished { label: String, result: Result<(), String> },
+    /// A Trakt or Jellyfin sync got further, with the line to show meanwhile
+    #[cfg_attr(not(any(feature = "trakt", feature = "jellyfin")), allow(dead_code))]
+    SyncProgress(String),
+    /// A Trakt or Jellyfin sync finished with its status line, or failed with the message
+    #[cfg_attr(not(any(feature = "trakt", feature = "jellyfin")), allow(dead_code))]
+    SyncFinished(Result<String, String>),
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -66,26 +259,36 @@ pub enum Mode {
     Entry,               // initial load from disk
     SeriesSelect,        // series selection
     SeriesCreate,        // create a new series
+    NotesEdit,           // edit the notes of an episode
+    AltTitlesEdit,       // edit the alternate titles of an episode
     Menu,                // context menu
+    Confirm,             // yes/no dialog before a destructive action
     TorrentSearchInput,  // torrent search input
     TorrentSearchResults, // torrent search results
+    Digest,              // weekly digest
+    ArchiveReview,       // archives found by a rescan
+    ScanReport,          // what the last rescan imported, skipped and found missing
+    Help,                // keybinding reference
+    Notifications,       // recent status messages
+    VersionSelect,       // pick which file of an episode to play
+    ThemeSelect,         // preview and pick a theme file
+    Relocate,            // point the library at the folder it was moved to
+    AddFromUrl,          // type the URL of a web video to download into the library
+    RenameFile,          // preview renaming an episode's file from the editor
+    DuplicateReview,     // choose which copies of duplicate videos to keep
+    ConfigReport,        // problems found in config.yaml when the app started
 }
 
+/// Cut a string to `max_length` terminal columns, ending it with "..." when it was cut
 pub fn truncate_string(s: &str, max_length: usize) -> String {
     // Handle edge case where max_length is too small for ellipsis
     if max_length < 3 {
-        // If max_length is 0, return empty string
-        if max_length == 0 {
-            return String::new();
-        }
-        // If max_length is 1 or 2, just truncate without ellipsis
-        return s.chars().take(max_length).collect();
+        // If max_length is 0, 1 or 2, just truncate without ellipsis
+        return text::truncate_to_width(s, max_length);
     }
     
-    if s.len() > max_length {
-        // Use saturating_sub to prevent underflow
-        let truncate_at = max_length.saturating_sub(3);
-        format!("{}...", &s[..truncate_at])
+    if text::display_width(s) > max_length {
+        format!("{}...", text::truncate_to_width(s, max_length - 3))
     } else {
         s.to_string()
     }
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+/// A file that can be played for an episode; the episode's own file comes first
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpisodeVersion {
+    /// Absolute path of the file
+    pub location: String,
+    pub label: String,
+}
+
+/// Words in file names that tell versions apart, and the label shown for each
+const VERSION_TAGS: &[(&str, &str)] = &[
+    ("2160p", "4K"),
+    ("4k", "4K"),
+    ("uhd", "4K"),
+    ("1080p", "1080p"),
+    ("720p", "720p"),
+    ("480p", "480p"),
+    ("directors cut", "Director's Cut"),
+    ("extended", "Extended"),
+    ("theatrical", "Theatrical"),
+    ("unrated", "Unrated"),
+    ("remastered", "Remastered"),
+];
+
+/// Label a version by the resolution and edition in its file name, falling back to the file name
+pub fn version_label(location: &str) -> String {
+    let stem = Path::new(location)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let normalized: String = stem
+        .to_lowercase()
+        .chars()
+        .filter(|c| *c != '\'')
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+
+    let mut tags: Vec<&str> = Vec::new();
+    for (pattern, tag) in VERSION_TAGS {
+        let pattern: Vec<&str> = pattern.split(' ').collect();
+        if words.windows(pattern.len()).any(|window| window == pattern.as_slice()) && !tags.contains(tag) {
+            tags.push(tag);
+        }
+    }
+    if tags.is_empty() {
+        stem
+    } else {
+        tags.join(" ")
+    }
+}
+
+/// Index of the version to preselect: the first whose label contains the preference, otherwise the episode's own file
+pub fn preferred_version_index(versions: &[EpisodeVersion], preferred: Option<&str>) -> usize {
+    preferred
+        .map(|preferred| preferred.trim().to_lowercase())
+        .filter(|preferred| !preferred.is_empty())
+        .and_then(|preferred| {
+            versions
+                .iter()
+                .position(|version| version.label.to_lowercase().contains(&preferred))
+        })
+        .unwrap_or(0)
+}
@@ -1,6 +1,6 @@
 use std::path::Path;
 use std::error::Error;
-use crate::database;
+use crate::database::Database;
 
 /// Extract duration in seconds from a video file
 /// Supports MKV, MP4, and AVI formats
@@ -242,11 +242,12 @@ fn extract_avi_duration(file_path: &Path) -> Result<u64, Box<dyn Error>> {
     Err("AVI header not found in file".into())
 }
 
-/// Extract duration and update episode length in database
+/// Extract duration and update episode length in database, returning the length in seconds
 pub fn extract_and_update_episode_length(
+    database: &Database,
     episode_id: usize,
     file_path: &Path,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<u64, Box<dyn Error>> {
     // Extract duration in seconds
     let duration_seconds = match extract_duration_seconds(file_path) {
         Ok(duration) => {
@@ -270,7 +271,7 @@ pub fn extract_and_update_episode_length(
     };
     
     // Update database
-    let conn = database::get_connection().lock().unwrap();
+    let conn = database.connection();
     conn.execute(
         "UPDATE episode SET length = ?1 WHERE id = ?2",
         rusqlite::params![duration_seconds as i64, episode_id],
@@ -283,7 +284,7 @@ pub fn extract_and_update_episode_length(
         format_duration_hms(duration_seconds)
     ));
     
-    Ok(())
+    Ok(duration_seconds)
 }
 
 /// Format duration in seconds as "hh:mm:ss"
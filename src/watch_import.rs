@@ -0,0 +1,213 @@
+use rusqlite::{Connection, OpenFlags};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// File name of the Plex Media Server library database
+pub const PLEX_DATABASE_NAME: &str = "com.plexapp.plugins.library.db";
+
+/// Media center whose watch history is being imported
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WatchSource {
+    Kodi,
+    Plex,
+}
+
+impl WatchSource {
+    /// Display name used in status messages
+    pub fn label(&self) -> &'static str {
+        match self {
+            WatchSource::Kodi => "Kodi",
+            WatchSource::Plex => "Plex",
+        }
+    }
+}
+
+/// Watch state of a single file as recorded by another media center
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchRecord {
+    /// Full path of the file as the media center knows it
+    pub path: String,
+    pub play_count: u32,
+    pub last_played: Option<String>,
+    pub resume_seconds: Option<u64>,
+}
+
+/// Summary of applying imported watch history to the library
+#[derive(Debug, Clone, Default)]
+pub struct WatchImportReport {
+    pub total: usize,
+    pub matched: usize,
+    /// Records whose file name and path fit more than one episode equally well, left alone
+    pub ambiguous: usize,
+    pub marked_watched: usize,
+    pub resumed: usize,
+}
+
+impl WatchImportReport {
+    /// One-line summary suitable for the status bar
+    pub fn summary(&self, source: WatchSource) -> String {
+        let mut summary = format!(
+            "Imported {} watch history: {} of {} files matched, {} marked watched, {} resume points",
+            source.label(),
+            self.matched,
+            self.total,
+            self.marked_watched,
+            self.resumed
+        );
+        if self.ambiguous > 0 {
+            summary.push_str(&format!(", {} ambiguous files skipped", self.ambiguous));
+        }
+        summary
+    }
+}
+
+/// Locate a Kodi or Plex database in the library root, falling back to Kodi's default userdata folder
+pub fn find_watch_database(root: &Path) -> Option<PathBuf> {
+    let mut candidates = vec![root.to_path_buf()];
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join(".kodi").join("userdata").join("Database"));
+    }
+
+    for dir in candidates {
+        let plex = dir.join(PLEX_DATABASE_NAME);
+        if plex.is_file() {
+            return Some(plex);
+        }
+        if let Some(kodi) = find_latest_kodi_database(&dir) {
+            return Some(kodi);
+        }
+    }
+
+    None
+}
+
+/// Pick the newest MyVideosNN.db in a directory
+fn find_latest_kodi_database(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let version = name.strip_prefix("MyVideos")?.strip_suffix(".db")?.parse::<u32>().ok()?;
+            Some((version, e.path()))
+        })
+        .max_by_key(|(version, _)| *version)
+        .map(|(_, path)| path)
+}
+
+/// Read watch history from a Kodi MyVideos or Plex library database
+pub fn read_watch_records(path: &Path) -> Result<(WatchSource, Vec<WatchRecord>), Box<dyn Error>> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+    let source = if table_exists(&conn, "metadata_item_settings")? {
+        WatchSource::Plex
+    } else if table_exists(&conn, "files")? {
+        WatchSource::Kodi
+    } else {
+        return Err(format!("{} is not a Kodi or Plex database", path.display()).into());
+    };
+
+    let records = match source {
+        WatchSource::Kodi => read_kodi_records(&conn)?,
+        WatchSource::Plex => read_plex_records(&conn)?,
+    };
+
+    crate::logger::log_info(&format!(
+        "Read {} {} watch records from {}",
+        records.len(),
+        source.label(),
+        path.display()
+    ));
+
+    Ok((source, records))
+}
+
+fn table_exists(conn: &Connection, table: &str) -> rusqlite::Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+        [table],
+        |row| row.get(0),
+    )
+}
+
+fn read_kodi_records(conn: &Connection) -> rusqlite::Result<Vec<WatchRecord>> {
+    // Resume points are stored as bookmarks of type 1; the folder of each file is in the path table
+    let mut stmt = conn.prepare(
+        "SELECT COALESCE(path.strPath, '') || files.strFilename,
+                COALESCE(files.playCount, 0),
+                files.lastPlayed,
+                (SELECT bookmark.timeInSeconds FROM bookmark
+                 WHERE bookmark.idFile = files.idFile AND bookmark.type = 1 LIMIT 1)
+         FROM files
+         LEFT JOIN path ON path.idPath = files.idPath
+         WHERE files.strFilename IS NOT NULL",
+    )?;
+
+    let records = stmt
+        .query_map([], |row| {
+            let last_played: Option<String> = row.get(2)?;
+            let resume: Option<f64> = row.get(3)?;
+            Ok(WatchRecord {
+                path: row.get(0)?,
+                play_count: row.get::<_, i64>(1)?.max(0) as u32,
+                last_played: last_played.as_deref().and_then(kodi_time_to_rfc3339),
+                resume_seconds: resume.filter(|s| *s > 0.0).map(|s| s as u64),
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(records)
+}
+
+fn read_plex_records(conn: &Connection) -> rusqlite::Result<Vec<WatchRecord>> {
+    let mut stmt = conn.prepare(
+        "SELECT media_parts.file,
+                COALESCE(settings.view_count, 0),
+                settings.last_viewed_at,
+                settings.view_offset
+         FROM media_parts
+         JOIN media_items ON media_items.id = media_parts.media_item_id
+         JOIN metadata_items ON metadata_items.id = media_items.metadata_item_id
+         JOIN metadata_item_settings AS settings ON settings.guid = metadata_items.guid
+         WHERE media_parts.file IS NOT NULL",
+    )?;
+
+    let records = stmt
+        .query_map([], |row| {
+            let last_viewed: Option<i64> = row.get(2)?;
+            // Plex stores the view offset in milliseconds
+            let offset_ms: Option<i64> = row.get(3)?;
+            Ok(WatchRecord {
+                path: row.get(0)?,
+                play_count: row.get::<_, i64>(1)?.max(0) as u32,
+                last_played: last_viewed
+                    .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+                    .map(|dt| dt.to_rfc3339()),
+                resume_seconds: offset_ms.filter(|ms| *ms > 0).map(|ms| (ms / 1000) as u64),
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(records)
+}
+
+/// Convert Kodi's local "YYYY-MM-DD HH:MM:SS" timestamps to RFC 3339
+fn kodi_time_to_rfc3339(value: &str) -> Option<String> {
+    let naive = chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok()?;
+    naive
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(|dt| dt.to_rfc3339())
+}
+
+/// Extract the file name from a path that may use either separator style
+pub fn file_name_of(path: &str) -> String {
+    path.rsplit(['/', '\\']).next().unwrap_or(path).to_string()
+}
+
+/// Number of trailing folder and file names two paths share, whichever separator style each uses
+pub fn common_suffix_len(a: &str, b: &str) -> usize {
+    let a = a.rsplit(['/', '\\']).filter(|name| !name.is_empty());
+    let b = b.rsplit(['/', '\\']).filter(|name| !name.is_empty());
+    a.zip(b).take_while(|(a, b)| a == b).count()
+}
@@ -0,0 +1,182 @@
+//! Adding web videos to the library by downloading them with yt-dlp
+//!
+//! "Add from URL" runs yt-dlp on a background thread, saving the video into `download_folder`
+//! under the library root. Once the file is in place yt-dlp prints what its extractor found as
+//! JSON, and the main loop gets it as `AppEvent::DownloadFinished`, imports the file and fills
+//! in the title, year and length from it.
+
+use crate::database::{Database, DatabaseError, DbResult};
+use crate::location;
+use crate::path_resolver::PathResolver;
+use crate::util::AppEvent;
+use serde_json::Value;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::Sender;
+use std::thread;
+
+/// Name yt-dlp gives the downloaded file in the download folder
+const OUTPUT_TEMPLATE: &str = "%(title)s [%(id)s].%(ext)s";
+
+/// A downloaded video with what the extractor knew about it
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebVideo {
+    pub path: PathBuf,
+    pub title: String,
+    pub year: Option<i32>,
+    /// Length in seconds
+    pub length: Option<u64>,
+    /// Page the video was downloaded from
+    pub source: Option<String>,
+    pub uploader: Option<String>,
+}
+
+impl WebVideo {
+    /// Notes saved with the episode, saying where it came from
+    pub fn notes(&self) -> Option<String> {
+        match (&self.source, &self.uploader) {
+            (Some(source), Some(uploader)) => Some(format!("Downloaded from {} (by {})", source, uploader)),
+            (Some(source), None) => Some(format!("Downloaded from {}", source)),
+            (None, Some(uploader)) => Some(format!("Downloaded from the web (by {})", uploader)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Check the typed URL, giving it back trimmed
+pub fn check_url(text: &str) -> Result<String, String> {
+    let url = text.trim();
+    if url.is_empty() {
+        return Err("Type the URL of a video".to_string());
+    }
+    let lower = url.to_lowercase();
+    if !(lower.starts_with("http://") || lower.starts_with("https://")) || url.contains(char::is_whitespace) {
+        return Err(format!("Not a web address: {}", url));
+    }
+    Ok(url.to_string())
+}
+
+/// Folder downloads go into: `setting` under the library `root`, unless it is absolute
+pub fn download_folder(root: &Path, setting: &str) -> PathBuf {
+    let setting = setting.trim();
+    if setting.is_empty() {
+        return root.to_path_buf();
+    }
+    root.join(setting)
+}
+
+/// yt-dlp command downloading `url` into `folder`, printing the extractor's JSON once the file is in place
+pub fn build_command(program: &str, url: &str, folder: &Path) -> Command {
+    let mut command = Command::new(program);
+    command
+        .arg("--no-playlist")
+        .arg("--paths")
+        .arg(folder)
+        .arg("--output")
+        .arg(OUTPUT_TEMPLATE)
+        .arg("--print")
+        .arg("after_move:%()j")
+        .arg("--")
+        .arg(url);
+    command
+}
+
+/// Read the video from yt-dlp's output: the last line that is a JSON object with a file path
+pub fn parse_output(stdout: &str) -> Result<WebVideo, String> {
+    let info = stdout
+        .lines()
+        .rev()
+        .filter(|line| line.trim_start().starts_with('{'))
+        .find_map(|line| serde_json::from_str::<Value>(line).ok())
+        .ok_or("yt-dlp didn't say what it downloaded")?;
+    let text = |key: &str| info.get(key).and_then(Value::as_str).map(str::trim).filter(|value| !value.is_empty());
+
+    let path = text("filepath").ok_or("yt-dlp didn't say where it saved the video")?;
+    let path = PathBuf::from(path);
+    let title = text("title")
+        .map(str::to_string)
+        .or_else(|| path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+        .unwrap_or_default();
+    // release_year is set for films and music; otherwise the year of the upload date, YYYYMMDD
+    let year = info
+        .get("release_year")
+        .and_then(Value::as_i64)
+        .and_then(|year| i32::try_from(year).ok())
+        .or_else(|| text("upload_date").and_then(|date| date.get(..4)).and_then(|year| year.parse().ok()));
+    let length = info
+        .get("duration")
+        .and_then(Value::as_f64)
+        .filter(|seconds| *seconds > 0.0)
+        .map(|seconds| seconds.round() as u64);
+
+    Ok(WebVideo {
+        path,
+        title,
+        year,
+        length,
+        source: text("webpage_url").map(str::to_string),
+        uploader: text("uploader").or_else(|| text("channel")).map(str::to_string),
+    })
+}
+
+/// What went wrong from yt-dlp's error output: its last "ERROR:" line, or the last line
+pub fn error_message(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+    lines
+        .iter()
+        .rev()
+        .find_map(|line| line.strip_prefix("ERROR:"))
+        .or(lines.last().copied())
+        .map(|line| line.trim().to_string())
+        .unwrap_or_else(|| "yt-dlp failed".to_string())
+}
+
+/// Add the downloaded `video` to the library with its title, year, length and where it came
+/// from, returning the episode's id; a file already in the library has its details replaced
+pub fn import(video: &WebVideo, database: &Database, resolver: &PathResolver) -> DbResult<usize> {
+    let name = video.path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    database.import_episode_relative(&location::encode(&video.path), &name, resolver)?;
+    let episode_id = database.find_episode_id(&video.path, resolver)?
+        .ok_or_else(|| DatabaseError::Import(format!("{} wasn't added to the library", video.path.display())))?;
+
+    let mut details = database.get_episode_detail(episode_id)?;
+    if !video.title.is_empty() {
+        details.title = video.title.clone();
+    }
+    if let Some(year) = video.year {
+        details.year = year.to_string();
+    }
+    if let Some(length) = video.length {
+        details.length = length.to_string();
+    }
+    database.update_episode_detail(episode_id, &details)?;
+    if let Some(notes) = video.notes() {
+        database.update_episode_notes(episode_id, &notes)?;
+    }
+    Ok(episode_id)
+}
+
+/// Download `url` into `folder` with `program` on a background thread, sending the result to the main loop over `tx`
+pub fn spawn_download(program: String, url: String, folder: PathBuf, tx: Sender<AppEvent>) {
+    thread::spawn(move || {
+        let result = download(&program, &url, &folder);
+        let _ = tx.send(AppEvent::DownloadFinished(result));
+    });
+}
+
+fn download(program: &str, url: &str, folder: &Path) -> Result<WebVideo, String> {
+    std::fs::create_dir_all(folder).map_err(|e| format!("Couldn't create {}: {}", folder.display(), e))?;
+    crate::logger::log_info(&format!("Downloading {} into {}", url, folder.display()));
+    let output = build_command(program, url, folder)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => format!("{} not found; install yt-dlp or set ytdlp_path in config.yaml", program),
+            _ => format!("Couldn't run {}: {}", program, e),
+        })?;
+    if !output.status.success() {
+        return Err(error_message(&String::from_utf8_lossy(&output.stderr)));
+    }
+    parse_output(&String::from_utf8_lossy(&output.stdout))
+}
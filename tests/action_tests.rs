@@ -0,0 +1,227 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use movies::action::{browse_action, browse_key_action, command_line_action, Action, Count};
+use movies::app_state::{AppContext, AppState};
+use movies::config::Config;
+use movies::database::Database;
+use movies::handlers::{apply_browse_action, handle_browse_mode};
+use movies::keymap::{Key, Keymap};
+use movies::path_resolver::PathResolver;
+use movies::util::{Entry, Mode};
+use std::sync::{mpsc, Arc};
+use tempfile::TempDir;
+
+fn episode(episode_id: usize) -> Entry {
+    Entry::Episode {
+        episode_id,
+        name: format!("episode{}.mkv", episode_id),
+        location: format!("/videos/episode{}.mkv", episode_id),
+    }
+}
+
+#[test]
+fn test_browse_keys_map_to_actions() {
+    let keymap = Keymap::default();
+    let none = KeyModifiers::NONE;
+    assert_eq!(browse_action(&keymap, KeyCode::Up, none, false), Some(Action::MoveUp));
+    assert_eq!(browse_action(&keymap, KeyCode::Char('k'), none, false), Some(Action::MoveUp));
+    assert_eq!(browse_action(&keymap, KeyCode::Char('j'), none, false), Some(Action::MoveDown));
+    assert_eq!(browse_action(&keymap, KeyCode::F(1), none, false), Some(Action::OpenMenu));
+    assert_eq!(browse_action(&keymap, KeyCode::Enter, none, false), Some(Action::PlaySelected));
+    assert_eq!(browse_action(&keymap, KeyCode::Esc, none, false), Some(Action::Back));
+    assert_eq!(browse_action(&keymap, KeyCode::Right, KeyModifiers::CONTROL, false), Some(Action::ResizeSplit(true)));
+    assert_eq!(browse_action(&keymap, KeyCode::Left, none, false), Some(Action::MoveLeft));
+    assert_eq!(browse_action(&keymap, KeyCode::Char('q'), none, false), Some(Action::RecordMacro));
+    assert_eq!(browse_action(&keymap, KeyCode::Char('@'), none, false), Some(Action::ReplayMacro));
+
+    // Digits pick a tab with Alt, and a pinned filter without it
+    assert_eq!(browse_action(&keymap, KeyCode::Char('2'), KeyModifiers::ALT, false), Some(Action::SwitchTab(2)));
+    assert_eq!(browse_action(&keymap, KeyCode::Char('2'), none, false), Some(Action::TogglePinnedFilter(2)));
+    assert_eq!(browse_action(&keymap, KeyCode::Char('7'), none, false), None);
+}
+
+#[test]
+fn test_filter_mode_only_maps_filter_keys() {
+    let keymap = Keymap::default();
+    let none = KeyModifiers::NONE;
+    assert_eq!(browse_action(&keymap, KeyCode::Char('j'), none, true), Some(Action::FilterInsert('j')));
+    assert_eq!(browse_action(&keymap, KeyCode::Enter, none, true), Some(Action::AcceptFilter));
+    assert_eq!(browse_action(&keymap, KeyCode::Esc, none, true), Some(Action::CancelFilter));
+    assert_eq!(browse_action(&keymap, KeyCode::Left, KeyModifiers::CONTROL, true), Some(Action::FilterWordLeft));
+    assert_eq!(browse_action(&keymap, KeyCode::Up, none, true), Some(Action::FilterHistoryPrevious));
+    assert_eq!(browse_action(&keymap, KeyCode::F(1), none, true), None);
+}
+
+#[test]
+fn test_key_sequences_wait_for_their_last_key() {
+    let vim = Keymap::preset("vim").unwrap();
+    let mut pending = Vec::new();
+    let none = KeyModifiers::NONE;
+
+    assert_eq!(browse_key_action(&vim, &mut pending, KeyCode::Char('g'), none, false), None);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(browse_key_action(&vim, &mut pending, KeyCode::Char('g'), none, false), Some(Action::MoveTop));
+    assert!(pending.is_empty());
+
+    // A key that doesn't finish the sequence drops it and counts by itself
+    browse_key_action(&vim, &mut pending, KeyCode::Char('g'), none, false);
+    assert_eq!(browse_key_action(&vim, &mut pending, KeyCode::Char('j'), none, false), Some(Action::MoveDown));
+    assert!(pending.is_empty());
+    assert_eq!(browse_key_action(&vim, &mut pending, KeyCode::Char('2'), none, false), Some(Action::TogglePinnedFilter(2)));
+
+    assert_eq!(command_line_action(KeyCode::Char('q')), Some(Action::CommandInsert('q')));
+    assert_eq!(command_line_action(KeyCode::Enter), Some(Action::RunCommand));
+}
+
+/// Runs `check` with a context over an empty library
+fn with_context(check: impl FnOnce(&AppContext)) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.sqlite");
+    let database = Arc::new(Database::open(&db_path).unwrap());
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let config = Config::default();
+    let (tx, _rx) = mpsc::channel();
+    check(&AppContext {
+        config: &config,
+        database: &database,
+        resolver: &resolver,
+        tx: &tx,
+        config_path: &db_path.with_file_name("config.yaml"),
+    });
+}
+
+#[test]
+fn test_vim_motions_and_command_line() {
+    with_context(|context| {
+        let mut state = AppState::new((1..=5).map(episode).collect());
+
+        apply_browse_action(Action::MoveBottom, &mut state, context, 1).unwrap();
+        assert_eq!(state.current_item, 4);
+        apply_browse_action(Action::MoveTop, &mut state, context, 1).unwrap();
+        assert_eq!((state.current_item, state.first_entry), (0, 0));
+
+        // Without a kept filter there is nothing to jump through
+        apply_browse_action(Action::PreviousMatch, &mut state, context, 1).unwrap();
+        assert_eq!(state.current_item, 0);
+        state.search = "episode".to_string();
+        apply_browse_action(Action::PreviousMatch, &mut state, context, 1).unwrap();
+        assert_eq!(state.current_item, 4);
+        apply_browse_action(Action::NextMatch, &mut state, context, 1).unwrap();
+        assert_eq!(state.current_item, 0);
+
+        let run = |command: &str, state: &mut AppState| {
+            apply_browse_action(Action::OpenCommandLine, state, context, 1).unwrap();
+            for c in command.chars() {
+                apply_browse_action(Action::CommandInsert(c), state, context, 1).unwrap();
+            }
+            apply_browse_action(Action::RunCommand, state, context, 1).unwrap()
+        };
+        assert!(run("3", &mut state));
+        assert_eq!(state.current_item, 2);
+        assert!(state.command_line.is_none());
+        assert!(run("help", &mut state));
+        assert_eq!(state.mode, Mode::Help);
+        state.mode = Mode::Browse;
+        assert!(run("fly", &mut state));
+        assert_eq!(state.notifications.history().last().map(|n| n.message.as_str()), Some("Not a command: fly"));
+        assert!(!run("q", &mut state));
+    });
+}
+
+#[test]
+fn test_only_moves_is_for_the_moves_through_the_list() {
+    assert!(Action::MoveDown.only_moves());
+    assert!(Action::PageUp.only_moves());
+    assert!(Action::NextMatch.only_moves());
+    assert!(!Action::PlaySelected.only_moves(), "Opening a series changes the list");
+    assert!(!Action::MoveLeft.only_moves(), "Left leaves the view outside the grid");
+    assert!(!Action::FilterInsert('a').only_moves());
+}
+
+#[test]
+fn test_counts_are_typed_digit_by_digit() {
+    let count = Count::Repeat(1).push_digit(5);
+    assert_eq!((count, count.label()), (Count::Repeat(15), "15".to_string()));
+    assert_eq!(count.pop_digit(), Some(Count::Repeat(1)));
+    assert_eq!(Count::Repeat(1).pop_digit(), None);
+
+    let jump = Count::Jump(None);
+    assert_eq!(jump.label(), "#");
+    assert_eq!(jump.push_digit(4).push_digit(2).label(), "#42");
+    assert_eq!(Count::Jump(Some(4)).pop_digit(), Some(Count::Jump(None)));
+    assert_eq!(jump.pop_digit(), None);
+}
+
+#[test]
+fn test_count_prefixes_and_jumps() {
+    with_context(|context| {
+        let mut state = AppState::new((1..=30).map(episode).collect());
+        let press = |code: KeyCode, state: &mut AppState| {
+            handle_browse_mode(code, KeyModifiers::NONE, state, context, 1).unwrap();
+        };
+
+        press(KeyCode::Char('1'), &mut state);
+        press(KeyCode::Char('5'), &mut state);
+        assert_eq!(state.count, Some(Count::Repeat(15)));
+        press(KeyCode::Char('j'), &mut state);
+        assert_eq!((state.current_item, state.count), (15, None));
+        press(KeyCode::Char('4'), &mut state);
+        press(KeyCode::Up, &mut state);
+        assert_eq!(state.current_item, 11);
+
+        for code in [KeyCode::Char('#'), KeyCode::Char('2'), KeyCode::Char('5'), KeyCode::Backspace, KeyCode::Char('3'), KeyCode::Enter] {
+            press(code, &mut state);
+        }
+        assert_eq!((state.current_item, state.count), (22, None));
+
+        // Esc drops the count without going back
+        press(KeyCode::Char('9'), &mut state);
+        press(KeyCode::Esc, &mut state);
+        press(KeyCode::Char('j'), &mut state);
+        assert_eq!(state.current_item, 23);
+    });
+}
+
+#[test]
+fn test_apply_browse_actions() {
+    with_context(test_apply_browse_actions_with);
+}
+
+fn test_apply_browse_actions_with(context: &AppContext) {
+    let mut state = AppState::new(vec![episode(1), episode(2)]);
+
+    assert!(apply_browse_action(Action::MoveDown, &mut state, context, 1).unwrap());
+    assert_eq!(state.current_item, 1);
+    apply_browse_action(Action::OpenMenu, &mut state, context, 1).unwrap();
+    assert_eq!((state.mode, state.remembered_item), (Mode::Menu, 1));
+
+    state.mode = Mode::Browse;
+    for action in [Action::StartFilter, Action::FilterInsert('a'), Action::FilterInsert('b'), Action::FilterCursorLeft, Action::FilterBackspace] {
+        apply_browse_action(action, &mut state, context, 1).unwrap();
+    }
+    assert_eq!((state.search.as_str(), state.edit_cursor_pos), ("b", 0));
+    apply_browse_action(Action::CancelFilter, &mut state, context, 1).unwrap();
+    assert!(!state.filter_mode && state.search.is_empty());
+
+    // A kept filter comes back with Up the next time the filter is typed
+    for action in [Action::StartFilter, Action::FilterInsert('x'), Action::AcceptFilter, Action::StartFilter] {
+        apply_browse_action(action, &mut state, context, 1).unwrap();
+    }
+    state.search.clear();
+    apply_browse_action(Action::FilterHistoryPrevious, &mut state, context, 1).unwrap();
+    assert_eq!((state.search.as_str(), state.edit_cursor_pos), ("x", 1));
+    apply_browse_action(Action::CancelFilter, &mut state, context, 1).unwrap();
+
+    // q starts and stops a macro recording, and @ queues its keys
+    apply_browse_action(Action::ReplayMacro, &mut state, context, 1).unwrap();
+    assert_eq!(state.notifications.history().last().map(|n| n.message.as_str()), Some("No macro recorded yet"));
+    apply_browse_action(Action::RecordMacro, &mut state, context, 1).unwrap();
+    state.macros.record(Key::pressed(KeyCode::Down, KeyModifiers::NONE));
+    apply_browse_action(Action::RecordMacro, &mut state, context, 1).unwrap();
+    assert!(!state.macros.is_recording());
+    apply_browse_action(Action::ReplayMacro, &mut state, context, 1).unwrap();
+    assert_eq!(state.macros.next_key(), Some(Key::pressed(KeyCode::Down, KeyModifiers::NONE)));
+
+    // Back in the library with nothing to clear quits
+    state.filtered_entries.clear();
+    assert!(!apply_browse_action(Action::Back, &mut state, context, 1).unwrap());
+}
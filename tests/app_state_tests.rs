@@ -0,0 +1,71 @@
+use movies::app_state::AppState;
+use movies::menu::MenuAction;
+use movies::util::{Entry, Mode, ViewContext};
+
+fn episode(episode_id: usize) -> Entry {
+    Entry::Episode {
+        episode_id,
+        name: format!("episode{}.mkv", episode_id),
+        location: format!("/videos/episode{}.mkv", episode_id),
+    }
+}
+
+#[test]
+fn test_new_state_browses_every_entry() {
+    let state = AppState::new(vec![episode(1), episode(2)]);
+
+    assert_eq!(state.mode, Mode::Browse);
+    assert_eq!(state.view_context, ViewContext::TopLevel);
+    assert_eq!(state.filtered_entries.len(), 2);
+    assert_eq!((state.current_item, state.first_entry), (0, 0));
+    assert!(state.search.is_empty());
+    assert!(state.original_edit_details.is_none());
+    assert!(state.redraw);
+}
+
+#[test]
+fn test_menu_items_are_for_the_remembered_entry() {
+    let series = Entry::Series {
+        series_id: 1,
+        name: "Lost".to_string(),
+    };
+    let mut state = AppState::new(vec![series, episode(2)]);
+    let has_edit = |state: &AppState| state.menu_items().iter().any(|item| matches!(item.action, MenuAction::Edit));
+
+    // The menu was opened on the series, so the episode's Edit item is not offered
+    state.current_item = 1;
+    assert!(!has_edit(&state));
+
+    state.remembered_item = 1;
+    assert!(has_edit(&state));
+}
+
+#[test]
+fn test_assigning_a_series_with_none_listed_keeps_the_picker_open() {
+    use movies::app_state::AppContext;
+    use movies::config::Config;
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use std::sync::Arc;
+
+    let mut state = AppState::new(vec![episode(1)]);
+    state.selected_entry_id = Some(1);
+    state.mode = Mode::SeriesSelect;
+    state.series.clear();
+
+    let library = tempfile::TempDir::new().unwrap();
+    let db_path = library.path().join("videos.sqlite");
+    let database = Arc::new(Database::open(&db_path).unwrap());
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let config = Config::default();
+    let (tx, _rx) = std::sync::mpsc::channel();
+    let context = AppContext {
+        config: &config,
+        database: &database,
+        resolver: &resolver,
+        tx: &tx,
+        config_path: &db_path.with_file_name("config.yaml"),
+    };
+    movies::handlers::handle_series_select_mode(crossterm::event::KeyCode::Enter, &mut state, &context);
+    assert_eq!(state.mode, Mode::SeriesSelect);
+}
@@ -0,0 +1,52 @@
+use movies::archive::{find_pending_archives, is_archive, videos_in_listing};
+use movies::config::ScanRules;
+use movies::extras::SampleFiles;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn rules() -> ScanRules {
+    ScanRules {
+        video_extensions: vec!["mkv".to_string(), "mp4".to_string()],
+        min_file_size_mb: 0,
+        exclude: Vec::new(),
+        follow_symlinks: false,
+        dedupe_hardlinks: false,
+        samples: SampleFiles::Import,
+        sample_max_seconds: 0,
+    }
+}
+
+#[test]
+fn test_is_archive_skips_later_volumes() {
+    assert!(is_archive(Path::new("/videos/Show.S01.zip")));
+    assert!(is_archive(Path::new("/videos/Show.S01.RAR")));
+    assert!(is_archive(Path::new("/videos/Show.S01.part01.rar")));
+    assert!(!is_archive(Path::new("/videos/Show.S01.part02.rar")));
+    assert!(!is_archive(Path::new("/videos/Show.S01.mkv")));
+}
+
+#[test]
+fn test_videos_in_listing() {
+    let listing = "Path = Show.S01.rar\nType = Rar\n\nPath = Show/S01E01.mkv\nSize = 100\nPath = Show/info.nfo\nShow/S01E02.MP4\n";
+    assert_eq!(
+        videos_in_listing(listing, &rules()),
+        vec!["Show/S01E01.mkv".to_string(), "Show/S01E02.MP4".to_string()]
+    );
+}
+
+#[test]
+fn test_find_pending_archives_skips_extracted_and_empty() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    // `cat` stands in for a list command: each fake archive holds its own listing
+    fs::write(temp_dir.path().join("pending.zip"), "Path = S01E01.mkv\n").unwrap();
+    fs::write(temp_dir.path().join("extracted.zip"), "Path = S01E02.mkv\n").unwrap();
+    fs::write(temp_dir.path().join("S01E02.mkv"), "").unwrap();
+    fs::write(temp_dir.path().join("subtitles.zip"), "Path = S01E01.srt\n").unwrap();
+
+    let archives = find_pending_archives(temp_dir.path(), "cat {archive}", &rules());
+    assert_eq!(archives.len(), 1);
+    assert_eq!(archives[0].path, temp_dir.path().join("pending.zip"));
+    assert_eq!(archives[0].videos, vec!["S01E01.mkv".to_string()]);
+    assert_eq!(archives[0].label(), "pending.zip (1 video)");
+}
@@ -0,0 +1,90 @@
+use movies::artwork::{
+    find_episode_artwork, find_folder_artwork, fit_cells, kitty_sequence, png_dimensions, sixel_sequence,
+    CELL_HEIGHT_PX, CELL_WIDTH_PX,
+};
+use movies::terminal::{graphics_protocol_from_env, GraphicsProtocol};
+use std::collections::HashMap;
+use std::fs;
+use tempfile::TempDir;
+
+/// A solid red PNG of the given size
+fn red_png(width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut bytes, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        let pixels: Vec<u8> = (0..width * height).flat_map(|_| [255, 0, 0]).collect();
+        writer.write_image_data(&pixels).unwrap();
+    }
+    bytes
+}
+
+#[test]
+fn test_png_dimensions_and_fit() {
+    assert_eq!(png_dimensions(&red_png(300, 450)), Some((300, 450)));
+    assert_eq!(png_dimensions(b"not a png at all, just some text"), None);
+
+    // A 2:3 poster in a wide area is limited by height; cells are twice as tall as wide
+    assert_eq!(fit_cells(300, 450, 60, 10), (13, 10));
+    // A wide image in a narrow area is limited by width
+    assert_eq!(fit_cells(1920, 1080, 20, 20), (20, 5));
+    assert_eq!(fit_cells(0, 10, 20, 20), (0, 0));
+}
+
+#[test]
+fn test_find_artwork_prefers_episode_thumbnail() {
+    let dir = TempDir::new().unwrap();
+    let season = dir.path().join("Show").join("Season 1");
+    fs::create_dir_all(&season).unwrap();
+    let video = season.join("S01E01.mkv");
+    fs::write(&video, b"").unwrap();
+
+    assert_eq!(find_episode_artwork(&video), None);
+
+    // The series poster one folder up is shared by every episode
+    let poster = dir.path().join("Show").join("poster.png");
+    fs::write(&poster, red_png(2, 3)).unwrap();
+    assert_eq!(find_folder_artwork(&video), Some(poster.clone()));
+    assert_eq!(find_episode_artwork(&video), Some(poster));
+
+    let thumbnail = season.join("S01E01-thumb.png");
+    fs::write(&thumbnail, red_png(2, 3)).unwrap();
+    assert_eq!(find_episode_artwork(&video), Some(thumbnail));
+}
+
+#[test]
+fn test_kitty_sequence_is_chunked() {
+    // The payload isn't decoded here, so any bytes big enough to need several chunks will do
+    let sequence = kitty_sequence(&[7u8; 10_000], 10, 5);
+    assert!(sequence.starts_with("\x1b_Ga=T,f=100,i=1,p=1,c=10,r=5,C=1,q=2,"));
+    assert!(sequence.ends_with("\x1b\\"));
+    let chunks = sequence.matches("\x1b_G").count();
+    assert_eq!(chunks, 4, "10,000 bytes are 13,336 base64 characters");
+    assert_eq!(chunks, sequence.matches("m=1;").count() + 1, "Every chunk but the last says more follow");
+    assert!(sequence.contains("\x1b_Gm=0;"), "The last chunk closes the transfer");
+}
+
+#[test]
+fn test_sixel_sequence_scales_to_cells() {
+    let sequence = sixel_sequence(&red_png(4, 4), 2, 1).unwrap();
+    assert!(sequence.starts_with(&format!("\x1bPq\"1;1;{};{}", 2 * CELL_WIDTH_PX, CELL_HEIGHT_PX)));
+    assert!(sequence.contains("#180;2;100;0;0"), "Pure red is defined in the palette");
+    assert_eq!(sequence.matches('-').count(), CELL_HEIGHT_PX.div_ceil(6), "One band per six pixel rows");
+    assert!(sequence.ends_with("\x1b\\"));
+    assert_eq!(sixel_sequence(b"not a png", 2, 1), None);
+}
+
+#[test]
+fn test_graphics_protocol_from_env() {
+    let detect = |vars: &[(&str, &str)]| {
+        let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        graphics_protocol_from_env(|name| vars.get(name).cloned())
+    };
+    assert_eq!(detect(&[("TERM", "xterm-kitty")]), Some(GraphicsProtocol::Kitty));
+    assert_eq!(detect(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")]), Some(GraphicsProtocol::Kitty));
+    assert_eq!(detect(&[("TERM", "foot")]), Some(GraphicsProtocol::Sixel));
+    assert_eq!(detect(&[("TERM", "xterm-256color")]), None);
+    assert_eq!(detect(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux-1000/default,1,0")]), None);
+}
@@ -1031,3 +1031,94 @@ fn test_mode_change_triggers_full_redraw() {
     let result = manager.render_to_terminal();
     assert!(result.is_ok(), "Browse mode render after second mode change should succeed");
 }
+
+#[test]
+fn test_desired_text_and_ansi_export() {
+    let mut manager = BufferManager::new(10, 2);
+    {
+        let mut writer = manager.get_writer();
+        writer.move_to(0, 0);
+        writer.write_str("Hi");
+        writer.move_to(0, 1);
+        writer.set_fg_color(Color::Red);
+        writer.write_str("Red");
+    }
+
+    assert_eq!(manager.desired_text(), "Hi\nRed\n");
+
+    let ansi = manager.desired_ansi();
+    assert!(ansi.contains("\x1b[38;5;9m\x1b[49mRed"));
+    assert_eq!(ansi.lines().count(), 2);
+}
+
+#[test]
+fn test_write_char_fills_the_tail_of_wide_characters() {
+    let mut buffer = ScreenBuffer::new(6, 1);
+    {
+        let mut writer = BufferWriter::new(&mut buffer);
+        writer.write_str("a進b");
+        // Writing over the tail blanks the wide character it belonged to
+        writer.move_to(2, 0);
+        writer.write_char('x');
+        // A wide character that would be cut by the edge is drawn as a space
+        writer.move_to(5, 0);
+        writer.write_char('人');
+    }
+
+    assert_eq!(buffer.get_cell(0, 0).unwrap().character, 'a');
+    assert_eq!(buffer.get_cell(1, 0).unwrap().character, ' ');
+    assert_eq!(buffer.get_cell(2, 0).unwrap().character, 'x');
+    assert_eq!(buffer.get_cell(3, 0).unwrap().character, 'b');
+    assert_eq!(buffer.get_cell(5, 0).unwrap().character, ' ');
+}
+
+#[test]
+fn test_render_to_writes_only_the_changed_cells_and_the_cursor() {
+    let mut manager = BufferManager::new(20, 5);
+    manager.get_writer().write_str("Hello");
+    let mut out = Vec::new();
+    manager.render_to(&mut out).unwrap();
+    let first = String::from_utf8(out).unwrap();
+    assert!(first.contains("Hello"));
+    assert!(first.starts_with("\u{1b}[?25l"), "The cursor is hidden while cells are written");
+
+    // One changed letter is all that is written, with the cursor put back in the input field
+    manager.clear_desired_buffer();
+    manager.get_writer().write_str("Hallo");
+    manager.set_cursor(Some((3, 2)));
+    let mut out = Vec::new();
+    manager.render_to(&mut out).unwrap();
+    let second = String::from_utf8(out).unwrap();
+    assert!(second.contains('a') && !second.contains("llo"), "{:?}", second);
+    assert!(second.ends_with("\u{1b}[3;4H\u{1b}[?25h"), "{:?}", second);
+
+    // The same frame again writes nothing at all
+    manager.clear_desired_buffer();
+    manager.get_writer().write_str("Hallo");
+    manager.set_cursor(Some((3, 2)));
+    let mut out = Vec::new();
+    manager.render_to(&mut out).unwrap();
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_render_to_moves_the_cursor_without_redrawing() {
+    let mut manager = BufferManager::new(20, 5);
+    manager.get_writer().write_str("filter: ab");
+    manager.set_cursor(Some((10, 0)));
+    manager.render_to(&mut Vec::new()).unwrap();
+
+    manager.clear_desired_buffer();
+    manager.get_writer().write_str("filter: ab");
+    manager.set_cursor(Some((9, 0)));
+    let mut out = Vec::new();
+    manager.render_to(&mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "\u{1b}[1;10H\u{1b}[?25h");
+
+    // Leaving the field hides the cursor
+    manager.clear_desired_buffer();
+    manager.get_writer().write_str("filter: ab");
+    let mut out = Vec::new();
+    manager.render_to(&mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "\u{1b}[?25l");
+}
@@ -0,0 +1,88 @@
+use movies::cli::*;
+use movies::config::{generate_yaml_with_comments, Config};
+use std::path::PathBuf;
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+fn no_env(_: &str) -> Option<String> {
+    None
+}
+
+#[test]
+fn test_no_arguments_override_nothing() {
+    assert_eq!(parse_args(&[], no_env), Ok(Command::Run(Overrides::default())));
+}
+
+#[test]
+fn test_flags_override_config_values() {
+    let command = parse_args(&args(&["--db", "/tmp/test.sqlite", "--config=/tmp/test.yaml", "--log-level", "debug"]), no_env);
+    assert_eq!(
+        command,
+        Ok(Command::Run(Overrides {
+            db: Some(PathBuf::from("/tmp/test.sqlite")),
+            config: Some(PathBuf::from("/tmp/test.yaml")),
+            log_level: Some("debug".to_string()),
+            portable: false,
+        }))
+    );
+}
+
+#[test]
+fn test_flags_win_over_environment_variables() {
+    let env = |name: &str| match name {
+        "MOVIES_DB" => Some("/env/videos.sqlite".to_string()),
+        "MOVIES_LOG_LEVEL" => Some("warn".to_string()),
+        "MOVIES_CONFIG" => Some("".to_string()),
+        _ => None,
+    };
+    let Ok(Command::Run(overrides)) = parse_args(&args(&["--log-level", "error"]), env) else {
+        panic!("expected overrides");
+    };
+    assert_eq!(overrides.db, Some(PathBuf::from("/env/videos.sqlite")));
+    assert_eq!(overrides.config, None, "An empty variable is left out");
+    assert_eq!(overrides.log_level.as_deref(), Some("error"));
+}
+
+#[test]
+fn test_portable_flag() {
+    let Ok(Command::Run(overrides)) = parse_args(&args(&["--portable", "--log-level=info"]), no_env) else {
+        panic!("expected overrides");
+    };
+    assert!(overrides.portable);
+    assert_eq!(overrides.log_level.as_deref(), Some("info"));
+}
+
+#[test]
+fn test_bad_arguments_are_errors() {
+    assert_eq!(parse_args(&args(&["--db"]), no_env), Err("--db needs a value".to_string()));
+    assert_eq!(parse_args(&args(&["--library", "x"]), no_env), Err("Unknown argument '--library'".to_string()));
+    assert!(parse_args(&args(&["--log-level", "loud"]), no_env).unwrap_err().contains("'loud'"));
+    let env = |_: &str| Some("verbose".to_string());
+    assert!(parse_args(&[], env).is_err());
+}
+
+#[test]
+fn test_demo_and_help() {
+    assert_eq!(parse_args(&args(&["--demo"]), no_env), Ok(Command::Demo(PathBuf::from("demo-screenshots"))));
+    assert_eq!(parse_args(&args(&["--demo", "shots"]), no_env), Ok(Command::Demo(PathBuf::from("shots"))));
+    assert_eq!(parse_args(&args(&["--db", "x", "--help"]), no_env), Ok(Command::Help));
+}
+
+#[test]
+fn test_database_override_is_never_saved() {
+    let mut config = Config {
+        db_location: Some("/real/videos.sqlite".to_string()),
+        db_override: Some("/test/videos.sqlite".to_string()),
+        ..Config::default()
+    };
+    assert_eq!(config.get_database_path(), Some(PathBuf::from("/test/videos.sqlite")));
+
+    config.set_database_path(PathBuf::from("/moved/videos.sqlite"));
+    assert_eq!(config.get_database_path(), Some(PathBuf::from("/moved/videos.sqlite")));
+    let saved = generate_yaml_with_comments(&config);
+    assert!(saved.contains("/real/videos.sqlite"));
+    assert!(!saved.contains("/test/") && !saved.contains("/moved/"));
+    assert!(!config.is_first_run());
+}
@@ -0,0 +1,28 @@
+use movies::clipboard::episode_title;
+use movies::dto::{EpisodeDetail, Season, Series};
+
+fn details(title: &str, series: Option<&str>, season: Option<usize>, episode: &str) -> EpisodeDetail {
+    EpisodeDetail {
+        title: title.to_string(),
+        series: series.map(|name| Series { id: 1, name: name.to_string() }),
+        season: season.map(|number| Season { id: 1, number }),
+        episode_number: episode.to_string(),
+        ..EpisodeDetail::default()
+    }
+}
+
+#[test]
+fn test_episode_titles_name_the_series_season_and_episode() {
+    assert_eq!(episode_title(&details("Undertow", Some("The Wire"), Some(2), "5")), "The Wire S02E05 \u{2014} Undertow");
+    assert_eq!(episode_title(&details("Undertow", Some("The Wire"), Some(2), "")), "The Wire S02 \u{2014} Undertow");
+    assert_eq!(episode_title(&details("Pilot", Some("Andor"), None, "1")), "Andor E01 \u{2014} Pilot");
+    assert_eq!(episode_title(&details("Special", Some("Andor"), None, "")), "Andor \u{2014} Special");
+}
+
+#[test]
+fn test_movie_titles_have_their_year() {
+    let mut movie = details("Heat", None, None, "");
+    assert_eq!(episode_title(&movie), "Heat");
+    movie.year = "1995".to_string();
+    assert_eq!(episode_title(&movie), "Heat (1995)");
+}
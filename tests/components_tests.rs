@@ -1,6 +1,6 @@
 use movies::components::*;
 use movies::components::episode::Episode;
-use movies::theme::Theme;
+use movies::theme::{string_to_color, Theme};
 
 #[test]
 fn test_component_trait_signature() {
@@ -154,4 +154,485 @@ fn test_empty_browser_edge_cases() {
     // Test zero dimensions
     let result_zero = empty_browser.render(0, 0, &theme, false);
     assert_eq!(result_zero.len(), 0, "Zero height should return empty result");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_notes_editor_scrolls_to_keep_cursor_visible() {
+    let theme = Theme::default();
+    let notes = "stopped at 32:00, bad audio sync after the intro".to_string();
+    let cursor = notes.chars().count();
+
+    // 20 wide window leaves 18 columns for text
+    let editor = NotesEditor::new(notes, cursor, 20);
+    let result = editor.render(20, 4, &theme, false);
+
+    assert_eq!(result.len(), 4, "Notes window should have borders, prompt, and input rows");
+    assert!(result.iter().all(|row| row.len() == 20));
+    assert_eq!(result[0][0].character, '╔');
+    let input: String = result[2].iter().map(|c| c.character).collect();
+    assert_eq!(input, "║c after the intro ║");
+    assert_eq!(editor.cursor_column(), 18);
+}
+
+#[test]
+fn test_alt_titles_draft_round_trip_and_editor_rows() {
+    use movies::components::alt_titles_editor::AltTitlesDraft;
+
+    let theme = Theme::default();
+    let mut titles = movies::dto::AltTitles::new();
+    titles.insert("ja".to_string(), "Sen to Chihiro".to_string());
+
+    let mut draft = AltTitlesDraft::from_titles(&titles);
+    assert_eq!(draft.rows, vec!["ja: Sen to Chihiro".to_string(), String::new()]);
+    assert_eq!(draft.selected, 1);
+
+    draft.rows[1] = "FR : Le Voyage de Chihiro".to_string();
+    draft.normalize();
+    assert_eq!(draft.rows.len(), 3, "A new empty row follows the one being typed");
+    let parsed = draft.to_titles().unwrap();
+    assert_eq!(parsed.get("fr").map(String::as_str), Some("Le Voyage de Chihiro"));
+    assert_eq!(parsed.len(), 2);
+
+    draft.rows[0] = "no separator".to_string();
+    assert!(draft.to_titles().is_err());
+
+    let editor = AltTitlesEditor::new(&draft, 3, 30);
+    let result = editor.render(30, editor.height(), &theme, false);
+    assert_eq!(result.len(), 6, "Borders, prompt, and one row per title");
+    assert!(result.iter().all(|row| row.len() == 30));
+    assert_eq!(editor.cursor_row(), 3);
+    assert_eq!(editor.cursor_column(), 4);
+}
+
+#[test]
+fn test_tutorial_overlay_wraps_step_text_inside_borders() {
+    let theme = Theme::default();
+    let tutorial = movies::tutorial::Tutorial::new();
+
+    let overlay = TutorialOverlay::new(&tutorial, 30);
+    let result = overlay.render(30, overlay.height(), &theme, false);
+
+    assert_eq!(result.len(), overlay.height());
+    assert!(result.iter().all(|row| row.len() == 30));
+    assert_eq!(result[0][0].character, '╔');
+    let title: String = result[1].iter().map(|c| c.character).collect();
+    assert!(title.starts_with("║ Tutorial 1/7: Welcome"));
+    let footer: String = result[result.len() - 2].iter().map(|c| c.character).collect();
+    assert!(footer.contains("[ESC] end tutorial"));
+}
+
+#[test]
+fn test_category_renders_completion_bar_and_percent() {
+    let mut theme = Theme::default();
+    let category = Category::new("[The Wire]".to_string(), 5, 3, CategoryType::Series);
+
+    theme.progress_display = "bar".to_string();
+    let result = category.render(30, 1, &theme, false);
+    let text: String = result[0].iter().map(|c| c.character).collect();
+    assert!(text.starts_with("[The Wire]"));
+    assert!(text.ends_with("▓▓▓░░ 60%"));
+    assert_eq!(result[0].len(), 30);
+
+    theme.progress_display = "percent".to_string();
+    let result = category.render(30, 1, &theme, false);
+    let text: String = result[0].iter().map(|c| c.character).collect();
+    assert!(text.ends_with(" 60%"));
+    assert!(!text.contains('▓'));
+
+    let empty = Category::new("Season 1".to_string(), 0, 0, CategoryType::Season);
+    assert_eq!(empty.watched_percent(), 0);
+}
+
+#[test]
+fn test_category_indicator_for_unwatched_and_new_episodes() {
+    let theme = Theme::default();
+    let text = |category: &Category| -> String {
+        category.render(30, 1, &theme, false)[0].iter().map(|c| c.character).collect()
+    };
+
+    let unwatched = Category::new("[Lost]".to_string(), 4, 2, CategoryType::Series);
+    assert!(text(&unwatched).starts_with("[Lost] ● "));
+
+    let new = unwatched.clone().with_new_count(3);
+    assert!(text(&new).starts_with("[Lost] (3 new) "));
+    let result = new.render(30, 1, &theme, false);
+    assert_eq!(result[0].len(), 30);
+    assert_eq!(result[0][8].fg_color, string_to_color(&theme.category_indicator_fg).unwrap());
+
+    let watched = Category::new("[Lost]".to_string(), 4, 4, CategoryType::Series);
+    assert!(text(&watched).starts_with("[Lost]  "));
+    assert!(!text(&watched).contains('●'));
+}
+
+#[test]
+fn test_toast_renders_a_bordered_message() {
+    let theme = Theme::default();
+    let toast = Toast::new("Marked 4 episodes as watched");
+    assert_eq!(toast.width(80), 32);
+    let result = toast.render(80, 3, &theme, false);
+    let rows: Vec<String> = result.iter().map(|row| row.iter().map(|c| c.character).collect()).collect();
+    assert_eq!(rows[0], "╔══════════════════════════════╗");
+    assert_eq!(rows[1], "║ Marked 4 episodes as watched ║");
+    assert_eq!(rows[2], "╚══════════════════════════════╝");
+
+    // Narrow screens cut the message instead of the border
+    let result = toast.render(12, 3, &theme, false);
+    let middle: String = result[1].iter().map(|c| c.character).collect();
+    assert_eq!(middle, "║ Marked 4 ║");
+}
+
+#[test]
+fn test_context_menu_item_at_maps_clicks_to_rows() {
+    use movies::menu::{MenuAction, MenuItem, MenuLocation};
+    let item = |label: &str, action| MenuItem {
+        label: label.to_string(),
+        hotkey: None,
+        action,
+        location: MenuLocation::ContextMenu,
+    };
+    let menu = ContextMenu::new(vec![item("edit", MenuAction::Edit), item("rescan", MenuAction::Rescan)], 0);
+
+    // "rescan" is the widest label: 6 + 1 + 4 = 11 columns at the right edge of an 80 column terminal
+    assert_eq!(menu.item_at(80, 75, 1), Some(0));
+    assert_eq!(menu.item_at(80, 69, 2), Some(1));
+    assert_eq!(menu.item_at(80, 75, 0), None, "Top border");
+    assert_eq!(menu.item_at(80, 75, 3), None, "Bottom border");
+    assert_eq!(menu.item_at(80, 68, 1), None, "Left of the menu");
+}
+
+#[test]
+fn test_browser_marker_column_for_marked_items() {
+    let theme = Theme::default();
+    let episodes = vec![
+        Episode::new("One".to_string(), false, true, false),
+        Episode::new("Two".to_string(), false, true, false),
+    ];
+    let mut browser = Browser::new((0, 0), 20, vec![], episodes);
+    let text = |rows: &Vec<Vec<Cell>>, index: usize| rows[index].iter().map(|cell| cell.character).collect::<String>();
+
+    let plain = browser.render(20, 2, &theme, false);
+
+    browser.marked_items.insert(1);
+    let marked = browser.render(20, 2, &theme, false);
+    assert_eq!(text(&marked, 0), format!("  {}", &text(&plain, 0)[..text(&plain, 0).len() - 2]), "Unmarked rows are indented");
+    assert!(text(&marked, 1).starts_with("* "), "Marked rows show the marker: {:?}", text(&marked, 1));
+    assert_eq!(marked[1].len(), 20, "The marker column fits within the width");
+}
+
+#[test]
+fn test_confirm_dialog_highlights_selected_button() {
+    let theme = Theme::default();
+    let mut dialog = ConfirmDialog::new("Unwatch all", "Mark every standalone episode as unwatched?");
+    let width = 40;
+    let height = dialog.height(width);
+
+    let rows = dialog.render(width, height, &theme, false);
+    assert_eq!(rows.len(), height);
+    assert!(rows.iter().all(|row| row.len() == width));
+    assert_eq!(rows[0][0].character, '╔');
+    assert_eq!(rows[height - 1][0].character, '╚');
+
+    // Buttons share the last content row; the highlighted one is drawn bold
+    let buttons = &rows[height - 2];
+    let yes = buttons.iter().position(|c| c.character == 'Y').unwrap();
+    let no = buttons.iter().position(|c| c.character == 'N').unwrap();
+    assert!(!buttons[yes].style.bold && buttons[no].style.bold, "No starts highlighted");
+
+    dialog.toggle();
+    let rows = dialog.render(width, height, &theme, false);
+    assert!(rows[height - 2][yes].style.bold, "Yes is highlighted after toggling");
+}
+
+#[test]
+fn test_theme_picker_starts_on_active_theme() {
+    let theme = Theme::default();
+    let names = vec![
+        "THEME-dark.yaml".to_string(),
+        "THEME-default.yaml".to_string(),
+        "THEME-solarized.yaml".to_string(),
+    ];
+    let mut picker = ThemePicker::new(names, "THEME-default.yaml", theme.clone());
+    assert_eq!(picker.selected_name(), Some("THEME-default.yaml"));
+
+    let rows = picker.render(80, 24, &theme, false);
+    assert_eq!(rows.len(), 5, "Borders plus one row per theme");
+    let row: String = rows[2].iter().map(|c| c.character).collect();
+    assert!(row.contains("default") && !row.contains("THEME-"), "{}", row);
+
+    assert!(picker.move_selection(true));
+    assert!(!picker.move_selection(true), "The highlight stops at the last theme");
+    assert_eq!(picker.selected_name(), Some("THEME-solarized.yaml"));
+}
+
+#[test]
+fn test_dialog_borders_follow_theme() {
+    let theme = Theme {
+        active_border_style: "ascii".to_string(),
+        ..Theme::default()
+    };
+    let dialog = ConfirmDialog::new("Delete", "Remove 'Pilot' from the library?");
+    let rows = dialog.render(30, dialog.height(30), &theme, false);
+
+    let top: String = rows[0].iter().map(|c| c.character).collect();
+    assert_eq!(top, format!("+{}+", "-".repeat(28)));
+    assert_eq!(rows[1][0].character, '|');
+    assert!(rows.iter().flatten().all(|cell| cell.character.is_ascii()), "Only ASCII characters are drawn");
+}
+
+#[test]
+fn test_filter_matches_are_highlighted() {
+    use crossterm::style::Color;
+    use std::ops::Range;
+
+    let theme = Theme::default();
+    let episode = Episode::new("The Office".to_string(), false, true, false).with_highlights(vec![Range { start: 4, end: 10 }]);
+    let row = &episode.render(30, 1, &theme, false)[0];
+
+    // The unwatched indicator and its space come before the name
+    assert_eq!(row[6].character, 'O');
+    assert_eq!((row[6].fg_color, row[6].bg_color), (Color::Black, Color::Yellow));
+    assert_ne!(row[5].bg_color, Color::Yellow, "Only the match is highlighted");
+
+    // The selected row keeps its colors and underlines the match
+    let selected = &episode.render(30, 1, &theme, true)[0];
+    assert_eq!(selected[6].bg_color, selected[0].bg_color);
+    assert!(selected[6].style.underlined && !selected[5].style.underlined);
+
+    let series = Category::new("[The Office]".to_string(), 3, 1, CategoryType::Series).with_highlights(vec![Range { start: 5, end: 11 }]);
+    let row = &series.render(40, 1, &theme, false)[0];
+    assert_eq!(row[5].bg_color, Color::Yellow);
+    assert_ne!(row[11].bg_color, Color::Yellow);
+}
+
+#[test]
+fn test_grid_browser_lays_out_tiles_and_scrolls_by_row() {
+    use movies::components::grid_browser::{TILE_HEIGHT, TILE_WIDTH};
+
+    let theme = Theme::default();
+    let categories = (1..=7)
+        .map(|number| Category::new(format!("Season {}", number), 10, 3, CategoryType::Season))
+        .collect();
+    let mut browser = Browser::new((0, 0), 45, categories, vec![]);
+    browser.set_selected_item(6);
+    let mut grid = GridBrowser::new(browser);
+    assert_eq!(GridBrowser::columns_for(45), 3);
+
+    // Two rows of tiles fit, so the third row scrolls into view
+    grid.ensure_selection_visible(2 * TILE_HEIGHT);
+    assert_eq!(grid.browser.first_visible_item, 3, "Scrolled by one whole row");
+
+    let rows = grid.render(45, 2 * TILE_HEIGHT, &theme, false);
+    assert_eq!(rows.len(), 2 * TILE_HEIGHT);
+    let text = |row: &Vec<Cell>| row.iter().map(|cell| cell.character).collect::<String>();
+    assert!(text(&rows[0]).starts_with("Season 4"), "{}", text(&rows[0]));
+    assert!(text(&rows[1]).contains("3/10"), "{}", text(&rows[1]));
+    assert_eq!(rows[TILE_HEIGHT][0].character, 'S', "Season 7 starts the second row");
+    assert_eq!(rows[TILE_HEIGHT][TILE_WIDTH].character, ' ', "Nothing after the last tile");
+
+    assert_eq!(GridBrowser::item_at(45, TILE_WIDTH + 2, 1, 3, 7), Some(4));
+    assert_eq!(GridBrowser::item_at(45, TILE_WIDTH - 1, 0, 3, 7), None, "The gap between tiles");
+    assert_eq!(GridBrowser::item_at(45, TILE_WIDTH, TILE_HEIGHT, 3, 7), None, "Past the last tile");
+}
+
+#[test]
+fn test_empty_state_explains_the_empty_view() {
+    use movies::util::ViewContext;
+
+    let theme = Theme::default();
+    let text = |row: &Vec<Cell>| row.iter().map(|cell| cell.character).collect::<String>();
+
+    let filtered = EmptyState::for_view(&ViewContext::TopLevel, "zzz", false);
+    assert_eq!(filtered.title, "Nothing matches \"zzz\"");
+    assert!(filtered.suggestions.iter().any(|s| s.contains("Esc to clear the filter")));
+
+    let season = ViewContext::Season {
+        season_id: 1,
+        series_name: "Firefly".to_string(),
+        season_number: 2,
+    };
+    let empty_season = EmptyState::for_view(&season, "", true);
+    assert_eq!(empty_season.title, "Firefly season 2 has no episodes");
+    assert_eq!(empty_season.suggestions.last().map(String::as_str), Some("Press 1-3 to switch pinned filters off"));
+
+    let library = EmptyState::for_view(&ViewContext::TopLevel, "", false);
+    let rows = library.render(40, 11, &theme, false);
+    assert_eq!(rows.len(), 11);
+    assert!(rows.iter().all(|row| row.len() == 40));
+    // Title, blank line and two suggestions, centered both ways
+    assert_eq!(text(&rows[3]).trim(), "Your library is empty");
+    assert!(rows[3][text(&rows[3]).find('Y').unwrap()].style.bold);
+    assert_eq!(text(&rows[3]).find('Y'), Some((40 - "Your library is empty".len()) / 2));
+    assert_eq!(text(&rows[5]).trim(), "Press s to rescan for videos");
+}
+
+#[test]
+fn test_help_bar_drops_bindings_that_do_not_fit() {
+    use movies::keybindings::KeyBinding;
+
+    let theme = Theme::default();
+    let binding = |keys: &str, description: &str| KeyBinding {
+        keys: keys.to_string(),
+        description: description.to_string(),
+    };
+    let bar = HelpBar::new(vec![binding("Enter", "play"), binding("F2", "edit"), binding("?", "help")]);
+    let text = |width: usize| bar.render(width, 1, &theme, false)[0].iter().map(|cell| cell.character).collect::<String>();
+    assert_eq!(text(40).trim_end(), "Enter play  F2 edit  ? help");
+    assert_eq!(text(20).trim_end(), "Enter play  F2 edit", "The last binding is left off whole");
+
+    let row = &bar.render(30, 1, &theme, false)[0];
+    assert_eq!(row.len(), 30);
+    assert!(row[0].style.bold && !row[6].style.bold, "Keys are bold, descriptions are not");
+}
+
+#[test]
+fn test_episode_with_wide_title_renders_one_cell_per_column() {
+    let theme = Theme {
+        unwatched_indicator: String::new(),
+        ..Theme::default()
+    };
+    let episode = Episode::new("進撃の巨人".to_string(), false, true, false);
+
+    let row = &episode.render(7, 1, &theme, false)[0];
+    let characters: Vec<char> = row.iter().map(|cell| cell.character).collect();
+    // The third character would need columns 7 and 8, so it is left off
+    assert_eq!(
+        characters,
+        vec!['進', movies::text::WIDE_CHAR_TAIL, '撃', movies::text::WIDE_CHAR_TAIL, 'の', movies::text::WIDE_CHAR_TAIL]
+    );
+}
+
+#[test]
+fn test_browser_scroll_margin_keeps_rows_around_the_selection() {
+    let episodes = (1..=20)
+        .map(|number| Episode::new(format!("Episode {}", number), false, true, false))
+        .collect();
+    let mut browser = Browser::new((0, 0), 40, vec![], episodes);
+    browser.scroll_margin = 2;
+
+    // Scrolls down while two rows remain below the selection
+    browser.set_selected_item(8);
+    browser.ensure_selection_visible(10);
+    assert_eq!(browser.first_visible_item, 1);
+
+    // Scrolls up while two rows remain above it
+    browser.set_selected_item(2);
+    browser.ensure_selection_visible(10);
+    assert_eq!(browser.first_visible_item, 0);
+    browser.first_visible_item = 5;
+    browser.set_selected_item(6);
+    browser.ensure_selection_visible(10);
+    assert_eq!(browser.first_visible_item, 4);
+
+    // The last item can still be reached
+    browser.set_selected_item(19);
+    browser.ensure_selection_visible(10);
+    assert_eq!(browser.first_visible_item, 10);
+
+    // A margin larger than half the viewport is reduced
+    browser.scroll_margin = 50;
+    browser.first_visible_item = 0;
+    browser.set_selected_item(12);
+    browser.ensure_selection_visible(4);
+    assert_eq!(browser.first_visible_item, 10);
+}
+
+#[test]
+fn test_windowed_browser_draws_like_the_whole_list() {
+    let theme = Theme::default();
+    let episode = |number: usize| Episode::new(format!("Episode {}", number), number > 50, true, false);
+    let mut whole = Browser::new((0, 0), 40, vec![Category::new("Season 1".to_string(), 3, 1, CategoryType::Season)], (1..=99).map(episode).collect());
+    whole.set_selected_item(60);
+    whole.ensure_selection_visible(10);
+    let first = whole.first_visible_item;
+
+    // Only the items on screen are held, yet the scrollbar and selection cover the whole list
+    let mut window = Browser::windowed((0, 0), 40, 100);
+    window.set_selected_item(60);
+    window.ensure_selection_visible(10);
+    assert_eq!(window.first_visible_item, first);
+    window.set_window(first, vec![], (first..first + 10).map(episode).collect());
+    assert_eq!(window.total_items(), 100);
+    assert!(window.episode_at(first).is_some() && window.episode_at(first - 1).is_none());
+    assert!(window.category_at(0).is_none());
+    assert_eq!(window.render(40, 10, &theme, true), whole.render(40, 10, &theme, true));
+}
+
+#[test]
+fn test_header_shows_filtered_and_view_counts() {
+    use movies::components::header::{Breadcrumbs, FilterLine};
+    use movies::util::{SortMode, ViewContext};
+
+    let filter_line = FilterLine::new("pilot".to_string(), false, String::new(), String::new(), String::new(), SortMode::Default, Some((37, 412)));
+    assert_eq!(filter_line.render(), "filter: pilot    37 / 412 entries");
+    let filter_line = FilterLine::new(String::new(), false, String::new(), "[1]*Lost".to_string(), String::new(), SortMode::Default, None);
+    assert_eq!(filter_line.render(), "tabs: [1]*Lost");
+    let filter_line = FilterLine::new(String::new(), false, String::new(), String::new(), "#12".to_string(), SortMode::Default, None);
+    assert_eq!(filter_line.render(), "#12");
+
+    let season = ViewContext::Season {
+        season_id: 4,
+        series_name: "Show".to_string(),
+        season_number: 2,
+    };
+    let breadcrumbs = Breadcrumbs::new(season.clone(), Some((8, 3)));
+    assert_eq!(breadcrumbs.render(), "Browsing [Show] -> [season 2]    Season 2 \u{2014} 8 episodes, 3 unwatched");
+    assert_eq!(Breadcrumbs::new(season, None).render(), "Browsing [Show] -> [season 2]");
+
+    let series = ViewContext::Series {
+        series_id: 1,
+        series_name: "Show".to_string(),
+    };
+    assert_eq!(
+        Breadcrumbs::new(series, Some((1, 0))).render(),
+        "Browsing [Show]    Show \u{2014} 1 episode, 0 unwatched"
+    );
+}
+
+#[test]
+fn test_browser_group_headers_take_rows_but_not_the_selection() {
+    use movies::components::browser::BrowserRow;
+
+    let theme = Theme::default();
+    let seasons = (1..=3)
+        .map(|number| Category::new(format!("Season {}", number), 10, 3, CategoryType::Season))
+        .collect();
+    let episodes = vec![Episode::new("Pilot".to_string(), false, true, false)];
+    let mut browser = Browser::new((0, 0), 40, seasons, episodes);
+    browser.group_headers = vec![(0, "Seasons".to_string()), (3, "Unassigned episodes".to_string())];
+
+    assert_eq!(browser.total_rows(), 6);
+    assert_eq!(
+        Browser::layout_rows(&browser.group_headers, 0, 4, 10),
+        vec![
+            BrowserRow::Header("Seasons".to_string()),
+            BrowserRow::Item(0),
+            BrowserRow::Item(1),
+            BrowserRow::Item(2),
+            BrowserRow::Header("Unassigned episodes".to_string()),
+            BrowserRow::Item(3),
+        ]
+    );
+    assert_eq!(Browser::item_at_row(&browser.group_headers, 0, 4, 0), None);
+    assert_eq!(Browser::item_at_row(&browser.group_headers, 0, 4, 5), Some(3));
+
+    let rows = browser.render(40, 6, &theme, true);
+    let text = |row: &Vec<Cell>| row.iter().map(|cell| cell.character).collect::<String>();
+    assert!(text(&rows[0]).starts_with("Seasons"));
+    assert!(text(&rows[4]).starts_with("Unassigned episodes"));
+    assert!(text(&rows[5]).contains("Pilot"));
+
+    // The last item is brought into view with its header in a short viewport
+    browser.set_selected_item(3);
+    browser.ensure_selection_visible(3);
+    assert_eq!(browser.first_visible_item, 2);
+}
+
+#[test]
+fn test_offline_episodes_are_not_drawn_as_missing() {
+    let theme = Theme::default();
+    let offline = Episode::new("Movie".to_string(), false, true, false).with_offline(true);
+    let missing = Episode::new("Movie".to_string(), false, false, false);
+    let name_cell = |episode: &Episode| episode.render(30, 1, &theme, false)[0][2].fg_color;
+    assert_eq!(name_cell(&offline), string_to_color(&theme.offline_fg).unwrap());
+    assert_eq!(name_cell(&missing), string_to_color(&theme.invalid_fg).unwrap());
+}
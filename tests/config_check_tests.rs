@@ -0,0 +1,74 @@
+use movies::config::Config;
+use movies::config_check::*;
+
+fn config_with(player: &str) -> Config {
+    Config {
+        video_player: player.to_string(),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn test_default_values_have_no_problems() {
+    let problems = check_values(&config_with("sh"));
+    assert!(problems.is_empty(), "{:?}", problems);
+}
+
+#[test]
+fn test_unknown_keys_suggest_the_setting_meant() {
+    let content = "vidoe_player: mpv\nvideo_extensions: [mkv]\nvideo_player: mpv\nfoo: 1\n";
+    let problems = unknown_keys(content);
+    assert_eq!(problems.len(), 2);
+    assert!(problems[0].contains("'vidoe_player'") && problems[0].contains("did you mean 'video_player'"));
+    assert!(problems[1].contains("'foo'") && !problems[1].contains("did you mean"));
+}
+
+#[test]
+fn test_settings_left_out_when_unset_are_known() {
+    let content = "list_split: 40\npreferred_version: 4K\ntrakt_client_id: abc\ndb_location: /tmp/db\n";
+    assert!(unknown_keys(content).is_empty());
+    assert!(known_keys().contains(&"keybindings".to_string()));
+}
+
+#[test]
+fn test_missing_player_is_reported() {
+    let problems = check_values(&config_with("/nonexistent/bin/player"));
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("'/nonexistent/bin/player' was not found"));
+    assert!(!player_exists("no-such-player-anywhere"));
+    assert!(player_exists("sh"));
+}
+
+#[test]
+fn test_bad_extensions_are_reported() {
+    let config = Config {
+        video_extensions: vec![".mkv".to_string(), "mp4".to_string(), "m 4v".to_string(), "".to_string()],
+        ..config_with("sh")
+    };
+    let problems = check_values(&config);
+    assert_eq!(problems.len(), 3, "{:?}", problems);
+    assert!(problems[0].contains("without the dot, as 'mkv'"));
+    assert!(problems[1].contains("'m 4v'"));
+    assert!(problems[2].contains("empty entry"));
+}
+
+#[test]
+fn test_values_a_setting_does_not_take_are_reported() {
+    let config = Config {
+        watched_threshold: 0,
+        log_level: "verbose".to_string(),
+        sample_files: "maybe".to_string(),
+        default_sort: "size_desc".to_string(),
+        startup_view: "favorites".to_string(),
+        rename_template: "{show}.{ext}".to_string(),
+        ..config_with("sh")
+    };
+    let problems = check_values(&config);
+    assert_eq!(problems.len(), 6, "{:?}", problems);
+    assert!(problems[0].starts_with("watched_threshold 0"));
+    assert!(problems[1].contains("log_level 'verbose'"));
+    assert!(problems[2].contains("sample_files 'maybe'"));
+    assert!(problems[3].contains("default_sort 'size_desc'"));
+    assert!(problems[4].contains("startup_view 'favorites'"));
+    assert!(problems[5].starts_with("rename_template can't be used"));
+}
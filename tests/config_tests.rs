@@ -1,6 +1,8 @@
 use movies::config::*;
+use movies::extras::SampleFiles;
 use movies::logger;
 use std::fs;
+use std::path::Path;
 use tempfile::TempDir;
 
 /// Test Case 9: Missing config field defaults
@@ -34,6 +36,15 @@ video_player: /usr/bin/vlc
     // Verify watched_threshold has default value
     assert_eq!(config.watched_threshold, 95);
 
+    // Verify scroll_margin has default value
+    assert_eq!(config.scroll_margin, 2);
+
+    // Verify list_split is unset, keeping the fixed list width
+    assert_eq!(config.list_split, None);
+
+    // Verify accessible mode is off by default
+    assert!(!config.accessible);
+
     // Verify existing fields are preserved
     assert_eq!(config.video_extensions, vec!["mp4", "mkv"]);
     assert_eq!(config.video_player, "/usr/bin/vlc");
@@ -379,6 +390,49 @@ video_player: /usr/bin/vlc
     assert!(log_contents.contains("Could not parse config.yaml"));
 }
 
+/// Test Case: Log rotation settings default to 10 MB and three earlier logs
+#[test]
+fn test_log_rotation_settings() {
+    let config: Config = serde_yaml::from_str("video_extensions: [mkv]\nvideo_player: mpv\n").unwrap();
+    assert_eq!(config.log_rotation(), logger::LogRotation::default());
+
+    let config: Config =
+        serde_yaml::from_str("video_extensions: [mkv]\nvideo_player: mpv\nlog_max_size: 0\nlog_keep_files: 5\n").unwrap();
+    assert_eq!(config.log_rotation(), logger::LogRotation { max_size: 0, keep_files: 5 });
+    assert!(generate_yaml_with_comments(&config).contains("log_keep_files: 5\n"));
+}
+
+/// Test Case: A config that can't be parsed is kept next to the defaults written in its place
+#[test]
+fn test_unparseable_config_is_kept_and_reported() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let yaml_path = temp_dir.path().join("config.yaml");
+    let broken = "video_player: [unclosed\n";
+    fs::write(&yaml_path, broken).expect("Failed to write invalid config");
+
+    let (config, problems) = load_config(&yaml_path.to_path_buf());
+    assert_eq!(config.active_theme, "THEME-default.yaml");
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].starts_with("Could not parse config.yaml"));
+    assert!(problems[0].contains("config.yaml.broken"));
+    assert_eq!(fs::read_to_string(temp_dir.path().join("config.yaml.broken")).unwrap(), broken);
+    assert!(read_config(&yaml_path).video_extensions.contains(&"mkv".to_string()));
+}
+
+/// Test Case: Problems in a config that parses are returned with it
+#[test]
+fn test_load_config_reports_unknown_settings() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let yaml_path = temp_dir.path().join("config.yaml");
+    fs::write(&yaml_path, "video_extensions: [mkv]\nvideo_player: sh\nmouse_support: true\n")
+        .expect("Failed to write config");
+
+    let (config, problems) = load_config(&yaml_path.to_path_buf());
+    assert_eq!(config.video_player, "sh");
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("'mouse_support'"));
+}
+
 /// Test Case: Config with active_theme field loads correctly
 /// When config.yaml contains an active_theme field, the loaded Config
 /// should contain that value.
@@ -629,3 +683,179 @@ fn test_yaml_generation_includes_watched_threshold_documentation() {
     assert!(yaml.contains("Invalid values will default to 95"));
     assert!(yaml.contains("watched_threshold: 95"));
 }
+
+/// Test Case: Root override replaces scan rules for a matching root
+/// When a root override matches the scanned root, its extensions and size filter
+/// should replace the global values while other roots keep the defaults.
+#[test]
+fn test_root_override_applies_to_matching_root() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let home_videos = temp_dir.path().join("home-videos");
+    let movies = temp_dir.path().join("movies");
+    fs::create_dir_all(&home_videos).expect("Failed to create dir");
+    fs::create_dir_all(&movies).expect("Failed to create dir");
+
+    let config = Config {
+        min_file_size_mb: 50,
+        root_overrides: vec![RootOverride {
+            path: home_videos.to_string_lossy().to_string(),
+            video_extensions: Some(vec!["MP4".to_string(), "mts".to_string()]),
+            min_file_size_mb: Some(0),
+        }],
+        ..Default::default()
+    };
+
+    let rules = config.scan_rules_for(&home_videos);
+    assert_eq!(rules.video_extensions, vec!["mp4", "mts"]);
+    assert_eq!(rules.min_file_size_mb, 0);
+
+    let default_rules = config.scan_rules_for(&movies);
+    assert_eq!(default_rules.video_extensions, config.video_extensions);
+    assert_eq!(default_rules.min_file_size_mb, 50);
+}
+
+/// Test Case: Root override paths survive the generated config file
+/// A Windows path or a folder name with quotes should be written so it reads back unchanged.
+#[test]
+fn test_yaml_generation_escapes_root_override_paths() {
+    let config = Config {
+        root_overrides: vec![
+            RootOverride {
+                path: "D:\\Videos\\Home \"Movies\"".to_string(),
+                video_extensions: Some(vec!["mp4".to_string(), "m2ts".to_string()]),
+                min_file_size_mb: Some(0),
+            },
+            RootOverride { path: "/media/it's: here".to_string(), video_extensions: None, min_file_size_mb: None },
+        ],
+        ..Default::default()
+    };
+
+    let yaml = generate_yaml_with_comments(&config);
+    let parsed: Config = serde_yaml::from_str(&yaml).expect("Generated config should parse");
+
+    assert_eq!(parsed.root_overrides, config.root_overrides);
+}
+
+/// Test Case: Exclude patterns with escapes survive the generated config file
+#[test]
+fn test_yaml_generation_escapes_scan_exclude_patterns() {
+    let config = Config {
+        scan_exclude: vec!["**/\\[sample\\]*".to_string(), "*\"draft\"*".to_string()],
+        ..Default::default()
+    };
+
+    let yaml = generate_yaml_with_comments(&config);
+    let parsed: Config = serde_yaml::from_str(&yaml).expect("Generated config should parse");
+
+    assert_eq!(parsed.scan_exclude, config.scan_exclude);
+}
+
+/// Test Case: Library folders survive the generated config file
+#[test]
+fn test_yaml_generation_escapes_library_roots() {
+    let config = Config {
+        library_roots: vec!["E:\\Movies".to_string(), "/media/\"external\"".to_string()],
+        ..Default::default()
+    };
+
+    let yaml = generate_yaml_with_comments(&config);
+    let parsed: Config = serde_yaml::from_str(&yaml).expect("Generated config should parse");
+
+    assert_eq!(parsed.library_roots, config.library_roots);
+}
+
+/// Test Case: Scan rules filter by extension and minimum size
+#[test]
+fn test_scan_rules_accepts_by_extension_and_size() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let clip = temp_dir.path().join("clip.MTS");
+    let notes = temp_dir.path().join("notes.txt");
+    fs::write(&clip, vec![0u8; 1024]).expect("Failed to write file");
+    fs::write(&notes, "text").expect("Failed to write file");
+
+    let mut rules = ScanRules {
+        video_extensions: vec!["mts".to_string()],
+        min_file_size_mb: 0,
+        exclude: Vec::new(),
+        follow_symlinks: false,
+        dedupe_hardlinks: false,
+        samples: SampleFiles::Import,
+        sample_max_seconds: 0,
+    };
+    assert!(rules.accepts(&clip));
+    assert!(!rules.accepts(&notes));
+
+    rules.min_file_size_mb = 1;
+    assert!(!rules.accepts(&clip));
+}
+
+/// Test Case: Exclude patterns match paths relative to the scanned root
+#[test]
+fn test_scan_rules_exclude_patterns() {
+    let root = Path::new("/videos");
+    let rules = ScanRules {
+        video_extensions: vec!["mkv".to_string()],
+        min_file_size_mb: 0,
+        exclude: vec!["**/extras/**".to_string(), "*sample*".to_string(), "Movies/*/trailer?.mkv".to_string()],
+        follow_symlinks: false,
+        dedupe_hardlinks: false,
+        samples: SampleFiles::Import,
+        sample_max_seconds: 0,
+    };
+    assert!(rules.excludes(root, Path::new("/videos/Show/Season 1/Extras/gag reel.mkv")));
+    assert!(rules.excludes(root, Path::new("/videos/extras/interview.mkv")));
+    assert!(rules.excludes(root, Path::new("/videos/Movie/Movie-SAMPLE.mkv")));
+    assert!(rules.excludes(root, Path::new("/videos/Movies/Heat/trailer2.mkv")));
+    assert!(!rules.excludes(root, Path::new("/videos/Movies/Heat/trailer10.mkv")));
+    assert!(!rules.excludes(root, Path::new("/videos/Movies/Heat/Heat.mkv")));
+    // Only the part below the root is matched
+    assert!(!rules.excludes(Path::new("/extras"), Path::new("/extras/Show/episode.mkv")));
+    assert!(!ScanRules { exclude: Vec::new(), ..rules }.excludes(root, Path::new("/videos/extras/a.mkv")));
+}
+
+/// Test Case: Rebound keys survive saving the config
+#[test]
+fn test_keybindings_round_trip_through_saved_config() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let yaml_path = temp_dir.path().join("config.yaml");
+    fs::write(
+        &yaml_path,
+        "video_extensions: [mp4]\nvideo_player: vlc\nkeybindings:\n  browse:\n    toggle_grid: G\n    move_down: [Down, \"Ctrl+n\"]\n  menu:\n    rescan: null\n",
+    )
+    .expect("Failed to write test config");
+
+    let config = read_config(&yaml_path);
+    assert_eq!(config.keybindings.browse["toggle_grid"].keys(), vec!["G"]);
+    assert_eq!(config.keybindings.browse["move_down"].keys(), vec!["Down", "Ctrl+n"]);
+    assert_eq!(config.keybindings.menu["rescan"], None);
+
+    save_config(&config, &yaml_path);
+    let saved = read_config(&yaml_path);
+    assert_eq!(saved.keybindings.browse["move_down"].keys(), vec!["Down", "Ctrl+n"]);
+    assert_eq!(saved.keybindings, config.keybindings);
+    assert!(Config::default().keybindings.is_empty());
+}
+
+/// Test Case: Scans go into linked folders only when following symbolic links
+#[cfg(unix)]
+#[test]
+fn test_scan_rules_walk_follows_symlinks_when_asked() {
+    let library = TempDir::new().expect("Failed to create temp dir");
+    let drive = TempDir::new().expect("Failed to create temp dir");
+    fs::write(drive.path().join("movie.mkv"), "video").expect("Failed to write file");
+    std::os::unix::fs::symlink(drive.path(), library.path().join("Drive")).expect("Failed to link");
+
+    let mut rules = Config::default().scan_rules_for(library.path());
+    let videos = |rules: &ScanRules| -> Vec<String> {
+        rules
+            .walk(library.path())
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file() && rules.accepts(e.path()))
+            .map(|e| e.path().strip_prefix(library.path()).unwrap().to_string_lossy().to_string())
+            .collect()
+    };
+    assert!(videos(&rules).is_empty());
+    rules.follow_symlinks = true;
+    assert_eq!(videos(&rules), vec!["Drive/movie.mkv"]);
+}
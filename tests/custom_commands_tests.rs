@@ -0,0 +1,160 @@
+use crossterm::event::KeyCode;
+use movies::config::{generate_yaml_with_comments, Config, CustomCommand};
+use movies::custom_commands::{build_command, install, installed, run};
+use movies::dto::EpisodeDetail;
+use movies::keymap::Keymap;
+use movies::menu::{all_menu_items, get_available_menu_items, MenuAction, MenuContext};
+use movies::util::{Entry, Mode};
+use std::path::Path;
+use std::process::Command;
+
+fn custom(label: &str, command: &str, hotkey: Option<&str>) -> CustomCommand {
+    CustomCommand {
+        label: label.to_string(),
+        command: command.to_string(),
+        hotkey: hotkey.map(str::to_string),
+    }
+}
+
+fn context(selected_entry: Option<Entry>) -> MenuContext {
+    MenuContext {
+        selected_entry,
+        episode_detail: EpisodeDetail::default(),
+        mode: Mode::Browse,
+        last_action: None,
+        marked_count: 0,
+        extras_view: false,
+    }
+}
+
+#[test]
+fn test_build_command_replaces_the_placeholders_in_each_argument() {
+    let command = build_command(
+        "HandBrakeCLI -i {path} -o {path}.mp4 --title-label {series}:{title}",
+        Path::new("/videos/The Wire/S01E01.mkv"),
+        "The Target",
+        "The Wire",
+    )
+    .unwrap();
+    assert_eq!(command.get_program(), "HandBrakeCLI");
+    let args: Vec<String> = command.get_args().map(|arg| arg.to_string_lossy().to_string()).collect();
+    assert_eq!(
+        args,
+        vec![
+            "-i",
+            "/videos/The Wire/S01E01.mkv",
+            "-o",
+            "/videos/The Wire/S01E01.mkv.mp4",
+            "--title-label",
+            "The Wire:The Target",
+        ]
+    );
+    assert!(build_command("   ", Path::new("/videos/a.mkv"), "", "").is_none());
+}
+
+#[test]
+fn test_build_command_leaves_placeholders_in_the_details_alone() {
+    let command = build_command("tag {title} {series}-{path}", Path::new("/videos/{title}.mkv"), "Why {series}?", "{path}").unwrap();
+    let args: Vec<String> = command.get_args().map(|arg| arg.to_string_lossy().to_string()).collect();
+    assert_eq!(args, vec!["Why {series}?", "{path}-/videos/{title}.mkv"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_build_command_passes_a_non_utf8_path_unchanged() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = Path::new(OsStr::from_bytes(b"/videos/caf\xe9.mkv"));
+    let command = build_command("mediainfo --file={path}", path, "", "").unwrap();
+    let args: Vec<&OsStr> = command.get_args().collect();
+    assert_eq!(args, vec![OsStr::from_bytes(b"--file=/videos/caf\xe9.mkv")]);
+}
+
+#[test]
+#[serial_test::serial]
+fn test_install_checks_labels_and_hotkeys() {
+    let commands = vec![
+        custom("check with mediainfo", "mediainfo {path}", Some("i")),
+        custom("transcode", "HandBrakeCLI -i {path} -o {path}.mp4", Some("F12")),
+        custom("probe", "ffprobe {path}", Some("i")),
+        custom("  ", "true", None),
+        custom("nothing", " ", None),
+        custom("shifted", "true", Some("Ctrl+x")),
+        custom("edit again", "true", Some("F2")),
+    ];
+    let problems = install(&commands, &Keymap::default());
+
+    let menu: Vec<(String, Option<KeyCode>)> = installed().into_iter().map(|command| (command.label, command.hotkey)).collect();
+    assert_eq!(
+        menu,
+        vec![
+            ("check with mediainfo".to_string(), Some(KeyCode::Char('i'))),
+            ("transcode".to_string(), Some(KeyCode::F(12))),
+            ("probe".to_string(), None),
+            ("shifted".to_string(), None),
+            ("edit again".to_string(), None),
+        ]
+    );
+    assert_eq!(problems.len(), 5, "{:?}", problems);
+    assert!(problems.iter().any(|problem| problem.contains("both probe and check with mediainfo")));
+    assert!(problems.iter().any(|problem| problem.contains("no label")));
+    assert!(problems.iter().any(|problem| problem.contains("'nothing' has no command")));
+    assert!(problems.iter().any(|problem| problem.contains("shifted must be a letter or function key")));
+    assert!(problems.iter().any(|problem| problem.contains("both edit again and edit")));
+
+    // Installing again replaces the commands instead of adding to them
+    assert!(install(&commands[..1], &Keymap::default()).is_empty());
+    assert_eq!(installed().len(), 1);
+    install(&[], &Keymap::default());
+}
+
+#[test]
+#[serial_test::serial]
+fn test_custom_commands_are_in_the_menu_for_episodes() {
+    install(&[custom("check with mediainfo", "mediainfo {path}", Some("i"))], &Keymap::default());
+
+    let item = all_menu_items().into_iter().find(|item| matches!(item.action, MenuAction::Custom(0))).unwrap();
+    assert_eq!(item.label, "check with mediainfo");
+    assert_eq!(item.hotkey, Some(KeyCode::Char('i')));
+    assert_eq!(item.action.usage_key(), "custom_command");
+
+    let episode = Entry::Episode { episode_id: 1, name: "S01E01.mkv".to_string(), location: "S01E01.mkv".to_string() };
+    let available = |entry: Option<Entry>| {
+        get_available_menu_items(&context(entry))
+            .iter()
+            .any(|item| matches!(item.action, MenuAction::Custom(_)))
+    };
+    assert!(available(Some(episode)));
+    assert!(!available(None));
+    install(&[], &Keymap::default());
+}
+
+#[test]
+#[serial_test::serial]
+fn test_config_yaml_keeps_the_custom_commands() {
+    install(&[custom("probe", "ffprobe {path}", None)], &Keymap::default());
+    let config = Config {
+        custom_commands: vec![
+            custom("check with \"mediainfo\"", "mediainfo {path}", Some("i")),
+            custom("probe", "ffprobe {path}", None),
+        ],
+        ..Config::default()
+    };
+    let yaml = generate_yaml_with_comments(&config);
+    assert!(
+        !yaml.contains(" custom_command,") && !yaml.contains(" custom_command\n"),
+        "Custom commands aren't listed among the menu keybindings"
+    );
+    let parsed: Config = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(parsed.custom_commands, config.custom_commands);
+    install(&[], &Keymap::default());
+}
+
+#[test]
+#[cfg(unix)]
+fn test_run_reports_the_exit_status() {
+    assert!(run("echo", Command::new("true")).is_ok());
+    assert!(run("fail", Command::new("false")).unwrap_err().contains("exited with"));
+    assert!(run("missing", Command::new("movies-no-such-program")).unwrap_err().contains("not found"));
+}
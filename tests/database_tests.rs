@@ -0,0 +1,524 @@
+use movies::database::DatabaseError;
+use std::error::Error;
+
+#[test]
+fn test_database_error_messages_and_sources() {
+    assert_eq!(DatabaseError::EpisodeNotFound(7).to_string(), "Episode 7 not found");
+
+    let sqlite: DatabaseError = rusqlite::Error::QueryReturnedNoRows.into();
+    assert!(sqlite.to_string().starts_with("Database error:"));
+    assert!(sqlite.source().is_some());
+
+    let io: DatabaseError = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied").into();
+    assert_eq!(io.to_string(), "IO error: denied");
+    assert!(DatabaseError::EpisodeNotFound(7).source().is_none());
+}
+
+#[test]
+fn test_separate_databases_are_isolated() {
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use movies::util::Entry;
+    use tempfile::TempDir;
+
+    let first_dir = TempDir::new().expect("Failed to create temp dir");
+    let second_dir = TempDir::new().expect("Failed to create temp dir");
+    let first = Database::open(&first_dir.path().join("videos.sqlite")).unwrap();
+    let second = Database::open(&second_dir.path().join("videos.sqlite")).unwrap();
+
+    let video = first_dir.path().join("movie.mp4");
+    std::fs::write(&video, b"").unwrap();
+    let resolver = PathResolver::from_database_path(&first_dir.path().join("videos.sqlite")).unwrap();
+    assert!(first.import_episode_relative(video.to_str().unwrap(), "Movie", &resolver).unwrap());
+    assert!(!first.import_episode_relative(video.to_str().unwrap(), "Movie", &resolver).unwrap());
+
+    let entries = first.get_entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    let episode_id = match &entries[0] {
+        Entry::Episode { episode_id, location, .. } => {
+            assert_eq!(location, "movie.mp4");
+            *episode_id
+        }
+        other => panic!("Expected an episode, got {:?}", other),
+    };
+    assert!(first.toggle_watched_status(episode_id).unwrap());
+    assert_eq!(first.get_episode_detail(episode_id).unwrap().watched, "true");
+
+    assert!(second.get_entries().unwrap().is_empty());
+    assert!(matches!(
+        second.get_episode_detail(episode_id),
+        Err(DatabaseError::EpisodeNotFound(_))
+    ));
+}
+
+#[test]
+fn test_category_counts_cover_every_series_and_season() {
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use movies::util::{CategoryCounts, Entry, EntryKey};
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    for name in ["ep1", "ep2", "ep3"] {
+        let video = temp_dir.path().join(format!("{}.mkv", name));
+        std::fs::write(&video, b"").unwrap();
+        database.import_episode_relative(video.to_str().unwrap(), name, &resolver).unwrap();
+    }
+    let ids: Vec<usize> = database
+        .get_entries()
+        .unwrap()
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Episode { episode_id, .. } => Some(*episode_id),
+            _ => None,
+        })
+        .collect();
+
+    let series_id = database.create_series_and_assign("Show", ids[0]).unwrap().series.unwrap().id;
+    database.assign_series(series_id, ids[1]).unwrap();
+    let season_id = database.create_season_and_assign(series_id, 1, ids[1]).unwrap();
+    database.toggle_watched_status(ids[1]).unwrap();
+    let empty_series = database.create_series_and_assign("Empty", ids[2]).unwrap().series.unwrap().id;
+    database.clear_series_data(ids[2]).unwrap();
+
+    let counts = database.get_category_counts().unwrap();
+    assert_eq!(
+        counts[&EntryKey::Series(series_id)],
+        CategoryCounts { name: "Show".to_string(), total: 2, unwatched: 1, new: 0 }
+    );
+    assert_eq!(
+        counts[&EntryKey::Season(season_id)],
+        CategoryCounts { name: "Season 1".to_string(), total: 1, unwatched: 0, new: 0 }
+    );
+    assert_eq!(counts[&EntryKey::Series(empty_series)].total, 0);
+}
+
+#[test]
+fn test_category_counts_include_new_episodes() {
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use movies::util::{Entry, EntryKey};
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    std::fs::create_dir(temp_dir.path().join("show")).unwrap();
+    // Episodes imported under their file name are new until they are renamed
+    for (file, name) in [("show/a.mkv", "a.mkv"), ("show/b.mkv", "b.mkv"), ("show/c.mkv", "Pilot")] {
+        let video = temp_dir.path().join(file);
+        std::fs::write(&video, b"").unwrap();
+        database.import_episode_relative(video.to_str().unwrap(), name, &resolver).unwrap();
+    }
+    let ids: Vec<usize> = database
+        .get_entries()
+        .unwrap()
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Episode { episode_id, .. } => Some(*episode_id),
+            _ => None,
+        })
+        .collect();
+    let series_id = database.create_series_and_assign("Show", ids[0]).unwrap().series.unwrap().id;
+    for id in &ids[1..] {
+        database.assign_series(series_id, *id).unwrap();
+    }
+
+    let counts = database.get_category_counts().unwrap();
+    assert_eq!(counts[&EntryKey::Series(series_id)].total, 3);
+    assert_eq!(counts[&EntryKey::Series(series_id)].new, 2);
+}
+
+#[test]
+fn test_alt_titles_are_saved_and_loaded() {
+    use movies::database::Database;
+    use movies::dto::AltTitles;
+    use movies::path_resolver::PathResolver;
+    use movies::util::Entry;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let video = temp_dir.path().join("movie.mkv");
+    std::fs::write(&video, b"").unwrap();
+    database.import_episode_relative(video.to_str().unwrap(), "Movie", &resolver).unwrap();
+    let episode_id = match &database.get_entries().unwrap()[0] {
+        Entry::Episode { episode_id, .. } => *episode_id,
+        other => panic!("Expected an episode, got {:?}", other),
+    };
+
+    assert!(database.get_episode_detail(episode_id).unwrap().alt_titles.is_empty());
+    assert!(database.get_all_alt_titles().unwrap().is_empty());
+
+    let mut titles = AltTitles::new();
+    titles.insert("de".to_string(), "Der Film".to_string());
+    database.update_episode_alt_titles(episode_id, &titles).unwrap();
+    assert_eq!(database.get_episode_detail(episode_id).unwrap().alt_titles, titles);
+    assert_eq!(database.get_all_alt_titles().unwrap()[&episode_id], titles);
+
+    // Clearing every title leaves nothing behind
+    database.update_episode_alt_titles(episode_id, &AltTitles::new()).unwrap();
+    assert!(database.get_all_alt_titles().unwrap().is_empty());
+}
+
+#[test]
+fn test_writes_made_together_are_undone_together() {
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use movies::util::Entry;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let video = temp_dir.path().join("movie.mkv");
+    std::fs::write(&video, b"").unwrap();
+    database.import_episode_relative(video.to_str().unwrap(), "Movie", &resolver).unwrap();
+    let episode_id = match &database.get_entries().unwrap()[0] {
+        Entry::Episode { episode_id, .. } => *episode_id,
+        other => panic!("Expected an episode, got {:?}", other),
+    };
+
+    // A write that fails takes back the ones before it
+    let failed = database.write_together(|writes| {
+        writes.update_episode_notes(episode_id, "Seen it")?;
+        writes.create_series("Films")?;
+        writes.toggle_watched_status(episode_id + 100)
+    });
+    assert!(failed.is_err());
+    assert_eq!(database.get_episode_detail(episode_id).unwrap().notes, None);
+    assert!(database.get_all_series().unwrap().is_empty());
+
+    database
+        .write_together(|writes| {
+            writes.update_episode_notes(episode_id, "Seen it")?;
+            writes.toggle_watched_status(episode_id)
+        })
+        .unwrap();
+    let details = database.get_episode_detail(episode_id).unwrap();
+    assert_eq!((details.notes.as_deref(), details.watched.as_str()), (Some("Seen it"), "true"));
+}
+
+#[test]
+fn test_merge_versions_shares_one_episode() {
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use movies::util::Entry;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    for file in ["Movie.1080p.mkv", "Movie.2160p.mkv", "Other.mkv"] {
+        let video = temp_dir.path().join(file);
+        std::fs::write(&video, b"").unwrap();
+        let name = if file == "Other.mkv" { "Other" } else { "Movie" };
+        database.import_episode_relative(video.to_str().unwrap(), name, &resolver).unwrap();
+    }
+    let ids: Vec<usize> = database
+        .get_entries()
+        .unwrap()
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Episode { episode_id, .. } => Some(*episode_id),
+            _ => None,
+        })
+        .collect();
+    // The 4K file was watched before the merge
+    database.toggle_watched_status(ids[1]).unwrap();
+
+    assert_eq!(database.merge_versions(ids[0]).unwrap(), 1);
+    assert_eq!(database.get_entries().unwrap().len(), 2);
+    assert_eq!(database.get_episode_detail(ids[0]).unwrap().watched, "true");
+
+    let versions = database.get_episode_versions(ids[0], &resolver).unwrap();
+    let labels: Vec<&str> = versions.iter().map(|version| version.label.as_str()).collect();
+    assert_eq!(labels, vec!["1080p", "4K"]);
+    assert_eq!(versions[1].location, temp_dir.path().join("Movie.2160p.mkv").to_str().unwrap());
+
+    // A rescan does not import the attached file again
+    assert!(database.episode_exists(None, "Movie.2160p.mkv").unwrap());
+    assert_eq!(database.merge_versions(ids[0]).unwrap(), 0);
+}
+
+#[test]
+fn test_sort_keys_roll_up_into_series() {
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use movies::util::{Entry, EntryKey};
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    for (name, size) in [("ep1", 100), ("ep2", 250)] {
+        let video = temp_dir.path().join(format!("{}.mkv", name));
+        std::fs::write(&video, vec![0u8; size]).unwrap();
+        database.import_episode_relative(video.to_str().unwrap(), name, &resolver).unwrap();
+    }
+    let ids: Vec<usize> = database
+        .get_entries()
+        .unwrap()
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Episode { episode_id, .. } => Some(*episode_id),
+            _ => None,
+        })
+        .collect();
+    let series_id = database.create_series_and_assign("Show", ids[0]).unwrap().series.unwrap().id;
+    database.assign_series(series_id, ids[1]).unwrap();
+    database.toggle_watched_status(ids[0]).unwrap();
+
+    let keys = database.get_sort_keys().unwrap();
+    assert_eq!(keys.file_size[&EntryKey::Episode(ids[1])], 250);
+    assert_eq!(keys.file_size[&EntryKey::Series(series_id)], 350);
+    assert!(keys.watched[&EntryKey::Episode(ids[0])]);
+    assert!(!keys.watched[&EntryKey::Series(series_id)]);
+    assert!(keys.added.contains_key(&EntryKey::Series(series_id)));
+}
+
+#[test]
+fn test_episodes_under_another_root_resolve_against_it() {
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use movies::util::Entry;
+    use tempfile::TempDir;
+
+    let library = TempDir::new().expect("Failed to create temp dir");
+    let drive = TempDir::new().expect("Failed to create temp dir");
+    let db_path = library.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let mut resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let drive_root = drive.path().canonicalize().unwrap();
+    let root_id = database.register_root(drive_root.to_str().unwrap()).unwrap();
+    assert_eq!(database.register_root(drive_root.to_str().unwrap()).unwrap(), root_id);
+    resolver.add_root(root_id, &drive_root);
+
+    // The same relative path under both roots is two episodes
+    for folder in [library.path(), drive.path()] {
+        let video = folder.join("Movie.mkv");
+        std::fs::write(&video, b"").unwrap();
+        assert!(database.import_episode_relative(video.to_str().unwrap(), "Movie", &resolver).unwrap());
+    }
+    assert!(database.episode_exists(None, "Movie.mkv").unwrap());
+    assert!(database.episode_exists(Some(root_id), "Movie.mkv").unwrap());
+
+    let mut locations: Vec<String> = database
+        .get_entries()
+        .unwrap()
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Entry::Episode { location, .. } => Some(resolver.to_absolute(std::path::Path::new(&location)).to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    locations.sort();
+    let mut expected = vec![
+        library.path().canonicalize().unwrap().join("Movie.mkv").to_string_lossy().to_string(),
+        drive_root.join("Movie.mkv").to_string_lossy().to_string(),
+    ];
+    expected.sort();
+    assert_eq!(locations, expected);
+}
+
+#[test]
+fn test_folder_series_are_found_or_created() {
+    use movies::database::Database;
+    use movies::folder_series::series_proposals;
+    use movies::path_resolver::PathResolver;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    for location in ["Show/Season 01/e1.mkv", "Show/Season 02/e2.mkv", "Show/extra.mkv", "Movie.mkv"] {
+        let video = temp_dir.path().join(location);
+        std::fs::create_dir_all(video.parent().unwrap()).unwrap();
+        std::fs::write(&video, b"").unwrap();
+        database.import_episode_relative(video.to_str().unwrap(), location, &resolver).unwrap();
+    }
+    let id_of = |location: &str| {
+        database.get_unassigned_episodes().unwrap().into_iter().find(|(_, found)| found == location).unwrap().0
+    };
+    let (first, second, extra) = (id_of("Show/Season 01/e1.mkv"), id_of("Show/Season 02/e2.mkv"), id_of("Show/extra.mkv"));
+    let series_id = database.create_series_and_assign("SHOW", extra).unwrap().series.unwrap().id;
+
+    let proposals = series_proposals(database.get_unassigned_episodes().unwrap());
+    assert_eq!(proposals.len(), 2);
+    // The series already there is used, whatever its case
+    assert_eq!(database.assign_folder_series(&proposals).unwrap(), 0);
+    for (episode_id, season) in [(first, 1), (second, 2)] {
+        let detail = database.get_episode_detail(episode_id).unwrap();
+        assert_eq!(detail.series.map(|series| series.id), Some(series_id));
+        assert_eq!(detail.season.map(|found| found.number), Some(season));
+    }
+    let unassigned: Vec<String> = database.get_unassigned_episodes().unwrap().into_iter().map(|(_, location)| location).collect();
+    assert_eq!(unassigned, vec!["Movie.mkv"]);
+}
+
+#[test]
+fn test_cached_episode_details_are_read_again_after_a_change() {
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use movies::util::Entry;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let video = temp_dir.path().join("movie.mkv");
+    std::fs::write(&video, b"").unwrap();
+    database.import_episode_relative(video.to_str().unwrap(), "Movie", &resolver).unwrap();
+    let episode_id = match &database.get_entries().unwrap()[0] {
+        Entry::Episode { episode_id, .. } => *episode_id,
+        other => panic!("Expected an episode, got {:?}", other),
+    };
+
+    let before = database.change_count().unwrap();
+    assert_eq!(database.cached_episode_detail(episode_id).unwrap().watched, "false");
+    assert_eq!(database.change_count().unwrap(), before, "Reading changes nothing");
+
+    database.toggle_watched_status(episode_id).unwrap();
+    assert!(database.change_count().unwrap() > before);
+    assert_eq!(database.cached_episode_detail(episode_id).unwrap().watched, "true");
+
+    let mut details = database.cached_episode_detail(episode_id).unwrap();
+    details.title = "Renamed".to_string();
+    database.update_episode_detail(episode_id, &details).unwrap();
+    assert_eq!(database.cached_episode_detail(episode_id).unwrap().title, "Renamed");
+
+    // Counts are shared until the library changes
+    let counts = database.cached_category_counts().unwrap();
+    assert!(std::sync::Arc::ptr_eq(&counts, &database.cached_category_counts().unwrap()));
+    let series_id = database.create_series_and_assign("Show", episode_id).unwrap().series.unwrap().id;
+    let counts = database.cached_category_counts().unwrap();
+    assert_eq!(counts[&movies::util::EntryKey::Series(series_id)].total, 1);
+}
+
+#[test]
+fn test_watch_history_matches_shared_file_names_by_folder() {
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use movies::watch_import::WatchRecord;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    for show in ["Show A", "Show B"] {
+        let video = temp_dir.path().join(show).join("S01E01.mkv");
+        std::fs::create_dir_all(video.parent().unwrap()).unwrap();
+        std::fs::write(&video, b"").unwrap();
+        database.import_episode_relative(video.to_str().unwrap(), "S01E01", &resolver).unwrap();
+    }
+    let played = |path: &str| WatchRecord { path: path.to_string(), play_count: 1, last_played: None, resume_seconds: None };
+
+    // Only the folder names tell the two episodes apart, and a bare file name can't
+    let report = database
+        .apply_watch_history(&[played("smb://nas/tv/Show A/S01E01.mkv"), played("/downloads/S01E01.mkv")])
+        .unwrap();
+    assert_eq!((report.total, report.matched, report.ambiguous, report.marked_watched), (2, 1, 1, 1));
+
+    let watched: Vec<(String, bool)> = database
+        .get_library_records()
+        .unwrap()
+        .into_iter()
+        .map(|record| (record.location, record.watched))
+        .collect();
+    assert!(watched.contains(&("Show A/S01E01.mkv".to_string(), true)));
+    assert!(watched.contains(&("Show B/S01E01.mkv".to_string(), false)));
+}
+
+#[test]
+fn test_partial_import_keeps_the_columns_it_lacks() {
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let video = temp_dir.path().join("pilot.mkv");
+    std::fs::write(&video, b"").unwrap();
+    database.import_episode_relative(video.to_str().unwrap(), "pilot", &resolver).unwrap();
+    let episode_id = database.find_episode_id(&video, &resolver).unwrap().unwrap();
+    let series_id = database.create_series_and_assign("Show", episode_id).unwrap().series.unwrap().id;
+    database.create_season_and_assign(series_id, 1, episode_id).unwrap();
+    database.toggle_watched_status(episode_id).unwrap();
+    let before = database.get_library_records().unwrap().remove(0);
+
+    // Only the title is in the file, so the series, season and watch state are left alone
+    let csv = temp_dir.path().join("titles.csv");
+    std::fs::write(&csv, format!("location,title\n{},Pilot\n", before.location)).unwrap();
+    let preview = database.import_library(&csv, true).unwrap();
+    assert_eq!(preview.changes, vec![format!("{}: title: 'pilot' -> 'Pilot'", before.location)]);
+    database.import_library(&csv, false).unwrap();
+    let after = database.get_library_records().unwrap().remove(0);
+    assert_eq!(after, movies::export::LibraryRecord { title: "Pilot".to_string(), ..before.clone() });
+
+    // Taking the episode out of its series leaves no empty series behind
+    std::fs::write(&csv, format!("location,series\n{},\n", before.location)).unwrap();
+    database.import_library(&csv, false).unwrap();
+    let after = database.get_library_records().unwrap().remove(0);
+    assert_eq!((after.series, after.season, after.watched), (None, None, true));
+    assert!(database.get_all_series().unwrap().is_empty());
+}
+
+#[test]
+fn test_import_matches_episodes_by_root_and_location() {
+    use movies::database::Database;
+    use movies::export::{records_to_csv, records_to_json};
+    use movies::path_resolver::PathResolver;
+    use tempfile::TempDir;
+
+    let library = TempDir::new().expect("Failed to create temp dir");
+    let drive = TempDir::new().expect("Failed to create temp dir");
+    let db_path = library.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let mut resolver = PathResolver::from_database_path(&db_path).unwrap();
+    database.add_library_roots(&mut resolver, &[drive.path().to_string_lossy().to_string()]);
+    for folder in [library.path(), drive.path()] {
+        let video = folder.join("Movie.mkv");
+        std::fs::write(&video, b"").unwrap();
+        database.import_episode_relative(video.to_str().unwrap(), "Movie", &resolver).unwrap();
+    }
+
+    // Retitle only the copy on the second root in the export
+    let mut records = database.get_library_records().unwrap();
+    assert_eq!(records.iter().filter(|record| record.root.is_some()).count(), 1);
+    for record in records.iter_mut().filter(|record| record.root.is_some()) {
+        record.title = "Movie (drive)".to_string();
+    }
+    let title_of = |root: bool| {
+        database.get_library_records().unwrap().into_iter().find(|record| record.root.is_some() == root).unwrap().title
+    };
+
+    let json = library.path().join("library_export.json");
+    std::fs::write(&json, records_to_json(&records).unwrap()).unwrap();
+    let report = database.import_library(&json, false).unwrap();
+    assert_eq!((report.updated, report.unchanged, report.unmatched.len()), (1, 1, 0));
+    assert_eq!((title_of(false), title_of(true)), ("Movie".to_string(), "Movie (drive)".to_string()));
+
+    // The CSV export carries the root too
+    for record in records.iter_mut() {
+        record.title = format!("{} from CSV", record.title);
+    }
+    let csv = library.path().join("library_export.csv");
+    std::fs::write(&csv, records_to_csv(&records)).unwrap();
+    let report = database.import_library(&csv, false).unwrap();
+    assert_eq!((report.updated, report.unmatched.len()), (2, 0));
+    assert_eq!((title_of(false), title_of(true)), ("Movie from CSV".to_string(), "Movie (drive) from CSV".to_string()));
+}
@@ -0,0 +1,95 @@
+use movies::database::Database;
+use movies::db_repair::{backup_path, check_at_startup, create_backup, dump_and_reload, quick_check, restore_backup};
+use rusqlite::Connection;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_library(path: &Path) {
+    let conn = Connection::open(path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE series (id INTEGER PRIMARY KEY, name TEXT NOT NULL UNIQUE);
+         CREATE TABLE episode (id INTEGER PRIMARY KEY, location TEXT NOT NULL, name TEXT NOT NULL, series_id INTEGER);
+         CREATE INDEX episode_location ON episode(location);
+         INSERT INTO series VALUES (1, 'Show');
+         INSERT INTO episode VALUES (1, 'Show/ep1.mkv', 'Pilot', 1);
+         INSERT INTO episode VALUES (2, 'movie.mp4', 'Movie', NULL);",
+    )
+    .unwrap();
+}
+
+fn episode_count(path: &Path) -> i64 {
+    let conn = Connection::open(path).unwrap();
+    conn.query_row("SELECT COUNT(*) FROM episode", [], |row| row.get(0)).unwrap()
+}
+
+#[test]
+fn test_quick_check_reports_healthy_and_unreadable_files() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let healthy = temp_dir.path().join("videos.sqlite");
+    create_library(&healthy);
+    assert!(quick_check(&healthy).is_empty());
+
+    let garbage = temp_dir.path().join("garbage.sqlite");
+    std::fs::write(&garbage, vec![0x5a; 8192]).unwrap();
+    assert!(!quick_check(&garbage).is_empty());
+}
+
+#[test]
+fn test_new_database_path_opens_without_recovery() {
+    // A --db path that doesn't exist yet starts a new library rather than looking damaged
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("new.sqlite");
+    assert!(check_at_startup(&db_path).is_empty());
+    assert!(!db_path.exists() && !backup_path(&db_path).exists());
+    Database::open(&db_path).unwrap();
+    assert!(db_path.exists());
+
+    // Once it exists it is checked and backed up like any other
+    assert!(check_at_startup(&db_path).is_empty());
+    assert!(backup_path(&db_path).exists());
+
+    let garbage = temp_dir.path().join("garbage.sqlite");
+    std::fs::write(&garbage, vec![0x5a; 8192]).unwrap();
+    assert!(!check_at_startup(&garbage).is_empty());
+    assert!(!backup_path(&garbage).exists());
+}
+
+#[test]
+fn test_dump_and_reload_keeps_rows_and_quarantines_original() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    create_library(&db_path);
+
+    let mut messages = Vec::new();
+    let report = dump_and_reload(&db_path, &mut |message| messages.push(message.to_string())).unwrap();
+
+    assert_eq!(report.tables, 2);
+    assert_eq!(report.rows_recovered, 3);
+    assert_eq!(report.rows_lost, 0);
+    assert_eq!(messages.len(), 2);
+    assert_eq!(episode_count(&db_path), 2);
+    assert!(quick_check(&db_path).is_empty());
+
+    let quarantined = std::fs::read_dir(temp_dir.path())
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .any(|e| e.file_name().to_string_lossy().starts_with("videos.sqlite.corrupt-"));
+    assert!(quarantined);
+}
+
+#[test]
+fn test_restore_backup_replaces_damaged_database() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    create_library(&db_path);
+
+    assert_eq!(create_backup(&db_path).unwrap(), backup_path(&db_path));
+
+    // Clobber the live database
+    std::fs::write(&db_path, vec![0x5a; 8192]).unwrap();
+    assert!(!quick_check(&db_path).is_empty());
+
+    restore_backup(&db_path).unwrap();
+    assert!(quick_check(&db_path).is_empty());
+    assert_eq!(episode_count(&db_path), 2);
+}
@@ -25,6 +25,9 @@ fn test_metadata_display_creation() {
         episode_number: "1".to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let metadata_display = MetadataDisplay::new(
@@ -53,6 +56,9 @@ fn test_metadata_display_path_extraction() {
         episode_number: "".to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let metadata_display = MetadataDisplay::new(
@@ -80,6 +86,9 @@ fn test_metadata_display_empty_fields() {
         episode_number: "".to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let metadata_display = MetadataDisplay::new(
@@ -114,6 +123,9 @@ fn test_episode_editor_creation() {
         episode_number: "1".to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let mut dirty_fields = HashSet::new();
@@ -149,6 +161,9 @@ fn test_episode_editor_field_highlighting() {
         episode_number: "1".to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let mut dirty_fields = HashSet::new();
@@ -184,6 +199,9 @@ fn test_episode_editor_non_editable_fields() {
         episode_number: "1".to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let dirty_fields = HashSet::new();
@@ -225,6 +243,9 @@ fn test_detail_panel_browse_mode() {
         episode_number: "1".to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let dirty_fields = HashSet::new();
@@ -266,6 +287,9 @@ fn test_detail_panel_edit_mode() {
         episode_number: "1".to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let mut dirty_fields = HashSet::new();
@@ -302,6 +326,9 @@ fn test_detail_panel_mode_switching() {
         episode_number: "1".to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let dirty_fields = HashSet::new();
@@ -354,6 +381,9 @@ fn test_detail_panel_other_modes_default_to_browse() {
         episode_number: "1".to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let dirty_fields = HashSet::new();
@@ -377,3 +407,70 @@ fn test_detail_panel_other_modes_default_to_browse() {
     assert_eq!(result.len(), 10);
     assert_eq!(result[0].len(), 80);
 }
+
+#[test]
+fn test_metadata_display_shows_notes_when_present() {
+    let mut episode_details = EpisodeDetail {
+        title: "Test Episode".to_string(),
+        year: "".to_string(),
+        watched: "false".to_string(),
+        length: "".to_string(),
+        series: None,
+        season: None,
+        episode_number: "".to_string(),
+        last_watched_time: None,
+        last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
+    };
+    let theme = Theme::default();
+    let row_text = |row: &Vec<movies::components::Cell>| row.iter().map(|c| c.character).collect::<String>();
+
+    let without_notes = MetadataDisplay::new(episode_details.clone(), "episode.mp4".to_string())
+        .render(40, 13, &theme, false);
+    assert!(row_text(&without_notes[12]).trim().is_empty());
+
+    episode_details.notes = Some("bad audio sync".to_string());
+    let with_notes = MetadataDisplay::new(episode_details, "episode.mp4".to_string())
+        .render(40, 13, &theme, false);
+    assert_eq!(row_text(&with_notes[12]).trim_end(), "Notes: bad audio sync");
+}
+
+#[test]
+fn test_episode_editor_shows_original_values_of_changed_fields() {
+    let original = EpisodeDetail {
+        title: "episode01.mkv".to_string(),
+        year: "2023".to_string(),
+        watched: "false".to_string(),
+        length: "".to_string(),
+        series: None,
+        season: None,
+        episode_number: "".to_string(),
+        last_watched_time: None,
+        last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
+    };
+    let edited = EpisodeDetail {
+        title: "Pilot".to_string(),
+        year: "2024".to_string(),
+        episode_number: "1".to_string(),
+        ..original.clone()
+    };
+    let dirty_fields: HashSet<EpisodeField> = [EpisodeField::Title, EpisodeField::EpisodeNumber].into_iter().collect();
+    let editor = EpisodeEditor::new(edited, EpisodeField::Title, 0, None, dirty_fields, "/videos/episode01.mkv".to_string())
+        .with_original(original);
+
+    let result = editor.render(60, 9, &Theme::default(), false);
+    let row_text = |row: usize| -> String { result[row].iter().map(|c| c.character).collect() };
+    assert!(row_text(2).starts_with("Title: Pilot  (was episode01.mkv)"));
+    assert!(row_text(8).starts_with("Ep #: 1  (was empty)"));
+    // Fields that aren't marked dirty show no note
+    assert!(!row_text(3).contains("was"));
+
+    let note_start = "Title: Pilot  ".chars().count();
+    assert!(result[2][note_start].style.dim);
+    assert!(!result[2][0].style.dim);
+}
@@ -0,0 +1,84 @@
+use chrono::{Local, TimeZone, Utc};
+use movies::database::Database;
+use movies::digest::{is_due, mark_shown, week_key, DigestTarget};
+use movies::path_resolver::PathResolver;
+use movies::util::Entry;
+use tempfile::TempDir;
+
+#[test]
+fn test_digest_is_due_once_per_week() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("state").join("digest_week");
+    let monday = Local.with_ymd_and_hms(2024, 2, 12, 9, 0, 0).unwrap();
+    let sunday = Local.with_ymd_and_hms(2024, 2, 18, 21, 0, 0).unwrap();
+    let next_monday = Local.with_ymd_and_hms(2024, 2, 19, 9, 0, 0).unwrap();
+
+    assert_eq!(week_key(monday), "2024-W07");
+    assert!(is_due(&path, monday));
+    mark_shown(&path, monday).unwrap();
+    assert!(!is_due(&path, sunday));
+    assert!(is_due(&path, next_monday));
+}
+
+#[test]
+fn test_weekly_digest_sections() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    for name in ["a1", "a2", "a3", "b1", "b2"] {
+        let video = temp_dir.path().join(format!("{}.mkv", name));
+        std::fs::write(&video, b"").unwrap();
+        database.import_episode_relative(video.to_str().unwrap(), name, &resolver).unwrap();
+    }
+    let ids: Vec<usize> = database
+        .get_entries()
+        .unwrap()
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Episode { episode_id, .. } => Some(*episode_id),
+            _ => None,
+        })
+        .collect();
+
+    // "Current" has one of three watched recently; "Old" was last watched two months ago
+    let current = database.create_series_and_assign("Current", ids[0]).unwrap().series.unwrap().id;
+    database.assign_series(current, ids[1]).unwrap();
+    database.assign_series(current, ids[2]).unwrap();
+    let old = database.create_series_and_assign("Old", ids[3]).unwrap().series.unwrap().id;
+    database.assign_series(old, ids[4]).unwrap();
+
+    let now = Utc::now();
+    let recent = (now - chrono::Duration::days(2)).to_rfc3339();
+    let long_ago = (now - chrono::Duration::days(60)).to_rfc3339();
+    {
+        let conn = database.connection();
+        conn.execute(
+            "UPDATE episode SET watched = 1, length = 1800, last_watched_time = ?1 WHERE id = ?2",
+            rusqlite::params![recent, ids[0]],
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE episode SET watched = 1, length = 3600, last_watched_time = ?1 WHERE id = ?2",
+            rusqlite::params![long_ago, ids[3]],
+        )
+        .unwrap();
+        // Only the first three episodes count as new
+        conn.execute(
+            "UPDATE episode SET added_time = ?1 WHERE id IN (?2, ?3)",
+            rusqlite::params![long_ago, ids[3], ids[4]],
+        )
+        .unwrap();
+    }
+
+    let digest = database.get_weekly_digest(now).unwrap();
+    assert_eq!(digest.new_items.len(), 3);
+    assert_eq!(digest.watch_seconds_last_week, 1800);
+    assert_eq!(digest.closest_to_finishing.len(), 1);
+    assert_eq!(digest.closest_to_finishing[0].label, "Current (2 left)");
+    assert_eq!(digest.closest_to_finishing[0].target, DigestTarget::Series(current));
+    assert_eq!(digest.stale_series.len(), 1);
+    assert_eq!(digest.stale_series[0].target, DigestTarget::Series(old));
+    assert_eq!(digest.items().len(), 5);
+    assert!(!digest.is_empty());
+}
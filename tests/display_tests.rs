@@ -46,3 +46,40 @@ fn test_theme_has_all_required_fields() {
 }
 
 
+
+/// Test Case: Group headers split a series into seasons, specials and loose episodes
+#[test]
+fn test_group_headers_in_series_view() {
+    use movies::display::group_headers;
+    use movies::util::{Entry, ViewContext};
+
+    let entries = vec![
+        Entry::Season { number: 0, season_id: 10 },
+        Entry::Season { number: 1, season_id: 11 },
+        Entry::Season { number: 2, season_id: 12 },
+        Entry::Episode { episode_id: 1, name: "Pilot".to_string(), location: "pilot.mkv".to_string() },
+    ];
+    let series = ViewContext::Series { series_id: 1, series_name: "Show".to_string() };
+    assert_eq!(
+        group_headers(&entries, &series),
+        vec![
+            (0, "Specials".to_string()),
+            (1, "Seasons".to_string()),
+            (3, "Unassigned episodes".to_string()),
+        ]
+    );
+    assert!(group_headers(&entries, &ViewContext::TopLevel).is_empty());
+}
+
+/// Test Case: The list takes its share of the terminal, leaving room for the details
+#[test]
+fn test_list_width_for_split() {
+    use movies::display::{list_width_for, COL1_WIDTH};
+
+    // Without a split the list keeps its fixed width
+    assert_eq!(list_width_for(200, None), COL1_WIDTH);
+    assert_eq!(list_width_for(200, Some(40)), 80);
+    // The details keep 20 columns plus the border, and the list never gets narrower than 20
+    assert_eq!(list_width_for(100, Some(90)), 78);
+    assert_eq!(list_width_for(100, Some(10)), 20);
+}
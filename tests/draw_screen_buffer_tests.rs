@@ -1,11 +1,12 @@
+use movies::app_state::{AppState, DrawContext};
 use movies::buffer::BufferManager;
+use movies::database::Database;
 use movies::theme::Theme;
-use movies::util::{Entry, Mode, ViewContext};
-use movies::dto::{EpisodeDetail, Series};
-use movies::episode_field::EpisodeField;
-use movies::menu::MenuItem;
+use movies::notifications::Notifications;
+use movies::util::{Entry, Mode};
+use movies::dto::EpisodeDetail;
 use movies::path_resolver::PathResolver;
-use std::collections::HashSet;
+use std::path::Path;
 use tempfile::TempDir;
 
 /// Helper function to create a test EpisodeDetail
@@ -20,6 +21,9 @@ fn create_test_episode_detail() -> EpisodeDetail {
         episode_number: String::from("1"),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     }
 }
 
@@ -31,6 +35,18 @@ fn create_test_path_resolver() -> PathResolver {
     PathResolver::from_database_path(&db_path).unwrap()
 }
 
+/// Helper function to create an empty in-memory library
+fn create_test_database() -> Database {
+    Database::open(Path::new(":memory:")).unwrap()
+}
+
+/// Helper function to create notifications showing one status message
+fn status_message(message: &str) -> Notifications {
+    let mut notifications = Notifications::default();
+    notifications.info(message);
+    notifications
+}
+
 /// Test Case: draw_screen writes to buffer instead of terminal
 /// When draw_screen is called, it should write to the buffer manager's desired buffer
 /// instead of directly to the terminal.
@@ -40,41 +56,18 @@ fn test_draw_screen_writes_to_buffer() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries: Vec<Entry> = vec![];
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Call draw_screen
     let result = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     
@@ -90,15 +83,10 @@ fn test_desired_buffer_starts_empty() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries: Vec<Entry> = vec![];
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Write some content to the desired buffer before calling draw_screen
     {
@@ -109,29 +97,11 @@ fn test_desired_buffer_starts_empty() {
     
     // Call draw_screen - it should clear the desired buffer
     let result = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     
@@ -152,41 +122,18 @@ fn test_render_to_terminal_called() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries: Vec<Entry> = vec![];
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Call draw_screen
     let result = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     
@@ -207,99 +154,42 @@ fn test_multiple_draw_screen_calls() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries: Vec<Entry> = vec![];
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // First call
     let result1 = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result1.is_ok(), "First draw_screen should succeed");
     
     // Second call with different mode
     let result2 = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Entry,
-        &String::from("/test/path"),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            mode: Mode::Entry,
+            entry_path: String::from("/test/path"),
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result2.is_ok(), "Second draw_screen should succeed");
     
     // Third call back to Browse mode
     let result3 = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result3.is_ok(), "Third draw_screen should succeed");
@@ -321,41 +211,19 @@ fn test_buffer_writer_replaces_terminal_io() {
     // Use empty entries to avoid database access
     let entries: Vec<Entry> = vec![];
     
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Call draw_screen - all content should be written to buffer
     let result = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "Test status message",
-        &resolver,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            notifications: status_message("Test status message"),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     
@@ -376,128 +244,55 @@ fn test_buffer_writes_in_all_modes() {
     let theme = Theme::default();
     // Use empty entries to avoid database access
     let entries: Vec<Entry> = vec![];
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Test Browse mode
     let result_browse = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result_browse.is_ok(), "Browse mode should render successfully");
     
     // Test Edit mode
     let result_edit = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Edit,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            mode: Mode::Edit,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result_edit.is_ok(), "Edit mode should render successfully");
     
     // Test Entry mode
     let result_entry = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Entry,
-        &String::from("/test/path"),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            mode: Mode::Entry,
+            entry_path: String::from("/test/path"),
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result_entry.is_ok(), "Entry mode should render successfully");
     
     // Test Menu mode
     let result_menu = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Menu,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            mode: Mode::Menu,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result_menu.is_ok(), "Menu mode should render successfully");
@@ -512,15 +307,10 @@ fn test_clear_desired_buffer_called() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries: Vec<Entry> = vec![];
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Manually write some content to desired buffer
     {
@@ -531,29 +321,11 @@ fn test_clear_desired_buffer_called() {
     
     // Call draw_screen - it should clear the desired buffer first
     let result = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     
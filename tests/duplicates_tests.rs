@@ -0,0 +1,99 @@
+use movies::database::Database;
+use movies::duplicates::{find_duplicates, quick_hash, DuplicateCopy, DuplicateGroup, HASH_SAMPLE_BYTES};
+use movies::path_resolver::PathResolver;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn copy(episode_id: usize, path: &Path, length: u64) -> DuplicateCopy {
+    DuplicateCopy {
+        episode_id,
+        name: format!("Episode {}", episode_id),
+        path: path.to_path_buf(),
+        size: fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(1000),
+        length,
+        keep: true,
+    }
+}
+
+#[test]
+fn test_copies_with_the_same_content_are_grouped() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("movie.mkv");
+    let second = dir.path().join("movie (1).mkv");
+    let same_size = dir.path().join("other.mkv");
+    fs::write(&first, b"the same video").unwrap();
+    fs::write(&second, b"the same video").unwrap();
+    fs::write(&same_size, b"another  video").unwrap();
+
+    let groups = find_duplicates(vec![copy(1, &first, 0), copy(2, &same_size, 0), copy(3, &second, 0)]);
+    assert_eq!(groups.len(), 1, "A file of the same size with other content isn't a copy");
+    let ids: Vec<usize> = groups[0].copies.iter().map(|copy| copy.episode_id).collect();
+    assert_eq!(ids, vec![1, 3]);
+    assert!(groups[0].copies[0].keep, "The first copy is kept to start with");
+    assert!(!groups[0].copies[1].keep);
+    assert_eq!(groups[0].heading(), "2 copies of 14 B");
+}
+
+#[test]
+fn test_files_that_cant_be_read_go_by_size_and_length() {
+    let dir = TempDir::new().unwrap();
+    let offline = |name: &str| dir.path().join("offline").join(name);
+
+    let groups = find_duplicates(vec![
+        copy(1, &offline("a.mkv"), 5400),
+        copy(2, &offline("b.mkv"), 5400),
+        copy(3, &offline("c.mkv"), 6000),
+        copy(4, &offline("d.mkv"), 0),
+    ]);
+    assert_eq!(groups.len(), 1);
+    let ids: Vec<usize> = groups[0].copies.iter().map(|copy| copy.episode_id).collect();
+    assert_eq!(ids, vec![1, 2], "Without a length there is nothing to go by");
+}
+
+#[test]
+fn test_hash_covers_the_end_of_large_files() {
+    let dir = TempDir::new().unwrap();
+    let size = (HASH_SAMPLE_BYTES * 3) as usize;
+    let mut contents = vec![0u8; size];
+    let first = dir.path().join("first.mkv");
+    fs::write(&first, &contents).unwrap();
+    contents[size - 1] = 1;
+    let second = dir.path().join("second.mkv");
+    fs::write(&second, &contents).unwrap();
+
+    assert_ne!(quick_hash(&first).unwrap(), quick_hash(&second).unwrap());
+}
+
+#[test]
+fn test_a_group_has_to_keep_a_copy() {
+    let path = PathBuf::from("/videos/movie.mkv");
+    let mut group = DuplicateGroup { copies: vec![copy(1, &path, 60), copy(2, &path, 60)] };
+    group.copies[1].keep = false;
+    assert!(group.keeps_one());
+    assert_eq!(group.to_trash().map(|copy| copy.episode_id).collect::<Vec<_>>(), vec![2]);
+    assert!(group.copies[1].label().starts_with("[trash] Episode 2"));
+
+    group.copies[0].keep = false;
+    assert!(!group.keeps_one());
+}
+
+#[test]
+fn test_candidates_share_a_size_with_another_episode() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let root = resolver.get_root_dir().to_path_buf();
+
+    for (name, contents) in [("a.mkv", "twelve bytes"), ("b.mkv", "twelve bytes"), ("c.mkv", "a different size")] {
+        let path = root.join(name);
+        fs::write(&path, contents).unwrap();
+        database.import_episode_relative(path.to_str().unwrap(), name, &resolver).unwrap();
+    }
+
+    let candidates = database.get_duplicate_candidates(&resolver).unwrap();
+    let paths: Vec<PathBuf> = candidates.iter().map(|copy| copy.path.clone()).collect();
+    assert_eq!(paths, vec![root.join("a.mkv"), root.join("b.mkv")]);
+    assert_eq!(find_duplicates(candidates).len(), 1);
+}
@@ -19,6 +19,9 @@ fn create_episode_detail(
         episode_number: episode_number.to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     }
 }
 
@@ -129,4 +132,15 @@ fn test_progress_field_display_names() {
 fn test_progress_fields_not_editable() {
     assert!(!EpisodeField::LastWatchedTime.is_editable());
     assert!(!EpisodeField::LastProgressTime.is_editable());
-}
\ No newline at end of file
+}
+#[test]
+fn test_play_count_field() {
+    let mut details = create_episode_detail("Title", "2020", "false", "0", "1");
+    assert_eq!(EpisodeField::PlayCount.get_field_value(&details), "0");
+
+    details.play_count = 3;
+    assert_eq!(EpisodeField::PlayCount.get_field_value(&details), "3");
+    assert_eq!(EpisodeField::PlayCount.display_name(), "Plays");
+    assert!(!EpisodeField::PlayCount.is_editable());
+    assert_eq!(EpisodeField::from(11), EpisodeField::PlayCount);
+}
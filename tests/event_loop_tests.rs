@@ -0,0 +1,143 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use movies::buffer::BufferManager;
+use movies::config::Config;
+use movies::database::Database;
+use movies::event_loop::{self, Startup};
+use movies::input::EventSource;
+use movies::path_resolver::PathResolver;
+use movies::terminal;
+use movies::theme::Theme;
+use std::collections::VecDeque;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+const WIDTH: usize = 100;
+const HEIGHT: usize = 30;
+
+/// Key presses handed to the main loop one after another, all ready at once
+/// Running out is an error, so a script that doesn't quit fails instead of hanging
+struct ScriptedEvents {
+    events: VecDeque<Event>,
+}
+
+impl ScriptedEvents {
+    fn keys(codes: impl IntoIterator<Item = KeyCode>) -> Self {
+        ScriptedEvents {
+            events: codes.into_iter().map(|code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE))).collect(),
+        }
+    }
+}
+
+impl EventSource for ScriptedEvents {
+    fn poll(&mut self, _timeout: Duration) -> io::Result<bool> {
+        if self.events.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "the scripted events ran out"));
+        }
+        Ok(true)
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        self.events
+            .pop_front()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "the scripted events ran out"))
+    }
+}
+
+/// A library of two videos in `dir`, with its database opened
+fn library(dir: &TempDir) -> (Database, PathResolver) {
+    let db_path = dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    for name in ["Alpha", "Bravo"] {
+        let video = dir.path().join(format!("{}.mkv", name));
+        std::fs::write(&video, b"").unwrap();
+        database.import_episode_relative(video.to_str().unwrap(), name, &resolver).unwrap();
+    }
+    (database, resolver)
+}
+
+/// Run the main loop headlessly over the library in `dir` with `events`
+fn run(dir: &TempDir, events: &mut ScriptedEvents) -> (io::Result<()>, BufferManager) {
+    run_with_problems(dir, events, Vec::new())
+}
+
+/// Like `run`, starting with `config_problems` found in config.yaml
+fn run_with_problems(dir: &TempDir, events: &mut ScriptedEvents, config_problems: Vec<String>) -> (io::Result<()>, BufferManager) {
+    let (database, resolver) = library(dir);
+    terminal::set_headless(WIDTH, HEIGHT);
+    let mut buffer_manager = BufferManager::new(WIDTH, HEIGHT);
+    let config = Config {
+        restore_session: false,
+        ..Config::default()
+    };
+    let startup = Startup {
+        entries: database.get_entries().unwrap(),
+        config,
+        theme: Theme::default(),
+        database: Some(Arc::new(database)),
+        resolver: Some(resolver),
+        config_path: dir.path().join("config.yaml"),
+        startup_message: String::new(),
+        show_tutorial: false,
+        handle_signals: false,
+        config_problems,
+    };
+    let result = event_loop::run(startup, events, &mut buffer_manager);
+    (result, buffer_manager)
+}
+
+#[test]
+#[serial_test::serial]
+fn test_rename_an_episode_and_quit() {
+    let dir = TempDir::new().unwrap();
+    let mut keys = vec![KeyCode::Down, KeyCode::F(2), KeyCode::End];
+    keys.extend(" II".chars().map(KeyCode::Char));
+    keys.extend([KeyCode::F(2), KeyCode::Esc]);
+    let mut events = ScriptedEvents::keys(keys);
+
+    let (result, buffer_manager) = run(&dir, &mut events);
+    result.unwrap();
+    assert!(events.events.is_empty(), "Esc in the library quits after the last key");
+
+    let database = Database::open(&dir.path().join("videos.sqlite")).unwrap();
+    let renamed = database
+        .get_entries()
+        .unwrap()
+        .iter()
+        .find_map(|entry| match entry {
+            movies::util::Entry::Episode { episode_id, name, .. } if name.starts_with("Bravo") => Some(*episode_id),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(database.get_episode_detail(renamed).unwrap().title, "Bravo II");
+    assert!(buffer_manager.desired_text().contains("Bravo II"));
+}
+
+#[test]
+#[serial_test::serial]
+fn test_running_out_of_events_ends_the_loop() {
+    let dir = TempDir::new().unwrap();
+    let mut events = ScriptedEvents::keys([KeyCode::Down]);
+    let (result, _) = run(&dir, &mut events);
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+#[serial_test::serial]
+fn test_config_problems_open_the_startup_report() {
+    let dir = TempDir::new().unwrap();
+    let problem = "Unknown setting 'vidoe_player' is ignored; did you mean 'video_player'?".to_string();
+    let mut events = ScriptedEvents::keys([]);
+    let (_, buffer_manager) = run_with_problems(&dir, &mut events, vec![problem]);
+    let text = buffer_manager.desired_text();
+    assert!(text.contains("Startup report"));
+    assert!(text.contains("did you mean 'video_player'?"));
+
+    // Esc closes the report and a second Esc quits
+    let mut events = ScriptedEvents::keys([KeyCode::Esc, KeyCode::Esc]);
+    let (result, buffer_manager) = run_with_problems(&dir, &mut events, vec!["Unknown setting 'foo' is ignored".to_string()]);
+    result.unwrap();
+    assert!(!buffer_manager.desired_text().contains("Startup report"));
+}
@@ -0,0 +1,139 @@
+use movies::export::{
+    describe_changes, escape_csv_field, CSV_HEADER, merge_record, parse_csv, parse_json, records_to_csv, records_to_json, ImportColumns,
+    LibraryRecord,
+};
+
+fn sample_record() -> LibraryRecord {
+    LibraryRecord {
+        location: "Shows/The Wire/S01E01.mkv".to_string(),
+        title: "The Target, Part 1".to_string(),
+        year: Some(2002),
+        watched: true,
+        length: Some(3720),
+        series: Some("The Wire".to_string()),
+        season: Some(1),
+        episode_number: Some(1),
+        last_watched_time: Some("2024-01-01T12:00:00+00:00".to_string()),
+        last_progress_time: Some(0),
+        root: None,
+    }
+}
+
+#[test]
+fn test_escape_csv_field_quotes_special_characters() {
+    assert_eq!(escape_csv_field("plain"), "plain");
+    assert_eq!(escape_csv_field("a,b"), "\"a,b\"");
+    assert_eq!(escape_csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    assert_eq!(escape_csv_field("line\nbreak"), "\"line\nbreak\"");
+}
+
+#[test]
+fn test_records_to_csv_includes_header_and_rows() {
+    let mut standalone = sample_record();
+    standalone.series = None;
+    standalone.season = None;
+    standalone.episode_number = None;
+    standalone.year = None;
+
+    let csv = records_to_csv(&[sample_record(), standalone]);
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("location,title,year,watched"));
+    assert!(lines[1].contains("\"The Target, Part 1\""));
+    assert!(lines[1].contains(",The Wire,1,1,"));
+    assert!(lines[2].contains(",,true,3720,,,,"));
+}
+
+#[test]
+fn test_records_to_json_round_trip() {
+    let records = vec![sample_record()];
+    let json = records_to_json(&records).unwrap();
+    let parsed: Vec<LibraryRecord> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(parsed, records);
+}
+
+#[test]
+fn test_parse_csv_round_trip() {
+    let mut standalone = sample_record();
+    standalone.title = "Quote \"Test\"\nSecond line".to_string();
+    standalone.series = None;
+    standalone.season = None;
+    standalone.episode_number = None;
+    let records = vec![sample_record(), standalone];
+
+    let (parsed, columns) = parse_csv(&records_to_csv(&records)).unwrap();
+
+    assert_eq!(parsed, records);
+    assert_eq!(columns, ImportColumns::named(CSV_HEADER));
+}
+
+#[test]
+fn test_parse_csv_rejects_invalid_numbers() {
+    let csv = "location,title,year\nmovie.mkv,Movie,nineteen\n";
+    let error = parse_csv(csv).unwrap_err();
+
+    assert!(error.contains("year"));
+    assert!(error.contains("line 2"));
+
+    // A quoted line break doesn't throw off the line numbers of the rows after it
+    let csv = "location,title,year\nfirst.mkv,\"Two\nlines\",2001\nsecond.mkv,Movie,nineteen\n";
+    let error = parse_csv(csv).unwrap_err();
+    assert!(error.contains("line 4"), "{}", error);
+}
+
+#[test]
+fn test_describe_changes_lists_only_differences() {
+    let current = sample_record();
+    let mut incoming = sample_record();
+    assert!(describe_changes(&current, &incoming, &ImportColumns::named(CSV_HEADER)).is_empty());
+
+    incoming.watched = false;
+    incoming.season = Some(2);
+    incoming.length = None;
+    let changes = describe_changes(&current, &incoming, &ImportColumns::named(CSV_HEADER));
+
+    assert_eq!(changes.len(), 2);
+    assert!(changes[0].starts_with("watched"));
+    assert!(changes[1].starts_with("season"));
+}
+
+#[test]
+fn test_missing_columns_keep_the_current_values() {
+    let current = sample_record();
+    let (records, columns) = parse_csv("location,title\nShows/The Wire/S01E01.mkv,The Target\n").unwrap();
+    assert!(columns.has("title") && !columns.has("watched") && !columns.has("series"));
+
+    let merged = merge_record(&current, &records[0], &columns);
+    assert_eq!(merged, LibraryRecord { title: "The Target".to_string(), ..current.clone() });
+    assert_eq!(describe_changes(&current, &records[0], &columns), vec!["title: 'The Target, Part 1' -> 'The Target'"]);
+
+    // An empty column is there, and clears the value
+    let (records, columns) = parse_csv("location,series,watched\nShows/The Wire/S01E01.mkv,,false\n").unwrap();
+    let merged = merge_record(&current, &records[0], &columns);
+    assert_eq!((merged.series, merged.season, merged.watched), (None, None, false));
+    assert_eq!(merged.episode_number, Some(1));
+}
+
+#[test]
+fn test_season_stays_only_within_its_series() {
+    let current = sample_record();
+    let (records, columns) = parse_csv("location,series\nShows/The Wire/S01E01.mkv,The Corner\n").unwrap();
+    let merged = merge_record(&current, &records[0], &columns);
+    assert_eq!((merged.series.as_deref(), merged.season), (Some("The Corner"), None));
+}
+
+#[test]
+fn test_parse_json_finds_the_fields_every_record_has() {
+    let json = r#"[
+        {"location": "a.mkv", "title": "A", "watched": true, "year": 2001},
+        {"location": "b.mkv", "title": "B", "watched": false}
+    ]"#;
+    let (records, columns) = parse_json(json).unwrap();
+    assert_eq!(records.len(), 2);
+    assert!(columns.has("watched") && !columns.has("year") && !columns.has("series"));
+
+    let (_, columns) = parse_json(&records_to_json(&[sample_record()]).unwrap()).unwrap();
+    assert_eq!(columns, ImportColumns::named(CSV_HEADER));
+}
@@ -0,0 +1,115 @@
+use movies::database::Database;
+use movies::external_editor::{apply, check_library, editor_command, parse, to_yaml, with_error, EditableDetails};
+use movies::location;
+use movies::path_resolver::PathResolver;
+use std::fs;
+use tempfile::TempDir;
+
+fn sample_details() -> EditableDetails {
+    EditableDetails {
+        title: "Undertow".to_string(),
+        year: Some(2003),
+        watched: false,
+        series: Some("The Wire".to_string()),
+        season: Some(2),
+        episode_number: Some(5),
+        notes: Some("First line\nSecond line".to_string()),
+        alt_titles: [("de".to_string(), "Sog".to_string())].into_iter().collect(),
+    }
+}
+
+#[test]
+fn test_yaml_round_trip() {
+    let details = sample_details();
+    let yaml = to_yaml(&details, "S02E05.mkv");
+    assert!(yaml.starts_with("# Details of S02E05.mkv\n"));
+    assert_eq!(parse(&yaml).unwrap(), details);
+}
+
+#[test]
+fn test_parse_accepts_unquoted_numbers_and_empty_values() {
+    let yaml = "title: 1984\nyear: 1984\nwatched: true\nseries:\nseason:\nepisode_number:\nnotes:\n";
+    let details = parse(yaml).unwrap();
+    assert_eq!(details.title, "1984");
+    assert_eq!(details.year, Some(1984));
+    assert!(details.watched);
+    assert_eq!(details.series, None);
+    assert_eq!(details.notes, None);
+}
+
+#[test]
+fn test_parse_rejects_invalid_details() {
+    assert!(parse("title: \"\"\n").unwrap_err().contains("title"));
+    assert!(parse("title: Heat\nyear: 95\n").unwrap_err().contains("year"));
+    assert!(parse("title: Heat\nseason: 1\n").unwrap_err().contains("season needs a series"));
+    assert!(parse("title: Heat\nseries: Crime\nepisode_number: 3\n").unwrap_err().contains("episode_number"));
+    assert!(parse("title: Heat\nyear: soon\n").is_err());
+    assert!(parse("title: Heat\nrating: 5\n").is_err(), "Unknown fields are mistakes, not ignored");
+}
+
+#[test]
+fn test_with_error_replaces_the_previous_error() {
+    let text = "title: Heat\n";
+    let once = with_error(text, "year must have four digits");
+    assert_eq!(once, "# ERROR: year must have four digits\ntitle: Heat\n");
+    let twice = with_error(&once, "season needs a series");
+    assert_eq!(twice, "# ERROR: season needs a series\ntitle: Heat\n");
+}
+
+#[test]
+fn test_editor_command_prefers_visual_then_editor() {
+    let env = |visual: Option<&str>, editor: Option<&str>| {
+        let visual = visual.map(str::to_string);
+        let editor = editor.map(str::to_string);
+        move |name: &str| match name {
+            "VISUAL" => visual.clone(),
+            "EDITOR" => editor.clone(),
+            _ => None,
+        }
+    };
+    assert_eq!(editor_command(env(Some("code --wait"), Some("nano"))), "code --wait");
+    assert_eq!(editor_command(env(Some(" "), Some("nano"))), "nano");
+    assert!(!editor_command(env(None, None)).is_empty());
+}
+
+#[test]
+#[serial_test::serial]
+fn test_apply_saves_the_changed_fields() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let path = dir.path().join("S02E05.mkv");
+    fs::write(&path, b"").unwrap();
+    database.import_episode_relative(&location::encode(&path), "S02E05.mkv", &resolver).unwrap();
+    let episode_id = database.find_episode_id(&path, &resolver).unwrap().unwrap();
+
+    let original = EditableDetails::from_detail(&database.get_episode_detail(episode_id).unwrap());
+    let mut edited = sample_details();
+    edited.season = Some(1);
+    check_library(&database, &edited).unwrap();
+
+    let changes = apply(&database, episode_id, &original, &edited).unwrap();
+    assert!(changes.contains(&"title") && changes.contains(&"series") && changes.contains(&"season"));
+    assert!(!changes.contains(&"watched"));
+    let saved = EditableDetails::from_detail(&database.get_episode_detail(episode_id).unwrap());
+    assert_eq!(saved, edited);
+
+    // Season 3 of a series with only season 1 can't be made
+    let mut skipped = edited.clone();
+    skipped.season = Some(3);
+    assert!(check_library(&database, &skipped).is_err());
+
+    // Taking the episode out of the series clears its season and number too
+    let mut standalone = edited.clone();
+    standalone.series = None;
+    standalone.season = None;
+    standalone.episode_number = None;
+    standalone.watched = true;
+    let changes = apply(&database, episode_id, &edited, &standalone).unwrap();
+    assert_eq!(changes, vec!["watched", "series", "season", "episode number"]);
+    let saved = database.get_episode_detail(episode_id).unwrap();
+    assert!(saved.series.is_none() && saved.season.is_none());
+    assert_eq!(saved.watched, "true");
+    assert!(saved.last_watched_time.is_some());
+}
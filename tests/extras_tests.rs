@@ -0,0 +1,37 @@
+use movies::extras::{is_sample_length, is_sample_name, SampleFiles};
+use std::path::Path;
+
+#[test]
+fn test_sample_names() {
+    assert!(is_sample_name(Path::new("/videos/Movie (2020)/Movie-sample.mkv")));
+    assert!(is_sample_name(Path::new("/videos/Movie/sample-movie.mkv")));
+    assert!(is_sample_name(Path::new("/videos/Movie/Movie (2020) - Trailer.mp4")));
+    assert!(is_sample_name(Path::new("/videos/Movie/Samples/movie.mkv")));
+    assert!(is_sample_name(Path::new("/videos/Movie/TRAILERS/teaser.mkv")));
+}
+
+#[test]
+fn test_words_inside_a_title_are_not_samples() {
+    assert!(!is_sample_name(Path::new("/videos/Trailer Park Boys/Trailer Park Boys S01E01.mkv")));
+    assert!(!is_sample_name(Path::new("/videos/The Sample Size.mkv")));
+    assert!(!is_sample_name(Path::new("/videos/Movies/Sampler.mkv")));
+}
+
+#[test]
+fn test_sample_lengths() {
+    assert!(is_sample_length(45, 60));
+    assert!(is_sample_length(60, 60));
+    assert!(!is_sample_length(61, 60));
+    // An unknown length or a maximum of 0 never counts
+    assert!(!is_sample_length(0, 60));
+    assert!(!is_sample_length(45, 0));
+}
+
+#[test]
+fn test_sample_files_config_values() {
+    assert_eq!(SampleFiles::from_config("skip"), Some(SampleFiles::Skip));
+    assert_eq!(SampleFiles::from_config(" Flag "), Some(SampleFiles::Flag));
+    assert_eq!(SampleFiles::from_config("import"), Some(SampleFiles::Import));
+    assert_eq!(SampleFiles::from_config("hide"), None);
+    assert_eq!(SampleFiles::default(), SampleFiles::Flag);
+}
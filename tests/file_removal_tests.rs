@@ -0,0 +1,49 @@
+use movies::file_removal::{remove_episode_files, Removal};
+use movies::path_resolver::PathResolver;
+use std::fs;
+use tempfile::TempDir;
+
+/// The trash follows XDG_DATA_HOME on Linux only
+#[cfg(target_os = "linux")]
+#[test]
+fn test_episode_files_go_to_the_trash() {
+    let dir = TempDir::new().unwrap();
+    // Keep the trash inside the temporary folder rather than the user's own
+    std::env::set_var("XDG_DATA_HOME", dir.path().join("data"));
+    let library = dir.path().join("library");
+    fs::create_dir_all(&library).unwrap();
+    let db_path = library.join("videos.sqlite");
+    fs::write(&db_path, "test").unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let root = resolver.get_root_dir().to_path_buf();
+
+    let video = root.join("movie.mkv");
+    fs::write(&video, b"video").unwrap();
+    let gone = root.join("movie.720p.mkv");
+    let removal = remove_episode_files(&[video.clone(), gone], &resolver).unwrap();
+    assert_eq!(removal, Removal { trashed: 1, deleted: 0 }, "A file already gone is passed over");
+    assert!(!video.exists());
+    assert!(dir.path().join("data/Trash/files/movie.mkv").exists());
+}
+
+#[test]
+fn test_files_outside_the_library_are_left_alone() {
+    let library = TempDir::new().unwrap();
+    let elsewhere = TempDir::new().unwrap();
+    let db_path = library.path().join("videos.sqlite");
+    fs::write(&db_path, "test").unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+
+    let outside = elsewhere.path().canonicalize().unwrap().join("movie.mkv");
+    fs::write(&outside, b"video").unwrap();
+    let error = remove_episode_files(std::slice::from_ref(&outside), &resolver).unwrap_err();
+    assert!(error.starts_with("Not in a folder of the library"));
+    assert!(outside.exists());
+}
+
+#[test]
+fn test_removal_summary() {
+    assert_eq!(Removal { trashed: 1, deleted: 0 }.summary("Movie"), "Moved 'Movie' to the trash");
+    assert_eq!(Removal { trashed: 0, deleted: 1 }.summary("Movie"), "Deleted 'Movie' for good; there was no trash to move it to");
+    assert_eq!(Removal::default().summary("Movie"), "Removed 'Movie' from the library; its file was already gone");
+}
@@ -0,0 +1,51 @@
+use movies::filter_history::{FilterHistory, MAX_FILTER_HISTORY};
+use tempfile::TempDir;
+
+/// Every filter in the history, newest first
+fn recall_all(history: &mut FilterHistory) -> Vec<String> {
+    let mut filters = Vec::new();
+    while let Some(filter) = history.older("") {
+        filters.push(filter.to_string());
+    }
+    history.stop_recalling();
+    filters
+}
+
+#[test]
+fn test_recalling_steps_through_kept_filters_and_back_to_the_draft() {
+    let mut history = FilterHistory::default();
+    assert_eq!(history.older("dra"), None);
+
+    for filter in ["lost", "unwatched <30min", "  ", "dark"] {
+        history.add(filter);
+    }
+    history.add("lost");
+    assert_eq!(recall_all(&mut history), ["lost", "dark", "unwatched <30min"], "Blank filters are not kept, repeats move up");
+
+    assert_eq!(history.older("dra"), Some("lost"));
+    assert_eq!(history.older("ignored"), Some("dark"));
+    assert_eq!(history.older(""), Some("unwatched <30min"));
+    assert_eq!(history.older(""), None, "Nothing older");
+    assert_eq!(history.newer(), Some("dark"));
+    assert_eq!(history.newer(), Some("lost"));
+    assert_eq!(history.newer(), Some("dra"), "Past the newest comes back to what was typed");
+    assert_eq!(history.newer(), None);
+}
+
+#[test]
+fn test_history_is_capped_and_saved() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("data").join("filter_history.json");
+    let mut history = FilterHistory::load(path.clone());
+    assert!(recall_all(&mut history).is_empty());
+
+    for index in 0..MAX_FILTER_HISTORY + 5 {
+        history.add(&format!("filter {}", index));
+    }
+    let kept = recall_all(&mut history);
+    assert_eq!(kept.len(), MAX_FILTER_HISTORY);
+    assert_eq!(kept.last().map(String::as_str), Some("filter 5"));
+
+    let mut reloaded = FilterHistory::load(path);
+    assert_eq!(recall_all(&mut reloaded), kept);
+}
@@ -1,11 +1,13 @@
+use movies::app_state::{AppState, DrawContext};
 use movies::buffer::BufferManager;
+use movies::database::Database;
 use movies::theme::Theme;
+use movies::notifications::Notifications;
 use movies::util::{Entry, Mode, ViewContext};
 use movies::dto::{EpisodeDetail, Series};
 use movies::episode_field::EpisodeField;
-use movies::menu::MenuItem;
 use movies::path_resolver::PathResolver;
-use std::collections::HashSet;
+use std::path::Path;
 use tempfile::TempDir;
 
 /// Helper function to create a test EpisodeDetail
@@ -20,6 +22,9 @@ fn create_test_episode_detail() -> EpisodeDetail {
         episode_number: String::from("1"),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     }
 }
 
@@ -31,6 +36,18 @@ fn create_test_path_resolver() -> PathResolver {
     PathResolver::from_database_path(&db_path).unwrap()
 }
 
+/// Helper function to create an empty in-memory library
+fn create_test_database() -> Database {
+    Database::open(Path::new(":memory:")).unwrap()
+}
+
+/// Helper function to create notifications showing one status message
+fn status_message(message: &str) -> Notifications {
+    let mut notifications = Notifications::default();
+    notifications.info(message);
+    notifications
+}
+
 /// Helper function to create test entries (empty to avoid database access)
 fn create_test_entries() -> Vec<Entry> {
     vec![]
@@ -50,15 +67,10 @@ fn test_all_ui_modes_render_successfully() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Test all modes
     let modes = vec![
@@ -75,29 +87,12 @@ fn test_all_ui_modes_render_successfully() {
         buffer_manager.force_full_redraw();
         
         let result = movies::display::draw_screen(
-            &entries,
-            0,
-            &mut first_entry,
-            "",
-            &theme,
-            &mode,
-            &String::new(),
-            &edit_details,
-            EpisodeField::Title,
-            0,
-            &series,
-            &mut series_selection,
-            "",
-            None,
-            &None,
-            &dirty_fields,
-            &menu_items,
-            0,
-            false,
-            &mut first_series,
-            &view_context,
-            "",
-            &resolver,
+            &mut AppState {
+                mode: mode.clone(),
+                edit_details: edit_details.clone(),
+                ..AppState::new(entries.clone())
+            },
+            &draw_context,
             &mut buffer_manager,
         );
         
@@ -119,128 +114,54 @@ fn test_navigation_updates_buffer_correctly() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Render initial state (item 0 selected)
     let result1 = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result1.is_ok(), "Initial render should succeed");
     
     // Navigate to item 1 (simulating down arrow)
     let result2 = movies::display::draw_screen(
-        &entries,
-        1,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            current_item: 1,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result2.is_ok(), "Navigation to item 1 should succeed");
     
     // Navigate to item 2 (simulating another down arrow)
     let result3 = movies::display::draw_screen(
-        &entries,
-        2,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            current_item: 2,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result3.is_ok(), "Navigation to item 2 should succeed");
     
     // Navigate back to item 1 (simulating up arrow)
     let result4 = movies::display::draw_screen(
-        &entries,
-        1,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            current_item: 1,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result4.is_ok(), "Navigation back to item 1 should succeed");
@@ -256,41 +177,19 @@ fn test_edit_mode_text_input_updates() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let mut edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Render initial Edit mode
     let result1 = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Edit,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            mode: Mode::Edit,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result1.is_ok(), "Initial Edit mode render should succeed");
@@ -299,58 +198,25 @@ fn test_edit_mode_text_input_updates() {
     edit_details.title = String::from("Test Episode - Modified");
     
     let result2 = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Edit,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            mode: Mode::Edit,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result2.is_ok(), "Edit mode with modified text should render successfully");
     
     // Simulate cursor movement - change field
     let result3 = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Edit,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Year,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            mode: Mode::Edit,
+            edit_details: edit_details.clone(),
+            edit_field: EpisodeField::Year,
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result3.is_ok(), "Edit mode with different field should render successfully");
@@ -366,44 +232,22 @@ fn test_terminal_resize_in_all_modes() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     let modes = vec![Mode::Browse, Mode::Edit, Mode::Menu];
     
     for mode in modes {
         // Render at initial size
         let result1 = movies::display::draw_screen(
-            &entries,
-            0,
-            &mut first_entry,
-            "",
-            &theme,
-            &mode,
-            &String::new(),
-            &edit_details,
-            EpisodeField::Title,
-            0,
-            &series,
-            &mut series_selection,
-            "",
-            None,
-            &None,
-            &dirty_fields,
-            &menu_items,
-            0,
-            false,
-            &mut first_series,
-            &view_context,
-            "",
-            &resolver,
+            &mut AppState {
+                mode: mode.clone(),
+                edit_details: edit_details.clone(),
+                ..AppState::new(entries.clone())
+            },
+            &draw_context,
             &mut buffer_manager,
         );
         assert!(result1.is_ok(), "Mode should render at initial size");
@@ -413,29 +257,12 @@ fn test_terminal_resize_in_all_modes() {
         
         // Render at new size
         let result2 = movies::display::draw_screen(
-            &entries,
-            0,
-            &mut first_entry,
-            "",
-            &theme,
-            &mode,
-            &String::new(),
-            &edit_details,
-            EpisodeField::Title,
-            0,
-            &series,
-            &mut series_selection,
-            "",
-            None,
-            &None,
-            &dirty_fields,
-            &menu_items,
-            0,
-            false,
-            &mut first_series,
-            &view_context,
-            "",
-            &resolver,
+            &mut AppState {
+                mode: mode.clone(),
+                edit_details: edit_details.clone(),
+                ..AppState::new(entries.clone())
+            },
+            &draw_context,
             &mut buffer_manager,
         );
         assert!(result2.is_ok(), "Mode should render after resize");
@@ -444,29 +271,12 @@ fn test_terminal_resize_in_all_modes() {
         buffer_manager.resize(60, 20);
         
         let result3 = movies::display::draw_screen(
-            &entries,
-            0,
-            &mut first_entry,
-            "",
-            &theme,
-            &mode,
-            &String::new(),
-            &edit_details,
-            EpisodeField::Title,
-            0,
-            &series,
-            &mut series_selection,
-            "",
-            None,
-            &None,
-            &dirty_fields,
-            &menu_items,
-            0,
-            false,
-            &mut first_series,
-            &view_context,
-            "",
-            &resolver,
+            &mut AppState {
+                mode: mode.clone(),
+                edit_details: edit_details.clone(),
+                ..AppState::new(entries.clone())
+            },
+            &draw_context,
             &mut buffer_manager,
         );
         assert!(result3.is_ok(), "Mode should render after smaller resize");
@@ -486,41 +296,18 @@ fn test_mode_switching_triggers_full_redraw() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Start in Browse mode
     let result1 = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result1.is_ok(), "Browse mode should render");
@@ -528,29 +315,12 @@ fn test_mode_switching_triggers_full_redraw() {
     // Switch to Edit mode (force full redraw)
     buffer_manager.force_full_redraw();
     let result2 = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Edit,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            mode: Mode::Edit,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result2.is_ok(), "Edit mode should render after mode switch");
@@ -558,29 +328,12 @@ fn test_mode_switching_triggers_full_redraw() {
     // Switch to Menu mode (force full redraw)
     buffer_manager.force_full_redraw();
     let result3 = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Menu,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            mode: Mode::Menu,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result3.is_ok(), "Menu mode should render after mode switch");
@@ -588,29 +341,11 @@ fn test_mode_switching_triggers_full_redraw() {
     // Switch back to Browse mode (force full redraw)
     buffer_manager.force_full_redraw();
     let result4 = movies::display::draw_screen(
-        &entries,
-        0,
-        &mut first_entry,
-        "",
-        &theme,
-        &Mode::Browse,
-        &String::new(),
-        &edit_details,
-        EpisodeField::Title,
-        0,
-        &series,
-        &mut series_selection,
-        "",
-        None,
-        &None,
-        &dirty_fields,
-        &menu_items,
-        0,
-        false,
-        &mut first_series,
-        &view_context,
-        "",
-        &resolver,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
         &mut buffer_manager,
     );
     assert!(result4.is_ok(), "Browse mode should render after returning from Menu");
@@ -626,74 +361,80 @@ fn test_no_visual_artifacts_after_operations() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Perform a sequence of operations
     
     // 1. Initial render in Browse mode
     let _ = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     
     // 2. Navigate down
     let _ = movies::display::draw_screen(
-        &entries, 1, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            current_item: 1,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     
     // 3. Switch to Edit mode
     buffer_manager.force_full_redraw();
     let _ = movies::display::draw_screen(
-        &entries, 1, &mut first_entry, "", &theme, &Mode::Edit,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            current_item: 1,
+            mode: Mode::Edit,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     
     // 4. Resize terminal
     buffer_manager.resize(100, 30);
     let _ = movies::display::draw_screen(
-        &entries, 1, &mut first_entry, "", &theme, &Mode::Edit,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            current_item: 1,
+            mode: Mode::Edit,
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     
     // 5. Switch back to Browse mode
     buffer_manager.force_full_redraw();
     let _ = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     
     // 6. Resize back to original
     buffer_manager.resize(80, 24);
     let result = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     
     // After all operations, rendering should still work correctly
@@ -714,43 +455,20 @@ fn test_rapid_navigation_no_corruption() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Simulate rapid navigation (20 quick movements)
     // Since entries is empty, we just test with current_item = 0
     for i in 0..20 {
         let result = movies::display::draw_screen(
-            &entries,
-            0, // Always 0 since entries is empty
-            &mut first_entry,
-            "",
-            &theme,
-            &Mode::Browse,
-            &String::new(),
-            &edit_details,
-            EpisodeField::Title,
-            0,
-            &series,
-            &mut series_selection,
-            "",
-            None,
-            &None,
-            &dirty_fields,
-            &menu_items,
-            0,
-            false,
-            &mut first_series,
-            &view_context,
-            "",
-            &resolver,
+            &mut AppState {
+                edit_details: edit_details.clone(),
+                ..AppState::new(entries.clone())
+            },
+            &draw_context,
             &mut buffer_manager,
         );
         
@@ -771,53 +489,54 @@ fn test_status_message_updates() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Render with no status message
     let result1 = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     assert!(result1.is_ok(), "Render without status message should succeed");
     
     // Render with status message
     let result2 = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "Test status message", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            notifications: status_message("Test status message"),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     assert!(result2.is_ok(), "Render with status message should succeed");
     
     // Render with different status message
     let result3 = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "Different message", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            notifications: status_message("Different message"),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     assert!(result3.is_ok(), "Render with different status message should succeed");
     
     // Clear status message
     let result4 = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     assert!(result4.is_ok(), "Render after clearing status message should succeed");
 }
@@ -832,53 +551,54 @@ fn test_filter_mode_updates() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Render without filter
     let result1 = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     assert!(result1.is_ok(), "Render without filter should succeed");
     
     // Render with filter text
     let result2 = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "test", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            search: String::from("test"),
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     assert!(result2.is_ok(), "Render with filter should succeed");
     
     // Update filter text
     let result3 = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "test series", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            search: String::from("test series"),
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     assert!(result3.is_ok(), "Render with updated filter should succeed");
     
     // Clear filter
     let result4 = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &view_context, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     assert!(result4.is_ok(), "Render after clearing filter should succeed");
 }
@@ -892,15 +612,10 @@ fn test_entry_mode_path_input() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Simulate typing a path character by character
     let paths = vec![
@@ -918,11 +633,14 @@ fn test_entry_mode_path_input() {
     
     for path in paths {
         let result = movies::display::draw_screen(
-            &entries, 0, &mut first_entry, "", &theme, &Mode::Entry,
-            &String::from(path), &edit_details, EpisodeField::Title, 0,
-            &series, &mut series_selection, "", None, &None,
-            &dirty_fields, &menu_items, 0, false, &mut first_series,
-            &view_context, "", &resolver, &mut buffer_manager,
+            &mut AppState {
+                mode: Mode::Entry,
+                entry_path: String::from(path),
+                edit_details: edit_details.clone(),
+                ..AppState::new(entries.clone())
+            },
+            &draw_context,
+            &mut buffer_manager,
         );
         
         assert!(result.is_ok(), "Entry mode with path '{}' should render successfully", path);
@@ -943,7 +661,6 @@ fn test_series_select_mode_navigation() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
     
     // Create test series list
@@ -962,21 +679,22 @@ fn test_series_select_mode_navigation() {
         },
     ];
     
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Navigate through series list
     for i in 0..series.len() {
         let result = movies::display::draw_screen(
-            &entries, 0, &mut first_entry, "", &theme, &Mode::SeriesSelect,
-            &String::new(), &edit_details, EpisodeField::Title, 0,
-            &series, &mut series_selection, "", None, &None,
-            &dirty_fields, &menu_items, i, false, &mut first_series,
-            &view_context, "", &resolver, &mut buffer_manager,
+            &mut AppState {
+                mode: Mode::SeriesSelect,
+                edit_details: edit_details.clone(),
+                series: series.clone(),
+                menu_selection: i,
+                ..AppState::new(entries.clone())
+            },
+            &draw_context,
+            &mut buffer_manager,
         );
         
         assert!(result.is_ok(), "SeriesSelect mode at position {} should render", i);
@@ -992,15 +710,10 @@ fn test_series_create_mode_text_input() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Simulate typing a series name
     let names = vec![
@@ -1018,11 +731,14 @@ fn test_series_create_mode_text_input() {
     
     for name in names {
         let result = movies::display::draw_screen(
-            &entries, 0, &mut first_entry, "", &theme, &Mode::SeriesCreate,
-            &String::new(), &edit_details, EpisodeField::Title, 0,
-            &series, &mut series_selection, name, None, &None,
-            &dirty_fields, &menu_items, 0, false, &mut first_series,
-            &view_context, "", &resolver, &mut buffer_manager,
+            &mut AppState {
+                mode: Mode::SeriesCreate,
+                edit_details: edit_details.clone(),
+                new_series: String::from(name),
+                ..AppState::new(entries.clone())
+            },
+            &draw_context,
+            &mut buffer_manager,
         );
         
         assert!(result.is_ok(), "SeriesCreate mode with name '{}' should render", name);
@@ -1039,47 +755,25 @@ fn test_menu_mode_navigation() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    
-    // Create test menu items
-    let menu_items = vec![
-        MenuItem {
-            label: String::from("Menu Item 1"),
-            hotkey: None,
-            action: movies::menu::MenuAction::ToggleWatched,
-            location: movies::menu::MenuLocation::ContextMenu,
-        },
-        MenuItem {
-            label: String::from("Menu Item 2"),
-            hotkey: None,
-            action: movies::menu::MenuAction::ToggleWatched,
-            location: movies::menu::MenuLocation::ContextMenu,
-        },
-        MenuItem {
-            label: String::from("Menu Item 3"),
-            hotkey: None,
-            action: movies::menu::MenuAction::ToggleWatched,
-            location: movies::menu::MenuLocation::ContextMenu,
-        },
-    ];
     
-    let mut first_series = 0;
-    let view_context = ViewContext::TopLevel;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
+    
+    // The menu lists the items available for the state it is drawn from
+    let mut state = AppState {
+        mode: Mode::Menu,
+        edit_details: edit_details.clone(),
+        ..AppState::new(entries.clone())
+    };
+    let menu_items = state.menu_items();
+    assert!(!menu_items.is_empty(), "The menu should have items to navigate");
     
     // Navigate through menu items
     for i in 0..menu_items.len() {
-        let result = movies::display::draw_screen(
-            &entries, 0, &mut first_entry, "", &theme, &Mode::Menu,
-            &String::new(), &edit_details, EpisodeField::Title, 0,
-            &series, &mut series_selection, "", None, &None,
-            &dirty_fields, &menu_items, i, false, &mut first_series,
-            &view_context, "", &resolver, &mut buffer_manager,
-        );
+        state.menu_selection = i;
+        let result = movies::display::draw_screen(&mut state, &draw_context, &mut buffer_manager);
         
         assert!(result.is_ok(), "Menu mode at position {} should render", i);
     }
@@ -1094,42 +788,43 @@ fn test_buffer_consistency_across_view_contexts() {
     let mut buffer_manager = BufferManager::new(80, 24);
     let theme = Theme::default();
     let entries = create_test_entries();
-    let mut first_entry = 0;
     let edit_details = create_test_episode_detail();
-    let series: Vec<Series> = vec![];
-    let mut series_selection = None;
-    let dirty_fields = HashSet::new();
-    let menu_items: Vec<MenuItem> = vec![];
-    let mut first_series = 0;
+    let database = create_test_database();
     let resolver = create_test_path_resolver();
+    let draw_context = DrawContext { theme: &theme, database: &database, resolver: &resolver, scroll_margin: 0, accessible: false };
     
     // Test TopLevel view context
     let result1 = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &ViewContext::TopLevel, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     assert!(result1.is_ok(), "TopLevel view context should render");
     
     // Test Series view context
     let result2 = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &ViewContext::Series { series_id: 1, series_name: String::from("Test Series") }, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            view_context: ViewContext::Series { series_id: 1, series_name: String::from("Test Series") },
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     assert!(result2.is_ok(), "Series view context should render");
     
     // Test Season view context
     let result3 = movies::display::draw_screen(
-        &entries, 0, &mut first_entry, "", &theme, &Mode::Browse,
-        &String::new(), &edit_details, EpisodeField::Title, 0,
-        &series, &mut series_selection, "", None, &None,
-        &dirty_fields, &menu_items, 0, false, &mut first_series,
-        &ViewContext::Season { season_id: 1, series_name: String::from("Test Series"), season_number: 1 }, "", &resolver, &mut buffer_manager,
+        &mut AppState {
+            edit_details: edit_details.clone(),
+            view_context: ViewContext::Season { season_id: 1, series_name: String::from("Test Series"), season_number: 1 },
+            ..AppState::new(entries.clone())
+        },
+        &draw_context,
+        &mut buffer_manager,
     );
     assert!(result3.is_ok(), "Season view context should render");
 }
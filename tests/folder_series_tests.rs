@@ -0,0 +1,28 @@
+use movies::folder_series::{infer_series, series_proposals, SeriesProposal};
+
+#[test]
+fn test_season_folders_name_the_series() {
+    assert_eq!(infer_series("Show Name/Season 02/file.mkv"), Some(("Show Name".to_string(), 2)));
+    assert_eq!(infer_series("TV/Show/season_3/file.mkv"), Some(("Show".to_string(), 3)));
+    assert_eq!(infer_series("Show/Series 1/file.mkv"), Some(("Show".to_string(), 1)));
+    assert_eq!(infer_series("Show/S04/file.mkv"), Some(("Show".to_string(), 4)));
+    assert_eq!(infer_series("/media/Show/Specials/file.mkv"), Some(("Show".to_string(), 0)));
+}
+
+#[test]
+fn test_other_folders_are_left_alone() {
+    assert_eq!(infer_series("Show/file.mkv"), None);
+    assert_eq!(infer_series("Season 1/file.mkv"), None);
+    assert_eq!(infer_series("Movies/Seinfeld/file.mkv"), None);
+    assert_eq!(infer_series("Show/Season One/file.mkv"), None);
+    assert_eq!(infer_series("Show/Season/file.mkv"), None);
+}
+
+#[test]
+fn test_proposals_keep_only_episodes_in_season_folders() {
+    let proposals = series_proposals(vec![(1, "Movie.mkv".to_string()), (2, "Show/S01/e1.mkv".to_string())]);
+    assert_eq!(
+        proposals,
+        vec![SeriesProposal { episode_id: 2, location: "Show/S01/e1.mkv".to_string(), series: "Show".to_string(), season: 1 }]
+    );
+}
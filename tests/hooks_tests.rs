@@ -0,0 +1,136 @@
+use movies::database::Database;
+use movies::hooks::{self, fire, save_changes, script_path, HookEpisode, HookEvent};
+use movies::location;
+use movies::path_resolver::PathResolver;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Open a library in `dir` holding one episode, returning it with the episode's id and path
+fn library_with_episode(dir: &Path) -> (Database, usize, PathBuf) {
+    let db_path = dir.join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let path = dir.join("Alien.1979.mkv");
+    fs::write(&path, b"").unwrap();
+    database.import_episode_relative(&location::encode(&path), "Alien.1979.mkv", &resolver).unwrap();
+    let episode_id = database.find_episode_id(&path, &resolver).unwrap().unwrap();
+    (database, episode_id, path)
+}
+
+#[test]
+fn test_function_names() {
+    assert_eq!(HookEvent::Import.function_name(), "on_import");
+    assert_eq!(HookEvent::Play.function_name(), "on_play");
+    assert_eq!(HookEvent::Watched.function_name(), "on_watched");
+}
+
+#[test]
+fn test_script_path_is_relative_to_the_config_folder() {
+    let config_dir = Path::new("/home/user/.config/movies");
+    assert_eq!(script_path("hooks.rhai", config_dir), PathBuf::from("/home/user/.config/movies/hooks.rhai"));
+    assert_eq!(script_path(" /opt/hooks.rhai ", config_dir), PathBuf::from("/opt/hooks.rhai"));
+}
+
+#[test]
+#[serial_test::serial]
+fn test_save_changes_writes_only_the_changed_fields() {
+    let dir = TempDir::new().unwrap();
+    let (database, episode_id, path) = library_with_episode(dir.path());
+    let original = HookEpisode::load(&database, episode_id, &path).unwrap();
+    assert_eq!(original.path, path.to_string_lossy());
+    assert!(!original.watched);
+
+    let edited = HookEpisode {
+        title: "Alien".to_string(),
+        year: "1979".to_string(),
+        watched: true,
+        notes: "Director's cut".to_string(),
+        series: Some("Ignored".to_string()),
+        ..original.clone()
+    };
+    assert_eq!(save_changes(&database, &original, &edited).unwrap(), vec!["title", "year", "watched", "notes"]);
+    let saved = HookEpisode::load(&database, episode_id, &path).unwrap();
+    assert_eq!(saved, HookEpisode { series: None, ..edited.clone() });
+
+    // A blank title is never saved
+    let blank = HookEpisode { title: " ".to_string(), ..saved.clone() };
+    assert!(save_changes(&database, &saved, &blank).unwrap().is_empty());
+}
+
+#[test]
+#[serial_test::serial]
+fn test_fire_does_nothing_without_a_script() {
+    let dir = TempDir::new().unwrap();
+    let (database, episode_id, path) = library_with_episode(dir.path());
+    hooks::install(None).unwrap();
+    assert!(!hooks::handles(HookEvent::Import));
+    assert_eq!(fire(&database, HookEvent::Import, episode_id, &path), Ok(Vec::new()));
+}
+
+#[test]
+#[cfg(not(feature = "scripting"))]
+fn test_install_needs_the_scripting_feature() {
+    assert!(hooks::install(Some(Path::new("hooks.rhai"))).unwrap_err().contains("scripting feature"));
+}
+
+#[test]
+#[cfg(feature = "scripting")]
+#[serial_test::serial]
+fn test_hooks_change_the_episode() {
+    let dir = TempDir::new().unwrap();
+    let (database, episode_id, path) = library_with_episode(dir.path());
+    let script = dir.path().join("hooks.rhai");
+    fs::write(
+        &script,
+        r#"
+        fn on_import(episode) {
+            let name = episode.title;
+            name.replace(".1979.mkv", "");
+            episode.title = name;
+            episode.year = 1979;
+            episode
+        }
+        fn on_watched(episode) {
+            episode.notes = "watched " + episode.title;
+            episode
+        }
+        fn on_play(episode) {
+            print("playing " + episode.path);
+        }
+        "#,
+    )
+    .unwrap();
+    hooks::install(Some(&script)).unwrap();
+    assert!(hooks::handles(HookEvent::Import) && hooks::handles(HookEvent::Play));
+
+    assert_eq!(fire(&database, HookEvent::Import, episode_id, &path), Ok(vec!["title", "year"]));
+    assert_eq!(fire(&database, HookEvent::Play, episode_id, &path), Ok(Vec::new()));
+    assert_eq!(fire(&database, HookEvent::Watched, episode_id, &path), Ok(vec!["notes"]));
+    let details = database.get_episode_detail(episode_id).unwrap();
+    assert_eq!(details.title, "Alien");
+    assert_eq!(details.year, "1979");
+    assert_eq!(details.notes.as_deref(), Some("watched Alien"));
+
+    hooks::install(None).unwrap();
+}
+
+#[test]
+#[cfg(feature = "scripting")]
+#[serial_test::serial]
+fn test_broken_scripts_are_reported() {
+    let dir = TempDir::new().unwrap();
+    let (database, episode_id, path) = library_with_episode(dir.path());
+    let script = dir.path().join("hooks.rhai");
+
+    fs::write(&script, "fn on_import(episode) {").unwrap();
+    assert!(hooks::install(Some(&script)).unwrap_err().contains("hooks.rhai"));
+    assert!(hooks::install(Some(&dir.path().join("missing.rhai"))).is_err());
+
+    fs::write(&script, "fn on_import(episode) { episode.year = \"soon\"; episode }\nfn on_play(episode) { loop {} }").unwrap();
+    hooks::install(Some(&script)).unwrap();
+    assert!(fire(&database, HookEvent::Import, episode_id, &path).unwrap_err().contains("year"));
+    assert!(fire(&database, HookEvent::Play, episode_id, &path).unwrap_err().starts_with("on_play failed"), "Endless loops are stopped");
+
+    hooks::install(None).unwrap();
+}
@@ -0,0 +1,66 @@
+use crossterm::event::KeyCode;
+use movies::input::{entry_at_row, is_scroll_key, ClickTracker, ScrollAccelerator, DOUBLE_CLICK_WINDOW, REPEAT_WINDOW};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_held_key_accelerates_along_curve() {
+    let mut accelerator = ScrollAccelerator::new(vec![1, 3, 10], 2);
+    let start = Instant::now();
+    let tick = Duration::from_millis(30);
+
+    let steps: Vec<usize> = (0..6)
+        .map(|i| accelerator.step_for(KeyCode::Down, start + tick * i))
+        .collect();
+
+    assert_eq!(steps, vec![1, 1, 3, 3, 10, 10]);
+}
+
+#[test]
+fn test_pause_or_key_change_resets_acceleration() {
+    let mut accelerator = ScrollAccelerator::new(vec![1, 5], 1);
+    let start = Instant::now();
+
+    assert_eq!(accelerator.step_for(KeyCode::Down, start), 1);
+    assert_eq!(accelerator.step_for(KeyCode::Down, start + Duration::from_millis(20)), 5);
+
+    // Changing direction starts a new streak
+    assert_eq!(accelerator.step_for(KeyCode::Up, start + Duration::from_millis(40)), 1);
+
+    // A pause longer than the repeat window is a fresh single press
+    let later = start + Duration::from_millis(40) + REPEAT_WINDOW * 2;
+    assert_eq!(accelerator.step_for(KeyCode::Up, later), 1);
+}
+
+#[test]
+fn test_invalid_curve_falls_back_to_single_steps() {
+    let mut accelerator = ScrollAccelerator::new(vec![0], 0);
+    let start = Instant::now();
+
+    assert_eq!(accelerator.step_for(KeyCode::Down, start), 1);
+    assert_eq!(accelerator.step_for(KeyCode::Down, start + Duration::from_millis(10)), 1);
+    assert!(is_scroll_key(KeyCode::Char('j')));
+    assert!(!is_scroll_key(KeyCode::PageDown));
+}
+
+#[test]
+fn test_second_click_on_same_item_is_double_click() {
+    let mut clicks = ClickTracker::default();
+    let start = Instant::now();
+
+    assert!(!clicks.click(3, start));
+    assert!(clicks.click(3, start + Duration::from_millis(100)));
+    // A third click starts over rather than counting as another double-click
+    assert!(!clicks.click(3, start + Duration::from_millis(200)));
+    assert!(!clicks.click(4, start + Duration::from_millis(250)));
+    assert!(!clicks.click(4, start + Duration::from_millis(250) + DOUBLE_CLICK_WINDOW * 2));
+}
+
+#[test]
+fn test_entry_at_row_maps_visible_rows_only() {
+    // List drawn from row 4 with 10 visible rows, scrolled to entry 20 of 25
+    assert_eq!(entry_at_row(4, 4, 10, 20, 25), Some(20));
+    assert_eq!(entry_at_row(8, 4, 10, 20, 25), Some(24));
+    assert_eq!(entry_at_row(9, 4, 10, 20, 25), None);
+    assert_eq!(entry_at_row(3, 4, 10, 20, 25), None);
+    assert_eq!(entry_at_row(14, 4, 10, 0, 25), None);
+}
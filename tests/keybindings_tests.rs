@@ -0,0 +1,85 @@
+use movies::keybindings::{format_hotkey, help_lines, help_sections};
+use movies::menu::all_menu_items;
+
+#[test]
+fn test_help_lists_every_menu_hotkey() {
+    let sections = help_sections();
+    let bindings: Vec<_> = sections.iter().flat_map(|section| &section.bindings).collect();
+
+    for item in all_menu_items() {
+        if let Some(hotkey) = &item.hotkey {
+            let keys = format_hotkey(hotkey);
+            assert!(
+                bindings.iter().any(|b| b.keys == keys && b.description == item.label.to_lowercase()),
+                "Help is missing [{}] {}",
+                keys,
+                item.label
+            );
+        }
+    }
+    assert!(sections[0].bindings.iter().any(|b| b.keys == "?"), "Browse lists the help key");
+}
+
+#[test]
+fn test_help_lines_align_descriptions() {
+    let lines = help_lines();
+    assert_eq!(lines[0], "Browse");
+
+    let escape_column = |line: &String| line.find("go back a level").or_else(|| line.find("open the menu"));
+    let back = lines.iter().find(|l| l.contains("go back a level")).and_then(escape_column);
+    let menu = lines.iter().find(|l| l.contains("open the menu")).and_then(escape_column);
+    assert!(back.is_some());
+    assert_eq!(back, menu, "Descriptions start in the same column");
+}
+
+#[test]
+fn test_footer_follows_mode_and_selection() {
+    use movies::dto::EpisodeDetail;
+    use movies::keybindings::footer_bindings;
+    use movies::menu::{get_context_menu_items, MenuContext};
+    use movies::util::{Entry, Mode};
+
+    let episode = Entry::Episode {
+        episode_id: 1,
+        name: "Pilot".to_string(),
+        location: "Show/Pilot.mkv".to_string(),
+    };
+    let context = MenuContext {
+        selected_entry: Some(episode.clone()),
+        episode_detail: EpisodeDetail {
+            title: "Pilot".to_string(),
+            year: String::new(),
+            watched: "false".to_string(),
+            length: "45".to_string(),
+            series: None,
+            season: None,
+            episode_number: String::new(),
+            last_watched_time: None,
+            last_progress_time: None,
+            notes: None,
+            play_count: 0,
+            alt_titles: Default::default(),
+        },
+        mode: Mode::Browse,
+        last_action: None,
+        marked_count: 0,
+        extras_view: false,
+    };
+    let items = get_context_menu_items(&context);
+    let keys = |bindings: Vec<movies::keybindings::KeyBinding>| bindings.into_iter().map(|b| b.keys).collect::<Vec<_>>();
+
+    let browse = footer_bindings(&Mode::Browse, false, Some(&episode), &items);
+    assert_eq!((browse[0].keys.as_str(), browse[0].description.as_str()), ("Enter", "play"));
+    for item in items.iter().filter(|item| item.hotkey.is_some()) {
+        let hotkey = format_hotkey(item.hotkey.as_ref().unwrap());
+        assert!(browse.iter().any(|b| b.keys == hotkey && b.description == item.label.to_lowercase()), "Missing {}", hotkey);
+    }
+
+    let series = Entry::Series { series_id: 1, name: "Firefly".to_string() };
+    assert_eq!(footer_bindings(&Mode::Browse, false, Some(&series), &[])[0].description, "open");
+
+    // Other modes and the filter list their own keys from the help screen
+    assert_eq!(keys(footer_bindings(&Mode::Browse, true, Some(&episode), &items))[..2], ["type", "←/→, Home/End"]);
+    assert_eq!(keys(footer_bindings(&Mode::Menu, false, None, &[])), ["↑/↓", "Enter", "Esc"]);
+    assert!(footer_bindings(&Mode::Entry, false, None, &[]).is_empty());
+}
@@ -0,0 +1,110 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use movies::action::Action;
+use movies::config::{KeyList, KeybindingsConfig};
+use movies::keymap::{Key, KeySequence, Keymap, Lookup};
+use movies::menu::MenuAction;
+
+fn rebind(browse: &[(&str, &[&str])], menu: &[(&str, Option<&str>)]) -> KeybindingsConfig {
+    KeybindingsConfig {
+        browse: browse
+            .iter()
+            .map(|(name, keys)| (name.to_string(), KeyList::Many(keys.iter().map(|key| key.to_string()).collect())))
+            .collect(),
+        preset: None,
+        menu: menu.iter().map(|(name, key)| (name.to_string(), key.map(str::to_string))).collect(),
+    }
+}
+
+#[test]
+fn test_parse_keys() {
+    assert_eq!(Key::parse("g"), Ok(Key { code: KeyCode::Char('g'), modifiers: KeyModifiers::NONE }));
+    assert_eq!(Key::parse("Ctrl+Left"), Ok(Key { code: KeyCode::Left, modifiers: KeyModifiers::CONTROL }));
+    assert_eq!(Key::parse("alt+x").map(|key| key.modifiers), Ok(KeyModifiers::ALT));
+    assert_eq!(Key::parse("Space").map(|key| key.code), Ok(KeyCode::Char(' ')));
+    assert_eq!(Key::parse("F12").map(|key| key.code), Ok(KeyCode::F(12)));
+    assert_eq!(Key::parse("PgDn").map(|key| key.label()), Ok("PgDn".to_string()));
+    assert_eq!(Key::parse("Ctrl+Right").map(|key| key.label()), Ok("Ctrl+→".to_string()));
+    assert!(Key::parse("F13").is_err());
+    assert!(Key::parse("Hyper").is_err());
+}
+
+#[test]
+fn test_default_keymap_matches_the_builtin_keys() {
+    let keymap = Keymap::default();
+    assert_eq!(keymap.browse_action(KeyCode::Char('j'), KeyModifiers::NONE), Some(Action::MoveDown));
+    assert_eq!(keymap.browse_action(KeyCode::Char('T'), KeyModifiers::SHIFT), Some(Action::CloseTab));
+    assert_eq!(keymap.browse_action(KeyCode::Left, KeyModifiers::CONTROL), Some(Action::ResizeSplit(false)));
+    assert_eq!(keymap.browse_action(KeyCode::Left, KeyModifiers::NONE), Some(Action::MoveLeft));
+    assert_eq!(keymap.browse_action(KeyCode::Char('y'), KeyModifiers::NONE), Some(Action::CopyPath));
+    assert_eq!(keymap.browse_action(KeyCode::Char('Y'), KeyModifiers::SHIFT), Some(Action::CopyTitle));
+    assert_eq!(keymap.menu_hotkey(&MenuAction::Rescan, Some(KeyCode::Char('s'))), Some(KeyCode::Char('s')));
+
+    let (unchanged, problems) = Keymap::from_config(&KeybindingsConfig::default());
+    assert_eq!(unchanged, keymap);
+    assert!(problems.is_empty());
+}
+
+#[test]
+fn test_rebinding_replaces_the_default_keys() {
+    let config = rebind(&[("toggle_grid", &["G"]), ("page_down", &[])], &[("rescan", Some("r")), ("edit", None)]);
+    let (keymap, problems) = Keymap::from_config(&config);
+    assert!(problems.is_empty(), "{:?}", problems);
+
+    assert_eq!(keymap.browse_action(KeyCode::Char('G'), KeyModifiers::SHIFT), Some(Action::ToggleGrid));
+    assert_eq!(keymap.browse_action(KeyCode::Char('g'), KeyModifiers::NONE), None);
+    assert!(keymap.keys_for("page_down").is_empty());
+    assert_eq!(keymap.menu_hotkey(&MenuAction::Rescan, Some(KeyCode::Char('s'))), Some(KeyCode::Char('r')));
+    assert_eq!(keymap.menu_hotkey(&MenuAction::Edit, Some(KeyCode::F(2))), None);
+}
+
+#[test]
+fn test_clashing_and_unknown_rebindings_keep_the_defaults() {
+    let config = rebind(
+        &[("toggle_grid", &["j"]), ("pin_tab", &["1"]), ("show_help", &["Ctrl+s"]), ("fly", &["f"]), ("back", &["Hyper"])],
+        &[("rescan", Some("o")), ("export_library", Some("x")), ("delete", Some("Ctrl+d"))],
+    );
+    let (keymap, problems) = Keymap::from_config(&config);
+
+    // j moves down, 1 is pinned filter 1, and the rescan hotkey s runs whatever modifiers are held
+    assert_eq!(keymap.browse_action(KeyCode::Char('g'), KeyModifiers::NONE), Some(Action::ToggleGrid));
+    assert_eq!(keymap.browse_action(KeyCode::Char('j'), KeyModifiers::NONE), Some(Action::MoveDown));
+    assert_eq!(keymap.keys_for("pin_tab"), [KeySequence::parse("t").unwrap()]);
+    assert_eq!(keymap.keys_for("show_help"), [KeySequence::parse("?").unwrap()]);
+    assert_eq!(keymap.menu_hotkey(&MenuAction::Rescan, Some(KeyCode::Char('s'))), Some(KeyCode::Char('s')));
+    assert!(problems.iter().any(|p| p == "j is bound to both toggle_grid and move_down; toggle_grid keeps its default keys"));
+    assert!(problems.iter().any(|p| p.contains("pinned filter 1")));
+    assert!(problems.iter().any(|p| p.contains("Ctrl+s is bound to both show_help and rescan")));
+    assert!(problems.iter().any(|p| p.contains("o is bound to both rescan and cycle_sort")));
+    assert!(problems.iter().any(|p| p == "Unknown browse action 'fly' in keybindings"));
+    assert!(problems.iter().any(|p| p.contains("Unknown key 'Hyper' for back")));
+    assert!(problems.iter().any(|p| p.contains("export_library")));
+    assert!(problems.iter().any(|p| p == "The hotkey for delete must be a letter or function key"));
+    assert_eq!(problems.len(), 8);
+}
+
+#[test]
+fn test_vim_preset() {
+    let keymap = Keymap::preset("vim").unwrap();
+    let g = Key::parse("g").unwrap();
+    assert_eq!(keymap.lookup(&[g]), Lookup::Pending);
+    assert_eq!(keymap.lookup(&[g, g]), Lookup::Action(Action::MoveTop));
+    assert_eq!(keymap.browse_action(KeyCode::Char('G'), KeyModifiers::SHIFT), Some(Action::MoveBottom));
+    assert_eq!(keymap.browse_action(KeyCode::Char('d'), KeyModifiers::CONTROL), Some(Action::HalfPageDown));
+    assert_eq!(keymap.browse_action(KeyCode::Char('n'), KeyModifiers::NONE), Some(Action::NextMatch));
+    assert_eq!(keymap.browse_action(KeyCode::Char(':'), KeyModifiers::NONE), Some(Action::OpenCommandLine));
+    assert_eq!(keymap.browse_action(KeyCode::Char('g'), KeyModifiers::CONTROL), Some(Action::ToggleGrid));
+    assert_eq!(keymap.keys_for("move_top")[0].label(), "gg");
+    assert!(Keymap::preset("emacs").is_none());
+
+    // A single g can't be bound while gg starts with it
+    let config = KeybindingsConfig {
+        preset: Some("vim".to_string()),
+        ..rebind(&[("toggle_grid", &["g"])], &[])
+    };
+    let (keymap, problems) = Keymap::from_config(&config);
+    assert_eq!(problems, ["g is bound to both toggle_grid and move_top; toggle_grid keeps its default keys"]);
+    assert_eq!(keymap, Keymap::preset("vim").unwrap());
+
+    let (_, problems) = Keymap::from_config(&KeybindingsConfig { preset: Some("emacs".to_string()), ..Default::default() });
+    assert_eq!(problems, ["Unknown keybindings preset 'emacs', using the default keys"]);
+}
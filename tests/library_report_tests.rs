@@ -0,0 +1,109 @@
+use movies::export::LibraryRecord;
+use movies::library_report::{build_report, format_runtime, render, ReportFormat};
+
+fn record(title: &str, series: Option<&str>, season: Option<usize>, watched: bool, length: Option<i64>) -> LibraryRecord {
+    LibraryRecord {
+        location: format!("{}.mkv", title),
+        title: title.to_string(),
+        year: None,
+        watched,
+        length,
+        series: series.map(str::to_string),
+        season,
+        episode_number: None,
+        last_watched_time: None,
+        last_progress_time: None,
+        root: None,
+    }
+}
+
+fn sample_records() -> Vec<LibraryRecord> {
+    vec![
+        record("Undertow", Some("The Wire"), Some(2), false, Some(3480)),
+        record("The Target", Some("The Wire"), Some(1), true, Some(3720)),
+        record("The Detail", Some("The Wire"), Some(1), false, None),
+        record("Pilot", Some("Firefly"), None, true, Some(2580)),
+        record("Heat", None, None, true, Some(10200)),
+        record("Alien", None, None, false, Some(7020)),
+    ]
+}
+
+#[test]
+fn test_build_report_groups_series_and_seasons() {
+    let report = build_report(&sample_records());
+
+    let names: Vec<&str> = report.series.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["Firefly", "The Wire"]);
+
+    let wire = &report.series[1];
+    let seasons: Vec<Option<usize>> = wire.seasons.iter().map(|s| s.number).collect();
+    assert_eq!(seasons, vec![Some(1), Some(2)]);
+    assert_eq!(wire.seasons[0].progress.videos, 2);
+    assert_eq!(wire.seasons[0].progress.watched, 1);
+    assert_eq!(wire.seasons[0].progress.seconds, 3720);
+    assert_eq!(wire.progress.videos, 3);
+    assert_eq!(wire.progress.seconds, 7200);
+
+    assert_eq!(report.series[0].seasons[0].number, None);
+
+    let titles: Vec<&str> = report.standalone.iter().map(|r| r.title.as_str()).collect();
+    assert_eq!(titles, vec!["Alien", "Heat"]);
+
+    assert_eq!(report.total.videos, 6);
+    assert_eq!(report.total.watched, 3);
+    assert_eq!(report.total.percent(), 50);
+    assert_eq!(report.total.seconds, 27000);
+}
+
+#[test]
+fn test_format_runtime() {
+    assert_eq!(format_runtime(0), "0 min");
+    assert_eq!(format_runtime(2880), "48 min");
+    assert_eq!(format_runtime(11100), "3 h 05 min");
+    assert_eq!(format_runtime(-60), "0 min");
+}
+
+#[test]
+fn test_markdown_report_lists_series_seasons_and_totals() {
+    let markdown = render(&sample_records(), ReportFormat::Markdown, "2026-10-16 20:00");
+
+    assert!(markdown.starts_with("# Video Library\n"));
+    assert!(markdown.contains("Generated 2026-10-16 20:00"));
+    assert!(markdown.contains("6 videos, 3 watched (50%), 7 h 30 min in total"));
+    assert!(markdown.contains("| The Wire | 2 | 1 of 3 (33%) | 2 h 00 min |"));
+    assert!(markdown.contains("### The Wire"));
+    assert!(markdown.contains("| Season 1 | 1 of 2 (50%) | 1 h 02 min |"));
+    assert!(markdown.contains("| No season | 1 of 1 (100%) | 43 min |"));
+    assert!(markdown.contains("| Heat |  | yes | 2 h 50 min |"));
+}
+
+#[test]
+fn test_markdown_report_escapes_pipes() {
+    let records = vec![record("This | That", None, None, false, None)];
+    let markdown = render(&records, ReportFormat::Markdown, "today");
+
+    assert!(markdown.contains("| This \\| That |"));
+    assert!(!markdown.contains("## Series"));
+}
+
+#[test]
+fn test_html_report_escapes_titles() {
+    let records = vec![
+        record("Episode 1", Some("Cartoons \"Classic\""), Some(1), true, Some(420)),
+        record("Tom & Jerry <1>", None, None, false, None),
+    ];
+    let html = render(&records, ReportFormat::Html, "today");
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h3>Cartoons &quot;Classic&quot;</h3>"));
+    assert!(html.contains("<progress value=\"1\" max=\"1\"></progress> 1 of 1 (100%)"));
+    assert!(html.contains("<td>Tom &amp; Jerry &lt;1&gt;</td>"));
+    assert!(html.trim_end().ends_with("</html>"));
+}
+
+#[test]
+fn test_empty_library_report() {
+    let markdown = render(&[], ReportFormat::Markdown, "today");
+    assert!(markdown.contains("0 videos, 0 watched (0%), 0 min in total"));
+    assert_eq!(ReportFormat::Html.default_file_name(), "library_report.html");
+}
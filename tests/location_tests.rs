@@ -0,0 +1,73 @@
+use movies::location::{decode, display, encode};
+use std::path::{Path, PathBuf};
+
+#[test]
+fn test_utf8_paths_are_stored_as_they_are() {
+    for path in ["movies/Spirited Away (2001).mkv", "séries/日本/第1話.mp4", "100% Wolf.mkv"] {
+        assert_eq!(encode(Path::new(path)), path);
+        assert_eq!(decode(path), PathBuf::from(path));
+        assert_eq!(display(path), path);
+    }
+}
+
+#[test]
+fn test_escape_characters_in_a_name_survive() {
+    let path = Path::new("odd/\u{EF80}\u{EFFF}.mkv");
+    let location = encode(path);
+    assert_ne!(location, "odd/\u{EF80}\u{EFFF}.mkv");
+    assert_eq!(decode(&location), path);
+}
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use movies::player_plugin::{GenericPlayerPlugin, PlayerPlugin};
+    use movies::util::Entry;
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use tempfile::TempDir;
+
+    /// "café" in Latin-1, which isn't valid UTF-8
+    fn latin1_name() -> &'static OsStr {
+        OsStr::from_bytes(b"caf\xe9.mkv")
+    }
+
+    #[test]
+    fn test_names_that_arent_utf8_round_trip() {
+        let path = Path::new("movies").join(latin1_name());
+        let location = encode(&path);
+        assert_eq!(decode(&location), path);
+        assert_eq!(display(&location), "movies/caf\u{FFFD}.mkv");
+
+        // An incomplete sequence at the end is kept too
+        let path = Path::new(OsStr::from_bytes(b"clip\xe6\x97"));
+        assert_eq!(decode(&encode(path)), path);
+    }
+
+    #[test]
+    fn test_files_that_arent_utf8_are_imported_and_played() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("videos.sqlite");
+        let database = Database::open(&db_path).unwrap();
+        let resolver = PathResolver::from_database_path(&db_path).unwrap();
+        let video = resolver.get_root_dir().join(latin1_name());
+        std::fs::write(&video, b"video").unwrap();
+
+        let name = latin1_name().to_string_lossy().to_string();
+        assert!(database.import_episode_relative(&encode(&video), &name, &resolver).unwrap());
+        let episode_id = match &database.get_entries().unwrap()[0] {
+            Entry::Episode { episode_id, name, .. } => {
+                assert_eq!(name, "caf\u{FFFD}.mkv");
+                *episode_id
+            }
+            other => panic!("Expected an episode, got {:?}", other),
+        };
+
+        let absolute_location = database.get_episode_absolute_location(episode_id, &resolver).unwrap();
+        assert_eq!(decode(&absolute_location), video);
+        let (_, args) = GenericPlayerPlugin::new("/usr/bin/mpv".to_string()).launch_command(&decode(&absolute_location), None);
+        assert_eq!(args.last().unwrap(), video.as_os_str());
+    }
+}
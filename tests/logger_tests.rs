@@ -351,3 +351,70 @@ fn test_write_without_initialization() {
     
     // If we get here, the test passed (no panic occurred)
 }
+
+/// Test that the last session's log is kept and the oldest past log_keep_files is dropped
+#[test]
+#[serial_test::serial]
+fn test_log_rotates_on_start() {
+    cleanup_logger();
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let log_file = temp_dir.path().join("movies.log");
+    set_log_rotation(LogRotation { max_size: 0, keep_files: 2 });
+    for session in 1..=3 {
+        initialize_logger(log_file.clone(), LogLevel::Info).expect("Failed to initialize logger");
+        log_info(&format!("SESSION_{}", session));
+    }
+    cleanup_logger();
+    set_log_rotation(LogRotation::default());
+
+    assert!(fs::read_to_string(&log_file).unwrap().contains("SESSION_3"));
+    assert!(fs::read_to_string(rotated_log_path(&log_file, 1)).unwrap().contains("SESSION_2"));
+    assert!(fs::read_to_string(rotated_log_path(&log_file, 2)).unwrap().contains("SESSION_1"));
+    assert!(!rotated_log_path(&log_file, 3).exists());
+}
+
+/// Test that an empty log isn't rotated, and that no earlier logs still keeps one
+#[test]
+#[serial_test::serial]
+fn test_log_rotation_keeps_the_last_session() {
+    cleanup_logger();
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let log_file = temp_dir.path().join("movies.log");
+    set_log_rotation(LogRotation { max_size: 0, keep_files: 0 });
+    initialize_logger(log_file.clone(), LogLevel::Info).expect("Failed to initialize logger");
+    initialize_logger(log_file.clone(), LogLevel::Info).expect("Failed to initialize logger");
+    assert!(!rotated_log_path(&log_file, 1).exists(), "An empty log isn't worth keeping");
+
+    log_info("CRASHED_SESSION");
+    initialize_logger(log_file.clone(), LogLevel::Info).expect("Failed to initialize logger");
+    cleanup_logger();
+    set_log_rotation(LogRotation::default());
+
+    assert!(fs::read_to_string(rotated_log_path(&log_file, 1)).unwrap().contains("CRASHED_SESSION"));
+    assert_eq!(rotated_log_path(&log_file, 1).file_name().unwrap(), "movies.log.1");
+}
+
+/// Test that the log starts a new file while running once it passes the size cap
+#[test]
+#[serial_test::serial]
+fn test_log_rotates_past_max_size() {
+    cleanup_logger();
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let log_file = temp_dir.path().join("movies.log");
+    set_log_rotation(LogRotation { max_size: 200, keep_files: 3 });
+    initialize_logger(log_file.clone(), LogLevel::Info).expect("Failed to initialize logger");
+    for line in 0..10 {
+        log_info(&format!("LINE_{:02} of a log that grows past its cap", line));
+    }
+    cleanup_logger();
+    set_log_rotation(LogRotation::default());
+
+    let current = fs::read_to_string(&log_file).unwrap();
+    assert!(current.len() < 200);
+    assert!(current.contains("LINE_09"));
+    assert!(fs::read_to_string(rotated_log_path(&log_file, 1)).unwrap().contains("LINE_08"));
+    assert!(!rotated_log_path(&log_file, 4).exists());
+}
@@ -0,0 +1,59 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use movies::keymap::Key;
+use movies::macros::MacroRecorder;
+
+fn key(c: char) -> Key {
+    Key::pressed(KeyCode::Char(c), KeyModifiers::NONE)
+}
+
+fn replayed(recorder: &mut MacroRecorder) -> Vec<Key> {
+    std::iter::from_fn(|| recorder.next_key()).collect()
+}
+
+#[test]
+fn test_keys_are_only_recorded_while_recording() {
+    let mut recorder = MacroRecorder::default();
+    recorder.record(key('a'));
+    assert!(!recorder.has_macro());
+
+    recorder.start_recording();
+    assert!(recorder.is_recording());
+    recorder.record(key('b'));
+    recorder.record(Key::pressed(KeyCode::Down, KeyModifiers::NONE));
+    assert_eq!(recorder.stop_recording(), 2);
+    assert!(!recorder.is_recording());
+
+    assert!(recorder.replay());
+    assert_eq!(replayed(&mut recorder), vec![key('b'), Key::pressed(KeyCode::Down, KeyModifiers::NONE)]);
+}
+
+#[test]
+fn test_empty_recording_keeps_the_previous_macro() {
+    let mut recorder = MacroRecorder::default();
+    recorder.start_recording();
+    recorder.record(key('x'));
+    recorder.stop_recording();
+
+    recorder.start_recording();
+    assert_eq!(recorder.stop_recording(), 0);
+    assert!(recorder.replay());
+    assert_eq!(replayed(&mut recorder), vec![key('x')]);
+}
+
+#[test]
+fn test_replay_is_refused_while_recording_or_replaying() {
+    let mut recorder = MacroRecorder::default();
+    assert!(!recorder.replay());
+
+    recorder.start_recording();
+    recorder.record(key('@'));
+    assert!(!recorder.replay());
+    recorder.stop_recording();
+
+    // The last key is handled before the replay is over, so a macro ending in @ can't restart it
+    assert!(recorder.replay());
+    assert_eq!(recorder.next_key(), Some(key('@')));
+    assert!(!recorder.replay());
+    assert_eq!(recorder.next_key(), None);
+    assert!(recorder.replay());
+}
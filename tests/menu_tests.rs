@@ -1,6 +1,6 @@
 use movies::menu::*;
 use movies::dto::{EpisodeDetail, Season, Series};
-use movies::util::{Entry, Mode};
+use movies::util::{Entry, Mode, ViewContext};
 use crossterm::event::KeyCode;
 
 #[test]
@@ -16,6 +16,9 @@ fn test_first_line_preferred_respects_availability() {
         episode_number: String::new(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let context = MenuContext {
@@ -27,6 +30,8 @@ fn test_first_line_preferred_respects_availability() {
         episode_detail: episode_detail.clone(),
         mode: Mode::Browse,
         last_action: None,
+        marked_count: 0,
+        extras_view: false,
     };
 
     // Get first line preferred items
@@ -49,6 +54,9 @@ fn test_context_aware_filtering_episode_without_series() {
         episode_number: String::new(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let context = MenuContext {
@@ -60,6 +68,8 @@ fn test_context_aware_filtering_episode_without_series() {
         episode_detail: episode_detail.clone(),
         mode: Mode::Browse,
         last_action: None,
+        marked_count: 0,
+        extras_view: false,
     };
 
     let available_items = get_available_menu_items(&context);
@@ -73,6 +83,13 @@ fn test_context_aware_filtering_episode_without_series() {
     assert!(available_items.iter().any(|i| matches!(i.action, MenuAction::UnwatchAll)));
     assert!(!available_items.iter().any(|i| matches!(i.action, MenuAction::RepeatAction)));
     assert!(!available_items.iter().any(|i| matches!(i.action, MenuAction::ClearSeriesData)));
+
+    // An extra can only be put back from the Extras view
+    assert!(available_items.iter().any(|i| matches!(i.action, MenuAction::ShowExtras)));
+    assert!(!available_items.iter().any(|i| matches!(i.action, MenuAction::NotAnExtra)));
+    let in_extras = get_available_menu_items(&MenuContext { extras_view: true, ..context });
+    assert!(!in_extras.iter().any(|i| matches!(i.action, MenuAction::ShowExtras)));
+    assert!(in_extras.iter().any(|i| matches!(i.action, MenuAction::NotAnExtra)));
 }
 
 #[test]
@@ -93,6 +110,9 @@ fn test_context_aware_filtering_episode_with_series() {
         episode_number: "1".to_string(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let context = MenuContext {
@@ -104,6 +124,8 @@ fn test_context_aware_filtering_episode_with_series() {
         episode_detail: episode_detail.clone(),
         mode: Mode::Browse,
         last_action: None,
+        marked_count: 0,
+        extras_view: false,
     };
 
     let available_items = get_available_menu_items(&context);
@@ -131,6 +153,9 @@ fn test_context_aware_filtering_series_selected() {
         episode_number: String::new(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let context = MenuContext {
@@ -141,6 +166,8 @@ fn test_context_aware_filtering_series_selected() {
         episode_detail: episode_detail.clone(),
         mode: Mode::Browse,
         last_action: None,
+        marked_count: 0,
+        extras_view: false,
     };
 
     let available_items = get_available_menu_items(&context);
@@ -208,6 +235,9 @@ fn test_menu_selection_highlighting() {
         episode_number: String::new(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     let context = MenuContext {
@@ -219,6 +249,8 @@ fn test_menu_selection_highlighting() {
         episode_detail: episode_detail.clone(),
         mode: Mode::Browse,
         last_action: None,
+        marked_count: 0,
+        extras_view: false,
     };
 
     // Get available menu items
@@ -256,6 +288,9 @@ fn test_search_online_available_in_browse_mode() {
         episode_number: String::new(),
         last_watched_time: None,
         last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
     };
 
     // Test in Browse mode - SearchOnline should be available
@@ -268,6 +303,8 @@ fn test_search_online_available_in_browse_mode() {
         episode_detail: episode_detail.clone(),
         mode: Mode::Browse,
         last_action: None,
+        marked_count: 0,
+        extras_view: false,
     };
 
     let available_items_browse = get_available_menu_items(&context_browse);
@@ -286,6 +323,8 @@ fn test_search_online_available_in_browse_mode() {
         episode_detail: episode_detail.clone(),
         mode: Mode::Edit,
         last_action: None,
+        marked_count: 0,
+        extras_view: false,
     };
 
     let available_items_edit = get_available_menu_items(&context_edit);
@@ -304,6 +343,8 @@ fn test_search_online_available_in_browse_mode() {
         episode_detail: episode_detail.clone(),
         mode: Mode::Menu,
         last_action: None,
+        marked_count: 0,
+        extras_view: false,
     };
 
     let available_items_menu = get_available_menu_items(&context_menu);
@@ -312,3 +353,85 @@ fn test_search_online_available_in_browse_mode() {
         "SearchOnline should NOT be available in Menu mode"
     );
 }
+
+#[test]
+fn test_batch_actions_available_for_marked_episodes() {
+    let episode_detail = EpisodeDetail {
+        title: "Test Episode".to_string(),
+        year: "2023".to_string(),
+        watched: "0".to_string(),
+        length: "45".to_string(),
+        series: None,
+        season: None,
+        episode_number: String::new(),
+        last_watched_time: None,
+        last_progress_time: None,
+        notes: None,
+        play_count: 0,
+        alt_titles: Default::default(),
+    };
+
+    // With a series selected, batch actions still apply to the marked episodes
+    let context = MenuContext {
+        selected_entry: Some(Entry::Series {
+            series_id: 1,
+            name: "Test Series".to_string(),
+        }),
+        episode_detail,
+        mode: Mode::Browse,
+        last_action: None,
+        marked_count: 3,
+        extras_view: false,
+    };
+
+    let items = get_context_menu_items(&context);
+    for item in items.iter().filter(|item| is_batch_action(&item.action)) {
+        assert!(item.label.ends_with("(3 marked)"), "Batch label names the count: {}", item.label);
+    }
+    assert_eq!(items.iter().filter(|item| is_batch_action(&item.action)).count(), 4);
+    assert!(!items.iter().any(|i| matches!(i.action, MenuAction::Edit)), "Edit still needs a selected episode");
+}
+
+#[test]
+fn test_destructive_actions_need_confirmation() {
+    let episode = Entry::Episode {
+        episode_id: 7,
+        name: "Pilot.mkv".to_string(),
+        location: "Show/Pilot.mkv".to_string(),
+    };
+    let season_view = ViewContext::Season {
+        season_id: 2,
+        series_name: "Show".to_string(),
+        season_number: 1,
+    };
+
+    let dialog = confirmation_dialog(&MenuAction::Delete, Some(&episode), &ViewContext::TopLevel, 0).unwrap();
+    assert!(dialog.message.contains("'Pilot.mkv'"), "Names the episode: {}", dialog.message);
+    assert!(!dialog.yes_selected, "No is highlighted so a stray Enter does nothing");
+
+    // Marked episodes are what a batch action changes
+    let dialog = confirmation_dialog(&MenuAction::ClearSeriesData, Some(&episode), &ViewContext::TopLevel, 4).unwrap();
+    assert!(dialog.message.contains("the 4 marked episodes"), "{}", dialog.message);
+
+    let dialog = confirmation_dialog(&MenuAction::UnwatchAll, Some(&episode), &season_view, 0).unwrap();
+    assert!(dialog.message.contains("'Show' season 1"), "Names the view's scope: {}", dialog.message);
+
+    assert!(confirmation_dialog(&MenuAction::ToggleWatched, Some(&episode), &ViewContext::TopLevel, 4).is_none());
+    assert!(confirmation_dialog(&MenuAction::Edit, Some(&episode), &ViewContext::TopLevel, 0).is_none());
+}
+
+#[test]
+fn test_confirmations_follow_the_config() {
+    let mut config = movies::config::Config::default();
+    for action in [MenuAction::Delete, MenuAction::UnwatchAll, MenuAction::ClearSeriesData, MenuAction::DeleteFile] {
+        assert!(asks_confirmation(&action, &config), "{:?} asks by default", action);
+    }
+
+    config.confirm_delete = false;
+    config.confirm_unwatch_all = false;
+    config.confirm_clear_series = false;
+    assert!(!asks_confirmation(&MenuAction::Delete, &config));
+    assert!(!asks_confirmation(&MenuAction::UnwatchAll, &config));
+    assert!(!asks_confirmation(&MenuAction::ClearSeriesData, &config));
+    assert!(asks_confirmation(&MenuAction::DeleteFile, &config), "Files may be deleted for good, so it always asks");
+}
@@ -0,0 +1,223 @@
+use crossterm::event::KeyCode;
+use movies::app_state::AppState;
+use movies::archive::Archive;
+use movies::folder_series::SeriesProposal;
+use movies::handlers::{handle_help_mode, handle_scan_report_mode};
+use movies::modal::ModalStack;
+use movies::util::{Entry, Mode};
+
+fn episode(episode_id: usize) -> Entry {
+    Entry::Episode {
+        episode_id,
+        name: format!("episode{}.mkv", episode_id),
+        location: format!("/videos/episode{}.mkv", episode_id),
+    }
+}
+
+#[test]
+fn test_closing_modals_returns_to_each_screen_under_them() {
+    let mut state = AppState::new((1..=30).map(episode).collect());
+    state.current_item = 20;
+    state.first_entry = 12;
+
+    state.open_modal(Mode::Menu);
+    state.menu_selection = 3;
+    state.current_item = 0;
+    state.open_modal(Mode::Confirm);
+    assert_eq!(state.modals.top().map(|screen| &screen.mode), Some(&Mode::Menu));
+
+    // Saying no to the dialog goes back to the menu on the same item
+    state.close_modal();
+    assert_eq!((state.mode.clone(), state.menu_selection), (Mode::Menu, 3));
+
+    // Closing the menu puts back the selection and scroll of the list
+    state.close_modal();
+    assert_eq!((state.mode.clone(), state.current_item, state.first_entry), (Mode::Browse, 20, 12));
+    assert!(state.modals.top().is_none());
+
+    // With nothing under it, closing lands in Browse
+    state.mode = Mode::Help;
+    state.close_modal();
+    assert_eq!(state.mode, Mode::Browse);
+}
+
+#[test]
+fn test_closing_a_modal_keeps_the_selection_in_a_shorter_list() {
+    let mut state = AppState::new((1..=10).map(episode).collect());
+    state.current_item = 9;
+    state.first_entry = 5;
+    state.open_modal(Mode::Menu);
+    state.filtered_entries.truncate(4);
+    state.close_modal();
+    assert_eq!((state.current_item, state.first_entry), (3, 3));
+}
+
+#[test]
+fn test_going_back_to_a_screen_drops_the_modals_over_it() {
+    let mut modals = ModalStack::default();
+    let mut mode = Mode::Browse;
+    modals.open(&mut mode, Mode::Menu, 4, 0, 0);
+    modals.open(&mut mode, Mode::Confirm, 4, 0, 2);
+    assert_eq!(mode, Mode::Confirm);
+
+    // A handler that replaces the top screen keeps the ones under it
+    modals.follow(&Mode::Edit);
+    assert_eq!(modals.top().map(|screen| &screen.mode), Some(&Mode::Menu));
+    modals.follow(&Mode::Menu);
+    assert_eq!(modals.top().map(|screen| &screen.mode), Some(&Mode::Browse));
+    modals.open(&mut mode, Mode::Confirm, 4, 0, 2);
+    modals.follow(&Mode::Browse);
+    assert!(modals.top().is_none());
+}
+
+#[test]
+fn test_help_closes_back_to_browse() {
+    let mut state = AppState::new((1..=5).map(episode).collect());
+    state.current_item = 2;
+    state.open_modal(Mode::Help);
+    state.help_scroll = 4;
+    handle_help_mode(KeyCode::Esc, &mut state).unwrap();
+    assert_eq!((state.mode, state.help_scroll, state.current_item), (Mode::Browse, 0, 2));
+}
+
+#[test]
+fn test_scan_report_goes_on_to_the_archives() {
+    use movies::app_state::AppContext;
+    use movies::config::Config;
+    use movies::database::Database;
+    use movies::path_resolver::PathResolver;
+    use std::sync::Arc;
+
+    let library = tempfile::TempDir::new().unwrap();
+    let db_path = library.path().join("videos.sqlite");
+    let database = Arc::new(Database::open(&db_path).unwrap());
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let config = Config::default();
+    let (tx, _rx) = std::sync::mpsc::channel();
+    let context = AppContext {
+        config: &config,
+        database: &database,
+        resolver: &resolver,
+        tx: &tx,
+        config_path: &db_path.with_file_name("config.yaml"),
+    };
+    let mut state = AppState::new((1..=5).map(episode).collect());
+    state.mode = Mode::ScanReport;
+    state.pending_archives = vec![Archive { path: "/videos/Show.zip".into(), videos: vec!["S01E01.mkv".to_string()] }];
+    handle_scan_report_mode(KeyCode::Esc, &mut state, &context).unwrap();
+    assert_eq!(state.mode, Mode::ArchiveReview);
+
+    state.mode = Mode::ScanReport;
+    state.pending_archives.clear();
+    handle_scan_report_mode(KeyCode::Enter, &mut state, &context).unwrap();
+    assert_eq!(state.mode, Mode::Browse);
+
+    // Esc leaves the series found in folder names unassigned
+    state.mode = Mode::ScanReport;
+    state.scan_report.series_proposals =
+        vec![SeriesProposal { episode_id: 1, location: "Show/S01/e1.mkv".to_string(), series: "Show".to_string(), season: 1 }];
+    handle_scan_report_mode(KeyCode::Esc, &mut state, &context).unwrap();
+    assert!(state.scan_report.series_proposals.is_empty());
+    assert_eq!(state.mode, Mode::Browse);
+}
+
+#[test]
+fn test_relocate_typing_starts_a_new_check() {
+    use movies::config::Config;
+    use movies::handlers::handle_relocate_mode;
+    use movies::relocate::RelocationCheck;
+
+    let mut state = AppState::new((1..=5).map(episode).collect());
+    let mut config = Config::default();
+    let config_path = std::env::temp_dir().join("movies-relocate-test-config.yaml");
+    let mut database = None;
+    let mut resolver = None;
+    state.mode = Mode::Relocate;
+    state.relocate_path = "/media/ne".to_string();
+    state.relocation_check =
+        Some(RelocationCheck { new_root: "/media/ne".into(), sampled: 2, found: 0, missing: Vec::new(), has_database: false });
+
+    handle_relocate_mode(KeyCode::Char('w'), &mut state, &mut config, &config_path, &mut database, &mut resolver);
+    assert_eq!(state.relocate_path, "/media/new");
+    assert!(state.relocation_check.is_none());
+
+    handle_relocate_mode(KeyCode::Esc, &mut state, &mut config, &config_path, &mut database, &mut resolver);
+    assert_eq!(state.mode, Mode::Browse);
+    assert!(config.db_location.is_none() && !config_path.exists());
+}
+
+#[test]
+fn test_scan_folder_only_scans_folders_of_the_library() {
+    use movies::app_state::AppContext;
+    use movies::config::Config;
+    use movies::database::Database;
+    use movies::handlers::handle_scan_folder_entry;
+    use movies::path_resolver::PathResolver;
+    use std::sync::Arc;
+
+    let library = tempfile::TempDir::new().unwrap();
+    let elsewhere = tempfile::TempDir::new().unwrap();
+    let db_path = library.path().join("videos.sqlite");
+    let database = Arc::new(Database::open(&db_path).unwrap());
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let config = Config::default();
+    let (tx, _rx) = std::sync::mpsc::channel();
+    let context = AppContext {
+        config: &config,
+        database: &database,
+        resolver: &resolver,
+        tx: &tx,
+        config_path: &db_path.with_file_name("config.yaml"),
+    };
+
+    let mut state = AppState::new((1..=5).map(episode).collect());
+    state.mode = Mode::Entry;
+    state.scan_folder_entry = true;
+    state.entry_path = elsewhere.path().display().to_string();
+    handle_scan_folder_entry(KeyCode::Enter, &mut state, &context);
+    assert_eq!(state.mode, Mode::Entry);
+    let error = state.notifications.history().last().map(|n| n.message.clone()).unwrap();
+    assert!(error.starts_with("Not a folder of the library"));
+
+    handle_scan_folder_entry(KeyCode::Esc, &mut state, &context);
+    assert_eq!(state.mode, Mode::Browse);
+    assert!(!state.scan_folder_entry);
+}
+
+#[test]
+fn test_tab_completes_the_folder_typed_in_entry_mode() {
+    use movies::app_state::AppContext;
+    use movies::config::Config;
+    use movies::database::Database;
+    use movies::handlers::handle_scan_folder_entry;
+    use movies::path_resolver::PathResolver;
+    use std::sync::Arc;
+
+    let library = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir(library.path().join("Cartoons")).unwrap();
+    std::fs::create_dir(library.path().join("Comedy")).unwrap();
+    let db_path = library.path().join("videos.sqlite");
+    let database = Arc::new(Database::open(&db_path).unwrap());
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let config = Config::default();
+    let (tx, _rx) = std::sync::mpsc::channel();
+    let context = AppContext {
+        config: &config,
+        database: &database,
+        resolver: &resolver,
+        tx: &tx,
+        config_path: &db_path.with_file_name("config.yaml"),
+    };
+
+    let mut state = AppState::new((1..=5).map(episode).collect());
+    state.mode = Mode::Entry;
+    state.scan_folder_entry = true;
+    state.entry_path = format!("{}/C", library.path().display());
+    handle_scan_folder_entry(KeyCode::Tab, &mut state, &context);
+    assert_eq!(state.entry_completions, vec!["Cartoons".to_string(), "Comedy".to_string()]);
+
+    handle_scan_folder_entry(KeyCode::Char('o'), &mut state, &context);
+    assert!(state.entry_completions.is_empty());
+    handle_scan_folder_entry(KeyCode::Tab, &mut state, &context);
+    assert_eq!(state.entry_path, format!("{}/Comedy{}", library.path().display(), std::path::MAIN_SEPARATOR));
+}
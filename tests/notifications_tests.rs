@@ -0,0 +1,88 @@
+use movies::notifications::{NotificationLevel, Notifications, HISTORY_SIZE, MIN_SHOWN, TOAST_DURATION};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_notifications_show_in_order() {
+    let mut notifications = Notifications::default();
+    assert!(notifications.current().is_none());
+
+    notifications.info("Rescan complete. Found 3 new videos");
+    notifications.warn("1 unsupported format(s): [wmv]");
+    let current = notifications.current().unwrap();
+    assert_eq!(current.message, "Rescan complete. Found 3 new videos");
+    assert_eq!(current.level, NotificationLevel::Info);
+
+    // A later message waits its turn instead of replacing the scan result
+    notifications.dismiss();
+    assert_eq!(notifications.current().unwrap().level, NotificationLevel::Warn);
+    notifications.dismiss();
+    assert!(notifications.current().is_none());
+}
+
+#[test]
+fn test_notifications_expire_by_level() {
+    let mut notifications = Notifications::default();
+    let start = Instant::now();
+    notifications.error("Failed to load series: disk I/O error");
+
+    // The first check starts the clock
+    assert!(!notifications.expire(start));
+    assert!(!notifications.expire(start + NotificationLevel::Info.timeout()));
+    assert!(notifications.expire(start + NotificationLevel::Error.timeout()));
+    assert!(notifications.current().is_none());
+    assert!(!notifications.expire(start + Duration::from_secs(60)));
+}
+
+#[test]
+fn test_waiting_notifications_shorten_the_current_one() {
+    let mut notifications = Notifications::default();
+    let start = Instant::now();
+    notifications.info("Exported 12 videos to library.json");
+    notifications.info("Usage counters exported to usage.json");
+
+    assert!(!notifications.expire(start));
+    assert!(notifications.expire(start + MIN_SHOWN));
+    assert_eq!(notifications.current().unwrap().message, "Usage counters exported to usage.json");
+
+    // The last one gets its full time, counted from when it appeared
+    assert!(!notifications.expire(start + MIN_SHOWN * 2));
+    assert!(notifications.expire(start + MIN_SHOWN + NotificationLevel::Info.timeout()));
+}
+
+#[test]
+fn test_notification_history_is_bounded_and_skips_repeats() {
+    let mut notifications = Notifications::default();
+    notifications.info("");
+    notifications.info("Notes saved");
+    notifications.info("Notes saved");
+    assert_eq!(notifications.history().len(), 1);
+
+    for index in 0..HISTORY_SIZE + 5 {
+        notifications.info(format!("Message {}", index));
+    }
+    assert_eq!(notifications.history().len(), HISTORY_SIZE);
+    assert_eq!(
+        notifications.history().next_back().unwrap().message,
+        format!("Message {}", HISTORY_SIZE + 4)
+    );
+    assert_eq!(notifications.history().next().unwrap().message, "Message 5");
+}
+
+#[test]
+fn test_toast_expires_apart_from_the_status_bar() {
+    let mut notifications = Notifications::default();
+    notifications.info("Rescan complete. No new videos found");
+    notifications.toast("Marked 4 episodes as watched");
+    notifications.toast("Marked 3 episodes as unwatched");
+    let start = Instant::now();
+
+    // The newest toast replaces the one up and never takes the status bar
+    assert_eq!(notifications.current_toast().unwrap().message, "Marked 3 episodes as unwatched");
+    assert_eq!(notifications.current().unwrap().message, "Rescan complete. No new videos found");
+    assert_eq!(notifications.history().len(), 3);
+
+    assert!(!notifications.expire(start));
+    assert!(notifications.expire(start + TOAST_DURATION));
+    assert!(notifications.current_toast().is_none());
+    assert!(notifications.current().is_some());
+}
@@ -0,0 +1,53 @@
+use movies::dto::{EpisodeDetail, Season, Series};
+use movies::now_playing::NowPlaying;
+use std::path::{Path, PathBuf};
+
+fn episode() -> NowPlaying {
+    NowPlaying {
+        episode_id: 42,
+        title: "The Target".to_string(),
+        series: Some("The Wire".to_string()),
+        season: Some(1),
+        episode_number: Some(1),
+        path: PathBuf::from("/videos/The Wire/S01E01 #1.mkv"),
+        length: 3600,
+        start: 0,
+    }
+}
+
+#[test]
+fn test_new_reads_the_episode_details() {
+    let details = EpisodeDetail {
+        title: "The Target".to_string(),
+        length: "3600".to_string(),
+        series: Some(Series { id: 7, name: "The Wire".to_string() }),
+        season: Some(Season { id: 3, number: 1 }),
+        episode_number: "1".to_string(),
+        ..EpisodeDetail::default()
+    };
+    let playing = NowPlaying::new(42, &details, Path::new("/videos/The Wire/S01E01 #1.mkv"), 0);
+    assert_eq!(playing, episode());
+
+    let movie = NowPlaying::new(5, &EpisodeDetail { title: "Heat".to_string(), ..EpisodeDetail::default() }, Path::new("/videos/Heat.mkv"), 95);
+    assert_eq!((movie.series, movie.season, movie.episode_number, movie.length, movie.start), (None, None, None, 0, 95));
+}
+
+#[test]
+fn test_album_names_the_series_and_season() {
+    assert_eq!(episode().album().as_deref(), Some("The Wire, Season 1"));
+    assert_eq!(NowPlaying { season: None, ..episode() }.album().as_deref(), Some("The Wire"));
+    assert_eq!(NowPlaying { series: None, ..episode() }.album(), None);
+}
+
+#[test]
+fn test_url_and_track_id() {
+    assert_eq!(episode().url(), "file:///videos/The%20Wire/S01E01%20%231.mkv");
+    assert_eq!(NowPlaying { path: PathBuf::from("C:\\Videos\\Heat.mkv"), ..episode() }.url(), "file:///C:/Videos/Heat.mkv");
+    assert_eq!(episode().track_id(), "/org/movies/episode/42");
+}
+
+#[test]
+#[cfg(not(feature = "mpris"))]
+fn test_publish_needs_the_mpris_feature() {
+    assert!(movies::now_playing::publish(episode()).is_none());
+}
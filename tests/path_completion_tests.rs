@@ -0,0 +1,83 @@
+use movies::path_completion::{check_path, complete, PathStatus};
+use std::fs;
+use std::path::MAIN_SEPARATOR;
+use tempfile::TempDir;
+
+/// A folder with "Movies", "Music", "Series" and ".hidden" folders and a "notes.txt" file in it
+fn library() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for folder in ["Movies", "Music", "Series", ".hidden"] {
+        fs::create_dir(dir.path().join(folder)).unwrap();
+    }
+    fs::write(dir.path().join("notes.txt"), "").unwrap();
+    dir
+}
+
+fn typed(dir: &TempDir, rest: &str) -> String {
+    format!("{}{}{}", dir.path().display(), MAIN_SEPARATOR, rest)
+}
+
+#[test]
+fn test_a_single_match_is_completed_with_a_separator() {
+    let dir = library();
+    let completion = complete(&typed(&dir, "Se"));
+    assert_eq!(completion.path, typed(&dir, &format!("Series{}", MAIN_SEPARATOR)));
+    assert!(completion.candidates.is_empty());
+}
+
+#[test]
+fn test_several_matches_complete_as_far_as_they_agree() {
+    let dir = library();
+    let completion = complete(&typed(&dir, "M"));
+    assert_eq!(completion.path, typed(&dir, "M"));
+    assert_eq!(completion.candidates, vec!["Movies".to_string(), "Music".to_string()]);
+
+    let completion = complete(&typed(&dir, "Mo"));
+    assert_eq!(completion.path, typed(&dir, &format!("Movies{}", MAIN_SEPARATOR)));
+}
+
+#[test]
+fn test_files_and_hidden_folders_are_left_out_until_a_dot_is_typed() {
+    let dir = library();
+    let completion = complete(&typed(&dir, ""));
+    assert_eq!(completion.candidates, vec!["Movies".to_string(), "Music".to_string(), "Series".to_string()]);
+
+    let completion = complete(&typed(&dir, "."));
+    assert_eq!(completion.path, typed(&dir, &format!(".hidden{}", MAIN_SEPARATOR)));
+    assert_eq!(complete(&typed(&dir, "no")).path, typed(&dir, "no"));
+}
+
+#[test]
+fn test_a_path_without_matches_is_left_as_it_is() {
+    let dir = library();
+    let missing = typed(&dir, &format!("Gone{}Mo", MAIN_SEPARATOR));
+    let completion = complete(&missing);
+    assert_eq!(completion.path, missing);
+    assert!(completion.candidates.is_empty());
+}
+
+#[test]
+fn test_check_path_tells_folders_from_typos_and_files() {
+    let dir = library();
+    assert_eq!(check_path(""), PathStatus::Empty);
+    assert_eq!(check_path(&typed(&dir, "Movies")), PathStatus::Ready);
+    assert_eq!(check_path(&typed(&dir, "Moveis")), PathStatus::Missing);
+    assert_eq!(check_path(&typed(&dir, "notes.txt")), PathStatus::NotADirectory);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_check_path_reports_folders_that_cant_be_read() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = library();
+    let locked = dir.path().join("Movies");
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+    // Root reads any folder, so there is nothing to report when the tests run as root
+    let readable = fs::read_dir(&locked).is_ok();
+    let status = check_path(&locked.display().to_string());
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+    if !readable {
+        assert_eq!(status, PathStatus::NoPermission);
+    }
+}
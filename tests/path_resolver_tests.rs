@@ -109,3 +109,66 @@ fn test_path_validation_rejects_outside_root() {
         }
     }
 }
+
+#[test]
+fn test_locate_finds_the_root_holding_a_path() {
+    let library = TempDir::new().unwrap();
+    let drive = TempDir::new().unwrap();
+    let db_path = library.path().join("videos.sqlite");
+    fs::write(&db_path, "test").unwrap();
+    let mut resolver = PathResolver::from_database_path(&db_path).unwrap();
+
+    let outside = drive.path().join("Show").join("episode.mkv");
+    fs::create_dir_all(outside.parent().unwrap()).unwrap();
+    fs::write(&outside, "test").unwrap();
+    assert!(matches!(resolver.locate(&outside), Err(PathResolverError::PathNotUnderRoot(_))));
+
+    // Adding the same id again replaces the folder
+    resolver.add_root(3, library.path());
+    resolver.add_root(3, drive.path());
+    assert_eq!(resolver.extra_roots().len(), 1);
+    assert_eq!(resolver.locate(&outside).unwrap(), (Some(3), PathBuf::from("Show/episode.mkv")));
+
+    let inside = library.path().join("movie.mkv");
+    fs::write(&inside, "test").unwrap();
+    assert_eq!(resolver.locate(&inside).unwrap(), (None, PathBuf::from("movie.mkv")));
+
+    // Absolute locations of episodes under another root pass through
+    let absolute = drive.path().canonicalize().unwrap().join("Show/episode.mkv");
+    assert_eq!(resolver.to_absolute(&absolute), absolute);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_locate_keeps_links_leading_out_of_the_root() {
+    let library = TempDir::new().unwrap();
+    let drive = TempDir::new().unwrap();
+    let db_path = library.path().join("videos.sqlite");
+    fs::write(&db_path, "test").unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+
+    fs::write(drive.path().join("movie.mkv"), "test").unwrap();
+    std::os::unix::fs::symlink(drive.path(), library.path().join("Drive")).unwrap();
+    let linked = resolver.get_root_dir().join("Drive").join("movie.mkv");
+    assert_eq!(resolver.locate(&linked).unwrap(), (None, PathBuf::from("Drive/movie.mkv")));
+    assert!(resolver.locate(&drive.path().join("movie.mkv")).is_err());
+}
+
+#[test]
+fn test_offline_roots_are_missing_or_empty_folders() {
+    let library = TempDir::new().unwrap();
+    let db_path = library.path().join("videos.sqlite");
+    fs::write(&db_path, "test").unwrap();
+    let mut resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let mounted = TempDir::new().unwrap();
+    fs::write(mounted.path().join("movie.mkv"), b"").unwrap();
+    let mount_point = TempDir::new().unwrap();
+    let unplugged = library.path().join("unplugged");
+    resolver.add_root(1, mounted.path());
+    resolver.add_root(2, mount_point.path());
+    resolver.add_root(3, &unplugged);
+
+    assert_eq!(resolver.offline_roots(), vec![mount_point.path().canonicalize().unwrap(), unplugged.clone()]);
+    assert!(is_offline(&unplugged));
+    assert!(!is_offline(library.path()));
+}
@@ -0,0 +1,36 @@
+use movies::config::Config;
+use movies::paths::*;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn test_relative_path_goes_up_where_needed() {
+    let base = Path::new("/media/drive/apps/movies");
+    assert_eq!(relative_path(Path::new("/media/drive/apps/movies/videos.sqlite"), base), Some(PathBuf::from("videos.sqlite")));
+    assert_eq!(
+        relative_path(Path::new("/media/drive/Videos/videos.sqlite"), base),
+        Some(PathBuf::from("../../Videos/videos.sqlite"))
+    );
+    assert_eq!(relative_path(Path::new("videos.sqlite"), base), None);
+}
+
+#[test]
+#[serial_test::serial]
+fn test_portable_mode_keeps_everything_in_its_folder() {
+    let portable = tempfile::TempDir::new().unwrap();
+    set_portable_dir(Some(portable.path().to_path_buf()));
+    assert_eq!(config_dir(), Some(portable.path().to_path_buf()));
+    assert_eq!(data_dir(), Some(portable.path().to_path_buf()));
+    assert_eq!(AppPaths::new().unwrap().config_file, portable.path().join("config.yaml"));
+
+    // The database is stored relative to the portable folder and read back against it
+    let library = portable.path().join("Videos").join("videos.sqlite");
+    let mut config = Config::default();
+    config.set_database_path(library.clone());
+    assert_eq!(config.db_location.as_deref(), Some(Path::new("Videos").join("videos.sqlite").to_str().unwrap()));
+    assert_eq!(config.get_database_path(), Some(library.clone()));
+
+    set_portable_dir(None);
+    assert_ne!(data_dir(), Some(portable.path().to_path_buf()));
+    assert_eq!(resolve_location("/media/videos.sqlite"), PathBuf::from("/media/videos.sqlite"));
+    assert_eq!(store_location(&library), library.to_string_lossy());
+}
@@ -1,4 +1,5 @@
 use movies::player_plugin::{PlayerPlugin, CelluloidPlugin, GenericPlayerPlugin, create_player_plugin};
+use std::ffi::OsString;
 use std::path::Path;
 use std::fs;
 use tempfile::TempDir;
@@ -121,8 +122,8 @@ fn test_generic_player_vlc_launch_command() {
     let (command, args) = plugin.launch_command(file_path, Some(60));
     
     assert_eq!(command, "/usr/bin/vlc");
-    assert!(args.contains(&"--start-time=60".to_string()));
-    assert!(args.contains(&"/path/to/video.mp4".to_string()));
+    assert!(args.contains(&OsString::from("--start-time=60")));
+    assert!(args.contains(&OsString::from("/path/to/video.mp4")));
 }
 
 #[test]
@@ -133,8 +134,8 @@ fn test_generic_player_mpv_launch_command() {
     let (command, args) = plugin.launch_command(file_path, Some(90));
     
     assert_eq!(command, "/usr/bin/mpv");
-    assert!(args.contains(&"--start=90".to_string()));
-    assert!(args.contains(&"/path/to/video.mp4".to_string()));
+    assert!(args.contains(&OsString::from("--start=90")));
+    assert!(args.contains(&OsString::from("/path/to/video.mp4")));
 }
 
 #[test]
@@ -154,7 +155,7 @@ fn test_create_player_plugin_celluloid() {
     let (command, args) = plugin.launch_command(file_path, None);
     
     assert_eq!(command, "celluloid");
-    assert!(args.contains(&"--mpv-save-position-on-quit".to_string()));
+    assert!(args.contains(&OsString::from("--mpv-save-position-on-quit")));
 }
 
 #[test]
@@ -165,5 +166,5 @@ fn test_create_player_plugin_generic() {
     let (command, args) = plugin.launch_command(file_path, None);
     
     assert_eq!(command, "/usr/bin/vlc");
-    assert!(args.contains(&"/path/to/video.mp4".to_string()));
+    assert!(args.contains(&OsString::from("/path/to/video.mp4")));
 }
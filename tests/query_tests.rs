@@ -0,0 +1,171 @@
+use movies::dto::AltTitles;
+use movies::query::{filter_entries, match_ranges, EpisodeFacts, FilterCache, PinnedFilters, Query, QueryTerm, MAX_PINNED_FILTERS};
+use movies::util::Entry;
+use std::collections::HashMap;
+
+fn episode(episode_id: usize, name: &str) -> Entry {
+    Entry::Episode {
+        episode_id,
+        name: name.to_string(),
+        location: format!("{}.mkv", name),
+    }
+}
+
+fn facts(episode_id: usize, series_id: Option<usize>, watched: bool, length: u64) -> EpisodeFacts {
+    EpisodeFacts {
+        episode_id,
+        series_id,
+        season_id: None,
+        watched,
+        length: Some(length),
+    }
+}
+
+#[test]
+fn test_parse_recognizes_keywords_and_lengths() {
+    let query = Query::parse("Pilot unwatched <30min >2h");
+    assert_eq!(
+        query.terms(),
+        &[
+            QueryTerm::Text("pilot".to_string()),
+            QueryTerm::Watched(false),
+            QueryTerm::ShorterThan(30 * 60),
+            QueryTerm::LongerThan(2 * 3600),
+        ]
+    );
+    assert!(query.needs_facts());
+
+    // Unknown keys and units fall back to name text
+    let query = Query::parse("tag:kids <30sec");
+    assert_eq!(
+        query.terms(),
+        &[QueryTerm::Text("tag:kids".to_string()), QueryTerm::Text("<30sec".to_string())]
+    );
+    assert!(!query.needs_facts());
+}
+
+#[test]
+fn test_filter_entries_combines_queries_and_matches_series_by_episode() {
+    let entries = vec![
+        Entry::Series { series_id: 1, name: "Cartoons".to_string() },
+        episode(10, "Short Film"),
+        episode(11, "Long Movie"),
+    ];
+    let facts = vec![
+        facts(5, Some(1), false, 20 * 60),
+        facts(10, None, true, 15 * 60),
+        facts(11, None, false, 120 * 60),
+    ];
+
+    let short = Query::parse("<30min");
+    let names: Vec<String> = filter_entries(&entries, &[&short], &facts, &HashMap::new())
+        .iter()
+        .map(|entry| match entry {
+            Entry::Series { name, .. } | Entry::Episode { name, .. } => name.clone(),
+            Entry::Season { number, .. } => number.to_string(),
+        })
+        .collect();
+    assert_eq!(names, vec!["Cartoons", "Short Film"]);
+
+    let unwatched = Query::parse("unwatched");
+    let typed = Query::parse("movie");
+    let filtered = filter_entries(&entries, &[&unwatched, &typed], &facts, &HashMap::new());
+    assert_eq!(filtered.len(), 1);
+    assert!(matches!(filtered[0], Entry::Episode { episode_id: 11, .. }));
+
+    // An empty query keeps everything
+    assert_eq!(filter_entries(&entries, &[&Query::parse("")], &[], &HashMap::new()).len(), 3);
+}
+
+#[test]
+fn test_filter_entries_matches_alternate_titles() {
+    let entries = vec![episode(10, "Spirited Away"), episode(11, "Totoro")];
+    let mut titles = AltTitles::new();
+    titles.insert("ja".to_string(), "Sen to Chihiro no Kamikakushi".to_string());
+    let alt_titles = HashMap::from([(10, titles)]);
+
+    let query = Query::parse("chihiro");
+    let filtered = filter_entries(&entries, &[&query], &[], &alt_titles);
+    assert_eq!(filtered.len(), 1);
+    assert!(matches!(filtered[0], Entry::Episode { episode_id: 10, .. }));
+
+    // The display name still matches, and other episodes are unaffected
+    assert_eq!(filter_entries(&entries, &[&Query::parse("spirited")], &[], &alt_titles).len(), 1);
+    assert!(filter_entries(&entries, &[&Query::parse("kamikakushi totoro")], &[], &alt_titles).is_empty());
+}
+
+#[test]
+fn test_pinned_filters_pin_toggle_and_limit() {
+    let mut pinned = PinnedFilters::new(&["unwatched".to_string(), "  ".to_string()]);
+    assert_eq!(pinned.expressions(), vec!["unwatched"]);
+    assert!(pinned.active_queries().is_empty());
+
+    assert!(pinned.pin("").is_err());
+    assert!(pinned.pin("unwatched").is_err());
+    assert_eq!(pinned.pin("<30min"), Ok(2));
+    assert_eq!(pinned.pin("kids"), Ok(3));
+    assert_eq!(MAX_PINNED_FILTERS, 3);
+    assert!(pinned.pin("watched").is_err());
+
+    assert_eq!(pinned.toggle(2), Some(true));
+    assert_eq!(pinned.toggle(4), None);
+    assert_eq!(pinned.active_queries().len(), 1);
+    assert_eq!(pinned.label(), "[1] unwatched  [2]*<30min  [3] kids");
+
+    pinned.clear();
+    assert!(pinned.expressions().is_empty());
+    assert_eq!(pinned.label(), "");
+}
+
+#[test]
+fn test_match_ranges_for_highlighting() {
+    let query = Query::parse("office unwatched s01");
+    assert_eq!(query.text_terms(), vec!["office", "s01"]);
+
+    assert_eq!(match_ranges("The Office S01E01", &query.text_terms()), vec![4..10, 11..14]);
+    // Overlapping matches merge into one range, counted in characters
+    assert_eq!(match_ranges("Amélie Amélie", &["mé", "éli"]), vec![1..5, 8..12]);
+    assert!(match_ranges("Pilot", &[]).is_empty());
+}
+
+#[test]
+fn test_typing_onto_a_query_narrows_it() {
+    let narrows = |query: &str, previous: &str| Query::parse(query).narrows(&Query::parse(previous));
+    assert!(narrows("lo", ""));
+    assert!(narrows("los", "lo"));
+    assert!(narrows("lost >2h", "lost"));
+    assert!(narrows("lost pilot", "los"));
+    assert!(!narrows("lo", "los"), "Deleting a character widens the match");
+    assert!(!narrows("watched", "watche"), "A finished keyword is no longer name text");
+    assert!(!narrows("<30", "<3"));
+}
+
+#[test]
+fn test_filter_cache_narrows_the_last_result() {
+    let entries = vec![episode(1, "Lost Pilot"), episode(2, "Lost Finale"), episode(3, "Dark")];
+    let alt_titles = HashMap::new();
+    let mut cache = FilterCache::default();
+    let names = |matches: &[Entry]| -> Vec<String> {
+        matches
+            .iter()
+            .map(|entry| match entry {
+                Entry::Episode { name, .. } => name.clone(),
+                _ => String::new(),
+            })
+            .collect()
+    };
+
+    let lost = Query::parse("lost");
+    assert_eq!(names(cache.filter(&entries, &[&lost], &[], &alt_titles)).len(), 2);
+
+    // Narrowing only looks at the last result, so an entry added since is not picked up...
+    let grown = [entries.clone(), vec![episode(4, "Lost Again")]].concat();
+    let pilot = Query::parse("lost p");
+    assert_eq!(names(cache.filter(&grown, &[&pilot], &[], &alt_titles)), vec!["Lost Pilot"]);
+    let lost = Query::parse("lost");
+    assert_eq!(names(cache.filter(&grown, &[&lost], &[], &alt_titles)).len(), 3, "...until the filter widens");
+
+    // ...or the cache is cleared
+    cache.clear();
+    assert_eq!(names(cache.filter(&grown, &[&pilot], &[], &alt_titles)), vec!["Lost Pilot"]);
+}
@@ -0,0 +1,58 @@
+use movies::database::Database;
+use movies::relocate::{check_relocation, move_database, sample_locations};
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_sample_is_spread_over_the_library() {
+    let locations: Vec<String> = (0..100).map(|n| format!("{:03}.mkv", n)).collect();
+    let sample = sample_locations(&locations, 4);
+    assert_eq!(sample, vec!["000.mkv", "025.mkv", "050.mkv", "075.mkv"]);
+    assert_eq!(sample_locations(&locations[..3], 4).len(), 3);
+}
+
+#[test]
+fn test_check_looks_for_relative_paths_under_the_new_root() {
+    let new_root = TempDir::new().unwrap();
+    fs::create_dir_all(new_root.path().join("Show")).unwrap();
+    fs::write(new_root.path().join("Show/e1.mkv"), b"").unwrap();
+    fs::write(new_root.path().join("movie.mkv"), b"").unwrap();
+    let locations = vec![
+        "Show/e1.mkv".to_string(),
+        "movie.mkv".to_string(),
+        "gone.mkv".to_string(),
+        // Under another folder of the library, so not looked for
+        "/media/drive/other.mkv".to_string(),
+    ];
+
+    let check = check_relocation(new_root.path(), &locations, "videos.sqlite").unwrap();
+    assert_eq!((check.sampled, check.found), (3, 2));
+    assert_eq!(check.missing, vec!["gone.mkv"]);
+    assert!(check.looks_right() && !check.has_database);
+    assert_eq!(check.summary(), format!("Found 2 of 3 sampled videos under {}", check.new_root.display()));
+
+    let wrong = check_relocation(new_root.path(), &["a.mkv".to_string(), "b.mkv".to_string()], "videos.sqlite").unwrap();
+    assert!(!wrong.looks_right());
+    assert!(check_relocation(&new_root.path().join("nowhere"), &locations, "videos.sqlite").is_err());
+}
+
+#[test]
+fn test_database_is_copied_unless_the_new_root_has_one() {
+    let old_root = TempDir::new().unwrap();
+    let new_root = TempDir::new().unwrap();
+    let old_db = old_root.path().join("videos.sqlite");
+    let database = Database::open(&old_db).unwrap();
+    database.register_root("/media/drive").unwrap();
+
+    let (new_db, copied) = move_database(&old_db, new_root.path()).unwrap();
+    assert!(copied);
+    assert_eq!(new_db, new_root.path().join("videos.sqlite"));
+    assert!(old_db.is_file(), "The old database is left in place");
+    assert!(!new_root.path().join("videos.sqlite.tmp").exists());
+    let copy = Database::open(&new_db).unwrap();
+    assert_eq!(copy.register_root("/media/drive").unwrap(), database.register_root("/media/drive").unwrap());
+
+    // A database already in the folder is used as it is
+    let (again, copied) = move_database(&old_db, new_root.path()).unwrap();
+    assert_eq!((again, copied), (new_db, false));
+}
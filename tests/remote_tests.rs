@@ -0,0 +1,167 @@
+use movies::remote::{format_reply, parse_command, RemoteCommand};
+
+#[test]
+fn test_parse_command() {
+    assert_eq!(parse_command("play 42"), Ok(RemoteCommand::Play(42)));
+    assert_eq!(parse_command("  Toggle-Watched 7 "), Ok(RemoteCommand::ToggleWatched(7)));
+    assert_eq!(parse_command("rescan"), Ok(RemoteCommand::Rescan));
+    assert_eq!(parse_command("status"), Ok(RemoteCommand::Status));
+
+    assert!(parse_command("play").unwrap_err().contains("needs an episode id"));
+    assert!(parse_command("play pilot").unwrap_err().contains("'pilot' is not an episode id"));
+    assert!(parse_command("play 1 2").unwrap_err().contains("too many arguments"));
+    assert!(parse_command("rescan now").unwrap_err().contains("takes no arguments"));
+    assert!(parse_command("pause").unwrap_err().contains("unknown command 'pause'"));
+}
+
+#[test]
+fn test_format_reply() {
+    assert_eq!(format_reply(&Ok("playing 42".to_string())), "ok playing 42");
+    assert_eq!(format_reply(&Ok(String::new())), "ok");
+    assert_eq!(format_reply(&Err("a rescan is already running".to_string())), "error a rescan is already running");
+}
+
+#[cfg(unix)]
+mod unix {
+    use movies::remote::{RemoteCommand, RemoteServer, CLIENT_QUEUE_LINES};
+    use movies::util::AppEvent;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_commands_are_answered_by_the_main_loop_and_events_reach_clients() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("movies.sock");
+        let (tx, rx) = std::sync::mpsc::channel();
+        let server = RemoteServer::start(&path, tx).unwrap();
+        assert!(RemoteServer::start(&path, std::sync::mpsc::channel().0).is_err(), "Only one instance listens");
+
+        let mut client = UnixStream::connect(&path).unwrap();
+        client.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let mut replies = BufReader::new(client.try_clone().unwrap());
+        let mut line = String::new();
+
+        // A command that can't be parsed is answered without the main loop
+        client.write_all(b"pause\n").unwrap();
+        replies.read_line(&mut line).unwrap();
+        assert!(line.starts_with("error unknown command"), "{}", line);
+
+        client.write_all(b"play 42\n").unwrap();
+        match rx.recv_timeout(Duration::from_secs(5)).unwrap() {
+            AppEvent::Remote(request) => {
+                assert_eq!(request.command, RemoteCommand::Play(42));
+                request.reply.send(Ok("playing 42".to_string())).unwrap();
+            }
+            other => panic!("Unexpected event {:?}", other),
+        }
+        line.clear();
+        replies.read_line(&mut line).unwrap();
+        assert_eq!(line, "ok playing 42\n");
+
+        server.emit("stopped");
+        line.clear();
+        replies.read_line(&mut line).unwrap();
+        assert_eq!(line, "event stopped\n");
+
+        drop(server);
+        assert!(!path.exists(), "The socket is removed when the app quits");
+    }
+
+    /// Connect a client and wait until the server has it, by way of a command answered without the main loop
+    fn connect(path: &std::path::Path) -> (UnixStream, BufReader<UnixStream>) {
+        let mut client = UnixStream::connect(path).unwrap();
+        client.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let mut replies = BufReader::new(client.try_clone().unwrap());
+        client.write_all(b"pause\n").unwrap();
+        let mut line = String::new();
+        replies.read_line(&mut line).unwrap();
+        (client, replies)
+    }
+
+    #[test]
+    fn test_a_client_that_stops_reading_does_not_block_events() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("movies.sock");
+        let server = RemoteServer::start(&path, std::sync::mpsc::channel().0).unwrap();
+        let _stuck = connect(&path);
+
+        // Far more than the socket buffer holds; with blocking writes this never finished
+        let (done, finished) = std::sync::mpsc::channel();
+        let server = std::thread::spawn(move || {
+            let event = format!("playing {}", "x".repeat(1000));
+            for _ in 0..CLIENT_QUEUE_LINES * 100 {
+                server.emit(&event);
+            }
+            done.send(()).unwrap();
+            server
+        });
+        finished.recv_timeout(Duration::from_secs(5)).expect("emit waited on a client that doesn't read");
+        let server = server.join().unwrap();
+
+        // Clients that do read still get their events
+        let (_client, mut replies) = connect(&path);
+        server.emit("stopped");
+        let mut line = String::new();
+        replies.read_line(&mut line).unwrap();
+        assert_eq!(line, "event stopped\n");
+    }
+
+    #[test]
+    fn test_a_socket_left_behind_is_replaced() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state").join("movies.sock");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+        let server = RemoteServer::start(&path, std::sync::mpsc::channel().0).unwrap();
+        assert_eq!(server.path(), path);
+        UnixStream::connect(&path).unwrap();
+    }
+}
+
+#[test]
+#[serial_test::serial]
+fn test_remote_commands_change_the_library() {
+    use movies::app_state::{AppContext, AppState};
+    use movies::config::Config;
+    use movies::database::Database;
+    use movies::handlers::handle_remote_command;
+    use std::sync::Arc;
+    use movies::path_resolver::PathResolver;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let db_path = dir.path().join("videos.sqlite");
+    let database = Arc::new(Database::open(&db_path).unwrap());
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let video = dir.path().join("Pilot.mkv");
+    std::fs::write(&video, b"").unwrap();
+    database.import_episode_relative(video.to_str().unwrap(), "Pilot.mkv", &resolver).unwrap();
+    let entries = database.get_entries().unwrap();
+    let Some(movies::util::Entry::Episode { episode_id, .. }) = entries.first().cloned() else {
+        panic!("The video was imported");
+    };
+
+    let config = Config::default();
+    let (tx, _rx) = std::sync::mpsc::channel();
+    let context = AppContext {
+        config: &config,
+        database: &database,
+        resolver: &resolver,
+        tx: &tx,
+        config_path: &dir.path().join("config.yaml"),
+    };
+    let mut state = AppState::new(entries);
+
+    assert_eq!(handle_remote_command(RemoteCommand::ToggleWatched(episode_id), &mut state, &context), Ok("watched".to_string()));
+    assert_eq!(database.get_episode_detail(episode_id).unwrap().watched, "true");
+    assert!(handle_remote_command(RemoteCommand::ToggleWatched(episode_id + 100), &mut state, &context).is_err());
+
+    assert_eq!(handle_remote_command(RemoteCommand::Status, &mut state, &context), Ok("idle".to_string()));
+    state.playing_file = Some(video.display().to_string());
+    assert_eq!(
+        handle_remote_command(RemoteCommand::Play(episode_id), &mut state, &context),
+        Err("a video is already playing".to_string())
+    );
+}
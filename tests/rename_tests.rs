@@ -0,0 +1,135 @@
+use movies::database::Database;
+use movies::path_resolver::PathResolver;
+use movies::rename::{render_template, rename_file, rename_target, RenameFields};
+use movies::util::Entry;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const SERIES_TEMPLATE: &str = "{series} - S{season:02}E{episode:02} - {title}.{ext}";
+
+fn episode_fields() -> RenameFields {
+    RenameFields {
+        series: Some("The Show".to_string()),
+        season: Some(1),
+        episode: Some(4),
+        title: "Pilot".to_string(),
+        year: "2020".to_string(),
+        ext: "mkv".to_string(),
+    }
+}
+
+#[test]
+fn test_template_pads_numbers_and_fills_in_the_details() {
+    assert_eq!(render_template(SERIES_TEMPLATE, &episode_fields()).unwrap(), PathBuf::from("The Show - S01E04 - Pilot.mkv"));
+    assert_eq!(render_template("{title} ({year}).{ext}", &episode_fields()).unwrap(), PathBuf::from("Pilot (2020).mkv"));
+    assert_eq!(
+        render_template("Season {season}/{episode:03}.{ext}", &episode_fields()).unwrap(),
+        PathBuf::from("Season 1/004.mkv"),
+        "A folder in the template stays below the file's folder"
+    );
+}
+
+#[test]
+fn test_values_are_made_safe_for_file_names() {
+    let fields = RenameFields { title: "Mission: Impossible / Part 2?".to_string(), ..episode_fields() };
+    assert_eq!(render_template("{title}.{ext}", &fields).unwrap(), PathBuf::from("Mission - Impossible - Part 2-.mkv"));
+
+    let fields = RenameFields { title: "..".to_string(), ..episode_fields() };
+    assert_eq!(render_template("{title}", &fields).unwrap_err(), "The rename template has to name a file in the episode's folder: {title}");
+}
+
+#[test]
+fn test_bad_templates_are_errors() {
+    let fields = episode_fields();
+    assert_eq!(render_template("{name}.{ext}", &fields).unwrap_err(), "Unknown placeholder {name} in the rename template");
+    assert_eq!(render_template("{season:xx}", &fields).unwrap_err(), "Bad width in {season:xx} in the rename template");
+    assert!(render_template("{title", &fields).unwrap_err().starts_with("Unclosed {"));
+    assert!(render_template("/tmp/{title}.{ext}", &fields).is_err(), "An absolute path leaves the folder");
+    assert!(render_template("../{title}.{ext}", &fields).is_err());
+}
+
+#[test]
+fn test_target_is_in_the_file_folder() {
+    let to = rename_target(Path::new("/videos/show/episode.4.mkv"), SERIES_TEMPLATE, &episode_fields()).unwrap();
+    assert_eq!(to, PathBuf::from("/videos/show/The Show - S01E04 - Pilot.mkv"));
+}
+
+#[test]
+fn test_renaming_keeps_an_existing_file() {
+    let dir = TempDir::new().unwrap();
+    let from = dir.path().join("old.mkv");
+    let taken = dir.path().join("taken.mkv");
+    fs::write(&from, b"video").unwrap();
+    fs::write(&taken, b"other").unwrap();
+    assert!(rename_file(&from, &taken).unwrap_err().starts_with("There is already a file at"));
+    assert_eq!(fs::read(&taken).unwrap(), b"other");
+
+    let to = dir.path().join("Season 1/new.mkv");
+    rename_file(&from, &to).unwrap();
+    assert!(!from.exists());
+    assert_eq!(fs::read(&to).unwrap(), b"video");
+}
+
+#[test]
+fn test_episode_location_follows_the_renamed_file() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let root = resolver.get_root_dir().to_path_buf();
+
+    let from = root.join("movie.mp4");
+    fs::write(&from, b"video").unwrap();
+    database.import_episode_relative(from.to_str().unwrap(), "Movie", &resolver).unwrap();
+    let episode_id = match &database.get_entries().unwrap()[0] {
+        Entry::Episode { episode_id, .. } => *episode_id,
+        other => panic!("Expected an episode, got {:?}", other),
+    };
+
+    let to = root.join("Movies/Movie (2020).mp4");
+    rename_file(&from, &to).unwrap();
+    database.update_episode_location(episode_id, &to, &resolver).unwrap();
+    assert_eq!(database.get_episode_absolute_location(episode_id, &resolver).unwrap(), to.to_str().unwrap());
+}
+
+#[test]
+fn test_rename_from_the_editor_waits_for_unsaved_changes() {
+    use movies::app_state::{AppContext, AppState};
+    use movies::config::Config;
+    use movies::episode_field::EpisodeField;
+    use movies::handlers::open_rename_preview;
+    use movies::util::Mode;
+    use std::sync::Arc;
+
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("videos.sqlite");
+    let database = Arc::new(Database::open(&db_path).unwrap());
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let from = resolver.get_root_dir().join("movie.mp4");
+    fs::write(&from, b"video").unwrap();
+    database.import_episode_relative(from.to_str().unwrap(), "Movie", &resolver).unwrap();
+    let config = Config::default();
+    let (tx, _rx) = std::sync::mpsc::channel();
+    let context = AppContext {
+        config: &config,
+        database: &database,
+        resolver: &resolver,
+        tx: &tx,
+        config_path: &db_path.with_file_name("config.yaml"),
+    };
+
+    let mut state = AppState::new(database.get_entries().unwrap());
+    state.mode = Mode::Edit;
+    state.edit_details.title = "Typed But Not Saved".to_string();
+    state.dirty_fields.insert(EpisodeField::Title);
+    open_rename_preview(&mut state, &context);
+    assert!(state.rename_preview.is_none());
+    assert_eq!(state.mode, Mode::Edit);
+
+    // Once saved the preview is worked out from what is in the library
+    state.dirty_fields.clear();
+    open_rename_preview(&mut state, &context);
+    let preview = state.rename_preview.as_ref().expect("A preview should be open");
+    assert_eq!(preview.to.file_name().unwrap(), "Movie.mp4");
+}
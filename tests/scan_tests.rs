@@ -0,0 +1,317 @@
+use movies::app_state::AppState;
+use movies::config::{Config, RootOverride, ScanRules};
+use movies::database::Database;
+use movies::extras::SampleFiles;
+use movies::folder_series::SeriesProposal;
+use movies::handlers::finish_rescan;
+use movies::path_resolver::PathResolver;
+use movies::util::{Entry, Mode};
+use movies::scan::{library_rules, rescan, rescan_folder, HardLinks, RescanSchedule, ScanProgress, ScanReport, SkipReason, SkippedFile};
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+#[test]
+fn test_scan_progress_status_line() {
+    let walking = ScanProgress {
+        scanned: 1534,
+        imported: 12,
+        ..Default::default()
+    };
+    assert_eq!(walking.status_line(0), "⠋ Scanned 1534 files, imported 12");
+    assert!(walking.status_line(1).starts_with('⠙'), "The spinner moves on each frame");
+
+    // Reading lengths has a known total, so it gets a bar
+    let reading = ScanProgress {
+        lengths_done: 10,
+        lengths_total: 40,
+        ..walking
+    };
+    let line = reading.status_line(0);
+    assert!(line.ends_with("█████░░░░░░░░░░░░░░░ 10/40"), "{}", line);
+    assert!(line.contains("Scanned 1534 files, imported 12"));
+}
+
+#[test]
+fn test_rescan_imports_new_videos_and_reports_progress() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let rules = ScanRules {
+        video_extensions: vec!["mkv".to_string(), "mp4".to_string()],
+        min_file_size_mb: 0,
+        exclude: Vec::new(),
+        follow_symlinks: false,
+        dedupe_hardlinks: false,
+        samples: SampleFiles::Import,
+        sample_max_seconds: 0,
+    };
+
+    fs::create_dir_all(dir.path().join("Show")).unwrap();
+    fs::write(dir.path().join("movie.mkv"), b"").unwrap();
+    fs::write(dir.path().join("Show").join("S01E01.mp4"), b"").unwrap();
+    fs::write(dir.path().join("Show").join("notes.txt"), b"").unwrap();
+
+    let mut updates = Vec::new();
+    let report = rescan(dir.path(), |_| rules.clone(), &database, &resolver, &AtomicBool::new(false), |progress| updates.push(progress));
+    assert!(!report.cancelled);
+    // The database file itself is walked too
+    assert_eq!(report.scanned, 4);
+    assert_eq!(report.imported, 2);
+    assert_eq!(report.imported_files.len(), 2);
+    assert!(report.imported_files.contains(&dir.path().join("movie.mkv")));
+
+    // Always told when the scan starts and when reading lengths starts
+    assert_eq!(updates.first(), Some(&ScanProgress::default()));
+    let reading = updates.iter().find(|progress| progress.lengths_total > 0).unwrap();
+    assert_eq!((reading.scanned, reading.imported, reading.lengths_total), (4, 2, 2));
+
+    let again = rescan(dir.path(), |_| rules.clone(), &database, &resolver, &AtomicBool::new(false), |_| {});
+    assert_eq!(again.imported, 0, "Videos already in the library are not imported twice");
+
+    // Cancelling stops at the next file and skips reading lengths
+    fs::write(dir.path().join("late.mkv"), b"").unwrap();
+    let cancel = AtomicBool::new(false);
+    let mut updates = Vec::new();
+    let cancelled = rescan(dir.path(), |_| rules.clone(), &database, &resolver, &cancel, |progress| {
+        updates.push(progress);
+        cancel.store(true, Ordering::SeqCst);
+    });
+    assert!(cancelled.cancelled);
+    assert_eq!((cancelled.scanned, cancelled.imported), (0, 0));
+    assert!(updates.iter().all(|progress| progress.lengths_total == 0));
+
+    // Excluded videos are listed as skipped instead of imported, and deleted files as missing
+    fs::create_dir_all(dir.path().join("Show").join("Extras")).unwrap();
+    let extra = dir.path().join("Show").join("Extras").join("making-of.mkv");
+    fs::write(&extra, b"").unwrap();
+    fs::remove_file(dir.path().join("movie.mkv")).unwrap();
+    let rules = ScanRules { exclude: vec!["**/extras/**".to_string()], ..rules };
+    let excluded = rescan(dir.path(), |_| rules.clone(), &database, &resolver, &AtomicBool::new(false), |_| {});
+    assert_eq!(excluded.imported_files, vec![dir.path().join("late.mkv")]);
+    assert_eq!(excluded.skipped, vec![SkippedFile { path: extra, reason: SkipReason::Excluded }]);
+    let root = dir.path().canonicalize().unwrap();
+    assert_eq!(excluded.missing_files, vec![root.join("movie.mkv")]);
+}
+
+#[test]
+fn test_rescan_folder_only_walks_that_folder() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let rules = ScanRules {
+        video_extensions: vec!["mkv".to_string()],
+        min_file_size_mb: 0,
+        exclude: Vec::new(),
+        follow_symlinks: false,
+        dedupe_hardlinks: false,
+        samples: SampleFiles::Import,
+        sample_max_seconds: 0,
+    };
+
+    fs::create_dir_all(dir.path().join("Show")).unwrap();
+    fs::write(dir.path().join("movie.mkv"), b"").unwrap();
+    fs::write(dir.path().join("Show").join("S01E01.mkv"), b"").unwrap();
+    rescan(dir.path(), |_| rules.clone(), &database, &resolver, &AtomicBool::new(false), |_| {});
+
+    // Files outside the folder are neither imported nor reported missing
+    fs::write(dir.path().join("other.mkv"), b"").unwrap();
+    fs::remove_file(dir.path().join("movie.mkv")).unwrap();
+    fs::write(dir.path().join("Show").join("S01E02.mkv"), b"").unwrap();
+    fs::remove_file(dir.path().join("Show").join("S01E01.mkv")).unwrap();
+    let show = dir.path().join("Show").canonicalize().unwrap();
+    let report = rescan_folder(&show, &rules, &database, &resolver, &AtomicBool::new(false), |_| {});
+    assert_eq!(report.folder.as_deref(), Some(show.as_path()));
+    assert_eq!(report.imported_files, vec![show.join("S01E02.mkv")]);
+    assert_eq!(report.missing_files, vec![show.join("S01E01.mkv")]);
+    assert_eq!(report.summary(), "Scan of Show complete. Found 1 new videos");
+}
+
+#[test]
+fn test_samples_are_flagged_as_extras_or_skipped() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let rules = ScanRules {
+        video_extensions: vec!["mkv".to_string()],
+        min_file_size_mb: 0,
+        exclude: Vec::new(),
+        follow_symlinks: false,
+        dedupe_hardlinks: false,
+        samples: SampleFiles::Flag,
+        sample_max_seconds: 60,
+    };
+
+    fs::write(dir.path().join("movie.mkv"), b"").unwrap();
+    fs::write(dir.path().join("movie-sample.mkv"), b"").unwrap();
+    let report = rescan(dir.path(), |_| rules.clone(), &database, &resolver, &AtomicBool::new(false), |_| {});
+    assert_eq!(report.imported, 2);
+    assert_eq!(report.extras, vec![dir.path().join("movie-sample.mkv")]);
+    assert!(report.lines().contains(&"Imported as extras (1)".to_string()));
+
+    // Extras only show up in their own view
+    let names = |entries: Vec<Entry>| -> Vec<String> {
+        entries.into_iter().map(|entry| match entry {
+            Entry::Episode { name, .. } | Entry::Series { name, .. } => name,
+            Entry::Season { number, .. } => number.to_string(),
+        }).collect()
+    };
+    assert_eq!(names(database.get_entries().unwrap()), vec!["movie.mkv"]);
+    assert_eq!(names(database.get_extras().unwrap()), vec!["movie-sample.mkv"]);
+
+    fs::write(dir.path().join("movie.trailer.mkv"), b"").unwrap();
+    let rules = ScanRules { samples: SampleFiles::Skip, ..rules };
+    let skipped = rescan(dir.path(), |_| rules.clone(), &database, &resolver, &AtomicBool::new(false), |_| {});
+    assert_eq!(skipped.imported, 0);
+    assert!(skipped.skipped.contains(&SkippedFile { path: dir.path().join("movie.trailer.mkv"), reason: SkipReason::Sample }));
+    // Like excluded files, a sample already in the library stays there
+    assert_eq!(names(database.get_extras().unwrap()), vec!["movie-sample.mkv"]);
+}
+
+#[test]
+fn test_rescan_applies_each_roots_override() {
+    let library = TempDir::new().unwrap();
+    let drive = TempDir::new().unwrap();
+    let db_path = library.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let mut resolver = PathResolver::from_database_path(&db_path).unwrap();
+    let drive_root = drive.path().canonicalize().unwrap();
+    let root_id = database.register_root(drive_root.to_str().unwrap()).unwrap();
+    resolver.add_root(root_id, &drive_root);
+
+    let config = Config {
+        video_extensions: vec!["mkv".to_string()],
+        min_file_size_mb: 0,
+        root_overrides: vec![
+            RootOverride {
+                path: library.path().to_string_lossy().to_string(),
+                video_extensions: Some(vec!["mp4".to_string()]),
+                min_file_size_mb: None,
+            },
+            RootOverride {
+                path: drive_root.to_string_lossy().to_string(),
+                video_extensions: Some(vec!["mts".to_string()]),
+                min_file_size_mb: None,
+            },
+        ],
+        ..Default::default()
+    };
+    for folder in [library.path(), drive.path()] {
+        for name in ["a.mkv", "b.mp4", "c.mts"] {
+            fs::write(folder.join(name), b"").unwrap();
+        }
+    }
+
+    let root = resolver.get_root_dir().to_path_buf();
+    let report = rescan(&root, library_rules(&config, &root, &resolver), &database, &resolver, &AtomicBool::new(false), |_| {});
+    let mut imported: Vec<_> = report.imported_files.iter().map(|path| path.file_name().unwrap().to_owned()).collect();
+    imported.sort();
+    assert_eq!(imported, vec!["b.mp4", "c.mts"], "Each root is walked with its own extensions");
+    assert!(report.imported_files.contains(&root.join("b.mp4")));
+    assert!(report.imported_files.contains(&drive_root.join("c.mts")));
+}
+
+#[test]
+fn test_scan_report_lines_list_each_section() {
+    let report = ScanReport {
+        root: "/videos".into(),
+        scanned: 12,
+        imported: 1,
+        imported_files: vec!["/videos/Show/S01E02.mkv".into()],
+        skipped: vec![
+            SkippedFile { path: "/videos/extras/blooper.mkv".into(), reason: SkipReason::Excluded },
+            SkippedFile { path: "/videos/locked".into(), reason: SkipReason::Unreadable("Permission denied".to_string()) },
+        ],
+        missing_files: vec!["/videos/Show/S01E01.mkv".into()],
+        ..Default::default()
+    };
+    assert_eq!(
+        report.lines(),
+        vec![
+            "Rescan complete. Found 1 new videos. Scanned 12 files",
+            "",
+            "Imported (1)",
+            "  Show/S01E02.mkv",
+            "",
+            "Skipped (2)",
+            "  extras/blooper.mkv  (excluded by scan_exclude)",
+            "  locked  (unreadable: Permission denied)",
+            "",
+            "Missing files (1)",
+            "  Show/S01E01.mkv",
+        ]
+    );
+}
+
+#[test]
+fn test_rescan_schedule_counts_from_the_last_rescan() {
+    let start = Instant::now();
+    let mut schedule = RescanSchedule::new(30, start);
+    assert!(!schedule.is_due(start + Duration::from_secs(29 * 60)));
+    assert!(schedule.is_due(start + Duration::from_secs(30 * 60)));
+
+    schedule.restart(start + Duration::from_secs(30 * 60));
+    assert!(!schedule.is_due(start + Duration::from_secs(45 * 60)));
+
+    let off = RescanSchedule::new(0, start);
+    assert!(!off.is_due(start + Duration::from_secs(24 * 60 * 60)));
+}
+
+#[test]
+fn test_automatic_rescan_leaves_the_screen_alone() {
+    let dir = TempDir::new().unwrap();
+    let database = Database::open(&dir.path().join("videos.sqlite")).unwrap();
+    let mut state = AppState::new(Vec::new());
+    state.automatic_rescan = true;
+    let report = ScanReport { root: "/videos".into(), imported: 2, ..Default::default() };
+    finish_rescan(report, &mut state, &Config::default(), &database);
+    assert_eq!(state.mode, Mode::Browse);
+    assert!(!state.automatic_rescan);
+    assert_eq!(state.scan_report.imported, 2);
+    let messages: Vec<&str> = state.notifications.history().map(|n| n.message.as_str()).collect();
+    assert!(messages.contains(&"Automatic rescan found 2 new videos and 0 missing files"), "{:?}", messages);
+
+    // One the user started opens the report
+    let report = ScanReport { root: "/videos".into(), ..Default::default() };
+    finish_rescan(report, &mut state, &Config { archive_list_command: String::new(), ..Default::default() }, &database);
+    assert_eq!(state.mode, Mode::ScanReport);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_hard_links_to_a_file_found_earlier() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("a.mkv");
+    let second = dir.path().join("b.mkv");
+    let other = dir.path().join("c.mkv");
+    fs::write(&first, b"video").unwrap();
+    fs::hard_link(&first, &second).unwrap();
+    fs::write(&other, b"video").unwrap();
+
+    let mut hard_links = HardLinks::default();
+    assert_eq!(hard_links.earlier_link(&first), None);
+    assert_eq!(hard_links.earlier_link(&second), Some(first.clone()));
+    assert_eq!(hard_links.earlier_link(&other), None);
+    // Seeing the first path again is not a duplicate
+    assert_eq!(hard_links.earlier_link(&first), None);
+    assert_eq!(SkipReason::SameFile(first.clone()).label(), format!("same file as {}", first.display()));
+}
+
+#[test]
+fn test_scan_report_lists_series_from_folder_names() {
+    let proposal = SeriesProposal { episode_id: 3, location: "Show/Season 02/e1.mkv".to_string(), series: "Show".to_string(), season: 2 };
+    let report = ScanReport { root: "/videos".into(), series_proposals: vec![proposal], ..Default::default() };
+    assert_eq!(
+        report.lines()[1..],
+        [
+            "",
+            "Series from folder names (1)",
+            "  Show/Season 02/e1.mkv  → Show, season 2",
+            "Enter puts these episodes in their series, Esc leaves them as they are",
+        ]
+    );
+}
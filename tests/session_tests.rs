@@ -0,0 +1,111 @@
+use movies::app_state::AppState;
+use movies::database::Database;
+use movies::path_resolver::PathResolver;
+use movies::session::{load_session, open_startup_view, save_session, Session, StartupView};
+use movies::util::{Entry, ViewContext};
+use tempfile::TempDir;
+
+#[test]
+fn test_session_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("data").join("session.json");
+    assert_eq!(load_session(&path), None);
+
+    let session = Session {
+        view_context: ViewContext::Season { season_id: 7, series_name: "Andor".to_string(), season_number: 2 },
+        filter: "unwatched".to_string(),
+        current_item: 5,
+        first_entry: 2,
+    };
+    save_session(&session, &path).unwrap();
+    assert_eq!(load_session(&path), Some(session));
+
+    std::fs::write(&path, "not json").unwrap();
+    assert_eq!(load_session(&path), None, "An unreadable session is ignored");
+}
+
+#[test]
+#[serial_test::serial]
+fn test_restore_session() {
+    let dir = TempDir::new().unwrap();
+    let database = Database::open(&dir.path().join("videos.sqlite")).unwrap();
+
+    let mut state = AppState::new(Vec::new());
+    state.search = "kept".to_string();
+    state.current_item = 3;
+    state.first_entry = 1;
+    let saved = Session::from_state(&state);
+    assert_eq!((saved.filter.as_str(), saved.current_item, saved.first_entry), ("kept", 3, 1));
+
+    let mut restored = AppState::new(Vec::new());
+    saved.restore(&mut restored, &database);
+    assert_eq!((restored.search.as_str(), restored.current_item, restored.first_entry), ("kept", 3, 1));
+
+    // A series that is gone leaves the library on screen
+    let mut state = AppState::new(Vec::new());
+    Session {
+        view_context: ViewContext::Series { series_id: 42, series_name: "Gone".to_string() },
+        filter: "x".to_string(),
+        current_item: 2,
+        first_entry: 0,
+    }
+    .restore(&mut state, &database);
+    assert_eq!((state.view_context, state.search.as_str(), state.current_item), (ViewContext::TopLevel, "", 0));
+}
+
+#[test]
+fn test_startup_view_from_config() {
+    assert_eq!(StartupView::from_config("top_level"), Some(StartupView::TopLevel));
+    assert_eq!(StartupView::from_config("Last Session"), Some(StartupView::LastSession));
+    assert_eq!(StartupView::from_config("continue-watching"), Some(StartupView::ContinueWatching));
+    assert_eq!(
+        StartupView::from_config("series: The Office"),
+        Some(StartupView::Series("The Office".to_string()))
+    );
+    assert_eq!(StartupView::from_config("series:"), None);
+    assert_eq!(StartupView::from_config("favorites"), None);
+}
+
+#[test]
+#[serial_test::serial]
+fn test_open_startup_view() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+    for name in ["e1", "e2", "e3", "movie"] {
+        let video = dir.path().join(format!("{}.mkv", name));
+        std::fs::write(&video, b"").unwrap();
+        database.import_episode_relative(video.to_str().unwrap(), name, &resolver).unwrap();
+    }
+    let ids: Vec<usize> = database
+        .get_entries()
+        .unwrap()
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Episode { episode_id, .. } => Some(*episode_id),
+            _ => None,
+        })
+        .collect();
+    let series = database.create_series_and_assign("Andor", ids[0]).unwrap().series.unwrap().id;
+    database.assign_series(series, ids[1]).unwrap();
+    database.assign_series(series, ids[2]).unwrap();
+    database.toggle_watched_status(ids[0]).unwrap();
+    database.record_playback_finished(ids[0]).unwrap();
+
+    // The view of the episode played last, at the next episode not watched yet
+    let mut state = AppState::new(Vec::new());
+    open_startup_view(StartupView::ContinueWatching, &mut state, &database, true);
+    assert_eq!(state.view_context, ViewContext::Series { series_id: series, series_name: "Andor".to_string() });
+    assert!(matches!(state.entries[state.current_item], Entry::Episode { episode_id, .. } if episode_id == ids[1]));
+
+    let mut state = AppState::new(Vec::new());
+    open_startup_view(StartupView::Series("andor".to_string()), &mut state, &database, true);
+    assert_eq!(state.view_context, ViewContext::Series { series_id: series, series_name: "Andor".to_string() });
+
+    // A series that doesn't exist leaves the library on screen and says so
+    let mut state = AppState::new(Vec::new());
+    open_startup_view(StartupView::Series("Gone".to_string()), &mut state, &database, true);
+    assert_eq!(state.view_context, ViewContext::TopLevel);
+    assert!(state.notifications.history().last().unwrap().message.contains("'Gone'"));
+}
@@ -0,0 +1,22 @@
+use movies::splash::{get_ascii_art, load_ascii_art};
+use tempfile::TempDir;
+
+#[test]
+fn test_custom_art_is_read_without_blank_lines_around_it() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("splash.txt");
+    std::fs::write(&path, "\n\r\n   MY\r\n  MOVIES  \n\n").unwrap();
+    assert_eq!(load_ascii_art(&path).unwrap(), "   MY\r\n  MOVIES");
+}
+
+#[test]
+fn test_missing_or_empty_art_is_reported() {
+    let dir = TempDir::new().unwrap();
+    let missing = load_ascii_art(&dir.path().join("missing.txt")).unwrap_err();
+    assert!(missing.contains("can't be read"), "{}", missing);
+
+    let path = dir.path().join("empty.txt");
+    std::fs::write(&path, " \n\n").unwrap();
+    assert!(load_ascii_art(&path).unwrap_err().contains("is empty"));
+    assert!(!get_ascii_art().is_empty());
+}
@@ -0,0 +1,267 @@
+use movies::app_state::AppState;
+use movies::database::Database;
+use movies::handlers::finish_sync;
+use movies::notifications::NotificationLevel;
+use movies::sync::SyncReport;
+use movies::util::AppEvent;
+use std::time::Duration;
+
+#[test]
+fn test_sync_report_summary() {
+    let report = SyncReport {
+        pushed: 4,
+        pulled: 2,
+        unmatched: 1,
+    };
+
+    assert_eq!(
+        report.summary("Trakt"),
+        "Trakt sync complete: 4 watched sent, 2 marked watched locally, 1 remote items unmatched"
+    );
+}
+
+#[test]
+fn test_sync_runs_in_the_background_and_reports_to_the_main_loop() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let (release, wait) = std::sync::mpsc::channel::<()>();
+    assert!(movies::sync::spawn(tx.clone(), move |progress| {
+        progress("Syncing with Trakt...");
+        wait.recv().unwrap();
+        Err("Trakt sync failed: offline".to_string())
+    }));
+    assert!(!movies::sync::spawn(tx, |_| Ok(String::new())), "Only one sync runs at a time");
+
+    let progress = match rx.recv_timeout(Duration::from_secs(5)).unwrap() {
+        AppEvent::SyncProgress(line) => line,
+        other => panic!("Unexpected event {:?}", other),
+    };
+    assert_eq!(progress, "Syncing with Trakt...");
+    release.send(()).unwrap();
+    let result = match rx.recv_timeout(Duration::from_secs(5)).unwrap() {
+        AppEvent::SyncFinished(result) => result,
+        other => panic!("Unexpected event {:?}", other),
+    };
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let database = Database::open(&dir.path().join("videos.sqlite")).unwrap();
+    let mut state = AppState::new(Vec::new());
+    state.sync_status = Some(progress);
+    finish_sync(result, &mut state, &database);
+    assert_eq!(state.sync_status, None);
+    let last = state.notifications.history().last().unwrap();
+    assert_eq!((last.level, last.message.as_str()), (NotificationLevel::Error, "Trakt sync failed: offline"));
+}
+
+#[cfg(feature = "trakt")]
+mod trakt {
+    use movies::export::LibraryRecord;
+    use movies::sync::trakt::{build_history_payload, match_remote_watched};
+    use serde_json::json;
+
+    fn record(location: &str, title: &str, series: Option<(&str, usize, i64)>, watched: bool) -> LibraryRecord {
+        LibraryRecord {
+            location: location.to_string(),
+            title: title.to_string(),
+            year: Some(1995),
+            watched,
+            length: None,
+            series: series.map(|(name, _, _)| name.to_string()),
+            season: series.map(|(_, season, _)| season),
+            episode_number: series.map(|(_, _, episode)| episode),
+            last_watched_time: None,
+            last_progress_time: None,
+            root: None,
+        }
+    }
+
+    #[test]
+    fn test_history_payload_groups_episodes_by_show() {
+        let records = vec![
+            record("Heat.mkv", "Heat", None, true),
+            record("Show/s1e1.mkv", "Pilot", Some(("Show", 1, 1)), true),
+            record("Show/s1e2.mkv", "Second", Some(("Show", 1, 2)), true),
+            record("Show/s1e3.mkv", "Third", Some(("Show", 1, 3)), false),
+        ];
+
+        let payload = build_history_payload(&records, None);
+
+        assert_eq!(payload["movies"].as_array().unwrap().len(), 1);
+        assert_eq!(payload["movies"][0]["title"], "Heat");
+        assert_eq!(payload["shows"][0]["title"], "Show");
+        assert_eq!(payload["shows"][0]["seasons"][0]["episodes"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_second_push_sends_only_newer_plays() {
+        let mut heat = record("Heat.mkv", "Heat", None, true);
+        heat.last_watched_time = Some("2024-01-01T20:00:00+00:00".to_string());
+        let mut pilot = record("Show/s1e1.mkv", "Pilot", Some(("Show", 1, 1)), true);
+        pilot.last_watched_time = Some("2024-01-02T20:00:00+00:00".to_string());
+        let mut records = vec![heat, pilot, record("Ronin.mkv", "Ronin", None, true)];
+
+        let first = build_history_payload(&records, None);
+        assert_eq!(first["movies"].as_array().unwrap().len(), 2);
+        assert_eq!(first["shows"].as_array().unwrap().len(), 1);
+
+        let last_push = "2024-01-03T00:00:00Z".parse().unwrap();
+        let second = build_history_payload(&records, Some(last_push));
+        assert!(second["movies"].as_array().unwrap().is_empty());
+        assert!(second["shows"].as_array().unwrap().is_empty());
+
+        records[0].last_watched_time = Some("2024-01-04T20:00:00+00:00".to_string());
+        let third = build_history_payload(&records, Some(last_push));
+        assert_eq!(third["movies"].as_array().unwrap().len(), 1);
+        assert_eq!(third["movies"][0]["title"], "Heat");
+    }
+
+    #[test]
+    fn test_match_remote_watched() {
+        let records = vec![
+            record("Heat.mkv", "Heat", None, false),
+            record("Show/s1e2.mkv", "Second", Some(("Show", 1, 2)), false),
+        ];
+        let movies = json!([
+            { "last_watched_at": "2024-01-01T00:00:00.000Z", "movie": { "title": "heat", "year": 1995 } },
+            { "movie": { "title": "Ronin", "year": 1998 } }
+        ]);
+        let shows = json!([
+            { "show": { "title": "Show" }, "seasons": [{ "number": 1, "episodes": [{ "number": 2 }] }] }
+        ]);
+
+        let (matched, unmatched) = match_remote_watched(&records, &movies, &shows);
+
+        assert_eq!(unmatched, 1);
+        assert_eq!(matched.len(), 2);
+        assert_eq!(matched[0], ("Heat.mkv".to_string(), Some("2024-01-01T00:00:00.000Z".to_string())));
+        assert_eq!(matched[1].0, "Show/s1e2.mkv");
+    }
+}
+
+#[cfg(feature = "jellyfin")]
+mod jellyfin {
+    use movies::export::LibraryRecord;
+    use movies::sync::jellyfin::{match_items, parse_items, plan_sync, JellyfinItem, SyncChange, SyncPlan};
+    use serde_json::json;
+
+    fn record(location: &str, title: &str, watched: bool, progress: Option<i64>) -> LibraryRecord {
+        LibraryRecord {
+            location: location.to_string(),
+            title: title.to_string(),
+            year: Some(1995),
+            watched,
+            length: None,
+            series: None,
+            season: None,
+            episode_number: None,
+            last_watched_time: None,
+            last_progress_time: progress,
+            root: None,
+        }
+    }
+
+    fn item(id: &str, path: &str, played: bool, position_seconds: u64) -> JellyfinItem {
+        JellyfinItem {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: Some(path.to_string()),
+            played,
+            position_seconds,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_items() {
+        let response = json!({ "Items": [
+            {
+                "Id": "a1", "Name": "Pilot", "Path": "/media/tv/Show/S01E01.mkv", "SeriesName": "Show",
+                "ParentIndexNumber": 1, "IndexNumber": 1, "ProductionYear": 2008,
+                "UserData": { "Played": false, "PlaybackPositionTicks": 6_000_000_000u64 }
+            },
+            { "Id": "b2", "Name": "Heat", "UserData": { "Played": true, "LastPlayedDate": "2024-01-01T00:00:00Z" } },
+            { "Name": "No id" }
+        ]});
+
+        let items = parse_items(&response);
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].series.as_deref(), Some("Show"));
+        assert_eq!((items[0].season, items[0].episode_number), (Some(1), Some(1)));
+        assert_eq!(items[0].position_seconds, 600);
+        assert!(items[1].played && items[1].path.is_none());
+        assert_eq!(items[1].last_played.as_deref(), Some("2024-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_match_items_by_path_then_by_details() {
+        let mut episode = record("Show/Season 1/S01E02.mkv", "Second", false, None);
+        episode.series = Some("Show".to_string());
+        episode.season = Some(1);
+        episode.episode_number = Some(2);
+        let records = vec![record("Movies/Heat.mkv", "Heat", false, None), episode];
+        let items = vec![
+            item("heat", "D:\\Media\\movies\\heat.mkv", false, 0),
+            JellyfinItem {
+                series: Some("show".to_string()),
+                season: Some(1),
+                episode_number: Some(2),
+                ..item("second", "/srv/other/name.mkv", false, 0)
+            },
+            item("ronin", "/srv/Movies/Ronin.mkv", false, 0),
+        ];
+
+        let (pairs, unmatched) = match_items(&records, &items);
+
+        assert_eq!(unmatched, 1);
+        let ids: Vec<(&str, &str)> = pairs.iter().map(|(record, item)| (record.title.as_str(), item.id.as_str())).collect();
+        assert_eq!(ids, vec![("Heat", "heat"), ("Second", "second")]);
+    }
+
+    #[test]
+    fn test_plan_sync_both_ways() {
+        let records = vec![
+            record("a.mkv", "Watched here", true, None),
+            record("b.mkv", "Watched there", false, Some(300)),
+            record("c.mkv", "Further there", false, Some(60)),
+            record("d.mkv", "Further here", false, Some(1200)),
+            record("e.mkv", "About the same", false, Some(100)),
+        ];
+        let items = vec![
+            item("a", "/srv/a.mkv", false, 0),
+            JellyfinItem { last_played: Some("2024-01-01T00:00:00Z".to_string()), ..item("b", "/srv/b.mkv", true, 0) },
+            item("c", "/srv/c.mkv", false, 900),
+            item("d", "/srv/d.mkv", false, 30),
+            item("e", "/srv/e.mkv", false, 105),
+        ];
+
+        let plan = plan_sync(&records, &items);
+
+        assert_eq!(
+            plan.changes,
+            vec![
+                SyncChange::PlayedRemotely { item_id: "a".to_string(), title: "Watched here".to_string() },
+                SyncChange::WatchedLocally {
+                    location: "b.mkv".to_string(),
+                    title: "Watched there".to_string(),
+                    watched_at: Some("2024-01-01T00:00:00Z".to_string()),
+                },
+                SyncChange::PositionLocally { location: "c.mkv".to_string(), title: "Further there".to_string(), seconds: 900 },
+                SyncChange::PositionRemotely { item_id: "d".to_string(), title: "Further here".to_string(), seconds: 1200 },
+            ]
+        );
+        assert_eq!(plan.changes[2].describe(), "Further there: resume here at 0:15:00");
+    }
+
+    #[test]
+    fn test_sync_plan_summary() {
+        let plan = SyncPlan {
+            changes: vec![SyncChange::PlayedRemotely { item_id: "a".to_string(), title: "A".to_string() }],
+            unmatched: 2,
+        };
+        assert_eq!(
+            plan.summary(false),
+            "Jellyfin sync complete: 1 marked played on Jellyfin, 0 marked watched locally, 0 positions updated, 2 server items unmatched"
+        );
+        assert!(plan.summary(true).starts_with("Jellyfin sync preview: 1 to mark played on Jellyfin"));
+    }
+}
@@ -0,0 +1,94 @@
+use movies::tabs::{Tab, Tabs, MAX_TABS};
+use movies::util::{SortMode, ViewContext};
+
+fn series_tab(series_id: usize, name: &str) -> Tab {
+    Tab {
+        view_context: ViewContext::Series {
+            series_id,
+            series_name: name.to_string(),
+        },
+        filter: String::new(),
+        sort_mode: SortMode::Default,
+        current_item: 0,
+        first_entry: 0,
+    }
+}
+
+#[test]
+fn test_tabs_pin_and_label_views() {
+    let mut tabs = Tabs::default();
+    assert_eq!(tabs.label(), "");
+
+    assert_eq!(tabs.pin(series_tab(1, "Lost")), Ok(1));
+    let unwatched = Tab {
+        view_context: ViewContext::TopLevel,
+        filter: "unwatched ".to_string(),
+        sort_mode: SortMode::RecentlyWatched,
+        current_item: 0,
+        first_entry: 0,
+    };
+    assert_eq!(tabs.pin(unwatched), Ok(2));
+    assert_eq!(tabs.label(), "[1] Lost  [2]*Library \"unwatched\"");
+
+    // The same view and filter can only be pinned once
+    assert!(tabs.pin(series_tab(1, "Lost")).is_err());
+
+    let season = Tab {
+        view_context: ViewContext::Season {
+            season_id: 7,
+            series_name: "Lost".to_string(),
+            season_number: 2,
+        },
+        ..series_tab(1, "Lost")
+    };
+    assert_eq!(season.label(), "Lost S2");
+}
+
+#[test]
+fn test_switching_tabs_remembers_the_selection() {
+    let mut tabs = Tabs::default();
+    tabs.pin(series_tab(1, "Lost")).unwrap();
+    tabs.pin(series_tab(2, "Fargo")).unwrap();
+
+    // Leaving Fargo on its fifth episode and coming back returns there
+    let left = Tab {
+        current_item: 4,
+        first_entry: 2,
+        ..series_tab(2, "Fargo")
+    };
+    assert_eq!(tabs.switch_to(1, left).unwrap(), series_tab(1, "Lost"));
+    let back = tabs.switch_to(2, series_tab(1, "Lost")).unwrap();
+    assert_eq!((back.current_item, back.first_entry), (4, 2));
+
+    assert!(tabs.switch_to(3, series_tab(2, "Fargo")).is_none());
+    assert!(tabs.switch_to(0, series_tab(2, "Fargo")).is_none());
+}
+
+#[test]
+fn test_leaving_a_tab_view_deactivates_it() {
+    let mut tabs = Tabs::default();
+    tabs.pin(series_tab(1, "Lost")).unwrap();
+    tabs.follow(&series_tab(1, "Lost").view_context);
+    assert_eq!(tabs.label(), "[1]*Lost");
+
+    tabs.follow(&ViewContext::TopLevel);
+    assert_eq!(tabs.label(), "[1] Lost");
+    assert_eq!(tabs.close_active(), None);
+
+    // Switching to a tab while none is active leaves the other tabs alone
+    tabs.pin(series_tab(2, "Fargo")).unwrap();
+    tabs.follow(&ViewContext::TopLevel);
+    tabs.switch_to(1, series_tab(9, "Elsewhere")).unwrap();
+    assert_eq!(tabs.label(), "[1]*Lost  [2] Fargo");
+    assert_eq!(tabs.close_active(), Some(1));
+    assert_eq!(tabs.label(), "[1] Fargo");
+}
+
+#[test]
+fn test_tabs_are_limited() {
+    let mut tabs = Tabs::default();
+    for series_id in 0..MAX_TABS {
+        tabs.pin(series_tab(series_id, "Show")).unwrap();
+    }
+    assert!(tabs.pin(series_tab(MAX_TABS, "Show")).is_err());
+}
@@ -0,0 +1,60 @@
+use movies::text_input::TextInput;
+
+/// Run `edit` on `text` with the cursor at `cursor`, returning the text and where the cursor ended up
+fn edited(text: &str, cursor: usize, edit: impl FnOnce(&mut TextInput)) -> (String, usize) {
+    let (mut text, mut cursor) = (text.to_string(), cursor);
+    edit(&mut TextInput::new(&mut text, &mut cursor));
+    (text, cursor)
+}
+
+#[test]
+fn test_typing_and_removing_characters() {
+    assert_eq!(edited("Alen", 2, |input| input.insert('i')), ("Alien".to_string(), 3));
+    assert_eq!(edited("Alien", 5, |input| assert!(input.backspace())), ("Alie".to_string(), 4));
+    assert_eq!(edited("Alien", 0, |input| assert!(input.delete())), ("lien".to_string(), 0));
+    assert_eq!(edited("Alien", 0, |input| assert!(!input.backspace())), ("Alien".to_string(), 0));
+    assert_eq!(edited("Alien", 5, |input| assert!(!input.delete())), ("Alien".to_string(), 5));
+    // Typing works in text with multi-byte characters before the cursor
+    assert_eq!(edited("進撃巨人", 2, |input| input.insert('の')), ("進撃の巨人".to_string(), 3));
+}
+
+#[test]
+fn test_cursor_past_the_end_is_moved_back() {
+    assert_eq!(edited("Up", 10, |input| input.insert('!')), ("Up!".to_string(), 3));
+}
+
+#[test]
+fn test_clusters_are_moved_over_and_removed_whole() {
+    let accented = "Poke\u{301}mon";
+    assert_eq!(edited(accented, 3, |input| input.right()).1, 5);
+    assert_eq!(edited(accented, 5, |input| input.left()).1, 3);
+    assert_eq!(edited(accented, 5, |input| assert!(input.backspace())), ("Pokmon".to_string(), 3));
+    assert_eq!(edited(accented, 3, |input| assert!(input.delete())), ("Pokmon".to_string(), 3));
+
+    let family = "👨\u{200D}👩\u{200D}👧";
+    assert_eq!(edited(&format!("a{}b", family), 1, |input| input.right()).1, 6);
+    assert_eq!(edited(&format!("a{}b", family), 6, |input| assert!(input.backspace())), ("ab".to_string(), 1));
+}
+
+#[test]
+fn test_home_and_end() {
+    assert_eq!(edited("進撃の巨人", 2, |input| input.end()).1, 5);
+    assert_eq!(edited("進撃の巨人", 2, |input| input.home()).1, 0);
+}
+
+#[test]
+fn test_word_jumps() {
+    let title = "The  Wire season";
+    assert_eq!(edited(title, 0, |input| input.word_right()).1, 5);
+    assert_eq!(edited(title, 5, |input| input.word_right()).1, 10);
+    assert_eq!(edited(title, 10, |input| input.word_right()).1, 16);
+    assert_eq!(edited(title, 16, |input| input.word_right()).1, 16);
+    assert_eq!(edited(title, 16, |input| input.word_left()).1, 10);
+    assert_eq!(edited(title, 10, |input| input.word_left()).1, 5);
+    assert_eq!(edited(title, 7, |input| input.word_left()).1, 5);
+    assert_eq!(edited(title, 5, |input| input.word_left()).1, 0);
+    assert_eq!(edited(title, 0, |input| input.word_left()).1, 0);
+    // A word ending in an accent is jumped over whole
+    assert_eq!(edited("Poke\u{301} mon", 0, |input| input.word_right()).1, 6);
+    assert_eq!(edited("Poke\u{301} mon", 5, |input| input.word_left()).1, 0);
+}
@@ -0,0 +1,62 @@
+use movies::text::{
+    byte_offset, column_at, display_width, next_boundary, prev_boundary, remove_chars, truncate_to_width,
+};
+
+#[test]
+fn test_display_width_counts_wide_and_zero_width_characters() {
+    assert_eq!(display_width("Pilot"), 5);
+    assert_eq!(display_width("進撃の巨人"), 10);
+    assert_eq!(display_width("🎬 Movie"), 8);
+    // e followed by a combining acute accent
+    assert_eq!(display_width("Pok\u{e9}mon"), 7);
+    assert_eq!(display_width("Poke\u{301}mon"), 7);
+}
+
+#[test]
+fn test_truncate_to_width_never_splits_a_wide_character() {
+    assert_eq!(truncate_to_width("進撃の巨人", 5), "進撃");
+    assert_eq!(truncate_to_width("進撃の巨人", 6), "進撃の");
+    assert_eq!(truncate_to_width("ab🎬cd", 3), "ab");
+    assert_eq!(truncate_to_width("Pilot", 10), "Pilot");
+    assert_eq!(truncate_to_width("Pilot", 0), "");
+}
+
+#[test]
+fn test_truncate_to_width_keeps_clusters_together() {
+    // The accent stays with its letter
+    assert_eq!(truncate_to_width("Poke\u{301}mon", 4), "Poke\u{301}");
+    // A family emoji is joined with zero-width joiners and is cut as one
+    let family = "👨\u{200D}👩\u{200D}👧";
+    assert_eq!(truncate_to_width(&format!("{}x", family), 2), "");
+    assert_eq!(truncate_to_width(&format!("{}x", family), 6), family);
+}
+
+#[test]
+fn test_boundaries_step_over_whole_clusters() {
+    let s = "ae\u{301}b";
+    assert_eq!(next_boundary(s, 0), 1);
+    assert_eq!(next_boundary(s, 1), 3);
+    assert_eq!(next_boundary(s, 3), 4);
+    assert_eq!(next_boundary(s, 4), 4);
+    assert_eq!(prev_boundary(s, 4), 3);
+    assert_eq!(prev_boundary(s, 3), 1);
+    assert_eq!(prev_boundary(s, 0), 0);
+}
+
+#[test]
+fn test_column_at_and_byte_offset_convert_char_indices() {
+    let s = "進撃 x";
+    assert_eq!(column_at(s, 0), 0);
+    assert_eq!(column_at(s, 1), 2);
+    assert_eq!(column_at(s, 3), 5);
+    assert_eq!(byte_offset(s, 1), 3);
+    assert_eq!(byte_offset(s, 3), 7);
+    assert_eq!(byte_offset(s, 10), s.len());
+}
+
+#[test]
+fn test_remove_chars_edits_multibyte_text() {
+    let mut s = "進撃の巨人".to_string();
+    remove_chars(&mut s, 1, 3);
+    assert_eq!(s, "進巨人");
+}
@@ -203,4 +203,150 @@ fn test_yaml_generation_includes_comments() {
     assert!(yaml_content.contains("current_bg: White"));
     assert!(yaml_content.contains("watched_indicator: \"●\""));
     assert!(yaml_content.contains("scrollbar_track_char: \"│\""));
+    assert!(yaml_content.contains("progress_display: count"));
+    assert!(yaml_content.contains("progress_filled_char: \"▓\""));
+}
+
+/// Test that only THEME-*.yaml files are offered by the theme picker
+#[test]
+fn test_list_theme_files() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("THEME-solarized.yaml"), "").unwrap();
+    fs::write(temp_dir.path().join("THEME-default.yaml"), "").unwrap();
+    fs::write(temp_dir.path().join("config.yaml"), "").unwrap();
+    fs::write(temp_dir.path().join("THEME-notes.txt"), "").unwrap();
+
+    assert_eq!(
+        list_theme_files(temp_dir.path()),
+        vec!["THEME-default.yaml".to_string(), "THEME-solarized.yaml".to_string()]
+    );
+}
+
+/// Test that the built-in themes are installed once, load back as they were, and keep edits
+#[test]
+fn test_install_builtin_themes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let edited = temp_dir.path().join("THEME-dark.yaml");
+    fs::write(&edited, "current_bg: Red\n").unwrap();
+
+    let installed = install_builtin_themes(temp_dir.path()).unwrap();
+    assert_eq!(
+        installed,
+        vec!["THEME-light.yaml", "THEME-solarized.yaml", "THEME-high-contrast.yaml", "THEME-monochrome.yaml"]
+    );
+    assert_eq!(fs::read_to_string(&edited).unwrap(), "current_bg: Red\n");
+    assert_eq!(list_theme_files(temp_dir.path()).len(), 5);
+    assert!(install_builtin_themes(temp_dir.path()).unwrap().is_empty());
+
+    for (name, theme) in builtin_themes() {
+        assert!(theme.invalid_colors().is_empty(), "{} has colors that can't be read", name);
+        if name == "dark" {
+            continue;
+        }
+        let loaded = load_theme(&temp_dir.path().join(format!("THEME-{}.yaml", name)));
+        assert_eq!(serde_yaml::to_string(&loaded).unwrap(), serde_yaml::to_string(&theme).unwrap(), "{}", name);
+    }
+}
+
+/// Test named, hex and 256-color values
+#[test]
+fn test_string_to_color_formats() {
+    use crossterm::style::Color;
+
+    assert_eq!(string_to_color("DarkGray"), Some(Color::DarkGrey));
+    assert_eq!(string_to_color("#3A3a5c"), Some(Color::Rgb { r: 0x3a, g: 0x3a, b: 0x5c }));
+    assert_eq!(string_to_color("ansi(244)"), Some(Color::AnsiValue(244)));
+    assert_eq!(string_to_color(" ANSI( 16 ) "), Some(Color::AnsiValue(16)));
+
+    assert_eq!(string_to_color("#3a3a5"), None);
+    assert_eq!(string_to_color("#gg0000"), None);
+    assert_eq!(string_to_color("ansi(256)"), None);
+    assert_eq!(string_to_color("Gray"), None);
+}
+
+/// Test that unparseable color values are reported by field
+#[test]
+fn test_invalid_colors() {
+    let mut theme = Theme::default();
+    assert!(theme.invalid_colors().is_empty());
+
+    theme.current_bg = "#3a3a5c".to_string();
+    theme.count_fg = "Gray".to_string();
+    theme.header_fg = "ansi(300)".to_string();
+    assert_eq!(theme.invalid_colors(), vec![("count_fg", "Gray"), ("header_fg", "ansi(300)")]);
+}
+
+/// Test that a theme only needs the keys it changes from the theme it extends
+#[test]
+fn test_load_theme_with_extends() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(
+        temp_dir.path().join("THEME-base.yaml"),
+        "current_fg: Red\ncurrent_bg: Blue\nwatched_indicator: \"★\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("THEME-middle.yaml"),
+        "extends: THEME-base.yaml\ncurrent_bg: Green\n",
+    )
+    .unwrap();
+    let theme_path = temp_dir.path().join("THEME-top.yaml");
+    fs::write(&theme_path, "extends: THEME-middle.yaml\nstatus_fg: Yellow\n").unwrap();
+
+    let loaded_theme = load_theme(&theme_path);
+    assert_eq!(loaded_theme.current_fg, "Red", "Inherited through two levels");
+    assert_eq!(loaded_theme.current_bg, "Green", "Overridden by the middle theme");
+    assert_eq!(loaded_theme.status_fg, "Yellow");
+    assert_eq!(loaded_theme.watched_indicator, "★");
+    assert_eq!(loaded_theme.series_fg, Theme::default().series_fg, "Keys set nowhere use the defaults");
+}
+
+/// Test that themes extending each other still load
+#[test]
+fn test_load_theme_with_extends_cycle() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("THEME-a.yaml"), "extends: THEME-b.yaml\ncurrent_fg: Red\n").unwrap();
+    fs::write(temp_dir.path().join("THEME-b.yaml"), "extends: THEME-a.yaml\ncurrent_bg: Blue\n").unwrap();
+    let missing_base = temp_dir.path().join("THEME-c.yaml");
+    fs::write(&missing_base, "extends: THEME-missing.yaml\ncurrent_fg: Cyan\n").unwrap();
+
+    let loaded_theme = load_theme(&temp_dir.path().join("THEME-a.yaml"));
+    assert_eq!(loaded_theme.current_fg, "Red");
+    assert_eq!(loaded_theme.current_bg, "Blue");
+
+    let loaded_theme = load_theme(&missing_base);
+    assert_eq!(loaded_theme.current_fg, "Cyan", "A missing base theme is skipped");
+    assert!(!temp_dir.path().join("THEME-missing.yaml").exists(), "Missing base themes are not created");
+}
+
+/// Test the border sets a theme can name
+#[test]
+fn test_border_styles() {
+    let mut theme = Theme::default();
+    assert_eq!(theme.border_chars(), BorderChars::SINGLE);
+    assert_eq!(theme.active_border_chars(), BorderChars::DOUBLE);
+
+    theme.border_style = "Rounded".to_string();
+    theme.active_border_style = "ascii".to_string();
+    assert_eq!(theme.border_chars().top_left, '╭');
+    assert_eq!(theme.active_border_chars(), BorderChars::ASCII);
+
+    // Unknown names fall back to the default sets
+    theme.active_border_style = "heavy".to_string();
+    assert_eq!(theme.active_border_chars(), BorderChars::DOUBLE);
+}
+
+/// Test the accessible variant of a theme uses ASCII and keeps the selection colors
+#[test]
+fn test_make_accessible() {
+    let mut theme = Theme::default();
+    theme.make_accessible();
+
+    assert_eq!(theme.border_chars(), BorderChars::ASCII);
+    assert_eq!(theme.active_border_chars(), BorderChars::ASCII);
+    assert_eq!(theme.watched_indicator, "[WATCHED]");
+    assert_eq!(theme.unwatched_indicator, "[UNWATCHED]");
+    assert!(theme.scrollbar_track_char.is_ascii() && theme.progress_filled_char.is_ascii());
+    assert_eq!(theme.series_fg, "Reset");
+    assert_eq!(theme.current_fg, Theme::default().current_fg, "The selection stays visible");
 }
@@ -1,107 +1,80 @@
 use crossterm::event::KeyCode;
+use movies::app_state::AppState;
 use movies::handlers::handle_torrent_search_input;
 use movies::torrent_search::TorrentResult;
 use movies::util::Mode;
 
+/// State in the torrent search input, with nothing drawn yet
+fn search_input_state(search_query: &str) -> AppState {
+    AppState {
+        mode: Mode::TorrentSearchInput,
+        search_query: search_query.to_string(),
+        redraw: false,
+        ..AppState::new(Vec::new())
+    }
+}
+
+/// State in the torrent search results with `selected_torrent_result` selected
+fn search_results_state(torrent_results: Vec<TorrentResult>, selected_torrent_result: usize) -> AppState {
+    AppState {
+        mode: Mode::TorrentSearchResults,
+        torrent_results,
+        selected_torrent_result,
+        redraw: false,
+        ..AppState::new(Vec::new())
+    }
+}
+
 #[test]
 fn test_handle_torrent_search_input_character_input() {
-    let mut mode = Mode::TorrentSearchInput;
-    let mut search_query = String::new();
-    let mut torrent_results: Vec<TorrentResult> = Vec::new();
-    let mut selected_torrent_result = 0;
-    let mut redraw = false;
+    let mut state = search_input_state("");
 
     // Test character input
-    handle_torrent_search_input(
-        KeyCode::Char('t'),
-        &mut mode,
-        &mut search_query,
-        &mut torrent_results,
-        &mut selected_torrent_result,
-        &mut redraw,
-    );
-
-    assert_eq!(search_query, "t");
-    assert!(redraw);
-    assert_eq!(mode, Mode::TorrentSearchInput);
+    handle_torrent_search_input(KeyCode::Char('t'), &mut state);
+
+    assert_eq!(state.search_query, "t");
+    assert!(state.redraw);
+    assert_eq!(state.mode, Mode::TorrentSearchInput);
 }
 
 #[test]
 fn test_handle_torrent_search_input_backspace() {
-    let mut mode = Mode::TorrentSearchInput;
-    let mut search_query = String::from("test");
-    let mut torrent_results: Vec<TorrentResult> = Vec::new();
-    let mut selected_torrent_result = 0;
-    let mut redraw = false;
+    let mut state = search_input_state("test");
 
     // Test backspace
-    handle_torrent_search_input(
-        KeyCode::Backspace,
-        &mut mode,
-        &mut search_query,
-        &mut torrent_results,
-        &mut selected_torrent_result,
-        &mut redraw,
-    );
-
-    assert_eq!(search_query, "tes");
-    assert!(redraw);
-    assert_eq!(mode, Mode::TorrentSearchInput);
+    handle_torrent_search_input(KeyCode::Backspace, &mut state);
+
+    assert_eq!(state.search_query, "tes");
+    assert!(state.redraw);
+    assert_eq!(state.mode, Mode::TorrentSearchInput);
 }
 
 #[test]
 fn test_handle_torrent_search_input_escape() {
-    let mut mode = Mode::TorrentSearchInput;
-    let mut search_query = String::from("test");
-    let mut torrent_results: Vec<TorrentResult> = Vec::new();
-    let mut selected_torrent_result = 0;
-    let mut redraw = false;
+    let mut state = search_input_state("test");
 
     // Test escape
-    handle_torrent_search_input(
-        KeyCode::Esc,
-        &mut mode,
-        &mut search_query,
-        &mut torrent_results,
-        &mut selected_torrent_result,
-        &mut redraw,
-    );
-
-    assert_eq!(mode, Mode::Browse);
-    assert!(redraw);
+    handle_torrent_search_input(KeyCode::Esc, &mut state);
+
+    assert_eq!(state.mode, Mode::Browse);
+    assert!(state.redraw);
 }
 
 #[test]
 fn test_handle_torrent_search_input_enter_empty_query() {
-    let mut mode = Mode::TorrentSearchInput;
-    let mut search_query = String::new();
-    let mut torrent_results: Vec<TorrentResult> = Vec::new();
-    let mut selected_torrent_result = 0;
-    let mut redraw = false;
+    let mut state = search_input_state("");
 
     // Test enter with empty query (should do nothing)
-    handle_torrent_search_input(
-        KeyCode::Enter,
-        &mut mode,
-        &mut search_query,
-        &mut torrent_results,
-        &mut selected_torrent_result,
-        &mut redraw,
-    );
-
-    assert_eq!(mode, Mode::TorrentSearchInput);
-    assert!(!redraw);
+    handle_torrent_search_input(KeyCode::Enter, &mut state);
+
+    assert_eq!(state.mode, Mode::TorrentSearchInput);
+    assert!(!state.redraw);
 }
 
 #[test]
 fn test_handle_torrent_search_results_navigation_up() {
     use movies::handlers::handle_torrent_search_results;
-    
-    let mut mode = Mode::TorrentSearchResults;
-    let mut selected_result = 2;
-    let mut status_message = String::new();
-    let mut redraw = false;
-    
+
     let torrent_results = vec![
         TorrentResult {
             name: "Movie 1".to_string(),
@@ -128,30 +101,19 @@ fn test_handle_torrent_search_results_navigation_up() {
             magnet_link: "magnet:?xt=urn:btih:3".to_string(),
         },
     ];
-    
-    handle_torrent_search_results(
-        KeyCode::Up,
-        &mut mode,
-        &torrent_results,
-        &mut selected_result,
-        &mut status_message,
-        &mut redraw,
-    );
-    
-    assert_eq!(selected_result, 1);
-    assert!(redraw);
-    assert_eq!(mode, Mode::TorrentSearchResults);
+    let mut state = search_results_state(torrent_results, 2);
+
+    handle_torrent_search_results(KeyCode::Up, &mut state);
+
+    assert_eq!(state.selected_torrent_result, 1);
+    assert!(state.redraw);
+    assert_eq!(state.mode, Mode::TorrentSearchResults);
 }
 
 #[test]
 fn test_handle_torrent_search_results_navigation_down() {
     use movies::handlers::handle_torrent_search_results;
-    
-    let mut mode = Mode::TorrentSearchResults;
-    let mut selected_result = 0;
-    let mut status_message = String::new();
-    let mut redraw = false;
-    
+
     let torrent_results = vec![
         TorrentResult {
             name: "Movie 1".to_string(),
@@ -170,30 +132,19 @@ fn test_handle_torrent_search_results_navigation_down() {
             magnet_link: "magnet:?xt=urn:btih:2".to_string(),
         },
     ];
-    
-    handle_torrent_search_results(
-        KeyCode::Down,
-        &mut mode,
-        &torrent_results,
-        &mut selected_result,
-        &mut status_message,
-        &mut redraw,
-    );
-    
-    assert_eq!(selected_result, 1);
-    assert!(redraw);
-    assert_eq!(mode, Mode::TorrentSearchResults);
+    let mut state = search_results_state(torrent_results, 0);
+
+    handle_torrent_search_results(KeyCode::Down, &mut state);
+
+    assert_eq!(state.selected_torrent_result, 1);
+    assert!(state.redraw);
+    assert_eq!(state.mode, Mode::TorrentSearchResults);
 }
 
 #[test]
 fn test_handle_torrent_search_results_navigation_boundaries() {
     use movies::handlers::handle_torrent_search_results;
-    
-    let mut mode = Mode::TorrentSearchResults;
-    let mut selected_result = 0;
-    let mut status_message = String::new();
-    let mut redraw = false;
-    
+
     let torrent_results = vec![
         TorrentResult {
             name: "Movie 1".to_string(),
@@ -204,43 +155,25 @@ fn test_handle_torrent_search_results_navigation_boundaries() {
             magnet_link: "magnet:?xt=urn:btih:1".to_string(),
         },
     ];
-    
+    let mut state = search_results_state(torrent_results, 0);
+
     // Try to go up from first item (should do nothing)
-    handle_torrent_search_results(
-        KeyCode::Up,
-        &mut mode,
-        &torrent_results,
-        &mut selected_result,
-        &mut status_message,
-        &mut redraw,
-    );
-    
-    assert_eq!(selected_result, 0);
-    
+    handle_torrent_search_results(KeyCode::Up, &mut state);
+
+    assert_eq!(state.selected_torrent_result, 0);
+
     // Try to go down from last item (should do nothing)
-    redraw = false;
-    handle_torrent_search_results(
-        KeyCode::Down,
-        &mut mode,
-        &torrent_results,
-        &mut selected_result,
-        &mut status_message,
-        &mut redraw,
-    );
-    
-    assert_eq!(selected_result, 0);
-    assert!(!redraw);
+    state.redraw = false;
+    handle_torrent_search_results(KeyCode::Down, &mut state);
+
+    assert_eq!(state.selected_torrent_result, 0);
+    assert!(!state.redraw);
 }
 
 #[test]
 fn test_handle_torrent_search_results_escape() {
     use movies::handlers::handle_torrent_search_results;
-    
-    let mut mode = Mode::TorrentSearchResults;
-    let mut selected_result = 0;
-    let mut status_message = String::new();
-    let mut redraw = false;
-    
+
     let torrent_results = vec![
         TorrentResult {
             name: "Movie 1".to_string(),
@@ -251,16 +184,10 @@ fn test_handle_torrent_search_results_escape() {
             magnet_link: "magnet:?xt=urn:btih:1".to_string(),
         },
     ];
-    
-    handle_torrent_search_results(
-        KeyCode::Esc,
-        &mut mode,
-        &torrent_results,
-        &mut selected_result,
-        &mut status_message,
-        &mut redraw,
-    );
-    
-    assert_eq!(mode, Mode::Browse);
-    assert!(redraw);
+    let mut state = search_results_state(torrent_results, 0);
+
+    handle_torrent_search_results(KeyCode::Esc, &mut state);
+
+    assert_eq!(state.mode, Mode::Browse);
+    assert!(state.redraw);
 }
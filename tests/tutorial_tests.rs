@@ -0,0 +1,31 @@
+use crossterm::event::KeyCode;
+use movies::tutorial::{Tutorial, TutorialKey, TutorialRegion, TutorialStep};
+
+#[test]
+fn test_tutorial_walks_through_every_step() {
+    let mut tutorial = Tutorial::new();
+    assert_eq!(tutorial.progress(), (1, 7));
+
+    // The welcome step keeps Enter from playing a video
+    assert_eq!(tutorial.handle_key(KeyCode::Enter), TutorialKey::Consumed);
+    assert_eq!(tutorial.step(), TutorialStep::Navigate);
+    assert_eq!(tutorial.step().region(), Some(TutorialRegion::Browser));
+
+    // Real actions advance the tour and still reach the app
+    for key in [KeyCode::Down, KeyCode::Char('/'), KeyCode::F(3), KeyCode::F(4), KeyCode::F(1)] {
+        assert_eq!(tutorial.handle_key(key), TutorialKey::Advanced);
+    }
+    assert_eq!(tutorial.step(), TutorialStep::Done);
+    assert_eq!(tutorial.progress(), (7, 7));
+    assert_eq!(tutorial.handle_key(KeyCode::Enter), TutorialKey::Finished);
+}
+
+#[test]
+fn test_tutorial_ignores_other_keys_and_ends_on_escape() {
+    let mut tutorial = Tutorial::new();
+    tutorial.handle_key(KeyCode::Enter);
+
+    assert_eq!(tutorial.handle_key(KeyCode::F(3)), TutorialKey::Ignored);
+    assert_eq!(tutorial.step(), TutorialStep::Navigate);
+    assert_eq!(tutorial.handle_key(KeyCode::Esc), TutorialKey::Finished);
+}
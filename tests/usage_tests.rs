@@ -0,0 +1,43 @@
+use movies::usage::{load_usage, save_usage, UsageStats, TRACKED_ACTIONS};
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn test_top_actions_orders_by_count_then_name() {
+    let mut stats = UsageStats::default();
+    for action in ["play", "play", "play", "edit", "rescan", "rescan"] {
+        stats.record_action(action);
+    }
+
+    assert_eq!(stats.top_actions(2), vec![("play", 3), ("rescan", 2)]);
+    assert_eq!(stats.top_actions(10).len(), 3);
+}
+
+#[test]
+fn test_average_session_and_feature_adoption() {
+    let mut stats = UsageStats::default();
+    assert_eq!(stats.average_session(), None);
+
+    stats.record_session(Duration::from_secs(600));
+    stats.record_session(Duration::from_secs(1200));
+    assert_eq!(stats.average_session(), Some(Duration::from_secs(900)));
+
+    stats.record_action("play");
+    stats.record_action("not_a_feature");
+    assert_eq!(stats.feature_adoption(), (1, TRACKED_ACTIONS.len()));
+    assert!(stats.summary().contains("2 sessions"));
+}
+
+#[test]
+fn test_usage_round_trips_and_tolerates_missing_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join("nested").join("usage.json");
+    assert_eq!(load_usage(&path), UsageStats::default());
+
+    let mut stats = UsageStats::default();
+    stats.record_action("edit");
+    stats.record_session(Duration::from_secs(42));
+    save_usage(&stats, &path).unwrap();
+
+    assert_eq!(load_usage(&path), stats);
+}
@@ -1,5 +1,7 @@
 use movies::config::Config;
-use movies::util::run_video_player_with_resume;
+use movies::dto::AltTitles;
+use movies::util::{apply_preferred_titles, run_video_player_with_resume, sort_entries, Entry, EntryKey, SortKeys, SortMode};
+use std::collections::HashMap;
 use std::path::Path;
 
 #[test]
@@ -118,4 +120,131 @@ fn test_video_player_fallback_resume_parameters() {
             // Expected since unknown_player doesn't exist - this is fine for the test
         }
     }
-}
\ No newline at end of file
+}
+#[test]
+fn test_sort_entries_recently_watched() {
+    let episode = |id: usize, name: &str| Entry::Episode {
+        episode_id: id,
+        name: name.to_string(),
+        location: format!("{}.mkv", name),
+    };
+    let mut entries = vec![
+        Entry::Series { series_id: 1, name: "Show".to_string() },
+        episode(10, "never"),
+        episode(11, "older"),
+        episode(12, "newer"),
+        episode(13, "also never"),
+    ];
+
+    let mut keys = SortKeys::default();
+    keys.last_watched.insert(EntryKey::Series(1), 200);
+    keys.last_watched.insert(EntryKey::Episode(11), 100);
+    keys.last_watched.insert(EntryKey::Episode(12), 300);
+
+    sort_entries(&mut entries, SortMode::Default, &keys);
+    assert_eq!(entries[0].key(), EntryKey::Series(1));
+
+    sort_entries(&mut entries, SortMode::RecentlyWatched, &keys);
+    let order: Vec<EntryKey> = entries.iter().map(|e| e.key()).collect();
+    assert_eq!(
+        order,
+        vec![
+            EntryKey::Episode(12),
+            EntryKey::Series(1),
+            EntryKey::Episode(11),
+            EntryKey::Episode(10),
+            EntryKey::Episode(13),
+        ]
+    );
+    assert_eq!(SortMode::RecentlyWatched.next(), SortMode::MostPlayed);
+}
+
+#[test]
+fn test_sort_entries_most_played() {
+    let episode = |id: usize| Entry::Episode {
+        episode_id: id,
+        name: format!("episode {}", id),
+        location: format!("{}.mkv", id),
+    };
+    let mut entries = vec![episode(1), episode(2), episode(3)];
+
+    let mut keys = SortKeys::default();
+    keys.play_count.insert(EntryKey::Episode(2), 1);
+    keys.play_count.insert(EntryKey::Episode(3), 4);
+
+    sort_entries(&mut entries, SortMode::MostPlayed, &keys);
+    let order: Vec<EntryKey> = entries.iter().map(|e| e.key()).collect();
+    assert_eq!(order, vec![EntryKey::Episode(3), EntryKey::Episode(2), EntryKey::Episode(1)]);
+    assert_eq!(SortMode::MostPlayed.next(), SortMode::Default);
+}
+
+#[test]
+fn test_sort_entries_library_orders() {
+    let episode = |id: usize, name: &str| Entry::Episode {
+        episode_id: id,
+        name: name.to_string(),
+        location: format!("{}.mkv", name),
+    };
+    let mut entries = vec![
+        episode(1, "charlie"),
+        Entry::Series { series_id: 7, name: "Bravo".to_string() },
+        episode(2, "alpha"),
+    ];
+    let order = |entries: &[Entry]| entries.iter().map(|e| e.key()).collect::<Vec<EntryKey>>();
+
+    let mut keys = SortKeys::default();
+    sort_entries(&mut entries, SortMode::Name, &keys);
+    assert_eq!(order(&entries), vec![EntryKey::Episode(2), EntryKey::Series(7), EntryKey::Episode(1)]);
+
+    // Oldest year first, entries without a year last
+    keys.year.insert(EntryKey::Episode(1), 1999);
+    keys.year.insert(EntryKey::Series(7), 2005);
+    sort_entries(&mut entries, SortMode::Year, &keys);
+    assert_eq!(order(&entries), vec![EntryKey::Episode(1), EntryKey::Series(7), EntryKey::Episode(2)]);
+
+    // Largest first
+    keys.file_size.insert(EntryKey::Episode(2), 900);
+    keys.file_size.insert(EntryKey::Series(7), 2000);
+    sort_entries(&mut entries, SortMode::FileSize, &keys);
+    assert_eq!(order(&entries), vec![EntryKey::Series(7), EntryKey::Episode(2), EntryKey::Episode(1)]);
+
+    // Unwatched first
+    keys.watched.insert(EntryKey::Series(7), true);
+    keys.watched.insert(EntryKey::Episode(1), false);
+    keys.watched.insert(EntryKey::Episode(2), true);
+    sort_entries(&mut entries, SortMode::Watched, &keys);
+    assert_eq!(order(&entries), vec![EntryKey::Episode(1), EntryKey::Series(7), EntryKey::Episode(2)]);
+}
+
+#[test]
+fn test_sort_mode_from_config() {
+    assert_eq!(SortMode::from_config("date_added"), Some(SortMode::DateAdded));
+    assert_eq!(SortMode::from_config("File Size"), Some(SortMode::FileSize));
+    assert_eq!(SortMode::from_config("episode-number"), Some(SortMode::EpisodeNumber));
+    assert_eq!(SortMode::from_config("default"), Some(SortMode::Default));
+    assert_eq!(SortMode::from_config("rating"), None);
+    assert_eq!(SortMode::Default.next(), SortMode::Name);
+}
+
+#[test]
+fn test_apply_preferred_titles() {
+    let mut entries = vec![
+        Entry::Series { series_id: 1, name: "Show".to_string() },
+        Entry::Episode { episode_id: 10, name: "Spirited Away".to_string(), location: "a.mkv".to_string() },
+        Entry::Episode { episode_id: 11, name: "Totoro".to_string(), location: "b.mkv".to_string() },
+    ];
+    let mut titles = AltTitles::new();
+    titles.insert("ja".to_string(), "Sen to Chihiro".to_string());
+    let alt_titles = HashMap::from([(10, titles)]);
+
+    apply_preferred_titles(&mut entries, &alt_titles, " JA ");
+
+    let names: Vec<&str> = entries
+        .iter()
+        .map(|entry| match entry {
+            Entry::Series { name, .. } | Entry::Episode { name, .. } => name.as_str(),
+            Entry::Season { .. } => "",
+        })
+        .collect();
+    assert_eq!(names, vec!["Show", "Sen to Chihiro", "Totoro"]);
+}
@@ -0,0 +1,22 @@
+use movies::version::{preferred_version_index, version_label, EpisodeVersion};
+
+#[test]
+fn test_version_label_from_file_name() {
+    assert_eq!(version_label("Movie/Movie.2010.2160p.HDR.mkv"), "4K");
+    assert_eq!(version_label("Movie.2010.Director's.Cut.1080p.mkv"), "1080p Director's Cut");
+    assert_eq!(version_label("Movie_Extended_Edition.mp4"), "Extended");
+    assert_eq!(version_label("Movie.2010.mkv"), "Movie.2010");
+}
+
+#[test]
+fn test_preferred_version_index() {
+    let versions: Vec<EpisodeVersion> = ["1080p", "4K Director's Cut"]
+        .iter()
+        .map(|label| EpisodeVersion { location: format!("/videos/{}.mkv", label), label: label.to_string() })
+        .collect();
+    assert_eq!(preferred_version_index(&versions, Some("4k")), 1);
+    assert_eq!(preferred_version_index(&versions, Some("director's cut")), 1);
+    assert_eq!(preferred_version_index(&versions, Some("720p")), 0);
+    assert_eq!(preferred_version_index(&versions, Some(" ")), 0);
+    assert_eq!(preferred_version_index(&versions, None), 0);
+}
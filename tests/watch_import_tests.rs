@@ -0,0 +1,76 @@
+use movies::watch_import::{common_suffix_len, file_name_of, find_watch_database, read_watch_records, WatchSource};
+use rusqlite::Connection;
+use tempfile::TempDir;
+
+#[test]
+fn test_read_kodi_watch_records() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("MyVideos131.db");
+    let conn = Connection::open(&db_path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE path (idPath INTEGER PRIMARY KEY, strPath TEXT);
+         CREATE TABLE files (idFile INTEGER PRIMARY KEY, idPath INTEGER, strFilename TEXT, playCount INTEGER, lastPlayed TEXT);
+         INSERT INTO path VALUES (1, 'smb://nas/tv/Show/');
+         CREATE TABLE bookmark (idBookmark INTEGER PRIMARY KEY, idFile INTEGER, timeInSeconds DOUBLE, type INTEGER);
+         INSERT INTO files VALUES (1, 1, 'Episode 1.mkv', 2, '2023-05-01 20:15:00');
+         INSERT INTO files VALUES (2, 1, 'Episode 2.mkv', NULL, NULL);
+         INSERT INTO bookmark VALUES (1, 2, 754.5, 1);",
+    )
+    .unwrap();
+    drop(conn);
+
+    assert_eq!(find_watch_database(temp_dir.path()), Some(db_path.clone()));
+
+    let (source, mut records) = read_watch_records(&db_path).unwrap();
+    records.sort_by(|a, b| a.path.cmp(&b.path));
+
+    assert_eq!(source, WatchSource::Kodi);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].path, "smb://nas/tv/Show/Episode 1.mkv");
+    assert_eq!(records[0].play_count, 2);
+    assert!(records[0].last_played.as_deref().unwrap().starts_with("2023-05-01T20:15:00"));
+    assert_eq!(records[1].play_count, 0);
+    assert_eq!(records[1].resume_seconds, Some(754));
+}
+
+#[test]
+fn test_read_plex_watch_records() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("com.plexapp.plugins.library.db");
+    let conn = Connection::open(&db_path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE metadata_items (id INTEGER PRIMARY KEY, guid TEXT);
+         CREATE TABLE media_items (id INTEGER PRIMARY KEY, metadata_item_id INTEGER);
+         CREATE TABLE media_parts (id INTEGER PRIMARY KEY, media_item_id INTEGER, file TEXT);
+         CREATE TABLE metadata_item_settings (id INTEGER PRIMARY KEY, guid TEXT, view_count INTEGER, last_viewed_at INTEGER, view_offset INTEGER);
+         INSERT INTO metadata_items VALUES (1, 'plex://movie/1');
+         INSERT INTO media_items VALUES (1, 1);
+         INSERT INTO media_parts VALUES (1, 1, '/data/movies/Heat (1995).mkv');
+         INSERT INTO metadata_item_settings VALUES (1, 'plex://movie/1', 1, 1700000000, 90000);",
+    )
+    .unwrap();
+    drop(conn);
+
+    let (source, records) = read_watch_records(&db_path).unwrap();
+
+    assert_eq!(source, WatchSource::Plex);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].path, "/data/movies/Heat (1995).mkv");
+    assert_eq!(records[0].play_count, 1);
+    assert_eq!(records[0].resume_seconds, Some(90));
+    assert!(records[0].last_played.is_some());
+}
+
+#[test]
+fn test_file_name_of_handles_both_separators() {
+    assert_eq!(file_name_of("Shows/Show/ep1.mkv"), "ep1.mkv");
+    assert_eq!(file_name_of("C:\\Videos\\movie.mp4"), "movie.mp4");
+    assert_eq!(file_name_of("plain.avi"), "plain.avi");
+}
+
+#[test]
+fn test_common_suffix_len_counts_shared_trailing_names() {
+    assert_eq!(common_suffix_len("/mnt/tv/Show A/S01E01.mkv", "Show A/S01E01.mkv"), 2);
+    assert_eq!(common_suffix_len("C:\\TV\\Show B\\S01E01.mkv", "Show A/S01E01.mkv"), 1);
+    assert_eq!(common_suffix_len("S01E02.mkv", "Show A/S01E01.mkv"), 0);
+}
@@ -0,0 +1,114 @@
+use movies::database::Database;
+use movies::path_resolver::PathResolver;
+use movies::web_video::{build_command, check_url, download_folder, error_message, import, parse_output, WebVideo};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+#[test]
+fn test_check_url() {
+    assert_eq!(check_url("  https://example.com/watch?v=abc  "), Ok("https://example.com/watch?v=abc".to_string()));
+    assert!(check_url("HTTP://example.com/video").is_ok());
+    assert!(check_url("").is_err());
+    assert!(check_url("example.com/video").is_err());
+    assert!(check_url("https://example.com/a video").is_err());
+    assert!(check_url("--exec rm").is_err());
+}
+
+#[test]
+fn test_download_folder_is_under_the_root_unless_absolute() {
+    let root = Path::new("/videos");
+    assert_eq!(download_folder(root, "Downloads"), PathBuf::from("/videos/Downloads"));
+    assert_eq!(download_folder(root, ""), PathBuf::from("/videos"));
+    assert_eq!(download_folder(root, "/mnt/web"), PathBuf::from("/mnt/web"));
+}
+
+#[test]
+fn test_build_command_puts_the_url_after_the_options() {
+    let command = build_command("yt-dlp", "https://example.com/v", Path::new("/videos/Downloads"));
+    assert_eq!(command.get_program(), "yt-dlp");
+    let args: Vec<String> = command.get_args().map(|arg| arg.to_string_lossy().to_string()).collect();
+    assert_eq!(args[args.len() - 2..], ["--".to_string(), "https://example.com/v".to_string()]);
+    assert!(args.contains(&"--no-playlist".to_string()));
+    assert!(args.contains(&"/videos/Downloads".to_string()));
+    assert!(args.contains(&"after_move:%()j".to_string()));
+}
+
+#[test]
+fn test_parse_output_reads_the_extractor_info() {
+    let stdout = concat!(
+        "WARNING: something harmless\n",
+        r#"{"title": "Building a Shed", "filepath": "/videos/Downloads/Building a Shed [abc].mp4", "#,
+        r#""upload_date": "20230415", "duration": 612.4, "webpage_url": "https://example.com/v/abc", "uploader": "Woodworks"}"#,
+        "\n"
+    );
+    let video = parse_output(stdout).unwrap();
+    assert_eq!(video.path, PathBuf::from("/videos/Downloads/Building a Shed [abc].mp4"));
+    assert_eq!(video.title, "Building a Shed");
+    assert_eq!(video.year, Some(2023));
+    assert_eq!(video.length, Some(612));
+    assert_eq!(video.notes().unwrap(), "Downloaded from https://example.com/v/abc (by Woodworks)");
+}
+
+#[test]
+fn test_parse_output_prefers_release_year_and_falls_back_to_the_file_name() {
+    let stdout = r#"{"filepath": "/videos/Downloads/clip [x1].webm", "release_year": 1968, "upload_date": "20200101"}"#;
+    let video = parse_output(stdout).unwrap();
+    assert_eq!(video.title, "clip [x1]");
+    assert_eq!(video.year, Some(1968));
+    assert_eq!(video.length, None);
+    assert_eq!(video.notes(), None);
+}
+
+#[test]
+fn test_parse_output_without_a_file_is_an_error() {
+    assert!(parse_output("").is_err());
+    assert!(parse_output(r#"{"title": "No file"}"#).is_err());
+}
+
+#[test]
+fn test_error_message_picks_the_error_line() {
+    let stderr = "WARNING: slow connection\nERROR: [generic] Unsupported URL: https://example.com\n";
+    assert_eq!(error_message(stderr), "[generic] Unsupported URL: https://example.com");
+    assert_eq!(error_message("something broke\n"), "something broke");
+    assert_eq!(error_message(""), "yt-dlp failed");
+}
+
+#[test]
+#[serial_test::serial]
+fn test_import_adds_the_video_with_its_details() {
+    let dir = TempDir::new().unwrap();
+    let db_path = dir.path().join("videos.sqlite");
+    let database = Database::open(&db_path).unwrap();
+    let resolver = PathResolver::from_database_path(&db_path).unwrap();
+
+    let folder = dir.path().join("Downloads");
+    fs::create_dir_all(&folder).unwrap();
+    let path = folder.join("Building a Shed [abc].mp4");
+    fs::write(&path, b"").unwrap();
+    let video = WebVideo {
+        path: path.clone(),
+        title: "Building a Shed".to_string(),
+        year: Some(2023),
+        length: Some(612),
+        source: Some("https://example.com/v/abc".to_string()),
+        uploader: None,
+    };
+
+    let episode_id = import(&video, &database, &resolver).unwrap();
+    assert_eq!(database.find_episode_id(&path, &resolver).unwrap(), Some(episode_id));
+    let details = database.get_episode_detail(episode_id).unwrap();
+    assert_eq!(details.title, "Building a Shed");
+    assert_eq!(details.year, "2023");
+    assert_eq!(details.length, "612");
+    assert_eq!(details.notes.as_deref(), Some("Downloaded from https://example.com/v/abc"));
+
+    // Downloading the same video again updates the episode instead of adding another
+    let again = WebVideo { title: "Building a Shed (HD)".to_string(), ..video };
+    assert_eq!(import(&again, &database, &resolver).unwrap(), episode_id);
+    assert_eq!(database.get_episode_detail(episode_id).unwrap().title, "Building a Shed (HD)");
+
+    let other = folder.join("other.mp4");
+    fs::write(&other, b"").unwrap();
+    assert_eq!(database.find_episode_id(&other, &resolver).unwrap(), None);
+}